@@ -0,0 +1,169 @@
+/* zk-thresh-pro C ABI. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef ZK_THRESH_PRO_H
+#define ZK_THRESH_PRO_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status of a successful call.
+ */
+#define ZKT_OK 0
+
+/**
+ * Status of a call passed a null pointer where a value is required.
+ */
+#define ZKT_ERROR_NULL_ARGUMENT -1
+
+/**
+ * Status of a call that panicked; the panic was caught before reaching the caller.
+ */
+#define ZKT_ERROR_PANIC -2
+
+/**
+ * Length of a secret scalar.
+ */
+#define ZKT_SECRET_LENGTH 32
+
+/**
+ * Length of the binary encoding of one slice.
+ */
+#define ZKT_SHARE_LENGTH 232
+
+/**
+ * A list of slices.
+ */
+typedef struct ZktShares ZktShares;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Version of the library, e.g. `"0.1.0"`; a static string, not to be freed.
+ */
+const char *zkt_version(void);
+
+/**
+ * Message of the last failed call on this thread, or null if none failed.
+ *
+ * The string stays valid until the next failing call on the same thread and is not to be freed.
+ */
+const char *zkt_last_error_message(void);
+
+/**
+ * Deal `secret` into `num_shares` slices, any `threshold` of which recover it.
+ *
+ * # Safety
+ *
+ * `secret` must point to [`ZKT_SECRET_LENGTH`] readable bytes and `out` to a writable
+ * `ZktShares*`.
+ */
+int zkt_generate_shares(const uint8_t *secret,
+                        size_t threshold,
+                        size_t num_shares,
+                        ZktShares **out);
+
+/**
+ * An empty list, to be filled with [`zkt_shares_push_bytes`]; null if it cannot be allocated.
+ */
+ZktShares *zkt_shares_new(void);
+
+/**
+ * Release `shares`; null is ignored.
+ *
+ * # Safety
+ *
+ * `shares` must be null or a list returned by this library that has not been freed yet.
+ */
+void zkt_shares_free(ZktShares *shares);
+
+/**
+ * Number of slices in `shares`, 0 if it is null.
+ *
+ * # Safety
+ *
+ * `shares` must be null or a live list.
+ */
+size_t zkt_shares_len(const ZktShares *shares);
+
+/**
+ * Parse a JSON array of `ShareData`.
+ *
+ * The slices are decoded only; check them with [`zkt_verify_shares`].
+ *
+ * # Safety
+ *
+ * `json` must be a NUL-terminated string and `out` must point to a writable `ZktShares*`.
+ */
+int zkt_shares_from_json(const char *json, ZktShares **out);
+
+/**
+ * Serialize `shares` as a JSON array of `ShareData`, to be freed with [`zkt_string_free`].
+ *
+ * # Safety
+ *
+ * `shares` must be a live list and `out` must point to a writable `char*`.
+ */
+int zkt_shares_to_json(const ZktShares *shares, char **out);
+
+/**
+ * Append the slice in its [`ZKT_SHARE_LENGTH`]-byte binary encoding to `shares`.
+ *
+ * # Safety
+ *
+ * `shares` must be a live list and `bytes` must point to `length` readable bytes.
+ */
+int zkt_shares_push_bytes(ZktShares *shares, const uint8_t *bytes, size_t length);
+
+/**
+ * Write slice `position` of `shares` in its binary encoding to `out`.
+ *
+ * Returns the `ZKT-E005` status if `position` is out of range.
+ *
+ * # Safety
+ *
+ * `shares` must be a live list and `out` must point to [`ZKT_SHARE_LENGTH`] writable bytes.
+ */
+int zkt_share_to_bytes(const ZktShares *shares, size_t position, uint8_t *out);
+
+/**
+ * Check the proof of every slice in `shares` against its own commitment and index.
+ *
+ * # Safety
+ *
+ * `shares` must be a live list.
+ */
+int zkt_verify_shares(const ZktShares *shares);
+
+/**
+ * Recover the secret from `shares` into `out`, once every slice proof verifies.
+ *
+ * The slices are checked and interpolated in constant time by [`crate::recover_secret`]; an
+ * invalid slice fails as by `From<VerifyError> for CryptoError`, with the `ZKT-V…` code of its
+ * failure in the message.
+ *
+ * # Safety
+ *
+ * `shares` must be a live list and `out` must point to [`ZKT_SECRET_LENGTH`] writable bytes.
+ */
+int zkt_recover_secret(const ZktShares *shares, uint8_t *out);
+
+/**
+ * Zeroize and release a string returned by the library; null is ignored.
+ *
+ * # Safety
+ *
+ * `string` must be null or a string returned by this library that has not been freed yet.
+ */
+void zkt_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ZK_THRESH_PRO_H */
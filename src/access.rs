@@ -0,0 +1,393 @@
+//! **access module**
+//!
+//! Monotone access structures beyond `t`-of-`n`: a [`Policy`] combines named parties with AND, OR
+//! and threshold gates, e.g. "2 of ops AND 1 of security", and [`Policy::compile`] turns it into a
+//! [`LinearSharingScheme`] (a monotone span program) that
+//! [`generate_policy_shares`](crate::sharing::generate_policy_shares) and
+//! [`recover_secret_with_policy`](crate::sharing::recover_secret_with_policy) share and recover
+//! with.
+//!
+//! Compilation follows the standard construction for threshold trees: the root gets the vector
+//! `(1)`; a `k`-of-`m` gate with vector `v` gives its `i`-th child `v` followed by
+//! `(i, i², ..., i^{k-1})` in `k - 1` fresh columns, so the children's values are the points
+//! `1..=m` of a polynomial of degree `k - 1` through the gate's value. AND is `m`-of-`m` and OR is
+//! `1`-of-`m`. Every leaf becomes one row, labelled with its party; sharing `secret` multiplies
+//! the rows by `(secret, ρ_2, ..., ρ_w)` for random `ρ`. A party named under several leaves holds
+//! one slice per leaf.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::lagrange_fft;
+use crate::limits::MAX_ENTERPRISE_SHARES;
+use crate::sharing::ShareData;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::HashSet;
+
+/// Monotone access policy over named parties.
+///
+/// Serialized as an externally tagged enum with named fields, e.g.
+/// `{"Threshold": {"threshold": 2, "children": [{"Party": {"name": "ops-1"}}, ...]}}`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(from = "PolicyNode", into = "PolicyNode")]
+pub enum Policy {
+    /// Satisfied by the named party.
+    Party(String),
+    /// Satisfied when every child is.
+    And(Vec<Policy>),
+    /// Satisfied when any child is.
+    Or(Vec<Policy>),
+    /// Satisfied when at least the given number of children are.
+    Threshold(usize, Vec<Policy>),
+}
+
+/// Wire form of [`Policy`], with named fields.
+#[derive(serde::Serialize, serde::Deserialize)]
+enum PolicyNode {
+    Party {
+        name: String,
+    },
+    And {
+        children: Vec<Policy>,
+    },
+    Or {
+        children: Vec<Policy>,
+    },
+    Threshold {
+        threshold: usize,
+        children: Vec<Policy>,
+    },
+}
+
+impl From<PolicyNode> for Policy {
+    fn from(node: PolicyNode) -> Self {
+        match node {
+            PolicyNode::Party { name } => Policy::Party(name),
+            PolicyNode::And { children } => Policy::And(children),
+            PolicyNode::Or { children } => Policy::Or(children),
+            PolicyNode::Threshold {
+                threshold,
+                children,
+            } => Policy::Threshold(threshold, children),
+        }
+    }
+}
+
+impl From<Policy> for PolicyNode {
+    fn from(policy: Policy) -> Self {
+        match policy {
+            Policy::Party(name) => PolicyNode::Party { name },
+            Policy::And(children) => PolicyNode::And { children },
+            Policy::Or(children) => PolicyNode::Or { children },
+            Policy::Threshold(threshold, children) => PolicyNode::Threshold {
+                threshold,
+                children,
+            },
+        }
+    }
+}
+
+fn invalid(reason: String) -> CryptoError {
+    CryptoError::Validation {
+        field: "policy".to_string(),
+        reason,
+    }
+}
+
+impl Policy {
+    pub fn party(name: &str) -> Self {
+        Policy::Party(name.to_string())
+    }
+
+    /// `threshold`-of-`children` gate with AND and OR as its extremes.
+    fn gate(&self) -> Option<(usize, &[Policy])> {
+        match self {
+            Policy::Party(_) => None,
+            Policy::And(children) => Some((children.len(), children)),
+            Policy::Or(children) => Some((1, children)),
+            Policy::Threshold(threshold, children) => Some((*threshold, children)),
+        }
+    }
+
+    /// Check the policy is well formed.
+    ///
+    /// Returns `CryptoError::Validation` for an empty party name, a gate without children or a
+    /// threshold outside `1..=children`, and `CryptoError::ResourceExhaustion` for more than
+    /// [`MAX_ENTERPRISE_SHARES`] leaves.
+    pub fn validate(&self) -> CryptoResult<()> {
+        self.check()?;
+        if self.leaves() > MAX_ENTERPRISE_SHARES {
+            return Err(CryptoError::ResourceExhaustion {
+                resource: "share_count".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check(&self) -> CryptoResult<()> {
+        match self.gate() {
+            None => match self {
+                Policy::Party(name) if name.is_empty() => {
+                    Err(invalid("party name is empty".to_string()))
+                }
+                _ => Ok(()),
+            },
+            Some((threshold, children)) => {
+                if threshold == 0 || threshold > children.len() {
+                    return Err(invalid(format!(
+                        "threshold {} over {} children",
+                        threshold,
+                        children.len()
+                    )));
+                }
+                children.iter().try_for_each(Policy::check)
+            }
+        }
+    }
+
+    fn leaves(&self) -> usize {
+        match self.gate() {
+            None => 1,
+            Some((_, children)) => children.iter().map(Policy::leaves).sum(),
+        }
+    }
+
+    /// Party of every leaf, in the order of the slices dealt under the policy.
+    pub fn parties(&self) -> Vec<&str> {
+        match self {
+            Policy::Party(name) => vec![name.as_str()],
+            _ => self
+                .gate()
+                .into_iter()
+                .flat_map(|(_, children)| children)
+                .flat_map(Policy::parties)
+                .collect(),
+        }
+    }
+
+    /// Whether the parties `holders` together satisfy the policy.
+    pub fn is_satisfied_by(&self, holders: &[&str]) -> bool {
+        match self {
+            Policy::Party(name) => holders.contains(&name.as_str()),
+            _ => self.gate().is_some_and(|(threshold, children)| {
+                children
+                    .iter()
+                    .filter(|c| c.is_satisfied_by(holders))
+                    .count()
+                    >= threshold
+            }),
+        }
+    }
+
+    /// Compile the policy into its linear secret sharing scheme; fails like [`Self::validate`].
+    pub fn compile(&self) -> CryptoResult<LinearSharingScheme> {
+        self.validate()?;
+        let mut scheme = LinearSharingScheme {
+            policy: self.clone(),
+            parties: Vec::new(),
+            rows: Vec::new(),
+            width: 1,
+        };
+        scheme.add(self, vec![Scalar::ONE]);
+        let width = scheme.width;
+        for row in &mut scheme.rows {
+            row.resize(width, Scalar::ZERO);
+        }
+        Ok(scheme)
+    }
+}
+
+/// Share-generating matrix of a [`Policy`]: one row per leaf, labelled with its party.
+///
+/// Row `k` (0-based) is the slice at index `k + 1`. A set of rows reconstructs the secret exactly
+/// when their parties satisfy the policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearSharingScheme {
+    policy: Policy,
+    parties: Vec<String>,
+    rows: Vec<Vec<Scalar>>,
+    width: usize,
+}
+
+impl LinearSharingScheme {
+    fn add(&mut self, policy: &Policy, vector: Vec<Scalar>) {
+        let Some((threshold, children)) = policy.gate() else {
+            if let Policy::Party(name) = policy {
+                self.parties.push(name.clone());
+                self.rows.push(vector);
+            }
+            return;
+        };
+        let first = self.width;
+        self.width += threshold - 1;
+        for (position, child) in children.iter().enumerate() {
+            let x = Scalar::from(position as u64 + 1);
+            let mut extended = vector.clone();
+            extended.resize(first, Scalar::ZERO);
+            let mut power = Scalar::ONE;
+            for _ in 1..threshold {
+                power *= x;
+                extended.push(power);
+            }
+            self.add(child, extended);
+        }
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Number of rows, i.e. of slices dealt.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Number of columns: one for the secret and one per random coordinate.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Party holding slice `index` (1-based), `None` outside the scheme.
+    pub fn party(&self, index: usize) -> Option<&str> {
+        self.parties.get(index.checked_sub(1)?).map(String::as_str)
+    }
+
+    /// Row of slice `index` (1-based), `None` outside the scheme.
+    pub fn row(&self, index: usize) -> Option<&[Scalar]> {
+        self.rows.get(index.checked_sub(1)?).map(Vec::as_slice)
+    }
+
+    /// Slice values `M·vector` for a vector of [`Self::width`] entries whose first is the secret.
+    pub(crate) fn share_values(&self, vector: &[Scalar]) -> Vec<Scalar> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().zip(vector).map(|(m, v)| m * v).sum())
+            .collect()
+    }
+
+    /// Reconstruction weights `(index, ω)` with `Σ ω·row(index) = (1, 0, ..., 0)` over slices at
+    /// `indices`, or `None` if their parties do not satisfy the policy.
+    ///
+    /// Each satisfied gate uses its first `k` satisfied children; slices not needed get no weight.
+    pub fn reconstruction(&self, indices: &[usize]) -> Option<Vec<(usize, Scalar)>> {
+        let available: HashSet<usize> = indices.iter().copied().collect();
+        let mut next = 1;
+        weights(&self.policy, &available, &mut next)
+    }
+}
+
+/// Slice dealt under a [`Policy`], labelled with the party holding it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct PolicyShare {
+    pub party: String,
+    /// The slice; its index is its row in the policy's [`LinearSharingScheme`].
+    pub share: ShareData,
+}
+
+/// Reconstruction weights of the subtree `policy`, whose first leaf is slice `*next`.
+fn weights(
+    policy: &Policy,
+    available: &HashSet<usize>,
+    next: &mut usize,
+) -> Option<Vec<(usize, Scalar)>> {
+    let Some((threshold, children)) = policy.gate() else {
+        let index = *next;
+        *next += 1;
+        return available
+            .contains(&index)
+            .then(|| vec![(index, Scalar::ONE)]);
+    };
+    // Every child is visited to keep the leaf numbering, satisfied or not.
+    let results: Vec<_> = children
+        .iter()
+        .map(|child| weights(child, available, next))
+        .collect();
+    let chosen: Vec<(usize, Vec<(usize, Scalar)>)> = results
+        .into_iter()
+        .enumerate()
+        .filter_map(|(position, result)| result.map(|w| (position + 1, w)))
+        .take(threshold)
+        .collect();
+    if chosen.len() < threshold {
+        return None;
+    }
+    let xs: Vec<Scalar> = chosen
+        .iter()
+        .map(|(position, _)| Scalar::from(*position as u64))
+        .collect();
+    let lambda = lagrange_fft::compute_lagrange_coefficients(&xs).ok()?;
+    Some(
+        chosen
+            .into_iter()
+            .zip(lambda)
+            .flat_map(|((_, child), l)| child.into_iter().map(move |(i, w)| (i, w * l)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team() -> Policy {
+        Policy::And(vec![
+            Policy::Threshold(
+                2,
+                vec![
+                    Policy::party("ops-1"),
+                    Policy::party("ops-2"),
+                    Policy::party("ops-3"),
+                ],
+            ),
+            Policy::Or(vec![Policy::party("sec-1"), Policy::party("sec-2")]),
+        ])
+    }
+
+    #[test]
+    fn test_policy_compiles_to_spanning_rows() {
+        let policy = team();
+        let scheme = policy.compile().unwrap();
+        assert_eq!(
+            policy.parties(),
+            ["ops-1", "ops-2", "ops-3", "sec-1", "sec-2"]
+        );
+        assert_eq!((scheme.len(), scheme.width()), (5, 3));
+        assert_eq!(scheme.party(4), Some("sec-1"));
+        assert_eq!(scheme.party(0), None);
+
+        let target: Vec<Scalar> = [Scalar::ONE, Scalar::ZERO, Scalar::ZERO].to_vec();
+        for holders in [[1, 3, 5], [2, 3, 4], [1, 2, 4]] {
+            let weights = scheme.reconstruction(&holders).unwrap();
+            let mut combined = vec![Scalar::ZERO; scheme.width()];
+            for (index, w) in &weights {
+                for (c, m) in combined.iter_mut().zip(scheme.row(*index).unwrap()) {
+                    *c += w * m;
+                }
+            }
+            assert_eq!(combined, target);
+        }
+        // Two of ops without security, or one of ops with both of security, is not enough.
+        assert!(scheme.reconstruction(&[1, 2]).is_none());
+        assert!(scheme.reconstruction(&[1, 4, 5]).is_none());
+        assert!(policy.is_satisfied_by(&["ops-3", "sec-2", "ops-1"]));
+        assert!(!policy.is_satisfied_by(&["ops-3", "sec-2", "sec-1"]));
+
+        let json = serde_json::to_value(&policy).unwrap();
+        assert_eq!(
+            json["And"]["children"][1]["Or"]["children"][0]["Party"]["name"],
+            "sec-1"
+        );
+        assert_eq!(serde_json::from_value::<Policy>(json).unwrap(), policy);
+
+        for bad in [
+            Policy::Threshold(3, vec![Policy::party("a"), Policy::party("b")]),
+            Policy::Threshold(0, vec![Policy::party("a")]),
+            Policy::Or(vec![]),
+            Policy::And(vec![Policy::party("")]),
+        ] {
+            assert!(matches!(bad.compile(), Err(CryptoError::Validation { .. })));
+        }
+    }
+}
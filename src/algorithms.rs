@@ -0,0 +1,272 @@
+//! **algorithms module**
+//!
+//! Registry of the algorithm identifiers recorded in every envelope and transcript.
+//!
+//! Each [`AlgorithmId`] has a stable name and an [`AlgorithmKind`]. An [`AlgorithmSuite`] names
+//! the curve, hash, key encapsulation, AEAD, KDF and proof system an artifact was produced with,
+//! and is embedded as the `algorithms` field of [`crate::wrapping::WrappedShare`],
+//! [`crate::sealing::SealedShare`], [`crate::threshold_decrypt::ThresholdCiphertext`],
+//! [`crate::pvss::PvssDealing`], [`crate::transcript::CeremonyTranscript`],
+//! [`crate::beacon::BeaconTranscript`] and [`crate::policy_proof::PolicyComplianceProof`].
+//!
+//! Parsing rejects a name missing from the registry and a registered name in the wrong slot (a
+//! hash given as the curve, say). Opening or verifying an artifact additionally requires the
+//! exact suite its version is produced with ([`AlgorithmSuite::require`]), so an artifact made
+//! under a future suite (e.g. a post-quantum KEM) fails loudly instead of being processed with
+//! today's algorithms. Artifacts written before the field existed carry no suite and parse as
+//! the suite their version was always produced with.
+//!
+//! Names are never reused: a new algorithm gets a new identifier, and a retired one stays in the
+//! registry so old artifacts keep parsing.
+
+use crate::error::{CryptoError, CryptoResult};
+use std::fmt;
+
+/// Role an algorithm plays in an artifact.
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AlgorithmKind {
+    Curve,
+    Hash,
+    Kem,
+    Aead,
+    Kdf,
+    ProofSystem,
+}
+
+/// A registered algorithm, serialized as its [`AlgorithmId::name`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlgorithmId {
+    /// The ristretto255 prime-order group.
+    #[serde(rename = "ristretto255")]
+    Ristretto255,
+    /// BLAKE3, including its XOF and keyed modes.
+    #[serde(rename = "blake3")]
+    Blake3,
+    /// Static-ephemeral X25519 Diffie-Hellman.
+    #[serde(rename = "x25519")]
+    X25519,
+    /// ElGamal in the exponent to a threshold group key.
+    #[serde(rename = "threshold-elgamal")]
+    ThresholdElgamal,
+    /// Keyed BLAKE3 XOF keystream, then a keyed BLAKE3 tag (encrypt-then-MAC).
+    #[serde(rename = "blake3-xof-etm")]
+    Blake3XofEtm,
+    /// BLAKE3 `derive_key` with a domain-separation context.
+    #[serde(rename = "blake3-derive-key")]
+    Blake3DeriveKey,
+    /// Fiat-Shamir Schnorr proof of a Pedersen commitment opening.
+    #[serde(rename = "schnorr-pedersen-fiat-shamir")]
+    SchnorrPedersen,
+    /// Chaum-Pedersen proof of discrete-log equality.
+    #[serde(rename = "chaum-pedersen-dleq")]
+    ChaumPedersenDleq,
+    /// SHA-256, with HKDF and RFC 9380 `expand_message_xmd`.
+    #[serde(rename = "sha256")]
+    Sha256,
+    /// SHA3-256 and the SHAKE256-based cSHAKE and KMAC.
+    #[serde(rename = "sha3-256")]
+    Sha3_256,
+    /// One-out-of-many proof of a Pedersen commitment opening with a per-statement tag.
+    #[serde(rename = "linkable-ring-pedersen")]
+    RingPedersenOpening,
+}
+
+impl AlgorithmId {
+    /// Every registered identifier.
+    pub const ALL: [AlgorithmId; 11] = [
+        AlgorithmId::Ristretto255,
+        AlgorithmId::Blake3,
+        AlgorithmId::X25519,
+        AlgorithmId::ThresholdElgamal,
+        AlgorithmId::Blake3XofEtm,
+        AlgorithmId::Blake3DeriveKey,
+        AlgorithmId::SchnorrPedersen,
+        AlgorithmId::ChaumPedersenDleq,
+        AlgorithmId::Sha256,
+        AlgorithmId::Sha3_256,
+        AlgorithmId::RingPedersenOpening,
+    ];
+
+    /// Stable name written to artifacts.
+    pub fn name(self) -> &'static str {
+        match self {
+            AlgorithmId::Ristretto255 => "ristretto255",
+            AlgorithmId::Blake3 => "blake3",
+            AlgorithmId::X25519 => "x25519",
+            AlgorithmId::ThresholdElgamal => "threshold-elgamal",
+            AlgorithmId::Blake3XofEtm => "blake3-xof-etm",
+            AlgorithmId::Blake3DeriveKey => "blake3-derive-key",
+            AlgorithmId::SchnorrPedersen => "schnorr-pedersen-fiat-shamir",
+            AlgorithmId::ChaumPedersenDleq => "chaum-pedersen-dleq",
+            AlgorithmId::Sha256 => "sha256",
+            AlgorithmId::Sha3_256 => "sha3-256",
+            AlgorithmId::RingPedersenOpening => "linkable-ring-pedersen",
+        }
+    }
+
+    pub fn kind(self) -> AlgorithmKind {
+        match self {
+            AlgorithmId::Ristretto255 => AlgorithmKind::Curve,
+            AlgorithmId::Blake3 | AlgorithmId::Sha256 | AlgorithmId::Sha3_256 => {
+                AlgorithmKind::Hash
+            }
+            AlgorithmId::X25519 | AlgorithmId::ThresholdElgamal => AlgorithmKind::Kem,
+            AlgorithmId::Blake3XofEtm => AlgorithmKind::Aead,
+            AlgorithmId::Blake3DeriveKey => AlgorithmKind::Kdf,
+            AlgorithmId::SchnorrPedersen
+            | AlgorithmId::ChaumPedersenDleq
+            | AlgorithmId::RingPedersenOpening => AlgorithmKind::ProofSystem,
+        }
+    }
+
+    /// Look up a registered name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.name() == name)
+    }
+
+    /// Names of the registered identifiers of `kind`.
+    pub fn names(kind: AlgorithmKind) -> Vec<&'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(|id| id.kind() == kind)
+            .map(AlgorithmId::name)
+            .collect()
+    }
+}
+
+impl fmt::Display for AlgorithmId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Algorithms an artifact was produced with; slots an artifact does not use are `null`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(try_from = "RawSuite")]
+pub struct AlgorithmSuite {
+    pub curve: AlgorithmId,
+    pub hash: AlgorithmId,
+    pub kem: Option<AlgorithmId>,
+    pub aead: Option<AlgorithmId>,
+    pub kdf: Option<AlgorithmId>,
+    pub proof: Option<AlgorithmId>,
+}
+
+/// Unchecked wire form of an [`AlgorithmSuite`].
+#[derive(serde::Deserialize)]
+struct RawSuite {
+    curve: AlgorithmId,
+    hash: AlgorithmId,
+    kem: Option<AlgorithmId>,
+    aead: Option<AlgorithmId>,
+    kdf: Option<AlgorithmId>,
+    proof: Option<AlgorithmId>,
+}
+
+impl TryFrom<RawSuite> for AlgorithmSuite {
+    type Error = CryptoError;
+
+    fn try_from(raw: RawSuite) -> CryptoResult<Self> {
+        let suite = AlgorithmSuite {
+            curve: raw.curve,
+            hash: raw.hash,
+            kem: raw.kem,
+            aead: raw.aead,
+            kdf: raw.kdf,
+            proof: raw.proof,
+        };
+        suite.validate()?;
+        Ok(suite)
+    }
+}
+
+impl AlgorithmSuite {
+    /// Check that every slot holds an identifier of its own kind.
+    pub fn validate(&self) -> CryptoResult<()> {
+        let slots = [
+            ("curve", Some(self.curve), AlgorithmKind::Curve),
+            ("hash", Some(self.hash), AlgorithmKind::Hash),
+            ("kem", self.kem, AlgorithmKind::Kem),
+            ("aead", self.aead, AlgorithmKind::Aead),
+            ("kdf", self.kdf, AlgorithmKind::Kdf),
+            ("proof", self.proof, AlgorithmKind::ProofSystem),
+        ];
+        for (slot, id, kind) in slots {
+            if let Some(id) = id.filter(|id| id.kind() != kind) {
+                return Err(CryptoError::Serialization {
+                    details: format!("{} is not a valid {} algorithm", id, slot),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Require the artifact `what` to have been produced with `expected`.
+    ///
+    /// Returns `CryptoError::Serialization` naming both suites otherwise.
+    pub fn require(&self, expected: &AlgorithmSuite, what: &str) -> CryptoResult<()> {
+        if self == expected {
+            return Ok(());
+        }
+        Err(CryptoError::Serialization {
+            details: format!(
+                "{} was produced with {}, only {} is supported",
+                what, self, expected
+            ),
+        })
+    }
+}
+
+impl fmt::Display for AlgorithmSuite {
+    /// The names of the used slots, in slot order, joined with `+`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{}", self.curve, self.hash)?;
+        for id in [self.kem, self.aead, self.kdf, self.proof]
+            .into_iter()
+            .flatten()
+        {
+            write!(f, "+{}", id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_names_roundtrip() {
+        for id in AlgorithmId::ALL {
+            assert_eq!(AlgorithmId::from_name(id.name()), Some(id));
+            assert_eq!(
+                serde_json::to_string(&id).unwrap(),
+                format!("\"{}\"", id.name())
+            );
+        }
+        assert_eq!(AlgorithmId::from_name("ml-kem-768"), None);
+    }
+
+    #[test]
+    fn test_suite_is_validated_on_parse() {
+        let suite = crate::wrapping::WRAPPED_SHARE_ALGORITHMS;
+        let json = serde_json::to_string(&suite).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AlgorithmSuite>(&json).unwrap(),
+            suite
+        );
+
+        let unknown = json.replace("x25519", "ml-kem-768");
+        assert!(serde_json::from_str::<AlgorithmSuite>(&unknown).is_err());
+        let misplaced = json.replacen("\"ristretto255\"", "\"blake3\"", 1);
+        assert!(serde_json::from_str::<AlgorithmSuite>(&misplaced).is_err());
+
+        let other = AlgorithmSuite { kem: None, ..suite };
+        other.validate().unwrap();
+        assert!(matches!(
+            other.require(&suite, "wrapped share"),
+            Err(CryptoError::Serialization { .. })
+        ));
+    }
+}
@@ -0,0 +1,665 @@
+//! **archive module**
+//!
+//! Archival format for ceremony transcripts, audit records and commitment vectors.
+//!
+//! Large dealings produce megabytes of transcript and audit history that must be kept for years
+//! but are rarely read back whole. An archive packs the entries into chunks of about
+//! [`DEFAULT_CHUNK_SIZE`] bytes, compresses each chunk on its own and records a BLAKE3 checksum of
+//! every chunk in an index at the front. A reader parses the index only and then decompresses
+//! just the chunk holding the entry it wants, so a single transcript, audit record or commitment
+//! vector can be checked without reading the rest of the archive.
+//!
+//! The index is itself covered by the archive [`root`](Archive::root), which commits to every
+//! chunk checksum: anchoring the root (e.g. in a timestamped transcript) authenticates the whole
+//! archive. Layout (all integers little-endian):
+//!
+//! ```text
+//! header   magic "ZKTA" | version u8 | codec u8 | entries u32 | chunks u32 | root [32]
+//! chunks   per chunk: offset u64 | stored length u32 | raw length u32 | checksum [32]
+//! entries  per entry: kind u8 | chunk u32 | offset u32 | length u32
+//! data     the stored chunks, back to back
+//! ```
+//!
+//! Compression is supplied through [`ArchiveCodec`]. The crate ships [`Stored`], which keeps
+//! chunks as they are, and with the `zstd` feature `Zstd`, which writes [`CODEC_ZSTD`] archives.
+//! Other codecs are implemented by the application.
+
+use crate::error::{AuditRecord, CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::transcript::CeremonyTranscript;
+use crate::vss::VssCommitments;
+use std::io::{Read, Seek, SeekFrom};
+
+/// First bytes of every archive.
+pub const ARCHIVE_MAGIC: [u8; 4] = *b"ZKTA";
+
+/// Archive format version.
+pub const ARCHIVE_VERSION: u8 = 1;
+
+/// Length of the archive header.
+pub const HEADER_LENGTH: usize = 46;
+
+/// Length of one chunk record of the index.
+pub const CHUNK_RECORD_LENGTH: usize = 48;
+
+/// Length of one entry record of the index.
+pub const ENTRY_RECORD_LENGTH: usize = 13;
+
+/// Raw size at which [`ArchiveWriter`] starts a new chunk.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Codec id of chunks kept uncompressed.
+pub const CODEC_STORED: u8 = 0;
+
+/// Codec id of zstd-compressed chunks.
+pub const CODEC_ZSTD: u8 = 1;
+
+const CHUNK_CONTEXT: &str = "zk-thresh-pro 2024 archive chunk v1";
+const ROOT_CONTEXT: &str = "zk-thresh-pro 2024 archive root v1";
+
+/// Compression applied to each chunk.
+pub trait ArchiveCodec {
+    /// Codec id recorded in the header, e.g. [`CODEC_ZSTD`].
+    fn id(&self) -> u8;
+
+    fn compress(&self, raw: &[u8]) -> CryptoResult<Vec<u8>>;
+
+    /// Decompress a chunk whose uncompressed length is `raw_length`.
+    fn decompress(&self, stored: &[u8], raw_length: usize) -> CryptoResult<Vec<u8>>;
+}
+
+/// Chunks kept uncompressed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stored;
+
+impl ArchiveCodec for Stored {
+    fn id(&self) -> u8 {
+        CODEC_STORED
+    }
+
+    fn compress(&self, raw: &[u8]) -> CryptoResult<Vec<u8>> {
+        Ok(raw.to_vec())
+    }
+
+    fn decompress(&self, stored: &[u8], _raw_length: usize) -> CryptoResult<Vec<u8>> {
+        Ok(stored.to_vec())
+    }
+}
+
+/// zstd-compressed chunks at `level` (feature `zstd`); [`Zstd::default`] uses zstd's default
+/// level.
+#[cfg(feature = "zstd")]
+#[derive(Clone, Copy, Debug)]
+pub struct Zstd {
+    pub level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl Default for Zstd {
+    fn default() -> Self {
+        Zstd {
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl ArchiveCodec for Zstd {
+    fn id(&self) -> u8 {
+        CODEC_ZSTD
+    }
+
+    fn compress(&self, raw: &[u8]) -> CryptoResult<Vec<u8>> {
+        zstd::bulk::compress(raw, self.level).map_err(|e| CryptoError::Serialization {
+            details: format!("zstd compression failed: {}", e),
+        })
+    }
+
+    /// Decompress into at most `raw_length` bytes, so a corrupt chunk cannot inflate further.
+    fn decompress(&self, stored: &[u8], raw_length: usize) -> CryptoResult<Vec<u8>> {
+        zstd::bulk::decompress(stored, raw_length)
+            .map_err(|e| corrupt(format!("zstd chunk: {}", e)))
+    }
+}
+
+/// What an archive entry holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A [`CeremonyTranscript`] as JSON.
+    Transcript,
+    /// An [`AuditRecord`] as JSON.
+    AuditRecord,
+    /// [`VssCommitments`] in their canonical binary encoding.
+    Commitments,
+}
+
+impl EntryKind {
+    fn tag(self) -> u8 {
+        match self {
+            EntryKind::Transcript => 0,
+            EntryKind::AuditRecord => 1,
+            EntryKind::Commitments => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> CryptoResult<Self> {
+        match tag {
+            0 => Ok(EntryKind::Transcript),
+            1 => Ok(EntryKind::AuditRecord),
+            2 => Ok(EntryKind::Commitments),
+            _ => Err(corrupt(format!("unknown entry kind {}", tag))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ChunkRecord {
+    offset: u64,
+    stored_length: u32,
+    raw_length: u32,
+    checksum: [u8; 32],
+}
+
+#[derive(Clone, Copy, Debug)]
+struct EntryRecord {
+    kind: EntryKind,
+    chunk: u32,
+    offset: u32,
+    length: u32,
+}
+
+fn corrupt(details: String) -> CryptoError {
+    CryptoError::Serialization {
+        details: format!("archive: {}", details),
+    }
+}
+
+fn io_error(e: std::io::Error) -> CryptoError {
+    CryptoError::Configuration {
+        parameter: "archive".to_string(),
+        issue: e.to_string(),
+    }
+}
+
+fn json_error(e: serde_json::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: e.to_string(),
+    }
+}
+
+fn length_u32(length: usize) -> CryptoResult<u32> {
+    u32::try_from(length).map_err(|_| CryptoError::ResourceExhaustion {
+        resource: "archive chunk length".to_string(),
+    })
+}
+
+fn chunk_checksum(raw: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(CHUNK_CONTEXT);
+    digest::Update::update(&mut hasher, raw);
+    let mut checksum = [0u8; 32];
+    hasher.finalize_extended(&mut checksum);
+    checksum
+}
+
+/// Root over the header fields before it and the index.
+fn archive_root(prefix: &[u8], index: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(ROOT_CONTEXT);
+    digest::Update::update(&mut hasher, prefix);
+    digest::Update::update(&mut hasher, index);
+    let mut root = [0u8; 32];
+    hasher.finalize_extended(&mut root);
+    root
+}
+
+fn encode_index(chunks: &[ChunkRecord], entries: &[EntryRecord]) -> Vec<u8> {
+    let mut index = Vec::with_capacity(
+        chunks.len() * CHUNK_RECORD_LENGTH + entries.len() * ENTRY_RECORD_LENGTH,
+    );
+    for chunk in chunks {
+        index.extend_from_slice(&chunk.offset.to_le_bytes());
+        index.extend_from_slice(&chunk.stored_length.to_le_bytes());
+        index.extend_from_slice(&chunk.raw_length.to_le_bytes());
+        index.extend_from_slice(&chunk.checksum);
+    }
+    for entry in entries {
+        index.push(entry.kind.tag());
+        index.extend_from_slice(&entry.chunk.to_le_bytes());
+        index.extend_from_slice(&entry.offset.to_le_bytes());
+        index.extend_from_slice(&entry.length.to_le_bytes());
+    }
+    index
+}
+
+fn header_prefix(codec: u8, entries: u32, chunks: u32) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(HEADER_LENGTH - 32);
+    prefix.extend_from_slice(&ARCHIVE_MAGIC);
+    prefix.push(ARCHIVE_VERSION);
+    prefix.push(codec);
+    prefix.extend_from_slice(&entries.to_le_bytes());
+    prefix.extend_from_slice(&chunks.to_le_bytes());
+    prefix
+}
+
+/// Builds an archive entry by entry.
+pub struct ArchiveWriter<'c> {
+    codec: &'c dyn ArchiveCodec,
+    chunk_size: usize,
+    current: Vec<u8>,
+    chunks: Vec<(ChunkRecord, Vec<u8>)>,
+    entries: Vec<EntryRecord>,
+}
+
+impl<'c> ArchiveWriter<'c> {
+    pub fn new(codec: &'c dyn ArchiveCodec) -> Self {
+        Self::with_chunk_size(codec, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Start a new chunk once the current one holds `chunk_size` raw bytes; an entry larger than
+    /// that gets a chunk of its own.
+    pub fn with_chunk_size(codec: &'c dyn ArchiveCodec, chunk_size: usize) -> Self {
+        Self {
+            codec,
+            chunk_size: chunk_size.max(1),
+            current: Vec::new(),
+            chunks: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add_transcript(&mut self, transcript: &CeremonyTranscript) -> CryptoResult<()> {
+        let bytes = serde_json::to_vec(transcript).map_err(json_error)?;
+        self.add(EntryKind::Transcript, &bytes)
+    }
+
+    pub fn add_audit_record(&mut self, record: &AuditRecord) -> CryptoResult<()> {
+        let bytes = serde_json::to_vec(record).map_err(json_error)?;
+        self.add(EntryKind::AuditRecord, &bytes)
+    }
+
+    pub fn add_commitments(&mut self, commitments: &VssCommitments) -> CryptoResult<()> {
+        self.add(EntryKind::Commitments, &commitments.to_canonical_bytes())
+    }
+
+    fn add(&mut self, kind: EntryKind, bytes: &[u8]) -> CryptoResult<()> {
+        if !self.current.is_empty() && self.current.len() + bytes.len() > self.chunk_size {
+            self.close_chunk()?;
+        }
+        self.entries.push(EntryRecord {
+            kind,
+            chunk: length_u32(self.chunks.len())?,
+            offset: length_u32(self.current.len())?,
+            length: length_u32(bytes.len())?,
+        });
+        self.current.extend_from_slice(bytes);
+        length_u32(self.current.len())?;
+        Ok(())
+    }
+
+    fn close_chunk(&mut self) -> CryptoResult<()> {
+        let stored = self.codec.compress(&self.current)?;
+        let offset = self.chunks.last().map_or(0, |(chunk, _)| {
+            chunk.offset + u64::from(chunk.stored_length)
+        });
+        let record = ChunkRecord {
+            offset,
+            stored_length: length_u32(stored.len())?,
+            raw_length: length_u32(self.current.len())?,
+            checksum: chunk_checksum(&self.current),
+        };
+        self.chunks.push((record, stored));
+        self.current.clear();
+        Ok(())
+    }
+
+    /// Number of entries added so far.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Compress the last chunk and encode the archive.
+    pub fn finish(mut self) -> CryptoResult<Vec<u8>> {
+        if !self.current.is_empty() {
+            self.close_chunk()?;
+        }
+        let records: Vec<ChunkRecord> = self.chunks.iter().map(|(record, _)| *record).collect();
+        let index = encode_index(&records, &self.entries);
+        let prefix = header_prefix(
+            self.codec.id(),
+            length_u32(self.entries.len())?,
+            length_u32(records.len())?,
+        );
+        let mut archive = prefix.clone();
+        archive.extend_from_slice(&archive_root(&prefix, &index));
+        archive.extend_from_slice(&index);
+        for (_, stored) in &self.chunks {
+            archive.extend_from_slice(stored);
+        }
+        Ok(archive)
+    }
+}
+
+/// An archive opened for reading.
+///
+/// Opening reads the header and the index and checks the index against the root; entries are
+/// read on demand, decompressing only the chunk that holds them.
+pub struct Archive<'c, R: Read + Seek> {
+    source: R,
+    codec: &'c dyn ArchiveCodec,
+    root: [u8; 32],
+    data_start: u64,
+    chunks: Vec<ChunkRecord>,
+    entries: Vec<EntryRecord>,
+}
+
+impl<'c, R: Read + Seek> Archive<'c, R> {
+    /// Open the archive in `source`, e.g. a `File` or a `Cursor` over its bytes.
+    ///
+    /// Returns `CryptoError::Configuration` if it was written with another codec than `codec`
+    /// and `CryptoError::Serialization` if the header or the index is corrupt.
+    pub fn open(mut source: R, codec: &'c dyn ArchiveCodec) -> CryptoResult<Self> {
+        let total = source.seek(SeekFrom::End(0)).map_err(io_error)?;
+        source.seek(SeekFrom::Start(0)).map_err(io_error)?;
+        if total < HEADER_LENGTH as u64 {
+            return Err(corrupt("truncated header".to_string()));
+        }
+        let mut header = [0u8; HEADER_LENGTH];
+        source.read_exact(&mut header).map_err(io_error)?;
+        if header[..4] != ARCHIVE_MAGIC {
+            return Err(corrupt("bad magic".to_string()));
+        }
+        if header[4] != ARCHIVE_VERSION {
+            return Err(corrupt(format!("unsupported version {}", header[4])));
+        }
+        if header[5] != codec.id() {
+            return Err(CryptoError::Configuration {
+                parameter: "codec".to_string(),
+                issue: format!(
+                    "archive uses codec {}, reader has codec {}",
+                    header[5],
+                    codec.id()
+                ),
+            });
+        }
+        let entry_count = u32::from_le_bytes(header[6..10].try_into().expect("4 bytes"));
+        let chunk_count = u32::from_le_bytes(header[10..14].try_into().expect("4 bytes"));
+        let index_length = u64::from(chunk_count) * CHUNK_RECORD_LENGTH as u64
+            + u64::from(entry_count) * ENTRY_RECORD_LENGTH as u64;
+        let data_start = HEADER_LENGTH as u64 + index_length;
+        if data_start > total {
+            return Err(corrupt("truncated index".to_string()));
+        }
+        let mut index = vec![0u8; index_length as usize];
+        source.read_exact(&mut index).map_err(io_error)?;
+        let root: [u8; 32] = header[14..].try_into().expect("32 bytes");
+        if archive_root(&header[..14], &index) != root {
+            return Err(corrupt("index does not match the root".to_string()));
+        }
+
+        let (chunk_bytes, entry_bytes) = index.split_at(chunk_count as usize * CHUNK_RECORD_LENGTH);
+        let mut chunks = Vec::with_capacity(chunk_count as usize);
+        let mut next_offset = 0u64;
+        for record in chunk_bytes.chunks_exact(CHUNK_RECORD_LENGTH) {
+            let chunk = ChunkRecord {
+                offset: u64::from_le_bytes(record[..8].try_into().expect("8 bytes")),
+                stored_length: u32::from_le_bytes(record[8..12].try_into().expect("4 bytes")),
+                raw_length: u32::from_le_bytes(record[12..16].try_into().expect("4 bytes")),
+                checksum: record[16..].try_into().expect("32 bytes"),
+            };
+            if chunk.offset != next_offset {
+                return Err(corrupt(format!("chunk {} is not contiguous", chunks.len())));
+            }
+            next_offset += u64::from(chunk.stored_length);
+            chunks.push(chunk);
+        }
+        if data_start + next_offset > total {
+            return Err(corrupt("truncated data".to_string()));
+        }
+        let mut entries = Vec::with_capacity(entry_count as usize);
+        for record in entry_bytes.chunks_exact(ENTRY_RECORD_LENGTH) {
+            let entry = EntryRecord {
+                kind: EntryKind::from_tag(record[0])?,
+                chunk: u32::from_le_bytes(record[1..5].try_into().expect("4 bytes")),
+                offset: u32::from_le_bytes(record[5..9].try_into().expect("4 bytes")),
+                length: u32::from_le_bytes(record[9..].try_into().expect("4 bytes")),
+            };
+            let in_bounds = chunks.get(entry.chunk as usize).is_some_and(|chunk| {
+                u64::from(entry.offset) + u64::from(entry.length) <= u64::from(chunk.raw_length)
+            });
+            if !in_bounds {
+                return Err(corrupt(format!("entry {} is out of bounds", entries.len())));
+            }
+            entries.push(entry);
+        }
+        Ok(Self {
+            source,
+            codec,
+            root,
+            data_start,
+            chunks,
+            entries,
+        })
+    }
+
+    /// Commitment to the index, and through the chunk checksums to every entry.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Kinds of the entries, in the order they were added.
+    pub fn kinds(&self) -> Vec<EntryKind> {
+        self.entries.iter().map(|e| e.kind).collect()
+    }
+
+    /// Read, decompress and check chunk `position`.
+    fn read_chunk(&mut self, position: usize) -> CryptoResult<Vec<u8>> {
+        let chunk = self.chunks[position];
+        self.source
+            .seek(SeekFrom::Start(self.data_start + chunk.offset))
+            .map_err(io_error)?;
+        let mut stored = vec![0u8; chunk.stored_length as usize];
+        self.source.read_exact(&mut stored).map_err(io_error)?;
+        let raw = self.codec.decompress(&stored, chunk.raw_length as usize)?;
+        if raw.len() != chunk.raw_length as usize || chunk_checksum(&raw) != chunk.checksum {
+            return Err(corrupt(format!("chunk {} checksum mismatch", position)));
+        }
+        Ok(raw)
+    }
+
+    /// Raw bytes of entry `position`, checked against its chunk checksum.
+    ///
+    /// Returns `CryptoError::Validation` if there is no such entry.
+    pub fn entry(&mut self, position: usize) -> CryptoResult<(EntryKind, Vec<u8>)> {
+        let entry = *self
+            .entries
+            .get(position)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "position".to_string(),
+                reason: format!("the archive holds {} entries", self.entries.len()),
+            })?;
+        let raw = self.read_chunk(entry.chunk as usize)?;
+        let start = entry.offset as usize;
+        Ok((
+            entry.kind,
+            raw[start..start + entry.length as usize].to_vec(),
+        ))
+    }
+
+    fn entry_of_kind(&mut self, position: usize, kind: EntryKind) -> CryptoResult<Vec<u8>> {
+        match self.entry(position)? {
+            (found, bytes) if found == kind => Ok(bytes),
+            (found, _) => Err(CryptoError::Validation {
+                field: "position".to_string(),
+                reason: format!("entry {} is a {:?}, not a {:?}", position, found, kind),
+            }),
+        }
+    }
+
+    pub fn transcript(&mut self, position: usize) -> CryptoResult<CeremonyTranscript> {
+        let bytes = self.entry_of_kind(position, EntryKind::Transcript)?;
+        serde_json::from_slice(&bytes).map_err(json_error)
+    }
+
+    pub fn audit_record(&mut self, position: usize) -> CryptoResult<AuditRecord> {
+        let bytes = self.entry_of_kind(position, EntryKind::AuditRecord)?;
+        serde_json::from_slice(&bytes).map_err(json_error)
+    }
+
+    pub fn commitments(&mut self, position: usize) -> CryptoResult<VssCommitments> {
+        let bytes = self.entry_of_kind(position, EntryKind::Commitments)?;
+        VssCommitments::from_canonical_bytes(&bytes)
+    }
+
+    /// Check every chunk against its checksum.
+    pub fn verify(&mut self) -> CryptoResult<()> {
+        for position in 0..self.chunks.len() {
+            self.read_chunk(position)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::context::OperationContext;
+    use crate::error::SecurityEvent;
+    use crate::sharing::generate_key_shares_with_commitments;
+    use crate::transcript::CeremonyKind;
+    use curve25519_dalek::scalar::Scalar;
+    use std::io::Cursor;
+
+    /// Run-length encoding, enough to exercise a codec that changes chunk sizes.
+    struct RunLength;
+
+    impl ArchiveCodec for RunLength {
+        fn id(&self) -> u8 {
+            0x7f
+        }
+
+        fn compress(&self, raw: &[u8]) -> CryptoResult<Vec<u8>> {
+            let mut out = Vec::new();
+            for run in raw.chunk_by(|a, b| a == b) {
+                for part in run.chunks(255) {
+                    out.extend_from_slice(&[part.len() as u8, part[0]]);
+                }
+            }
+            Ok(out)
+        }
+
+        fn decompress(&self, stored: &[u8], raw_length: usize) -> CryptoResult<Vec<u8>> {
+            let mut out = Vec::with_capacity(raw_length);
+            for pair in stored.chunks_exact(2) {
+                out.extend(std::iter::repeat_n(pair[1], pair[0] as usize));
+            }
+            Ok(out)
+        }
+    }
+
+    #[test]
+    fn test_archive_partial_reads() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 2, 3).unwrap();
+        let context = OperationContext::new("archived", 0, "deal-1");
+        let transcript = CeremonyTranscript::new(CeremonyKind::Dealing, &context, 2, &shares);
+        let record = AuditRecord {
+            context: Some(context.clone()),
+            event: SecurityEvent::PolicyViolation {
+                policy: "x".repeat(600),
+                violation: "test".to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+        };
+
+        let codec = RunLength;
+        let mut writer = ArchiveWriter::with_chunk_size(&codec, 512);
+        writer.add_transcript(&transcript).unwrap();
+        writer.add_commitments(&commitments).unwrap();
+        writer.add_audit_record(&record).unwrap();
+        writer.add_transcript(&transcript).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut archive = Archive::open(Cursor::new(&bytes), &codec).unwrap();
+        assert_eq!(archive.len(), 4);
+        assert_eq!(archive.chunk_count(), 3);
+        assert_eq!(archive.commitments(1).unwrap(), commitments);
+        assert_eq!(archive.transcript(3).unwrap(), transcript);
+        assert!(archive.transcript(1).is_err());
+        assert!(archive.entry(4).is_err());
+        assert!(matches!(
+            archive.audit_record(2).unwrap().event,
+            SecurityEvent::PolicyViolation { ref policy, .. } if policy.len() == 600
+        ));
+        archive.verify().unwrap();
+        assert!(matches!(
+            Archive::open(Cursor::new(&bytes), &Stored),
+            Err(CryptoError::Configuration { .. })
+        ));
+
+        // Damage in one chunk leaves the others readable.
+        let mut damaged = bytes.clone();
+        let last = damaged.len() - 1;
+        damaged[last] ^= 1;
+        let mut archive = Archive::open(Cursor::new(&damaged), &codec).unwrap();
+        assert_eq!(archive.transcript(0).unwrap(), transcript);
+        assert!(archive.transcript(3).is_err());
+        assert!(archive.verify().is_err());
+
+        // Tampering with the index is caught on open.
+        let mut tampered = bytes.clone();
+        tampered[HEADER_LENGTH] ^= 1;
+        assert!(Archive::open(Cursor::new(&tampered), &codec).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_archive_round_trip() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(6u64), 3, 40).unwrap();
+        let context = OperationContext::new("archived", 0, "deal-2");
+        let transcript = CeremonyTranscript::new(CeremonyKind::Dealing, &context, 3, &shares);
+
+        let codec = Zstd::default();
+        let mut writer = ArchiveWriter::with_chunk_size(&codec, 4096);
+        for _ in 0..4 {
+            writer.add_transcript(&transcript).unwrap();
+            writer.add_commitments(&commitments).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+        let mut stored = ArchiveWriter::with_chunk_size(&Stored, 4096);
+        for _ in 0..4 {
+            stored.add_transcript(&transcript).unwrap();
+            stored.add_commitments(&commitments).unwrap();
+        }
+        assert!(bytes.len() < stored.finish().unwrap().len());
+
+        let mut archive = Archive::open(Cursor::new(&bytes), &codec).unwrap();
+        assert_eq!(bytes[5], CODEC_ZSTD);
+        assert_eq!(archive.len(), 8);
+        assert_eq!(archive.transcript(6).unwrap(), transcript);
+        assert_eq!(archive.commitments(3).unwrap(), commitments);
+        archive.verify().unwrap();
+
+        // A damaged frame fails to decompress or to match its checksum.
+        let mut damaged = bytes.clone();
+        let last = damaged.len() - 4;
+        damaged[last] ^= 0xff;
+        let mut archive = Archive::open(Cursor::new(&damaged), &codec).unwrap();
+        assert!(archive.verify().is_err());
+    }
+}
@@ -0,0 +1,633 @@
+//! **audit module**
+//!
+//! Persistence of audit records outside the process, with a BLAKE3 hash chain that makes edits
+//! to the persisted log evident.
+//!
+//! Every record logged by the [`AuditLogger`](crate::error::AuditLogger) is numbered and chained
+//! into a [`ChainedAuditRecord`]:
+//!
+//! ```text
+//! hash_i = BLAKE3-derive_key(hash_{i-1} ‖ sequence_le ‖ JSON(record))    hash_{-1} = 0^32
+//! ```
+//!
+//! and handed to every configured [`AuditSink`]. Changing, removing or reordering an entry
+//! breaks every later link, which [`verify_chain`] detects. A chain starts at sequence 0 with
+//! each logger, so a file appended to by several runs holds one chain per run; cutting off the
+//! tail of a run is only detected against a head recorded elsewhere
+//! ([`AuditLogger::chain_head`](crate::error::AuditLogger::chain_head)), e.g. in a timestamped
+//! ceremony transcript.
+//!
+//! Built-in sinks:
+//!
+//! - [`JsonLinesAuditSink`]: one JSON entry per line, appended to a file and synced per entry;
+//!   [`read_json_lines`] loads it back for [`verify_chain`]. With
+//!   [`JsonLinesAuditSink::canonical`] each line is [canonical JSON](crate::canonical_json)
+//!   instead; the chain hashes are the same either way.
+//! - [`SyslogAuditSink`]: RFC 5424 messages over a Unix datagram socket (`/dev/log`) or UDP.
+//! - [`RingBufferAuditSink`]: the latest entries in memory, shared between clones.
+//!
+//! For third-party review the logger's own chain is exported as an [`AuditExport`], which
+//! carries the hash construction and the head next to the entries. [`AuditExport::verify`] checks
+//! it alone and [`AuditExport::verify_anchored`] also against an independently recorded head. The
+//! hashed bytes are the compact JSON of `record` exactly as exported (fields in emitted order, no
+//! whitespace, non-ASCII unescaped), so an auditor can recompute the chain without this crate
+//! using any order-preserving JSON parser and a BLAKE3 `derive_key` with [`CHAIN_CONTEXT`].
+//!
+//! The sinks of an `EnterpriseCryptoSystem` are chosen with
+//! [`EnterpriseConfig::audit_sinks`](crate::EnterpriseConfig::audit_sinks). Files and sockets are
+//! opened at the first entry, and a failing sink does not stop logging: the failure is reported
+//! on the structured log and counted
+//! ([`AuditLogger::sink_failures`](crate::error::AuditLogger::sink_failures)).
+
+use crate::canonical_json::JsonFormat;
+use crate::error::{AuditRecord, CryptoError, CryptoResult, Severity};
+use crate::hash_adapter::Blake3Adapter;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::UdpSocket;
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// BLAKE3 `derive_key` context of the chain hashes.
+pub const CHAIN_CONTEXT: &str = "zk-thresh-pro 2024 audit chain v1";
+
+/// Version of the [`AuditExport`] format.
+pub const AUDIT_EXPORT_VERSION: u8 = 1;
+
+/// Syslog facility of the messages, `authpriv` (10).
+const SYSLOG_FACILITY: u8 = 10;
+
+/// An audit record as persisted: numbered and linked to its predecessor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainedAuditRecord {
+    /// Position in the chain, from 0.
+    pub sequence: u64,
+    /// Hash of the previous entry, zero for the first.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub previous: Vec<u8>,
+    pub record: AuditRecord,
+    /// Hash of this entry.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub hash: Vec<u8>,
+}
+
+impl ChainedAuditRecord {
+    /// Chain `record` after the entry with hash `previous`.
+    pub fn new(sequence: u64, previous: [u8; 32], record: AuditRecord) -> CryptoResult<Self> {
+        let hash = chain_hash(sequence, &previous, &record)?;
+        Ok(Self {
+            sequence,
+            previous: previous.to_vec(),
+            record,
+            hash: hash.to_vec(),
+        })
+    }
+}
+
+fn chain_hash(sequence: u64, previous: &[u8], record: &AuditRecord) -> CryptoResult<[u8; 32]> {
+    let json = serde_json::to_vec(record).map_err(|e| CryptoError::Serialization {
+        details: e.to_string(),
+    })?;
+    let mut hasher = Blake3Adapter::new_derive_key(CHAIN_CONTEXT);
+    digest::Update::update(&mut hasher, previous);
+    digest::Update::update(&mut hasher, &sequence.to_le_bytes());
+    digest::Update::update(&mut hasher, &json);
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    Ok(out)
+}
+
+/// Check the links of `entries`, which may hold several chains one after the other.
+///
+/// Returns the hash of the last entry, or `CryptoError::SecurityViolation` naming the first entry
+/// that was altered, removed or reordered.
+pub fn verify_chain(entries: &[ChainedAuditRecord]) -> CryptoResult<[u8; 32]> {
+    let mut head = [0u8; 32];
+    let mut expected_sequence = 0;
+    for (position, entry) in entries.iter().enumerate() {
+        if entry.sequence == 0 {
+            head = [0u8; 32];
+            expected_sequence = 0;
+        }
+        let broken = |reason: &str| CryptoError::SecurityViolation {
+            details: format!(
+                "audit chain broken at entry {} (sequence {}): {}",
+                position, entry.sequence, reason
+            ),
+        };
+        if entry.sequence != expected_sequence {
+            return Err(broken("sequence gap"));
+        }
+        if entry.previous[..] != head[..] {
+            return Err(broken("previous hash mismatch"));
+        }
+        if chain_hash(entry.sequence, &entry.previous, &entry.record)?[..] != entry.hash[..] {
+            return Err(broken("entry hash mismatch"));
+        }
+        head.copy_from_slice(&entry.hash);
+        expected_sequence += 1;
+    }
+    Ok(head)
+}
+
+/// One logger's complete audit chain, for independent verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExport {
+    /// Format version, [`AUDIT_EXPORT_VERSION`].
+    pub version: u8,
+    /// BLAKE3 `derive_key` context of the chain hashes, [`CHAIN_CONTEXT`].
+    pub context: String,
+    /// Every entry, from sequence 0.
+    pub entries: Vec<ChainedAuditRecord>,
+    /// Hash of the last entry, zero for an empty chain.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub head: Vec<u8>,
+}
+
+impl AuditExport {
+    pub(crate) fn new(entries: Vec<ChainedAuditRecord>, head: [u8; 32]) -> Self {
+        Self {
+            version: AUDIT_EXPORT_VERSION,
+            context: CHAIN_CONTEXT.to_string(),
+            entries,
+            head: head.to_vec(),
+        }
+    }
+
+    /// Check that the entries form one unbroken chain from sequence 0 ending at `head`.
+    ///
+    /// Returns the head, `CryptoError::Serialization` for an unknown version or hash context, and
+    /// `CryptoError::SecurityViolation` for a broken chain or a head that does not match it.
+    pub fn verify(&self) -> CryptoResult<[u8; 32]> {
+        if self.version != AUDIT_EXPORT_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported audit export version {}", self.version),
+            });
+        }
+        if self.context != CHAIN_CONTEXT {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported audit chain context {:?}", self.context),
+            });
+        }
+        let head = verify_chain(&self.entries)?;
+        // `verify_chain` accepts restarts; an export holds a single chain.
+        if let Some((position, entry)) = self
+            .entries
+            .iter()
+            .enumerate()
+            .find(|(position, entry)| entry.sequence != *position as u64)
+        {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "audit export restarts its chain at entry {} (sequence {})",
+                    position, entry.sequence
+                ),
+            });
+        }
+        if self.head[..] != head[..] {
+            return Err(CryptoError::SecurityViolation {
+                details: "audit export head does not match its last entry".to_string(),
+            });
+        }
+        Ok(head)
+    }
+
+    /// [`AuditExport::verify`], and check the head against one recorded elsewhere, e.g. in a
+    /// ceremony transcript, which also detects entries cut off the end.
+    pub fn verify_anchored(&self, anchor: &[u8; 32]) -> CryptoResult<()> {
+        if self.verify()? != *anchor {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "audit export head does not match the anchor {}",
+                    hex::encode(anchor)
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Destination of persisted audit entries.
+pub trait AuditSink {
+    /// Name of the sink, for logs.
+    fn name(&self) -> &str;
+
+    /// Persist one entry; entries arrive in chain order.
+    fn write(&mut self, entry: &ChainedAuditRecord) -> CryptoResult<()>;
+}
+
+/// Audit sink selected by configuration.
+#[derive(Debug, Clone)]
+pub enum AuditSinkConfig {
+    /// Append JSON lines to this file.
+    JsonLines(PathBuf),
+    /// Append canonical JSON lines to this file.
+    CanonicalJsonLines(PathBuf),
+    /// Send to syslog at this target.
+    Syslog(SyslogTarget),
+    /// Keep entries in this ring buffer; keep a clone to read them.
+    RingBuffer(RingBufferAuditSink),
+}
+
+impl AuditSinkConfig {
+    /// The configured sink. Nothing is opened until the first entry.
+    pub fn build(&self) -> Box<dyn AuditSink + Send + Sync> {
+        match self {
+            AuditSinkConfig::JsonLines(path) => Box::new(JsonLinesAuditSink::new(path)),
+            AuditSinkConfig::CanonicalJsonLines(path) => {
+                Box::new(JsonLinesAuditSink::canonical(path))
+            }
+            AuditSinkConfig::Syslog(target) => Box::new(SyslogAuditSink::new(target.clone())),
+            AuditSinkConfig::RingBuffer(buffer) => Box::new(buffer.clone()),
+        }
+    }
+}
+
+fn io_error(context: &str, e: std::io::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: format!("{}: {}", context, e),
+    }
+}
+
+/// Appends one JSON entry per line to a file, syncing it to disk after each entry.
+pub struct JsonLinesAuditSink {
+    path: PathBuf,
+    format: JsonFormat,
+    file: Option<File>,
+}
+
+impl JsonLinesAuditSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: JsonFormat::Compact,
+            file: None,
+        }
+    }
+
+    /// A sink writing each entry as canonical JSON.
+    pub fn canonical(path: impl Into<PathBuf>) -> Self {
+        Self {
+            format: JsonFormat::Canonical,
+            ..Self::new(path)
+        }
+    }
+}
+
+impl AuditSink for JsonLinesAuditSink {
+    fn name(&self) -> &str {
+        "json-lines"
+    }
+
+    fn write(&mut self, entry: &ChainedAuditRecord) -> CryptoResult<()> {
+        let mut line = self.format.to_vec(entry)?;
+        line.push(b'\n');
+        let file = match &mut self.file {
+            Some(file) => file,
+            file => file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .map_err(|e| io_error("cannot open audit log", e))?,
+            ),
+        };
+        file.write_all(&line)
+            .and_then(|()| file.sync_data())
+            .map_err(|e| io_error("cannot append to audit log", e))
+    }
+}
+
+/// Entries of a file written by [`JsonLinesAuditSink`], in order.
+///
+/// Returns `CryptoError::Serialization` if the file cannot be read or a line does not parse.
+pub fn read_json_lines(path: &Path) -> CryptoResult<Vec<ChainedAuditRecord>> {
+    let file = File::open(path).map_err(|e| io_error("cannot open audit log", e))?;
+    let mut entries = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| io_error("cannot read audit log", e))?;
+        if line.is_empty() {
+            continue;
+        }
+        entries.push(
+            serde_json::from_str(&line).map_err(|e| CryptoError::Serialization {
+                details: format!("audit log line {}: {}", number + 1, e),
+            })?,
+        );
+    }
+    Ok(entries)
+}
+
+/// Where [`SyslogAuditSink`] sends its messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyslogTarget {
+    /// Local syslog daemon socket, usually `/dev/log`.
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// Remote collector, `host:port`.
+    Udp(String),
+}
+
+#[cfg(unix)]
+impl Default for SyslogTarget {
+    fn default() -> Self {
+        SyslogTarget::Unix(PathBuf::from("/dev/log"))
+    }
+}
+
+enum SyslogSocket {
+    #[cfg(unix)]
+    Unix(UnixDatagram),
+    Udp(UdpSocket),
+}
+
+/// Sends each entry as an RFC 5424 message of facility `authpriv`.
+///
+/// The message carries the event code as MSGID and the structured line of
+/// [`crate::events::render`], with the sequence and chain hash appended, as MSG.
+pub struct SyslogAuditSink {
+    target: SyslogTarget,
+    socket: Option<SyslogSocket>,
+}
+
+impl SyslogAuditSink {
+    pub fn new(target: SyslogTarget) -> Self {
+        Self {
+            target,
+            socket: None,
+        }
+    }
+
+    fn connect(&self) -> std::io::Result<SyslogSocket> {
+        match &self.target {
+            #[cfg(unix)]
+            SyslogTarget::Unix(path) => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                Ok(SyslogSocket::Unix(socket))
+            }
+            SyslogTarget::Udp(address) => {
+                let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+                socket.connect(address)?;
+                Ok(SyslogSocket::Udp(socket))
+            }
+        }
+    }
+}
+
+/// RFC 5424 message of `entry`.
+fn syslog_message(entry: &ChainedAuditRecord) -> String {
+    let severity = match entry.record.event.severity() {
+        Severity::Low => 6,
+        Severity::Medium => 5,
+        Severity::High => 4,
+    };
+    let mut fields = entry.record.fields();
+    fields.push(("sequence", entry.sequence.to_string()));
+    fields.push(("chain", hex::encode(&entry.hash)));
+    let code = entry.record.event.code();
+    format!(
+        "<{}>1 {} - zk-thresh-pro {} {} - {}",
+        SYSLOG_FACILITY * 8 + severity,
+        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+        std::process::id(),
+        code,
+        crate::events::render(code, &fields, entry.record.event.message())
+    )
+}
+
+impl AuditSink for SyslogAuditSink {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    fn write(&mut self, entry: &ChainedAuditRecord) -> CryptoResult<()> {
+        let message = syslog_message(entry);
+        let socket = match &mut self.socket {
+            Some(socket) => socket,
+            None => {
+                let socket = self
+                    .connect()
+                    .map_err(|e| io_error("cannot connect to syslog", e))?;
+                self.socket.insert(socket)
+            }
+        };
+        let sent = match socket {
+            #[cfg(unix)]
+            SyslogSocket::Unix(socket) => socket.send(message.as_bytes()),
+            SyslogSocket::Udp(socket) => socket.send(message.as_bytes()),
+        };
+        if let Err(e) = sent {
+            // Reconnect at the next entry, e.g. after the daemon restarted.
+            self.socket = None;
+            return Err(io_error("cannot send to syslog", e));
+        }
+        Ok(())
+    }
+}
+
+/// The latest `capacity` entries in memory; clones share the same buffer.
+#[derive(Debug, Clone)]
+pub struct RingBufferAuditSink {
+    capacity: usize,
+    entries: Arc<Mutex<VecDeque<ChainedAuditRecord>>>,
+}
+
+impl RingBufferAuditSink {
+    /// Returns `CryptoError::Validation` for a zero capacity.
+    pub fn new(capacity: usize) -> CryptoResult<Self> {
+        if capacity == 0 {
+            return Err(CryptoError::Validation {
+                field: "capacity".to_string(),
+                reason: "must be at least 1".to_string(),
+            });
+        }
+        Ok(Self {
+            capacity,
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        })
+    }
+
+    /// Buffered entries, oldest first.
+    pub fn entries(&self) -> Vec<ChainedAuditRecord> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+}
+
+impl AuditSink for RingBufferAuditSink {
+    fn name(&self) -> &str {
+        "ring-buffer"
+    }
+
+    fn write(&mut self, entry: &ChainedAuditRecord) -> CryptoResult<()> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AuditLogger, SecurityEvent};
+
+    fn log_events(logger: &mut AuditLogger, count: usize) {
+        for i in 0..count {
+            logger.log_event(SecurityEvent::KeyGenerated {
+                key_id: format!("key-{}", i),
+                timestamp: chrono::Utc::now(),
+            });
+        }
+    }
+
+    #[test]
+    fn test_json_lines_chain_detects_tampering() {
+        let path =
+            std::env::temp_dir().join(format!("zk-thresh-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut logger = AuditLogger::new();
+        logger.add_sink(Box::new(JsonLinesAuditSink::new(&path)));
+        log_events(&mut logger, 3);
+        // A second run appends its own chain.
+        let mut restarted = AuditLogger::new();
+        restarted.add_sink(Box::new(JsonLinesAuditSink::new(&path)));
+        log_events(&mut restarted, 2);
+
+        let entries = read_json_lines(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 5);
+        assert_eq!(verify_chain(&entries).unwrap(), restarted.chain_head());
+        assert_eq!(verify_chain(&entries[..3]).unwrap(), logger.chain_head());
+
+        let mut edited = entries.clone();
+        edited[1].record.event = SecurityEvent::KeyGenerated {
+            key_id: "forged".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert!(verify_chain(&edited).is_err());
+        let mut removed = entries.clone();
+        removed.remove(1);
+        assert!(verify_chain(&removed).is_err());
+        let mut reordered = entries;
+        reordered.swap(3, 4);
+        assert!(verify_chain(&reordered).is_err());
+    }
+
+    #[test]
+    fn test_canonical_json_lines_keep_the_chain() {
+        let path = std::env::temp_dir().join(format!(
+            "zk-thresh-audit-canonical-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let mut logger = AuditLogger::new();
+        logger.add_sink(AuditSinkConfig::CanonicalJsonLines(path.clone()).build());
+        log_events(&mut logger, 3);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text
+            .lines()
+            .all(|line| crate::canonical_json::is_canonical(line.as_bytes())));
+        let entries = read_json_lines(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(verify_chain(&entries).unwrap(), logger.chain_head());
+    }
+
+    #[test]
+    fn test_export_verifies_independently() {
+        let mut logger = AuditLogger::new();
+        log_events(&mut logger, 3);
+        assert_eq!(logger.verify_chain().unwrap(), logger.chain_head());
+        let json = serde_json::to_string(&logger.export()).unwrap();
+        let export: AuditExport = serde_json::from_str(&json).unwrap();
+        export.verify_anchored(&logger.chain_head()).unwrap();
+
+        // The documented construction, with plain BLAKE3.
+        let last = &export.entries[2];
+        let mut hasher = blake3::Hasher::new_derive_key(&export.context);
+        hasher.update(&last.previous);
+        hasher.update(&last.sequence.to_le_bytes());
+        hasher.update(&serde_json::to_vec(&last.record).unwrap());
+        assert_eq!(hasher.finalize().as_bytes()[..], export.head[..]);
+
+        // Cutting off the tail is consistent on its own but not with the anchor.
+        let mut truncated = export.clone();
+        truncated.entries.pop();
+        truncated.head = truncated.entries[1].hash.clone();
+        truncated.verify().unwrap();
+        assert!(truncated.verify_anchored(&logger.chain_head()).is_err());
+
+        let mut edited = export.clone();
+        edited.entries[0].record.event = SecurityEvent::KeyGenerated {
+            key_id: "forged".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert!(edited.verify().is_err());
+        let mut restarted = export.clone();
+        restarted
+            .entries
+            .extend(AuditLogger::new().export().entries);
+        let mut other = AuditLogger::new();
+        log_events(&mut other, 1);
+        restarted.entries.extend(other.export().entries);
+        restarted.head = other.chain_head().to_vec();
+        assert!(restarted.verify().is_err());
+        let mut wrong_head = export.clone();
+        wrong_head.head = vec![0u8; 32];
+        assert!(wrong_head.verify().is_err());
+        let mut unknown = export;
+        unknown.context = "other".to_string();
+        assert!(unknown.verify().is_err());
+    }
+
+    #[test]
+    fn test_ring_buffer_and_failing_sinks() {
+        assert!(RingBufferAuditSink::new(0).is_err());
+        let ring = RingBufferAuditSink::new(2).unwrap();
+        let mut logger = AuditLogger::new();
+        logger.add_sink(AuditSinkConfig::RingBuffer(ring.clone()).build());
+        logger.add_sink(Box::new(JsonLinesAuditSink::new(
+            "/nonexistent-dir/zk-thresh-audit.jsonl",
+        )));
+        log_events(&mut logger, 3);
+
+        let entries = ring.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].sequence, 2);
+        assert_eq!(entries[1].hash[..], logger.chain_head()[..]);
+        assert_eq!(logger.sink_failures(), 3);
+        assert_eq!(logger.get_records().len(), 3);
+    }
+
+    #[test]
+    fn test_syslog_over_udp() {
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector
+            .set_read_timeout(Some(std::time::Duration::from_secs(5)))
+            .unwrap();
+        let address = collector.local_addr().unwrap().to_string();
+        let mut logger = AuditLogger::new();
+        logger.add_sink(AuditSinkConfig::Syslog(SyslogTarget::Udp(address)).build());
+        logger.log_event(SecurityEvent::UnauthorizedAccess {
+            attempt: "probe".to_string(),
+            timestamp: chrono::Utc::now(),
+        });
+
+        let mut buf = [0u8; 2048];
+        let len = collector.recv(&mut buf).unwrap();
+        let message = std::str::from_utf8(&buf[..len]).unwrap();
+        assert!(message.starts_with("<84>1 "));
+        assert!(message.contains(" zk-thresh-pro "));
+        assert!(message.contains("attempt=probe"));
+        assert!(message.contains(&format!("chain={}", hex::encode(logger.chain_head()))));
+        assert_eq!(logger.sink_failures(), 0);
+    }
+}
@@ -0,0 +1,434 @@
+//! **beacon module**
+//!
+//! Commit-reveal randomness beacon: `n` participants jointly produce a public random value that
+//! none of them can predict or choose, with a transcript anyone can check afterwards.
+//!
+//! Each round runs in two phases:
+//!
+//! 1. Every participant `i` draws a contribution `x_i` ([`BeaconContribution`]) and publishes the
+//!    commitment `H(round ‖ i ‖ x_i)`. The commitment is bound to the round and the index, so a
+//!    participant cannot copy another's.
+//! 2. Once all `n` commitments are collected, the participants reveal `x_i`; a reveal that does
+//!    not open its commitment is rejected.
+//!
+//! The output is `H(round ‖ n ‖ Σ x_i)`, summed with [`crate::utils::distributed_random_scalar`];
+//! it is uniform as long as one participant is honest. [`BeaconRound::finalize`] returns a
+//! [`BeaconTranscript`] of the commitments, reveals and output, which
+//! [`BeaconTranscript::verify`] re-checks without any secret.
+//!
+//! A participant that sees the other reveals can still withhold its own, aborting the round.
+//! The round then fails and names it ([`BeaconRound::missing_reveals`]) instead of finishing
+//! without it, since finishing would let the last revealer choose between two outputs; a rerun
+//! gives it that choice again, so withholding must be handled by policy (e.g. by excluding the
+//! participant from later rounds).
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use curve25519_dalek::scalar::Scalar;
+#[cfg(feature = "dealer")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Version of the [`BeaconTranscript`] format.
+pub const BEACON_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`BeaconTranscript`].
+pub const BEACON_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: None,
+    aead: None,
+    kdf: None,
+    proof: None,
+};
+
+const COMMITMENT_CONTEXT: &str = "zk-thresh-pro 2024 beacon commitment v1";
+const OUTPUT_CONTEXT: &str = "zk-thresh-pro 2024 beacon output v1";
+
+/// A participant's secret contribution to one round, kept until the reveal phase.
+#[cfg(feature = "dealer")]
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct BeaconContribution {
+    #[zeroize(skip)]
+    round: u64,
+    #[zeroize(skip)]
+    index: usize,
+    contribution: Scalar,
+}
+
+#[cfg(feature = "dealer")]
+impl BeaconContribution {
+    /// Draw the contribution of participant `index` to `round`.
+    ///
+    /// Returns `CryptoError::InsufficientEntropy` if the random generator fails.
+    pub fn new(round: u64, index: usize) -> CryptoResult<Self> {
+        Ok(Self {
+            round,
+            index,
+            contribution: crate::utils::random_scalar(&mut crate::utils::new_rng())?,
+        })
+    }
+
+    /// Commitment to publish in the commit phase.
+    pub fn commitment(&self) -> BeaconCommitment {
+        BeaconCommitment {
+            round: self.round,
+            index: self.index,
+            digest: commitment_digest(self.round, self.index, &self.contribution).to_vec(),
+        }
+    }
+
+    /// Reveal to publish once every commitment of the round is known.
+    pub fn reveal(self) -> BeaconReveal {
+        BeaconReveal {
+            round: self.round,
+            index: self.index,
+            contribution: self.contribution,
+        }
+    }
+}
+
+/// Published commitment `H(round ‖ index ‖ x)` to a contribution.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BeaconCommitment {
+    pub round: u64,
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub digest: Vec<u8>,
+}
+
+/// Published contribution, opening a [`BeaconCommitment`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BeaconReveal {
+    pub round: u64,
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub contribution: Scalar,
+}
+
+impl BeaconReveal {
+    /// Whether this reveal opens `commitment`.
+    pub fn opens(&self, commitment: &BeaconCommitment) -> bool {
+        self.round == commitment.round
+            && self.index == commitment.index
+            && commitment_digest(self.round, self.index, &self.contribution)[..]
+                == commitment.digest[..]
+    }
+}
+
+fn commitment_digest(round: u64, index: usize, contribution: &Scalar) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(COMMITMENT_CONTEXT);
+    digest::Update::update(&mut hasher, &round.to_le_bytes());
+    digest::Update::update(&mut hasher, &(index as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, contribution.as_bytes());
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    out
+}
+
+fn beacon_output(round: u64, reveals: &[BeaconReveal]) -> [u8; 32] {
+    let contributions: Vec<Scalar> = reveals.iter().map(|r| r.contribution).collect();
+    let sum = crate::utils::distributed_random_scalar(&contributions);
+    let mut hasher = Blake3Adapter::new_derive_key(OUTPUT_CONTEXT);
+    digest::Update::update(&mut hasher, &round.to_le_bytes());
+    digest::Update::update(&mut hasher, &(reveals.len() as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, sum.as_bytes());
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    out
+}
+
+/// Collector of one round, held by the coordinator or by every participant.
+#[derive(Clone, Debug)]
+pub struct BeaconRound {
+    round: u64,
+    commitments: Vec<Option<BeaconCommitment>>,
+    reveals: Vec<Option<BeaconReveal>>,
+}
+
+impl BeaconRound {
+    /// Round `round` among the participants with indices `1..=participants`.
+    ///
+    /// Returns `CryptoError::Validation` for fewer than two participants.
+    pub fn new(round: u64, participants: usize) -> CryptoResult<Self> {
+        if participants < 2 {
+            return Err(CryptoError::Validation {
+                field: "participants".to_string(),
+                reason: "a beacon needs at least two participants".to_string(),
+            });
+        }
+        Ok(Self {
+            round,
+            commitments: vec![None; participants],
+            reveals: vec![None; participants],
+        })
+    }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Whether every commitment is in, so reveals are accepted.
+    pub fn commitments_complete(&self) -> bool {
+        self.commitments.iter().all(Option::is_some)
+    }
+
+    /// Indices of the participants whose reveal is still missing.
+    pub fn missing_reveals(&self) -> Vec<usize> {
+        (1..=self.reveals.len())
+            .filter(|&i| self.reveals[i - 1].is_none())
+            .collect()
+    }
+
+    /// Slot of participant `index` for a message of `round`.
+    fn slot(&self, round: u64, index: usize) -> CryptoResult<usize> {
+        if round != self.round {
+            return Err(CryptoError::Validation {
+                field: "round".to_string(),
+                reason: format!("expected round {}, got {}", self.round, round),
+            });
+        }
+        if index == 0 || index > self.commitments.len() {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: format!("no participant {}", index),
+            });
+        }
+        Ok(index - 1)
+    }
+
+    /// Accept the commitment of one participant.
+    ///
+    /// Returns `CryptoError::Validation` for a wrong round or index, a second commitment of
+    /// the same participant, or a commitment arriving after the reveal phase has started.
+    pub fn commit(&mut self, commitment: BeaconCommitment) -> CryptoResult<()> {
+        let slot = self.slot(commitment.round, commitment.index)?;
+        if self.commitments_complete() {
+            return Err(CryptoError::Validation {
+                field: "commitment".to_string(),
+                reason: "the commit phase is over".to_string(),
+            });
+        }
+        if self.commitments[slot].is_some() {
+            return Err(CryptoError::Validation {
+                field: "commitment".to_string(),
+                reason: format!("participant {} already committed", commitment.index),
+            });
+        }
+        self.commitments[slot] = Some(commitment);
+        Ok(())
+    }
+
+    /// Accept the reveal of one participant.
+    ///
+    /// Returns `CryptoError::Validation` for a wrong round or index or before every commitment
+    /// is in, and `CryptoError::SecurityViolation` if the reveal does not open the participant's
+    /// commitment.
+    pub fn reveal(&mut self, reveal: BeaconReveal) -> CryptoResult<()> {
+        let slot = self.slot(reveal.round, reveal.index)?;
+        let commitment = match &self.commitments[slot] {
+            Some(commitment) if self.commitments_complete() => commitment,
+            _ => {
+                return Err(CryptoError::Validation {
+                    field: "reveal".to_string(),
+                    reason: "reveals are accepted once every participant has committed".to_string(),
+                })
+            }
+        };
+        if !reveal.opens(commitment) {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "beacon reveal of participant {} does not open its commitment",
+                    reveal.index
+                ),
+            });
+        }
+        self.reveals[slot] = Some(reveal);
+        Ok(())
+    }
+
+    /// Output and transcript of the round.
+    ///
+    /// Returns `CryptoError::Validation` naming the participants that have not revealed.
+    pub fn finalize(&self) -> CryptoResult<BeaconTranscript> {
+        let missing = self.missing_reveals();
+        if !missing.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "reveals".to_string(),
+                reason: format!("participants {:?} have not revealed", missing),
+            });
+        }
+        let commitments: Vec<BeaconCommitment> =
+            self.commitments.iter().flatten().cloned().collect();
+        let reveals: Vec<BeaconReveal> = self.reveals.iter().flatten().cloned().collect();
+        Ok(BeaconTranscript {
+            version: BEACON_VERSION,
+            algorithms: BEACON_ALGORITHMS,
+            round: self.round,
+            output: beacon_output(self.round, &reveals).to_vec(),
+            commitments,
+            reveals,
+        })
+    }
+}
+
+fn default_algorithms() -> AlgorithmSuite {
+    BEACON_ALGORITHMS
+}
+
+/// Public record of a finished round.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BeaconTranscript {
+    /// Transcript format version, [`BEACON_VERSION`].
+    pub version: u8,
+    /// Algorithms the transcript was produced with; [`BEACON_ALGORITHMS`] when absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    pub round: u64,
+    /// Commitments of participants `1..=n`, in order.
+    pub commitments: Vec<BeaconCommitment>,
+    /// Reveals of participants `1..=n`, in order.
+    pub reveals: Vec<BeaconReveal>,
+    /// Beacon output `H(round ‖ n ‖ Σ x_i)`.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub output: Vec<u8>,
+}
+
+impl BeaconTranscript {
+    /// Re-check every reveal against its commitment and recompute the output.
+    ///
+    /// Returns `CryptoError::Serialization` for an unknown version, `CryptoError::Validation`
+    /// for a malformed participant list and `CryptoError::SecurityViolation` if a reveal or the
+    /// output does not match.
+    pub fn verify(&self) -> CryptoResult<()> {
+        if self.version != BEACON_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported beacon transcript version {}", self.version),
+            });
+        }
+        self.algorithms
+            .require(&BEACON_ALGORITHMS, "beacon transcript")?;
+        if self.commitments.len() < 2 || self.reveals.len() != self.commitments.len() {
+            return Err(CryptoError::Validation {
+                field: "transcript".to_string(),
+                reason: "needs one commitment and one reveal per participant".to_string(),
+            });
+        }
+        for (position, (commitment, reveal)) in
+            self.commitments.iter().zip(&self.reveals).enumerate()
+        {
+            if commitment.round != self.round || commitment.index != position + 1 {
+                return Err(CryptoError::Validation {
+                    field: "transcript".to_string(),
+                    reason: format!("entry {} is out of order or of another round", position),
+                });
+            }
+            if !reveal.opens(commitment) {
+                return Err(CryptoError::SecurityViolation {
+                    details: format!(
+                        "beacon reveal of participant {} does not open its commitment",
+                        commitment.index
+                    ),
+                });
+            }
+        }
+        if beacon_output(self.round, &self.reveals)[..] != self.output[..] {
+            return Err(CryptoError::SecurityViolation {
+                details: "beacon output does not match the reveals".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The output as a scalar, e.g. to seed a public random choice.
+    pub fn output_scalar(&self) -> Scalar {
+        Scalar::hash_from_bytes::<Blake3Adapter>(&self.output)
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+
+    fn run_round(round: u64, n: usize) -> (BeaconRound, Vec<BeaconReveal>) {
+        let mut beacon = BeaconRound::new(round, n).unwrap();
+        let contributions: Vec<_> = (1..=n)
+            .map(|i| BeaconContribution::new(round, i).unwrap())
+            .collect();
+        for contribution in &contributions {
+            beacon.commit(contribution.commitment()).unwrap();
+        }
+        let reveals = contributions.into_iter().map(|c| c.reveal()).collect();
+        (beacon, reveals)
+    }
+
+    #[test]
+    fn test_beacon_round() {
+        let (mut beacon, reveals) = run_round(7, 3);
+        assert!(beacon.finalize().is_err());
+        for reveal in reveals.iter().rev() {
+            beacon.reveal(reveal.clone()).unwrap();
+        }
+        let transcript = beacon.finalize().unwrap();
+        transcript.verify().unwrap();
+
+        let json = serde_json::to_string(&transcript).unwrap();
+        let parsed: BeaconTranscript = serde_json::from_str(&json).unwrap();
+        parsed.verify().unwrap();
+        assert_eq!(parsed.output_scalar(), transcript.output_scalar());
+
+        // Fresh contributions give a fresh output.
+        let (mut other, reveals) = run_round(7, 3);
+        for reveal in reveals {
+            other.reveal(reveal).unwrap();
+        }
+        assert_ne!(other.finalize().unwrap().output, transcript.output);
+
+        let mut forged = transcript.clone();
+        forged.reveals[1].contribution += Scalar::ONE;
+        assert!(matches!(
+            forged.verify(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut forged = transcript;
+        forged.output[0] ^= 1;
+        assert!(forged.verify().is_err());
+    }
+
+    #[test]
+    fn test_beacon_rejects_misbehaviour() {
+        assert!(BeaconRound::new(1, 1).is_err());
+        let mut beacon = BeaconRound::new(1, 2).unwrap();
+        let first = BeaconContribution::new(1, 1).unwrap();
+        let second = BeaconContribution::new(1, 2).unwrap();
+        assert!(beacon
+            .commit(BeaconContribution::new(2, 1).unwrap().commitment())
+            .is_err());
+        assert!(beacon
+            .commit(BeaconContribution::new(1, 3).unwrap().commitment())
+            .is_err());
+
+        // Copying another participant's commitment does not bind to the copier's index.
+        let mut copied = first.commitment();
+        copied.index = 2;
+        beacon.commit(first.commitment()).unwrap();
+        assert!(beacon.commit(first.commitment()).is_err());
+        let first = first.reveal();
+        assert!(beacon.reveal(first.clone()).is_err());
+        beacon.commit(copied).unwrap();
+        beacon.reveal(first.clone()).unwrap();
+
+        let mut stolen = first;
+        stolen.index = 2;
+        assert!(matches!(
+            beacon.reveal(stolen),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(beacon.reveal(second.reveal()).is_err());
+        assert_eq!(beacon.missing_reveals(), [2]);
+        assert!(beacon.finalize().is_err());
+        assert!(beacon
+            .commit(BeaconContribution::new(1, 2).unwrap().commitment())
+            .is_err());
+    }
+}
@@ -0,0 +1,372 @@
+//! # Enterprise demonstration
+//!
+//! Walks through security validation, key generation, a DKG ceremony, VSS verification, batch and
+//! threshold recovery, then prints performance metrics and the audit trail as structured lines.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use env_logger::Builder;
+use log::{Level, LevelFilter};
+use rand::rngs::OsRng;
+use zk_thresh_pro::events::{self, structured_log, EventCode};
+use zk_thresh_pro::identity::{Participant, Roster, Signed, SignedPayload};
+use zk_thresh_pro::utils::{self, random_scalar, ANOTHER_POINT};
+use zk_thresh_pro::{
+    dkg, fingerprint, generate_proof, lagrange_fft, vss, AuditRecord, ComplianceMode, CryptoError,
+    CryptoResult, EnterpriseConfig, EnterpriseCryptoSystem, OperationContext, Redacted,
+    SecurityLevel, ShareData, SoftwareKeyProvider,
+};
+
+/// Run a `threshold`-of-`n` DKG with every participant in this process, each signing what it
+/// sends with its identity and passing the messages through their JSON encoding as a network
+/// transport would.
+fn run_dkg_ceremony(threshold: usize, n: usize) -> CryptoResult<Vec<dkg::DkgOutput>> {
+    const SESSION: &str = "demo-dkg";
+
+    fn wire<T: SignedPayload + serde::de::DeserializeOwned>(
+        identity: &Participant,
+        message: &T,
+    ) -> CryptoResult<Signed<T>> {
+        let encoded = serde_json::to_vec(&identity.sign(SESSION, message)?).map_err(|e| {
+            CryptoError::Serialization {
+                details: e.to_string(),
+            }
+        })?;
+        serde_json::from_slice(&encoded).map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })
+    }
+
+    let identities = (1..=n)
+        .map(Participant::generate)
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let roster = Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>())?;
+    let mut parties = (1..=n)
+        .map(|i| dkg::DkgParticipant::new(i, threshold, n, SESSION, roster.clone()))
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let broadcasts = parties
+        .iter_mut()
+        .map(|p| p.round1())
+        .collect::<CryptoResult<Vec<_>>>()?;
+    for party in parties.iter_mut() {
+        for broadcast in &broadcasts {
+            party.receive_round1(wire(&identities[broadcast.sender - 1], broadcast)?)?;
+        }
+    }
+    let mut messages = Vec::new();
+    for party in parties.iter_mut() {
+        messages.extend(party.round2()?);
+    }
+    let mut complaints = Vec::new();
+    for message in &messages {
+        let signed = wire(&identities[message.sender - 1], message)?;
+        complaints.extend(parties[message.recipient - 1].receive_round2(signed)?);
+    }
+    for party in parties.iter_mut() {
+        complaints.extend(party.complain_missing());
+    }
+    let mut responses = Vec::new();
+    for party in parties.iter_mut() {
+        for complaint in &complaints {
+            let signed = wire(&identities[complaint.complainant - 1], complaint)?;
+            responses.extend(party.receive_complaint(signed)?);
+        }
+    }
+    for party in parties.iter_mut() {
+        for response in &responses {
+            party.receive_complaint_response(wire(&identities[response.accused - 1], response)?)?;
+        }
+    }
+    parties.iter_mut().map(|p| p.finalize()).collect()
+}
+
+/// Enterprise demonstration function
+#[allow(dead_code)]
+fn enterprise_demonstration() -> CryptoResult<()> {
+    structured_log!(
+        Level::Info,
+        EventCode::ApplicationStarted,
+        "starting enterprise zero-knowledge threshold secret sharing demo"
+    );
+
+    // Initialize enterprise system
+    let config = EnterpriseConfig::default();
+    let mut crypto_system = EnterpriseCryptoSystem::new(config);
+
+    // Validate security
+    crypto_system.validate_security()?;
+
+    // Generate enterprise key
+    let keygen = OperationContext::new("master-key-001", 0, "demo-keygen-001");
+    let mut provider = SoftwareKeyProvider::new();
+    let key = crypto_system.generate_enterprise_key(&mut provider, &keygen)?;
+    let secret = key.secret;
+
+    // Create secret shares
+    let threshold = 5;
+    let num_shares = 10;
+    let shares = crypto_system.create_secret_shares(
+        secret,
+        threshold,
+        num_shares,
+        &keygen.for_operation("demo-operation-001"),
+    )?;
+
+    // Refresh shares into epoch 1
+    let shares = crypto_system.refresh_secret_shares(
+        &shares,
+        threshold,
+        &keygen.next_epoch("demo-refresh-001"),
+    )?;
+
+    // Recover secret
+    let selected_shares: Vec<_> = shares.into_iter().take(threshold).collect();
+    let recovered_secret = crypto_system
+        .recover_secret_enterprise(&selected_shares, &keygen.next_epoch("demo-recovery-001"))?;
+
+    // Verify correctness
+    if recovered_secret == secret {
+        structured_log!(
+            Level::Info,
+            EventCode::DemoStepCompleted,
+            "secret recovery successful - cryptographic correctness verified",
+            step = "verify_recovery"
+        );
+    } else {
+        structured_log!(
+            Level::Error,
+            EventCode::DemoStepFailed,
+            "secret recovery failed - cryptographic error detected",
+            step = "verify_recovery"
+        );
+        return Err(CryptoError::CryptographicOperation {
+            operation: "secret_verification".to_string(),
+        });
+    }
+
+    // Display performance metrics
+    let metrics = crypto_system.get_performance_metrics();
+    for metric in metrics {
+        log_metric(metric);
+    }
+
+    // Display the audit history of the key
+    for record in crypto_system.key_history(&keygen.key_id) {
+        log_audit_event(record);
+    }
+
+    Ok(())
+}
+
+/// Emit one performance metric as a structured line, tagged with its operation context.
+fn log_metric(metric: &lagrange_fft::PerformanceMetrics) {
+    let mut fields = metric
+        .context
+        .as_ref()
+        .map(OperationContext::fields)
+        .unwrap_or_default();
+    fields.extend([
+        ("operation_type", metric.operation_type.clone()),
+        ("duration_ns", metric.duration_ns.to_string()),
+        ("input_size", metric.input_size.to_string()),
+        ("algorithm", metric.algorithm_used.clone()),
+    ]);
+    if let Some(counts) = &metric.counts {
+        fields.extend([
+            ("scalar_muls", counts.scalar_muls.to_string()),
+            ("point_muls", counts.point_muls.to_string()),
+            ("inversions", counts.inversions.to_string()),
+            ("hash_bytes", counts.hash_bytes.to_string()),
+            ("worker_threads", counts.worker_threads.to_string()),
+        ]);
+    }
+    events::emit(
+        Level::Info,
+        EventCode::MetricReported.as_str(),
+        &fields,
+        "performance metric",
+    );
+}
+
+/// Re-emit a recorded audit event as a structured line, tagged with its own code.
+fn log_audit_event(record: &AuditRecord) {
+    let mut fields = vec![("event_code", record.event.code().to_string())];
+    fields.extend(record.fields());
+    events::emit(
+        Level::Info,
+        EventCode::AuditEventRecorded.as_str(),
+        &fields,
+        record.event.message(),
+    );
+}
+
+/// Main function - Enterprise demonstration
+fn main() -> CryptoResult<()> {
+    // 初始化日志
+    Builder::new()
+        .filter(None, LevelFilter::Info)
+        .format_timestamp_secs()
+        .init();
+
+    structured_log!(
+        Level::Info,
+        EventCode::ApplicationStarted,
+        "应用启动：创新型阈值系统演示"
+    );
+
+    // 1. 初始化企业配置
+    let config = EnterpriseConfig {
+        security_level: SecurityLevel::High,
+        audit_enabled: true,
+        audit_sinks: Vec::new(),
+        performance_monitoring: true,
+        compliance_mode: ComplianceMode::Standard,
+        max_key_lifetime_hours: 12,
+        hash_function: None,
+    };
+    let mut system = EnterpriseCryptoSystem::new(config);
+
+    // 2. 执行安全性校验
+    system.validate_security()?;
+    structured_log!(
+        Level::Info,
+        EventCode::SecurityValidationPassed,
+        "安全性校验通过"
+    );
+
+    // 3. 生成主密钥并记录审计事件
+    let master_key = system.generate_enterprise_key(
+        &mut SoftwareKeyProvider::new(),
+        &OperationContext::new("innovative-master-key", 0, "innovative-keygen"),
+    )?;
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "主密钥生成",
+        step = "master_key",
+        key_id = "innovative-master-key",
+        key_fingerprint = fingerprint(&master_key.secret),
+        state = format!("{:?}", master_key.state)
+    );
+
+    // —— 您的“创新”流程开始 —— //
+
+    // 4. 进程内运行 Pedersen DKG，无可信分发者生成多方分片
+    let mpc_output = run_dkg_ceremony(3, 6)?;
+    let mpc_shares: Vec<ShareData> = mpc_output.iter().map(|o| o.share.clone()).collect();
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "DKG 群公钥与分片生成",
+        step = "mpc_generate",
+        group_public_key = hex::encode(mpc_output[0].group_public_key.compress().as_bytes()),
+        num_shares = mpc_shares.len()
+    );
+
+    // 5. 验证分片正确性（VSS 校验）
+    if let Err(e) = vss::verify_share_validity(&mpc_shares) {
+        structured_log!(
+            Level::Error,
+            EventCode::DemoStepFailed,
+            "MPC 分片校验失败",
+            step = "mpc_verify",
+            verify_code = e.code(),
+            error = e.to_string()
+        );
+        return Err(CryptoError::CryptographicOperation {
+            operation: "mpc share validity".into(),
+        });
+    }
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "MPC 分片均已通过验证",
+        step = "mpc_verify"
+    );
+
+    // 6. 多方贡献随机数并聚合
+    let contributions: Vec<Scalar> = vec![
+        random_scalar(&mut OsRng)?,
+        random_scalar(&mut OsRng)?,
+        random_scalar(&mut OsRng)?,
+    ];
+    let aggregated_random = utils::distributed_random_scalar(&contributions);
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "聚合随机数",
+        step = "aggregate_random",
+        value = Redacted(&aggregated_random)
+    );
+
+    // 7. 生成基于共享秘密与聚合随机数的新的分片（创新应用示例）
+    let new_shares: Vec<ShareData> = {
+        let mut tmp = Vec::new();
+        for share in mpc_shares.iter() {
+            let combined = share.share + aggregated_random;
+            let random = random_scalar(&mut OsRng)?;
+            let commitment = RISTRETTO_BASEPOINT_POINT * combined + *ANOTHER_POINT * random;
+            let proof = generate_proof(combined, random, share.index, commitment)?;
+            tmp.push(ShareData {
+                index: share.index,
+                share: combined,
+                commitment,
+                random,
+                proof,
+            });
+        }
+        tmp
+    };
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "基于创新逻辑生成的新分片",
+        step = "derive_shares",
+        num_shares = new_shares.len()
+    );
+
+    // 8. 并行批量恢复秘密示例
+    let batch_results =
+        lagrange_fft::recover_secrets_batch(&[mpc_shares.clone(), new_shares.clone()]);
+    for (i, res) in batch_results.iter().enumerate() {
+        match res {
+            Ok(secret) => structured_log!(
+                Level::Info,
+                EventCode::BatchRecoverySucceeded,
+                "批量恢复成功",
+                batch = i,
+                secret = Redacted(secret)
+            ),
+            Err(e) => structured_log!(
+                Level::Error,
+                EventCode::BatchRecoveryFailed,
+                "批量恢复失败",
+                batch = i,
+                error = e
+            ),
+        }
+    }
+
+    // 9. 调用阈值秘密恢复
+    let recovered = system.recover_secret_enterprise(
+        &new_shares[..3],
+        &OperationContext::new("innovative-derived-key", 0, "innovative-recovery"),
+    )?;
+    structured_log!(
+        Level::Info,
+        EventCode::DemoStepCompleted,
+        "创新阈值恢复结果",
+        step = "threshold_recovery",
+        secret = Redacted(&recovered)
+    );
+
+    // 10. 输出性能与审计日志
+    for metric in system.get_performance_metrics() {
+        log_metric(metric);
+    }
+    for record in system.get_audit_records() {
+        log_audit_event(record);
+    }
+
+    Ok(())
+}
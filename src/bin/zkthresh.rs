@@ -0,0 +1,604 @@
+//! # zkthresh command-line tool
+//!
+//! Dealer, operator and custodian commands over the crate's JSON artifacts, for use from scripts:
+//!
+//! ```text
+//! zkthresh deal --secret <hex-file> --threshold <t> --shares <n> --out-dir <dir>
+//! zkthresh verify --commitments <commitments> <share.json>...
+//! zkthresh recover [--commitments <commitments>] [--encoding <raw|hex|base64>] --out <file> \
+//!     <share.json>...
+//! zkthresh refresh --commitments <commitments> --out-dir <dir> <share.json>...
+//! zkthresh adjust-threshold --threshold <t> --new-threshold <t'> --shares <n> --out-dir <dir> \
+//!     <share.json>...
+//! zkthresh custodian check --share <sealed.json> --key <key-file> \
+//!     --commitments <commitments> --root <hex> --challenge <hex>
+//! ```
+//!
+//! Slices are read and written as `ShareData` JSON, one file per slice (`share-<index>.json` in
+//! `--out-dir`), secrets as the hex of their 32-byte canonical encoding (`recover --encoding` also
+//! writes the raw bytes or base64). `deal` and `refresh` also write `commitments.json` and print
+//! the commitment root in hex; `verify` prints one line per slice. Slice and secret files hold
+//! secret material: create `--out-dir` with restrictive permissions.
+//!
+//! `custodian check` unseals the stored slice with the custodian's sealing key, verifies it against
+//! the published commitments and commitment root, re-seals it in place under a fresh nonce and
+//! prints a [`ShareAttestation`] answering the operator's `--challenge` nonce as JSON on stdout
+//! for the custodian to return to the operator.
+//!
+//! `<commitments>` is either the JSON form of `VssCommitments` or its canonical binary encoding.
+//! Diagnostics go to stderr as structured lines (`RUST_LOG` controls the level, default `warn`).
+//! Exit codes: 0 on success, 1 on a failed command or check, 2 on a usage error. `--help` on any
+//! subcommand describes its arguments.
+
+use clap::{Args, Parser, Subcommand};
+use curve25519_dalek::scalar::Scalar;
+use env_logger::Env;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use zk_thresh_pro::{
+    custodian, lagrange_fft, sharing, vss, CryptoError, CryptoResult, RecoveredSecret, SealedShare,
+    SealingKey, SecretEncoding, ShareData, VssCommitments,
+};
+
+/// Dealer, operator and custodian commands over the crate's JSON artifacts.
+#[derive(Debug, Parser)]
+#[command(name = "zkthresh", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// A parsed command line.
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+enum Command {
+    /// Deal the secret into slices and publish the coefficient commitments.
+    Deal {
+        /// File holding the hex of the 32-byte secret.
+        #[arg(long)]
+        secret: PathBuf,
+        #[arg(long)]
+        threshold: usize,
+        /// Number of slices to deal.
+        #[arg(long)]
+        shares: usize,
+        #[arg(long)]
+        out_dir: PathBuf,
+    },
+    /// Check slices against the published commitments.
+    Verify {
+        #[arg(long)]
+        commitments: PathBuf,
+        #[arg(required = true)]
+        shares: Vec<PathBuf>,
+    },
+    /// Recover the secret from slices whose proofs verify.
+    Recover {
+        #[arg(long)]
+        commitments: Option<PathBuf>,
+        /// Output encoding of the secret: raw, hex or base64.
+        #[arg(long, default_value = "hex", value_parser = parse_encoding)]
+        encoding: SecretEncoding,
+        #[arg(long)]
+        out: PathBuf,
+        #[arg(required = true)]
+        shares: Vec<PathBuf>,
+    },
+    /// Refresh the slices of a dealing, keeping its secret and threshold.
+    Refresh {
+        #[arg(long)]
+        commitments: PathBuf,
+        #[arg(long)]
+        out_dir: PathBuf,
+        #[arg(required = true)]
+        shares: Vec<PathBuf>,
+    },
+    /// Reshare the secret under a new threshold.
+    AdjustThreshold {
+        #[arg(long)]
+        threshold: usize,
+        #[arg(long)]
+        new_threshold: usize,
+        /// Number of slices to issue.
+        #[arg(long = "shares")]
+        shares_out: usize,
+        #[arg(long)]
+        out_dir: PathBuf,
+        #[arg(required = true)]
+        shares: Vec<PathBuf>,
+    },
+    /// Custodian commands.
+    #[command(subcommand)]
+    Custodian(CustodianCommand),
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+enum CustodianCommand {
+    /// Check, re-seal and attest a sealed slice.
+    Check(CheckArgs),
+}
+
+/// Arguments of `custodian check`.
+#[derive(Debug, PartialEq, Eq, Args)]
+struct CheckArgs {
+    /// Sealed slice, re-sealed in place.
+    #[arg(long)]
+    share: PathBuf,
+    /// File holding the hex of the custodian's sealing key.
+    #[arg(long)]
+    key: PathBuf,
+    #[arg(long)]
+    commitments: PathBuf,
+    /// Published commitment root, in hex.
+    #[arg(long)]
+    root: String,
+    /// The operator's challenge nonce, in hex.
+    #[arg(long)]
+    challenge: String,
+}
+
+fn parse_encoding(name: &str) -> Result<SecretEncoding, String> {
+    SecretEncoding::from_name(name).ok_or_else(|| "expected raw, hex or base64".to_string())
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> CryptoError {
+    CryptoError::Configuration {
+        parameter: path.display().to_string(),
+        issue: e.to_string(),
+    }
+}
+
+fn read_commitments(path: &Path) -> CryptoResult<VssCommitments> {
+    let bytes = std::fs::read(path).map_err(|e| io_error(path, e))?;
+    match serde_json::from_slice(&bytes) {
+        Ok(commitments) => Ok(commitments),
+        Err(_) => VssCommitments::from_canonical_bytes(&bytes),
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: e.to_string(),
+    }
+}
+
+fn read_share(path: &Path) -> CryptoResult<ShareData> {
+    serde_json::from_slice(&std::fs::read(path).map_err(|e| io_error(path, e))?)
+        .map_err(serialization_error)
+}
+
+fn read_shares(paths: &[PathBuf]) -> CryptoResult<Vec<ShareData>> {
+    paths.iter().map(|p| read_share(p)).collect()
+}
+
+/// Read a scalar stored as the hex of its canonical encoding.
+fn read_scalar(path: &Path) -> CryptoResult<Scalar> {
+    let encoded =
+        zeroize::Zeroizing::new(std::fs::read_to_string(path).map_err(|e| io_error(path, e))?);
+    let mut bytes = zeroize::Zeroizing::new([0u8; 32]);
+    hex::decode_to_slice(encoded.trim(), bytes.as_mut()).map_err(|e| {
+        CryptoError::Serialization {
+            details: format!("secret must be 32 hex-encoded bytes: {}", e),
+        }
+    })?;
+    Option::from(Scalar::from_canonical_bytes(*bytes)).ok_or_else(|| CryptoError::Serialization {
+        details: "secret is not a canonical scalar encoding".to_string(),
+    })
+}
+
+/// Replace `path` atomically, so an interrupted run never leaves a truncated envelope behind.
+fn write_atomically(path: &Path, contents: &[u8]) -> CryptoResult<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, contents).map_err(|e| io_error(&tmp, e))?;
+    std::fs::rename(&tmp, path).map_err(|e| io_error(path, e))
+}
+
+/// Write every slice to `out_dir/share-<index>.json`, and the commitments if given; returns the
+/// commitment root in hex, or an empty line.
+fn write_dealing(
+    out_dir: &Path,
+    shares: &[ShareData],
+    commitments: Option<&VssCommitments>,
+) -> CryptoResult<String> {
+    std::fs::create_dir_all(out_dir).map_err(|e| io_error(out_dir, e))?;
+    for share in shares {
+        let encoded = serde_json::to_vec_pretty(share).map_err(serialization_error)?;
+        write_atomically(
+            &out_dir.join(format!("share-{}.json", share.index)),
+            &encoded,
+        )?;
+    }
+    let Some(commitments) = commitments else {
+        return Ok(String::new());
+    };
+    let encoded = serde_json::to_vec_pretty(commitments).map_err(serialization_error)?;
+    write_atomically(&out_dir.join("commitments.json"), &encoded)?;
+    Ok(hex::encode(commitments.root()))
+}
+
+fn deal(secret: &Path, threshold: usize, n: usize, out_dir: &Path) -> CryptoResult<String> {
+    let secret = read_scalar(secret)?;
+    let (shares, commitments) =
+        sharing::generate_key_shares_with_commitments(secret, threshold, n)?;
+    write_dealing(out_dir, &shares, Some(&commitments))
+}
+
+fn verify(commitments: &Path, paths: &[PathBuf]) -> CryptoResult<String> {
+    let commitments = read_commitments(commitments)?;
+    let mut lines = Vec::with_capacity(paths.len());
+    let mut failed = Vec::new();
+    for path in paths {
+        let share = read_share(path)?;
+        let status = match commitments.verify_share(&share) {
+            Ok(()) => "ok".to_string(),
+            Err(e) => {
+                failed.push(share.index.to_string());
+                format!("invalid {} ({:?}): {}", e.code(), e.class(), e)
+            }
+        };
+        lines.push(format!(
+            "{} index={} {}",
+            path.display(),
+            share.index,
+            status
+        ));
+    }
+    for line in &lines {
+        println!("{}", line);
+    }
+    if failed.is_empty() {
+        Ok(hex::encode(commitments.root()))
+    } else {
+        Err(CryptoError::SecurityViolation {
+            details: format!("slices {} do not match the commitments", failed.join(", ")),
+        })
+    }
+}
+
+/// Check `shares` against the commitments if given, otherwise their proofs alone.
+fn check_shares(shares: &[ShareData], commitments: Option<&VssCommitments>) -> CryptoResult<()> {
+    let checked = match commitments {
+        Some(commitments) => vss::verify_share_validity_with_commitments(shares, commitments),
+        None => vss::verify_share_validity(shares),
+    };
+    checked.map_err(CryptoError::from)
+}
+
+fn recover(
+    commitments: Option<&Path>,
+    encoding: SecretEncoding,
+    out: &Path,
+    paths: &[PathBuf],
+) -> CryptoResult<String> {
+    let shares = read_shares(paths)?;
+    let commitments = commitments.map(read_commitments).transpose()?;
+    check_shares(&shares, commitments.as_ref())?;
+    if let Some(commitments) = &commitments {
+        if shares.len() < commitments.threshold() {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: format!(
+                    "{} slices provided, threshold is {}",
+                    shares.len(),
+                    commitments.threshold()
+                ),
+            });
+        }
+    }
+    let secret = lagrange_fft::recover_secret_fft(&shares).map_err(|e| {
+        CryptoError::CryptographicOperation {
+            operation: format!("secret_recovery: {}", e),
+        }
+    })?;
+    let encoded = RecoveredSecret::new(secret).encode(encoding);
+    write_atomically(out, &encoded)?;
+    Ok(String::new())
+}
+
+fn refresh(commitments: &Path, out_dir: &Path, paths: &[PathBuf]) -> CryptoResult<String> {
+    let shares = read_shares(paths)?;
+    let commitments = read_commitments(commitments)?;
+    check_shares(&shares, Some(&commitments))?;
+    let (shares, commitments) = sharing::update_shares_with_commitments(&shares, &commitments)?;
+    write_dealing(out_dir, &shares, Some(&commitments))
+}
+
+fn adjust_threshold(
+    threshold: usize,
+    new_threshold: usize,
+    n: usize,
+    out_dir: &Path,
+    paths: &[PathBuf],
+) -> CryptoResult<String> {
+    let shares = read_shares(paths)?;
+    check_shares(&shares, None)?;
+    if new_threshold == 0 || new_threshold > n {
+        return Err(CryptoError::Validation {
+            field: "new_threshold".to_string(),
+            reason: format!("new threshold {} with {} slices", new_threshold, n),
+        });
+    }
+    let shares =
+        sharing::adjust_threshold(&shares, threshold, new_threshold, n).map_err(|reason| {
+            CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason,
+            }
+        })?;
+    write_dealing(out_dir, &shares, None)
+}
+
+fn run(command: &Command) -> CryptoResult<String> {
+    match command {
+        Command::Deal {
+            secret,
+            threshold,
+            shares,
+            out_dir,
+        } => deal(secret, *threshold, *shares, out_dir),
+        Command::Verify {
+            commitments,
+            shares,
+        } => verify(commitments, shares),
+        Command::Recover {
+            commitments,
+            encoding,
+            out,
+            shares,
+        } => recover(commitments.as_deref(), *encoding, out, shares),
+        Command::Refresh {
+            commitments,
+            out_dir,
+            shares,
+        } => refresh(commitments, out_dir, shares),
+        Command::AdjustThreshold {
+            threshold,
+            new_threshold,
+            shares_out,
+            out_dir,
+            shares,
+        } => adjust_threshold(*threshold, *new_threshold, *shares_out, out_dir, shares),
+        Command::Custodian(CustodianCommand::Check(args)) => custodian_check(args),
+    }
+}
+
+fn custodian_check(args: &CheckArgs) -> CryptoResult<String> {
+    let key_hex = zeroize::Zeroizing::new(
+        std::fs::read_to_string(&args.key).map_err(|e| io_error(&args.key, e))?,
+    );
+    let key = SealingKey::from_hex(&key_hex)?;
+    let sealed: SealedShare =
+        serde_json::from_slice(&std::fs::read(&args.share).map_err(|e| io_error(&args.share, e))?)
+            .map_err(|e| CryptoError::Serialization {
+                details: e.to_string(),
+            })?;
+    let commitments = read_commitments(&args.commitments)?;
+    let root = hex::decode(args.root.trim()).map_err(|e| CryptoError::Validation {
+        field: "root".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let challenge = hex::decode(args.challenge.trim()).map_err(|e| CryptoError::Validation {
+        field: "challenge".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let (resealed, attestation) =
+        custodian::check_share(&sealed, &key, &commitments, &root, &challenge)?;
+    let encoded = serde_json::to_vec_pretty(&resealed).map_err(|e| CryptoError::Serialization {
+        details: e.to_string(),
+    })?;
+    write_atomically(&args.share, &encoded)?;
+    serde_json::to_string_pretty(&attestation).map_err(|e| CryptoError::Serialization {
+        details: e.to_string(),
+    })
+}
+
+fn main() -> ExitCode {
+    env_logger::Builder::from_env(Env::default().default_filter_or("warn"))
+        .format_timestamp_secs()
+        .init();
+
+    // Usage errors exit with status 2.
+    let cli = Cli::parse();
+    match run(&cli.command) {
+        Ok(output) => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {} {}", e.code(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Option<Command> {
+        let args = std::iter::once("zkthresh").chain(args.iter().copied());
+        Cli::try_parse_from(args).ok().map(|cli| cli.command)
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_check_args() {
+        let check = [
+            "custodian",
+            "check",
+            "--key",
+            "k",
+            "--share",
+            "s",
+            "--root",
+            "ab",
+            "--commitments",
+            "c",
+            "--challenge",
+            "cd",
+        ];
+        let Some(Command::Custodian(CustodianCommand::Check(parsed))) = parse(&check) else {
+            panic!("custodian check did not parse");
+        };
+        assert_eq!(parsed.share, PathBuf::from("s"));
+        assert_eq!(parsed.root, "ab");
+        assert_eq!(parsed.challenge, "cd");
+
+        assert!(parse(&["custodian", "check", "--share", "s"]).is_none());
+        assert!(parse(&["custodian", "check", "--share"]).is_none());
+        assert!(parse(&[&check[..], &["--extra", "x"]].concat()).is_none());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse(&[
+                "deal",
+                "--secret",
+                "s.hex",
+                "--threshold",
+                "2",
+                "--shares",
+                "3",
+                "--out-dir",
+                "out",
+            ]),
+            Some(Command::Deal {
+                secret: PathBuf::from("s.hex"),
+                threshold: 2,
+                shares: 3,
+                out_dir: PathBuf::from("out"),
+            })
+        );
+        assert_eq!(
+            parse(&["recover", "--out", "s.hex", "a.json", "b.json"]),
+            Some(Command::Recover {
+                commitments: None,
+                encoding: SecretEncoding::Hex,
+                out: PathBuf::from("s.hex"),
+                shares: vec![PathBuf::from("a.json"), PathBuf::from("b.json")],
+            })
+        );
+        assert!(matches!(
+            parse(&["recover", "--encoding", "base64", "--out", "s", "a.json"]),
+            Some(Command::Recover {
+                encoding: SecretEncoding::Base64,
+                ..
+            })
+        ));
+        assert!(parse(&["recover", "--encoding", "pem", "--out", "s", "a.json"]).is_none());
+        assert_eq!(
+            parse(&[
+                "adjust-threshold",
+                "--threshold",
+                "2",
+                "--new-threshold",
+                "3",
+                "--shares",
+                "4",
+                "--out-dir",
+                "o",
+                "a.json",
+            ]),
+            Some(Command::AdjustThreshold {
+                threshold: 2,
+                new_threshold: 3,
+                shares_out: 4,
+                out_dir: PathBuf::from("o"),
+                shares: vec![PathBuf::from("a.json")],
+            })
+        );
+
+        // Missing slices, a non-numeric threshold, a repeated flag and an unknown command.
+        assert!(parse(&["verify", "--commitments", "c"]).is_none());
+        assert!(parse(&[
+            "deal",
+            "--secret",
+            "s",
+            "--threshold",
+            "two",
+            "--shares",
+            "3",
+            "--out-dir",
+            "o",
+        ])
+        .is_none());
+        assert!(parse(&[
+            "verify",
+            "--commitments",
+            "c",
+            "--commitments",
+            "d",
+            "a.json",
+        ])
+        .is_none());
+        assert!(parse(&["combine", "a.json"]).is_none());
+    }
+
+    #[test]
+    fn test_deal_verify_refresh_recover() {
+        let dir = std::env::temp_dir().join(format!("zk-thresh-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let secret = Scalar::from(1234u64);
+        let secret_file = dir.join("secret.hex");
+        std::fs::write(&secret_file, hex::encode(secret.to_bytes())).unwrap();
+
+        let dealt = dir.join("dealt");
+        let root = deal(&secret_file, 2, 3, &dealt).unwrap();
+        let commitments = dealt.join("commitments.json");
+        let slices = |d: &Path, indices: &[usize]| -> Vec<PathBuf> {
+            indices
+                .iter()
+                .map(|i| d.join(format!("share-{}.json", i)))
+                .collect()
+        };
+        assert_eq!(
+            verify(&commitments, &slices(&dealt, &[1, 2, 3])).unwrap(),
+            root
+        );
+
+        let refreshed = dir.join("refreshed");
+        refresh(&commitments, &refreshed, &slices(&dealt, &[1, 2, 3])).unwrap();
+        let new_commitments = refreshed.join("commitments.json");
+        // Old slices do not match the refreshed commitments.
+        assert!(verify(&new_commitments, &slices(&dealt, &[1])).is_err());
+
+        let out = dir.join("recovered.hex");
+        let hex = SecretEncoding::Hex;
+        recover(
+            Some(&new_commitments),
+            hex,
+            &out,
+            &slices(&refreshed, &[1, 3]),
+        )
+        .unwrap();
+        assert_eq!(read_scalar(&out).unwrap(), secret);
+        assert!(recover(Some(&new_commitments), hex, &out, &slices(&refreshed, &[2])).is_err());
+
+        let adjusted = dir.join("adjusted");
+        adjust_threshold(2, 3, 4, &adjusted, &slices(&refreshed, &[1, 2])).unwrap();
+        recover(None, hex, &out, &slices(&adjusted, &[2, 3, 4])).unwrap();
+        assert_eq!(read_scalar(&out).unwrap(), secret);
+        let raw = dir.join("recovered.bin");
+        recover(
+            None,
+            SecretEncoding::Raw,
+            &raw,
+            &slices(&adjusted, &[1, 2, 4]),
+        )
+        .unwrap();
+        assert_eq!(std::fs::read(&raw).unwrap(), secret.to_bytes());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,559 @@
+//! **bip340 module**
+//!
+//! FROST two-round threshold Schnorr signatures over secp256k1 that verify as BIP-340 signatures,
+//! so a signing set of slice holders can spend from a Bitcoin Taproot key no single machine
+//! holds (`secp256k1` feature).
+//!
+//! Slices are [`KeyShare`]s of a [`deal`](ciphersuite::deal) over [`Secp256k1`]; the
+//! coordinator's [`PublicKeyPackage`] holds their verification shares `Y_i = s_i·G` and the group
+//! key `Y`. Everyone derives the same [`XOnlyKey`] to sign for: the group key itself
+//! ([`XOnlyKey::new`]) or the BIP-341 output key `Q = P + t·G` of a Taproot output with internal
+//! key `P` and an optional script tree ([`XOnlyKey::taproot`]). BIP-340 only knows keys and nonce
+//! points with even `y`, so the signers fold the sign flips of `P` and `Q` into their slices and
+//! the coordinator adds the tweak, and the signers negate their nonces when the group commitment
+//! `R` has odd `y`.
+//!
+//! The rounds are the FROST core of [`crate::frost::ciphersuite`] instantiated with
+//! [`Secp256k1Bip340`]: [`commit`], a [`SigningPackage`] of the commitments and the message,
+//! [`sign`] and [`aggregate`], with `z_i = ±(d_i + ρ_i·e_i) + λ_i·c·(±s_i)` and
+//! the BIP-340 challenge `c = H_BIP0340/challenge(R.x, Q.x, m)`. Binding factors and nonces use
+//! tagged SHA-256 hashes of their own, and the result is the 64-byte [`Signature`] `(R.x, z)`
+//! that any BIP-340 verifier, Bitcoin Core included, accepts for `Q.x`.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::frost::ciphersuite::{self, Ciphersuite};
+use crate::group::secp256k1::{Point, Scalar, Secp256k1};
+use crate::hash_adapter::sha256;
+use zeroize::Zeroize;
+
+const NONCE_TAG: &str = "zk-thresh-pro/frost-bip340/nonce";
+const MESSAGE_TAG: &str = "zk-thresh-pro/frost-bip340/message";
+const COMMITMENT_LIST_TAG: &str = "zk-thresh-pro/frost-bip340/commitment-list";
+const BINDING_TAG: &str = "zk-thresh-pro/frost-bip340/binding";
+
+/// `SHA256(SHA256(tag) || SHA256(tag) || parts)`, the tagged hash of BIP-340.
+pub fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = sha256::digest(&[tag.as_bytes()]);
+    let mut hasher = sha256::Sha256::new();
+    hasher.update(&tag_hash);
+    hasher.update(&tag_hash);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+/// The BIP-340 challenge `H_BIP0340/challenge(r, pk, m) mod n`.
+fn challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> Scalar {
+    Scalar::from_bytes_reduced(&tagged_hash("BIP0340/challenge", &[r, public_key, message]))
+}
+
+/// `-scalar` if `negate`, else `scalar`.
+fn conditional_negate(scalar: Scalar, negate: bool) -> Scalar {
+    if negate {
+        -scalar
+    } else {
+        scalar
+    }
+}
+
+/// A 64-byte BIP-340 signature `(R.x, z)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: [u8; 32],
+    pub s: Scalar,
+}
+
+impl Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// Decode [`Self::to_bytes`]; returns `None` if `s ≥ n`.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        Some(Self {
+            r: bytes[..32].try_into().unwrap(),
+            s: Scalar::from_bytes(bytes[32..].try_into().unwrap())?,
+        })
+    }
+
+    /// BIP-340 verification of `message` under the x-only `public_key`.
+    pub fn verify(&self, public_key: &[u8; 32], message: &[u8]) -> bool {
+        let Some(point) = Point::lift_x(public_key) else {
+            return false;
+        };
+        let e = challenge(&self.r, public_key, message);
+        let r = Point::generator() * self.s - point * e;
+        r.has_even_y() && r.x_only() == Some(self.r)
+    }
+}
+
+/// The x-only key a signing session signs for, with what the signers and the coordinator add to
+/// the group key to reach it.
+///
+/// The signing key of `Q` is `share_factor·s + tweak`, `share_factor = ±1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XOnlyKey {
+    output_key: Point,
+    share_factor_negated: bool,
+    tweak: Scalar,
+}
+
+impl XOnlyKey {
+    /// The group key `Y` itself, negated if its `y` is odd.
+    ///
+    /// Returns `CryptoError::Validation` for the identity.
+    pub fn new(group_public_key: &Point) -> CryptoResult<Self> {
+        Self::check(group_public_key)?;
+        let negated = !group_public_key.has_even_y();
+        Ok(Self {
+            output_key: if negated {
+                -*group_public_key
+            } else {
+                *group_public_key
+            },
+            share_factor_negated: negated,
+            tweak: Scalar::ZERO,
+        })
+    }
+
+    /// The BIP-341 Taproot output key for internal key `Y`:
+    /// `Q = P + H_TapTweak(P.x || merkle_root)·G` with `P` the even lift of `Y`. Without a script
+    /// tree (`merkle_root = None`) the tweak hashes `P.x` alone, as BIP-86 key-path-only outputs
+    /// do.
+    ///
+    /// Returns `CryptoError::Validation` for the identity and `CryptoError::CryptographicOperation`
+    /// in the negligible case that the tweak is not below `n` or `Q` is the identity.
+    pub fn taproot(group_public_key: &Point, merkle_root: Option<&[u8; 32]>) -> CryptoResult<Self> {
+        let internal = Self::new(group_public_key)?;
+        let internal_x = internal.to_bytes();
+        let root: &[u8] = merkle_root.map_or(&[], |r| &r[..]);
+        let tweak = Scalar::from_bytes(&tagged_hash("TapTweak", &[&internal_x, root])).ok_or_else(
+            || CryptoError::CryptographicOperation {
+                operation: "taproot tweak".to_string(),
+            },
+        )?;
+        let output = internal.output_key + Point::generator() * tweak;
+        if output.is_identity() {
+            return Err(CryptoError::CryptographicOperation {
+                operation: "taproot tweak".to_string(),
+            });
+        }
+        let negated = !output.has_even_y();
+        Ok(Self {
+            output_key: if negated { -output } else { output },
+            share_factor_negated: internal.share_factor_negated != negated,
+            tweak: conditional_negate(tweak, negated),
+        })
+    }
+
+    fn check(group_public_key: &Point) -> CryptoResult<()> {
+        if group_public_key.is_identity() {
+            return Err(CryptoError::Validation {
+                field: "group_public_key".to_string(),
+                reason: "the identity is not a public key".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The 32-byte x-only key of BIP-340, the witness program of a Taproot output.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.output_key
+            .x_only()
+            .expect("output keys are not the identity")
+    }
+}
+
+/// FROST over secp256k1 with tagged SHA-256 hashes, signing BIP-340 [`Signature`]s for an
+/// [`XOnlyKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256k1Bip340;
+
+impl Ciphersuite for Secp256k1Bip340 {
+    type Group = Secp256k1;
+    type Key = XOnlyKey;
+    type Signature = Signature;
+
+    const NAME: &'static str = "bip340";
+
+    fn key_bytes(key: &XOnlyKey) -> Vec<u8> {
+        key.to_bytes().to_vec()
+    }
+
+    fn negates_shares(key: &XOnlyKey) -> bool {
+        key.share_factor_negated
+    }
+
+    fn tweak(key: &XOnlyKey) -> Scalar {
+        key.tweak
+    }
+
+    fn identifier(index: usize) -> Vec<u8> {
+        (index as u64).to_be_bytes().to_vec()
+    }
+
+    fn nonce(randomness: &[u8; 32], secret: &Scalar) -> Scalar {
+        let mut secret_bytes = secret.to_bytes();
+        let mut digest = tagged_hash(NONCE_TAG, &[randomness, &secret_bytes]);
+        let nonce = Scalar::from_bytes_reduced(&digest);
+        secret_bytes.zeroize();
+        digest.zeroize();
+        nonce
+    }
+
+    fn hash_message(message: &[u8]) -> Vec<u8> {
+        tagged_hash(MESSAGE_TAG, &[message]).to_vec()
+    }
+
+    fn hash_commitment_list(list: &[u8]) -> Vec<u8> {
+        tagged_hash(COMMITMENT_LIST_TAG, &[list]).to_vec()
+    }
+
+    fn binding_factor(
+        key: &[u8],
+        message_hash: &[u8],
+        list_hash: &[u8],
+        identifier: &[u8],
+    ) -> Scalar {
+        Scalar::from_bytes_reduced(&tagged_hash(
+            BINDING_TAG,
+            &[key, message_hash, list_hash, identifier],
+        ))
+    }
+
+    /// BIP-340 only signs with an even `R`: an odd one negates every nonce.
+    fn check_group_commitment(commitment: &Point) -> CryptoResult<bool> {
+        if commitment.x_only().is_none() {
+            return Err(CryptoError::CryptographicOperation {
+                operation: "bip340 group commitment".to_string(),
+            });
+        }
+        Ok(!commitment.has_even_y())
+    }
+
+    fn challenge(commitment: &Point, key: &XOnlyKey, message: &[u8]) -> Scalar {
+        let r = commitment
+            .x_only()
+            .expect("checked by check_group_commitment");
+        challenge(&r, &key.to_bytes(), message)
+    }
+
+    fn signature(commitment: &Point, response: Scalar) -> Signature {
+        Signature {
+            r: commitment
+                .x_only()
+                .expect("checked by check_group_commitment"),
+            s: response,
+        }
+    }
+
+    fn verify(signature: &Signature, key: &XOnlyKey, message: &[u8]) -> bool {
+        signature.verify(&key.to_bytes(), message)
+    }
+}
+
+/// A slice of a [`deal`](ciphersuite::deal) over [`Secp256k1`].
+pub type KeyShare = ciphersuite::KeyShare<Secp256k1>;
+/// Round-one message: the public nonce commitments of one participant.
+pub type SigningCommitments = ciphersuite::SigningCommitments<Secp256k1Bip340>;
+/// Secret nonces behind a participant's [`SigningCommitments`]; consumed by [`sign`].
+#[cfg(feature = "dealer")]
+pub type SigningNonces = ciphersuite::SigningNonces<Secp256k1Bip340>;
+/// Coordinator message opening round two.
+pub type SigningPackage = ciphersuite::SigningPackage<Secp256k1Bip340>;
+/// Round-two message: one participant's signature share `z_i`.
+pub type SignatureShare = ciphersuite::SignatureShare<Secp256k1Bip340>;
+/// Group public key and verification shares, for the coordinator.
+pub type PublicKeyPackage = ciphersuite::PublicKeyPackage<Secp256k1Bip340>;
+/// Verification share `Y_i = s_i·G` of one participant.
+pub type VerificationShare = ciphersuite::VerificationShare<Secp256k1Bip340>;
+
+impl PublicKeyPackage {
+    /// Dealer-side package for the slices of one dealing, after checking each against
+    /// `commitments` with [`KeyShare::verify`](ciphersuite::KeyShare::verify).
+    ///
+    /// The group key is `Σ λ_i·Y_i` over every slice given, so all of them (or any threshold)
+    /// must be passed. Returns `CryptoError::SecurityViolation` if a slice does not verify and
+    /// `CryptoError::Validation` for no slices, a repeated index or an identity group key.
+    pub fn from_shares(shares: &[KeyShare], commitments: &[Point]) -> CryptoResult<Self> {
+        for share in shares {
+            share.verify(commitments)?;
+        }
+        Self::from_signing_shares(shares)
+    }
+}
+
+/// Round one: draw hiding and binding nonces for `share`.
+///
+/// Each nonce hashes fresh randomness together with the slice, so a weak generator alone does not
+/// leak the slice. Returns `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn commit(share: &KeyShare) -> CryptoResult<(SigningNonces, SigningCommitments)> {
+    ciphersuite::commit(share)
+}
+
+/// Round two: the signature share of `share` for `package`, signing for `key`; see
+/// [`ciphersuite::sign`].
+#[cfg(feature = "dealer")]
+pub fn sign(
+    share: &KeyShare,
+    nonces: SigningNonces,
+    package: &SigningPackage,
+    key: &XOnlyKey,
+) -> CryptoResult<SignatureShare> {
+    ciphersuite::sign(share, nonces, package, key)
+}
+
+/// Check one signature share: `z_i·G = ±(D_i + ρ_i·E_i) + λ_i·c·(±Y_i)`.
+pub fn verify_signature_share(
+    signature_share: &SignatureShare,
+    verification_share: &Point,
+    package: &SigningPackage,
+    key: &XOnlyKey,
+) -> bool {
+    ciphersuite::verify_signature_share(signature_share, verification_share, package, key)
+}
+
+/// Coordinator: check every signature share and combine them, with the tweak of `key`, into a
+/// BIP-340 signature for `key`; see [`ciphersuite::aggregate`].
+pub fn aggregate(
+    package: &SigningPackage,
+    signature_shares: &[SignatureShare],
+    public_keys: &PublicKeyPackage,
+    key: &XOnlyKey,
+) -> CryptoResult<Signature> {
+    ciphersuite::aggregate(package, signature_shares, public_keys, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes<const N: usize>(hex_str: &str) -> [u8; N] {
+        hex::decode(hex_str).unwrap().try_into().unwrap()
+    }
+
+    /// Vectors 0 to 14 of BIP-340's `test-vectors.csv`, with the comment column for the failures;
+    /// every failure must be rejected, by `from_bytes` for `s ≥ n` or by `verify` otherwise.
+    #[test]
+    fn test_bip340_verifies_reference_vectors() {
+        let cases = [
+            (
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+                 25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+                true,
+                "",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+                 8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+                true,
+                "",
+            ),
+            (
+                "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+                "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+                "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1b\
+                 ab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+                true,
+                "",
+            ),
+            (
+                "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec\
+                 97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+                true,
+                "test fails if msg is reduced modulo p or n",
+            ),
+            (
+                "d69c3509bb99e412e68b0fe8544e72837dfa30746d8be2aa65975f29d22dc7b9",
+                "4df3c3f68fcc83b27e9d42c90431a72499f17875c81a599b566c9889b9696703",
+                "00000000000000000000003b78ce563f89a0ed9414f5aa28ad0d96d6795f9c63\
+                 76afb1548af603b3eb45c9f8207dee1060cb71c04e80f593060b07d28308d7f4",
+                true,
+                "",
+            ),
+            (
+                "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+                "public key not on the curve",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556\
+                 3cc27944640ac607cd107ae10923d9ef7a73c643e166be5ebeafa34b1ac553e2",
+                false,
+                "has_even_y(R) is false",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "1fa62e331edbc21c394792d2ab1100a7b432b013df3f6ff4f99fcb33e0e1515f\
+                 28890b3edb6e7189b630448b515ce4f8622a954cfe545735aaea5134fccdb2bd",
+                false,
+                "negated message",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 961764b3aa9b2ffcb6ef947b6887a226e8d7c93e00c5ed0c1834ff0d0c2e6da6",
+                false,
+                "negated s value",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "0000000000000000000000000000000000000000000000000000000000000000\
+                 123dda8328af9c23a94c1feecfd123ba4fb73476f0d594dcb65c6425bd186051",
+                false,
+                "sG - eP is infinite",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "0000000000000000000000000000000000000000000000000000000000000001\
+                 7615fbaf5ae28864013c099742deadb4dba87f11ac6754f93780d5a1837cf197",
+                false,
+                "sG - eP is infinite",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "4a298dacae57395a15d0795ddbfd1dcb564da82b0f269bc70a74f8220429ba1d\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+                "sig[0:32] is not an X coordinate on the curve",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+                "sig[0:32] is equal to field size",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                false,
+                "sig[32:64] is equal to curve order",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+                "public key exceeds field size",
+            ),
+        ];
+        for (vector, (public_key, message, signature, valid, comment)) in cases.iter().enumerate() {
+            let public_key = bytes::<32>(public_key);
+            let message = bytes::<32>(message);
+            let signature = Signature::from_bytes(&bytes(&signature.replace(' ', "")));
+            let verified = signature.is_some_and(|s| s.verify(&public_key, &message));
+            assert_eq!(verified, *valid, "vector {}: {}", vector, comment);
+            if let (true, Some(signature)) = (valid, signature) {
+                let mut wrong = message;
+                wrong[0] ^= 1;
+                assert!(!signature.verify(&public_key, &wrong));
+            }
+        }
+    }
+
+    /// The first key-path-only output of BIP-86's test vectors.
+    #[test]
+    fn test_taproot_output_key_matches_bip86() {
+        let internal = Point::lift_x(&bytes(
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+        ))
+        .unwrap();
+        let key = XOnlyKey::taproot(&internal, None).unwrap();
+        assert_eq!(
+            hex::encode(key.to_bytes()),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert_eq!(
+            XOnlyKey::new(&-internal).unwrap().to_bytes(),
+            XOnlyKey::new(&internal).unwrap().to_bytes()
+        );
+        assert!(XOnlyKey::new(&Point::IDENTITY).is_err());
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_threshold_bip340_signing() {
+        let secret = crate::group::random_scalar::<Secp256k1>().unwrap();
+        let (shares, commitments) = ciphersuite::deal::<Secp256k1>(secret, 2, 3).unwrap();
+        let public_keys = PublicKeyPackage::from_shares(&shares, &commitments).unwrap();
+        assert_eq!(public_keys.group_public_key, Point::generator() * secret);
+        let merkle_root = [0x5a; 32];
+        let message = b"spend 0.5 BTC to cold storage";
+
+        // Enough rounds that the key, tweaked key and nonce parities all occur.
+        for round in 0..6 {
+            let key = match round % 3 {
+                0 => XOnlyKey::new(&public_keys.group_public_key),
+                1 => XOnlyKey::taproot(&public_keys.group_public_key, None),
+                _ => XOnlyKey::taproot(&public_keys.group_public_key, Some(&merkle_root)),
+            }
+            .unwrap();
+            let signers = [&shares[round % 3], &shares[(round + 1) % 3]];
+            let (nonces, round_one): (Vec<_>, Vec<_>) =
+                signers.iter().map(|s| commit(s).unwrap()).unzip();
+            let package = SigningPackage::new(message, round_one).unwrap();
+            let signature_shares: Vec<SignatureShare> = signers
+                .iter()
+                .zip(nonces)
+                .map(|(s, n)| sign(s, n, &package, &key).unwrap())
+                .collect();
+            for share in &signature_shares {
+                let y = public_keys.verification_share(share.index).unwrap();
+                assert!(verify_signature_share(share, &y, &package, &key));
+            }
+            let signature = aggregate(&package, &signature_shares, &public_keys, &key).unwrap();
+            assert!(signature.verify(&key.to_bytes(), message));
+            assert_eq!(
+                Signature::from_bytes(&signature.to_bytes()),
+                Some(signature)
+            );
+
+            let mut forged = signature_shares.clone();
+            forged[0].response = forged[0].response + Scalar::ONE;
+            assert!(matches!(
+                aggregate(&package, &forged, &public_keys, &key),
+                Err(CryptoError::SecurityViolation { .. })
+            ));
+        }
+
+        // Nonces are tied to their slice and package.
+        let (nonces, round_one) = commit(&shares[0]).unwrap();
+        let (_, other) = commit(&shares[1]).unwrap();
+        let key = XOnlyKey::new(&public_keys.group_public_key).unwrap();
+        let package = SigningPackage::new(message, vec![other, round_one]).unwrap();
+        assert_eq!(package.signers(), vec![1, 2]);
+        assert!(sign(&shares[2], nonces, &package, &key).is_err());
+        let json = serde_json::to_string(&package).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SigningPackage>(&json).unwrap(),
+            package
+        );
+        assert!(SigningPackage::new(message, vec![round_one, round_one]).is_err());
+    }
+}
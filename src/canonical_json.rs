@@ -0,0 +1,309 @@
+//! **canonical JSON module**
+//!
+//! A canonical JSON encoding of any serializable artifact (slices, ceremony transcripts, audit
+//! records), for partners that sign or hash the JSON text itself. The encoding follows the JSON
+//! Canonicalization Scheme (RFC 8785):
+//!
+//! - no whitespace;
+//! - object members sorted by the UTF-16 code units of their names;
+//! - strings with only `"`, `\` and the control characters escaped, using `\b \t \n \f \r` where
+//!   they exist and lowercase `\u00xx` otherwise; everything else, non-ASCII included, unescaped;
+//! - non-integral numbers in the ECMAScript `Number.prototype.toString` form (shortest
+//!   round-trip digits, exponent from `1e21` and below `1e-6`), `-0` written as `0`.
+//!
+//! One departure: integers are written exactly in decimal, where RFC 8785 would round those beyond
+//! 2⁵³ to the nearest double. No artifact of the crate carries such values in practice, and the
+//! exact form keeps `u64` fields lossless.
+//!
+//! The encoding round-trips: [`from_slice`] accepts exactly the bytes [`to_vec`] produces, and
+//! re-encoding a decoded artifact gives the same bytes. Which form an output takes is chosen with
+//! [`JsonFormat`]; the audit chain hash keeps hashing the compact, emitted-order JSON of each record
+//! (see [`audit`](crate::audit)), so chains stay verifiable whichever form they are persisted in.
+
+use crate::error::{CryptoError, CryptoResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Textual form of JSON output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonFormat {
+    /// `serde_json` compact output, fields in declaration order.
+    #[default]
+    Compact,
+    /// `serde_json` pretty output, fields in declaration order.
+    Pretty,
+    /// The canonical encoding of this module.
+    Canonical,
+}
+
+impl JsonFormat {
+    /// Encode `value` in this form.
+    pub fn to_vec<T: Serialize + ?Sized>(self, value: &T) -> CryptoResult<Vec<u8>> {
+        match self {
+            JsonFormat::Compact => serde_json::to_vec(value).map_err(json_error),
+            JsonFormat::Pretty => serde_json::to_vec_pretty(value).map_err(json_error),
+            JsonFormat::Canonical => to_vec(value),
+        }
+    }
+}
+
+fn json_error(e: serde_json::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: e.to_string(),
+    }
+}
+
+/// Canonical JSON of `value`.
+///
+/// Returns `CryptoError::Serialization` if `value` does not serialize to JSON.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> CryptoResult<Vec<u8>> {
+    let value = serde_json::to_value(value).map_err(json_error)?;
+    let mut out = Vec::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+/// Canonical JSON of `value`, as a string.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> CryptoResult<String> {
+    // The writer only emits UTF-8.
+    Ok(String::from_utf8(to_vec(value)?).expect("canonical JSON is UTF-8"))
+}
+
+/// Whether `bytes` is canonical JSON.
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    serde_json::from_slice::<Value>(bytes)
+        .ok()
+        .and_then(|value| to_vec(&value).ok())
+        .is_some_and(|encoded| encoded == bytes)
+}
+
+/// Decode `T` from canonical JSON.
+///
+/// Returns `CryptoError::Validation` if `bytes` is JSON but not in canonical form (whitespace,
+/// member order, duplicate names, escapes or number spellings), and `CryptoError::Serialization`
+/// if it does not parse as `T`.
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> CryptoResult<T> {
+    let value: Value = serde_json::from_slice(bytes).map_err(json_error)?;
+    if to_vec(&value)? != bytes {
+        return Err(CryptoError::Validation {
+            field: "json".to_string(),
+            reason: "not in canonical form".to_string(),
+        });
+    }
+    serde_json::from_value(value).map_err(json_error)
+}
+
+/// Decode `T` from a canonical JSON string.
+pub fn from_str<T: DeserializeOwned>(s: &str) -> CryptoResult<T> {
+    from_slice(s.as_bytes())
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) -> CryptoResult<()> {
+    match value {
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(b) => out.extend_from_slice(if *b { b"true" } else { b"false" }),
+        Value::Number(n) => write_number(n, out),
+        Value::String(s) => write_string(s, out)?,
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push(b'{');
+            for (i, (name, member)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_string(name, out)?;
+                out.push(b':');
+                write_value(member, out)?;
+            }
+            out.push(b'}');
+        }
+    }
+    Ok(())
+}
+
+fn write_string(s: &str, out: &mut Vec<u8>) -> CryptoResult<()> {
+    // serde_json escapes exactly as RFC 8785 requires.
+    serde_json::to_writer(out, s).map_err(json_error)
+}
+
+fn write_number(n: &serde_json::Number, out: &mut Vec<u8>) {
+    let text = match (n.as_u64(), n.as_i64(), n.as_f64()) {
+        (Some(u), _, _) => u.to_string(),
+        (_, Some(i), _) => i.to_string(),
+        // `serde_json` only holds finite floats, writing NaN and infinities as `null`.
+        (_, _, Some(f)) => ecmascript_number(f),
+        (None, None, None) => unreachable!("a JSON number is an integer or a float"),
+    };
+    out.extend_from_slice(text.as_bytes());
+}
+
+/// `Number.prototype.toString` of a finite `f` (ECMA-262, section 6.1.6.1.20).
+fn ecmascript_number(f: f64) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    // `{:e}` gives the shortest round-trip digits d.ddd and the exponent.
+    let scientific = format!("{:e}", f.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("`{:e}` has an exponent");
+    let digits: String = mantissa.chars().filter(|&c| c != '.').collect();
+    let k = digits.len() as i32;
+    // The value is 0.digits × 10^n.
+    let n = exponent
+        .parse::<i32>()
+        .expect("`{:e}` exponent is an integer")
+        + 1;
+    let sign = if f < 0.0 { "-" } else { "" };
+    let body = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let exponent_sign = if n > 0 { '+' } else { '-' };
+        let fraction = if k == 1 {
+            String::new()
+        } else {
+            format!(".{}", &digits[1..])
+        };
+        format!(
+            "{}{}e{}{}",
+            &digits[..1],
+            fraction,
+            exponent_sign,
+            (n - 1).abs()
+        )
+    };
+    format!("{}{}", sign, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc8785_ordering_escapes_and_numbers() {
+        // Sample from RFC 8785, section 3.2.3 (member order by UTF-16 code units).
+        let value = serde_json::json!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{0080}": "Control",
+            "\u{00f6}": "Latin Small Letter O With Diaeresis"
+        });
+        assert_eq!(
+            to_string(&value).unwrap(),
+            "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{0080}\":\"Control\",\
+             \"\u{00f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\
+             \"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+        );
+        assert_eq!(
+            to_string(&"\u{0f}\u{08}\"\\/\u{7f}é").unwrap(),
+            "\"\\u000f\\b\\\"\\\\/\u{7f}é\""
+        );
+
+        // Number samples from RFC 8785, appendix B.
+        for (f, expected) in [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (5e-324, "5e-324"),
+            (1.7976931348623157e308, "1.7976931348623157e+308"),
+            (9007199254740994.0, "9007199254740994"),
+            (295147905179352830000.0, "295147905179352830000"),
+            (1e21, "1e+21"),
+            (1e-7, "1e-7"),
+            (0.000001, "0.000001"),
+            (333333333.3333333, "333333333.3333333"),
+            (-1.5e-10, "-1.5e-10"),
+            (4.5, "4.5"),
+        ] {
+            assert_eq!(ecmascript_number(f), expected, "{}", f);
+        }
+        assert_eq!(to_string(&[u64::MAX]).unwrap(), "[18446744073709551615]");
+    }
+
+    #[test]
+    fn test_strict_decoding() {
+        for rejected in [
+            r#"{"b":1,"a":2}"#,
+            r#"{"a":1,"a":1}"#,
+            r#"{"a": 1}"#,
+            r#"{"a":1.0}"#,
+            r#"{"a":"\u0041"}"#,
+        ] {
+            assert!(!is_canonical(rejected.as_bytes()), "{}", rejected);
+            assert!(matches!(
+                from_str::<Value>(rejected),
+                Err(CryptoError::Validation { .. })
+            ));
+        }
+        assert!(is_canonical(br#"{"a":1,"b":[true,null,"x"]}"#));
+        assert!(matches!(
+            from_str::<Value>("{"),
+            Err(CryptoError::Serialization { .. })
+        ));
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_shares_transcripts_and_audit_records_round_trip() {
+        use crate::context::OperationContext;
+        use crate::error::{AuditRecord, SecurityEvent};
+        use crate::transcript::{CeremonyKind, CeremonyTranscript};
+
+        fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+            let encoded = to_vec(value).unwrap();
+            assert!(is_canonical(&encoded));
+            let decoded: T = from_slice(&encoded).unwrap();
+            assert_eq!(to_vec(&decoded).unwrap(), encoded);
+            // The emitted-order forms hold the same data but are not canonical.
+            for format in [JsonFormat::Compact, JsonFormat::Pretty] {
+                let other = format.to_vec(value).unwrap();
+                assert_ne!(other, encoded);
+                assert!(matches!(
+                    from_slice::<T>(&other),
+                    Err(CryptoError::Validation { .. })
+                ));
+                let reparsed: Value = serde_json::from_slice(&other).unwrap();
+                assert_eq!(to_vec(&reparsed).unwrap(), encoded);
+            }
+            decoded
+        }
+
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::from(7u64), 2, 3)
+                .unwrap();
+        assert_eq!(*round_trip(&shares[0]).to_bytes(), *shares[0].to_bytes());
+
+        let context = OperationContext::new("k", 0, "op-deal");
+        let transcript = CeremonyTranscript::new(CeremonyKind::Dealing, &context, 2, &shares);
+        assert_eq!(
+            round_trip(&transcript).canonical_bytes().unwrap(),
+            transcript.canonical_bytes().unwrap()
+        );
+
+        let record = AuditRecord {
+            context: Some(context),
+            event: SecurityEvent::KeyGenerated {
+                key_id: "k".to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+        };
+        assert_eq!(round_trip(&record).fields(), record.fields());
+    }
+}
@@ -0,0 +1,674 @@
+//! **ceremony module**
+//!
+//! [`Ceremony`]: bookkeeping of one multi-round protocol run (a DKG, a refresh or a threshold
+//! signing) between a fixed set of participants, so integrators do not have to rebuild it around
+//! every protocol.
+//!
+//! A ceremony runs as a sequence of rounds. [`Ceremony::open_round`] starts a round with a time
+//! budget and expects a message from every participant still in the ceremony;
+//! [`Ceremony::record`] (or [`Ceremony::record_signed`] for [`Signed`] messages) notes that a
+//! participant answered, keeping a digest of the canonical JSON of its message, not the message
+//! itself, so dealt slices never reach the store. Once the round deadline has passed,
+//! [`Ceremony::check_deadline`] and [`Ceremony::record`] fail with `CryptoError::Timeout`, and
+//! [`Ceremony::close_round`] drops the participants that did not answer from later rounds. The
+//! ceremony fails, also with `CryptoError::Timeout`, once fewer than the threshold remain.
+//! [`Ceremony::finish`] produces the [`ProtocolTranscript`] of the run.
+//!
+//! The protocol messages themselves are handled by the caller ([`crate::dkg`],
+//! [`crate::frost`], ...); the ceremony only records who answered what, and when.
+//!
+//! Every change is written to a [`CeremonyStore`] before the call returns, so a coordinator that
+//! restarts continues with [`Ceremony::resume`] where it stopped: round deadlines are wall-clock
+//! times, and a participant re-sending a message already recorded is accepted again.
+//!
+//! - [`MemoryCeremonyStore`] for tests and single-run tools.
+//! - [`FileCeremonyStore`] keeps one JSON file per ceremony in a directory, replaced atomically.
+
+use crate::canonical_json;
+use crate::context::OperationContext;
+use crate::counters::{io_error, write_atomically};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Roster, Signed, SignedPayload};
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Version of the [`CeremonyState`] and [`ProtocolTranscript`] formats.
+pub const CEREMONY_STATE_VERSION: u8 = 1;
+
+const RESPONSE_CONTEXT: &str = "zk-thresh-pro 2024 ceremony response v1";
+const TRANSCRIPT_CONTEXT: &str = "zk-thresh-pro 2024 protocol transcript v1";
+
+/// Protocol a ceremony runs.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolKind {
+    Dkg,
+    Refresh,
+    Signing,
+}
+
+/// Stage of a ceremony.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CeremonyPhase {
+    Running,
+    Completed,
+    /// Fewer participants than the threshold answered a round in time.
+    Failed,
+}
+
+/// A message recorded from one participant.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CeremonyResponse {
+    pub participant: usize,
+    /// Keyed BLAKE3 digest of the canonical JSON of the message.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub digest: Vec<u8>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub received_at: DateTime<Utc>,
+}
+
+/// One round of a ceremony.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CeremonyRound {
+    pub name: String,
+    /// Participants expected to answer, in increasing order.
+    pub expected: Vec<usize>,
+    /// Messages recorded, in arrival order.
+    pub responses: Vec<CeremonyResponse>,
+    /// Time budget the round was opened with.
+    pub timeout_ms: u64,
+    /// Deadline of the round, rounded up to whole seconds.
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+    /// Expected participants that had not answered when the round was closed.
+    pub missing: Vec<usize>,
+    pub closed: bool,
+}
+
+impl CeremonyRound {
+    /// Expected participants that have not answered yet.
+    pub fn pending(&self) -> Vec<usize> {
+        self.expected
+            .iter()
+            .copied()
+            .filter(|p| !self.responses.iter().any(|r| r.participant == *p))
+            .collect()
+    }
+
+    /// Whether the deadline has passed.
+    pub fn expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+/// Persisted state of a ceremony, as kept by a [`CeremonyStore`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CeremonyState {
+    /// Record format version, [`CEREMONY_STATE_VERSION`].
+    pub version: u8,
+    pub kind: ProtocolKind,
+    /// Context of the run; its operation id names the ceremony in the store.
+    pub context: OperationContext,
+    pub threshold: usize,
+    /// Participants still in the ceremony, in increasing order.
+    pub participants: Vec<usize>,
+    pub rounds: Vec<CeremonyRound>,
+    pub phase: CeremonyPhase,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub started_at: DateTime<Utc>,
+}
+
+/// Final record of a completed ceremony.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolTranscript {
+    /// Format version, [`CEREMONY_STATE_VERSION`].
+    pub version: u8,
+    pub kind: ProtocolKind,
+    pub context: OperationContext,
+    pub threshold: usize,
+    /// Participants that answered every round, in increasing order.
+    pub participants: Vec<usize>,
+    pub rounds: Vec<CeremonyRound>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub started_at: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub completed_at: DateTime<Utc>,
+}
+
+impl ProtocolTranscript {
+    /// Digest of the canonical JSON of the transcript, for participants to compare or sign.
+    pub fn digest(&self) -> CryptoResult<[u8; 32]> {
+        Ok(digest(TRANSCRIPT_CONTEXT, &canonical_json::to_vec(self)?))
+    }
+}
+
+fn digest(context: &str, bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(context);
+    digest::Update::update(&mut hasher, bytes);
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    out
+}
+
+/// Durable storage of ceremony states, one per operation id.
+pub trait CeremonyStore {
+    /// The state of the ceremony `operation_id`, if any.
+    fn load(&self, operation_id: &str) -> CryptoResult<Option<CeremonyState>>;
+
+    /// Durably replace the state of `state.context.operation_id`.
+    fn save(&mut self, state: &CeremonyState) -> CryptoResult<()>;
+
+    /// Forget the ceremony `operation_id`; removing an absent ceremony is not an error.
+    fn remove(&mut self, operation_id: &str) -> CryptoResult<()>;
+}
+
+/// Ceremony states held in process memory; they do not survive a restart.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCeremonyStore {
+    states: Vec<CeremonyState>,
+}
+
+impl MemoryCeremonyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CeremonyStore for MemoryCeremonyStore {
+    fn load(&self, operation_id: &str) -> CryptoResult<Option<CeremonyState>> {
+        Ok(self
+            .states
+            .iter()
+            .find(|s| s.context.operation_id == operation_id)
+            .cloned())
+    }
+
+    fn save(&mut self, state: &CeremonyState) -> CryptoResult<()> {
+        self.remove(&state.context.operation_id)?;
+        self.states.push(state.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, operation_id: &str) -> CryptoResult<()> {
+        self.states
+            .retain(|s| s.context.operation_id != operation_id);
+        Ok(())
+    }
+}
+
+/// Ceremony states kept as JSON files in a directory.
+///
+/// Each ceremony is a file named by the lowercase hex of its operation id, replaced atomically
+/// like the files of a [`crate::counters::FileCounterStore`]. The store is meant for a single
+/// process.
+#[derive(Clone, Debug)]
+pub struct FileCeremonyStore {
+    dir: PathBuf,
+}
+
+impl FileCeremonyStore {
+    /// Use `dir`, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>) -> CryptoResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, operation_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", hex::encode(operation_id)))
+    }
+}
+
+impl CeremonyStore for FileCeremonyStore {
+    fn load(&self, operation_id: &str) -> CryptoResult<Option<CeremonyState>> {
+        let path = self.path(operation_id);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+        let state: CeremonyState =
+            serde_json::from_slice(&bytes).map_err(|e| CryptoError::Serialization {
+                details: format!("ceremony file {} is corrupt: {}", path.display(), e),
+            })?;
+        if state.version != CEREMONY_STATE_VERSION || state.context.operation_id != operation_id {
+            return Err(CryptoError::Serialization {
+                details: format!(
+                    "ceremony file {} is not the state of {}",
+                    path.display(),
+                    operation_id
+                ),
+            });
+        }
+        Ok(Some(state))
+    }
+
+    fn save(&mut self, state: &CeremonyState) -> CryptoResult<()> {
+        let bytes = serde_json::to_vec(state).map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })?;
+        write_atomically(&self.dir, &self.path(&state.context.operation_id), &bytes)
+    }
+
+    fn remove(&mut self, operation_id: &str) -> CryptoResult<()> {
+        let path = self.path(operation_id);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(&path, e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A protocol run in progress, persisted to its store after every change.
+pub struct Ceremony {
+    state: CeremonyState,
+    store: Box<dyn CeremonyStore + Send + Sync>,
+}
+
+impl Ceremony {
+    /// Start the ceremony `context` of `participants` with threshold `threshold`.
+    ///
+    /// Returns `CryptoError::Validation` for a threshold outside `1..=participants.len()`, index
+    /// 0, an index listed twice, or an operation id the store already holds a ceremony for.
+    pub fn start(
+        mut store: Box<dyn CeremonyStore + Send + Sync>,
+        kind: ProtocolKind,
+        context: OperationContext,
+        threshold: usize,
+        participants: &[usize],
+    ) -> CryptoResult<Self> {
+        let mut sorted = participants.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        if sorted.len() != participants.len() || sorted.first() == Some(&0) {
+            return Err(CryptoError::Validation {
+                field: "participants".to_string(),
+                reason: "indices must be non-zero and distinct".to_string(),
+            });
+        }
+        if threshold == 0 || threshold > sorted.len() {
+            return Err(CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason: format!("must be between 1 and {}", sorted.len()),
+            });
+        }
+        if store.load(&context.operation_id)?.is_some() {
+            return Err(CryptoError::Validation {
+                field: "operation_id".to_string(),
+                reason: format!(
+                    "ceremony {} already exists; resume it instead",
+                    context.operation_id
+                ),
+            });
+        }
+        let state = CeremonyState {
+            version: CEREMONY_STATE_VERSION,
+            kind,
+            context,
+            threshold,
+            participants: sorted,
+            rounds: Vec::new(),
+            phase: CeremonyPhase::Running,
+            started_at: Utc::now().trunc_subsecs(0),
+        };
+        store.save(&state)?;
+        Ok(Self { state, store })
+    }
+
+    /// Continue the ceremony `operation_id` from its stored state.
+    ///
+    /// Returns `CryptoError::Validation` if the store holds no such ceremony.
+    pub fn resume(
+        store: Box<dyn CeremonyStore + Send + Sync>,
+        operation_id: &str,
+    ) -> CryptoResult<Self> {
+        let state = store
+            .load(operation_id)?
+            .ok_or_else(|| CryptoError::Validation {
+                field: "operation_id".to_string(),
+                reason: format!("no ceremony {}", operation_id),
+            })?;
+        Ok(Self { state, store })
+    }
+
+    pub fn state(&self) -> &CeremonyState {
+        &self.state
+    }
+
+    /// The round being run or last run.
+    pub fn current_round(&self) -> Option<&CeremonyRound> {
+        self.state.rounds.last()
+    }
+
+    /// Start round `name`, expecting a message from every participant by `timeout` from now.
+    ///
+    /// Returns `CryptoError::Validation` unless the ceremony is running and the previous round
+    /// is closed.
+    pub fn open_round(&mut self, name: &str, timeout: Duration) -> CryptoResult<()> {
+        self.require_running()?;
+        if self.current_round().is_some_and(|r| !r.closed) {
+            return Err(CryptoError::Validation {
+                field: "round".to_string(),
+                reason: "the previous round is still open".to_string(),
+            });
+        }
+        let expires_at = chrono::Duration::from_std(timeout)
+            .ok()
+            .and_then(|timeout| Utc::now().checked_add_signed(timeout))
+            .ok_or_else(|| CryptoError::Validation {
+                field: "timeout".to_string(),
+                reason: "deadline is out of range".to_string(),
+            })?;
+        let whole = expires_at.trunc_subsecs(0);
+        let expires_at = if whole < expires_at {
+            whole + chrono::Duration::seconds(1)
+        } else {
+            whole
+        };
+        self.state.rounds.push(CeremonyRound {
+            name: name.to_string(),
+            expected: self.state.participants.clone(),
+            responses: Vec::new(),
+            timeout_ms: timeout.as_millis().min(u128::from(u64::MAX)) as u64,
+            expires_at,
+            missing: Vec::new(),
+            closed: false,
+        });
+        self.persist()
+    }
+
+    /// Record `message` from `participant` in the open round.
+    ///
+    /// A message identical to the one already recorded from `participant` is accepted again, so
+    /// retransmissions after a restart are harmless. Returns `CryptoError::Timeout` past the
+    /// round deadline and `CryptoError::Validation` without an open round, for a participant not
+    /// expected in the round, or for a second, different message.
+    pub fn record<T: Serialize>(&mut self, participant: usize, message: &T) -> CryptoResult<()> {
+        self.check_deadline()?;
+        let digest = digest(RESPONSE_CONTEXT, &canonical_json::to_vec(message)?).to_vec();
+        let round = self.open_round_mut()?;
+        if !round.expected.contains(&participant) {
+            return Err(CryptoError::Validation {
+                field: "participant".to_string(),
+                reason: format!(
+                    "participant {} is not expected in round {}",
+                    participant, round.name
+                ),
+            });
+        }
+        if let Some(previous) = round
+            .responses
+            .iter()
+            .find(|r| r.participant == participant)
+        {
+            if previous.digest == digest {
+                return Ok(());
+            }
+            return Err(CryptoError::Validation {
+                field: "participant".to_string(),
+                reason: format!(
+                    "participant {} already sent a different message in round {}",
+                    participant, round.name
+                ),
+            });
+        }
+        round.responses.push(CeremonyResponse {
+            participant,
+            digest,
+            received_at: Utc::now().trunc_subsecs(0),
+        });
+        self.persist()
+    }
+
+    /// [`Self::record`] a message signed by its sender, once its signature verifies against
+    /// `roster` for the session named by the ceremony's operation id.
+    ///
+    /// The digest covers the whole envelope, signature included.
+    pub fn record_signed<T: SignedPayload>(
+        &mut self,
+        roster: &Roster,
+        message: &Signed<T>,
+    ) -> CryptoResult<()> {
+        message.verify(roster, &self.state.context.operation_id)?;
+        self.record(message.sender, message)
+    }
+
+    /// Participants the open round is still waiting for; empty without an open round.
+    pub fn pending(&self) -> Vec<usize> {
+        match self.current_round() {
+            Some(round) if !round.closed => round.pending(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// `CryptoError::Timeout` once the deadline of an open round that is still waiting for a
+    /// participant has passed.
+    pub fn check_deadline(&self) -> CryptoResult<()> {
+        match self.current_round() {
+            Some(round) if !round.closed && round.expired() && !round.pending().is_empty() => {
+                Err(self.timeout(round))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Close the open round and return the participants dropped for not answering it.
+    ///
+    /// Returns `CryptoError::Validation` while the round is waiting for participants within its
+    /// deadline, and `CryptoError::Timeout`, failing the ceremony, when fewer than the threshold
+    /// answered.
+    pub fn close_round(&mut self) -> CryptoResult<Vec<usize>> {
+        let round = self.open_round_mut()?;
+        let missing = round.pending();
+        if !missing.is_empty() && !round.expired() {
+            return Err(CryptoError::Validation {
+                field: "round".to_string(),
+                reason: format!("round {} is waiting for {:?}", round.name, missing),
+            });
+        }
+        round.missing = missing.clone();
+        round.closed = true;
+        self.state.participants.retain(|p| !missing.contains(p));
+        if self.state.participants.len() < self.state.threshold {
+            self.state.phase = CeremonyPhase::Failed;
+            self.persist()?;
+            let round = self.current_round().expect("round just closed");
+            return Err(self.timeout(round));
+        }
+        self.persist()?;
+        Ok(missing)
+    }
+
+    /// Complete the ceremony and return its transcript.
+    ///
+    /// Returns `CryptoError::Validation` unless at least one round was run and the last one is
+    /// closed. The completed state stays in the store until removed.
+    pub fn finish(&mut self) -> CryptoResult<ProtocolTranscript> {
+        self.require_running()?;
+        if !self.current_round().is_some_and(|r| r.closed) {
+            return Err(CryptoError::Validation {
+                field: "round".to_string(),
+                reason: "no round has been closed".to_string(),
+            });
+        }
+        self.state.phase = CeremonyPhase::Completed;
+        self.persist()?;
+        Ok(ProtocolTranscript {
+            version: CEREMONY_STATE_VERSION,
+            kind: self.state.kind,
+            context: self.state.context.clone(),
+            threshold: self.state.threshold,
+            participants: self.state.participants.clone(),
+            rounds: self.state.rounds.clone(),
+            started_at: self.state.started_at,
+            completed_at: Utc::now().trunc_subsecs(0),
+        })
+    }
+
+    fn require_running(&self) -> CryptoResult<()> {
+        if self.state.phase != CeremonyPhase::Running {
+            return Err(CryptoError::Validation {
+                field: "phase".to_string(),
+                reason: format!("ceremony is {:?}", self.state.phase),
+            });
+        }
+        Ok(())
+    }
+
+    fn open_round_mut(&mut self) -> CryptoResult<&mut CeremonyRound> {
+        self.require_running()?;
+        self.state
+            .rounds
+            .last_mut()
+            .filter(|r| !r.closed)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "round".to_string(),
+                reason: "no round is open".to_string(),
+            })
+    }
+
+    fn timeout(&self, round: &CeremonyRound) -> CryptoError {
+        CryptoError::Timeout {
+            operation: format!(
+                "{} round {} waiting for {:?}",
+                self.state.context.operation_id,
+                round.name,
+                round.pending()
+            ),
+            timeout_ms: round.timeout_ms,
+        }
+    }
+
+    fn persist(&mut self) -> CryptoResult<()> {
+        self.store.save(&self.state)
+    }
+}
+
+impl fmt::Debug for Ceremony {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ceremony")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::Participant;
+
+    #[test]
+    fn test_ceremony_rounds_timeouts_and_resume() {
+        let dir = std::env::temp_dir().join(format!("zk-thresh-ceremony-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = || Box::new(FileCeremonyStore::open(&dir).unwrap());
+        let context = OperationContext::new("k", 0, "dkg-1");
+        assert!(
+            Ceremony::start(store(), ProtocolKind::Dkg, context.clone(), 2, &[1, 1, 2]).is_err()
+        );
+        assert!(
+            Ceremony::start(store(), ProtocolKind::Dkg, context.clone(), 4, &[1, 2, 3]).is_err()
+        );
+        let mut ceremony =
+            Ceremony::start(store(), ProtocolKind::Dkg, context.clone(), 2, &[3, 1, 2]).unwrap();
+        assert!(Ceremony::start(store(), ProtocolKind::Dkg, context, 2, &[1, 2]).is_err());
+        assert!(ceremony.record(1, &"early").is_err());
+
+        let alice = Participant::from_seed(1, &[1; 32]).unwrap();
+        let roster = Roster::new(&[alice.identity()]).unwrap();
+        ceremony
+            .open_round("round1", Duration::from_secs(3600))
+            .unwrap();
+        let commitment = crate::threshold_sig::NonceCommitment {
+            index: 1,
+            commitment: curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT,
+        };
+        assert!(ceremony
+            .record_signed(&roster, &alice.sign("dkg-0", commitment).unwrap())
+            .is_err());
+        let signed = alice.sign("dkg-1", commitment).unwrap();
+        ceremony.record_signed(&roster, &signed).unwrap();
+        ceremony.record_signed(&roster, &signed).unwrap();
+        assert!(ceremony.record(1, &"other").is_err());
+        assert!(ceremony.record(4, &"outsider").is_err());
+        assert!(ceremony.close_round().is_err());
+        drop(ceremony);
+
+        // A restarted coordinator continues the round; the deadline passes with 2 answered.
+        let mut ceremony = Ceremony::resume(store(), "dkg-1").unwrap();
+        ceremony
+            .record(2, &serde_json::json!({"b": 1, "a": 2}))
+            .unwrap();
+        ceremony
+            .record(2, &serde_json::json!({"a": 2, "b": 1}))
+            .unwrap();
+        assert_eq!(ceremony.pending(), [3]);
+        let mut state = ceremony.state().clone();
+        state.rounds[0].expires_at = Utc::now().trunc_subsecs(0);
+        store().save(&state).unwrap();
+        let mut ceremony = Ceremony::resume(store(), "dkg-1").unwrap();
+        assert!(matches!(
+            ceremony.check_deadline(),
+            Err(CryptoError::Timeout {
+                timeout_ms: 3_600_000,
+                ..
+            })
+        ));
+        assert!(matches!(
+            ceremony.record(3, &"late"),
+            Err(CryptoError::Timeout { .. })
+        ));
+        assert_eq!(ceremony.close_round().unwrap(), [3]);
+        assert_eq!(ceremony.state().participants, [1, 2]);
+
+        ceremony
+            .open_round("round2", Duration::from_secs(60))
+            .unwrap();
+        assert!(ceremony.record(3, &"dropped").is_err());
+        ceremony.record(1, &"share for 2").unwrap();
+        ceremony.record(2, &"share for 1").unwrap();
+        assert!(ceremony.pending().is_empty());
+        assert!(ceremony.close_round().unwrap().is_empty());
+        let transcript = ceremony.finish().unwrap();
+        assert!(ceremony
+            .open_round("round3", Duration::from_secs(60))
+            .is_err());
+        assert_eq!(transcript.participants, [1, 2]);
+        assert_eq!(transcript.rounds[0].missing, [3]);
+        let parsed: ProtocolTranscript =
+            serde_json::from_str(&serde_json::to_string(&transcript).unwrap()).unwrap();
+        assert_eq!(parsed.digest().unwrap(), transcript.digest().unwrap());
+        assert_eq!(
+            store().load("dkg-1").unwrap().unwrap().phase,
+            CeremonyPhase::Completed
+        );
+        store().remove("dkg-1").unwrap();
+        assert!(Ceremony::resume(store(), "dkg-1").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_ceremony_fails_below_threshold() {
+        let context = OperationContext::new("k", 1, "sign-1");
+        let mut ceremony = Ceremony::start(
+            Box::new(MemoryCeremonyStore::new()),
+            ProtocolKind::Signing,
+            context,
+            2,
+            &[1, 2, 3],
+        )
+        .unwrap();
+        ceremony.open_round("commit", Duration::ZERO).unwrap();
+        ceremony.state.rounds[0].expires_at = Utc::now().trunc_subsecs(0);
+        assert!(matches!(
+            ceremony.close_round(),
+            Err(CryptoError::Timeout { operation, .. }) if operation.contains("[1, 2, 3]")
+        ));
+        assert_eq!(ceremony.state().phase, CeremonyPhase::Failed);
+        assert!(ceremony.finish().is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! **collection module**
+//!
+//! [`RecoveryCollection`]: the slices gathered for one recovery ceremony, verified as they arrive.
+//!
+//! Every submission is checked at once against the commitments of the key's epochs and answered
+//! with a [`ShareFeedback`] for the custodian who sent it: accepted, rejected with the error code
+//! and its likely cause, or superseded when the slice belongs to an epoch a refresh has since
+//! replaced. Operators see which custodians to chase while the ceremony is running, instead of
+//! learning at finalization that one of the slices was bad.
+//!
+//! `EnterpriseCryptoSystem::submit_share` additionally rejects revoked slices, and
+//! `EnterpriseCryptoSystem::finish_recovery` recovers from the accepted slices once the threshold
+//! is reached.
+
+use crate::context::OperationContext;
+use crate::error::{CryptoError, CryptoResult, FailureClass, VerifyError};
+use crate::sharing::ShareData;
+use crate::vss::{self, VssCommitments};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one submitted slice.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionStatus {
+    /// The slice verifies against the current commitments and counts towards the threshold.
+    Accepted,
+    /// The slice cannot be used.
+    Rejected {
+        /// Stable code of the failure, e.g. `ZKT-V004`.
+        code: String,
+        class: FailureClass,
+        reason: String,
+    },
+    /// The slice is valid for `epoch` but the key has been refreshed to `current_epoch`; the
+    /// custodian missed a refresh.
+    Superseded { epoch: u64, current_epoch: u64 },
+}
+
+/// Answer to a custodian's submission.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareFeedback {
+    pub custodian_id: String,
+    /// Slice index, 0 if the submission could not be parsed.
+    pub index: usize,
+    pub status: CollectionStatus,
+}
+
+impl ShareFeedback {
+    pub fn is_accepted(&self) -> bool {
+        self.status == CollectionStatus::Accepted
+    }
+
+    pub(crate) fn rejected(custodian_id: &str, index: usize, error: &VerifyError) -> Self {
+        Self::new(
+            custodian_id,
+            index,
+            CollectionStatus::Rejected {
+                code: error.code().to_string(),
+                class: error.class(),
+                reason: error.to_string(),
+            },
+        )
+    }
+
+    pub(crate) fn new(custodian_id: &str, index: usize, status: CollectionStatus) -> Self {
+        Self {
+            custodian_id: custodian_id.to_string(),
+            index,
+            status,
+        }
+    }
+}
+
+/// Slices collected for one recovery, with the feedback given on each submission.
+#[derive(Clone, Debug)]
+pub struct RecoveryCollection {
+    context: OperationContext,
+    threshold: usize,
+    history: Vec<(u64, VssCommitments)>,
+    accepted: Vec<(String, ShareData)>,
+    feedback: Vec<ShareFeedback>,
+}
+
+impl RecoveryCollection {
+    /// Collect `threshold` slices for the recovery `context`.
+    ///
+    /// `history` holds the commitments of every epoch of the key, oldest first, the last being
+    /// the current one, as for [`vss::verify_share_epochs`]. Returns `CryptoError::Validation`
+    /// if it is empty or `threshold` is zero.
+    pub fn new(
+        context: OperationContext,
+        threshold: usize,
+        history: Vec<(u64, VssCommitments)>,
+    ) -> CryptoResult<Self> {
+        if threshold == 0 {
+            return Err(CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason: "threshold must be at least 1".to_string(),
+            });
+        }
+        if history.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "history".to_string(),
+                reason: "no commitments to verify against".to_string(),
+            });
+        }
+        Ok(Self {
+            context,
+            threshold,
+            history,
+            accepted: Vec::new(),
+            feedback: Vec::new(),
+        })
+    }
+
+    /// Verify `share` from `custodian_id` and record the outcome.
+    ///
+    /// A second valid slice with an index already collected is rejected as `WrongContext`; the
+    /// first one is kept.
+    pub fn submit(&mut self, custodian_id: &str, share: ShareData) -> &ShareFeedback {
+        let history: Vec<_> = self.history.iter().map(|(e, c)| (*e, c)).collect();
+        let feedback = match vss::verify_share_epochs(&share, &history) {
+            Ok(()) => match self.accepted.iter().find(|(_, s)| s.index == share.index) {
+                Some((holder, _)) => ShareFeedback::rejected(
+                    custodian_id,
+                    share.index,
+                    &VerifyError::WrongContext {
+                        index: share.index,
+                        details: format!("already collected from {}", holder),
+                    },
+                ),
+                None => {
+                    let feedback =
+                        ShareFeedback::new(custodian_id, share.index, CollectionStatus::Accepted);
+                    self.accepted.push((custodian_id.to_string(), share));
+                    feedback
+                }
+            },
+            Err(VerifyError::StaleEpoch {
+                index,
+                epoch,
+                current_epoch,
+            }) => ShareFeedback::new(
+                custodian_id,
+                index,
+                CollectionStatus::Superseded {
+                    epoch,
+                    current_epoch,
+                },
+            ),
+            Err(e) => ShareFeedback::rejected(custodian_id, share.index, &e),
+        };
+        self.record(feedback)
+    }
+
+    /// Parse a slice from its JSON form with [`vss::parse_share`], then [`Self::submit`] it.
+    pub fn submit_json(&mut self, custodian_id: &str, json: &[u8]) -> &ShareFeedback {
+        match vss::parse_share(json) {
+            Ok(share) => self.submit(custodian_id, share),
+            Err(e) => self.record(ShareFeedback::rejected(custodian_id, 0, &e)),
+        }
+    }
+
+    pub(crate) fn record(&mut self, feedback: ShareFeedback) -> &ShareFeedback {
+        self.feedback.push(feedback);
+        self.feedback.last().expect("feedback just recorded")
+    }
+
+    pub fn context(&self) -> &OperationContext {
+        &self.context
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Feedback on every submission, in arrival order.
+    pub fn feedback(&self) -> &[ShareFeedback] {
+        &self.feedback
+    }
+
+    /// Latest feedback given to each custodian, in order of first submission.
+    pub fn custodian_status(&self) -> Vec<&ShareFeedback> {
+        let mut latest: Vec<&ShareFeedback> = Vec::new();
+        for feedback in &self.feedback {
+            match latest
+                .iter_mut()
+                .find(|f| f.custodian_id == feedback.custodian_id)
+            {
+                Some(entry) => *entry = feedback,
+                None => latest.push(feedback),
+            }
+        }
+        latest
+    }
+
+    /// Accepted slices, in arrival order.
+    pub fn shares(&self) -> Vec<ShareData> {
+        self.accepted.iter().map(|(_, s)| s.clone()).collect()
+    }
+
+    /// Number of further slices needed to reach the threshold.
+    pub fn missing(&self) -> usize {
+        self.threshold.saturating_sub(self.accepted.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing() == 0
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::{generate_key_shares_with_commitments, update_shares_with_commitments};
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_feedback_per_submission() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(9u64), 2, 4).unwrap();
+        let (refreshed, current) = update_shares_with_commitments(&shares, &commitments).unwrap();
+        let context = OperationContext::new("k", 1, "recover");
+        assert!(RecoveryCollection::new(context.clone(), 2, vec![]).is_err());
+        let mut collection =
+            RecoveryCollection::new(context, 2, vec![(0, commitments), (1, current)]).unwrap();
+
+        assert_eq!(
+            collection.submit("alice", shares[0].clone()).status,
+            CollectionStatus::Superseded {
+                epoch: 0,
+                current_epoch: 1
+            }
+        );
+        let mut forged = refreshed[1].clone();
+        forged.share += Scalar::ONE;
+        assert!(matches!(
+            &collection.submit("bob", forged).status,
+            CollectionStatus::Rejected { code, class: FailureClass::Malice, .. } if code == "ZKT-V003"
+        ));
+        assert!(matches!(
+            &collection.submit_json("carol", b"{}").status,
+            CollectionStatus::Rejected {
+                class: FailureClass::Corruption,
+                ..
+            }
+        ));
+        assert!(collection
+            .submit("alice", refreshed[0].clone())
+            .is_accepted());
+        assert!(!collection
+            .submit("dave", refreshed[0].clone())
+            .is_accepted());
+        assert_eq!(collection.missing(), 1);
+        let json = serde_json::to_vec(&refreshed[2]).unwrap();
+        assert!(collection.submit_json("carol", &json).is_accepted());
+        assert!(collection.is_complete());
+
+        let latest: Vec<_> = collection
+            .custodian_status()
+            .iter()
+            .map(|f| (f.custodian_id.as_str(), f.is_accepted()))
+            .collect();
+        assert_eq!(
+            latest,
+            [
+                ("alice", true),
+                ("bob", false),
+                ("carol", true),
+                ("dave", false)
+            ]
+        );
+        assert_eq!(collection.feedback().len(), 6);
+        assert_eq!(collection.shares().len(), 2);
+    }
+}
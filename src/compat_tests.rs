@@ -0,0 +1,173 @@
+//! Cross-platform compatibility vectors.
+//!
+//! Every value here was produced once and is checked byte for byte, on every target the crate
+//! is built for (64- and 32-bit, little- and big-endian, `verify-only` included): the binary and
+//! JSON encodings of a slice, the proof challenges, the commitment root, derived digests and the
+//! secret recovered through each interpolation path. A change that makes any of them depend on
+//! `usize` width, endianness or the curve backend fails here before it ships.
+//!
+//! The dealing is fixed: `f(x) = a₀ + a₁x + a₂x²` and blinding `g(x) = b₀ + b₁x + b₂x²` with
+//! every coefficient a repeated byte, dealt to indices 1 to 5. Only the proof nonces were random
+//! when the vectors were recorded, so the proofs are stored.
+
+use crate::proof::{self, Proof};
+use crate::sharing::ShareData;
+use crate::utils::pedersen_commit;
+use crate::vss::{self, VssCommitments};
+use curve25519_dalek::scalar::Scalar;
+
+/// Proofs of slices 1 to 5, as [`Proof::to_bytes`], with the transcript challenges of `Proof`
+/// version 2.
+const PROOFS: [&str; 5] = [
+    "961c8716a032d4b969920dbdb3751f987e57fddd400022c1fc074246e19a7d55e06c8a64cfc579653c7610cea2b8eab95ba6d3fd212be0d373a63b2afac84e0a3dd4c4c44cae74a27f0d86618c02951b7d1d190a65355f389d739e360a85970a837570185eb658d83806520ea28957896f5e8662662bc26d384778dd72fbfe0c",
+    "66af97930f6a34d1faeb8f5349bba1875418ff7abf9ca7b7493886c89545de29c2667f24e148fcc033e14d85a4caa15247672fd78ac455615d8413350a88c10c1a34496db43d1b65eec0f2f2683036555b563a36551271ebad0c9b1824938d0bfbe98d39d1dddb8ebd5c702c890f4f84c3c8f295851b71698112bb5d97b1f709",
+    "9cb4b10f04460db648a3ec25cd74219425806d7940be35495ce9ccc14eeb434e2e1080d01c167db7c6a9ff8cc2df07f139f9b9d18c876dca8d94115c50219a03418849df3c8d57313442fbb955f2451abcc5fffc0410d01b5ededafca2040706eb90a88e2f160e69708e60402920914d0eb700a8409535f4914c1baf6b27ce0e",
+    "94761e763a92058a16fba2f97dcfb1eb95edfdd2d812c353ca582fa2327b9a1c3bc575ead915e165217614177d7690efb2656d87501bb77c29d0782e77afb30a5344f53191459189eb7302e8d2dfaaa93d813a46a0cc399aa14bc5d60fb12900bd9457758e280e5922717b378f9a18620f1bffc13d22b78e81c85f50f40cb908",
+    "2c41c3bc57e0ac7fe6bfc4675003b8275d08d18b452ad3e61ab613fd653b453301e7662b4592501f6dd89664b60874afb913eaa5d961d644da8cd666122cf20abd7bbd68c9e4db2da30c82f468ed2a7958694b4171b59fba72441034acdb50014985e41868c78580a52dc9acee636a2ab2950cf2cddda6d1f5eb732b868d7f07",
+];
+
+/// Slice 1 as [`ShareData::to_bytes`]: index, share, commitment, random, proof.
+const SHARE_1_BYTES: &str = concat!(
+    "0100000000000000",
+    "d86ea338c813f85560b998942e8b2ce965666666666666666666666666666606",
+    "30771e9c69bd375fb5fd6fb1869a9e32ff8acdbc6d32b8d60369853004781c39",
+    "1c95988d7431ecd670cf7d73f45befc6feffffffffffffffffffffffffffff0f",
+    "961c8716a032d4b969920dbdb3751f987e57fddd400022c1fc074246e19a7d55e06c8a64cfc579653c7610cea2b8eab95ba6d3fd212be0d373a63b2afac84e0a3dd4c4c44cae74a27f0d86618c02951b7d1d190a65355f389d739e360a85970a837570185eb658d83806520ea28957896f5e8662662bc26d384778dd72fbfe0c",
+);
+
+const SHARE_1_JSON: &str = r#"{"index":1,"share":"d86ea338c813f85560b998942e8b2ce965666666666666666666666666666606","commitment":"30771e9c69bd375fb5fd6fb1869a9e32ff8acdbc6d32b8d60369853004781c39","random":"1c95988d7431ecd670cf7d73f45befc6feffffffffffffffffffffffffffff0f","proof":{"proof_ristretto_point":"961c8716a032d4b969920dbdb3751f987e57fddd400022c1fc074246e19a7d55","response_share":"e06c8a64cfc579653c7610cea2b8eab95ba6d3fd212be0d373a63b2afac84e0a","response_random":"3dd4c4c44cae74a27f0d86618c02951b7d1d190a65355f389d739e360a85970a","challenge":"837570185eb658d83806520ea28957896f5e8662662bc26d384778dd72fbfe0c"}}"#;
+
+const COMMITMENT_ROOT: &str = "6d967b3ff68b2d36361993f6fe8244f23ac443416271282651cf5d8c1fd8a029";
+
+const SECRET_FINGERPRINT: &str = "a9ec2cd4ed00dcc1";
+
+fn coefficient(byte: u8) -> Scalar {
+    Scalar::from_bytes_mod_order([byte; 32])
+}
+
+fn secret_polynomial() -> [Scalar; 3] {
+    [coefficient(0x11), coefficient(0x22), coefficient(0x33)]
+}
+
+fn blinding_polynomial() -> [Scalar; 3] {
+    [coefficient(0x44), coefficient(0x55), coefficient(0x66)]
+}
+
+fn evaluate(poly: &[Scalar], x: usize) -> Scalar {
+    let x = Scalar::from(x as u64);
+    poly.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+fn fixture() -> Vec<ShareData> {
+    let (f, g) = (secret_polynomial(), blinding_polynomial());
+    (1..=5)
+        .map(|index| {
+            let share = evaluate(&f, index);
+            let random = evaluate(&g, index);
+            let proof = hex::decode(PROOFS[index - 1]).unwrap();
+            ShareData {
+                index,
+                share,
+                commitment: pedersen_commit(&share, &random),
+                random,
+                proof: Proof::from_bytes(&proof).unwrap(),
+            }
+        })
+        .collect()
+}
+
+fn commitments() -> VssCommitments {
+    let pairs = secret_polynomial().into_iter().zip(blinding_polynomial());
+    VssCommitments {
+        coefficients: pairs.map(|(a, b)| pedersen_commit(&a, &b)).collect(),
+    }
+}
+
+#[test]
+fn test_share_encodings_are_byte_identical() {
+    let shares = fixture();
+    assert_eq!(hex::encode(shares[0].to_bytes().as_ref()), SHARE_1_BYTES);
+    assert_eq!(serde_json::to_string(&shares[0]).unwrap(), SHARE_1_JSON);
+
+    let decoded = ShareData::from_bytes(&hex::decode(SHARE_1_BYTES).unwrap()).unwrap();
+    assert_eq!(decoded.to_bytes(), shares[0].to_bytes());
+    let parsed = vss::parse_share(SHARE_1_JSON.as_bytes()).unwrap();
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), SHARE_1_JSON);
+    for share in &shares {
+        assert_eq!(
+            ShareData::from_bytes(share.to_bytes().as_ref())
+                .unwrap()
+                .to_bytes(),
+            share.to_bytes()
+        );
+    }
+}
+
+#[test]
+fn test_recorded_proofs_verify() {
+    let shares = fixture();
+    let commitments = commitments();
+    for share in &shares {
+        proof::verify_proof(&share.proof, share.commitment, share.index).unwrap();
+        commitments.verify_share(share).unwrap();
+    }
+    let statements: Vec<_> = shares
+        .iter()
+        .map(|s| (&s.proof, s.commitment, s.index))
+        .collect();
+    proof::verify_proofs_batch(&statements).unwrap();
+    vss::verify_share_validity(&shares).unwrap();
+}
+
+#[test]
+fn test_digests_are_stable() {
+    assert_eq!(hex::encode(commitments().root()), COMMITMENT_ROOT);
+    assert_eq!(
+        crate::redact::fingerprint(&secret_polynomial()[0]),
+        SECRET_FINGERPRINT
+    );
+}
+
+#[test]
+fn test_indices_beyond_32_bits() {
+    let mut bytes = fixture()[0].to_bytes();
+    bytes[..8].copy_from_slice(&(u64::from(u32::MAX) + 2).to_le_bytes());
+    let decoded = ShareData::from_bytes(bytes.as_ref());
+    if usize::BITS == 32 {
+        assert!(matches!(
+            decoded,
+            Err(crate::error::CryptoError::Serialization { .. })
+        ));
+    } else {
+        assert_eq!(decoded.unwrap().index as u64, u64::from(u32::MAX) + 2);
+    }
+}
+
+#[cfg(feature = "dealer")]
+#[test]
+fn test_recovery_is_platform_independent() {
+    use crate::lagrange_fft::{recover_secret_ct, recover_secret_fft};
+
+    let shares = fixture();
+    let secret = secret_polynomial()[0];
+    for skip in 0..shares.len() {
+        for other in skip + 1..shares.len() {
+            let subset: Vec<_> = (0..shares.len())
+                .filter(|&i| i != skip && i != other)
+                .map(|i| shares[i].clone())
+                .collect();
+            assert_eq!(recover_secret_fft(&subset).unwrap(), secret);
+            assert_eq!(recover_secret_ct(&subset).unwrap(), secret);
+        }
+    }
+    assert_eq!(recover_secret_fft(&shares).unwrap(), secret);
+
+    let key = crate::key_lifecycle::Key::new(secret);
+    let material = key
+        .derive_material("zk-thresh-pro compatibility vectors", 48)
+        .unwrap();
+    assert_eq!(
+        hex::encode(material.as_slice()),
+        "c5f8e6599b27647db9c12c89a51b205e661ecb6ea82256bd194eb349a86c44c9e77eed87df6dfd36dc3d3ec5f0ae77bc"
+    );
+}
@@ -0,0 +1,92 @@
+//! **context module**
+//!
+//! Correlation identifiers for audit events, metrics and structured log lines.
+//!
+//! An [`OperationContext`] names the key an operation acts on, the share epoch of that key and
+//! the operation itself. Every audit record and metric produced while the context is active
+//! carries all three, so the full history of one key across dealings, refreshes and recoveries
+//! can be reconstructed with a single `key_id=<id>` query.
+//!
+//! A context may also carry a [`Deadline`] for the operation, which is not serialized and not
+//! part of its identity: two contexts naming the same operation are equal whatever their
+//! deadlines.
+
+use crate::deadline::Deadline;
+use crate::error::CryptoResult;
+use serde::{Deserialize, Serialize};
+
+/// `(key_id, epoch, operation_id)` of the operation being performed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OperationContext {
+    /// Identifier of the key the operation acts on.
+    pub key_id: String,
+    /// Share epoch of the key: 0 for the initial dealing, incremented by every refresh or reshare.
+    pub epoch: u64,
+    /// Identifier of this operation, unique per invocation.
+    pub operation_id: String,
+    /// Deadline of the operation, checked by the enterprise operations run under the context.
+    #[serde(skip)]
+    deadline: Option<Deadline>,
+}
+
+impl PartialEq for OperationContext {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.key_id, self.epoch, &self.operation_id)
+            == (&other.key_id, other.epoch, &other.operation_id)
+    }
+}
+
+impl Eq for OperationContext {}
+
+impl std::hash::Hash for OperationContext {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (&self.key_id, self.epoch, &self.operation_id).hash(state);
+    }
+}
+
+impl OperationContext {
+    pub fn new(key_id: &str, epoch: u64, operation_id: &str) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            epoch,
+            operation_id: operation_id.to_string(),
+            deadline: None,
+        }
+    }
+
+    /// The same operation, to finish by `deadline`.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Deadline of the operation, if any.
+    pub fn deadline(&self) -> Option<&Deadline> {
+        self.deadline.as_ref()
+    }
+
+    /// `CryptoError::Timeout` naming `operation` once the deadline has passed or was cancelled.
+    pub fn check_deadline(&self, operation: &str) -> CryptoResult<()> {
+        crate::deadline::check(self.deadline(), operation)
+    }
+
+    /// A different operation on the same key and epoch, without a deadline.
+    pub fn for_operation(&self, operation_id: &str) -> Self {
+        Self::new(&self.key_id, self.epoch, operation_id)
+    }
+
+    /// An operation that moves the key to the next epoch (refresh or reshare), without a
+    /// deadline.
+    pub fn next_epoch(&self, operation_id: &str) -> Self {
+        Self::new(&self.key_id, self.epoch + 1, operation_id)
+    }
+
+    /// Structured `key=value` fields, in a fixed order.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("key_id", self.key_id.clone()),
+            ("epoch", self.epoch.to_string()),
+            ("operation_id", self.operation_id.clone()),
+        ]
+    }
+}
@@ -0,0 +1,216 @@
+//! **counters module**
+//!
+//! Monotonic counters that survive restarts: the share epoch of every key and the sequence number
+//! of operations.
+//!
+//! Replay protection and audit ordering both assume an epoch or operation id is never used twice.
+//! Kept in memory only, the counters start over when the process restarts, and a refresh could
+//! then be issued under an epoch holders already accepted. A [`CounterStore`] persists them; the
+//! `EnterpriseCryptoSystem` advances a counter in the store before it acts on the new value, so
+//! a crash can skip a value but never hand one out twice.
+//!
+//! - [`MemoryCounterStore`] for tests and single-run tools.
+//! - [`FileCounterStore`] keeps one small file per counter in a directory, replaced atomically.
+
+use crate::error::{CryptoError, CryptoResult};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Length of a counter file written by [`FileCounterStore`].
+pub const COUNTER_FILE_LENGTH: usize = 8;
+
+/// Counter of the last operation sequence number handed out.
+pub const OPERATION_SEQUENCE: &str = "operation_sequence";
+
+/// Counter of the last share epoch of `key_id`.
+pub fn epoch_counter(key_id: &str) -> String {
+    format!("epoch/{}", key_id)
+}
+
+/// Durable storage of named monotonic counters.
+pub trait CounterStore {
+    /// Current value of `name`, or `None` if it was never set.
+    fn get(&self, name: &str) -> CryptoResult<Option<u64>>;
+
+    /// Durably set `name` to `value`.
+    ///
+    /// Returns `CryptoError::Validation` unless `value` is greater than the current value; the
+    /// value is on stable storage when this returns `Ok`.
+    fn advance(&mut self, name: &str, value: u64) -> CryptoResult<()>;
+
+    /// Advance `name` by one from its current value (0 if unset) and return the new value.
+    fn increment(&mut self, name: &str) -> CryptoResult<u64> {
+        let next = match self.get(name)? {
+            Some(current) => {
+                current
+                    .checked_add(1)
+                    .ok_or_else(|| CryptoError::ResourceExhaustion {
+                        resource: name.to_string(),
+                    })?
+            }
+            None => 1,
+        };
+        self.advance(name, next)?;
+        Ok(next)
+    }
+}
+
+fn not_increasing(name: &str, value: u64, current: u64) -> CryptoError {
+    CryptoError::Validation {
+        field: name.to_string(),
+        reason: format!(
+            "{} does not advance past the stored value {}",
+            value, current
+        ),
+    }
+}
+
+/// Counters held in process memory; they do not survive a restart.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCounterStore {
+    counters: Vec<(String, u64)>,
+}
+
+impl MemoryCounterStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CounterStore for MemoryCounterStore {
+    fn get(&self, name: &str) -> CryptoResult<Option<u64>> {
+        Ok(self
+            .counters
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| *value))
+    }
+
+    fn advance(&mut self, name: &str, value: u64) -> CryptoResult<()> {
+        match self.counters.iter_mut().find(|(n, _)| n == name) {
+            Some((_, current)) if value <= *current => Err(not_increasing(name, value, *current)),
+            Some((_, current)) => {
+                *current = value;
+                Ok(())
+            }
+            None => {
+                self.counters.push((name.to_string(), value));
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Counters kept as files in a directory.
+///
+/// Each counter is a file named by the lowercase hex of its name, holding the value as a
+/// little-endian `u64`. An update writes a temporary file, syncs it and renames it over the old
+/// one, so a crash leaves either the old or the new value. The store is meant for a single
+/// process; concurrent writers must be serialized by the caller.
+#[derive(Clone, Debug)]
+pub struct FileCounterStore {
+    dir: PathBuf,
+}
+
+impl FileCounterStore {
+    /// Use `dir`, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>) -> CryptoResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(hex::encode(name))
+    }
+}
+
+pub(crate) fn io_error(path: &Path, e: std::io::Error) -> CryptoError {
+    CryptoError::Configuration {
+        parameter: path.display().to_string(),
+        issue: e.to_string(),
+    }
+}
+
+/// Replace `path` in `dir` with `bytes`: write a temporary file, sync it and rename it over the
+/// old one, so a crash leaves either the old or the new contents.
+pub(crate) fn write_atomically(dir: &Path, path: &Path, bytes: &[u8]) -> CryptoResult<()> {
+    let temporary = path.with_extension("tmp");
+    let mut file = fs::File::create(&temporary).map_err(|e| io_error(&temporary, e))?;
+    file.write_all(bytes)
+        .and_then(|()| file.sync_all())
+        .map_err(|e| io_error(&temporary, e))?;
+    fs::rename(&temporary, path).map_err(|e| io_error(path, e))?;
+    // Make the rename itself durable; directories cannot be opened for syncing elsewhere.
+    #[cfg(unix)]
+    fs::File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| io_error(dir, e))?;
+    Ok(())
+}
+
+impl CounterStore for FileCounterStore {
+    fn get(&self, name: &str) -> CryptoResult<Option<u64>> {
+        let path = self.path(name);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+        let bytes: [u8; COUNTER_FILE_LENGTH] =
+            bytes.try_into().map_err(|_| CryptoError::Serialization {
+                details: format!("counter file {} is corrupt", path.display()),
+            })?;
+        Ok(Some(u64::from_le_bytes(bytes)))
+    }
+
+    fn advance(&mut self, name: &str, value: u64) -> CryptoResult<()> {
+        if let Some(current) = self.get(name)? {
+            if value <= current {
+                return Err(not_increasing(name, value, current));
+            }
+        }
+        write_atomically(&self.dir, &self.path(name), &value.to_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_monotonic(store: &mut dyn CounterStore) {
+        assert_eq!(store.get("epoch/k").unwrap(), None);
+        store.advance("epoch/k", 0).unwrap();
+        assert!(matches!(
+            store.advance("epoch/k", 0),
+            Err(CryptoError::Validation { .. })
+        ));
+        store.advance("epoch/k", 3).unwrap();
+        assert_eq!(store.get("epoch/k").unwrap(), Some(3));
+        assert_eq!(store.increment(OPERATION_SEQUENCE).unwrap(), 1);
+        assert_eq!(store.increment(OPERATION_SEQUENCE).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_counters_are_monotonic_and_persistent() {
+        check_monotonic(&mut MemoryCounterStore::new());
+
+        let dir = std::env::temp_dir().join(format!("zk-thresh-counters-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        check_monotonic(&mut FileCounterStore::open(&dir).unwrap());
+
+        // A restarted process sees the stored values.
+        let mut reopened = FileCounterStore::open(&dir).unwrap();
+        assert_eq!(reopened.get("epoch/k").unwrap(), Some(3));
+        assert!(reopened.advance("epoch/k", 2).is_err());
+        assert_eq!(reopened.increment(OPERATION_SEQUENCE).unwrap(), 3);
+
+        fs::write(dir.join(hex::encode("epoch/k")), [1, 2, 3]).unwrap();
+        assert!(matches!(
+            reopened.get("epoch/k"),
+            Err(CryptoError::Serialization { .. })
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
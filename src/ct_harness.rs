@@ -0,0 +1,376 @@
+//! **ct_harness module**
+//!
+//! dudect-style timing tests of the paths the crate claims run in constant time (feature
+//! `ct-harness`, for test and audit builds).
+//!
+//! Each target is timed on two classes of inputs drawn in random order: one with the secret part
+//! of the input fixed, one with it drawn at random, everything else distributed alike. Welch's
+//! t-test then compares the two timing distributions, over all measurements and over the
+//! measurements below a series of percentiles (cropping removes the long tail of interrupts and
+//! cache misses that would otherwise drown a small difference). A constant-time implementation
+//! keeps `|t|` small however many samples are taken; `|t|` above [`DEFAULT_T_THRESHOLD`] is
+//! strong evidence of a secret-dependent timing, above 10 it is conclusive.
+//!
+//! Targets:
+//!
+//! - `recovery`: [`recover_secret_ct`] over fixed versus random slice values at the same indices;
+//! - `proof_verification`: [`verify_proof`] of valid proofs for a fixed versus a random witness;
+//! - `mac_comparison`: the tag comparison of the share file, wrapping, sealing and transport
+//!   formats, against a received tag equal to the expected one versus a random one.
+//!
+//! The test measures a build on a machine, not the source: run it with optimizations, on the
+//! target hardware, with the machine otherwise idle. A passing report is evidence, not proof; a
+//! failing one is worth investigating even if it turns out to be noise.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::lagrange_fft::recover_secret_ct;
+use crate::proof::{generate_proof, verify_proof};
+use crate::sharing::{generate_key_shares, ShareData};
+use crate::utils::{fill_random, new_rng, pedersen_commit, random_scalar};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Version of the [`HarnessReport`] format.
+pub const HARNESS_REPORT_VERSION: u8 = 1;
+
+/// `|t|` above which a target is reported as leaking.
+pub const DEFAULT_T_THRESHOLD: f64 = 4.5;
+
+/// Measurements per class by default.
+pub const DEFAULT_SAMPLES: usize = 10_000;
+
+/// Percentile crops tested besides the uncropped measurements.
+const CROPS: usize = 10;
+
+/// Proofs generated per class for the `proof_verification` target.
+const PROOF_POOL: usize = 64;
+
+/// Slices recovered per measurement of the `recovery` target.
+const RECOVERY_THRESHOLD: usize = 3;
+
+/// Samples and decision threshold of a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarnessConfig {
+    /// Measurements per class.
+    pub samples: usize,
+    /// `|t|` above which a target is reported as leaking.
+    pub threshold: f64,
+}
+
+impl Default for HarnessConfig {
+    fn default() -> Self {
+        Self {
+            samples: DEFAULT_SAMPLES,
+            threshold: DEFAULT_T_THRESHOLD,
+        }
+    }
+}
+
+impl HarnessConfig {
+    /// Returns `CryptoError::Validation` for fewer than 100 samples or a non-positive threshold.
+    pub fn validate(&self) -> CryptoResult<()> {
+        if self.samples < 100 {
+            return Err(CryptoError::Validation {
+                field: "samples".to_string(),
+                reason: "at least 100 measurements per class are needed".to_string(),
+            });
+        }
+        if self.threshold.is_nan() || self.threshold <= 0.0 {
+            return Err(CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason: "must be positive".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one target.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingReport {
+    /// Name of the target, e.g. `recovery`.
+    pub target: String,
+    /// Measurements in the smaller class.
+    pub samples: usize,
+    /// Mean duration of the fixed-input class, in nanoseconds.
+    pub fixed_mean_ns: f64,
+    /// Mean duration of the random-input class, in nanoseconds.
+    pub random_mean_ns: f64,
+    /// Largest `|t|` over the uncropped and cropped measurements.
+    pub max_t: f64,
+    /// Whether `max_t` exceeds the threshold of the run.
+    pub leak_suspected: bool,
+}
+
+/// Outcome of [`run`] over every target.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarnessReport {
+    /// Format version, [`HARNESS_REPORT_VERSION`].
+    pub version: u8,
+    /// `|t|` threshold of the run.
+    pub threshold: f64,
+    /// Whether the crate was built with optimizations; debug timings say little.
+    pub optimized: bool,
+    pub targets: Vec<TimingReport>,
+}
+
+impl HarnessReport {
+    /// Whether any target is reported as leaking.
+    pub fn leak_suspected(&self) -> bool {
+        self.targets.iter().any(|t| t.leak_suspected)
+    }
+}
+
+/// Time every target with `config`.
+pub fn run(config: &HarnessConfig) -> CryptoResult<HarnessReport> {
+    Ok(HarnessReport {
+        version: HARNESS_REPORT_VERSION,
+        threshold: config.threshold,
+        optimized: !cfg!(debug_assertions),
+        targets: vec![
+            measure_recovery(config)?,
+            measure_proof_verification(config)?,
+            measure_mac_comparison(config)?,
+        ],
+    })
+}
+
+/// Time `operation` on `inputs`, where `classes[i]` tells whether `inputs[i]` is of the random
+/// class, and compare the two classes.
+///
+/// The inputs are processed in the order given, which callers draw at random; a tenth of them is
+/// run once beforehand to warm caches and branch predictors.
+pub fn measure<I, R>(
+    target: &str,
+    config: &HarnessConfig,
+    inputs: &[I],
+    classes: &[bool],
+    mut operation: impl FnMut(&I) -> R,
+) -> CryptoResult<TimingReport> {
+    config.validate()?;
+    if inputs.len() != classes.len() {
+        return Err(CryptoError::Validation {
+            field: "classes".to_string(),
+            reason: format!("{} classes for {} inputs", classes.len(), inputs.len()),
+        });
+    }
+    for input in &inputs[..inputs.len() / 10] {
+        black_box(operation(black_box(input)));
+    }
+    let mut measurements = Vec::with_capacity(inputs.len());
+    for (input, &random) in inputs.iter().zip(classes) {
+        let start = Instant::now();
+        black_box(operation(black_box(input)));
+        measurements.push((random, start.elapsed().as_nanos() as f64));
+    }
+
+    let uncropped = Welch::over(&measurements, f64::INFINITY);
+    let mut sorted: Vec<f64> = measurements.iter().map(|(_, d)| *d).collect();
+    sorted.sort_by(f64::total_cmp);
+    let max_t = (0..CROPS)
+        .map(|k| {
+            let percentile = 1.0 - 0.5f64.powf(10.0 * (k + 1) as f64 / CROPS as f64);
+            let cutoff = sorted[((sorted.len() - 1) as f64 * percentile) as usize];
+            Welch::over(&measurements, cutoff).t().abs()
+        })
+        .fold(uncropped.t().abs(), f64::max);
+    Ok(TimingReport {
+        target: target.to_string(),
+        samples: uncropped.counts[0].min(uncropped.counts[1]),
+        fixed_mean_ns: uncropped.means[0],
+        random_mean_ns: uncropped.means[1],
+        max_t,
+        leak_suspected: max_t > config.threshold,
+    })
+}
+
+/// Running means and variances of both classes (Welford).
+struct Welch {
+    counts: [usize; 2],
+    means: [f64; 2],
+    squares: [f64; 2],
+}
+
+impl Welch {
+    /// Statistics of the measurements no longer than `cutoff`.
+    fn over(measurements: &[(bool, f64)], cutoff: f64) -> Self {
+        let mut welch = Welch {
+            counts: [0; 2],
+            means: [0.0; 2],
+            squares: [0.0; 2],
+        };
+        for &(random, duration) in measurements {
+            if duration > cutoff {
+                continue;
+            }
+            let class = random as usize;
+            welch.counts[class] += 1;
+            let delta = duration - welch.means[class];
+            welch.means[class] += delta / welch.counts[class] as f64;
+            welch.squares[class] += delta * (duration - welch.means[class]);
+        }
+        welch
+    }
+
+    /// Welch's t statistic, zero without two measurements in each class.
+    fn t(&self) -> f64 {
+        if self.counts.iter().any(|&n| n < 2) {
+            return 0.0;
+        }
+        let variance = |class: usize| self.squares[class] / (self.counts[class] - 1) as f64;
+        let error =
+            (variance(0) / self.counts[0] as f64 + variance(1) / self.counts[1] as f64).sqrt();
+        if error == 0.0 {
+            return 0.0;
+        }
+        (self.means[0] - self.means[1]) / error
+    }
+}
+
+/// `2 * samples` classes in random order, half of them `true`.
+fn random_classes(samples: usize) -> CryptoResult<Vec<bool>> {
+    let mut classes: Vec<bool> = (0..2 * samples).map(|i| i % 2 == 1).collect();
+    let mut bytes = vec![0u8; 8 * classes.len()];
+    fill_random(&mut bytes)?;
+    // Fisher–Yates; the modulo bias of 64-bit draws is irrelevant here.
+    for i in (1..classes.len()).rev() {
+        let draw = u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap());
+        classes.swap(i, (draw % (i as u64 + 1)) as usize);
+    }
+    Ok(classes)
+}
+
+/// [`recover_secret_ct`] on fixed versus random slice values at indices `1..=3`.
+pub fn measure_recovery(config: &HarnessConfig) -> CryptoResult<TimingReport> {
+    config.validate()?;
+    let mut rng = new_rng();
+    let fixed = generate_key_shares(
+        random_scalar(&mut rng)?,
+        RECOVERY_THRESHOLD,
+        RECOVERY_THRESHOLD,
+    )?;
+    let classes = random_classes(config.samples)?;
+    let mut inputs: Vec<Vec<ShareData>> = Vec::with_capacity(classes.len());
+    for &random in &classes {
+        let mut shares = fixed.clone();
+        if random {
+            for share in &mut shares {
+                share.share = random_scalar(&mut rng)?;
+            }
+        }
+        inputs.push(shares);
+    }
+    measure("recovery", config, &inputs, &classes, |shares| {
+        recover_secret_ct(shares)
+    })
+}
+
+/// [`verify_proof`] of valid proofs for a fixed versus a random witness.
+///
+/// Proofs come from a pool of [`PROOF_POOL`] per class, each with its own nonces, since
+/// generating one per measurement costs more than the measurement.
+pub fn measure_proof_verification(config: &HarnessConfig) -> CryptoResult<TimingReport> {
+    config.validate()?;
+    let mut rng = new_rng();
+    let witness = (random_scalar(&mut rng)?, random_scalar(&mut rng)?);
+    let mut pools: [Vec<_>; 2] = [Vec::new(), Vec::new()];
+    for (class, pool) in pools.iter_mut().enumerate() {
+        for _ in 0..PROOF_POOL {
+            let (share, blinding) = if class == 1 {
+                (random_scalar(&mut rng)?, random_scalar(&mut rng)?)
+            } else {
+                witness
+            };
+            let commitment = pedersen_commit(&share, &blinding);
+            pool.push((generate_proof(share, blinding, 1, commitment)?, commitment));
+        }
+    }
+    let classes = random_classes(config.samples)?;
+    let inputs: Vec<_> = classes
+        .iter()
+        .enumerate()
+        .map(|(i, &random)| &pools[random as usize][i % PROOF_POOL])
+        .collect();
+    measure(
+        "proof_verification",
+        config,
+        &inputs,
+        &classes,
+        |(proof, commitment)| verify_proof(proof, *commitment, 1),
+    )
+}
+
+/// Tag comparison against a received tag equal to the expected one versus a random one.
+pub fn measure_mac_comparison(config: &HarnessConfig) -> CryptoResult<TimingReport> {
+    config.validate()?;
+    let mut expected = [0u8; 32];
+    fill_random(&mut expected)?;
+    let classes = random_classes(config.samples)?;
+    let mut inputs = Vec::with_capacity(classes.len());
+    for &random in &classes {
+        let mut received = expected;
+        if random {
+            fill_random(&mut received)?;
+        }
+        inputs.push(received);
+    }
+    measure("mac_comparison", config, &inputs, &classes, |received| {
+        blake3::Hash::from(*received) == blake3::Hash::from(expected)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUICK: HarnessConfig = HarnessConfig {
+        samples: 100,
+        threshold: DEFAULT_T_THRESHOLD,
+    };
+
+    #[test]
+    fn test_detects_early_exit_comparison() {
+        let classes = random_classes(QUICK.samples).unwrap();
+        let expected = vec![7u8; 4096];
+        let inputs: Vec<Vec<u8>> = classes
+            .iter()
+            .map(|&random| {
+                let mut received = expected.clone();
+                if random {
+                    received[0] ^= 1;
+                }
+                received
+            })
+            .collect();
+        let report = measure("early_exit", &QUICK, &inputs, &classes, |received| {
+            received.iter().zip(&expected).all(|(a, b)| a == b)
+        })
+        .unwrap();
+        assert!(report.leak_suspected, "{:?}", report);
+        assert!(report.fixed_mean_ns > report.random_mean_ns);
+        assert_eq!(report.samples, QUICK.samples);
+    }
+
+    #[test]
+    fn test_runs_every_target() {
+        // Timings of a test build are not evidence either way; only the report is checked.
+        let report = run(&QUICK).unwrap();
+        let targets: Vec<_> = report.targets.iter().map(|t| t.target.as_str()).collect();
+        assert_eq!(
+            targets,
+            ["recovery", "proof_verification", "mac_comparison"]
+        );
+        assert!(report.targets.iter().all(|t| t.samples == QUICK.samples));
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: HarnessReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.targets.len(), 3);
+
+        assert!(run(&HarnessConfig {
+            samples: 10,
+            ..QUICK
+        })
+        .is_err());
+        assert!(measure("mismatched", &QUICK, &[0u8; 200], &[false; 100], |x| *x).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! **custodian module**
+//!
+//! Custodian-side health checks of sealed slices.
+//!
+//! [`check_share`] unseals a stored slice locally, verifies it against the dealer's published
+//! [`VssCommitments`] (after checking their root against the published fingerprint), re-seals it
+//! under a fresh nonce and returns a [`ShareAttestation`]. The attestation carries a fresh proof of
+//! knowledge of the slice's commitment opening, so the operator can confirm the custodian still
+//! holds a valid slice without the slice ever leaving the custodian.
+//!
+//! The proof's challenge is drawn from a transcript of the operator's challenge nonce, the check
+//! time and the digest of the re-sealed envelope besides the statement itself, so an attestation
+//! answers exactly one health-check request: replaying an old one against a new nonce, or
+//! altering its time or digest, fails [`ShareAttestation::verify`].
+
+use crate::proof::{self, Proof, Transcript};
+use crate::utils::ANOTHER_POINT;
+use crate::vss::VssCommitments;
+#[cfg(feature = "dealer")]
+use crate::{
+    error::{CryptoError, CryptoResult},
+    events::{structured_log, EventCode},
+    sealing::{self, SealedShare, SealingKey},
+};
+use chrono::{DateTime, Utc};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::Scalar;
+#[cfg(feature = "dealer")]
+use log::Level;
+
+/// Version of the [`ShareAttestation`] format.
+pub const ATTESTATION_VERSION: u8 = 2;
+
+/// Shortest operator challenge nonce [`check_share`] accepts, in bytes.
+pub const MIN_CHALLENGE_LENGTH: usize = 16;
+
+/// [`Transcript`] protocol of the attestation proofs.
+const ATTESTATION_PROTOCOL: &[u8] = b"custodian-attestation";
+
+/// Statement returned by a custodian after a successful health check.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ShareAttestation {
+    /// Attestation format version, [`ATTESTATION_VERSION`].
+    pub version: u8,
+    /// Index of the checked slice.
+    pub index: usize,
+    /// Root of the commitments the slice was verified against ([`VssCommitments::root`]).
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub commitment_root: Vec<u8>,
+    /// Commitment of the slice, `Σ_j index^j · C_j`.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+    /// Operator's challenge nonce the attestation answers.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub challenge: Vec<u8>,
+    /// Fresh proof of knowledge of the opening of `commitment`, bound to `challenge`,
+    /// `sealed_digest` and `checked_at`.
+    pub proof: Proof,
+    /// Digest of the re-sealed envelope now in storage ([`SealedShare::digest`]).
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub sealed_digest: Vec<u8>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ShareAttestation {
+    /// Operator-side check of an attestation against the published commitments and the
+    /// `challenge` nonce the operator sent with the request.
+    pub fn verify(&self, commitments: &VssCommitments, challenge: &[u8]) -> bool {
+        self.version == ATTESTATION_VERSION
+            && self.index != 0
+            && self.challenge == challenge
+            && self.commitment_root == commitments.root()
+            && self.commitment == commitments.expected_commitment(self.index)
+            && proof::verify_opening(&self.proof, self.commitment, self.index, |r| {
+                proof_challenge(&self.statement(), r)
+            })
+            .is_ok()
+    }
+
+    fn statement(&self) -> Statement<'_> {
+        Statement {
+            index: self.index,
+            commitment: &self.commitment,
+            commitment_root: &self.commitment_root,
+            challenge: &self.challenge,
+            sealed_digest: &self.sealed_digest,
+            checked_at: self.checked_at.timestamp(),
+        }
+    }
+}
+
+/// What an attestation proof is bound to besides the commitment opening.
+struct Statement<'a> {
+    index: usize,
+    commitment: &'a RistrettoPoint,
+    commitment_root: &'a [u8],
+    challenge: &'a [u8],
+    sealed_digest: &'a [u8],
+    /// Unix seconds, the resolution `checked_at` is serialized with.
+    checked_at: i64,
+}
+
+/// Challenge of the opening proof with proof point `r`.
+fn proof_challenge(statement: &Statement<'_>, r: &RistrettoPoint) -> Scalar {
+    let mut transcript = Transcript::new(ATTESTATION_PROTOCOL);
+    transcript.append_point(b"G", &RISTRETTO_BASEPOINT_POINT);
+    transcript.append_point(b"H", &ANOTHER_POINT);
+    transcript.append_point(b"C", statement.commitment);
+    transcript.append_point(b"R", r);
+    transcript.append_u64(b"index", statement.index as u64);
+    transcript.append_message(b"commitment_root", statement.commitment_root);
+    transcript.append_message(b"challenge", statement.challenge);
+    transcript.append_message(b"sealed_digest", statement.sealed_digest);
+    transcript.append_message(b"checked_at", &statement.checked_at.to_le_bytes());
+    transcript.challenge_scalar(b"c")
+}
+
+/// Unseal `sealed`, verify the slice against `commitments`, re-seal it and attest to the result.
+///
+/// `expected_root` is the commitment root published by the dealer and `challenge` the operator's
+/// fresh nonce for this check. Returns the re-sealed envelope, which replaces the stored one,
+/// together with the attestation. Fails with `CryptoError::Validation` if `challenge` is shorter
+/// than [`MIN_CHALLENGE_LENGTH`], and with `CryptoError::SecurityViolation` if the commitments do
+/// not match the root, the envelope does not authenticate, or the slice does not lie on the
+/// committed polynomial.
+#[cfg(feature = "dealer")]
+pub fn check_share(
+    sealed: &SealedShare,
+    key: &SealingKey,
+    commitments: &VssCommitments,
+    expected_root: &[u8],
+    challenge: &[u8],
+) -> CryptoResult<(SealedShare, ShareAttestation)> {
+    if challenge.len() < MIN_CHALLENGE_LENGTH {
+        return Err(CryptoError::Validation {
+            field: "challenge".to_string(),
+            reason: format!(
+                "challenge nonce of {} bytes, need at least {}",
+                challenge.len(),
+                MIN_CHALLENGE_LENGTH
+            ),
+        });
+    }
+    let root = commitments.root();
+    if root.as_slice() != expected_root {
+        return Err(CryptoError::SecurityViolation {
+            details: format!(
+                "commitment root {} does not match the published root {}",
+                hex::encode(root),
+                hex::encode(expected_root)
+            ),
+        });
+    }
+
+    let share = sealing::unseal(sealed, key)?;
+    if let Err(e) = commitments.verify_share(&share) {
+        return Err(CryptoError::SecurityViolation {
+            details: format!(
+                "slice {} does not match the published commitments: {} {}",
+                share.index,
+                e.code(),
+                e
+            ),
+        });
+    }
+
+    let resealed = sealing::seal(&share, key)?;
+    let sealed_digest = resealed.digest().to_vec();
+    let checked_at = Utc::now();
+    let statement = Statement {
+        index: share.index,
+        commitment: &share.commitment,
+        commitment_root: &root,
+        challenge,
+        sealed_digest: &sealed_digest,
+        checked_at: checked_at.timestamp(),
+    };
+    let proof = proof::prove_opening(share.share, share.random, |r| {
+        proof_challenge(&statement, r)
+    })?;
+    let attestation = ShareAttestation {
+        version: ATTESTATION_VERSION,
+        index: share.index,
+        commitment_root: root.to_vec(),
+        commitment: share.commitment,
+        challenge: challenge.to_vec(),
+        proof,
+        sealed_digest,
+        checked_at,
+    };
+
+    structured_log!(
+        Level::Info,
+        EventCode::ShareHealthChecked,
+        "custodian share check passed",
+        index = share.index,
+        commitment_root = hex::encode(root)
+    );
+    Ok((resealed, attestation))
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares_with_commitments;
+    use curve25519_dalek::Scalar;
+
+    #[test]
+    fn test_custodian_check_and_attestation() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(11u64), 2, 3).unwrap();
+        let key = SealingKey::generate().unwrap();
+        let sealed = sealing::seal(&shares[2], &key).unwrap();
+        let root = commitments.root();
+        let nonce = [7u8; MIN_CHALLENGE_LENGTH];
+
+        let (resealed, attestation) =
+            check_share(&sealed, &key, &commitments, &root, &nonce).unwrap();
+        assert_ne!(resealed, sealed);
+        assert_eq!(
+            sealing::unseal(&resealed, &key).unwrap().share,
+            shares[2].share
+        );
+        assert_eq!(attestation.sealed_digest, resealed.digest());
+        assert!(attestation.verify(&commitments, &nonce));
+        let json = serde_json::to_string(&attestation).unwrap();
+        let decoded: ShareAttestation = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify(&commitments, &nonce));
+
+        // The attestation does not carry over to another dealing.
+        let (_, other) = generate_key_shares_with_commitments(Scalar::from(11u64), 2, 3).unwrap();
+        assert!(!attestation.verify(&other, &nonce));
+        assert!(matches!(
+            check_share(&sealed, &key, &other, &root, &nonce),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(check_share(&sealed, &key, &other, &other.root(), &nonce).is_err());
+        assert!(matches!(
+            check_share(&sealed, &key, &commitments, &root, &nonce[1..]),
+            Err(CryptoError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_attestation_bound_to_request() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(12u64), 2, 3).unwrap();
+        let key = SealingKey::generate().unwrap();
+        let sealed = sealing::seal(&shares[0], &key).unwrap();
+        let nonce = [1u8; MIN_CHALLENGE_LENGTH];
+        let (_, attestation) =
+            check_share(&sealed, &key, &commitments, &commitments.root(), &nonce).unwrap();
+
+        // Replayed against the next request's nonce, even with the nonce field rewritten.
+        let next = [2u8; MIN_CHALLENGE_LENGTH];
+        assert!(!attestation.verify(&commitments, &next));
+        let mut replayed = attestation.clone();
+        replayed.challenge = next.to_vec();
+        assert!(!replayed.verify(&commitments, &next));
+
+        let mut backdated = attestation.clone();
+        backdated.checked_at -= chrono::Duration::days(30);
+        assert!(!backdated.verify(&commitments, &nonce));
+        let mut other_envelope = attestation.clone();
+        other_envelope.sealed_digest = sealed.digest().to_vec();
+        assert!(!other_envelope.verify(&commitments, &nonce));
+        assert!(attestation.verify(&commitments, &nonce));
+    }
+}
@@ -0,0 +1,151 @@
+//! **deadline module**
+//!
+//! Operation-scoped deadlines and cooperative cancellation.
+//!
+//! A [`Deadline`] is attached to an [`OperationContext`](crate::OperationContext) with
+//! [`OperationContext::with_deadline`](crate::OperationContext::with_deadline). Enterprise
+//! operations run under that context (dealings, refreshes, threshold changes, recoveries and
+//! recovery ceremonies) check it before they start and inside their long loops: once per slice
+//! generated, proved or verified, per chunk of a batch verification and per instance of a batch
+//! recovery. A check past the deadline, or after the deadline's [`CancellationToken`] was
+//! cancelled from another thread, fails with `CryptoError::Timeout`, so the operation stops at
+//! the next check instead of running to completion. Audit events already logged by the operation
+//! are kept; its epoch is only claimed, and its slices only issued, once it completes.
+//!
+//! `wasm32-unknown-unknown` has no clock in `std`, so there only cancellation takes effect.
+
+use crate::error::{CryptoError, CryptoResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Shared flag to cancel an operation from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every operation checking this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// Point in time by which an operation must finish, checked cooperatively.
+#[derive(Clone, Debug)]
+pub struct Deadline {
+    /// Time budget, reported in `CryptoError::Timeout`; 0 without a time limit.
+    budget_ms: u64,
+    #[cfg(not(target_arch = "wasm32"))]
+    expires: Option<std::time::Instant>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Deadline {
+    /// A deadline `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            budget_ms: budget.as_millis().min(u128::from(u64::MAX)) as u64,
+            #[cfg(not(target_arch = "wasm32"))]
+            expires: std::time::Instant::now().checked_add(budget),
+            cancellation: None,
+        }
+    }
+
+    /// No time limit; the operation stops only when `token` is cancelled.
+    pub fn cancellable(token: CancellationToken) -> Self {
+        Self {
+            budget_ms: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            expires: None,
+            cancellation: Some(token),
+        }
+    }
+
+    /// This deadline, also stopping when `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Time left before the deadline, `None` without a time limit.
+    pub fn remaining(&self) -> Option<Duration> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self
+            .expires
+            .map(|expires| expires.saturating_duration_since(std::time::Instant::now()));
+        #[cfg(target_arch = "wasm32")]
+        return None;
+    }
+
+    /// Whether the deadline has passed or the operation was cancelled.
+    pub fn expired(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+            || self.remaining().is_some_and(|left| left.is_zero())
+    }
+
+    /// `CryptoError::Timeout` naming `operation` once [`Self::expired`].
+    pub fn check(&self, operation: &str) -> CryptoResult<()> {
+        if !self.expired() {
+            return Ok(());
+        }
+        let cancelled = self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        Err(CryptoError::Timeout {
+            operation: if cancelled {
+                format!("{} (cancelled)", operation)
+            } else {
+                operation.to_string()
+            },
+            timeout_ms: self.budget_ms,
+        })
+    }
+}
+
+/// [`Deadline::check`] of an optional deadline.
+pub(crate) fn check(deadline: Option<&Deadline>, operation: &str) -> CryptoResult<()> {
+    deadline.map_or(Ok(()), |deadline| deadline.check(operation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_expiry_and_cancellation() {
+        let generous = Deadline::after(Duration::from_secs(3600));
+        assert!(!generous.expired());
+        generous.check("dealing").unwrap();
+        assert!(generous.remaining().unwrap() > Duration::from_secs(3500));
+
+        let passed = Deadline::after(Duration::ZERO);
+        assert!(matches!(
+            passed.check("dealing"),
+            Err(CryptoError::Timeout { operation, timeout_ms: 0 }) if operation == "dealing"
+        ));
+
+        let token = CancellationToken::new();
+        let deadline = Deadline::cancellable(token.clone());
+        assert_eq!(deadline.remaining(), None);
+        deadline.check("recovery").unwrap();
+        token.cancel();
+        assert!(matches!(
+            deadline.check("recovery"),
+            Err(CryptoError::Timeout { operation, .. }) if operation == "recovery (cancelled)"
+        ));
+        let both = generous.with_cancellation(token);
+        assert!(both.expired());
+        assert!(check(None, "recovery").is_ok());
+    }
+}
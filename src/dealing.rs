@@ -0,0 +1,434 @@
+//! **dealing module**
+//!
+//! Dealing-level aggregate proofs: one proof that all `n` slice commitments of a dealing lie on
+//! the committed polynomial of degree `t - 1`, checked with one multiscalar multiplication over
+//! all commitments and a single opening proof instead of `n` slice proofs.
+//!
+//! The statement is the Pedersen-VSS commitments `A_0..A_{t-1}` and the slice commitments
+//! `(i_k, C_k)`. Weights `ρ_k = ρ^k` are drawn from a [`Transcript`] of the whole statement, and
+//! the verifier checks `Σ ρ_k·C_k = Σ_j w_j·A_j` with `w_j = Σ ρ_k·i_k^j`: if any slice is off
+//! the polynomial, this holds only with probability about `n / ℓ`. The [`AggregateProof`] also
+//! proves knowledge of an opening of `P = Σ ρ_k·C_k`, so the dealer knows the slices it
+//! committed to. Holders still check their own slice against the commitments with
+//! [`UnprovenShare::verify`]; slices needed by APIs that check per-slice proofs are proved with
+//! [`generate_share_proofs`](crate::sharing::generate_share_proofs).
+//!
+//! A [`Dealing`] carries the context needed between generation and recovery along with the slices:
+//! threshold, number of slices, dealer, [`Ciphersuite`] and creation time. [`Dealing::verify`]
+//! checks that this metadata matches the slices and commitments before checking the proof; the
+//! dealer identifier and the creation time are not covered by the proof, so a dealing received
+//! through parties that could alter it travels [`Signed`] by the dealer's
+//! [`Participant`](crate::identity::Participant) identity and is checked with
+//! [`Dealing::verify_signed`]; a single slice sent on its own is checked with
+//! [`ShareData::verify_signed`](crate::sharing::ShareData::verify_signed).
+
+use crate::error::{CryptoResult, VerifyError};
+use crate::identity::{Roster, Signed, SignedPayload};
+use crate::op_counts::{count, Op};
+use crate::proof::{self, Proof, Transcript};
+use crate::serialization::Ciphersuite;
+use crate::sharing::UnprovenShare;
+use crate::vss::VssCommitments;
+use chrono::{DateTime, Utc};
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+use curve25519_dalek::{RistrettoPoint, Scalar};
+
+/// [`Transcript`] protocol of the aggregate proofs.
+const AGGREGATE_PROTOCOL: &[u8] = b"dealing-aggregate";
+
+/// Proof that every slice commitment of a dealing lies on the committed polynomial.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct AggregateProof {
+    /// Coefficient commitments of the dealing; their length is the threshold.
+    pub commitments: VssCommitments,
+    /// Proof of knowledge of an opening of `P = Σ ρ_k·C_k`.
+    pub proof: Proof,
+}
+
+/// A whole dealing: the slices, to distribute, their aggregate proof, to publish, and the
+/// context they were dealt in.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct Dealing {
+    pub shares: Vec<UnprovenShare>,
+    pub aggregate_proof: AggregateProof,
+    pub threshold: usize,
+    pub num_shares: usize,
+    pub dealer_id: String,
+    #[serde(with = "ciphersuite_id")]
+    pub ciphersuite: Ciphersuite,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Deal `secret` as `n` slices of threshold `threshold` with one aggregate proof, on behalf of
+/// `dealer_id`.
+///
+/// # Return value
+///
+/// Returns the dealing, `CryptoError::Validation` unless `1 <= threshold <= n`, or
+/// `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_dealing(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+    dealer_id: &str,
+) -> crate::error::CryptoResult<Dealing> {
+    use chrono::SubsecRound;
+    let (shares, commitments) = crate::sharing::generate_key_shares_deferred(secret, threshold, n)?;
+    let aggregate_proof = AggregateProof::prove(&shares, commitments)?;
+    Ok(Dealing {
+        shares,
+        aggregate_proof,
+        threshold,
+        num_shares: n,
+        dealer_id: dealer_id.to_string(),
+        ciphersuite: Ciphersuite::Ristretto255Blake3,
+        // Whole seconds, as serialized, so a parsed dealing compares equal to the original.
+        created_at: Utc::now().trunc_subsecs(0),
+    })
+}
+
+impl Dealing {
+    /// Coefficient commitments of the dealing.
+    pub fn commitments(&self) -> &VssCommitments {
+        &self.aggregate_proof.commitments
+    }
+
+    /// The public part of the slices, `(index, commitment)`, as checked by
+    /// [`AggregateProof::verify`].
+    pub fn statements(&self) -> Vec<(usize, RistrettoPoint)> {
+        self.shares
+            .iter()
+            .map(|s| (s.index, s.commitment))
+            .collect()
+    }
+
+    /// Check the metadata against the slices and commitments, then [`AggregateProof::verify`]
+    /// over the slices.
+    ///
+    /// # Return value
+    ///
+    /// Returns `Ok(())` for a consistent dealing with a valid proof, `VerifyError::WrongContext`
+    /// with index 0 if `threshold` is not the number of coefficient commitments, `num_shares` is
+    /// not the number of slices or the dealer identifier is empty, and otherwise the failure of
+    /// [`AggregateProof::verify`].
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let wrong = |details: String| VerifyError::WrongContext { index: 0, details };
+        let committed = self.commitments().coefficients.len();
+        if self.threshold != committed {
+            return Err(wrong(format!(
+                "threshold {} with {} coefficient commitments",
+                self.threshold, committed
+            )));
+        }
+        if self.num_shares != self.shares.len() {
+            return Err(wrong(format!(
+                "num_shares {} with {} slices",
+                self.num_shares,
+                self.shares.len()
+            )));
+        }
+        if self.dealer_id.is_empty() {
+            return Err(wrong("dealer_id is empty".to_string()));
+        }
+        self.aggregate_proof.verify(&self.statements())
+    }
+
+    /// [`Self::verify`] of a dealing signed for `session` by `dealer` under `roster`.
+    ///
+    /// Returns `CryptoError::SecurityViolation` for another session or signer, or if the dealing
+    /// does not verify.
+    pub fn verify_signed(
+        dealing: &Signed<Self>,
+        roster: &Roster,
+        session: &str,
+        dealer: usize,
+    ) -> CryptoResult<()> {
+        dealing.verify_from(roster, session, dealer)?.verify()?;
+        Ok(())
+    }
+}
+
+impl SignedPayload for Dealing {
+    const DOMAIN: &'static str = "dealing";
+}
+
+impl AggregateProof {
+    /// Prove `shares`, dealt with the coefficient commitments `commitments`.
+    #[cfg(feature = "dealer")]
+    pub fn prove(
+        shares: &[UnprovenShare],
+        commitments: VssCommitments,
+    ) -> crate::error::CryptoResult<Self> {
+        use zeroize::Zeroize;
+        let statements: Vec<_> = shares.iter().map(|s| (s.index, s.commitment)).collect();
+        let (transcript, weights) = statement_transcript(&commitments, &statements);
+        let (mut share, mut random) = (Scalar::ZERO, Scalar::ZERO);
+        for (s, weight) in shares.iter().zip(&weights) {
+            share += weight * s.share;
+            random += weight * s.random;
+        }
+        count(Op::ScalarMul, 2 * shares.len());
+        let proof = proof::prove_opening(share, random, |r| opening_challenge(transcript, r));
+        share.zeroize();
+        random.zeroize();
+        Ok(AggregateProof {
+            commitments,
+            proof: proof?,
+        })
+    }
+
+    /// Verify the dealing whose slices have the public parts `statements`, `(index, commitment)`.
+    ///
+    /// # Return value
+    ///
+    /// Returns `Ok(())` if every commitment lies on the committed polynomial and the proof
+    /// verifies; `VerifyError::WrongContext` for an empty commitment vector, fewer slices than
+    /// the threshold or a zero or repeated index; `VerifyError::CommitmentMismatch` for the first
+    /// slice off the polynomial; and `VerifyError::ChallengeMismatch` or
+    /// `VerifyError::InvalidResponse` with index 0 if the proof itself fails.
+    pub fn verify(&self, statements: &[(usize, RistrettoPoint)]) -> Result<(), VerifyError> {
+        let threshold = self.commitments.coefficients.len();
+        let wrong = |index: usize, details: String| VerifyError::WrongContext { index, details };
+        if threshold == 0 {
+            return Err(wrong(0, "commitment vector is empty".to_string()));
+        }
+        if statements.len() < threshold {
+            return Err(wrong(
+                0,
+                format!("{} slices for threshold {}", statements.len(), threshold),
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for &(index, _) in statements {
+            if index == 0 || !seen.insert(index) {
+                return Err(wrong(index, "index is zero or repeated".to_string()));
+            }
+        }
+
+        let (transcript, weights) = statement_transcript(&self.commitments, statements);
+        // w_j = Σ ρ_k·i_k^j, accumulated with the powers of each index.
+        let mut coefficient_weights = vec![Scalar::ZERO; threshold];
+        for (&(index, _), weight) in statements.iter().zip(&weights) {
+            let x = Scalar::from(index as u64);
+            let mut term = *weight;
+            for w in coefficient_weights.iter_mut() {
+                *w += term;
+                term *= x;
+            }
+        }
+        count(Op::ScalarMul, 2 * threshold * statements.len());
+
+        let scalars = weights
+            .iter()
+            .copied()
+            .chain(coefficient_weights.iter().map(|w| -w));
+        let points = statements
+            .iter()
+            .map(|(_, commitment)| *commitment)
+            .chain(self.commitments.coefficients.iter().copied());
+        count(Op::PointMul, statements.len() + threshold);
+        if !RistrettoPoint::vartime_multiscalar_mul(scalars, points).is_identity() {
+            return Err(statements
+                .iter()
+                .find(|(index, commitment)| {
+                    self.commitments.expected_commitment(*index) != *commitment
+                })
+                .map_or(
+                    VerifyError::CommitmentMismatch { index: 0 },
+                    |&(index, _)| VerifyError::CommitmentMismatch { index },
+                ));
+        }
+
+        let combined = RistrettoPoint::vartime_multiscalar_mul(
+            &coefficient_weights,
+            &self.commitments.coefficients,
+        );
+        proof::verify_opening(&self.proof, combined, 0, |r| {
+            opening_challenge(transcript, r)
+        })
+    }
+}
+
+/// Serde of a [`Ciphersuite`] as its identifier; unknown identifiers are rejected.
+mod ciphersuite_id {
+    use crate::serialization::Ciphersuite;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(suite: &Ciphersuite, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(suite.id())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Ciphersuite, D::Error> {
+        let id = u16::deserialize(deserializer)?;
+        Ciphersuite::from_id(id)
+            .ok_or_else(|| D::Error::custom(format!("unknown ciphersuite {}", id)))
+    }
+}
+
+/// Transcript of the statement and the weights `ρ^k` drawn from it.
+fn statement_transcript(
+    commitments: &VssCommitments,
+    statements: &[(usize, RistrettoPoint)],
+) -> (Transcript, Vec<Scalar>) {
+    let mut transcript = Transcript::new(AGGREGATE_PROTOCOL);
+    transcript.append_u64(b"threshold", commitments.coefficients.len() as u64);
+    transcript.append_u64(b"n", statements.len() as u64);
+    for coefficient in &commitments.coefficients {
+        transcript.append_point(b"A", coefficient);
+    }
+    for (index, commitment) in statements {
+        transcript.append_u64(b"index", *index as u64);
+        transcript.append_point(b"C", commitment);
+    }
+    let seed = transcript.challenge_scalar(b"rho");
+    let mut weight = Scalar::ONE;
+    let weights = statements
+        .iter()
+        .map(|_| {
+            let current = weight;
+            weight *= seed;
+            current
+        })
+        .collect();
+    (transcript, weights)
+}
+
+/// Challenge of the opening proof with proof point `r`.
+fn opening_challenge(mut transcript: Transcript, r: &RistrettoPoint) -> Scalar {
+    transcript.append_point(b"R", r);
+    transcript.challenge_scalar(b"c")
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::utils::ANOTHER_POINT;
+
+    #[test]
+    fn test_aggregate_proof_covers_the_dealing() {
+        let secret = Scalar::from(41u64);
+        for (threshold, n) in [(1, 1), (1, 3), (3, 5), (4, 4), (7, 70)] {
+            let dealing = generate_dealing(secret, threshold, n, "dealer-1").unwrap();
+            dealing.verify().unwrap();
+            assert_eq!((dealing.threshold, dealing.num_shares), (threshold, n));
+            assert!(dealing
+                .shares
+                .iter()
+                .all(|s| s.verify(&dealing.aggregate_proof.commitments)));
+            let json = serde_json::to_string(&dealing).unwrap();
+            let parsed: Dealing = serde_json::from_str(&json).unwrap();
+            parsed.verify().unwrap();
+            assert_eq!(parsed.dealer_id, "dealer-1");
+            assert_eq!(parsed.ciphersuite, Ciphersuite::Ristretto255Blake3);
+            assert_eq!(parsed.created_at, dealing.created_at);
+        }
+
+        let dealing = generate_dealing(secret, 3, 6, "dealer-1").unwrap();
+        let statements = dealing.statements();
+        // A subset or reordering of the slices is another statement.
+        assert!(matches!(
+            dealing.aggregate_proof.verify(&statements[..4]),
+            Err(VerifyError::ChallengeMismatch { index: 0 })
+        ));
+
+        let mut moved = statements.clone();
+        moved[4].1 += *ANOTHER_POINT;
+        assert_eq!(
+            dealing.aggregate_proof.verify(&moved),
+            Err(VerifyError::CommitmentMismatch { index: 5 })
+        );
+        let mut swapped = statements.clone();
+        swapped.swap(1, 2);
+        assert_eq!(
+            dealing.aggregate_proof.verify(&swapped),
+            Err(VerifyError::ChallengeMismatch { index: 0 })
+        );
+        assert!(matches!(
+            dealing.aggregate_proof.verify(&statements[..2]),
+            Err(VerifyError::WrongContext { index: 0, .. })
+        ));
+        let mut repeated = statements.clone();
+        repeated[5].0 = 1;
+        assert!(matches!(
+            dealing.aggregate_proof.verify(&repeated),
+            Err(VerifyError::WrongContext { index: 1, .. })
+        ));
+
+        // The proof does not carry over to another dealing with the same commitments.
+        let other = generate_dealing(secret, 3, 6, "dealer-1").unwrap();
+        let mut forged = other.aggregate_proof.clone();
+        forged.proof = dealing.aggregate_proof.proof.clone();
+        assert!(forged.verify(&other.statements()).is_err());
+    }
+
+    #[test]
+    fn test_dealing_metadata_is_checked() {
+        let dealing = generate_dealing(Scalar::from(8u64), 2, 4, "dealer-1").unwrap();
+        let mut lowered = dealing.clone();
+        lowered.threshold = 1;
+        let mut truncated = dealing.clone();
+        truncated.shares.pop();
+        let mut anonymous = dealing.clone();
+        anonymous.dealer_id.clear();
+        for tampered in [lowered, truncated, anonymous] {
+            assert!(matches!(
+                tampered.verify(),
+                Err(VerifyError::WrongContext { index: 0, .. })
+            ));
+        }
+
+        let mut json = serde_json::to_value(&dealing).unwrap();
+        assert_eq!(json["ciphersuite"], 1);
+        json["ciphersuite"] = 7.into();
+        assert!(serde_json::from_value::<Dealing>(json).is_err());
+    }
+
+    #[test]
+    fn test_signed_dealings() {
+        use crate::error::CryptoError;
+        use crate::identity::Participant;
+        use crate::sharing::ShareData;
+
+        let dealer = Participant::from_seed(1, &[1; 32]).unwrap();
+        let rogue = Participant::from_seed(2, &[2; 32]).unwrap();
+        let roster = Roster::new(&[dealer.identity(), rogue.identity()]).unwrap();
+        let dealing = generate_dealing(Scalar::from(9u64), 2, 3, "dealer-1").unwrap();
+        let signed = dealer.sign("deal-1", dealing.clone()).unwrap();
+        assert!(Dealing::verify_signed(&signed, &roster, "deal-1", 1).is_ok());
+
+        // Replayed into another session, forged by another participant, or altered in transit.
+        let forged = rogue.sign("deal-1", dealing.clone()).unwrap();
+        let mut altered = signed.clone();
+        altered.payload.threshold = 1;
+        for (message, session) in [
+            (&signed, "deal-2"),
+            (&forged, "deal-1"),
+            (&altered, "deal-1"),
+        ] {
+            assert!(matches!(
+                Dealing::verify_signed(message, &roster, session, 1),
+                Err(CryptoError::SecurityViolation { .. })
+            ));
+        }
+
+        let shares = crate::sharing::generate_key_shares(Scalar::from(9u64), 2, 3).unwrap();
+        let signed = dealer.sign("deal-1", shares[0].clone()).unwrap();
+        assert_eq!(
+            ShareData::verify_signed(&signed, &roster, "deal-1", 1)
+                .unwrap()
+                .index,
+            1
+        );
+        assert!(ShareData::verify_signed(&signed, &roster, "deal-2", 1).is_err());
+        let forged = rogue.sign("deal-1", shares[0].clone()).unwrap();
+        assert!(ShareData::verify_signed(&forged, &roster, "deal-1", 1).is_err());
+        let mut bad_proof = shares[1].clone();
+        bad_proof.commitment = shares[0].commitment;
+        let signed = dealer.sign("deal-1", bad_proof).unwrap();
+        assert!(ShareData::verify_signed(&signed, &roster, "deal-1", 1).is_err());
+    }
+}
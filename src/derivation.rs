@@ -0,0 +1,256 @@
+//! **derivation module**
+//!
+//! Purpose-specific child keys of one master sharing, derived without a new dealing or DKG.
+//!
+//! A child is named by a path of labels (e.g. `["payments", "2024"]`). Each step adds a public
+//! derivation factor to the key, like non-hardened BIP32:
+//!
+//! ```text
+//! δ = H(Y, label)    s' = s + δ    Y' = Y + δ·G
+//! ```
+//!
+//! where `Y = s·G` is the public key of the level above. Adding the constant `δ` to the sharing
+//! polynomial shifts every slice value by `δ`, so each holder derives its own child slice from its
+//! parent slice ([`Derivation::child_share`]), the child commitments follow from the parent ones
+//! ([`Derivation::child_commitments`]) and anyone derives the child public key from the parent
+//! public key alone. The child slices recover `s'` and sign under `Y'` with the usual
+//! [`crate::lagrange_fft`] and [`crate::threshold_sig`] functions.
+//!
+//! The factors are public and the derivation depends only on `Y`, so refreshing the parent
+//! sharing leaves every child key unchanged. Since `s = s' - δ`, a leaked child secret reveals the
+//! master secret: children separate purposes, not trust domains. Keys that must stay independent
+//! need their own dealing.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::op_counts::{count, Op};
+#[cfg(feature = "dealer")]
+use crate::sharing::ShareData;
+use crate::vss::VssCommitments;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+const FACTOR_CONTEXT: &str = "zk-thresh-pro 2024 derivation factor v1";
+
+/// Public derivation factor `δ = H(Y, label)` of the child `label` of the key `parent_public_key`.
+pub fn derivation_factor(parent_public_key: &RistrettoPoint, label: &str) -> Scalar {
+    let mut hasher = Blake3Adapter::new_derive_key(FACTOR_CONTEXT);
+    digest::Update::update(&mut hasher, parent_public_key.compress().as_bytes());
+    digest::Update::update(&mut hasher, &(label.len() as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, label.as_bytes());
+    Scalar::from_hash(hasher)
+}
+
+/// Derivation of the child at `path` below a parent key, holding only public values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Derivation {
+    parent_public_key: RistrettoPoint,
+    path: Vec<String>,
+    tweak: Scalar,
+    public_key: RistrettoPoint,
+}
+
+impl Derivation {
+    /// Walk `path` from the key `parent_public_key`, one factor per label.
+    ///
+    /// Returns `CryptoError::Validation` for an empty path or an empty label.
+    pub fn new(parent_public_key: RistrettoPoint, path: &[&str]) -> CryptoResult<Self> {
+        if path.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "path".to_string(),
+                reason: "must name at least one child".to_string(),
+            });
+        }
+        let mut tweak = Scalar::ZERO;
+        let mut public_key = parent_public_key;
+        for (level, label) in path.iter().enumerate() {
+            if label.is_empty() {
+                return Err(CryptoError::Validation {
+                    field: "path".to_string(),
+                    reason: format!("label {} is empty", level),
+                });
+            }
+            let factor = derivation_factor(&public_key, label);
+            count(Op::PointMul, 1);
+            public_key += RISTRETTO_BASEPOINT_POINT * factor;
+            tweak += factor;
+        }
+        Ok(Self {
+            parent_public_key,
+            path: path.iter().map(|label| label.to_string()).collect(),
+            tweak,
+            public_key,
+        })
+    }
+
+    /// Public key of the parent the path starts from.
+    pub fn parent_public_key(&self) -> RistrettoPoint {
+        self.parent_public_key
+    }
+
+    /// Labels of the path, root first.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Sum of the factors along the path, so that `s' = s + tweak`.
+    pub fn tweak(&self) -> Scalar {
+        self.tweak
+    }
+
+    /// Child public key `Y' = Y + tweak·G`.
+    pub fn public_key(&self) -> RistrettoPoint {
+        self.public_key
+    }
+
+    /// Child secret of the parent secret `secret`, for the holder of the unshared master key.
+    pub fn child_secret(&self, secret: &Scalar) -> Scalar {
+        secret + self.tweak
+    }
+
+    /// Child verification share `Y_i + tweak·G` of a parent verification share, so partial
+    /// signatures of child slices can be checked from published parent values.
+    pub fn child_verification_share(&self, verification_share: &RistrettoPoint) -> RistrettoPoint {
+        count(Op::PointMul, 1);
+        verification_share + RISTRETTO_BASEPOINT_POINT * self.tweak
+    }
+
+    /// Commitments of the child sharing: the constant term moves by `tweak·G`, the other
+    /// coefficients are unchanged.
+    ///
+    /// Returns `CryptoError::Validation` for an empty commitment vector.
+    pub fn child_commitments(&self, parent: &VssCommitments) -> CryptoResult<VssCommitments> {
+        let mut coefficients = parent.coefficients.clone();
+        let constant = coefficients
+            .first_mut()
+            .ok_or_else(|| CryptoError::Validation {
+                field: "commitments".to_string(),
+                reason: "commitment vector is empty".to_string(),
+            })?;
+        count(Op::PointMul, 1);
+        *constant += RISTRETTO_BASEPOINT_POINT * self.tweak;
+        Ok(VssCommitments { coefficients })
+    }
+
+    /// Child slice of a parent slice: value shifted by `tweak`, same blinding, with its
+    /// commitment and a fresh proof. Each holder derives its own child slice locally.
+    ///
+    /// Returns `CryptoError::InsufficientEntropy` if the proof nonces cannot be drawn.
+    #[cfg(feature = "dealer")]
+    pub fn child_share(&self, parent: &ShareData) -> CryptoResult<ShareData> {
+        let share = parent.share + self.tweak;
+        count(Op::PointMul, 1);
+        let commitment = parent.commitment + RISTRETTO_BASEPOINT_POINT * self.tweak;
+        let proof = crate::proof::generate_proof(share, parent.random, parent.index, commitment)?;
+        Ok(ShareData {
+            index: parent.index,
+            share,
+            commitment,
+            random: parent.random,
+            proof,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::lagrange_fft::recover_secret_fft;
+    use crate::sharing::{generate_key_shares_with_commitments, refresh_shares};
+    use crate::threshold_sig::{self, verification_share};
+
+    #[test]
+    fn test_child_shares_follow_the_child_key() {
+        let secret = Scalar::from(42u64);
+        let parent_public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let (shares, commitments) = generate_key_shares_with_commitments(secret, 3, 5).unwrap();
+
+        let derivation = Derivation::new(parent_public_key, &["payments", "2024"]).unwrap();
+        let child_secret = derivation.child_secret(&secret);
+        assert_eq!(
+            derivation.public_key(),
+            RISTRETTO_BASEPOINT_POINT * child_secret
+        );
+        let step = Derivation::new(parent_public_key, &["payments"]).unwrap();
+        let nested = Derivation::new(step.public_key(), &["2024"]).unwrap();
+        assert_eq!(nested.public_key(), derivation.public_key());
+
+        let child_commitments = derivation.child_commitments(&commitments).unwrap();
+        let children: Vec<ShareData> = shares
+            .iter()
+            .map(|s| derivation.child_share(s).unwrap())
+            .collect();
+        for child in &children {
+            child_commitments.verify_share(child).unwrap();
+        }
+        assert_eq!(recover_secret_fft(&children[1..4]).unwrap(), child_secret);
+
+        // The child slices sign under the child public key.
+        let signers = &children[..3];
+        let (nonces, nonce_commitments): (Vec<_>, Vec<_>) = signers
+            .iter()
+            .map(|s| threshold_sig::commit(s).unwrap())
+            .unzip();
+        let partials: Vec<_> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, nonce)| {
+                threshold_sig::sign_partial(
+                    s,
+                    nonce,
+                    &nonce_commitments,
+                    &derivation.public_key(),
+                    b"invoice",
+                )
+                .unwrap()
+            })
+            .collect();
+        assert!(threshold_sig::verify_partial(
+            &partials[0],
+            &derivation.child_verification_share(&verification_share(&shares[0])),
+            &nonce_commitments,
+            &derivation.public_key(),
+            b"invoice",
+        ));
+        let signature = threshold_sig::aggregate(
+            &partials,
+            &nonce_commitments,
+            &derivation.public_key(),
+            b"invoice",
+        )
+        .unwrap();
+        assert!(signature.verify(&derivation.public_key(), b"invoice"));
+
+        // Refreshing the parent keeps the child key.
+        let (refreshed, record) = refresh_shares(&shares, &commitments).unwrap();
+        let child_commitments = derivation.child_commitments(&record.commitments).unwrap();
+        let children: Vec<ShareData> = refreshed
+            .iter()
+            .map(|s| derivation.child_share(s).unwrap())
+            .collect();
+        assert!(children
+            .iter()
+            .all(|c| child_commitments.verify_share(c).is_ok()));
+        assert_eq!(recover_secret_fft(&children[..3]).unwrap(), child_secret);
+    }
+
+    #[test]
+    fn test_paths_are_validated_and_separated() {
+        let parent_public_key = RISTRETTO_BASEPOINT_POINT * Scalar::from(7u64);
+        assert!(Derivation::new(parent_public_key, &[]).is_err());
+        assert!(Derivation::new(parent_public_key, &["payments", ""]).is_err());
+        let payments = Derivation::new(parent_public_key, &["payments"]).unwrap();
+        let custody = Derivation::new(parent_public_key, &["custody"]).unwrap();
+        assert_ne!(payments.public_key(), custody.public_key());
+        assert_eq!(payments.path(), ["payments".to_string()]);
+        assert!(
+            Derivation::new(parent_public_key, &["a", "bc"]).unwrap()
+                != Derivation::new(parent_public_key, &["ab", "c"]).unwrap()
+        );
+        let empty = VssCommitments {
+            coefficients: Vec::new(),
+        };
+        assert!(payments.child_commitments(&empty).is_err());
+    }
+}
@@ -0,0 +1,1076 @@
+//! **dkg module**
+//!
+//! Message-driven Pedersen distributed key generation (DKG) without a trusted dealer.
+//!
+//! Every participant acts as a Pedersen-VSS dealer of a random polynomial and the joint secret is
+//! the sum of the qualified dealers' constant terms; nobody ever learns it. The protocol runs as a
+//! [`DkgParticipant`] state machine that only consumes and produces serializable messages, so the
+//! participants can live in different processes:
+//!
+//! 1. [`DkgParticipant::round1`] broadcasts the dealer's [`VssCommitments`], its public key share
+//!    `A_i = a_{i,0}·G` and a proof that `A_i` matches the committed constant term
+//!    ([`Round1Broadcast`]). Receivers check it with [`DkgParticipant::receive_round1`].
+//! 2. [`DkgParticipant::round2`] produces one [`Round2Share`] per other participant, to be sent over
+//!    a confidential channel. [`DkgParticipant::receive_round2`] checks each slice against the
+//!    sender's commitments and returns a [`Complaint`] to broadcast if it does not match.
+//! 3. An accused dealer answers every complaint with a public [`ComplaintResponse`] revealing the
+//!    disputed slice. A dealer whose round-one broadcast or response is invalid, or who leaves a
+//!    complaint unanswered, is disqualified by every honest participant.
+//! 4. [`DkgParticipant::finalize`] sums the slices of the qualified dealers into the participant's
+//!    [`ShareData`] and returns it with the joint commitments and group public key
+//!    ([`DkgOutput`]).
+//!
+//! All broadcasts (round one, complaints and responses) must reach every participant, so that the
+//! honest participants agree on the qualified set.
+//!
+//! Messages are relayed and broadcast through parties that could forge them, so every participant
+//! signs the messages it sends with its [`Participant`](crate::identity::Participant) identity,
+//! and a [`DkgParticipant`] is built with the ceremony's [`Roster`] and session id and accepts only
+//! [`Signed`] messages of that session, signed by the dealer of a broadcast or slice, the author
+//! of a complaint or the accused answering it. Messages signed in an earlier run under the same
+//! identities are refused, so a replayed broadcast cannot make an honest dealer look
+//! equivocating and a replayed complaint cannot force a slice into the open.
+//! [`DkgParticipant::insecure_unsigned`] returns an
+//! [`UnsignedDkgParticipant`] that skips signatures, for tests and single-process simulations
+//! where no message crosses a party that could forge it.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Roster, Signed, SignedPayload};
+use crate::sharing::{self, ShareData};
+use crate::utils::{self, ANOTHER_POINT};
+use crate::vss::VssCommitments;
+use crate::{lagrange_fft, proof};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const CONSTANT_TERM_CONTEXT: &str = "zk-thresh-pro 2024 dkg constant term v1";
+
+/// Proof of knowledge of `(a, b)` with `A = a·G` and `C_0 = a·G + b·H`.
+///
+/// Binds a dealer's public key share to its committed constant term and prevents rogue-key
+/// contributions.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConstantTermProof {
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    nonce_key: RistrettoPoint,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    nonce_commitment: RistrettoPoint,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    response_secret: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    response_blinding: Scalar,
+}
+
+fn constant_term_challenge(
+    sender: usize,
+    public_key_share: &RistrettoPoint,
+    constant_commitment: &RistrettoPoint,
+    nonce_key: &RistrettoPoint,
+    nonce_commitment: &RistrettoPoint,
+) -> Scalar {
+    let mut hasher = Blake3Adapter::new_derive_key(CONSTANT_TERM_CONTEXT);
+    digest::Update::update(&mut hasher, &(sender as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, public_key_share.compress().as_bytes());
+    digest::Update::update(&mut hasher, constant_commitment.compress().as_bytes());
+    digest::Update::update(&mut hasher, nonce_key.compress().as_bytes());
+    digest::Update::update(&mut hasher, nonce_commitment.compress().as_bytes());
+    Scalar::from_hash(hasher)
+}
+
+impl ConstantTermProof {
+    fn prove(sender: usize, secret: Scalar, blinding: Scalar) -> CryptoResult<Self> {
+        let mut rng = utils::new_rng();
+        let k_secret = utils::random_scalar(&mut rng)?;
+        let k_blinding = utils::random_scalar(&mut rng)?;
+        let nonce_key = RISTRETTO_BASEPOINT_POINT * k_secret;
+        let nonce_commitment = nonce_key + (*ANOTHER_POINT) * k_blinding;
+        let public_key_share = RISTRETTO_BASEPOINT_POINT * secret;
+        let constant_commitment = public_key_share + (*ANOTHER_POINT) * blinding;
+        let c = constant_term_challenge(
+            sender,
+            &public_key_share,
+            &constant_commitment,
+            &nonce_key,
+            &nonce_commitment,
+        );
+        Ok(Self {
+            nonce_key,
+            nonce_commitment,
+            response_secret: k_secret + c * secret,
+            response_blinding: k_blinding + c * blinding,
+        })
+    }
+
+    fn verify(
+        &self,
+        sender: usize,
+        public_key_share: &RistrettoPoint,
+        constant_commitment: &RistrettoPoint,
+    ) -> bool {
+        let c = constant_term_challenge(
+            sender,
+            public_key_share,
+            constant_commitment,
+            &self.nonce_key,
+            &self.nonce_commitment,
+        );
+        let g_part = RISTRETTO_BASEPOINT_POINT * self.response_secret;
+        g_part == self.nonce_key + public_key_share * c
+            && g_part + (*ANOTHER_POINT) * self.response_blinding
+                == self.nonce_commitment + constant_commitment * c
+    }
+}
+
+/// Round-one broadcast of one dealer.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Round1Broadcast {
+    pub sender: usize,
+    /// Pedersen-VSS commitments to the dealer's sharing and blinding polynomials.
+    pub commitments: VssCommitments,
+    /// `A_i = a_{i,0}·G`, the dealer's contribution to the group public key.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub public_key_share: RistrettoPoint,
+    pub proof: ConstantTermProof,
+}
+
+impl SignedPayload for Round1Broadcast {
+    const DOMAIN: &'static str = "dkg round1 broadcast";
+}
+
+/// Round-two slice `(f_i(j), g_i(j))` from dealer `sender` to participant `recipient`.
+///
+/// Must travel over a confidential, authenticated channel. `Debug` output is redacted and the
+/// slice is zeroized on drop.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Round2Share {
+    #[zeroize(skip)]
+    pub sender: usize,
+    #[zeroize(skip)]
+    pub recipient: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub share: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub random: Scalar,
+}
+
+impl fmt::Debug for Round2Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Round2Share")
+            .field("sender", &self.sender)
+            .field("recipient", &self.recipient)
+            .field("share", &crate::redact::Redacted(&self.share))
+            .field("random", &crate::redact::Redacted(&self.random))
+            .finish()
+    }
+}
+
+impl SignedPayload for Round2Share {
+    const DOMAIN: &'static str = "dkg round2 share";
+}
+
+/// Broadcast accusation that `accused` sent `complainant` an invalid slice, or none at all.
+#[derive(
+    serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct Complaint {
+    pub accused: usize,
+    pub complainant: usize,
+}
+
+impl SignedPayload for Complaint {
+    const DOMAIN: &'static str = "dkg complaint";
+}
+
+/// Broadcast answer of `accused` to a complaint, publicly revealing the disputed slice.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComplaintResponse {
+    pub accused: usize,
+    pub complainant: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub share: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub random: Scalar,
+}
+
+impl SignedPayload for ComplaintResponse {
+    const DOMAIN: &'static str = "dkg complaint response";
+}
+
+/// Result of a completed DKG for one participant.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct DkgOutput {
+    /// The participant's slice of the joint secret.
+    pub share: ShareData,
+    /// Joint commitments `Σ_{i∈QUAL} C_{i,j}`; every participant's slice verifies against them.
+    pub commitments: VssCommitments,
+    /// Group public key `Σ_{i∈QUAL} A_i`.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub group_public_key: RistrettoPoint,
+    /// Qualified dealers, in increasing order.
+    pub qualified: Vec<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Created,
+    Round1,
+    Round2,
+    Finalized,
+}
+
+/// One participant of a Pedersen DKG; see the module documentation for the message flow.
+pub struct DkgParticipant {
+    index: usize,
+    threshold: usize,
+    participants: usize,
+    phase: Phase,
+    polynomial: Vec<Scalar>,
+    blinding: Vec<Scalar>,
+    broadcasts: BTreeMap<usize, Round1Broadcast>,
+    /// Valid slices received for this participant, by dealer.
+    slices: BTreeMap<usize, (Scalar, Scalar)>,
+    complaints: BTreeSet<Complaint>,
+    answered: BTreeSet<Complaint>,
+    disqualified: BTreeSet<usize>,
+    /// Session and identities messages must be signed with; `None` only inside an
+    /// [`UnsignedDkgParticipant`].
+    roster: Option<(String, Roster)>,
+}
+
+impl fmt::Debug for DkgParticipant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DkgParticipant")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("participants", &self.participants)
+            .field("phase", &self.phase)
+            .field("disqualified", &self.disqualified)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for DkgParticipant {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl DkgParticipant {
+    /// Participant `index` of a `threshold`-of-`participants` DKG, accepting only messages signed
+    /// for `session` by their author under `roster`; indices run from 1 to `participants`.
+    ///
+    /// `session` must be unique to this run, e.g. the operation id of its
+    /// [`Ceremony`](crate::ceremony::Ceremony). Returns `CryptoError::Validation` for invalid
+    /// parameters, an empty `session` or if `roster` lacks the identity of a participant.
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+        session: &str,
+        roster: Roster,
+    ) -> CryptoResult<Self> {
+        let mut participant = Self::unsigned(index, threshold, participants)?;
+        let missing: Vec<usize> = (1..=participants)
+            .filter(|&i| roster.public_key(i).is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "roster".to_string(),
+                reason: format!("no identity for participants {:?}", missing),
+            });
+        }
+        if session.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "session".to_string(),
+                reason: "a signed DKG needs a session id".to_string(),
+            });
+        }
+        participant.roster = Some((session.to_string(), roster));
+        Ok(participant)
+    }
+
+    /// A participant that accepts unsigned messages, so any party relaying them can forge
+    /// broadcasts, slices and complaints in another participant's name. Only for tests and
+    /// simulations where every message stays in one trusted process.
+    pub fn insecure_unsigned(
+        index: usize,
+        threshold: usize,
+        participants: usize,
+    ) -> CryptoResult<UnsignedDkgParticipant> {
+        Self::unsigned(index, threshold, participants).map(UnsignedDkgParticipant)
+    }
+
+    fn unsigned(index: usize, threshold: usize, participants: usize) -> CryptoResult<Self> {
+        sharing::validate_parameters(threshold, participants)?;
+        if index == 0 || index > participants {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: format!("index must be in 1..={}, got {}", participants, index),
+            });
+        }
+        Ok(Self {
+            index,
+            threshold,
+            participants,
+            phase: Phase::Created,
+            polynomial: Vec::new(),
+            blinding: Vec::new(),
+            broadcasts: BTreeMap::new(),
+            slices: BTreeMap::new(),
+            complaints: BTreeSet::new(),
+            answered: BTreeSet::new(),
+            disqualified: BTreeSet::new(),
+            roster: None,
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Dealers disqualified so far.
+    pub fn disqualified(&self) -> Vec<usize> {
+        self.disqualified.iter().copied().collect()
+    }
+
+    /// The payload of `message`, signed by `author` for the session under the roster.
+    fn verify_signed<'a, T: SignedPayload>(
+        &self,
+        message: &'a Signed<T>,
+        author: usize,
+        step: &str,
+    ) -> CryptoResult<&'a T> {
+        let (session, roster) = self
+            .roster
+            .as_ref()
+            .ok_or_else(|| CryptoError::Validation {
+                field: "roster".to_string(),
+                reason: format!("{} needs a roster", step),
+            })?;
+        message.verify_from(roster, session, author)
+    }
+
+    fn expect_phase(&self, allowed: &[Phase], step: &str) -> CryptoResult<()> {
+        if allowed.contains(&self.phase) {
+            Ok(())
+        } else {
+            Err(CryptoError::Validation {
+                field: "phase".to_string(),
+                reason: format!("{} is not allowed in phase {:?}", step, self.phase),
+            })
+        }
+    }
+
+    fn check_peer(&self, field: &str, index: usize) -> CryptoResult<()> {
+        if index == 0 || index > self.participants {
+            return Err(CryptoError::Validation {
+                field: field.to_string(),
+                reason: format!("unknown participant {}", index),
+            });
+        }
+        Ok(())
+    }
+
+    fn wipe(&mut self) {
+        self.polynomial.zeroize();
+        self.blinding.zeroize();
+        for (share, random) in self.slices.values_mut() {
+            share.zeroize();
+            random.zeroize();
+        }
+    }
+
+    /// Whether `(share, random)` is the slice of `dealer` at `recipient`.
+    fn slice_is_valid(
+        &self,
+        dealer: usize,
+        recipient: usize,
+        share: Scalar,
+        random: Scalar,
+    ) -> bool {
+        self.broadcasts.get(&dealer).is_some_and(|b| {
+            RISTRETTO_BASEPOINT_POINT * share + (*ANOTHER_POINT) * random
+                == b.commitments.expected_commitment(recipient)
+        })
+    }
+
+    /// Round one: draw this dealer's polynomials and return the broadcast.
+    pub fn round1(&mut self) -> CryptoResult<Round1Broadcast> {
+        self.expect_phase(&[Phase::Created], "round1")?;
+        let mut rng = utils::new_rng();
+        let polynomial = (0..self.threshold)
+            .map(|_| utils::random_scalar(&mut rng))
+            .collect::<CryptoResult<Vec<_>>>()?;
+        let blinding = (0..self.threshold)
+            .map(|_| utils::random_scalar(&mut rng))
+            .collect::<CryptoResult<Vec<_>>>()?;
+        let commitments = VssCommitments {
+            coefficients: polynomial
+                .iter()
+                .zip(&blinding)
+                .map(|(a, b)| RISTRETTO_BASEPOINT_POINT * a + (*ANOTHER_POINT) * b)
+                .collect(),
+        };
+        let broadcast = Round1Broadcast {
+            sender: self.index,
+            commitments,
+            public_key_share: RISTRETTO_BASEPOINT_POINT * polynomial[0],
+            proof: ConstantTermProof::prove(self.index, polynomial[0], blinding[0])?,
+        };
+
+        let x = Scalar::from(self.index as u64);
+        self.slices.insert(
+            self.index,
+            (
+                lagrange_fft::poly_evaluate(&polynomial, x),
+                lagrange_fft::poly_evaluate(&blinding, x),
+            ),
+        );
+        self.polynomial = polynomial;
+        self.blinding = blinding;
+        self.broadcasts.insert(self.index, broadcast.clone());
+        self.phase = Phase::Round1;
+        Ok(broadcast)
+    }
+
+    /// Accept another dealer's round-one broadcast, signed by that dealer.
+    ///
+    /// A bad signature returns `CryptoError::SecurityViolation`. A malformed broadcast, an invalid
+    /// constant-term proof or a conflicting second broadcast disqualifies the sender and returns
+    /// `CryptoError::SecurityViolation`.
+    pub fn receive_round1(&mut self, message: Signed<Round1Broadcast>) -> CryptoResult<()> {
+        let broadcast = self.verify_signed(&message, message.payload.sender, "receive_round1")?;
+        self.accept_round1(broadcast.clone())
+    }
+
+    fn accept_round1(&mut self, broadcast: Round1Broadcast) -> CryptoResult<()> {
+        self.expect_phase(&[Phase::Created, Phase::Round1], "receive_round1")?;
+        let sender = broadcast.sender;
+        self.check_peer("sender", sender)?;
+        if let Some(existing) = self.broadcasts.get(&sender) {
+            if *existing == broadcast {
+                return Ok(());
+            }
+            self.broadcasts.remove(&sender);
+            self.disqualified.insert(sender);
+            return Err(CryptoError::SecurityViolation {
+                details: format!("dealer {} sent conflicting round-one broadcasts", sender),
+            });
+        }
+        let valid = broadcast.commitments.threshold() == self.threshold
+            && broadcast.proof.verify(
+                sender,
+                &broadcast.public_key_share,
+                &broadcast.commitments.coefficients[0],
+            );
+        if !valid || self.disqualified.contains(&sender) {
+            self.disqualified.insert(sender);
+            return Err(CryptoError::SecurityViolation {
+                details: format!("invalid round-one broadcast from dealer {}", sender),
+            });
+        }
+        self.broadcasts.insert(sender, broadcast);
+        Ok(())
+    }
+
+    /// Round two: this dealer's slices for every other participant.
+    ///
+    /// Requires a round-one broadcast (valid or disqualified) from every participant.
+    pub fn round2(&mut self) -> CryptoResult<Vec<Round2Share>> {
+        self.expect_phase(&[Phase::Round1], "round2")?;
+        let missing: Vec<usize> = (1..=self.participants)
+            .filter(|i| !self.broadcasts.contains_key(i) && !self.disqualified.contains(i))
+            .collect();
+        if !missing.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "round1".to_string(),
+                reason: format!("no round-one broadcast from {:?}", missing),
+            });
+        }
+        let shares = (1..=self.participants)
+            .filter(|&j| j != self.index)
+            .map(|j| {
+                let x = Scalar::from(j as u64);
+                Round2Share {
+                    sender: self.index,
+                    recipient: j,
+                    share: lagrange_fft::poly_evaluate(&self.polynomial, x),
+                    random: lagrange_fft::poly_evaluate(&self.blinding, x),
+                }
+            })
+            .collect();
+        self.phase = Phase::Round2;
+        Ok(shares)
+    }
+
+    /// Accept a round-two slice addressed to this participant, signed by its dealer.
+    ///
+    /// Returns the complaint to broadcast if the slice does not match the sender's commitments.
+    pub fn receive_round2(
+        &mut self,
+        message: Signed<Round2Share>,
+    ) -> CryptoResult<Option<Complaint>> {
+        let share = self.verify_signed(&message, message.payload.sender, "receive_round2")?;
+        self.accept_round2(share.clone())
+    }
+
+    fn accept_round2(&mut self, share: Round2Share) -> CryptoResult<Option<Complaint>> {
+        self.expect_phase(&[Phase::Round1, Phase::Round2], "receive_round2")?;
+        if share.recipient != self.index {
+            return Err(CryptoError::Validation {
+                field: "recipient".to_string(),
+                reason: format!("slice for {} delivered to {}", share.recipient, self.index),
+            });
+        }
+        self.check_peer("sender", share.sender)?;
+        if !self.broadcasts.contains_key(&share.sender) {
+            return Err(CryptoError::Validation {
+                field: "sender".to_string(),
+                reason: format!("no round-one broadcast from dealer {}", share.sender),
+            });
+        }
+        if self.slice_is_valid(share.sender, self.index, share.share, share.random) {
+            self.slices
+                .insert(share.sender, (share.share, share.random));
+            return Ok(None);
+        }
+        let complaint = Complaint {
+            accused: share.sender,
+            complainant: self.index,
+        };
+        self.complaints.insert(complaint);
+        Ok(Some(complaint))
+    }
+
+    /// Complaints against every qualified dealer whose slice has not arrived.
+    ///
+    /// Broadcast them once the delivery deadline for round two has passed.
+    pub fn complain_missing(&mut self) -> Vec<Complaint> {
+        let missing: Vec<Complaint> = self
+            .broadcasts
+            .keys()
+            .filter(|dealer| !self.slices.contains_key(dealer))
+            .map(|&accused| Complaint {
+                accused,
+                complainant: self.index,
+            })
+            .collect();
+        self.complaints.extend(missing.iter().copied());
+        missing
+    }
+
+    /// Record a broadcast complaint, signed by the complainant; if it accuses this participant,
+    /// return the public answer.
+    pub fn receive_complaint(
+        &mut self,
+        message: Signed<Complaint>,
+    ) -> CryptoResult<Option<ComplaintResponse>> {
+        let complaint =
+            *self.verify_signed(&message, message.payload.complainant, "receive_complaint")?;
+        self.accept_complaint(complaint)
+    }
+
+    fn accept_complaint(
+        &mut self,
+        complaint: Complaint,
+    ) -> CryptoResult<Option<ComplaintResponse>> {
+        self.expect_phase(&[Phase::Round1, Phase::Round2], "receive_complaint")?;
+        self.check_peer("accused", complaint.accused)?;
+        self.check_peer("complainant", complaint.complainant)?;
+        self.complaints.insert(complaint);
+        if complaint.accused != self.index || self.polynomial.is_empty() {
+            return Ok(None);
+        }
+        let x = Scalar::from(complaint.complainant as u64);
+        Ok(Some(ComplaintResponse {
+            accused: self.index,
+            complainant: complaint.complainant,
+            share: lagrange_fft::poly_evaluate(&self.polynomial, x),
+            random: lagrange_fft::poly_evaluate(&self.blinding, x),
+        }))
+    }
+
+    /// Check a broadcast complaint response, signed by the accused dealer.
+    ///
+    /// A valid answer resolves the complaint (and hands the complainant its slice); an invalid one
+    /// disqualifies the accused dealer and returns `CryptoError::SecurityViolation`.
+    pub fn receive_complaint_response(
+        &mut self,
+        message: Signed<ComplaintResponse>,
+    ) -> CryptoResult<()> {
+        let response = self.verify_signed(
+            &message,
+            message.payload.accused,
+            "receive_complaint_response",
+        )?;
+        self.accept_complaint_response(response.clone())
+    }
+
+    fn accept_complaint_response(&mut self, response: ComplaintResponse) -> CryptoResult<()> {
+        self.expect_phase(
+            &[Phase::Round1, Phase::Round2],
+            "receive_complaint_response",
+        )?;
+        let complaint = Complaint {
+            accused: response.accused,
+            complainant: response.complainant,
+        };
+        if !self.complaints.contains(&complaint) {
+            return Err(CryptoError::Validation {
+                field: "response".to_string(),
+                reason: format!(
+                    "no complaint by {} against {}",
+                    complaint.complainant, complaint.accused
+                ),
+            });
+        }
+        if !self.slice_is_valid(
+            complaint.accused,
+            complaint.complainant,
+            response.share,
+            response.random,
+        ) {
+            self.disqualified.insert(complaint.accused);
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "dealer {} answered a complaint with an invalid slice",
+                    complaint.accused
+                ),
+            });
+        }
+        self.answered.insert(complaint);
+        if complaint.complainant == self.index {
+            self.slices
+                .insert(complaint.accused, (response.share, response.random));
+        }
+        Ok(())
+    }
+
+    /// Qualified dealers: valid round-one broadcast, every complaint answered validly.
+    fn qualified(&self) -> Vec<usize> {
+        self.broadcasts
+            .keys()
+            .copied()
+            .filter(|dealer| {
+                !self.disqualified.contains(dealer)
+                    && self
+                        .complaints
+                        .iter()
+                        .filter(|c| c.accused == *dealer)
+                        .all(|c| self.answered.contains(c))
+            })
+            .collect()
+    }
+
+    /// Finish the protocol after the complaint phase.
+    ///
+    /// Unanswered complaints disqualify the accused dealer. Returns `CryptoError::Validation` if a
+    /// slice from a qualified dealer is still missing (call [`Self::complain_missing`] first).
+    pub fn finalize(&mut self) -> CryptoResult<DkgOutput> {
+        self.expect_phase(&[Phase::Round2], "finalize")?;
+        let qualified = self.qualified();
+        let missing: Vec<usize> = qualified
+            .iter()
+            .copied()
+            .filter(|dealer| !self.slices.contains_key(dealer))
+            .collect();
+        if !missing.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "round2".to_string(),
+                reason: format!("missing slices from qualified dealers {:?}", missing),
+            });
+        }
+
+        let mut share = Scalar::ZERO;
+        let mut random = Scalar::ZERO;
+        let mut coefficients = vec![RistrettoPoint::default(); self.threshold];
+        let mut group_public_key = RistrettoPoint::default();
+        for dealer in &qualified {
+            let (s, r) = self.slices[dealer];
+            share += s;
+            random += r;
+            let broadcast = &self.broadcasts[dealer];
+            for (sum, c) in coefficients
+                .iter_mut()
+                .zip(&broadcast.commitments.coefficients)
+            {
+                *sum += c;
+            }
+            group_public_key += broadcast.public_key_share;
+        }
+        let commitments = VssCommitments { coefficients };
+        let commitment = RISTRETTO_BASEPOINT_POINT * share + (*ANOTHER_POINT) * random;
+        let share = ShareData {
+            index: self.index,
+            share,
+            commitment,
+            random,
+            proof: proof::generate_proof(share, random, self.index, commitment)?,
+        };
+        if commitments.verify_share(&share).is_err() {
+            return Err(CryptoError::CryptographicOperation {
+                operation: "dkg share verification".to_string(),
+            });
+        }
+
+        self.wipe();
+        self.phase = Phase::Finalized;
+        Ok(DkgOutput {
+            share,
+            commitments,
+            group_public_key,
+            qualified,
+        })
+    }
+}
+
+/// A [`DkgParticipant`] that accepts unsigned messages; see
+/// [`DkgParticipant::insecure_unsigned`].
+///
+/// Runs the same protocol with the same checks, except that nothing attributes a message to its
+/// author.
+#[derive(Debug)]
+pub struct UnsignedDkgParticipant(DkgParticipant);
+
+impl UnsignedDkgParticipant {
+    pub fn index(&self) -> usize {
+        self.0.index()
+    }
+
+    /// See [`DkgParticipant::disqualified`].
+    pub fn disqualified(&self) -> Vec<usize> {
+        self.0.disqualified()
+    }
+
+    /// See [`DkgParticipant::round1`].
+    pub fn round1(&mut self) -> CryptoResult<Round1Broadcast> {
+        self.0.round1()
+    }
+
+    /// [`DkgParticipant::receive_round1`] without a signature.
+    pub fn receive_round1(&mut self, broadcast: Round1Broadcast) -> CryptoResult<()> {
+        self.0.accept_round1(broadcast)
+    }
+
+    /// See [`DkgParticipant::round2`].
+    pub fn round2(&mut self) -> CryptoResult<Vec<Round2Share>> {
+        self.0.round2()
+    }
+
+    /// [`DkgParticipant::receive_round2`] without a signature.
+    pub fn receive_round2(&mut self, share: Round2Share) -> CryptoResult<Option<Complaint>> {
+        self.0.accept_round2(share)
+    }
+
+    /// See [`DkgParticipant::complain_missing`].
+    pub fn complain_missing(&mut self) -> Vec<Complaint> {
+        self.0.complain_missing()
+    }
+
+    /// [`DkgParticipant::receive_complaint`] without a signature.
+    pub fn receive_complaint(
+        &mut self,
+        complaint: Complaint,
+    ) -> CryptoResult<Option<ComplaintResponse>> {
+        self.0.accept_complaint(complaint)
+    }
+
+    /// [`DkgParticipant::receive_complaint_response`] without a signature.
+    pub fn receive_complaint_response(&mut self, response: ComplaintResponse) -> CryptoResult<()> {
+        self.0.accept_complaint_response(response)
+    }
+
+    /// See [`DkgParticipant::finalize`].
+    pub fn finalize(&mut self) -> CryptoResult<DkgOutput> {
+        self.0.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::Participant;
+    use crate::lagrange_fft::recover_secret_fft;
+
+    const SESSION: &str = "dkg-test";
+
+    fn identities(n: usize) -> (Vec<Participant>, Roster) {
+        let identities: Vec<Participant> = (1..=n)
+            .map(|i| Participant::from_seed(i, &[i as u8; 32]).unwrap())
+            .collect();
+        let roster =
+            Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>()).unwrap();
+        (identities, roster)
+    }
+
+    fn participants(threshold: usize, n: usize) -> (Vec<DkgParticipant>, Vec<Participant>) {
+        let (identities, roster) = identities(n);
+        let parties = (1..=n)
+            .map(|i| DkgParticipant::new(i, threshold, n, SESSION, roster.clone()).unwrap())
+            .collect();
+        (parties, identities)
+    }
+
+    /// Exchange signed round-one broadcasts over JSON.
+    fn run_round1(parties: &mut [DkgParticipant], identities: &[Participant]) {
+        let broadcasts: Vec<String> = parties
+            .iter_mut()
+            .zip(identities)
+            .map(|(p, id)| {
+                serde_json::to_string(&id.sign(SESSION, p.round1().unwrap()).unwrap()).unwrap()
+            })
+            .collect();
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(serde_json::from_str(b).unwrap()).unwrap();
+            }
+        }
+    }
+
+    fn assert_consistent(outputs: &[DkgOutput], qualified: &[usize]) {
+        for o in outputs {
+            assert_eq!(o.qualified, qualified);
+            assert_eq!(o.commitments, outputs[0].commitments);
+            assert_eq!(o.group_public_key, outputs[0].group_public_key);
+            assert!(o.commitments.verify_share(&o.share).is_ok());
+        }
+        let shares: Vec<ShareData> = outputs.iter().map(|o| o.share.clone()).collect();
+        let secret = recover_secret_fft(&shares[..2]).unwrap();
+        assert_eq!(
+            RISTRETTO_BASEPOINT_POINT * secret,
+            outputs[0].group_public_key
+        );
+    }
+
+    #[test]
+    fn test_honest_dkg() {
+        let (mut parties, identities) = participants(2, 3);
+        run_round1(&mut parties, &identities);
+        let messages: Vec<Signed<Round2Share>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .flat_map(|(p, id)| {
+                p.round2()
+                    .unwrap()
+                    .into_iter()
+                    .map(|m| id.sign(SESSION, m).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for m in messages {
+            let wire = serde_json::to_string(&m).unwrap();
+            let complaint = parties[m.payload.recipient - 1]
+                .receive_round2(serde_json::from_str(&wire).unwrap())
+                .unwrap();
+            assert!(complaint.is_none());
+        }
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finalize().unwrap()).collect();
+        assert_consistent(&outputs, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_complaints_resolve_or_disqualify() {
+        // Dealer 1 corrupts its slice for 2 but answers the complaint; dealer 3 never sends to 2
+        // and stays silent.
+        let (mut parties, identities) = participants(2, 3);
+        run_round1(&mut parties, &identities);
+        let mut messages: Vec<Round2Share> = parties
+            .iter_mut()
+            .flat_map(|p| p.round2().unwrap())
+            .collect();
+        messages.retain(|m| !(m.sender == 3 && m.recipient == 2));
+        let mut complaints = Vec::new();
+        for mut m in messages {
+            if m.sender == 1 && m.recipient == 2 {
+                m.share += Scalar::ONE;
+            }
+            let signed = identities[m.sender - 1].sign(SESSION, m).unwrap();
+            complaints.extend(
+                parties[signed.payload.recipient - 1]
+                    .receive_round2(signed)
+                    .unwrap(),
+            );
+        }
+        assert_eq!(
+            complaints,
+            [Complaint {
+                accused: 1,
+                complainant: 2
+            }]
+        );
+        complaints.extend(parties[1].complain_missing());
+
+        // Every party hears the complaints; only a silenced dealer 3's answer is dropped.
+        let complaints: Vec<Signed<Complaint>> = complaints
+            .into_iter()
+            .map(|c| identities[c.complainant - 1].sign(SESSION, c).unwrap())
+            .collect();
+        let mut responses = Vec::new();
+        for p in parties.iter_mut() {
+            for c in &complaints {
+                responses.extend(p.receive_complaint(c.clone()).unwrap());
+            }
+        }
+        responses.retain(|r| r.accused != 3);
+        let responses: Vec<Signed<ComplaintResponse>> = responses
+            .into_iter()
+            .map(|r| identities[r.accused - 1].sign(SESSION, r).unwrap())
+            .collect();
+        for p in parties.iter_mut() {
+            for r in &responses {
+                p.receive_complaint_response(r.clone()).unwrap();
+            }
+        }
+
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finalize().unwrap()).collect();
+        assert_consistent(&outputs, &[1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_round1_and_phases() {
+        let (mut parties, identities) = participants(2, 2);
+        let mut forged = parties[0].round1().unwrap();
+        forged.public_key_share = RISTRETTO_BASEPOINT_POINT;
+        assert!(matches!(
+            parties[1].receive_round1(identities[0].sign(SESSION, forged).unwrap()),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert_eq!(parties[1].disqualified(), vec![1]);
+
+        assert!(parties[1].finalize().is_err());
+        assert!(parties[0].round1().is_err());
+        let (_, roster) = super::tests::identities(2);
+        assert!(DkgParticipant::new(3, 2, 2, SESSION, roster.clone()).is_err());
+        assert!(DkgParticipant::new(1, 3, 2, SESSION, roster.clone()).is_err());
+        assert!(DkgParticipant::new(1, 2, 2, "", roster.clone()).is_err());
+        // Every participant needs an identity in the roster.
+        assert!(DkgParticipant::new(1, 2, 3, SESSION, roster).is_err());
+    }
+
+    #[test]
+    fn test_signed_dkg_rejects_injected_messages() {
+        let (mut parties, identities) = participants(2, 3);
+        let broadcasts: Vec<Signed<Round1Broadcast>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .map(|(p, id)| id.sign(SESSION, p.round1().unwrap()).unwrap())
+            .collect();
+        // Participant 3 relays dealer 2's broadcast under its own signature.
+        let relayed = identities[2]
+            .sign(SESSION, broadcasts[1].payload.clone())
+            .unwrap();
+        assert!(matches!(
+            parties[0].receive_round1(relayed),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut unsigned = broadcasts[1].clone();
+        unsigned.signature = vec![0; 64];
+        assert!(parties[0].receive_round1(unsigned).is_err());
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(b.clone()).unwrap();
+            }
+        }
+
+        let messages: Vec<Signed<Round2Share>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .flat_map(|(p, id)| {
+                p.round2()
+                    .unwrap()
+                    .into_iter()
+                    .map(|m| id.sign(SESSION, m).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for m in messages {
+            let recipient = m.payload.recipient;
+            assert!(parties[recipient - 1].receive_round2(m).unwrap().is_none());
+        }
+
+        // A complaint forged in participant 2's name does not force dealer 1 to reveal a slice.
+        let forged = identities[2]
+            .sign(
+                SESSION,
+                Complaint {
+                    accused: 1,
+                    complainant: 2,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            parties[0].receive_complaint(forged),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finalize().unwrap()).collect();
+        assert_consistent(&outputs, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_messages_of_earlier_session_rejected() {
+        // The same identities ran an earlier DKG whose signed messages an attacker recorded.
+        let (mut earlier, identities) = participants(2, 3);
+        let old_broadcast = identities[0]
+            .sign("dkg-earlier", earlier[0].round1().unwrap())
+            .unwrap();
+        let old_complaint = identities[1]
+            .sign(
+                "dkg-earlier",
+                Complaint {
+                    accused: 1,
+                    complainant: 2,
+                },
+            )
+            .unwrap();
+
+        let (mut parties, _) = participants(2, 3);
+        let broadcast = identities[0]
+            .sign(SESSION, parties[0].round1().unwrap())
+            .unwrap();
+        parties[1].receive_round1(broadcast).unwrap();
+        // The replayed broadcast neither verifies nor makes dealer 1 look equivocating.
+        assert!(matches!(
+            parties[1].receive_round1(old_broadcast),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(parties[1].disqualified().is_empty());
+        // A replayed complaint does not force dealer 1 to reveal participant 2's slice.
+        assert!(matches!(
+            parties[0].receive_complaint(old_complaint),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_insecure_unsigned_dkg() {
+        let mut parties: Vec<UnsignedDkgParticipant> = (1..=3)
+            .map(|i| DkgParticipant::insecure_unsigned(i, 2, 3).unwrap())
+            .collect();
+        let broadcasts: Vec<Round1Broadcast> =
+            parties.iter_mut().map(|p| p.round1().unwrap()).collect();
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(b.clone()).unwrap();
+            }
+        }
+        let messages: Vec<Round2Share> = parties
+            .iter_mut()
+            .flat_map(|p| p.round2().unwrap())
+            .collect();
+        for m in messages {
+            assert!(parties[m.recipient - 1]
+                .receive_round2(m)
+                .unwrap()
+                .is_none());
+        }
+        let outputs: Vec<DkgOutput> = parties.iter_mut().map(|p| p.finalize().unwrap()).collect();
+        assert_consistent(&outputs, &[1, 2, 3]);
+        assert!(DkgParticipant::insecure_unsigned(0, 2, 3).is_err());
+    }
+}
@@ -0,0 +1,591 @@
+//! **ed25519 module**
+//!
+//! Ed25519 (RFC 8032) signing with keys held by this crate: [`Ed25519SigningKey`] adapts a shared
+//! or recovered scalar to an Ed25519 signing key, and FROST threshold signing produces signatures
+//! any Ed25519 verifier accepts, so existing Ed25519 SSH and CA keys can move into threshold
+//! custody without re-issuing certificates or `authorized_keys` entries.
+//!
+//! Ed25519 and ristretto255 share the base point and the scalar field modulo `ℓ`, so a slice's
+//! scalar is directly a share of the Ed25519 signing scalar `a`. The public key is `A = a·B` in
+//! Edwards encoding, not the Ristretto encoding of `a·G`: they name the same group element but
+//! differ as bytes, and only `A` is an Ed25519 key.
+//!
+//! **Clamping.** RFC 8032 derives `a` from a 32-byte seed as the first half of SHA-512(seed) with
+//! the low three bits cleared and bit 254 set. The clamped integer is larger than `ℓ`, but only
+//! `a mod ℓ` enters `A` and the signature, so [`Ed25519SigningKey::from_seed`] reduces it and the
+//! reduced scalar is what [`thresholdize`] shares; clamping exists for the X25519 ladder and
+//! small-subgroup inputs, neither of which signing has. The reverse does not exist: clamping and
+//! SHA-512 discard information, so a key made with [`Ed25519SigningKey::from_scalar`] (a recovered
+//! secret, a DKG key) has no seed and can only ever export its public key, never an RFC 8032 or
+//! OpenSSH private key. Conversely a scalar is never fed to the seed path, which would clamp and
+//! hash it into an unrelated key (see [`crate::recovered`]). Both rules are enforced by the
+//! constructors: seeds go through clamping, scalars never do, and
+//! [`Ed25519SigningKey::from_keypair_bytes`] rejects a seed whose derived public key differs from
+//! the stored one.
+//!
+//! Single-key signatures are deterministic as in RFC 8032, hashing the nonce key `prefix` with
+//! the message; keys without a seed derive `prefix` from the scalar under a domain tag, so their
+//! signatures differ from a seed holder's but verify under the same `A`.
+//!
+//! Threshold signing is the FROST core of [`crate::frost::ciphersuite`] instantiated with
+//! [`Ed25519Sha512`]: FROST(Ed25519, SHA-512) of RFC 9591, with the slice index as the
+//! identifier and the Ed25519 challenge `SHA-512(R || A || m)`; the tests check it against the
+//! vectors of RFC 9591 appendix E.1.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::frost::ciphersuite::{self, Ciphersuite};
+use crate::group::Edwards25519;
+use crate::hash_adapter::sha512;
+use crate::recovered::RecoveredSecret;
+use crate::sharing::ShareData;
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Context string of the FROST(Ed25519, SHA-512) ciphersuite of RFC 9591.
+pub const FROST_ED25519_CONTEXT: &str = "FROST-ED25519-SHA512-v1";
+const PREFIX_CONTEXT: &str = "zk-thresh-pro 2024 ed25519 nonce key v1";
+
+/// `H(context || tag || parts)` of RFC 9591 as 64 bytes (H4, H5).
+fn frost_hash(tag: &str, parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = sha512::Sha512::new();
+    hasher.update(FROST_ED25519_CONTEXT.as_bytes());
+    hasher.update(tag.as_bytes());
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+/// The Ed25519 challenge `SHA-512(R || A || m) mod ℓ` (H2 of RFC 9591).
+fn challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&sha512::digest(&[r, public_key, message]))
+}
+
+/// A 64-byte Ed25519 signature `(R, S)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed25519Signature {
+    /// Compressed `R`.
+    pub r: [u8; 32],
+    pub s: Scalar,
+}
+
+impl Ed25519Signature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(self.s.as_bytes());
+        bytes
+    }
+
+    /// Decode [`Self::to_bytes`]; returns `None` unless `S < ℓ`, as RFC 8032 requires.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        Some(Self {
+            r: bytes[..32].try_into().unwrap(),
+            s: Option::from(Scalar::from_canonical_bytes(
+                bytes[32..].try_into().unwrap(),
+            ))?,
+        })
+    }
+
+    /// RFC 8032 verification of `message` under `public_key`: `S·B = R + k·A`, compared on the
+    /// encoding of `R` as most Ed25519 libraries do.
+    pub fn verify(&self, public_key: &[u8; 32], message: &[u8]) -> bool {
+        let Some(a) = CompressedEdwardsY(*public_key).decompress() else {
+            return false;
+        };
+        let k = challenge(&self.r, public_key, message);
+        let r = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &self.s);
+        r.compress().to_bytes() == self.r
+    }
+}
+
+/// An Ed25519 signing key: the signing scalar `a (mod ℓ)`, the nonce key `prefix` and
+/// `A = a·B`.
+///
+/// `Debug` output shows the public key only; the secret parts are zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Ed25519SigningKey {
+    scalar: Scalar,
+    prefix: [u8; 32],
+    #[zeroize(skip)]
+    public_key: EdwardsPoint,
+}
+
+impl Ed25519SigningKey {
+    /// The key of an RFC 8032 seed (`ssh-keygen -t ed25519`, libsodium, most HSM exports): the
+    /// clamped first half of SHA-512(seed), reduced modulo `ℓ`, and the second half as `prefix`.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut expanded = sha512::digest(&[seed]);
+        let mut clamped: [u8; 32] = expanded[..32].try_into().unwrap();
+        clamped[0] &= 0b1111_1000;
+        clamped[31] &= 0b0111_1111;
+        clamped[31] |= 0b0100_0000;
+        let scalar = Scalar::from_bytes_mod_order(clamped);
+        let prefix = expanded[32..].try_into().unwrap();
+        clamped.zeroize();
+        expanded.zeroize();
+        Self {
+            scalar,
+            prefix,
+            public_key: ED25519_BASEPOINT_POINT * scalar,
+        }
+    }
+
+    /// The 64-byte `seed || A` secret key of libsodium and the OpenSSH private key format.
+    ///
+    /// Returns `CryptoError::Validation` if `A` is not the public key of the seed, e.g. a
+    /// corrupted or mismatched export.
+    pub fn from_keypair_bytes(bytes: &[u8; 64]) -> CryptoResult<Self> {
+        let key = Self::from_seed(bytes[..32].try_into().unwrap());
+        if key.public_key() != bytes[32..] {
+            return Err(CryptoError::Validation {
+                field: "keypair".to_string(),
+                reason: "public key does not belong to the seed".to_string(),
+            });
+        }
+        Ok(key)
+    }
+
+    /// The key with signing scalar `scalar` used as is, without clamping; it has no seed.
+    ///
+    /// Returns `CryptoError::Validation` for zero, whose public key is the identity.
+    pub fn from_scalar(scalar: &Scalar) -> CryptoResult<Self> {
+        if *scalar == Scalar::ZERO {
+            return Err(CryptoError::Validation {
+                field: "scalar".to_string(),
+                reason: "zero is not a signing key".to_string(),
+            });
+        }
+        let mut expanded = sha512::digest(&[PREFIX_CONTEXT.as_bytes(), scalar.as_bytes()]);
+        let prefix = expanded[..32].try_into().unwrap();
+        expanded.zeroize();
+        Ok(Self {
+            scalar: *scalar,
+            prefix,
+            public_key: ED25519_BASEPOINT_POINT * scalar,
+        })
+    }
+
+    /// The key of a recovered secret, with public key `s·B`: the key the slices signed for.
+    pub fn from_recovered(secret: &RecoveredSecret) -> CryptoResult<Self> {
+        Self::from_scalar(secret.as_scalar())
+    }
+
+    /// Compressed `A`, the 32 bytes of an `ssh-ed25519` public key.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.public_key.compress().to_bytes()
+    }
+
+    /// Deterministic RFC 8032 signature of `message`.
+    pub fn sign(&self, message: &[u8]) -> Ed25519Signature {
+        let mut nonce =
+            Scalar::from_bytes_mod_order_wide(&sha512::digest(&[&self.prefix, message]));
+        let r = (ED25519_BASEPOINT_POINT * nonce).compress().to_bytes();
+        let s = nonce + challenge(&r, &self.public_key(), message) * self.scalar;
+        nonce.zeroize();
+        Ed25519Signature { r, s }
+    }
+}
+
+impl fmt::Debug for Ed25519SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ed25519SigningKey")
+            .field("public_key", &hex::encode(self.public_key()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// FROST(Ed25519, SHA-512) of RFC 9591, signing [`Ed25519Signature`]s for the group public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed25519Sha512;
+
+impl Ciphersuite for Ed25519Sha512 {
+    type Group = Edwards25519;
+    type Key = EdwardsPoint;
+    type Signature = Ed25519Signature;
+
+    const NAME: &'static str = "ed25519";
+
+    fn key_bytes(key: &EdwardsPoint) -> Vec<u8> {
+        key.compress().to_bytes().to_vec()
+    }
+
+    fn identifier(index: usize) -> Vec<u8> {
+        Scalar::from(index as u64).to_bytes().to_vec()
+    }
+
+    fn nonce(randomness: &[u8; 32], secret: &Scalar) -> Scalar {
+        let mut digest = frost_hash("nonce", &[randomness, secret.as_bytes()]);
+        let nonce = Scalar::from_bytes_mod_order_wide(&digest);
+        digest.zeroize();
+        nonce
+    }
+
+    fn hash_message(message: &[u8]) -> Vec<u8> {
+        frost_hash("msg", &[message]).to_vec()
+    }
+
+    fn hash_commitment_list(list: &[u8]) -> Vec<u8> {
+        frost_hash("com", &[list]).to_vec()
+    }
+
+    fn binding_factor(
+        key: &[u8],
+        message_hash: &[u8],
+        list_hash: &[u8],
+        identifier: &[u8],
+    ) -> Scalar {
+        Scalar::from_bytes_mod_order_wide(&frost_hash(
+            "rho",
+            &[key, message_hash, list_hash, identifier],
+        ))
+    }
+
+    fn challenge(commitment: &EdwardsPoint, key: &EdwardsPoint, message: &[u8]) -> Scalar {
+        challenge(
+            &commitment.compress().to_bytes(),
+            &key.compress().to_bytes(),
+            message,
+        )
+    }
+
+    fn signature(commitment: &EdwardsPoint, response: Scalar) -> Ed25519Signature {
+        Ed25519Signature {
+            r: commitment.compress().to_bytes(),
+            s: response,
+        }
+    }
+
+    fn verify(signature: &Ed25519Signature, key: &EdwardsPoint, message: &[u8]) -> bool {
+        signature.verify(&key.compress().to_bytes(), message)
+    }
+}
+
+impl ciphersuite::SigningShare<Edwards25519> for ShareData {
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn signing_share(&self) -> Scalar {
+        self.share
+    }
+}
+
+/// Round-one message: the nonce commitments `(D_i, E_i)` of one participant. Points that are
+/// small-order or carry a torsion component are rejected on deserialization.
+pub type SigningCommitments = ciphersuite::SigningCommitments<Ed25519Sha512>;
+/// Secret nonces behind a participant's [`SigningCommitments`]; consumed by [`sign`].
+#[cfg(feature = "dealer")]
+pub type SigningNonces = ciphersuite::SigningNonces<Ed25519Sha512>;
+/// Coordinator message opening round two.
+pub type SigningPackage = ciphersuite::SigningPackage<Ed25519Sha512>;
+/// Round-two message: one participant's signature share `z_i`.
+pub type SignatureShare = ciphersuite::SignatureShare<Ed25519Sha512>;
+/// The Ed25519 group public key `A` and every participant's verification share `A_i = s_i·B`.
+pub type PublicKeyPackage = ciphersuite::PublicKeyPackage<Ed25519Sha512>;
+/// Verification share `A_i = s_i·B` of one participant.
+pub type VerificationShare = ciphersuite::VerificationShare<Ed25519Sha512>;
+
+impl PublicKeyPackage {
+    /// Package for the slices of one dealing; see
+    /// [`ciphersuite::PublicKeyPackage::from_signing_shares`].
+    #[cfg(feature = "dealer")]
+    pub fn from_shares(shares: &[ShareData]) -> CryptoResult<Self> {
+        Self::from_signing_shares(shares)
+    }
+
+    /// Compressed `A`.
+    pub fn public_key(&self) -> [u8; 32] {
+        self.group_public_key.compress().to_bytes()
+    }
+}
+
+/// Move `key` into threshold custody: `n` slices, any `threshold` of which sign for the same
+/// public key, and the coordinator's package. Discard every copy of the seed afterwards.
+///
+/// Returns the errors of [`crate::sharing::generate_key_shares`].
+#[cfg(feature = "dealer")]
+pub fn thresholdize(
+    key: &Ed25519SigningKey,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<(Vec<ShareData>, PublicKeyPackage)> {
+    let shares = crate::sharing::generate_key_shares(key.scalar, threshold, n)?;
+    let package = PublicKeyPackage::from_shares(&shares)?;
+    debug_assert_eq!(package.group_public_key, key.public_key);
+    Ok((shares, package))
+}
+
+/// Round one: draw hiding and binding nonces for `share`, `H3(random || s_i)` as RFC 9591.
+///
+/// Returns `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn commit(share: &ShareData) -> CryptoResult<(SigningNonces, SigningCommitments)> {
+    ciphersuite::commit(share)
+}
+
+/// Round two: the signature share of `share` for `package`; see [`ciphersuite::sign`].
+#[cfg(feature = "dealer")]
+pub fn sign(
+    share: &ShareData,
+    nonces: SigningNonces,
+    package: &SigningPackage,
+    group_public_key: &EdwardsPoint,
+) -> CryptoResult<SignatureShare> {
+    ciphersuite::sign(share, nonces, package, group_public_key)
+}
+
+/// Check one signature share: `z_i·B = D_i + ρ_i·E_i + c·λ_i·A_i`.
+pub fn verify_signature_share(
+    signature_share: &SignatureShare,
+    verification_share: &EdwardsPoint,
+    package: &SigningPackage,
+    group_public_key: &EdwardsPoint,
+) -> bool {
+    ciphersuite::verify_signature_share(
+        signature_share,
+        verification_share,
+        package,
+        group_public_key,
+    )
+}
+
+/// Coordinator: check every signature share and combine them into an Ed25519 signature under
+/// the group public key; see [`ciphersuite::aggregate`].
+pub fn aggregate(
+    package: &SigningPackage,
+    signature_shares: &[SignatureShare],
+    public_keys: &PublicKeyPackage,
+) -> CryptoResult<Ed25519Signature> {
+    ciphersuite::aggregate(
+        package,
+        signature_shares,
+        public_keys,
+        &public_keys.group_public_key,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes<const N: usize>(hex_str: &str) -> [u8; N] {
+        hex::decode(hex_str).unwrap().try_into().unwrap()
+    }
+
+    /// Test 1 of RFC 8032, section 7.1.
+    #[test]
+    fn test_ed25519_rfc8032_vector() {
+        let seed = bytes("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let public_key = bytes("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let key = Ed25519SigningKey::from_seed(&seed);
+        assert_eq!(key.public_key(), public_key);
+        let signature = key.sign(b"");
+        assert_eq!(
+            hex::encode(signature.to_bytes()),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555\
+             fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+        assert!(signature.verify(&public_key, b""));
+        assert!(!signature.verify(&public_key, b"x"));
+        assert_eq!(
+            Ed25519Signature::from_bytes(&signature.to_bytes()),
+            Some(signature)
+        );
+
+        let keypair: [u8; 64] = [seed, public_key].concat().try_into().unwrap();
+        assert!(Ed25519SigningKey::from_keypair_bytes(&keypair).is_ok());
+        let mut mismatched = keypair;
+        mismatched[40] ^= 1;
+        assert!(Ed25519SigningKey::from_keypair_bytes(&mismatched).is_err());
+        // S ≥ ℓ is rejected.
+        let mut unreduced = signature.to_bytes();
+        unreduced[63] = 0xff;
+        assert!(Ed25519Signature::from_bytes(&unreduced).is_none());
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_threshold_ed25519_signs_for_migrated_key() {
+        let seed = [0x42; 32];
+        let key = Ed25519SigningKey::from_seed(&seed);
+        let (shares, public_keys) = thresholdize(&key, 2, 3).unwrap();
+        assert_eq!(public_keys.public_key(), key.public_key());
+        let message = b"ssh-ed25519 certificate to be signed";
+
+        let signers = [&shares[0], &shares[2]];
+        let (nonces, round_one): (Vec<_>, Vec<_>) =
+            signers.iter().map(|s| commit(s).unwrap()).unzip();
+        let package = SigningPackage::new(message, round_one).unwrap();
+        let group_key = public_keys.group_public_key;
+        let signature_shares: Vec<SignatureShare> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, n)| sign(s, n, &package, &group_key).unwrap())
+            .collect();
+        for share in &signature_shares {
+            let y = public_keys.verification_share(share.index).unwrap();
+            assert!(verify_signature_share(share, &y, &package, &group_key));
+        }
+        let signature = aggregate(&package, &signature_shares, &public_keys).unwrap();
+        assert!(signature.verify(&key.public_key(), message));
+
+        let mut forged = signature_shares.clone();
+        forged[1].response += Scalar::ONE;
+        assert!(matches!(
+            aggregate(&package, &forged, &public_keys),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // The recovered scalar signs for the same key, without its seed.
+        let recovered = crate::sharing::recover_secret(&shares[1..]).unwrap();
+        let restored = Ed25519SigningKey::from_recovered(&RecoveredSecret::new(recovered)).unwrap();
+        assert_eq!(restored.public_key(), key.public_key());
+        assert!(restored.sign(message).verify(&key.public_key(), message));
+        assert_ne!(restored.sign(message), key.sign(message));
+        assert!(Ed25519SigningKey::from_scalar(&Scalar::ZERO).is_err());
+
+        let json = serde_json::to_string(&public_keys).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PublicKeyPackage>(&json).unwrap(),
+            public_keys
+        );
+    }
+
+    /// FROST(Ed25519, SHA-512) of RFC 9591 appendix E.1: participants 1 and 3 of a 2-of-3
+    /// dealing sign `"test"` with the nonces of the fixed randomness.
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_frost_ed25519_rfc9591_vector() {
+        let scalar = |hex_str: &str| Scalar::from_canonical_bytes(bytes(hex_str)).unwrap();
+        let point = |p: &EdwardsPoint| hex::encode(p.compress().as_bytes());
+        let secret = scalar("7b1c33d3f5291d85de664833beb1ad469f7fb6025a0ec78b3a790c6e13a98304");
+        let coefficient =
+            scalar("178199860edd8c62f5212ee91eff1295d0d670ab4ed4506866bae57e7030b204");
+        let group_key = ED25519_BASEPOINT_POINT * secret;
+        assert_eq!(
+            point(&group_key),
+            "15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673"
+        );
+        let share = |index: u64| ciphersuite::KeyShare::<Edwards25519> {
+            index: index as usize,
+            share: secret + coefficient * Scalar::from(index),
+        };
+        let signers = [share(1), share(3)];
+        assert_eq!(
+            hex::encode(signers[0].share.as_bytes()),
+            "929dcc590407aae7d388761cddb0c0db6f5627aea8e217f4a033f2ec83d93509"
+        );
+        assert_eq!(
+            hex::encode(signers[1].share.as_bytes()),
+            "d3cb090a075eb154e82fdb4b3cb507f110040905468bb9c46da8bdea643a9a02"
+        );
+
+        let randomness = [
+            (
+                bytes("0fd2e39e111cdc266f6c0f4d0fd45c947761f1f5d3cb583dfcb9bbaf8d4c9fec"),
+                bytes("69cd85f631d5f7f2721ed5e40519b1366f340a87c2f6856363dbdcda348a7501"),
+            ),
+            (
+                bytes("86d64a260059e495d0fb4fcc17ea3da7452391baa494d4b00321098ed2a0062f"),
+                bytes("13e6b25afb2eba51716a9a7d44130c0dbae0004a9ef8d7b5550c8a0e07c61775"),
+            ),
+        ];
+        let nonces: Vec<SigningNonces> = signers
+            .iter()
+            .zip(&randomness)
+            .map(|(s, (hiding, binding))| SigningNonces::from_randomness(s, hiding, binding))
+            .collect();
+        let expected_nonces = [
+            (
+                "812d6104142944d5a55924de6d49940956206909f2acaeedecda2b726e630407",
+                "b1110165fc2334149750b28dd813a39244f315cff14d4e89e6142f262ed83301",
+                "b5aa8ab305882a6fc69cbee9327e5a45e54c08af61ae77cb8207be3d2ce13de3",
+                "67e98ab55aa310c3120418e5050c9cf76cf387cb20ac9e4b6fdb6f82a469f932",
+            ),
+            (
+                "c256de65476204095ebdc01bd11dc10e57b36bc96284595b8215222374f99c0e",
+                "243d71944d929063bc51205714ae3c2218bd3451d0214dfb5aeec2a90c35180d",
+                "cfbdb165bd8aad6eb79deb8d287bcc0ab6658ae57fdcc98ed12c0669e90aec91",
+                "7487bc41a6e712eea2f2af24681b58b1cf1da278ea11fe4e8b78398965f13552",
+            ),
+        ];
+        for (n, expected) in nonces.iter().zip(expected_nonces) {
+            let (hiding, binding) = n.scalars();
+            let commitments = n.commitments();
+            assert_eq!(hex::encode(hiding.as_bytes()), expected.0);
+            assert_eq!(hex::encode(binding.as_bytes()), expected.1);
+            assert_eq!(point(&commitments.hiding), expected.2);
+            assert_eq!(point(&commitments.binding), expected.3);
+        }
+
+        let message = b"test";
+        let package =
+            SigningPackage::new(message, nonces.iter().map(|n| n.commitments()).collect()).unwrap();
+        let binding_factors: Vec<String> = package
+            .binding_factors(&group_key)
+            .iter()
+            .map(|rho| hex::encode(rho.as_bytes()))
+            .collect();
+        assert_eq!(
+            binding_factors,
+            [
+                "f2cb9d7dd9beff688da6fcc83fa89046b3479417f47f55600b106760eb3b5603",
+                "b087686bf35a13f3dc78e780a34b0fe8a77fef1b9938c563f5573d71d8d7890f",
+            ]
+        );
+
+        let signature_shares: Vec<SignatureShare> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, n)| ciphersuite::sign(s, n, &package, &group_key).unwrap())
+            .collect();
+        assert_eq!(
+            hex::encode(signature_shares[0].response.as_bytes()),
+            "001719ab5a53ee1a12095cd088fd149702c0720ce5fd2f29dbecf24b7281b603"
+        );
+        assert_eq!(
+            hex::encode(signature_shares[1].response.as_bytes()),
+            "bd86125de990acc5e1f13781d8e32c03a9bbd4c53539bbc106058bfd14326007"
+        );
+
+        let public_keys = PublicKeyPackage::from_signing_shares(&signers).unwrap();
+        let signature = aggregate(&package, &signature_shares, &public_keys).unwrap();
+        assert_eq!(
+            hex::encode(signature.to_bytes()),
+            "36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbe\
+             bd9d2b0844e49ae0f3fa935161e1419aab7b47d21a37ebeae1f17d4987b3160b"
+        );
+        assert!(signature.verify(&public_keys.public_key(), message));
+    }
+
+    #[test]
+    fn test_frost_ed25519_rejects_torsion_commitments() {
+        use curve25519_dalek::constants::EIGHT_TORSION;
+        let point = |p: EdwardsPoint| hex::encode(p.compress().as_bytes());
+        let honest = ED25519_BASEPOINT_POINT * Scalar::from(7u64);
+        let commitments = |hiding: String| {
+            serde_json::from_value::<SigningCommitments>(serde_json::json!({
+                "index": 1,
+                "hiding": hiding,
+                "binding": point(honest),
+            }))
+        };
+        assert!(commitments(point(honest)).is_ok());
+        // A point of small order, and a valid point plus a torsion component.
+        assert!(commitments(point(EIGHT_TORSION[1])).is_err());
+        assert!(commitments(point(honest + EIGHT_TORSION[4])).is_err());
+
+        let share = |p: EdwardsPoint| {
+            serde_json::from_value::<VerificationShare>(serde_json::json!({
+                "index": 1,
+                "point": point(p),
+            }))
+        };
+        assert!(share(honest).is_ok());
+        assert!(share(honest + EIGHT_TORSION[2]).is_err());
+    }
+}
@@ -3,6 +3,8 @@
 //! Comprehensive error types and handling for enterprise-grade applications
 //! with detailed error reporting and audit trail support.
 
+use crate::audit::{AuditExport, AuditSink, ChainedAuditRecord};
+use crate::context::OperationContext;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -41,6 +43,160 @@ pub enum CryptoError {
 
     #[error("Configuration error: {parameter} - {issue}")]
     Configuration { parameter: String, issue: String },
+
+    #[error("Revoked share: slice {index} issued by operation {operation_id}")]
+    RevokedShare { operation_id: String, index: usize },
+
+    #[error("Safe mode: {operation} refused ({reason})")]
+    SafeMode { operation: String, reason: String },
+
+    #[error("Key expired: {key_id} expired at {expired_at}")]
+    KeyExpired {
+        key_id: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        expired_at: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+impl CryptoError {
+    /// Stable, locale-independent error code (see [`crate::events`]).
+    pub fn code(&self) -> &'static str {
+        match self {
+            CryptoError::KeyGeneration { .. } => "ZKT-E001",
+            CryptoError::InvalidKeyStateTransition { .. } => "ZKT-E002",
+            CryptoError::CryptographicOperation { .. } => "ZKT-E003",
+            CryptoError::Serialization { .. } => "ZKT-E004",
+            CryptoError::Validation { .. } => "ZKT-E005",
+            CryptoError::SecurityViolation { .. } => "ZKT-E006",
+            CryptoError::InsufficientEntropy { .. } => "ZKT-E007",
+            CryptoError::Timeout { .. } => "ZKT-E008",
+            CryptoError::ResourceExhaustion { .. } => "ZKT-E009",
+            CryptoError::Network { .. } => "ZKT-E010",
+            CryptoError::Configuration { .. } => "ZKT-E011",
+            CryptoError::RevokedShare { .. } => "ZKT-E012",
+            CryptoError::SafeMode { .. } => "ZKT-E013",
+            CryptoError::KeyExpired { .. } => "ZKT-E014",
+        }
+    }
+}
+
+/// Why a proof or slice failed verification.
+///
+/// Returned by [`crate::proof::verify_proof`] and the `vss` verification functions. Converts into
+/// [`CryptoError`] (`Serialization` for encoding failures, `SecurityViolation` otherwise) where a
+/// caller only propagates it.
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerifyError {
+    /// The proof's challenge is not the hash of its statement: the proof, its commitment or the
+    /// index it is checked under was altered.
+    #[error("proof challenge of slice {index} does not match its statement")]
+    ChallengeMismatch { index: usize },
+
+    /// The challenge matches but the responses do not satisfy the verification equation.
+    #[error("proof responses of slice {index} do not verify")]
+    InvalidResponse { index: usize },
+
+    /// The slice value and blinding random do not open the slice's own commitment.
+    #[error("slice {index} does not open its commitment")]
+    OpeningMismatch { index: usize },
+
+    /// The slice commitment is not on the polynomial committed to by the dealing.
+    #[error("commitment of slice {index} is not on the committed polynomial")]
+    CommitmentMismatch { index: usize },
+
+    /// A field is not a canonical encoding (bad hex or length, unreduced scalar, invalid or
+    /// identity point) or is missing.
+    #[error("non-canonical encoding of {field}: {reason}")]
+    NonCanonicalEncoding { field: String, reason: String },
+
+    /// The slice was checked in the wrong context, e.g. under the reserved index 0 or against an
+    /// empty commitment vector.
+    #[error("slice {index} checked in the wrong context: {details}")]
+    WrongContext { index: usize, details: String },
+
+    /// The slice matches the commitments of an earlier epoch of the key, not the current ones.
+    #[error("slice {index} is from epoch {epoch}, current epoch is {current_epoch}")]
+    StaleEpoch {
+        index: usize,
+        epoch: u64,
+        current_epoch: u64,
+    },
+
+    /// A refreshed coefficient commitment is not the old one plus the published update; for
+    /// `coefficient` 0 the refresh changed the commitment to the secret.
+    #[error("refreshed commitment C_{coefficient} is not the old commitment plus the update")]
+    RefreshMismatch { coefficient: usize },
+}
+
+/// Most likely cause of a [`VerifyError`], for triage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FailureClass {
+    /// Damaged storage or transport; restore the slice from a backup or re-fetch it.
+    Corruption,
+    /// Forged or altered data, or a dishonest dealer; investigate before using the slice.
+    Malice,
+    /// Mixed versions, epochs or deployments; re-sync with the current dealing.
+    VersionSkew,
+}
+
+impl VerifyError {
+    /// Stable, locale-independent code (see [`crate::events`]).
+    pub fn code(&self) -> &'static str {
+        match self {
+            VerifyError::ChallengeMismatch { .. } => "ZKT-V001",
+            VerifyError::InvalidResponse { .. } => "ZKT-V002",
+            VerifyError::OpeningMismatch { .. } => "ZKT-V003",
+            VerifyError::CommitmentMismatch { .. } => "ZKT-V004",
+            VerifyError::NonCanonicalEncoding { .. } => "ZKT-V005",
+            VerifyError::WrongContext { .. } => "ZKT-V006",
+            VerifyError::StaleEpoch { .. } => "ZKT-V007",
+            VerifyError::RefreshMismatch { .. } => "ZKT-V008",
+        }
+    }
+
+    /// Most likely cause.
+    ///
+    /// A well-formed slice that fails an algebraic check was produced or altered by someone;
+    /// encoding failures are usually damage, and context or epoch failures usually skew.
+    pub fn class(&self) -> FailureClass {
+        match self {
+            VerifyError::NonCanonicalEncoding { .. } => FailureClass::Corruption,
+            VerifyError::ChallengeMismatch { .. }
+            | VerifyError::InvalidResponse { .. }
+            | VerifyError::OpeningMismatch { .. }
+            | VerifyError::CommitmentMismatch { .. }
+            | VerifyError::RefreshMismatch { .. } => FailureClass::Malice,
+            VerifyError::WrongContext { .. } | VerifyError::StaleEpoch { .. } => {
+                FailureClass::VersionSkew
+            }
+        }
+    }
+
+    /// Index of the slice concerned, if known.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            VerifyError::ChallengeMismatch { index }
+            | VerifyError::InvalidResponse { index }
+            | VerifyError::OpeningMismatch { index }
+            | VerifyError::CommitmentMismatch { index }
+            | VerifyError::WrongContext { index, .. }
+            | VerifyError::StaleEpoch { index, .. } => Some(*index),
+            VerifyError::NonCanonicalEncoding { .. } | VerifyError::RefreshMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<VerifyError> for CryptoError {
+    fn from(e: VerifyError) -> Self {
+        match e {
+            VerifyError::NonCanonicalEncoding { .. } => CryptoError::Serialization {
+                details: format!("{} {}", e.code(), e),
+            },
+            _ => CryptoError::SecurityViolation {
+                details: format!("{} {}", e.code(), e),
+            },
+        }
+    }
 }
 
 /// Security audit event types
@@ -77,27 +233,666 @@ pub enum SecurityEvent {
         #[serde(with = "chrono::serde::ts_seconds")]
         timestamp: chrono::DateTime<chrono::Utc>,
     },
+    SharesDealt {
+        key_id: String,
+        threshold: usize,
+        num_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    SharesRefreshed {
+        key_id: String,
+        threshold: usize,
+        num_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    SecretRecovered {
+        key_id: String,
+        shares_used: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Slices distributed before their proofs; see `sharing::generate_key_shares_deferred`.
+    SharesDealtUnproven {
+        key_id: String,
+        threshold: usize,
+        num_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Proofs of a deferred dealing generated for publication.
+    ShareProofsPublished {
+        key_id: String,
+        num_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A slice marked compromised in the [`crate::registry::ShareRegistry`].
+    ShareRevoked {
+        key_id: String,
+        /// Operation that issued the slice.
+        issued_by: String,
+        index: usize,
+        reason: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A revoked slice presented for recovery.
+    RevokedShareRejected {
+        key_id: String,
+        /// Operation that issued the slice.
+        issued_by: String,
+        index: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Secret-producing operations suspended; see `EnterpriseCryptoSystem::enter_safe_mode`.
+    SafeModeEntered {
+        reason: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Safe mode left after a passing self-test.
+    SafeModeExited {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A slice whose proof verified before it was used, e.g. for recovery.
+    ShareVerified {
+        key_id: String,
+        index: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// A slice whose proof did not verify.
+    ShareVerificationFailed {
+        key_id: String,
+        index: usize,
+        /// Stable code of the verification error, e.g. `ZKT-V001`.
+        code: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Slices reissued under a new threshold; see `sharing::adjust_threshold`.
+    ThresholdAdjusted {
+        key_id: String,
+        old_threshold: usize,
+        new_threshold: usize,
+        num_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Sharing of a key rotated: its slices refreshed into a new epoch and the slices they
+    /// replace retired; see `key_lifecycle::KeyRotationManager`.
+    KeyRotated {
+        key_id: String,
+        epoch: u64,
+        /// Slices of the previous sharing revoked in the registry.
+        retired_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Participant removed from the sharing of a key: the remaining slices re-shared into a new
+    /// epoch and the previous sharing retired; see
+    /// `EnterpriseCryptoSystem::disenroll_participant`.
+    ParticipantDisenrolled {
+        key_id: String,
+        /// Index of the removed slice.
+        index: usize,
+        epoch: u64,
+        num_shares: usize,
+        /// Slices of the previous sharing revoked in the registry, the removed one included.
+        retired_shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// An operation refused because the key is past its expiry time; see `Key::expiry_time`.
+    KeyExpired {
+        key_id: String,
+        /// Operation refused, e.g. `share_generation`.
+        operation: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        expired_at: chrono::DateTime<chrono::Utc>,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Public state of a key exported by another system loaded; see `public_state`.
+    PublicStateImported {
+        key_id: String,
+        /// Last share epoch of the key.
+        epoch: u64,
+        /// Registry entries imported.
+        shares: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Key material derived with [`crate::key_lifecycle::Key::derive_material`].
+    KeyMaterialDerived {
+        key_id: String,
+        /// Application context the material was derived for.
+        purpose: String,
+        length: usize,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+    /// Request of an authenticated participant answered by `service::Service`.
+    ServiceRequestHandled {
+        participant: usize,
+        /// Requested operation, e.g. `recover`.
+        operation: String,
+        /// `ok`, or the stable code of the error returned.
+        outcome: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        timestamp: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+/// Severity of a [`SecurityEvent`], for alerting and dashboards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// Routine lifecycle and share operations.
+    Low,
+    /// Operations that expose or end a key (recovery, retirement, destruction).
+    Medium,
+    /// Access and policy violations.
+    High,
+}
+
+impl SecurityEvent {
+    /// Severity of the event.
+    pub fn severity(&self) -> Severity {
+        match self {
+            SecurityEvent::UnauthorizedAccess { .. }
+            | SecurityEvent::PolicyViolation { .. }
+            | SecurityEvent::RevokedShareRejected { .. }
+            | SecurityEvent::KeyExpired { .. }
+            | SecurityEvent::ShareVerificationFailed { .. }
+            | SecurityEvent::SafeModeEntered { .. } => Severity::High,
+            SecurityEvent::KeyRetired { .. }
+            | SecurityEvent::KeyDestroyed { .. }
+            | SecurityEvent::SecretRecovered { .. }
+            | SecurityEvent::SharesDealtUnproven { .. }
+            | SecurityEvent::ShareRevoked { .. }
+            | SecurityEvent::KeyMaterialDerived { .. }
+            | SecurityEvent::ThresholdAdjusted { .. }
+            | SecurityEvent::ParticipantDisenrolled { .. }
+            | SecurityEvent::PublicStateImported { .. }
+            | SecurityEvent::SafeModeExited { .. } => Severity::Medium,
+            SecurityEvent::KeyGenerated { .. }
+            | SecurityEvent::KeyActivated { .. }
+            | SecurityEvent::SharesDealt { .. }
+            | SecurityEvent::SharesRefreshed { .. }
+            | SecurityEvent::ShareProofsPublished { .. }
+            | SecurityEvent::ShareVerified { .. }
+            | SecurityEvent::KeyRotated { .. }
+            | SecurityEvent::ServiceRequestHandled { .. } => Severity::Low,
+        }
+    }
+
+    /// Stable, locale-independent audit event code (see [`crate::events`]).
+    pub fn code(&self) -> &'static str {
+        match self {
+            SecurityEvent::KeyGenerated { .. } => "ZKT-A001",
+            SecurityEvent::KeyActivated { .. } => "ZKT-A002",
+            SecurityEvent::KeyRetired { .. } => "ZKT-A003",
+            SecurityEvent::KeyDestroyed { .. } => "ZKT-A004",
+            SecurityEvent::UnauthorizedAccess { .. } => "ZKT-A005",
+            SecurityEvent::PolicyViolation { .. } => "ZKT-A006",
+            SecurityEvent::SharesDealt { .. } => "ZKT-A007",
+            SecurityEvent::SharesRefreshed { .. } => "ZKT-A008",
+            SecurityEvent::SecretRecovered { .. } => "ZKT-A009",
+            SecurityEvent::SharesDealtUnproven { .. } => "ZKT-A010",
+            SecurityEvent::ShareProofsPublished { .. } => "ZKT-A011",
+            SecurityEvent::ShareRevoked { .. } => "ZKT-A012",
+            SecurityEvent::RevokedShareRejected { .. } => "ZKT-A013",
+            SecurityEvent::KeyMaterialDerived { .. } => "ZKT-A014",
+            SecurityEvent::SafeModeEntered { .. } => "ZKT-A015",
+            SecurityEvent::SafeModeExited { .. } => "ZKT-A016",
+            SecurityEvent::ShareVerified { .. } => "ZKT-A017",
+            SecurityEvent::ShareVerificationFailed { .. } => "ZKT-A018",
+            SecurityEvent::ThresholdAdjusted { .. } => "ZKT-A019",
+            SecurityEvent::KeyRotated { .. } => "ZKT-A020",
+            SecurityEvent::PublicStateImported { .. } => "ZKT-A021",
+            SecurityEvent::KeyExpired { .. } => "ZKT-A022",
+            SecurityEvent::ParticipantDisenrolled { .. } => "ZKT-A023",
+            SecurityEvent::ServiceRequestHandled { .. } => "ZKT-A024",
+        }
+    }
+
+    /// Structured `key=value` fields of the event, in a fixed order.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            SecurityEvent::KeyGenerated { key_id, timestamp }
+            | SecurityEvent::KeyActivated { key_id, timestamp }
+            | SecurityEvent::KeyRetired { key_id, timestamp }
+            | SecurityEvent::KeyDestroyed { key_id, timestamp } => vec![
+                ("key_id", key_id.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::UnauthorizedAccess { attempt, timestamp } => vec![
+                ("attempt", attempt.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::PolicyViolation {
+                policy,
+                violation,
+                timestamp,
+            } => vec![
+                ("policy", policy.clone()),
+                ("violation", violation.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::SharesDealt {
+                key_id,
+                threshold,
+                num_shares,
+                timestamp,
+            }
+            | SecurityEvent::SharesRefreshed {
+                key_id,
+                threshold,
+                num_shares,
+                timestamp,
+            }
+            | SecurityEvent::SharesDealtUnproven {
+                key_id,
+                threshold,
+                num_shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("threshold", threshold.to_string()),
+                ("num_shares", num_shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::SecretRecovered {
+                key_id,
+                shares_used,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("shares_used", shares_used.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ShareProofsPublished {
+                key_id,
+                num_shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("num_shares", num_shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ShareRevoked {
+                key_id,
+                issued_by,
+                index,
+                reason,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("issued_by", issued_by.clone()),
+                ("index", index.to_string()),
+                ("reason", reason.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::RevokedShareRejected {
+                key_id,
+                issued_by,
+                index,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("issued_by", issued_by.clone()),
+                ("index", index.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ShareVerified {
+                key_id,
+                index,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("index", index.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ShareVerificationFailed {
+                key_id,
+                index,
+                code,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("index", index.to_string()),
+                ("error", code.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ThresholdAdjusted {
+                key_id,
+                old_threshold,
+                new_threshold,
+                num_shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("old_threshold", old_threshold.to_string()),
+                ("new_threshold", new_threshold.to_string()),
+                ("num_shares", num_shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::KeyRotated {
+                key_id,
+                epoch,
+                retired_shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("epoch", epoch.to_string()),
+                ("retired_shares", retired_shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ParticipantDisenrolled {
+                key_id,
+                index,
+                epoch,
+                num_shares,
+                retired_shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("index", index.to_string()),
+                ("epoch", epoch.to_string()),
+                ("num_shares", num_shares.to_string()),
+                ("retired_shares", retired_shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::KeyExpired {
+                key_id,
+                operation,
+                expired_at,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("operation", operation.clone()),
+                ("expired_at", expired_at.timestamp().to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::PublicStateImported {
+                key_id,
+                epoch,
+                shares,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("epoch", epoch.to_string()),
+                ("shares", shares.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::KeyMaterialDerived {
+                key_id,
+                purpose,
+                length,
+                timestamp,
+            } => vec![
+                ("key_id", key_id.clone()),
+                ("purpose", purpose.clone()),
+                ("length", length.to_string()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::ServiceRequestHandled {
+                participant,
+                operation,
+                outcome,
+                timestamp,
+            } => vec![
+                ("participant", participant.to_string()),
+                ("operation", operation.clone()),
+                ("outcome", outcome.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::SafeModeEntered { reason, timestamp } => vec![
+                ("reason", reason.clone()),
+                ("timestamp", timestamp.timestamp().to_string()),
+            ],
+            SecurityEvent::SafeModeExited { timestamp } => {
+                vec![("timestamp", timestamp.timestamp().to_string())]
+            }
+        }
+    }
+
+    /// Human-readable summary, kept separate from the code and fields.
+    pub fn message(&self) -> &'static str {
+        match self {
+            SecurityEvent::KeyGenerated { .. } => "key generated",
+            SecurityEvent::KeyActivated { .. } => "key activated",
+            SecurityEvent::KeyRetired { .. } => "key retired",
+            SecurityEvent::KeyDestroyed { .. } => "key destroyed",
+            SecurityEvent::UnauthorizedAccess { .. } => "unauthorized access attempt",
+            SecurityEvent::PolicyViolation { .. } => "policy violation",
+            SecurityEvent::SharesDealt { .. } => "shares dealt",
+            SecurityEvent::SharesRefreshed { .. } => "shares refreshed",
+            SecurityEvent::SecretRecovered { .. } => "secret recovered",
+            SecurityEvent::SharesDealtUnproven { .. } => "shares dealt without proofs",
+            SecurityEvent::ShareProofsPublished { .. } => "share proofs published",
+            SecurityEvent::ShareRevoked { .. } => "share revoked",
+            SecurityEvent::RevokedShareRejected { .. } => "revoked share rejected",
+            SecurityEvent::KeyMaterialDerived { .. } => "key material derived",
+            SecurityEvent::SafeModeEntered { .. } => "safe mode entered",
+            SecurityEvent::SafeModeExited { .. } => "safe mode exited",
+            SecurityEvent::ShareVerified { .. } => "share verified",
+            SecurityEvent::ShareVerificationFailed { .. } => "share verification failed",
+            SecurityEvent::ThresholdAdjusted { .. } => "threshold adjusted",
+            SecurityEvent::KeyRotated { .. } => "key rotated",
+            SecurityEvent::PublicStateImported { .. } => "public state imported",
+            SecurityEvent::KeyExpired { .. } => "expired key refused",
+            SecurityEvent::ParticipantDisenrolled { .. } => "participant disenrolled",
+            SecurityEvent::ServiceRequestHandled { .. } => "service request handled",
+        }
+    }
+}
+
+/// An audit event together with the operation context it was recorded in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Context active when the event was logged, if any.
+    pub context: Option<OperationContext>,
+    pub event: SecurityEvent,
+}
+
+impl AuditRecord {
+    /// Structured fields: the context fields first, then the event fields not already present.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = self
+            .context
+            .as_ref()
+            .map(OperationContext::fields)
+            .unwrap_or_default();
+        for (key, value) in self.event.fields() {
+            if !fields.iter().any(|(existing, _)| *existing == key) {
+                fields.push((key, value));
+            }
+        }
+        fields
+    }
+
+    /// Whether the record concerns `key_id`, either through its context or the event itself.
+    pub fn concerns_key(&self, key_id: &str) -> bool {
+        self.context.as_ref().is_some_and(|c| c.key_id == key_id)
+            || self
+                .event
+                .fields()
+                .iter()
+                .any(|(key, value)| *key == "key_id" && value == key_id)
+    }
 }
 
 /// Enterprise audit logger
+///
+/// Events are tagged with the context set by [`AuditLogger::set_context`] when they are logged,
+/// chained into a [`ChainedAuditRecord`] and written to every [`AuditSink`] added with
+/// [`AuditLogger::add_sink`].
 pub struct AuditLogger {
+    records: Vec<AuditRecord>,
+    /// The event of each record, for [`Self::get_events`].
     events: Vec<SecurityEvent>,
+    /// Chain hash of each record, `None` for a record that could not be chained.
+    hashes: Vec<Option<[u8; 32]>>,
+    context: Option<OperationContext>,
+    sinks: Vec<Box<dyn AuditSink + Send + Sync>>,
+    head: [u8; 32],
+    sequence: u64,
+    sink_failures: u64,
 }
 
 impl AuditLogger {
     pub fn new() -> Self {
-        Self { events: Vec::new() }
+        Self {
+            records: Vec::new(),
+            events: Vec::new(),
+            hashes: Vec::new(),
+            context: None,
+            sinks: Vec::new(),
+            head: [0u8; 32],
+            sequence: 0,
+            sink_failures: 0,
+        }
+    }
+
+    /// Set (or clear) the context attached to subsequently logged events.
+    pub fn set_context(&mut self, context: Option<OperationContext>) {
+        self.context = context;
+    }
+
+    /// Persist subsequently logged events to `sink`.
+    pub fn add_sink(&mut self, sink: Box<dyn AuditSink + Send + Sync>) {
+        self.sinks.push(sink);
+    }
+
+    /// Hash of the latest chained entry, zero before the first; see [`crate::audit`].
+    pub fn chain_head(&self) -> [u8; 32] {
+        self.head
+    }
+
+    /// Entries a sink failed to persist so far.
+    pub fn sink_failures(&self) -> u64 {
+        self.sink_failures
     }
 
     pub fn log_event(&mut self, event: SecurityEvent) {
-        self.events.push(event.clone());
-        // In production, this would write to secure audit log
-        log::info!("Security event: {:?}", event);
+        let record = AuditRecord {
+            context: self.context.clone(),
+            event,
+        };
+        crate::events::emit(
+            log::Level::Info,
+            record.event.code(),
+            &record.fields(),
+            record.event.message(),
+        );
+        let hash = match ChainedAuditRecord::new(self.sequence, self.head, record.clone()) {
+            Ok(entry) => {
+                self.head.copy_from_slice(&entry.hash);
+                self.sequence += 1;
+                for sink in &mut self.sinks {
+                    if let Err(e) = sink.write(&entry) {
+                        self.sink_failures += 1;
+                        crate::events::emit(
+                            log::Level::Error,
+                            e.code(),
+                            &[
+                                ("sink", sink.name().to_string()),
+                                ("sequence", entry.sequence.to_string()),
+                                ("error", e.to_string()),
+                            ],
+                            "audit entry could not be persisted",
+                        );
+                    }
+                }
+                Some(self.head)
+            }
+            Err(e) => {
+                self.sink_failures += self.sinks.len() as u64;
+                crate::events::emit(
+                    log::Level::Error,
+                    e.code(),
+                    &[("error", e.to_string())],
+                    "audit entry could not be chained",
+                );
+                None
+            }
+        };
+        self.events.push(record.event.clone());
+        self.records.push(record);
+        self.hashes.push(hash);
+    }
+
+    /// The chained records, in order.
+    fn chain(&self) -> Vec<ChainedAuditRecord> {
+        let mut previous = [0u8; 32];
+        let chained = self
+            .records
+            .iter()
+            .zip(&self.hashes)
+            .filter_map(|(record, hash)| Some((record, (*hash)?)));
+        chained
+            .enumerate()
+            .map(|(sequence, (record, hash))| {
+                let entry = ChainedAuditRecord {
+                    sequence: sequence as u64,
+                    previous: previous.to_vec(),
+                    record: record.clone(),
+                    hash: hash.to_vec(),
+                };
+                previous = hash;
+                entry
+            })
+            .collect()
+    }
+
+    /// Recompute the chain over the records in memory and check it against the recorded hashes
+    /// and [`AuditLogger::chain_head`].
+    ///
+    /// Returns the head, or `CryptoError::SecurityViolation` naming the first entry that no
+    /// longer matches; see [`crate::audit::verify_chain`].
+    pub fn verify_chain(&self) -> CryptoResult<[u8; 32]> {
+        let head = crate::audit::verify_chain(&self.chain())?;
+        if head != self.head {
+            return Err(CryptoError::SecurityViolation {
+                details: "audit chain does not end at the logger's head".to_string(),
+            });
+        }
+        Ok(head)
+    }
+
+    /// The complete chain of this logger for third-party auditors; see [`AuditExport`].
+    pub fn export(&self) -> AuditExport {
+        AuditExport::new(self.chain(), self.head)
     }
 
+    /// All events, in logging order; [`Self::get_records`] has them with their contexts.
     pub fn get_events(&self) -> &[SecurityEvent] {
         &self.events
     }
+
+    /// All records, in logging order.
+    pub fn get_records(&self) -> &[AuditRecord] {
+        &self.records
+    }
+
+    /// Every record concerning `key_id`, in logging order.
+    pub fn history(&self, key_id: &str) -> Vec<&AuditRecord> {
+        self.records
+            .iter()
+            .filter(|r| r.concerns_key(key_id))
+            .collect()
+    }
+}
+
+impl Default for AuditLogger {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Result type for all cryptographic operations
@@ -165,6 +960,99 @@ mod tests {
         };
 
         assert!(error.to_string().contains("Key generation failed"));
+        assert_eq!(error.code(), "ZKT-E001");
+    }
+
+    #[test]
+    fn test_event_codes_are_unique() {
+        let now = chrono::Utc::now();
+        let events = [
+            SecurityEvent::KeyGenerated {
+                key_id: "k".into(),
+                timestamp: now,
+            },
+            SecurityEvent::KeyActivated {
+                key_id: "k".into(),
+                timestamp: now,
+            },
+            SecurityEvent::KeyRetired {
+                key_id: "k".into(),
+                timestamp: now,
+            },
+            SecurityEvent::KeyDestroyed {
+                key_id: "k".into(),
+                timestamp: now,
+            },
+            SecurityEvent::UnauthorizedAccess {
+                attempt: "a".into(),
+                timestamp: now,
+            },
+            SecurityEvent::PolicyViolation {
+                policy: "p".into(),
+                violation: "v".into(),
+                timestamp: now,
+            },
+            SecurityEvent::SharesDealt {
+                key_id: "k".into(),
+                threshold: 2,
+                num_shares: 3,
+                timestamp: now,
+            },
+            SecurityEvent::SharesRefreshed {
+                key_id: "k".into(),
+                threshold: 2,
+                num_shares: 3,
+                timestamp: now,
+            },
+            SecurityEvent::SecretRecovered {
+                key_id: "k".into(),
+                shares_used: 2,
+                timestamp: now,
+            },
+            SecurityEvent::SharesDealtUnproven {
+                key_id: "k".into(),
+                threshold: 2,
+                num_shares: 3,
+                timestamp: now,
+            },
+            SecurityEvent::ShareProofsPublished {
+                key_id: "k".into(),
+                num_shares: 3,
+                timestamp: now,
+            },
+            SecurityEvent::ShareRevoked {
+                key_id: "k".into(),
+                issued_by: "o".into(),
+                index: 1,
+                reason: "r".into(),
+                timestamp: now,
+            },
+            SecurityEvent::RevokedShareRejected {
+                key_id: "k".into(),
+                issued_by: "o".into(),
+                index: 1,
+                timestamp: now,
+            },
+            SecurityEvent::KeyMaterialDerived {
+                key_id: "k".into(),
+                purpose: "p".into(),
+                length: 32,
+                timestamp: now,
+            },
+            SecurityEvent::SafeModeEntered {
+                reason: "r".into(),
+                timestamp: now,
+            },
+            SecurityEvent::SafeModeExited { timestamp: now },
+            SecurityEvent::ServiceRequestHandled {
+                participant: 2,
+                operation: "verify".into(),
+                outcome: "ok".into(),
+                timestamp: now,
+            },
+        ];
+        let codes: std::collections::HashSet<_> = events.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), events.len());
     }
 
     #[test]
@@ -179,6 +1067,45 @@ mod tests {
         assert_eq!(logger.get_events().len(), 1);
     }
 
+    #[test]
+    fn test_audit_context_tagging() {
+        let mut logger = AuditLogger::new();
+        let context = OperationContext::new("key-7", 2, "refresh-1");
+        logger.set_context(Some(context.clone()));
+        logger.log_event(SecurityEvent::PolicyViolation {
+            policy: "p".into(),
+            violation: "v".into(),
+            timestamp: chrono::Utc::now(),
+        });
+        logger.set_context(None);
+        logger.log_event(SecurityEvent::KeyRetired {
+            key_id: "key-7".into(),
+            timestamp: chrono::Utc::now(),
+        });
+        logger.log_event(SecurityEvent::KeyRetired {
+            key_id: "other".into(),
+            timestamp: chrono::Utc::now(),
+        });
+
+        let history = logger.history("key-7");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].context.as_ref(), Some(&context));
+        assert_eq!(
+            &history[0].fields()[..3],
+            &[
+                ("key_id", "key-7".to_string()),
+                ("epoch", "2".to_string()),
+                ("operation_id", "refresh-1".to_string()),
+            ]
+        );
+        // The events alone, as before records carried contexts.
+        let events: &[SecurityEvent] = logger.get_events();
+        assert_eq!(events.len(), logger.get_records().len());
+        assert!(
+            matches!(events[2], SecurityEvent::KeyRetired { ref key_id, .. } if key_id == "other")
+        );
+    }
+
     #[test]
     fn test_error_recovery() {
         let mut handler = ErrorHandler::new();
@@ -0,0 +1,183 @@
+//! **events module**
+//!
+//! Stable, locale-independent event codes and structured log lines.
+//!
+//! Every operational log line, audit event and error carries a code of the form `ZKT-<class><nnn>`
+//! that never changes once released, plus `key=value` fields. The human-readable text is kept in a
+//! separate trailing `msg="..."` field, so SIEM rules and alerting can match on codes and fields
+//! without depending on wording or language.
+//!
+//! Code classes:
+//! - `S` security validation
+//! - `K` key lifecycle
+//! - `H` share dealing and maintenance
+//! - `R` secret recovery
+//! - `M` performance metrics
+//! - `D` demonstration / application flow
+//! - `N` network transport
+//! - `A` audit events (see [`crate::error::SecurityEvent::code`])
+//! - `E` errors (see [`crate::error::CryptoError::code`])
+//! - `V` verification failures (see [`crate::error::VerifyError::code`])
+
+use log::Level;
+use std::fmt;
+
+/// Log target used for all structured lines.
+pub const LOG_TARGET: &str = "zk_thresh_pro::events";
+
+/// Stable codes for operational (non-audit, non-error) log lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventCode {
+    SecurityValidationStarted,
+    SecurityPropertyVerified,
+    ComplianceModeEnabled,
+    HashBenchmarkCompleted,
+    SecurityValidationPassed,
+    EnterpriseKeyGenerated,
+    KeyRotated,
+    SharesGenerated,
+    SharesRefreshed,
+    ShareHealthChecked,
+    TranscriptStamped,
+    TranscriptStampFailed,
+    SharesDealtUnproven,
+    ShareProofsPublished,
+    ThresholdAdjusted,
+    ShareholderShareStored,
+    ShareholderRequestDenied,
+    ParticipantDisenrolled,
+    SecretRecovered,
+    RecoveryCompleted,
+    BatchRecoverySucceeded,
+    BatchRecoveryFailed,
+    ShareCollected,
+    ShareCollectionRejected,
+    ShareholderShareReleased,
+    ThresholdComplianceProven,
+    MetricReported,
+    MetricsFlushed,
+    MetricsFlushFailed,
+    AuditEventRecorded,
+    ApplicationStarted,
+    DemoStepCompleted,
+    DemoStepFailed,
+    TransportChannelEstablished,
+    TransportChannelRejected,
+}
+
+impl EventCode {
+    /// The stable wire code of this event.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            EventCode::SecurityValidationStarted => "ZKT-S001",
+            EventCode::SecurityPropertyVerified => "ZKT-S002",
+            EventCode::ComplianceModeEnabled => "ZKT-S003",
+            EventCode::HashBenchmarkCompleted => "ZKT-S004",
+            EventCode::SecurityValidationPassed => "ZKT-S005",
+            EventCode::EnterpriseKeyGenerated => "ZKT-K001",
+            EventCode::KeyRotated => "ZKT-K002",
+            EventCode::SharesGenerated => "ZKT-H001",
+            EventCode::SharesRefreshed => "ZKT-H002",
+            EventCode::ShareHealthChecked => "ZKT-H003",
+            EventCode::TranscriptStamped => "ZKT-H004",
+            EventCode::TranscriptStampFailed => "ZKT-H005",
+            EventCode::SharesDealtUnproven => "ZKT-H006",
+            EventCode::ShareProofsPublished => "ZKT-H007",
+            EventCode::ThresholdAdjusted => "ZKT-H008",
+            EventCode::ShareholderShareStored => "ZKT-H009",
+            EventCode::ShareholderRequestDenied => "ZKT-H010",
+            EventCode::ParticipantDisenrolled => "ZKT-H011",
+            EventCode::SecretRecovered => "ZKT-R001",
+            EventCode::RecoveryCompleted => "ZKT-R002",
+            EventCode::BatchRecoverySucceeded => "ZKT-R003",
+            EventCode::BatchRecoveryFailed => "ZKT-R004",
+            EventCode::ShareCollected => "ZKT-R005",
+            EventCode::ShareCollectionRejected => "ZKT-R006",
+            EventCode::ShareholderShareReleased => "ZKT-R007",
+            EventCode::ThresholdComplianceProven => "ZKT-R008",
+            EventCode::MetricReported => "ZKT-M001",
+            EventCode::MetricsFlushed => "ZKT-M002",
+            EventCode::MetricsFlushFailed => "ZKT-M003",
+            EventCode::AuditEventRecorded => "ZKT-A000",
+            EventCode::ApplicationStarted => "ZKT-D001",
+            EventCode::DemoStepCompleted => "ZKT-D002",
+            EventCode::DemoStepFailed => "ZKT-D003",
+            EventCode::TransportChannelEstablished => "ZKT-N001",
+            EventCode::TransportChannelRejected => "ZKT-N002",
+        }
+    }
+}
+
+impl fmt::Display for EventCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Render a structured line: `code=<code> <key>=<value>... msg="<text>"`.
+///
+/// Values containing whitespace, quotes or `=` are quoted with Rust string escaping so that the
+/// line stays unambiguous to parse.
+pub fn render(code: &str, fields: &[(&str, String)], message: &str) -> String {
+    let mut line = format!("code={}", code);
+    for (key, value) in fields {
+        line.push(' ');
+        line.push_str(key);
+        line.push('=');
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"' || c == '=') {
+            line.push_str(&format!("{:?}", value));
+        } else {
+            line.push_str(value);
+        }
+    }
+    line.push_str(&format!(" msg={:?}", message));
+    line
+}
+
+/// Emit a structured line at `level` on [`LOG_TARGET`].
+pub fn emit(level: Level, code: &str, fields: &[(&str, String)], message: &str) {
+    log::log!(target: LOG_TARGET, level, "{}", render(code, fields, message));
+}
+
+/// Emit a structured log line with an [`EventCode`], a human message and `key = value` fields.
+///
+/// ```ignore
+/// structured_log!(Level::Info, EventCode::SharesGenerated, "shares generated",
+///     operation_id = op, threshold = t, num_shares = n);
+/// ```
+#[macro_export]
+macro_rules! structured_log {
+    ($level:expr, $code:expr, $msg:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::events::emit(
+            $level,
+            $crate::events::EventCode::as_str($code),
+            &[$((stringify!($key), ::std::string::ToString::to_string(&$value))),*],
+            $msg,
+        )
+    };
+}
+
+pub use structured_log;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_line() {
+        let line = render(
+            EventCode::SharesGenerated.as_str(),
+            &[
+                ("operation_id", "op-1".to_string()),
+                ("threshold", 3.to_string()),
+                ("note", "two words".to_string()),
+            ],
+            "shares generated",
+        );
+
+        assert_eq!(
+            line,
+            "code=ZKT-H001 operation_id=op-1 threshold=3 note=\"two words\" msg=\"shares generated\""
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! **ffi module**
+//!
+//! C ABI for services written in other languages (feature `ffi`), declared in
+//! `include/zk_thresh_pro.h`. The header is generated from this module with
+//!
+//! ```text
+//! cbindgen --config cbindgen.toml --output include/zk_thresh_pro.h
+//! ```
+//!
+//! and must be regenerated whenever a signature here changes. Link against the `cdylib`
+//! (`libzk_thresh_pro.so`, `.dylib` or `zk_thresh_pro.dll`) built with `--features ffi`.
+//!
+//! Conventions:
+//!
+//! - Every fallible function returns an `int` status: `0` on success, `1`–`99` for the
+//!   `CryptoError` of that number (`ZKT-E004` is 4), `101`–`199` for the `VerifyError` of that
+//!   number plus 100 (`ZKT-V003` is 103), [`ZKT_ERROR_NULL_ARGUMENT`] for a null pointer and
+//!   [`ZKT_ERROR_PANIC`] for a panic. [`zkt_last_error_message`] describes the last failure on
+//!   the calling thread.
+//! - No panic unwinds into the caller: every function catches it at the boundary, returning
+//!   [`ZKT_ERROR_PANIC`], or null, zero or nothing where there is no status.
+//! - Results are written through out-pointers, which are left untouched on failure.
+//! - A `ZktShares*` is owned by the caller and released with [`zkt_shares_free`]; a `char*`
+//!   returned by the library is released with [`zkt_string_free`], which zeroizes it first since
+//!   shares serialize their secret parts. Borrowed pointers are never retained.
+//! - Secrets cross the boundary as 32-byte canonical little-endian scalars, slices as the
+//!   `ShareData` JSON or 232-byte binary encodings of [`crate::format`].
+//! - Handles are not synchronized: use one from one thread at a time.
+
+use crate::error::{CryptoError, VerifyError};
+use crate::recovered::RecoveredSecret;
+use crate::serialization::scalar_from_slice;
+use crate::sharing::{self, ShareData, SHARE_DATA_LENGTH};
+use crate::vss;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use zeroize::Zeroize;
+
+/// Status of a successful call.
+pub const ZKT_OK: c_int = 0;
+
+/// Status of a call passed a null pointer where a value is required.
+pub const ZKT_ERROR_NULL_ARGUMENT: c_int = -1;
+
+/// Status of a call that panicked; the panic was caught before reaching the caller.
+pub const ZKT_ERROR_PANIC: c_int = -2;
+
+/// Length of a secret scalar.
+pub const ZKT_SECRET_LENGTH: usize = 32;
+
+/// Length of the binary encoding of one slice.
+pub const ZKT_SHARE_LENGTH: usize = 232;
+
+// Spelled out for cbindgen, which cannot evaluate the expression behind SHARE_DATA_LENGTH.
+const _: () = assert!(ZKT_SHARE_LENGTH == SHARE_DATA_LENGTH);
+
+/// A list of slices.
+pub struct ZktShares(Vec<ShareData>);
+
+enum Failure {
+    Crypto(CryptoError),
+    Verify(VerifyError),
+    Null(&'static str),
+    Panic,
+}
+
+impl From<CryptoError> for Failure {
+    fn from(error: CryptoError) -> Self {
+        Failure::Crypto(error)
+    }
+}
+
+impl From<VerifyError> for Failure {
+    fn from(error: VerifyError) -> Self {
+        Failure::Verify(error)
+    }
+}
+
+/// Status for a stable `ZKT-E…` or `ZKT-V…` code.
+fn status(code: &str) -> c_int {
+    let number: c_int = code[5..].parse().expect("stable codes end in digits");
+    if code.as_bytes()[4] == b'V' {
+        100 + number
+    } else {
+        number
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn run(call: impl FnOnce() -> Result<(), Failure>) -> c_int {
+    let result = panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or(Err(Failure::Panic));
+    let (status, message) = match result {
+        Ok(()) => return ZKT_OK,
+        Err(Failure::Crypto(e)) => (status(e.code()), format!("{} {}", e.code(), e)),
+        Err(Failure::Verify(e)) => (status(e.code()), format!("{} {}", e.code(), e)),
+        Err(Failure::Null(argument)) => (
+            ZKT_ERROR_NULL_ARGUMENT,
+            format!("{} must not be null", argument),
+        ),
+        Err(Failure::Panic) => (ZKT_ERROR_PANIC, "the call panicked".to_string()),
+    };
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    status
+}
+
+/// `call`, or `fallback` if it panics, for functions without a status.
+fn guard<T>(fallback: T, call: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or(fallback)
+}
+
+fn json_error(e: serde_json::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: e.to_string(),
+    }
+}
+
+unsafe fn borrow<'a, T>(pointer: *const T, argument: &'static str) -> Result<&'a T, Failure> {
+    pointer.as_ref().ok_or(Failure::Null(argument))
+}
+
+unsafe fn borrow_bytes<'a>(
+    pointer: *const u8,
+    length: usize,
+    argument: &'static str,
+) -> Result<&'a [u8], Failure> {
+    if pointer.is_null() {
+        return Err(Failure::Null(argument));
+    }
+    Ok(std::slice::from_raw_parts(pointer, length))
+}
+
+/// Out-pointer for a returned handle, checked before anything is allocated for it.
+unsafe fn returned_handle<'a, T>(
+    pointer: *mut *mut T,
+    argument: &'static str,
+) -> Result<&'a mut *mut T, Failure> {
+    pointer.as_mut().ok_or(Failure::Null(argument))
+}
+
+fn into_c_string(value: String) -> Result<*mut c_char, Failure> {
+    let string = CString::new(value).map_err(|e| CryptoError::Serialization {
+        details: e.to_string(),
+    })?;
+    Ok(string.into_raw())
+}
+
+/// Version of the library, e.g. `"0.1.0"`; a static string, not to be freed.
+#[no_mangle]
+pub extern "C" fn zkt_version() -> *const c_char {
+    guard(std::ptr::null(), || {
+        concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+    })
+}
+
+/// Message of the last failed call on this thread, or null if none failed.
+///
+/// The string stays valid until the next failing call on the same thread and is not to be freed.
+#[no_mangle]
+pub extern "C" fn zkt_last_error_message() -> *const c_char {
+    guard(std::ptr::null(), || {
+        LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map_or(std::ptr::null(), |message| message.as_ptr())
+        })
+    })
+}
+
+/// Deal `secret` into `num_shares` slices, any `threshold` of which recover it.
+///
+/// # Safety
+///
+/// `secret` must point to [`ZKT_SECRET_LENGTH`] readable bytes and `out` to a writable
+/// `ZktShares*`.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_generate_shares(
+    secret: *const u8,
+    threshold: usize,
+    num_shares: usize,
+    out: *mut *mut ZktShares,
+) -> c_int {
+    run(|| {
+        let bytes = borrow_bytes(secret, ZKT_SECRET_LENGTH, "secret")?;
+        let out = returned_handle(out, "out")?;
+        let secret = RecoveredSecret::new(scalar_from_slice(bytes, "secret")?);
+        let shares = sharing::generate_key_shares(*secret.as_scalar(), threshold, num_shares)?;
+        *out = Box::into_raw(Box::new(ZktShares(shares)));
+        Ok(())
+    })
+}
+
+/// An empty list, to be filled with [`zkt_shares_push_bytes`]; null if it cannot be allocated.
+#[no_mangle]
+pub extern "C" fn zkt_shares_new() -> *mut ZktShares {
+    guard(std::ptr::null_mut(), || {
+        Box::into_raw(Box::new(ZktShares(Vec::new())))
+    })
+}
+
+/// Release `shares`; null is ignored.
+///
+/// # Safety
+///
+/// `shares` must be null or a list returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_shares_free(shares: *mut ZktShares) {
+    guard((), || {
+        if !shares.is_null() {
+            drop(Box::from_raw(shares));
+        }
+    })
+}
+
+/// Number of slices in `shares`, 0 if it is null.
+///
+/// # Safety
+///
+/// `shares` must be null or a live list.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_shares_len(shares: *const ZktShares) -> usize {
+    guard(0, || shares.as_ref().map_or(0, |shares| shares.0.len()))
+}
+
+/// Parse a JSON array of `ShareData`.
+///
+/// The slices are decoded only; check them with [`zkt_verify_shares`].
+///
+/// # Safety
+///
+/// `json` must be a NUL-terminated string and `out` must point to a writable `ZktShares*`.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_shares_from_json(
+    json: *const c_char,
+    out: *mut *mut ZktShares,
+) -> c_int {
+    run(|| {
+        if json.is_null() {
+            return Err(Failure::Null("json"));
+        }
+        let out = returned_handle(out, "out")?;
+        let shares: Vec<ShareData> =
+            serde_json::from_slice(CStr::from_ptr(json).to_bytes()).map_err(json_error)?;
+        *out = Box::into_raw(Box::new(ZktShares(shares)));
+        Ok(())
+    })
+}
+
+/// Serialize `shares` as a JSON array of `ShareData`, to be freed with [`zkt_string_free`].
+///
+/// # Safety
+///
+/// `shares` must be a live list and `out` must point to a writable `char*`.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_shares_to_json(
+    shares: *const ZktShares,
+    out: *mut *mut c_char,
+) -> c_int {
+    run(|| {
+        let shares = borrow(shares, "shares")?;
+        let out = returned_handle(out, "out")?;
+        let json = serde_json::to_string(&shares.0).map_err(json_error)?;
+        *out = into_c_string(json)?;
+        Ok(())
+    })
+}
+
+/// Append the slice in its [`ZKT_SHARE_LENGTH`]-byte binary encoding to `shares`.
+///
+/// # Safety
+///
+/// `shares` must be a live list and `bytes` must point to `length` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_shares_push_bytes(
+    shares: *mut ZktShares,
+    bytes: *const u8,
+    length: usize,
+) -> c_int {
+    run(|| {
+        let shares = shares.as_mut().ok_or(Failure::Null("shares"))?;
+        let share = ShareData::from_bytes(borrow_bytes(bytes, length, "bytes")?)?;
+        shares.0.push(share);
+        Ok(())
+    })
+}
+
+/// Write slice `position` of `shares` in its binary encoding to `out`.
+///
+/// Returns the `ZKT-E005` status if `position` is out of range.
+///
+/// # Safety
+///
+/// `shares` must be a live list and `out` must point to [`ZKT_SHARE_LENGTH`] writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_share_to_bytes(
+    shares: *const ZktShares,
+    position: usize,
+    out: *mut u8,
+) -> c_int {
+    run(|| {
+        let shares = borrow(shares, "shares")?;
+        let share = shares
+            .0
+            .get(position)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "position".to_string(),
+                reason: format!("the list holds {} slices", shares.0.len()),
+            })?;
+        if out.is_null() {
+            return Err(Failure::Null("out"));
+        }
+        std::ptr::copy_nonoverlapping(share.to_bytes().as_ptr(), out, ZKT_SHARE_LENGTH);
+        Ok(())
+    })
+}
+
+/// Check the proof of every slice in `shares` against its own commitment and index.
+///
+/// # Safety
+///
+/// `shares` must be a live list.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_verify_shares(shares: *const ZktShares) -> c_int {
+    run(|| {
+        let shares = borrow(shares, "shares")?;
+        vss::verify_share_validity(&shares.0)?;
+        Ok(())
+    })
+}
+
+/// Recover the secret from `shares` into `out`, once every slice proof verifies.
+///
+/// The slices are checked and interpolated in constant time by [`crate::recover_secret`]; an
+/// invalid slice fails as by `From<VerifyError> for CryptoError`, with the `ZKT-V…` code of its
+/// failure in the message.
+///
+/// # Safety
+///
+/// `shares` must be a live list and `out` must point to [`ZKT_SECRET_LENGTH`] writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_recover_secret(shares: *const ZktShares, out: *mut u8) -> c_int {
+    run(|| {
+        let shares = borrow(shares, "shares")?;
+        if out.is_null() {
+            return Err(Failure::Null("out"));
+        }
+        let secret = RecoveredSecret::new(crate::recover_secret(&shares.0)?);
+        std::ptr::copy_nonoverlapping(
+            secret.as_scalar().as_bytes().as_ptr(),
+            out,
+            ZKT_SECRET_LENGTH,
+        );
+        Ok(())
+    })
+}
+
+/// Zeroize and release a string returned by the library; null is ignored.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zkt_string_free(string: *mut c_char) {
+    guard((), || {
+        if !string.is_null() {
+            CString::from_raw(string).into_bytes().zeroize();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_ffi_round_trip() {
+        let secret = [7u8; ZKT_SECRET_LENGTH];
+        let mut shares = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                zkt_generate_shares(secret.as_ptr(), 2, 3, &mut shares),
+                ZKT_OK
+            );
+            assert_eq!(zkt_shares_len(shares), 3);
+            assert_eq!(zkt_verify_shares(shares), ZKT_OK);
+
+            // Two slices through the binary encoding, all three through JSON.
+            let subset = zkt_shares_new();
+            let mut bytes = [0u8; ZKT_SHARE_LENGTH];
+            for position in [0, 2] {
+                assert_eq!(
+                    zkt_share_to_bytes(shares, position, bytes.as_mut_ptr()),
+                    ZKT_OK
+                );
+                assert_eq!(
+                    zkt_shares_push_bytes(subset, bytes.as_ptr(), bytes.len()),
+                    ZKT_OK
+                );
+            }
+            let mut recovered = [0u8; ZKT_SECRET_LENGTH];
+            assert_eq!(zkt_recover_secret(subset, recovered.as_mut_ptr()), ZKT_OK);
+            assert_eq!(recovered, secret);
+
+            let mut json = ptr::null_mut();
+            assert_eq!(zkt_shares_to_json(shares, &mut json), ZKT_OK);
+            let mut parsed = ptr::null_mut();
+            assert_eq!(zkt_shares_from_json(json, &mut parsed), ZKT_OK);
+            assert_eq!(zkt_shares_len(parsed), 3);
+            zkt_string_free(json);
+
+            zkt_shares_free(subset);
+            zkt_shares_free(parsed);
+            zkt_shares_free(shares);
+        }
+    }
+
+    #[test]
+    fn test_ffi_errors_carry_stable_codes() {
+        let mut shares = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                zkt_generate_shares(ptr::null(), 2, 3, &mut shares),
+                ZKT_ERROR_NULL_ARGUMENT
+            );
+            assert!(shares.is_null());
+            let message = CStr::from_ptr(zkt_last_error_message());
+            assert_eq!(message.to_str().unwrap(), "secret must not be null");
+
+            // A non-canonical scalar is a serialization error, ZKT-E004.
+            assert_eq!(
+                zkt_generate_shares([0xff; 32].as_ptr(), 2, 3, &mut shares),
+                4
+            );
+            let message = CStr::from_ptr(zkt_last_error_message()).to_str().unwrap();
+            assert!(message.starts_with("ZKT-E004"));
+
+            let empty = zkt_shares_new();
+            let mut bytes = [0u8; ZKT_SHARE_LENGTH];
+            assert_eq!(zkt_share_to_bytes(empty, 0, bytes.as_mut_ptr()), 5);
+            assert_eq!(zkt_shares_push_bytes(empty, bytes.as_ptr(), 3), 4);
+
+            // A slice whose proof fails is refused instead of interpolated, as ZKT-E006.
+            assert_eq!(
+                zkt_generate_shares([7u8; 32].as_ptr(), 1, 1, &mut shares),
+                ZKT_OK
+            );
+            assert_eq!(zkt_share_to_bytes(shares, 0, bytes.as_mut_ptr()), ZKT_OK);
+            bytes[136..168].copy_from_slice(curve25519_dalek::Scalar::ONE.as_bytes());
+            assert_eq!(
+                zkt_shares_push_bytes(empty, bytes.as_ptr(), bytes.len()),
+                ZKT_OK
+            );
+            let mut recovered = [0u8; ZKT_SECRET_LENGTH];
+            assert_eq!(zkt_recover_secret(empty, recovered.as_mut_ptr()), 6);
+            assert_eq!(recovered, [0u8; ZKT_SECRET_LENGTH]);
+            zkt_shares_free(empty);
+            zkt_shares_free(shares);
+        }
+        assert_eq!(status("ZKT-V003"), 103);
+
+        // Panics stop at the boundary.
+        assert_eq!(
+            run(|| panic!("unreachable in the library")),
+            ZKT_ERROR_PANIC
+        );
+        let message = unsafe { CStr::from_ptr(zkt_last_error_message()) };
+        assert_eq!(message.to_str().unwrap(), "the call panicked");
+        assert_eq!(guard(0usize, || panic!("unreachable in the library")), 0);
+    }
+}
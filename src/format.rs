@@ -0,0 +1,5032 @@
+//! **format module**
+//!
+//! Machine-readable description of every serialized artifact the crate can emit.
+//!
+//! [`describe()`] returns the field layout, encodings, versions and algorithms of each artifact,
+//! so integrators and auditors can generate parsers and validation rules instead of reverse
+//! engineering JSON samples. The description is itself serializable (`serde_json::to_string`).
+//!
+//! Whenever a serialized type changes shape, its entry here must change with it and its
+//! `version` must be bumped.
+
+use serde::Serialize;
+
+/// Encoding of one field value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldKind {
+    /// Unsigned JSON integer with the given bit width.
+    Unsigned { bits: u32 },
+    /// JSON floating-point number.
+    Number,
+    /// JSON `true` or `false`.
+    Boolean,
+    /// UTF-8 JSON string.
+    String,
+    /// Lowercase hex of the 32-byte canonical little-endian encoding of a Ristretto scalar
+    /// (the raw 32 bytes under non-human-readable serde formats).
+    HexScalar,
+    /// Lowercase hex of a 32-byte compressed Ristretto point; the identity and non-canonical
+    /// encodings are rejected (the raw 32 bytes under non-human-readable serde formats).
+    HexRistrettoPoint,
+    /// Lowercase hex string of raw bytes, optionally of fixed length (in bytes). All hex fields
+    /// reject uppercase digits.
+    HexBytes { length: Option<usize> },
+    /// JSON array of byte values (0-255), optionally of fixed length.
+    ByteArray { length: Option<usize> },
+    /// JSON integer holding UNIX seconds (UTC).
+    UnixSeconds,
+    /// JSON string holding one of the listed variant names.
+    Enum { variants: Vec<&'static str> },
+    /// JSON array of elements of the given kind.
+    Array { element: Box<FieldKind> },
+    /// Nested artifact described elsewhere in the same description.
+    Artifact { name: &'static str },
+    /// `null` or a value of the given kind.
+    Optional { inner: Box<FieldKind> },
+    /// JSON object from keys of the given string kind to values of the given kind.
+    Map {
+        key: Box<FieldKind>,
+        value: Box<FieldKind>,
+    },
+}
+
+/// One named field of an artifact.
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    #[serde(flatten)]
+    pub kind: FieldKind,
+    pub description: &'static str,
+}
+
+/// Shape of an artifact's top-level JSON value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    /// JSON object with the listed fields.
+    Object,
+    /// JSON array whose positions are the listed fields, in order.
+    Tuple,
+    /// Externally tagged enum: `{ "<Variant>": { ...fields } }`; fields list the union of all
+    /// variant fields, and `variants` lists the tags.
+    TaggedEnum { variants: Vec<&'static str> },
+}
+
+/// Description of one serialized artifact.
+#[derive(Clone, Debug, Serialize)]
+pub struct ArtifactDescription {
+    /// Rust type name of the artifact.
+    pub name: &'static str,
+    /// Format version; bumped on every incompatible layout change.
+    pub version: u32,
+    /// Wire encoding.
+    pub encoding: &'static str,
+    pub layout: Layout,
+    pub fields: Vec<FieldDescription>,
+    /// Algorithms needed to produce or validate the artifact.
+    pub algorithms: Vec<&'static str>,
+    pub description: &'static str,
+}
+
+/// Description of every artifact, tagged with the producing crate version.
+#[derive(Clone, Debug, Serialize)]
+pub struct FormatDescription {
+    pub crate_name: &'static str,
+    pub crate_version: &'static str,
+    pub artifacts: Vec<ArtifactDescription>,
+}
+
+impl FormatDescription {
+    /// Look up an artifact by type name.
+    pub fn artifact(&self, name: &str) -> Option<&ArtifactDescription> {
+        self.artifacts.iter().find(|a| a.name == name)
+    }
+}
+
+fn field(name: &'static str, kind: FieldKind, description: &'static str) -> FieldDescription {
+    FieldDescription {
+        name,
+        kind,
+        description,
+    }
+}
+
+fn array(element: FieldKind) -> FieldKind {
+    FieldKind::Array {
+        element: Box::new(element),
+    }
+}
+
+fn optional(inner: FieldKind) -> FieldKind {
+    FieldKind::Optional {
+        inner: Box::new(inner),
+    }
+}
+
+/// `usize` fields are written as 64-bit integers on every target; 32-bit targets reject values
+/// above `u32::MAX` when reading.
+const USIZE: FieldKind = FieldKind::Unsigned { bits: 64 };
+/// SEC1 compressed secp256k1 point; the identity would be the single byte `00`.
+/// Compressed ed25519 point in the prime-order subgroup, other than the identity.
+const EDWARDS_POINT: FieldKind = FieldKind::HexBytes { length: Some(32) };
+const SECP256K1_POINT: FieldKind = FieldKind::HexBytes { length: Some(33) };
+
+/// Describe every serialized artifact the crate can emit.
+pub fn describe() -> FormatDescription {
+    FormatDescription {
+        crate_name: env!("CARGO_PKG_NAME"),
+        crate_version: env!("CARGO_PKG_VERSION"),
+        artifacts: vec![
+            proof(),
+            proof_binary(),
+            share_data(),
+            share_data_binary(),
+            unproven_share(),
+            share_file(),
+            share_mnemonic(),
+            share_qr_payload(),
+            gf256_share_vault(),
+            share_proof(),
+            dealing(),
+            policy(),
+            policy_share(),
+            hierarchical_share(),
+            aggregate_proof(),
+            multi_share_data(),
+            vss_commitments(),
+            vss_commitments_binary(),
+            commitment_diff(),
+            refresh_record(),
+            refresh_links(),
+            share_link(),
+            committee(),
+            plan_step(),
+            reconfiguration_plan(),
+            nonce_commitment(),
+            partial_signature(),
+            schnorr_signature(),
+            schnorr_signature_binary(),
+            frost_signing_commitments(),
+            frost_signing_package(),
+            frost_signature_share(),
+            frost_public_key_package(),
+            frost_verification_share(),
+            ed25519_signing_commitments(),
+            ed25519_signing_package(),
+            ed25519_signature_share(),
+            ed25519_public_key_package(),
+            ed25519_verification_share(),
+            ed25519_signature(),
+            bip340_signing_commitments(),
+            bip340_signing_package(),
+            bip340_signature_share(),
+            bip340_public_key_package(),
+            bip340_verification_share(),
+            bip340_signature(),
+            frost_header(),
+            frost_key_package_rfc9591(),
+            frost_public_key_package_rfc9591(),
+            dkg_round1_broadcast(),
+            dkg_constant_term_proof(),
+            dkg_round2_share(),
+            dkg_complaint(),
+            dkg_complaint_response(),
+            dkg_output(),
+            repair_request(),
+            repair_part(),
+            repair_sum(),
+            transport_message(),
+            recovery_request(),
+            service_request(),
+            participant_identity(),
+            signed_message(),
+            service_response(),
+            share_feedback(),
+            collection_status(),
+            algorithm_suite(),
+            ceremony_transcript(),
+            timestamp_token(),
+            ceremony_state(),
+            ceremony_round(),
+            ceremony_response(),
+            protocol_transcript(),
+            sealed_share(),
+            wrapped_share(),
+            pvss_dealing(),
+            pvss_encrypted_share(),
+            pvss_decrypted_share(),
+            pvss_dleq_proof(),
+            threshold_ciphertext(),
+            partial_decryption(),
+            beacon_commitment(),
+            beacon_reveal(),
+            beacon_transcript(),
+            policy_compliance_proof(),
+            committee_member(),
+            contribution_proof(),
+            index_assignment(),
+            custodian_index(),
+            share_attestation(),
+            stored_share(),
+            security_event(),
+            operation_context(),
+            audit_record(),
+            chained_audit_record(),
+            audit_export(),
+            share_registry(),
+            registry_entry(),
+            rotation_entry(),
+            share_status(),
+            public_state(),
+            key_policy(),
+            system_status(),
+            key_binary(),
+            counter_file(),
+            archive(),
+            archive_chunk(),
+            archive_entry(),
+            key_status(),
+            key_state_counts(),
+            custodian_status(),
+            performance_summary(),
+            crypto_error(),
+            verify_error(),
+            performance_metrics(),
+            operation_counts(),
+            harness_report(),
+            timing_report(),
+            lagrange_error(),
+            blake3_adapter(),
+            blake3_hasher_state(),
+            blake3_chain_state(),
+        ],
+    }
+}
+
+fn proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Proof",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "proof_ristretto_point",
+                FieldKind::HexRistrettoPoint,
+                "R = k_s·G + k_r·H",
+            ),
+            field("response_share", FieldKind::HexScalar, "z_s = k_s + c·s"),
+            field("response_random", FieldKind::HexScalar, "z_r = k_r + c·r"),
+            field(
+                "challenge",
+                FieldKind::HexScalar,
+                "c = Transcript(\"pedersen-opening\"): G, H, C, R, index_le, challenge \"c\"",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "blake3-512", "schnorr-pedersen-fiat-shamir"],
+        description: "Non-interactive proof of knowledge of a Pedersen commitment opening",
+    }
+}
+
+fn proof_binary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ProofBinary",
+        version: 2,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "proof_ristretto_point",
+                FieldKind::ByteArray { length: Some(32) },
+                "Compressed R",
+            ),
+            field(
+                "response_share",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian z_s",
+            ),
+            field(
+                "response_random",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian z_r",
+            ),
+            field(
+                "challenge",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian c",
+            ),
+        ],
+        algorithms: vec!["ristretto255"],
+        description: "Fixed 128-byte encoding of Proof (to_bytes)",
+    }
+}
+
+fn share_data_binary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareDataBinary",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "index",
+                FieldKind::Unsigned { bits: 64 },
+                "Little-endian; non-zero",
+            ),
+            field(
+                "share",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian f(index); secret",
+            ),
+            field(
+                "commitment",
+                FieldKind::ByteArray { length: Some(32) },
+                "Compressed C",
+            ),
+            field(
+                "random",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian g(index); secret",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact {
+                    name: "ProofBinary",
+                },
+                "Proof over commitment",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "shamir", "pedersen-commitment"],
+        description: "Fixed 232-byte encoding of ShareData (to_bytes)",
+    }
+}
+
+fn key_binary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "KeyBinary",
+        version: 2,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "secret",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian scalar; secret",
+            ),
+            field(
+                "state",
+                FieldKind::Unsigned { bits: 8 },
+                "0 Generated, 1 Active, 2 Retired, 3 Destroyed",
+            ),
+            field(
+                "creation_time",
+                FieldKind::ByteArray { length: Some(8) },
+                "Little-endian i64 microseconds since the UNIX epoch",
+            ),
+            field(
+                "activation_time",
+                FieldKind::ByteArray { length: Some(9) },
+                "Presence byte (0 or 1) then the time as creation_time; all zero when absent",
+            ),
+            field(
+                "retirement_time",
+                FieldKind::ByteArray { length: Some(9) },
+                "As activation_time",
+            ),
+            field(
+                "expiry_time",
+                FieldKind::ByteArray { length: Some(9) },
+                "As activation_time; absent from version 1 encodings",
+            ),
+        ],
+        algorithms: vec!["ristretto255"],
+        description:
+            "Fixed 68-byte encoding of Key (to_bytes); the 59-byte version 1 is still decoded",
+    }
+}
+
+fn counter_file() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CounterFile",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![field(
+            "value",
+            FieldKind::ByteArray { length: Some(8) },
+            "Little-endian u64; the file is named by the hex of the counter name",
+        )],
+        algorithms: vec![],
+        description: "One FileCounterStore counter (epoch/<key_id> or operation_sequence)",
+    }
+}
+
+fn archive() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Archive",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field("magic", FieldKind::ByteArray { length: Some(4) }, "ASCII \"ZKTA\""),
+            field("version", FieldKind::Unsigned { bits: 8 }, "Archive version, 1"),
+            field(
+                "codec",
+                FieldKind::Unsigned { bits: 8 },
+                "Chunk compression: 0 stored, 1 zstd; other ids are application codecs",
+            ),
+            field(
+                "entry_count",
+                FieldKind::Unsigned { bits: 32 },
+                "Number of entries, little-endian",
+            ),
+            field(
+                "chunk_count",
+                FieldKind::Unsigned { bits: 32 },
+                "Number of chunks, little-endian",
+            ),
+            field(
+                "root",
+                FieldKind::ByteArray { length: Some(32) },
+                "BLAKE3 derive_key(\"zk-thresh-pro 2024 archive root v1\") over the preceding header bytes and the index",
+            ),
+            field(
+                "chunks",
+                array(FieldKind::Artifact {
+                    name: "ArchiveChunk",
+                }),
+                "chunk_count records of 48 bytes",
+            ),
+            field(
+                "entries",
+                array(FieldKind::Artifact {
+                    name: "ArchiveEntry",
+                }),
+                "entry_count records of 13 bytes, in the order the entries were added",
+            ),
+            field(
+                "data",
+                FieldKind::ByteArray { length: None },
+                "The stored chunks back to back",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Chunked archive of transcripts, audit records and commitments; entries are read by decompressing their chunk only",
+    }
+}
+
+fn archive_chunk() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ArchiveChunk",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "offset",
+                FieldKind::Unsigned { bits: 64 },
+                "Start of the stored chunk from the start of the data, little-endian",
+            ),
+            field(
+                "stored_length",
+                FieldKind::Unsigned { bits: 32 },
+                "Compressed length, little-endian",
+            ),
+            field(
+                "raw_length",
+                FieldKind::Unsigned { bits: 32 },
+                "Uncompressed length, little-endian",
+            ),
+            field(
+                "checksum",
+                FieldKind::ByteArray { length: Some(32) },
+                "BLAKE3 derive_key(\"zk-thresh-pro 2024 archive chunk v1\") over the uncompressed chunk",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Index record of one Archive chunk",
+    }
+}
+
+fn archive_entry() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ArchiveEntry",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "kind",
+                FieldKind::Unsigned { bits: 8 },
+                "0 CeremonyTranscript JSON, 1 AuditRecord JSON, 2 VssCommitmentsBinary",
+            ),
+            field(
+                "chunk",
+                FieldKind::Unsigned { bits: 32 },
+                "Chunk holding the entry, little-endian",
+            ),
+            field(
+                "offset",
+                FieldKind::Unsigned { bits: 32 },
+                "Start within the uncompressed chunk, little-endian",
+            ),
+            field(
+                "length",
+                FieldKind::Unsigned { bits: 32 },
+                "Length of the entry, little-endian",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Index record of one Archive entry",
+    }
+}
+
+fn share_data() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareData",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Evaluation point, non-zero and unique"),
+            field("share", FieldKind::HexScalar, "f(index); secret"),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "C = share·G + random·H; equals Σ index^j·C_j in a VSS dealing",
+            ),
+            field(
+                "random",
+                FieldKind::HexScalar,
+                "Blinding random g(index); secret",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Proof over commitment",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "shamir", "pedersen-commitment"],
+        description: "One slice of a Shamir sharing with its commitment and proof; TypedShare<T, N> serializes identically",
+    }
+}
+
+fn share_file() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareFile",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "magic",
+                FieldKind::ByteArray { length: Some(4) },
+                "ASCII \"ZKTS\"",
+            ),
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Format version, 1",
+            ),
+            field(
+                "ciphersuite",
+                FieldKind::Unsigned { bits: 16 },
+                "Little-endian; 1 = ristretto255 with BLAKE3",
+            ),
+            field(
+                "length",
+                FieldKind::Unsigned { bits: 32 },
+                "Payload length in bytes, little-endian; at most 65536",
+            ),
+            field(
+                "payload",
+                FieldKind::Artifact { name: "ShareData" },
+                "ShareData JSON, UTF-8",
+            ),
+            field(
+                "tag",
+                FieldKind::ByteArray { length: Some(32) },
+                "Keyed BLAKE3 over all preceding bytes, key derived from the caller's key",
+            ),
+        ],
+        algorithms: vec!["blake3-keyed", "blake3-derive-key"],
+        description: "Long-term on-disk form of one slice (ShareFile::write / ShareFile::read)",
+    }
+}
+
+fn unproven_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "UnprovenShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Evaluation point, non-zero and unique"),
+            field("share", FieldKind::HexScalar, "f(index); secret"),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "C = share·G + random·H = Σ index^j·C_j",
+            ),
+            field(
+                "random",
+                FieldKind::HexScalar,
+                "Blinding random g(index); secret",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "shamir", "pedersen-commitment"],
+        description: "Slice of a deferred-proof dealing, awaiting its ShareProof",
+    }
+}
+
+fn share_mnemonic() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareMnemonic",
+        version: 1,
+        encoding: "words",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 4 },
+                "Format version, 1; followed by 2 zero bits",
+            ),
+            field(
+                "index",
+                FieldKind::Unsigned { bits: 32 },
+                "Big-endian; non-zero",
+            ),
+            field(
+                "share",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian slice value",
+            ),
+            field(
+                "random",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian blinding",
+            ),
+            field(
+                "checksum",
+                FieldKind::Unsigned { bits: 30 },
+                "RS1024 over the data words, customization string \"zk-thresh-pro share\"",
+            ),
+        ],
+        algorithms: vec!["slip39-wordlist", "rs1024", "pedersen-commitment"],
+        description: "58 SLIP-39 English words, 10 bits each, most significant first (to_mnemonic / from_mnemonic); the commitment is recomputed and the proof regenerated on decoding",
+    }
+}
+
+fn share_qr_payload() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareQrPayload",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "magic",
+                FieldKind::ByteArray { length: Some(2) },
+                "ASCII \"ZQ\"",
+            ),
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Format version, 1",
+            ),
+            field(
+                "ciphersuite",
+                FieldKind::Unsigned { bits: 16 },
+                "Little-endian; 1 = ristretto255 with BLAKE3",
+            ),
+            field(
+                "index",
+                FieldKind::Unsigned { bits: 32 },
+                "Little-endian; non-zero",
+            ),
+            field(
+                "share",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian slice value",
+            ),
+            field(
+                "random",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian blinding",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact {
+                    name: "ProofBinary",
+                },
+                "Proof of the opening of the recomputed commitment",
+            ),
+            field(
+                "parity",
+                FieldKind::ByteArray { length: Some(32) },
+                "Reed-Solomon check bytes over GF(2^8) (AES field, generator 3, roots 3^0..3^31), correcting 16 bytes",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "pedersen-commitment", "reed-solomon-gf256"],
+        description: "233-byte QR code payload of one slice (to_qr_payload / from_qr_payload, qr feature); the commitment is recomputed on reading",
+    }
+}
+
+fn gf256_share_vault() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Gf256ShareVault",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "y",
+                FieldKind::ByteArray { length: None },
+                "One GF(2^8) evaluation per secret byte",
+            ),
+            field(
+                "x",
+                FieldKind::Unsigned { bits: 8 },
+                "Evaluation point; non-zero",
+            ),
+        ],
+        algorithms: vec!["shamir-gf256-aes-field"],
+        description: "Byte-wise GF(256) share in the layout HashiCorp Vault documents (Gf256Share::to_vault_bytes / from_vault_bytes); not tested against Vault output",
+    }
+}
+
+fn share_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Index of the proven slice"),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Proof over the slice commitment",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "blake3-512", "schnorr-pedersen-fiat-shamir"],
+        description: "Proof published after a deferred-proof dealing",
+    }
+}
+
+fn dealing() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Dealing",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "shares",
+                array(FieldKind::Artifact {
+                    name: "UnprovenShare",
+                }),
+                "The n slices, without per-slice proofs; secret",
+            ),
+            field(
+                "aggregate_proof",
+                FieldKind::Artifact {
+                    name: "AggregateProof",
+                },
+                "Proof over every slice commitment",
+            ),
+            field(
+                "threshold",
+                USIZE,
+                "Slices needed to recover; the number of coefficient commitments",
+            ),
+            field("num_shares", USIZE, "Number of slices, n"),
+            field(
+                "dealer_id",
+                FieldKind::String,
+                "Dealer identifier, non-empty; not covered by the proof",
+            ),
+            field(
+                "ciphersuite",
+                FieldKind::Unsigned { bits: 16 },
+                "1 = ristretto255 with BLAKE3",
+            ),
+            field(
+                "created_at",
+                FieldKind::UnixSeconds,
+                "Creation time; not covered by the proof",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "shamir", "pedersen-vss"],
+        description: "Whole dealing with one aggregate proof and its metadata (generate_dealing)",
+    }
+}
+
+fn policy() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Policy",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec!["Party", "And", "Or", "Threshold"],
+        },
+        fields: vec![
+            field("name", FieldKind::String, "Party: name of the party"),
+            field(
+                "threshold",
+                USIZE,
+                "Threshold: children that must be satisfied",
+            ),
+            field(
+                "children",
+                array(FieldKind::Artifact { name: "Policy" }),
+                "And, Or, Threshold: sub-policies",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Monotone access policy compiled to a linear secret sharing scheme",
+    }
+}
+
+fn policy_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PolicyShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("party", FieldKind::String, "Party holding the slice"),
+            field(
+                "share",
+                FieldKind::Artifact { name: "ShareData" },
+                "Slice at the index of its row in the scheme",
+            ),
+        ],
+        algorithms: vec![
+            "ristretto255",
+            "linear-secret-sharing",
+            "pedersen-commitment",
+        ],
+        description: "Slice dealt under a Policy; secret",
+    }
+}
+
+fn hierarchical_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "HierarchicalShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("level", USIZE, "Level of the holder, from 1"),
+            field(
+                "share",
+                FieldKind::Artifact { name: "ShareData" },
+                "Derivative of order k_{level-1} of the dealing polynomial at the index",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "tassa-hierarchical", "pedersen-commitment"],
+        description: "Slice of a hierarchical threshold dealing; secret",
+    }
+}
+
+fn aggregate_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "AggregateProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "commitments",
+                FieldKind::Artifact {
+                    name: "VssCommitments",
+                },
+                "Coefficient commitments A_0..A_{t-1}",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Opening of P = Σ ρ^k·C_k; ρ and c from Transcript(\"dealing-aggregate\") \
+                 over t, n, every A_j and every (index, C_k)",
+            ),
+        ],
+        algorithms: vec![
+            "ristretto255",
+            "pedersen-vss",
+            "blake3-512",
+            "schnorr-pedersen-fiat-shamir",
+        ],
+        description:
+            "Proof that all slice commitments of a dealing lie on the committed polynomial",
+    }
+}
+
+fn multi_share_data() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "MultiShareData",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Evaluation point shared by all secrets"),
+            field(
+                "shares",
+                array(FieldKind::HexScalar),
+                "One value per secret; secret",
+            ),
+            field(
+                "commitments",
+                array(FieldKind::HexRistrettoPoint),
+                "One commitment per secret",
+            ),
+            field(
+                "randoms",
+                array(FieldKind::HexScalar),
+                "One blinding random per secret; secret",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Proof over Σ ρ_k·C_k with ρ derived from index and commitments",
+            ),
+        ],
+        algorithms: vec![
+            "ristretto255",
+            "shamir",
+            "pedersen-commitment",
+            "blake3-derive-key",
+        ],
+        description: "One slice of several secrets dealt together",
+    }
+}
+
+fn vss_commitments() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "VssCommitments",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![field(
+            "coefficients",
+            array(FieldKind::HexRistrettoPoint),
+            "C_j = a_j·G + b_j·H for j = 0..threshold, lowest degree first",
+        )],
+        algorithms: vec!["ristretto255", "pedersen-vss"],
+        description: "Dealer's commitments to the sharing and blinding polynomials",
+    }
+}
+
+fn vss_commitments_binary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "VssCommitmentsBinary",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field("version", FieldKind::Unsigned { bits: 8 }, "Encoding version, 1"),
+            field(
+                "count",
+                FieldKind::Unsigned { bits: 32 },
+                "Number of coefficients, little-endian",
+            ),
+            field(
+                "coefficients",
+                array(FieldKind::ByteArray { length: Some(32) }),
+                "Compressed points, lowest degree first; no separators",
+            ),
+        ],
+        algorithms: vec!["ristretto255"],
+        description: "Canonical encoding of VssCommitments (to_canonical_bytes), suitable for hashing and signing",
+    }
+}
+
+fn commitment_diff() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CommitmentDiff",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("old_threshold", USIZE, "Coefficients in the old vector"),
+            field("new_threshold", USIZE, "Coefficients in the new vector"),
+            field(
+                "changed_coefficients",
+                array(USIZE),
+                "Positions j whose C_j differs; 0 means the secret commitment changed",
+            ),
+        ],
+        algorithms: vec!["pedersen-vss"],
+        description: "Audit report comparing commitment vectors across a refresh or reshare",
+    }
+}
+
+fn refresh_links() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RefreshLinks",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "update",
+                array(FieldKind::HexRistrettoPoint),
+                "D_j = δ_j·G + ε_j·H for j = 1..threshold; D_0 is the identity and omitted",
+            ),
+            field(
+                "links",
+                array(FieldKind::Artifact { name: "ShareLink" }),
+                "One link per refreshed slice",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "pedersen", "chaum-pedersen-dleq", "blake3"],
+        description:
+            "Published proof that a refresh without VSS commitments kept every slice on the secret",
+    }
+}
+
+fn share_link() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareLink",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index"),
+            field(
+                "old_commitment",
+                FieldKind::HexRistrettoPoint,
+                "C = share·G + random·H before the refresh",
+            ),
+            field(
+                "new_commitment",
+                FieldKind::HexRistrettoPoint,
+                "C' after the refresh",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "DleqProof" },
+                "Knowledge of w with C' − C − Σ index^j·D_j = w·H; binding = BLAKE3 of the update",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "chaum-pedersen-dleq"],
+        description: "Link of one refreshed slice commitment to its previous commitment",
+    }
+}
+
+fn refresh_record() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RefreshRecord",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "old_root",
+                FieldKind::HexBytes { length: Some(32) },
+                "VssCommitments::root of the dealing before the refresh",
+            ),
+            field(
+                "update",
+                array(FieldKind::HexRistrettoPoint),
+                "D_j = δ_j·G + ε_j·H for j = 1..threshold; D_0 is the identity and omitted",
+            ),
+            field(
+                "commitments",
+                FieldKind::Artifact {
+                    name: "VssCommitments",
+                },
+                "Commitments after the refresh, C'_j = C_j + D_j",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "pedersen-vss", "blake3"],
+        description: "Published proof that a proactive refresh kept the committed secret",
+    }
+}
+
+fn committee() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Committee",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "members",
+                array(USIZE),
+                "Slice indices of the members, ascending",
+            ),
+            field("threshold", USIZE, "Slices needed to recover"),
+        ],
+        algorithms: vec![],
+        description: "Members and threshold of a committee, as planned by reconfigure::plan",
+    }
+}
+
+fn plan_step() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PlanStep",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "operation",
+                FieldKind::Enum {
+                    variants: vec!["Enroll", "Refresh", "Reshare"],
+                },
+                "Sharing operation to run",
+            ),
+            field(
+                "participants",
+                array(USIZE),
+                "Current members whose slices the step consumes",
+            ),
+            field("recipients", array(USIZE), "Members receiving a new slice"),
+            field(
+                "committee",
+                FieldKind::Artifact { name: "Committee" },
+                "Committee after the step",
+            ),
+            field(
+                "estimated_cost",
+                USIZE,
+                "Estimated cost in scalar multiplications",
+            ),
+        ],
+        algorithms: vec!["pedersen-vss"],
+        description: "One operation of a ReconfigurationPlan",
+    }
+}
+
+fn reconfiguration_plan() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ReconfigurationPlan",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "from",
+                FieldKind::Artifact { name: "Committee" },
+                "Committee before the plan",
+            ),
+            field(
+                "to",
+                FieldKind::Artifact { name: "Committee" },
+                "Committee after the plan",
+            ),
+            field(
+                "steps",
+                array(FieldKind::Artifact { name: "PlanStep" }),
+                "Operations in execution order",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Committee reconfiguration plan for review before execution",
+    }
+}
+
+fn nonce_commitment() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "NonceCommitment",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the signer"),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "R_i = k_i·G for the signer's single-use nonce k_i",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "schnorr"],
+        description: "Round-one message of threshold Schnorr signing",
+    }
+}
+
+fn partial_signature() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PartialSignature",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the signer"),
+            field(
+                "response",
+                FieldKind::HexScalar,
+                "z_i = k_i + c·λ_i·s_i over the signing set",
+            ),
+        ],
+        algorithms: vec!["ristretto255", "schnorr", "blake3"],
+        description: "Round-two message of threshold Schnorr signing",
+    }
+}
+
+fn schnorr_signature() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SchnorrSignature",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "Group nonce commitment R",
+            ),
+            field("response", FieldKind::HexScalar, "Response z"),
+        ],
+        algorithms: vec!["ristretto255", "schnorr", "blake3"],
+        description: "Aggregated threshold signature; verifies as z·G = R + H(R, Y, m)·Y",
+    }
+}
+
+fn schnorr_signature_binary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SchnorrSignatureBinary",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "commitment",
+                FieldKind::ByteArray { length: Some(32) },
+                "Compressed R",
+            ),
+            field(
+                "response",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian z",
+            ),
+        ],
+        algorithms: vec!["ristretto255"],
+        description: "Canonical 64-byte encoding of SchnorrSignature (to_bytes)",
+    }
+}
+
+fn frost_signing_commitments() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SigningCommitments",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "hiding",
+                FieldKind::HexRistrettoPoint,
+                "Hiding nonce commitment D_i",
+            ),
+            field(
+                "binding",
+                FieldKind::HexRistrettoPoint,
+                "Binding nonce commitment E_i",
+            ),
+        ],
+        algorithms: vec!["frost", "ristretto255"],
+        description: "FROST round-one message",
+    }
+}
+
+fn frost_signing_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SigningPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "message",
+                FieldKind::HexBytes { length: None },
+                "Message to be signed",
+            ),
+            field(
+                "commitments",
+                array(FieldKind::Artifact {
+                    name: "SigningCommitments",
+                }),
+                "Commitments of the signing set, strictly increasing by index",
+            ),
+        ],
+        algorithms: vec!["frost", "blake3"],
+        description: "FROST coordinator message opening round two",
+    }
+}
+
+fn frost_signature_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SignatureShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "response",
+                FieldKind::HexScalar,
+                "z_i = d_i + ρ_i·e_i + λ_i·s_i·c",
+            ),
+        ],
+        algorithms: vec!["frost", "ristretto255", "blake3"],
+        description: "FROST round-two message; aggregates into a SchnorrSignature",
+    }
+}
+
+fn frost_public_key_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PublicKeyPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "group_public_key",
+                FieldKind::HexRistrettoPoint,
+                "Group public key Y = s·G",
+            ),
+            field(
+                "verification_shares",
+                array(FieldKind::Artifact {
+                    name: "VerificationShare",
+                }),
+                "One entry per participant",
+            ),
+        ],
+        algorithms: vec!["frost", "ristretto255"],
+        description: "Public keys a FROST coordinator checks signature shares against",
+    }
+}
+
+fn frost_verification_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "VerificationShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field("point", FieldKind::HexRistrettoPoint, "Y_i = s_i·G"),
+        ],
+        algorithms: vec!["frost", "ristretto255"],
+        description: "Public verification share of one FROST participant",
+    }
+}
+
+fn ed25519_signing_commitments() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519SigningCommitments",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "hiding",
+                EDWARDS_POINT,
+                "Hiding nonce commitment D_i = d_i·B",
+            ),
+            field(
+                "binding",
+                EDWARDS_POINT,
+                "Binding nonce commitment E_i = e_i·B",
+            ),
+        ],
+        algorithms: vec!["frost", "ed25519"],
+        description: "Round-one message of threshold Ed25519 signing",
+    }
+}
+
+fn ed25519_signing_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519SigningPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "message",
+                FieldKind::HexBytes { length: None },
+                "Message to be signed",
+            ),
+            field(
+                "commitments",
+                array(FieldKind::Artifact {
+                    name: "Ed25519SigningCommitments",
+                }),
+                "Commitments of the signing set, strictly increasing by index",
+            ),
+        ],
+        algorithms: vec!["frost", "ed25519", "sha512"],
+        description: "Coordinator message opening round two of threshold Ed25519 signing",
+    }
+}
+
+fn ed25519_signature_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519SignatureShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "response",
+                FieldKind::HexScalar,
+                "z_i = d_i + ρ_i·e_i + λ_i·s_i·c",
+            ),
+        ],
+        algorithms: vec!["frost", "ed25519", "sha512"],
+        description: "Round-two message of threshold Ed25519 signing; aggregates into an \
+                      Ed25519Signature",
+    }
+}
+
+fn ed25519_public_key_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519PublicKeyPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "group_public_key",
+                EDWARDS_POINT,
+                "Ed25519 public key A = s·B",
+            ),
+            field(
+                "verification_shares",
+                array(FieldKind::Artifact {
+                    name: "Ed25519VerificationShare",
+                }),
+                "One entry per participant",
+            ),
+        ],
+        algorithms: vec!["frost", "ed25519"],
+        description: "Public keys a threshold Ed25519 coordinator checks signature shares against",
+    }
+}
+
+fn ed25519_verification_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519VerificationShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field("point", EDWARDS_POINT, "A_i = s_i·B"),
+        ],
+        algorithms: vec!["frost", "ed25519"],
+        description: "Public verification share of one threshold Ed25519 participant",
+    }
+}
+
+fn ed25519_signature() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Ed25519Signature",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "r",
+                FieldKind::ByteArray { length: Some(32) },
+                "Compressed Edwards R",
+            ),
+            field(
+                "s",
+                FieldKind::ByteArray { length: Some(32) },
+                "Canonical little-endian S",
+            ),
+        ],
+        algorithms: vec!["ed25519", "sha512"],
+        description: "64-byte RFC 8032 signature (to_bytes), from one key or a signing set",
+    }
+}
+
+fn bip340_signing_commitments() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340SigningCommitments",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "hiding",
+                SECP256K1_POINT,
+                "SEC1 compressed hiding nonce commitment D_i",
+            ),
+            field(
+                "binding",
+                SECP256K1_POINT,
+                "SEC1 compressed binding nonce commitment E_i",
+            ),
+        ],
+        algorithms: vec!["frost", "secp256k1"],
+        description: "Round-one message of threshold BIP-340 signing (secp256k1 feature)",
+    }
+}
+
+fn bip340_signing_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340SigningPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "message",
+                FieldKind::HexBytes { length: None },
+                "Message to be signed, e.g. a BIP-341 signature hash",
+            ),
+            field(
+                "commitments",
+                array(FieldKind::Artifact {
+                    name: "Bip340SigningCommitments",
+                }),
+                "Commitments of the signing set, strictly increasing by index",
+            ),
+        ],
+        algorithms: vec!["frost", "secp256k1", "sha256"],
+        description: "Coordinator message opening round two of threshold BIP-340 signing",
+    }
+}
+
+fn bip340_signature_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340SignatureShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field(
+                "response",
+                FieldKind::HexBytes { length: Some(32) },
+                "Big-endian z_i = ±(d_i + ρ_i·e_i) + λ_i·c·(±s_i)",
+            ),
+        ],
+        algorithms: vec!["frost", "secp256k1", "bip340"],
+        description: "Round-two message of threshold BIP-340 signing; aggregates into a \
+                      Bip340Signature",
+    }
+}
+
+fn bip340_public_key_package() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340PublicKeyPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "group_public_key",
+                SECP256K1_POINT,
+                "SEC1 compressed group public key Y = s·G",
+            ),
+            field(
+                "verification_shares",
+                array(FieldKind::Artifact {
+                    name: "Bip340VerificationShare",
+                }),
+                "One entry per participant",
+            ),
+        ],
+        algorithms: vec!["frost", "secp256k1"],
+        description: "Public keys a threshold BIP-340 coordinator checks signature shares against",
+    }
+}
+
+fn bip340_verification_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340VerificationShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index of the participant"),
+            field("point", SECP256K1_POINT, "SEC1 compressed Y_i = s_i·G"),
+        ],
+        algorithms: vec!["frost", "secp256k1"],
+        description: "Public verification share of one threshold BIP-340 participant",
+    }
+}
+
+fn bip340_signature() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Bip340Signature",
+        version: 1,
+        encoding: "binary",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "r",
+                FieldKind::ByteArray { length: Some(32) },
+                "x coordinate of the even-y group commitment R",
+            ),
+            field(
+                "s",
+                FieldKind::ByteArray { length: Some(32) },
+                "Big-endian z below the group order",
+            ),
+        ],
+        algorithms: vec!["secp256k1", "bip340", "sha256"],
+        description: "64-byte BIP-340 signature (to_bytes), valid for the x-only or Taproot \
+                      output key signed for",
+    }
+}
+
+fn frost_header() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "FrostHeader",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "frost-core format version, 0",
+            ),
+            field(
+                "ciphersuite",
+                FieldKind::String,
+                "\"FROST-RISTRETTO255-SHA512-v1\"",
+            ),
+        ],
+        algorithms: vec!["frost-ristretto255-sha512"],
+        description: "Header of frost-core serialized structures",
+    }
+}
+
+fn frost_key_package_rfc9591() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "FrostKeyPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "header",
+                FieldKind::Artifact {
+                    name: "FrostHeader",
+                },
+                "Format version and ciphersuite",
+            ),
+            field(
+                "identifier",
+                FieldKind::HexScalar,
+                "Scalar::from(index); only values below 2^32 are accepted",
+            ),
+            field("signing_share", FieldKind::HexScalar, "Slice value s_i"),
+            field(
+                "verifying_share",
+                FieldKind::HexRistrettoPoint,
+                "Y_i = s_i·G; must match signing_share",
+            ),
+            field(
+                "verifying_key",
+                FieldKind::HexRistrettoPoint,
+                "Group public key Y",
+            ),
+            field("min_signers", FieldKind::Unsigned { bits: 16 }, "Threshold"),
+        ],
+        algorithms: vec!["frost-ristretto255-sha512", "ristretto255"],
+        description: "frost-core KeyPackage of one slice (RFC 9591 interop, interop::frost)",
+    }
+}
+
+fn frost_public_key_package_rfc9591() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "FrostPublicKeyPackage",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "header",
+                FieldKind::Artifact {
+                    name: "FrostHeader",
+                },
+                "Format version and ciphersuite",
+            ),
+            field(
+                "verifying_shares",
+                FieldKind::Map {
+                    key: Box::new(FieldKind::HexScalar),
+                    value: Box::new(FieldKind::HexRistrettoPoint),
+                },
+                "Identifier to Y_i",
+            ),
+            field(
+                "verifying_key",
+                FieldKind::HexRistrettoPoint,
+                "Group public key Y",
+            ),
+            field(
+                "min_signers",
+                optional(FieldKind::Unsigned { bits: 16 }),
+                "Threshold; omitted when unknown, as before frost-core 2.1",
+            ),
+        ],
+        algorithms: vec!["frost-ristretto255-sha512", "ristretto255"],
+        description: "frost-core PublicKeyPackage (RFC 9591 interop, interop::frost)",
+    }
+}
+
+fn dkg_round1_broadcast() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Round1Broadcast",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("sender", USIZE, "Index of the dealing participant"),
+            field(
+                "commitments",
+                FieldKind::Artifact {
+                    name: "VssCommitments",
+                },
+                "Pedersen commitments C_{i,k} = a_{i,k}·G + b_{i,k}·H",
+            ),
+            field(
+                "public_key_share",
+                FieldKind::HexRistrettoPoint,
+                "A_i = a_{i,0}·G",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact {
+                    name: "ConstantTermProof",
+                },
+                "Proof that A_i matches the committed constant term",
+            ),
+        ],
+        algorithms: vec!["pedersen-dkg", "ristretto255", "blake3"],
+        description: "DKG round-one broadcast of one dealer",
+    }
+}
+
+fn dkg_constant_term_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ConstantTermProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("nonce_key", FieldKind::HexRistrettoPoint, "k_a·G"),
+            field(
+                "nonce_commitment",
+                FieldKind::HexRistrettoPoint,
+                "k_a·G + k_b·H",
+            ),
+            field("response_secret", FieldKind::HexScalar, "k_a + c·a"),
+            field("response_blinding", FieldKind::HexScalar, "k_b + c·b"),
+        ],
+        algorithms: vec!["pedersen-dkg", "ristretto255", "blake3"],
+        description: "Proof of knowledge of (a, b) with A = a·G and C_0 = a·G + b·H",
+    }
+}
+
+fn dkg_round2_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Round2Share",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("sender", USIZE, "Index of the dealing participant"),
+            field("recipient", USIZE, "Index of the receiving participant"),
+            field("share", FieldKind::HexScalar, "f_i(j)"),
+            field("random", FieldKind::HexScalar, "g_i(j)"),
+        ],
+        algorithms: vec!["pedersen-dkg"],
+        description: "DKG round-two slice; secret, sent over a confidential channel",
+    }
+}
+
+fn dkg_complaint() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Complaint",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "accused",
+                USIZE,
+                "Dealer whose slice was invalid or missing",
+            ),
+            field("complainant", USIZE, "Participant raising the complaint"),
+        ],
+        algorithms: vec!["pedersen-dkg"],
+        description: "DKG complaint broadcast",
+    }
+}
+
+fn dkg_complaint_response() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ComplaintResponse",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("accused", USIZE, "Dealer answering the complaint"),
+            field(
+                "complainant",
+                USIZE,
+                "Participant that raised the complaint",
+            ),
+            field("share", FieldKind::HexScalar, "Revealed f_i(j)"),
+            field("random", FieldKind::HexScalar, "Revealed g_i(j)"),
+        ],
+        algorithms: vec!["pedersen-dkg"],
+        description: "DKG complaint answer publicly revealing the disputed slice",
+    }
+}
+
+fn dkg_output() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "DkgOutput",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "share",
+                FieldKind::Artifact { name: "ShareData" },
+                "The participant's slice of the joint secret",
+            ),
+            field(
+                "commitments",
+                FieldKind::Artifact {
+                    name: "VssCommitments",
+                },
+                "Sum of the qualified dealers' commitments",
+            ),
+            field(
+                "group_public_key",
+                FieldKind::HexRistrettoPoint,
+                "Sum of the qualified dealers' A_i",
+            ),
+            field("qualified", array(USIZE), "Qualified dealers, increasing"),
+        ],
+        algorithms: vec!["pedersen-dkg", "ristretto255"],
+        description: "Result of a completed DKG for one participant; contains secret material",
+    }
+}
+
+fn repair_request() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RepairRequest",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("lost_index", USIZE, "Index of the slice to recover"),
+            field("helpers", array(USIZE), "Indices of the helping holders"),
+        ],
+        algorithms: vec!["share-recovery"],
+        description: "Share recovery request agreed on by the helpers and the lost participant",
+    }
+}
+
+fn repair_part() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RepairPart",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("lost_index", USIZE, "Index of the slice to recover"),
+            field("sender", USIZE, "Index of the sending helper"),
+            field("recipient", USIZE, "Index of the receiving helper"),
+            field("share", FieldKind::HexScalar, "Additive part of λ_i·f(i)"),
+            field("random", FieldKind::HexScalar, "Additive part of λ_i·g(i)"),
+        ],
+        algorithms: vec!["share-recovery"],
+        description: "Share recovery round-one part; secret, sent over a confidential channel",
+    }
+}
+
+fn repair_sum() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RepairSum",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("lost_index", USIZE, "Index of the slice to recover"),
+            field("sender", USIZE, "Index of the sending helper"),
+            field(
+                "share",
+                FieldKind::HexScalar,
+                "Sum of the value parts received",
+            ),
+            field(
+                "random",
+                FieldKind::HexScalar,
+                "Sum of the random parts received",
+            ),
+        ],
+        algorithms: vec!["share-recovery"],
+        description: "Share recovery round-two sum; secret, sent over a confidential channel",
+    }
+}
+
+fn transport_message() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Message",
+        version: 3,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec![
+                "Share",
+                "DkgRound1",
+                "DkgRound2",
+                "DkgComplaint",
+                "DkgComplaintResponse",
+                "RecoveryRequest",
+                "RecoveryResponse",
+                "ServiceRequest",
+                "ServiceResponse",
+            ],
+        },
+        fields: vec![
+            field("operation_id", FieldKind::String, "RecoveryResponse"),
+            field(
+                "share",
+                FieldKind::Artifact { name: "ShareData" },
+                "RecoveryResponse",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description:
+            "Transport frame payload; the Dkg variants wrap a SignedMessage of Round1Broadcast, \
+            Round2Share, Complaint or ComplaintResponse signed by its author for the DKG session; \
+            the others wrap ShareData, RecoveryRequest, ServiceRequest and ServiceResponse \
+            directly; the Service variants exist with the service feature",
+    }
+}
+
+fn recovery_request() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RecoveryRequest",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "Key and operation the slice is requested for",
+            ),
+            field("requester", USIZE, "Participant collecting the slices"),
+        ],
+        algorithms: vec![],
+        description: "Transport request for the recipient's slice",
+    }
+}
+
+fn service_request() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ServiceRequest",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec!["Deal", "Verify", "Recover", "Refresh"],
+        },
+        fields: vec![
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "All variants: context of the system operation",
+            ),
+            field("secret", FieldKind::HexScalar, "Deal: secret to share"),
+            field("threshold", USIZE, "Deal, Refresh"),
+            field("num_shares", USIZE, "Deal"),
+            field(
+                "shares",
+                array(FieldKind::Artifact { name: "ShareData" }),
+                "Verify, Recover, Refresh: slices presented",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Request to the share-coordination service, sent over a transport channel",
+    }
+}
+
+fn participant_identity() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ParticipantIdentity",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Participant index"),
+            field("public_key", EDWARDS_POINT, "Ed25519 identity public key"),
+        ],
+        algorithms: vec!["ed25519"],
+        description: "Long-term identity of a participant; a Roster is a JSON array of them",
+    }
+}
+
+fn signed_message() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SignedMessage",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("sender", USIZE, "Index of the signing participant"),
+            field(
+                "session",
+                FieldKind::String,
+                "Session (ceremony operation id, or transport session id) the message is signed for",
+            ),
+            field(
+                "payload",
+                FieldKind::Artifact { name: "Message" },
+                "Signed message: a transport Message, a DKG or signing round message, or a \
+                 RefreshRecord",
+            ),
+            field(
+                "signature",
+                FieldKind::HexBytes { length: Some(64) },
+                "Ed25519 signature of context ‖ domain of the payload type ‖ session ‖ sender \
+                 (u64 LE) ‖ canonical JSON of the payload",
+            ),
+        ],
+        algorithms: vec!["ed25519", "sha512"],
+        description: "Message signed with its author's identity key (identity::Signed)",
+    }
+}
+
+fn service_response() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ServiceResponse",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec!["Shares", "Verified", "Secret", "Error"],
+        },
+        fields: vec![
+            field("code", FieldKind::String, "Error: stable CryptoError code"),
+            field("message", FieldKind::String, "Error: human-readable text"),
+        ],
+        algorithms: vec![],
+        description: "Answer of the share-coordination service; Shares wraps ShareData slices, \
+                      Secret a hex scalar and Verified carries nothing",
+    }
+}
+
+fn share_feedback() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareFeedback",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "custodian_id",
+                FieldKind::String,
+                "Custodian who submitted the slice",
+            ),
+            field(
+                "index",
+                USIZE,
+                "Slice index; 0 if the submission could not be parsed",
+            ),
+            field(
+                "status",
+                FieldKind::Artifact {
+                    name: "CollectionStatus",
+                },
+                "Outcome of the submission",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Answer to one slice submitted during a recovery ceremony",
+    }
+}
+
+fn collection_status() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CollectionStatus",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec!["Accepted", "Rejected", "Superseded"],
+        },
+        fields: vec![
+            field(
+                "code",
+                FieldKind::String,
+                "Rejected: VerifyError or CryptoError code",
+            ),
+            field(
+                "class",
+                FieldKind::Enum {
+                    variants: vec!["Corruption", "Malice", "VersionSkew"],
+                },
+                "Rejected: likely cause",
+            ),
+            field("reason", FieldKind::String, "Rejected"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Superseded: epoch the slice belongs to",
+            ),
+            field(
+                "current_epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Superseded: current epoch of the key",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Outcome of a submitted slice; Accepted is the bare string \"Accepted\"",
+    }
+}
+
+fn algorithm_suite() -> ArtifactDescription {
+    use crate::algorithms::{AlgorithmId, AlgorithmKind};
+    let slot = |kind| FieldKind::Enum {
+        variants: AlgorithmId::names(kind),
+    };
+    ArtifactDescription {
+        name: "AlgorithmSuite",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("curve", slot(AlgorithmKind::Curve), "Prime-order group"),
+            field("hash", slot(AlgorithmKind::Hash), "Hash function"),
+            field(
+                "kem",
+                optional(slot(AlgorithmKind::Kem)),
+                "Key encapsulation; null when unused",
+            ),
+            field(
+                "aead",
+                optional(slot(AlgorithmKind::Aead)),
+                "Authenticated encryption; null when unused",
+            ),
+            field(
+                "kdf",
+                optional(slot(AlgorithmKind::Kdf)),
+                "Key derivation; null when unused",
+            ),
+            field(
+                "proof",
+                optional(slot(AlgorithmKind::ProofSystem)),
+                "Proof system; null when unused",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Algorithm identifiers of an envelope or transcript, checked on parse",
+    }
+}
+
+fn ceremony_transcript() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CeremonyTranscript",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Transcript format version",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field(
+                "kind",
+                FieldKind::Enum {
+                    variants: vec!["Dealing", "Refresh", "Recovery"],
+                },
+                "Ceremony recorded",
+            ),
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "Key, epoch and operation of the ceremony",
+            ),
+            field("threshold", USIZE, "Threshold of the sharing"),
+            field("indices", array(USIZE), "Slice indices involved, in order"),
+            field(
+                "commitments_digest",
+                FieldKind::HexBytes { length: Some(32) },
+                "transcript::commitments_digest of the slices involved",
+            ),
+            field("completed_at", FieldKind::UnixSeconds, "Completion time"),
+            field(
+                "timestamp",
+                optional(FieldKind::Artifact {
+                    name: "TimestampToken",
+                }),
+                "Authority token over the transcript without this field; omitted when unstamped",
+            ),
+        ],
+        algorithms: vec!["blake3", "ristretto255"],
+        description: "Record of a dealing, refresh or recovery ceremony",
+    }
+}
+
+fn timestamp_token() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "TimestampToken",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "authority",
+                FieldKind::String,
+                "Name of the timestamp authority",
+            ),
+            field(
+                "token",
+                FieldKind::HexBytes { length: None },
+                "Opaque token; DER TimeStampToken for RFC 3161 authorities",
+            ),
+            field(
+                "stamped_at",
+                FieldKind::UnixSeconds,
+                "Time asserted by the authority",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Timestamp-authority token stored in a CeremonyTranscript",
+    }
+}
+
+fn protocol_kind() -> FieldKind {
+    FieldKind::Enum {
+        variants: vec!["Dkg", "Refresh", "Signing"],
+    }
+}
+
+fn ceremony_state() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CeremonyState",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Record version, 1",
+            ),
+            field("kind", protocol_kind(), "Protocol run"),
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "Key, epoch and operation; the operation id names the ceremony in its store",
+            ),
+            field("threshold", USIZE, "Participants needed to continue"),
+            field(
+                "participants",
+                array(USIZE),
+                "Participants still in the ceremony, increasing",
+            ),
+            field(
+                "rounds",
+                array(FieldKind::Artifact {
+                    name: "CeremonyRound",
+                }),
+                "Rounds opened so far, in order",
+            ),
+            field(
+                "phase",
+                FieldKind::Enum {
+                    variants: vec!["Running", "Completed", "Failed"],
+                },
+                "Failed once fewer than the threshold answered a round in time",
+            ),
+            field("started_at", FieldKind::UnixSeconds, "Start time"),
+        ],
+        algorithms: vec![],
+        description: "Resumable state of a multi-round protocol run (ceremony::Ceremony)",
+    }
+}
+
+fn ceremony_round() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CeremonyRound",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("name", FieldKind::String, "Round name"),
+            field(
+                "expected",
+                array(USIZE),
+                "Participants expected to answer, increasing",
+            ),
+            field(
+                "responses",
+                array(FieldKind::Artifact {
+                    name: "CeremonyResponse",
+                }),
+                "Messages recorded, in arrival order",
+            ),
+            field(
+                "timeout_ms",
+                FieldKind::Unsigned { bits: 64 },
+                "Time budget of the round",
+            ),
+            field(
+                "expires_at",
+                FieldKind::UnixSeconds,
+                "Deadline, rounded up to whole seconds",
+            ),
+            field(
+                "missing",
+                array(USIZE),
+                "Participants dropped for not answering before the round was closed",
+            ),
+            field("closed", FieldKind::Boolean, "Whether the round is closed"),
+        ],
+        algorithms: vec![],
+        description: "One round of a ceremony",
+    }
+}
+
+fn ceremony_response() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CeremonyResponse",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("participant", USIZE, "Participant that answered"),
+            field(
+                "digest",
+                FieldKind::HexBytes { length: Some(32) },
+                "Keyed BLAKE3 digest of the canonical JSON of the message",
+            ),
+            field("received_at", FieldKind::UnixSeconds, "Arrival time"),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Message recorded from one participant; the message itself is not kept",
+    }
+}
+
+fn protocol_transcript() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ProtocolTranscript",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Format version, 1",
+            ),
+            field("kind", protocol_kind(), "Protocol run"),
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "Key, epoch and operation of the run",
+            ),
+            field("threshold", USIZE, "Participants needed to continue"),
+            field(
+                "participants",
+                array(USIZE),
+                "Participants that answered every round, increasing",
+            ),
+            field(
+                "rounds",
+                array(FieldKind::Artifact {
+                    name: "CeremonyRound",
+                }),
+                "Every round, in order",
+            ),
+            field("started_at", FieldKind::UnixSeconds, "Start time"),
+            field("completed_at", FieldKind::UnixSeconds, "Completion time"),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Final record of a completed ceremony",
+    }
+}
+
+fn sealed_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SealedShare",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Envelope version, 1",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field("index", USIZE, "Slice index; authenticated, not encrypted"),
+            field(
+                "nonce",
+                FieldKind::HexBytes { length: Some(24) },
+                "Fresh per seal",
+            ),
+            field(
+                "ciphertext",
+                FieldKind::HexBytes { length: None },
+                "ShareData JSON XORed with the keyed BLAKE3 XOF of the nonce",
+            ),
+            field(
+                "tag",
+                FieldKind::HexBytes { length: Some(32) },
+                "Keyed BLAKE3 over version, index, nonce and ciphertext",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Slice sealed at rest under a custodian sealing key (encrypt-then-MAC)",
+    }
+}
+
+fn wrapped_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "WrappedShare",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Envelope version",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field("index", USIZE, "Slice index"),
+            field(
+                "recipient",
+                FieldKind::HexBytes { length: Some(32) },
+                "Recipient X25519 public key",
+            ),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "Public slice commitment",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Public proof of knowledge of the commitment opening",
+            ),
+            field(
+                "ephemeral",
+                FieldKind::HexBytes { length: Some(32) },
+                "Dealer's ephemeral X25519 public key",
+            ),
+            field(
+                "ciphertext",
+                FieldKind::HexBytes { length: Some(64) },
+                "Encrypted share || random",
+            ),
+            field(
+                "tag",
+                FieldKind::HexBytes { length: Some(32) },
+                "Keyed BLAKE3 tag over version, index, commitment and ciphertext",
+            ),
+        ],
+        algorithms: vec!["x25519", "blake3", "ristretto255"],
+        description: "Slice encrypted to a participant's X25519 key (ECIES, BLAKE3 KDF)",
+    }
+}
+
+fn pvss_dealing() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PvssDealing",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Dealing format version",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field("threshold", USIZE, "Slices needed to recover secret·G"),
+            field(
+                "commitments",
+                array(FieldKind::HexRistrettoPoint),
+                "C_j = a_j·H, one per polynomial coefficient",
+            ),
+            field(
+                "shares",
+                array(FieldKind::Artifact {
+                    name: "EncryptedShare",
+                }),
+                "Encrypted slices with indices 1..=n, in order",
+            ),
+        ],
+        algorithms: vec!["schoenmakers-pvss", "ristretto255", "chaum-pedersen-dleq"],
+        description: "Publicly verifiable dealing of secret·G to the holders of PVSS keys",
+    }
+}
+
+fn pvss_encrypted_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "EncryptedShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index"),
+            field(
+                "recipient",
+                FieldKind::HexRistrettoPoint,
+                "Holder's PVSS public key y = x·G",
+            ),
+            field("ciphertext", FieldKind::HexRistrettoPoint, "Y = f(index)·y"),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "DleqProof" },
+                "log_H X = log_y Y, with X = Σ index^j·C_j",
+            ),
+        ],
+        algorithms: vec!["schoenmakers-pvss", "ristretto255", "chaum-pedersen-dleq"],
+        description: "One PVSS slice encrypted to its holder",
+    }
+}
+
+fn pvss_decrypted_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "DecryptedShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index"),
+            field("share", FieldKind::HexRistrettoPoint, "S = f(index)·G"),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "DleqProof" },
+                "log_G y = log_S Y",
+            ),
+        ],
+        algorithms: vec!["schoenmakers-pvss", "ristretto255", "chaum-pedersen-dleq"],
+        description: "One PVSS slice decrypted by its holder, with a proof of correct decryption",
+    }
+}
+
+fn pvss_dleq_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "DleqProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "challenge",
+                FieldKind::HexScalar,
+                "c = H(binding ‖ index_le ‖ g1 ‖ h1 ‖ g2 ‖ h2 ‖ a1 ‖ a2)",
+            ),
+            field("response", FieldKind::HexScalar, "z = w − c·x"),
+        ],
+        algorithms: vec!["chaum-pedersen-dleq", "ristretto255", "blake3-512"],
+        description:
+            "Proof of log_g1 h1 = log_g2 h2, bound to one slice of one dealing or ciphertext",
+    }
+}
+
+fn threshold_ciphertext() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ThresholdCiphertext",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Ciphertext format version",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field("ephemeral", FieldKind::HexRistrettoPoint, "R = r·G"),
+            field(
+                "ciphertext",
+                FieldKind::HexBytes { length: None },
+                "Message XOR keyed-BLAKE3 keystream of the encryption key",
+            ),
+            field(
+                "tag",
+                FieldKind::HexBytes { length: Some(32) },
+                "Keyed BLAKE3 of version ‖ R ‖ ciphertext under the MAC key",
+            ),
+        ],
+        algorithms: vec!["threshold-elgamal", "ristretto255", "blake3"],
+        description: "Message encrypted to a group public key Y; keys from BLAKE3(r·Y ‖ R ‖ Y)",
+    }
+}
+
+fn partial_decryption() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PartialDecryption",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index"),
+            field("share", FieldKind::HexRistrettoPoint, "D = share·R"),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "DleqProof" },
+                "log_G Y_index = log_R D, bound to the ciphertext",
+            ),
+        ],
+        algorithms: vec!["threshold-elgamal", "ristretto255", "chaum-pedersen-dleq"],
+        description: "One holder's share of the decryption of a ThresholdCiphertext",
+    }
+}
+
+fn beacon_commitment() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "BeaconCommitment",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("round", FieldKind::Unsigned { bits: 64 }, "Beacon round"),
+            field("index", USIZE, "Participant index, 1..=n"),
+            field(
+                "digest",
+                FieldKind::HexBytes { length: Some(32) },
+                "BLAKE3 derive_key(round_le ‖ index_le ‖ contribution)",
+            ),
+        ],
+        algorithms: vec!["commit-reveal", "blake3"],
+        description: "Commitment to a beacon contribution, published in the commit phase",
+    }
+}
+
+fn beacon_reveal() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "BeaconReveal",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("round", FieldKind::Unsigned { bits: 64 }, "Beacon round"),
+            field("index", USIZE, "Participant index, 1..=n"),
+            field(
+                "contribution",
+                FieldKind::HexScalar,
+                "Contribution x opening the BeaconCommitment",
+            ),
+        ],
+        algorithms: vec!["commit-reveal", "ristretto255"],
+        description: "Beacon contribution, published once every commitment is known",
+    }
+}
+
+fn beacon_transcript() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "BeaconTranscript",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Transcript format version",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the artifact was produced with; the version 1 suite when absent",
+            ),
+            field("round", FieldKind::Unsigned { bits: 64 }, "Beacon round"),
+            field(
+                "commitments",
+                array(FieldKind::Artifact {
+                    name: "BeaconCommitment",
+                }),
+                "Commitments of participants 1..=n, in order",
+            ),
+            field(
+                "reveals",
+                array(FieldKind::Artifact {
+                    name: "BeaconReveal",
+                }),
+                "Reveals of participants 1..=n, in order",
+            ),
+            field(
+                "output",
+                FieldKind::HexBytes { length: Some(32) },
+                "BLAKE3 derive_key(round_le ‖ n_le ‖ Σ contribution)",
+            ),
+        ],
+        algorithms: vec!["commit-reveal", "blake3", "ristretto255"],
+        description: "Publicly verifiable record of one randomness beacon round",
+    }
+}
+
+fn policy_compliance_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PolicyComplianceProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Proof format version, 1",
+            ),
+            field(
+                "algorithms",
+                FieldKind::Artifact {
+                    name: "AlgorithmSuite",
+                },
+                "Algorithms the proof was produced with",
+            ),
+            field("key_id", FieldKind::String, "Key recovered"),
+            field(
+                "recovery_operation_id",
+                FieldKind::String,
+                "Operation id of the recovery reported on",
+            ),
+            field(
+                "committee_operation_id",
+                FieldKind::String,
+                "Dealing or refresh that issued the committee's slices",
+            ),
+            field(
+                "threshold",
+                USIZE,
+                "Distinct committee slices the recovery must have used",
+            ),
+            field(
+                "committee",
+                array(FieldKind::Artifact {
+                    name: "CommitteeMember",
+                }),
+                "Live slices of the committee, in issuing order",
+            ),
+            field(
+                "contributions",
+                array(FieldKind::Artifact {
+                    name: "ContributionProof",
+                }),
+                "One contribution per slice used",
+            ),
+        ],
+        algorithms: vec!["linkable-ring-pedersen", "ristretto255", "blake3-512"],
+        description:
+            "Proof that a recovery used at least threshold distinct committee slices, not naming them",
+    }
+}
+
+fn committee_member() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CommitteeMember",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("index", USIZE, "Slice index"),
+            field(
+                "custodian_id",
+                optional(FieldKind::String),
+                "Custodian of the slice's last attestation",
+            ),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "C_i = s_i·G + r_i·H",
+            ),
+        ],
+        algorithms: vec!["pedersen-vss"],
+        description: "One slice of the committee of a PolicyComplianceProof",
+    }
+}
+
+fn contribution_proof() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ContributionProof",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "tag",
+                FieldKind::HexRistrettoPoint,
+                "T = s·J, J = from_uniform_bytes(BLAKE3 derive_key(key_id ‖ recovery_operation_id))",
+            ),
+            field(
+                "challenges",
+                array(FieldKind::HexScalar),
+                "c_j per committee member; Σ c_j = H(statement ‖ T ‖ (A_j ‖ B_j)…)",
+            ),
+            field(
+                "share_responses",
+                array(FieldKind::HexScalar),
+                "z_s,j, with A_j = z_s,j·G + z_r,j·H − c_j·C_j and B_j = z_s,j·J − c_j·T",
+            ),
+            field(
+                "random_responses",
+                array(FieldKind::HexScalar),
+                "z_r,j per committee member",
+            ),
+        ],
+        algorithms: vec!["linkable-ring-pedersen", "ristretto255", "blake3-512"],
+        description: "One-out-of-many proof that one committee slice contributed",
+    }
+}
+
+fn index_assignment() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "IndexAssignment",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Record format version",
+            ),
+            field(
+                "rule",
+                FieldKind::Enum {
+                    variants: vec!["Sequential", "Random", "Derived"],
+                },
+                "Assignment rule the indices follow",
+            ),
+            field(
+                "domain",
+                optional(FieldKind::String),
+                "Domain of the Derived rule; null for the other rules",
+            ),
+            field(
+                "entries",
+                array(FieldKind::Artifact {
+                    name: "CustodianIndex",
+                }),
+                "Custodians in dealing order",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Slice indices assigned to the custodians of a dealing, auditable by rule",
+    }
+}
+
+fn custodian_index() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CustodianIndex",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("custodian_id", FieldKind::String, "Custodian identifier"),
+            field(
+                "index",
+                USIZE,
+                "Slice index; Derived: BLAKE3 derive_key(len_le(domain) ‖ domain ‖ custodian_id) \
+                 as u64_le mod (2^32 − 1) + 1",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Slice index held by one custodian",
+    }
+}
+
+fn stored_share() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "StoredShare",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Record version, 1",
+            ),
+            field(
+                "context",
+                FieldKind::Artifact {
+                    name: "OperationContext",
+                },
+                "Dealing or refresh the slice came from",
+            ),
+            field(
+                "commitments",
+                FieldKind::Artifact {
+                    name: "VssCommitments",
+                },
+                "Commitments the slice was verified against",
+            ),
+            field(
+                "sealed",
+                FieldKind::Artifact {
+                    name: "SealedShare",
+                },
+                "The slice, sealed under the holder's sealing key",
+            ),
+        ],
+        algorithms: vec!["pedersen-vss", "blake3", "ristretto255"],
+        description:
+            "Slice held by a shareholder for one key (one file per key in a FileShareStore)",
+    }
+}
+
+fn share_attestation() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareAttestation",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Attestation version, 2",
+            ),
+            field("index", USIZE, "Index of the checked slice"),
+            field(
+                "commitment_root",
+                FieldKind::HexBytes { length: Some(32) },
+                "VssCommitments::root the slice was verified against",
+            ),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "Slice commitment Σ_j index^j · C_j",
+            ),
+            field(
+                "challenge",
+                FieldKind::HexBytes { length: None },
+                "Operator's challenge nonce, at least 16 bytes",
+            ),
+            field(
+                "proof",
+                FieldKind::Artifact { name: "Proof" },
+                "Fresh proof of knowledge of the commitment opening; its challenge is drawn from \
+                 the custodian-attestation transcript of the statement, the challenge nonce, \
+                 sealed_digest and checked_at",
+            ),
+            field(
+                "sealed_digest",
+                FieldKind::HexBytes { length: Some(32) },
+                "Digest of the re-sealed envelope",
+            ),
+            field("checked_at", FieldKind::UnixSeconds, "Time of the check"),
+        ],
+        algorithms: vec!["pedersen-vss", "blake3", "ristretto255"],
+        description: "Custodian health-check attestation returned to the operator",
+    }
+}
+
+fn security_event() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SecurityEvent",
+        version: 12,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec![
+                "KeyGenerated",
+                "KeyActivated",
+                "KeyRetired",
+                "KeyDestroyed",
+                "UnauthorizedAccess",
+                "PolicyViolation",
+                "SharesDealt",
+                "SharesRefreshed",
+                "SecretRecovered",
+                "SharesDealtUnproven",
+                "ShareProofsPublished",
+                "ShareRevoked",
+                "RevokedShareRejected",
+                "KeyMaterialDerived",
+                "SafeModeEntered",
+                "SafeModeExited",
+                "ShareVerified",
+                "ShareVerificationFailed",
+                "ThresholdAdjusted",
+                "KeyRotated",
+                "PublicStateImported",
+                "KeyExpired",
+                "ParticipantDisenrolled",
+                "ServiceRequestHandled",
+            ],
+        },
+        fields: vec![
+            field(
+                "key_id",
+                FieldKind::String,
+                "Key lifecycle and share lifecycle variants",
+            ),
+            field("attempt", FieldKind::String, "UnauthorizedAccess"),
+            field("policy", FieldKind::String, "PolicyViolation"),
+            field("violation", FieldKind::String, "PolicyViolation"),
+            field(
+                "threshold",
+                USIZE,
+                "SharesDealt, SharesRefreshed, SharesDealtUnproven",
+            ),
+            field(
+                "num_shares",
+                USIZE,
+                "SharesDealt, SharesRefreshed, SharesDealtUnproven, ShareProofsPublished, ThresholdAdjusted, \
+                ParticipantDisenrolled",
+            ),
+            field("shares_used", USIZE, "SecretRecovered"),
+            field(
+                "issued_by",
+                FieldKind::String,
+                "ShareRevoked, RevokedShareRejected: operation that issued the slice",
+            ),
+            field(
+                "index",
+                USIZE,
+                "ShareRevoked, RevokedShareRejected, ShareVerified, ShareVerificationFailed; \
+                ParticipantDisenrolled: removed slice",
+            ),
+            field(
+                "code",
+                FieldKind::String,
+                "ShareVerificationFailed: stable code of the verification error",
+            ),
+            field("old_threshold", USIZE, "ThresholdAdjusted"),
+            field("new_threshold", USIZE, "ThresholdAdjusted"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "KeyRotated, ParticipantDisenrolled: epoch of the new sharing; PublicStateImported: last \
+                epoch of the key",
+            ),
+            field(
+                "retired_shares",
+                USIZE,
+                "KeyRotated, ParticipantDisenrolled: slices of the previous sharing revoked",
+            ),
+            field(
+                "shares",
+                USIZE,
+                "PublicStateImported: registry entries imported",
+            ),
+            field(
+                "operation",
+                FieldKind::String,
+                "KeyExpired: operation refused; ServiceRequestHandled: operation requested",
+            ),
+            field(
+                "expired_at",
+                FieldKind::UnixSeconds,
+                "KeyExpired: expiry time of the key",
+            ),
+            field("reason", FieldKind::String, "ShareRevoked, SafeModeEntered"),
+            field(
+                "purpose",
+                FieldKind::String,
+                "KeyMaterialDerived: application context",
+            ),
+            field("length", USIZE, "KeyMaterialDerived: bytes derived"),
+            field(
+                "participant",
+                USIZE,
+                "ServiceRequestHandled: authenticated requester",
+            ),
+            field(
+                "outcome",
+                FieldKind::String,
+                "ServiceRequestHandled: ok, or the stable error code",
+            ),
+            field("timestamp", FieldKind::UnixSeconds, "All variants"),
+        ],
+        algorithms: vec![],
+        description: "Audit event; see SecurityEvent::code for stable codes",
+    }
+}
+
+fn operation_context() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "OperationContext",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("key_id", FieldKind::String, "Key the operation acts on"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Share epoch; 0 for the initial dealing, +1 per refresh or reshare",
+            ),
+            field("operation_id", FieldKind::String, "Unique per invocation"),
+        ],
+        algorithms: vec![],
+        description: "Correlation identifiers attached to audit records and metrics",
+    }
+}
+
+fn audit_record() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "AuditRecord",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "context",
+                optional(FieldKind::Artifact {
+                    name: "OperationContext",
+                }),
+                "Context active when the event was logged",
+            ),
+            field(
+                "event",
+                FieldKind::Artifact {
+                    name: "SecurityEvent",
+                },
+                "The audit event",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Audit event tagged with its operation context",
+    }
+}
+
+fn chained_audit_record() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ChainedAuditRecord",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "sequence",
+                FieldKind::Unsigned { bits: 64 },
+                "Position in the chain, from 0 per logger",
+            ),
+            field(
+                "previous",
+                FieldKind::HexBytes { length: Some(32) },
+                "Hash of the previous entry; zero for sequence 0",
+            ),
+            field(
+                "record",
+                FieldKind::Artifact {
+                    name: "AuditRecord",
+                },
+                "The audit record",
+            ),
+            field(
+                "hash",
+                FieldKind::HexBytes { length: Some(32) },
+                "BLAKE3 derive_key(previous ‖ sequence_le ‖ JSON(record))",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Persisted audit entry, one JSON line per entry in audit log files",
+    }
+}
+
+fn audit_export() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "AuditExport",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("version", FieldKind::Unsigned { bits: 8 }, "Format version, 1"),
+            field(
+                "context",
+                FieldKind::String,
+                "BLAKE3 derive_key context of the chain hashes",
+            ),
+            field(
+                "entries",
+                array(FieldKind::Artifact {
+                    name: "ChainedAuditRecord",
+                }),
+                "One chain from sequence 0; hashes cover the compact JSON of each record as emitted",
+            ),
+            field(
+                "head",
+                FieldKind::HexBytes { length: Some(32) },
+                "Hash of the last entry, zero if empty; compare with an independently recorded head",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Complete audit chain of one logger, for verification by third-party auditors",
+    }
+}
+
+fn rotation_entry() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RotationEntry",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("key_id", FieldKind::String, "Key whose sharing is tracked"),
+            field("threshold", USIZE, "Threshold of the sharing"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Epoch the current slices were issued in",
+            ),
+            field(
+                "operation_id",
+                FieldKind::String,
+                "Operation that issued the current slices",
+            ),
+            field(
+                "issued_at",
+                FieldKind::UnixSeconds,
+                "Issue time; the sharing is rotated once older than the maximum key lifetime",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Sharing tracked by a KeyRotationManager",
+    }
+}
+
+fn share_registry() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareRegistry",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![field(
+            "entries",
+            array(FieldKind::Artifact {
+                name: "RegistryEntry",
+            }),
+            "Issued slices in issuing order",
+        )],
+        algorithms: vec![],
+        description: "Issued slices and their revocation status",
+    }
+}
+
+fn registry_entry() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "RegistryEntry",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "operation_id",
+                FieldKind::String,
+                "Operation that issued the slice",
+            ),
+            field("index", USIZE, "Slice index"),
+            field("key_id", FieldKind::String, "Key the slice belongs to"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Share epoch the slice was issued in",
+            ),
+            field(
+                "commitment",
+                FieldKind::HexRistrettoPoint,
+                "Pedersen commitment of the slice",
+            ),
+            field(
+                "status",
+                FieldKind::Artifact {
+                    name: "ShareStatus",
+                },
+                "Whether the slice may still be used",
+            ),
+        ],
+        algorithms: vec!["pedersen-vss"],
+        description: "One issued slice in a ShareRegistry",
+    }
+}
+
+fn share_status() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "ShareStatus",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec!["Issued", "Revoked"],
+        },
+        fields: vec![
+            field("reason", FieldKind::String, "Revoked"),
+            field("revoked_at", FieldKind::UnixSeconds, "Revoked"),
+        ],
+        algorithms: vec![],
+        description: "Revocation status of an issued slice; Issued is the bare string \"Issued\"",
+    }
+}
+
+fn public_state() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PublicState",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Format version, 1",
+            ),
+            field("key_id", FieldKind::String, "Key the state belongs to"),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Last share epoch of the key",
+            ),
+            field(
+                "operation_sequence",
+                optional(FieldKind::Unsigned { bits: 64 }),
+                "Last operation sequence number of the exporting system",
+            ),
+            field(
+                "policy",
+                FieldKind::Artifact { name: "KeyPolicy" },
+                "Policy the key is operated under",
+            ),
+            field(
+                "shares",
+                array(FieldKind::Artifact {
+                    name: "RegistryEntry",
+                }),
+                "Every slice issued for the key, in issuing order",
+            ),
+            field(
+                "custodians",
+                array(FieldKind::Artifact {
+                    name: "CustodianStatus",
+                }),
+                "Last attestation of every custodian holding a slice of the key",
+            ),
+            field(
+                "transcripts",
+                array(FieldKind::Artifact {
+                    name: "CeremonyTranscript",
+                }),
+                "Transcripts of the key, oldest first",
+            ),
+            field("exported_at", FieldKind::UnixSeconds, "Export time"),
+        ],
+        algorithms: vec!["pedersen-vss", "blake3"],
+        description: "Public state of one key for offsite backup; contains no secret",
+    }
+}
+
+fn key_policy() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "KeyPolicy",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "threshold",
+                USIZE,
+                "Threshold of the latest dealing or refresh",
+            ),
+            field(
+                "security_level",
+                FieldKind::Enum {
+                    variants: vec!["Standard", "High", "Maximum"],
+                },
+                "Security level of the exporting system",
+            ),
+            field(
+                "compliance_mode",
+                FieldKind::String,
+                "Compliance mode of the exporting system, e.g. Fips140L3",
+            ),
+            field(
+                "max_key_lifetime_hours",
+                FieldKind::Unsigned { bits: 64 },
+                "Cryptoperiod of the key's slices",
+            ),
+            field(
+                "expires_at",
+                optional(FieldKind::UnixSeconds),
+                "Expiry time enforced for the key; null or absent if none",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Policy a key is operated under",
+    }
+}
+
+fn system_status() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "SystemStatus",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("generated_at", FieldKind::UnixSeconds, "Snapshot time"),
+            field(
+                "key_counts",
+                FieldKind::Artifact {
+                    name: "KeyStateCounts",
+                },
+                "Keys per lifecycle state",
+            ),
+            field(
+                "keys",
+                array(FieldKind::Artifact { name: "KeyStatus" }),
+                "Keys in order of first appearance",
+            ),
+            field(
+                "custodians",
+                array(FieldKind::Artifact {
+                    name: "CustodianStatus",
+                }),
+                "Last attestation per custodian",
+            ),
+            field(
+                "recent_high_severity_events",
+                array(FieldKind::Artifact {
+                    name: "AuditRecord",
+                }),
+                "Newest first, at most RECENT_EVENT_LIMIT",
+            ),
+            field(
+                "safe_mode",
+                optional(FieldKind::String),
+                "Reason the system is in safe mode, null otherwise",
+            ),
+            field(
+                "performance",
+                array(FieldKind::Artifact {
+                    name: "PerformanceSummary",
+                }),
+                "Duration statistics per operation type",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Read-only operator dashboard snapshot",
+    }
+}
+
+fn key_status() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "KeyStatus",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("key_id", FieldKind::String, "Key identifier"),
+            field(
+                "state",
+                optional(FieldKind::Enum {
+                    variants: vec!["Generated", "Active", "Retired", "Destroyed"],
+                }),
+                "Latest audited lifecycle state",
+            ),
+            field(
+                "epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "Epoch of the latest dealing or refresh",
+            ),
+            field(
+                "threshold",
+                USIZE,
+                "Threshold of the latest dealing or refresh",
+            ),
+            field(
+                "shares_outstanding",
+                USIZE,
+                "Slices issued by the latest dealing or refresh",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Status of one key in a SystemStatus",
+    }
+}
+
+fn key_state_counts() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "KeyStateCounts",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("generated", USIZE, "Keys generated but not activated"),
+            field("active", USIZE, "Active keys"),
+            field("retired", USIZE, "Retired keys"),
+            field("destroyed", USIZE, "Destroyed keys"),
+        ],
+        algorithms: vec![],
+        description: "Number of keys per lifecycle state",
+    }
+}
+
+fn custodian_status() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CustodianStatus",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("custodian_id", FieldKind::String, "Custodian identifier"),
+            field(
+                "key_id",
+                FieldKind::String,
+                "Key the attested slice belongs to",
+            ),
+            field("index", USIZE, "Index of the attested slice"),
+            field(
+                "commitment_root",
+                FieldKind::HexBytes { length: Some(32) },
+                "Commitment root of the attestation",
+            ),
+            field(
+                "checked_at",
+                FieldKind::UnixSeconds,
+                "Time of the custodian check",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Last verified attestation of one custodian",
+    }
+}
+
+fn performance_summary() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PerformanceSummary",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("operation_type", FieldKind::String, "Operation type"),
+            field("samples", USIZE, "Number of samples"),
+            field(
+                "mean_duration_ns",
+                FieldKind::Unsigned { bits: 64 },
+                "Mean duration",
+            ),
+            field(
+                "max_duration_ns",
+                FieldKind::Unsigned { bits: 64 },
+                "Maximum duration",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Duration statistics of one operation type",
+    }
+}
+
+fn crypto_error() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "CryptoError",
+        version: 3,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec![
+                "KeyGeneration",
+                "InvalidKeyStateTransition",
+                "CryptographicOperation",
+                "Serialization",
+                "Validation",
+                "SecurityViolation",
+                "InsufficientEntropy",
+                "Timeout",
+                "ResourceExhaustion",
+                "Network",
+                "Configuration",
+                "RevokedShare",
+                "SafeMode",
+                "KeyExpired",
+            ],
+        },
+        fields: vec![
+            field(
+                "reason",
+                FieldKind::String,
+                "KeyGeneration, Validation, SafeMode",
+            ),
+            field("from", FieldKind::String, "InvalidKeyStateTransition"),
+            field("to", FieldKind::String, "InvalidKeyStateTransition"),
+            field(
+                "operation",
+                FieldKind::String,
+                "CryptographicOperation, Timeout, SafeMode",
+            ),
+            field(
+                "details",
+                FieldKind::String,
+                "Serialization, SecurityViolation, Network",
+            ),
+            field("field", FieldKind::String, "Validation"),
+            field(
+                "required",
+                FieldKind::Unsigned { bits: 32 },
+                "InsufficientEntropy",
+            ),
+            field(
+                "actual",
+                FieldKind::Unsigned { bits: 32 },
+                "InsufficientEntropy",
+            ),
+            field("timeout_ms", FieldKind::Unsigned { bits: 64 }, "Timeout"),
+            field("resource", FieldKind::String, "ResourceExhaustion"),
+            field("parameter", FieldKind::String, "Configuration"),
+            field("issue", FieldKind::String, "Configuration"),
+            field("operation_id", FieldKind::String, "RevokedShare"),
+            field("index", USIZE, "RevokedShare"),
+            field("key_id", FieldKind::String, "KeyExpired"),
+            field("expired_at", FieldKind::UnixSeconds, "KeyExpired"),
+        ],
+        algorithms: vec![],
+        description: "Library error; see CryptoError::code for stable codes",
+    }
+}
+
+fn verify_error() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "VerifyError",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec![
+                "ChallengeMismatch",
+                "InvalidResponse",
+                "OpeningMismatch",
+                "CommitmentMismatch",
+                "NonCanonicalEncoding",
+                "WrongContext",
+                "StaleEpoch",
+                "RefreshMismatch",
+            ],
+        },
+        fields: vec![
+            field(
+                "index",
+                USIZE,
+                "Every variant except NonCanonicalEncoding and RefreshMismatch",
+            ),
+            field(
+                "field",
+                FieldKind::String,
+                "NonCanonicalEncoding; JSON path of the bad field",
+            ),
+            field("reason", FieldKind::String, "NonCanonicalEncoding"),
+            field("details", FieldKind::String, "WrongContext"),
+            field("epoch", FieldKind::Unsigned { bits: 64 }, "StaleEpoch"),
+            field(
+                "current_epoch",
+                FieldKind::Unsigned { bits: 64 },
+                "StaleEpoch",
+            ),
+            field(
+                "coefficient",
+                USIZE,
+                "RefreshMismatch; position j of the mismatching C_j",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Verification failure; see VerifyError::code and VerifyError::class",
+    }
+}
+
+fn performance_metrics() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "PerformanceMetrics",
+        version: 3,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("operation_type", FieldKind::String, "Operation name"),
+            field(
+                "duration_ns",
+                FieldKind::Unsigned { bits: 64 },
+                "Wall-clock duration",
+            ),
+            field("input_size", USIZE, "Number of input items"),
+            field("algorithm_used", FieldKind::String, "Algorithm name"),
+            field(
+                "context",
+                optional(FieldKind::Artifact {
+                    name: "OperationContext",
+                }),
+                "Operation context; omitted when the sample has none",
+            ),
+            field(
+                "counts",
+                optional(FieldKind::Artifact {
+                    name: "OperationCounts",
+                }),
+                "Primitive counts over the operation; omitted without the op-counters feature",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Performance sample for enterprise monitoring",
+    }
+}
+
+fn harness_report() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "HarnessReport",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "version",
+                FieldKind::Unsigned { bits: 8 },
+                "Format version, 1",
+            ),
+            field(
+                "threshold",
+                FieldKind::Number,
+                "|t| above which a target is reported as leaking",
+            ),
+            field(
+                "optimized",
+                FieldKind::Boolean,
+                "Whether the measured build had optimizations",
+            ),
+            field(
+                "targets",
+                array(FieldKind::Artifact {
+                    name: "TimingReport",
+                }),
+                "One report per target: recovery, proof_verification, mac_comparison",
+            ),
+        ],
+        algorithms: vec!["welch-t-test"],
+        description: "dudect-style constant-time test run (feature ct-harness)",
+    }
+}
+
+fn timing_report() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "TimingReport",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field("target", FieldKind::String, "Name of the timed operation"),
+            field("samples", USIZE, "Measurements in the smaller class"),
+            field(
+                "fixed_mean_ns",
+                FieldKind::Number,
+                "Mean duration with the secret input fixed",
+            ),
+            field(
+                "random_mean_ns",
+                FieldKind::Number,
+                "Mean duration with the secret input random",
+            ),
+            field(
+                "max_t",
+                FieldKind::Number,
+                "Largest |t| over the uncropped and percentile-cropped measurements",
+            ),
+            field(
+                "leak_suspected",
+                FieldKind::Boolean,
+                "max_t above the threshold of the run",
+            ),
+        ],
+        algorithms: vec!["welch-t-test"],
+        description: "Fixed-versus-random timing comparison of one operation",
+    }
+}
+
+fn operation_counts() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "OperationCounts",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "scalar_muls",
+                FieldKind::Unsigned { bits: 64 },
+                "Scalar-field multiplications",
+            ),
+            field(
+                "point_muls",
+                FieldKind::Unsigned { bits: 64 },
+                "Scalar·point products; an n-term multiscalar multiplication counts n",
+            ),
+            field(
+                "inversions",
+                FieldKind::Unsigned { bits: 64 },
+                "Scalar-field inversions; a batch inversion counts one",
+            ),
+            field(
+                "hash_bytes",
+                FieldKind::Unsigned { bits: 64 },
+                "Bytes absorbed by Blake3Adapter",
+            ),
+            field(
+                "worker_threads",
+                FieldKind::Unsigned { bits: 64 },
+                "rayon pool size when the counts were taken",
+            ),
+        ],
+        algorithms: vec![],
+        description:
+            "Process-wide primitive counts (op_counts), for comparison with benchmark baselines",
+    }
+}
+
+fn lagrange_error() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "LagrangeError",
+        version: 1,
+        encoding: "json",
+        layout: Layout::TaggedEnum {
+            variants: vec![
+                "InsufficientShares",
+                "InvalidShareIndex",
+                "DuplicateShareIndex",
+                "ZeroDerivative",
+                "PolynomialDegreeTooHigh",
+                "NumericalInstability",
+                "TooManyErrors",
+                "InconsistentShare",
+                "IndexSetMismatch",
+                "NotPoised",
+            ],
+        },
+        fields: vec![
+            field("needed", USIZE, "InsufficientShares"),
+            field("provided", USIZE, "InsufficientShares"),
+            field("index", USIZE, "Index-carrying variants"),
+            field("degree", USIZE, "PolynomialDegreeTooHigh"),
+            field(
+                "correctable",
+                USIZE,
+                "TooManyErrors: faulty slices the set could correct",
+            ),
+        ],
+        algorithms: vec![],
+        description: "Interpolation error; unit variants serialize as plain strings",
+    }
+}
+
+fn blake3_adapter() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Blake3Adapter",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Tuple,
+        fields: vec![
+            field(
+                "hasher_state",
+                FieldKind::Artifact {
+                    name: "Blake3HasherState",
+                },
+                "Position 0",
+            ),
+            field(
+                "security_level",
+                FieldKind::Enum {
+                    variants: vec!["Standard", "High", "Maximum"],
+                },
+                "Position 1",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Checkpoint of a hasher: chaining values and at most the last 64 bytes of \
+                      input. Version 1 checkpoints, which carried all input in \
+                      `accumulated_data`, are still accepted",
+    }
+}
+
+fn blake3_hasher_state() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Blake3HasherState",
+        version: 2,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "key",
+                optional(FieldKind::ByteArray { length: Some(32) }),
+                "Key for keyed mode",
+            ),
+            field(
+                "context",
+                optional(FieldKind::String),
+                "Context for derive-key mode",
+            ),
+            field(
+                "mode",
+                FieldKind::Enum {
+                    variants: vec!["Normal", "Keyed", "DeriveKey"],
+                },
+                "Hash mode",
+            ),
+            field(
+                "tree",
+                FieldKind::Artifact {
+                    name: "Blake3ChainState",
+                },
+                "Hash tree state of the input absorbed so far",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "Internal state embedded in a Blake3Adapter checkpoint",
+    }
+}
+
+fn blake3_chain_state() -> ArtifactDescription {
+    ArtifactDescription {
+        name: "Blake3ChainState",
+        version: 1,
+        encoding: "json",
+        layout: Layout::Object,
+        fields: vec![
+            field(
+                "chunk_counter",
+                FieldKind::Unsigned { bits: 64 },
+                "Number of completed 1024-byte chunks",
+            ),
+            field(
+                "cv_stack",
+                array(FieldKind::HexBytes { length: Some(32) }),
+                "Chaining values of the completed subtrees, largest first; one per set bit of \
+                 chunk_counter",
+            ),
+            field(
+                "chunk_cv",
+                FieldKind::HexBytes { length: Some(32) },
+                "Chaining value of the current chunk after its compressed blocks; zero before \
+                 the first",
+            ),
+            field(
+                "blocks_compressed",
+                FieldKind::Unsigned { bits: 8 },
+                "Compressed 64-byte blocks of the current chunk, at most 15",
+            ),
+            field(
+                "block",
+                FieldKind::HexBytes { length: None },
+                "Input of the current block, at most 64 bytes",
+            ),
+        ],
+        algorithms: vec!["blake3"],
+        description: "BLAKE3 incremental state embedded in a Blake3HasherState",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys of a serialized JSON object, sorted.
+    fn object_keys(value: &serde_json::Value) -> Vec<String> {
+        let mut keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn described_keys(artifact: &ArtifactDescription) -> Vec<String> {
+        let mut keys: Vec<_> = artifact.fields.iter().map(|f| f.name.to_string()).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_description_is_serializable() {
+        let description = describe();
+        let json = serde_json::to_value(&description).unwrap();
+
+        assert_eq!(json["crate_name"], "zk-thresh-pro");
+        assert!(description.artifact("ShareData").is_some());
+        assert!(description.artifact("Unknown").is_none());
+    }
+
+    #[test]
+    fn test_hasher_layout_matches_description() {
+        let description = describe();
+        let json = serde_json::to_value(crate::hash_adapter::Blake3Adapter::new()).unwrap();
+
+        assert_eq!(
+            object_keys(&json[0]),
+            described_keys(description.artifact("Blake3HasherState").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json[0]["tree"]),
+            described_keys(description.artifact("Blake3ChainState").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_operation_counts_layout_matches_description() {
+        let description = describe();
+        let json = serde_json::to_value(crate::op_counts::OperationCounts::default()).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("OperationCounts").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_audit_record_layout_matches_description() {
+        let description = describe();
+        let record = crate::error::AuditRecord {
+            context: Some(crate::context::OperationContext::new("k", 1, "op")),
+            event: crate::error::SecurityEvent::SharesRefreshed {
+                key_id: "k".into(),
+                threshold: 2,
+                num_shares: 3,
+                timestamp: chrono::Utc::now(),
+            },
+        };
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("AuditRecord").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["context"]),
+            described_keys(description.artifact("OperationContext").unwrap())
+        );
+
+        let chained = crate::audit::ChainedAuditRecord::new(0, [0u8; 32], record).unwrap();
+        assert_eq!(
+            object_keys(&serde_json::to_value(&chained).unwrap()),
+            described_keys(description.artifact("ChainedAuditRecord").unwrap())
+        );
+        let head: [u8; 32] = chained.hash.clone().try_into().unwrap();
+        let export = crate::audit::AuditExport::new(vec![chained], head);
+        assert_eq!(
+            object_keys(&serde_json::to_value(&export).unwrap()),
+            described_keys(description.artifact("AuditExport").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_share_registry_layout_matches_description() {
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::scalar::Scalar::from(5u64), 2, 2)
+                .unwrap();
+        let mut registry = crate::registry::ShareRegistry::new();
+        registry.issue(
+            &crate::context::OperationContext::new("k", 0, "op"),
+            &shares,
+        );
+        registry.revoke("op", 1, "lost").unwrap();
+        let json = serde_json::to_value(&registry).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("ShareRegistry").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["entries"][0]),
+            described_keys(description.artifact("RegistryEntry").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["entries"][0]["status"]["Revoked"]),
+            described_keys(description.artifact("ShareStatus").unwrap())
+        );
+        assert_eq!(json["entries"][1]["status"], "Issued");
+
+        let mut manager = crate::key_lifecycle::KeyRotationManager::new(24).unwrap();
+        manager.track(&crate::context::OperationContext::new("k", 0, "op"), 2);
+        assert_eq!(
+            object_keys(&serde_json::to_value(&manager.entries()[0]).unwrap()),
+            described_keys(description.artifact("RotationEntry").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_status_layout_matches_description() {
+        use crate::status::{CustodianStatus, KeyStateCounts, KeyStatus, PerformanceSummary};
+
+        let description = describe();
+        let status = crate::status::SystemStatus {
+            generated_at: chrono::Utc::now(),
+            key_counts: KeyStateCounts::default(),
+            keys: vec![KeyStatus {
+                key_id: "k".into(),
+                state: Some(crate::KeyState::Active),
+                epoch: 0,
+                threshold: 2,
+                shares_outstanding: 3,
+            }],
+            custodians: vec![CustodianStatus {
+                custodian_id: "c".into(),
+                key_id: "k".into(),
+                index: 1,
+                commitment_root: vec![0; 32],
+                checked_at: chrono::Utc::now(),
+            }],
+            recent_high_severity_events: vec![],
+            safe_mode: Some("entropy failure".into()),
+            performance: vec![PerformanceSummary {
+                operation_type: "op".into(),
+                samples: 1,
+                mean_duration_ns: 1,
+                max_duration_ns: 1,
+            }],
+        };
+        let json = serde_json::to_value(&status).unwrap();
+
+        for (name, value) in [
+            ("SystemStatus", &json),
+            ("KeyStateCounts", &json["key_counts"]),
+            ("KeyStatus", &json["keys"][0]),
+            ("CustodianStatus", &json["custodians"][0]),
+            ("PerformanceSummary", &json["performance"][0]),
+        ] {
+            assert_eq!(
+                object_keys(value),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_public_state_layout_matches_description() {
+        let description = describe();
+        let mut system = crate::EnterpriseCryptoSystem::new(crate::EnterpriseConfig::default());
+        let context = crate::OperationContext::new("k", 0, "deal");
+        system
+            .create_secret_shares(curve25519_dalek::Scalar::ONE, 2, 2, &context)
+            .unwrap();
+        let json = serde_json::to_value(system.export_public_state("k").unwrap()).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("PublicState").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["policy"]),
+            described_keys(description.artifact("KeyPolicy").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_share_layout_matches_description() {
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 2, 2).unwrap();
+        let json = serde_json::to_value(&shares[0]).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("ShareData").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["proof"]),
+            described_keys(description.artifact("Proof").unwrap())
+        );
+
+        let (unproven, _) =
+            crate::sharing::generate_key_shares_deferred(curve25519_dalek::Scalar::ONE, 2, 2)
+                .unwrap();
+        let proofs = crate::sharing::generate_share_proofs(&unproven).unwrap();
+        assert_eq!(
+            object_keys(&serde_json::to_value(&unproven[0]).unwrap()),
+            described_keys(description.artifact("UnprovenShare").unwrap())
+        );
+        assert_eq!(
+            object_keys(&serde_json::to_value(&proofs[0]).unwrap()),
+            described_keys(description.artifact("ShareProof").unwrap())
+        );
+
+        let dealing =
+            crate::dealing::generate_dealing(curve25519_dalek::Scalar::ONE, 2, 2, "dealer")
+                .unwrap();
+        let json = serde_json::to_value(&dealing).unwrap();
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("Dealing").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["aggregate_proof"]),
+            described_keys(description.artifact("AggregateProof").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_vss_layout_matches_description() {
+        let description = describe();
+        let (shares, commitments) = crate::sharing::generate_key_shares_with_commitments(
+            curve25519_dalek::Scalar::ONE,
+            2,
+            2,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&commitments).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("VssCommitments").unwrap())
+        );
+
+        let (_, record) = crate::sharing::refresh_shares(&shares, &commitments).unwrap();
+        assert_eq!(
+            object_keys(&serde_json::to_value(&record).unwrap()),
+            described_keys(description.artifact("RefreshRecord").unwrap())
+        );
+
+        let (_, links) = crate::sharing::update_shares_linked(&shares, 2).unwrap();
+        let json = serde_json::to_value(&links).unwrap();
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("RefreshLinks").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["links"][0]),
+            described_keys(description.artifact("ShareLink").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_reconfiguration_plan_layout_matches_description() {
+        use crate::reconfigure::{plan, Committee, ReconfigurationPolicy};
+
+        let description = describe();
+        let current = Committee::new(vec![1, 2], 2).unwrap();
+        let target = Committee::new(vec![1, 2, 3], 2).unwrap();
+        let plan = plan(&current, &target, &ReconfigurationPolicy::default()).unwrap();
+        let json = serde_json::to_value(&plan).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("ReconfigurationPlan").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["steps"][0]),
+            described_keys(description.artifact("PlanStep").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["to"]),
+            described_keys(description.artifact("Committee").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_transcript_layout_matches_description() {
+        use crate::transcript::{CeremonyKind, CeremonyTranscript, TestAuthority};
+
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 2, 2).unwrap();
+        let context = crate::context::OperationContext::new("k", 0, "op");
+        let mut transcript = CeremonyTranscript::new(CeremonyKind::Dealing, &context, 2, &shares);
+        transcript.stamp(&TestAuthority([0; 32])).unwrap();
+        let json = serde_json::to_value(&transcript).unwrap();
+
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("CeremonyTranscript").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["timestamp"]),
+            described_keys(description.artifact("TimestampToken").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_custodian_layout_matches_description() {
+        let description = describe();
+        let (shares, commitments) = crate::sharing::generate_key_shares_with_commitments(
+            curve25519_dalek::Scalar::ONE,
+            2,
+            2,
+        )
+        .unwrap();
+        let key = crate::sealing::SealingKey::generate().unwrap();
+        let sealed = crate::sealing::seal(&shares[0], &key).unwrap();
+        let wrapping_key = crate::wrapping::WrappingSecretKey::generate().unwrap();
+        let wrapped = shares[1].seal(&wrapping_key.public_key()).unwrap();
+        let (_, attestation) = crate::custodian::check_share(
+            &sealed,
+            &key,
+            &commitments,
+            &commitments.root(),
+            &[1; 16],
+        )
+        .unwrap();
+
+        assert_eq!(
+            object_keys(&serde_json::to_value(&sealed).unwrap()),
+            described_keys(description.artifact("SealedShare").unwrap())
+        );
+        assert_eq!(
+            object_keys(&serde_json::to_value(&wrapped).unwrap()),
+            described_keys(description.artifact("WrappedShare").unwrap())
+        );
+        assert_eq!(
+            object_keys(&serde_json::to_value(&wrapped).unwrap()["algorithms"]),
+            described_keys(description.artifact("AlgorithmSuite").unwrap())
+        );
+        assert_eq!(
+            object_keys(&serde_json::to_value(&attestation).unwrap()),
+            described_keys(description.artifact("ShareAttestation").unwrap())
+        );
+        let stored = crate::shareholder::StoredShare {
+            version: crate::shareholder::STORED_SHARE_VERSION,
+            context: crate::OperationContext::new("k", 0, "op"),
+            commitments,
+            sealed,
+        };
+        assert_eq!(
+            object_keys(&serde_json::to_value(&stored).unwrap()),
+            described_keys(description.artifact("StoredShare").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_pvss_layout_matches_description() {
+        use crate::pvss::{self, PvssSecretKey};
+
+        let description = describe();
+        let keys: Vec<_> = (0..2).map(|_| PvssSecretKey::generate().unwrap()).collect();
+        let recipients: Vec<_> = keys.iter().map(|k| k.public_key()).collect();
+        let dealing = pvss::deal(&curve25519_dalek::Scalar::from(5u64), 2, &recipients).unwrap();
+        let decrypted = pvss::decrypt_share(&dealing, &keys[0]).unwrap();
+
+        let cases = [
+            ("PvssDealing", serde_json::to_value(&dealing)),
+            ("EncryptedShare", serde_json::to_value(&dealing.shares[0])),
+            ("DecryptedShare", serde_json::to_value(&decrypted)),
+            ("DleqProof", serde_json::to_value(&decrypted.proof)),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_threshold_decryption_layout_matches_description() {
+        use crate::threshold_decrypt;
+
+        let description = describe();
+        let secret = curve25519_dalek::Scalar::from(5u64);
+        let public_key = curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = crate::sharing::generate_key_shares(secret, 2, 3).unwrap();
+        let ciphertext = threshold_decrypt::encrypt(&public_key, b"message").unwrap();
+        let partial = threshold_decrypt::partial_decrypt(&ciphertext, &shares[0]).unwrap();
+
+        let cases = [
+            ("ThresholdCiphertext", serde_json::to_value(&ciphertext)),
+            ("PartialDecryption", serde_json::to_value(&partial)),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_beacon_layout_matches_description() {
+        use crate::beacon::{BeaconContribution, BeaconRound};
+
+        let description = describe();
+        let mut round = BeaconRound::new(1, 2).unwrap();
+        let contributions: Vec<_> = (1..=2)
+            .map(|i| BeaconContribution::new(1, i).unwrap())
+            .collect();
+        for contribution in &contributions {
+            round.commit(contribution.commitment()).unwrap();
+        }
+        for contribution in contributions {
+            round.reveal(contribution.reveal()).unwrap();
+        }
+        let transcript = round.finalize().unwrap();
+
+        let cases = [
+            ("BeaconTranscript", serde_json::to_value(&transcript)),
+            (
+                "BeaconCommitment",
+                serde_json::to_value(&transcript.commitments[0]),
+            ),
+            ("BeaconReveal", serde_json::to_value(&transcript.reveals[0])),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_policy_proof_layout_matches_description() {
+        use crate::policy_proof::{CommitteeMember, PolicyComplianceProof};
+
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 1, 2).unwrap();
+        let committee = shares
+            .iter()
+            .map(|s| CommitteeMember {
+                index: s.index,
+                custodian_id: Some("custodian".to_string()),
+                commitment: s.commitment,
+            })
+            .collect();
+        let mut proof = PolicyComplianceProof::new("k", "recover", "deal", 1, committee).unwrap();
+        proof.contribute(&shares[0]).unwrap();
+
+        let cases = [
+            ("PolicyComplianceProof", serde_json::to_value(&proof)),
+            ("CommitteeMember", serde_json::to_value(&proof.committee[0])),
+            (
+                "ContributionProof",
+                serde_json::to_value(&proof.contributions[0]),
+            ),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_index_assignment_layout_matches_description() {
+        let description = describe();
+        let assignment = crate::indexing::IndexAssignment::derived("d", &["a", "b"]).unwrap();
+        let json = serde_json::to_value(&assignment).unwrap();
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("IndexAssignment").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["entries"][0]),
+            described_keys(description.artifact("CustodianIndex").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_dkg_layout_matches_description() {
+        use crate::dkg::{Complaint, DkgParticipant};
+
+        let description = describe();
+        let mut parties: Vec<_> = (1..=2)
+            .map(|i| DkgParticipant::insecure_unsigned(i, 2, 2).unwrap())
+            .collect();
+        let broadcasts: Vec<_> = parties.iter_mut().map(|p| p.round1().unwrap()).collect();
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(b.clone()).unwrap();
+            }
+        }
+        let slices = parties[0].round2().unwrap();
+        parties[1].round2().unwrap();
+        parties[1].receive_round2(slices[0].clone()).unwrap();
+        let output = parties[1].finalize().unwrap();
+        let complaint = Complaint {
+            accused: 1,
+            complainant: 2,
+        };
+        let response = parties[0].receive_complaint(complaint).unwrap().unwrap();
+
+        let cases = [
+            ("Round1Broadcast", serde_json::to_value(&broadcasts[0])),
+            (
+                "ConstantTermProof",
+                serde_json::to_value(&broadcasts[0].proof),
+            ),
+            ("Round2Share", serde_json::to_value(&slices[0])),
+            ("Complaint", serde_json::to_value(complaint)),
+            ("ComplaintResponse", serde_json::to_value(&response)),
+            ("DkgOutput", serde_json::to_value(&output)),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_policy_layout_matches_description() {
+        use crate::access::Policy;
+
+        let description = describe();
+        let artifact = description.artifact("Policy").unwrap();
+        let Layout::TaggedEnum { variants } = &artifact.layout else {
+            panic!("Policy is a tagged enum");
+        };
+        let described = described_keys(artifact);
+        let party = Policy::party("a");
+        let policies = [
+            party.clone(),
+            Policy::And(vec![party.clone()]),
+            Policy::Or(vec![party.clone()]),
+            Policy::Threshold(1, vec![party.clone()]),
+        ];
+        assert_eq!(policies.len(), variants.len());
+        for (policy, variant) in policies.iter().zip(variants) {
+            let json = serde_json::to_value(policy).unwrap();
+            assert_eq!(object_keys(&json), vec![variant.to_string()]);
+            assert!(object_keys(&json[*variant])
+                .iter()
+                .all(|k| described.contains(k)));
+        }
+
+        let share = crate::sharing::generate_policy_shares(curve25519_dalek::Scalar::ONE, &party)
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            object_keys(&serde_json::to_value(&share).unwrap()),
+            described_keys(description.artifact("PolicyShare").unwrap())
+        );
+        let share =
+            crate::sharing::generate_hierarchical_shares(curve25519_dalek::Scalar::ONE, &[1], &[1])
+                .unwrap()
+                .remove(0);
+        assert_eq!(
+            object_keys(&serde_json::to_value(&share).unwrap()),
+            described_keys(description.artifact("HierarchicalShare").unwrap())
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_recovery_layout_matches_description() {
+        use crate::recovery::{repair_parts, repair_sum, RepairRequest};
+
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 2, 3).unwrap();
+        let request = RepairRequest::new(3, vec![1, 2]).unwrap();
+        let parts = repair_parts(&shares[0], &request).unwrap();
+        let mut received = vec![parts[1].clone()];
+        received.extend(
+            repair_parts(&shares[1], &request)
+                .unwrap()
+                .into_iter()
+                .skip(1),
+        );
+        let sum = repair_sum(&request, 2, &received).unwrap();
+
+        let cases = [
+            ("RepairRequest", serde_json::to_value(&request)),
+            ("RepairPart", serde_json::to_value(&parts[0])),
+            ("RepairSum", serde_json::to_value(&sum)),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+    }
+
+    #[cfg(feature = "transport")]
+    #[test]
+    fn test_transport_layout_matches_description() {
+        use crate::transport::{Message, RecoveryRequest};
+
+        let description = describe();
+        let request = RecoveryRequest {
+            context: crate::context::OperationContext::new("k", 0, "op"),
+            requester: 1,
+        };
+        let share = crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 2, 2)
+            .unwrap()
+            .remove(0);
+        let response = serde_json::to_value(Message::RecoveryResponse {
+            operation_id: "op".into(),
+            share,
+        })
+        .unwrap();
+
+        assert_eq!(
+            object_keys(&serde_json::to_value(&request).unwrap()),
+            described_keys(description.artifact("RecoveryRequest").unwrap())
+        );
+        assert_eq!(
+            object_keys(&response["RecoveryResponse"]),
+            described_keys(description.artifact("Message").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_identity_layout_matches_description() {
+        use crate::identity::Participant;
+
+        let description = describe();
+        let participant = Participant::from_seed(1, &[1; 32]).unwrap();
+        let signed = participant
+            .sign(
+                "op",
+                crate::threshold_sig::PartialSignature {
+                    index: 1,
+                    response: curve25519_dalek::scalar::Scalar::ONE,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            object_keys(&serde_json::to_value(participant.identity()).unwrap()),
+            described_keys(description.artifact("ParticipantIdentity").unwrap())
+        );
+        assert_eq!(
+            object_keys(&serde_json::to_value(signed).unwrap()),
+            described_keys(description.artifact("SignedMessage").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_ceremony_layout_matches_description() {
+        use crate::ceremony::{Ceremony, MemoryCeremonyStore, ProtocolKind};
+
+        let description = describe();
+        let context = crate::context::OperationContext::new("k", 0, "op");
+        let mut ceremony = Ceremony::start(
+            Box::new(MemoryCeremonyStore::new()),
+            ProtocolKind::Dkg,
+            context,
+            1,
+            &[1],
+        )
+        .unwrap();
+        ceremony
+            .open_round("round1", std::time::Duration::from_secs(60))
+            .unwrap();
+        ceremony.record(1, &1u8).unwrap();
+        let state = serde_json::to_value(ceremony.state()).unwrap();
+        ceremony.close_round().unwrap();
+        let transcript = serde_json::to_value(ceremony.finish().unwrap()).unwrap();
+
+        assert_eq!(
+            object_keys(&state),
+            described_keys(description.artifact("CeremonyState").unwrap())
+        );
+        assert_eq!(
+            object_keys(&state["rounds"][0]),
+            described_keys(description.artifact("CeremonyRound").unwrap())
+        );
+        assert_eq!(
+            object_keys(&state["rounds"][0]["responses"][0]),
+            described_keys(description.artifact("CeremonyResponse").unwrap())
+        );
+        assert_eq!(
+            object_keys(&transcript),
+            described_keys(description.artifact("ProtocolTranscript").unwrap())
+        );
+    }
+
+    #[cfg(feature = "channel-service")]
+    #[test]
+    fn test_service_layout_matches_description() {
+        use crate::context::OperationContext;
+        use crate::service::{ServiceRequest, ServiceResponse};
+
+        let description = describe();
+        let shares =
+            crate::sharing::generate_key_shares(curve25519_dalek::Scalar::ONE, 2, 2).unwrap();
+        let deal = serde_json::to_value(ServiceRequest::Deal {
+            context: OperationContext::new("k", 0, "op"),
+            secret: zeroize::Zeroizing::new(curve25519_dalek::Scalar::ONE),
+            threshold: 2,
+            num_shares: 2,
+        })
+        .unwrap();
+        let refresh = serde_json::to_value(ServiceRequest::Refresh {
+            context: OperationContext::new("k", 1, "op"),
+            shares,
+            threshold: 2,
+        })
+        .unwrap();
+        let error = serde_json::to_value(ServiceResponse::Error {
+            code: "ZKT-E005".into(),
+            message: "m".into(),
+        })
+        .unwrap();
+
+        let described = described_keys(description.artifact("ServiceRequest").unwrap());
+        for keys in [object_keys(&deal["Deal"]), object_keys(&refresh["Refresh"])] {
+            assert!(keys.iter().all(|k| described.contains(k)));
+        }
+        assert_eq!(
+            object_keys(&error["Error"]),
+            described_keys(description.artifact("ServiceResponse").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_signature_layout_matches_description() {
+        use crate::threshold_sig::{NonceCommitment, PartialSignature, SchnorrSignature};
+        use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+        use curve25519_dalek::Scalar;
+
+        let description = describe();
+        let cases = [
+            (
+                "NonceCommitment",
+                serde_json::to_value(NonceCommitment {
+                    index: 1,
+                    commitment: RISTRETTO_BASEPOINT_POINT,
+                }),
+            ),
+            (
+                "PartialSignature",
+                serde_json::to_value(PartialSignature {
+                    index: 1,
+                    response: Scalar::ONE,
+                }),
+            ),
+            (
+                "SigningCommitments",
+                serde_json::to_value(crate::frost::SigningCommitments {
+                    index: 1,
+                    hiding: RISTRETTO_BASEPOINT_POINT,
+                    binding: RISTRETTO_BASEPOINT_POINT,
+                }),
+            ),
+            (
+                "SignatureShare",
+                serde_json::to_value(crate::frost::SignatureShare {
+                    index: 1,
+                    response: Scalar::ONE,
+                }),
+            ),
+            (
+                "PublicKeyPackage",
+                serde_json::to_value(crate::frost::PublicKeyPackage {
+                    group_public_key: RISTRETTO_BASEPOINT_POINT,
+                    verification_shares: vec![],
+                }),
+            ),
+            (
+                "FrostPublicKeyPackage",
+                serde_json::to_value(crate::interop::frost::FrostPublicKeyPackage {
+                    header: Default::default(),
+                    verifying_shares: vec![(Scalar::ONE, RISTRETTO_BASEPOINT_POINT)],
+                    verifying_key: RISTRETTO_BASEPOINT_POINT,
+                    min_signers: Some(1),
+                }),
+            ),
+            (
+                "FrostHeader",
+                serde_json::to_value(crate::interop::frost::FrostHeader::default()),
+            ),
+            (
+                "SchnorrSignature",
+                serde_json::to_value(SchnorrSignature {
+                    commitment: RISTRETTO_BASEPOINT_POINT,
+                    response: Scalar::ONE,
+                }),
+            ),
+        ];
+        for (name, json) in cases {
+            assert_eq!(
+                object_keys(&json.unwrap()),
+                described_keys(description.artifact(name).unwrap())
+            );
+        }
+        {
+            use crate::ed25519;
+            use curve25519_dalek::constants::ED25519_BASEPOINT_POINT as B;
+
+            let commitments = ed25519::SigningCommitments {
+                index: 1,
+                hiding: B,
+                binding: B,
+            };
+            let cases = [
+                (
+                    "Ed25519SigningCommitments",
+                    serde_json::to_value(commitments),
+                ),
+                (
+                    "Ed25519SigningPackage",
+                    serde_json::to_value(
+                        ed25519::SigningPackage::new(b"m", vec![commitments]).unwrap(),
+                    ),
+                ),
+                (
+                    "Ed25519SignatureShare",
+                    serde_json::to_value(ed25519::SignatureShare {
+                        index: 1,
+                        response: Scalar::ONE,
+                    }),
+                ),
+                (
+                    "Ed25519PublicKeyPackage",
+                    serde_json::to_value(ed25519::PublicKeyPackage {
+                        group_public_key: B,
+                        verification_shares: vec![],
+                    }),
+                ),
+                (
+                    "Ed25519VerificationShare",
+                    serde_json::to_value(ed25519::VerificationShare { index: 1, point: B }),
+                ),
+            ];
+            for (name, json) in cases {
+                assert_eq!(
+                    object_keys(&json.unwrap()),
+                    described_keys(description.artifact(name).unwrap())
+                );
+            }
+        }
+        #[cfg(feature = "secp256k1")]
+        {
+            use crate::bip340;
+            use crate::group::secp256k1::{Point, Scalar};
+
+            let point = Point::generator();
+            let commitments = bip340::SigningCommitments {
+                index: 1,
+                hiding: point,
+                binding: point,
+            };
+            let cases = [
+                (
+                    "Bip340SigningCommitments",
+                    serde_json::to_value(commitments),
+                ),
+                (
+                    "Bip340SigningPackage",
+                    serde_json::to_value(
+                        bip340::SigningPackage::new(b"m", vec![commitments]).unwrap(),
+                    ),
+                ),
+                (
+                    "Bip340SignatureShare",
+                    serde_json::to_value(bip340::SignatureShare {
+                        index: 1,
+                        response: Scalar::ONE,
+                    }),
+                ),
+                (
+                    "Bip340PublicKeyPackage",
+                    serde_json::to_value(bip340::PublicKeyPackage {
+                        group_public_key: point,
+                        verification_shares: vec![],
+                    }),
+                ),
+                (
+                    "Bip340VerificationShare",
+                    serde_json::to_value(bip340::VerificationShare { index: 1, point }),
+                ),
+            ];
+            for (name, json) in cases {
+                assert_eq!(
+                    object_keys(&json.unwrap()),
+                    described_keys(description.artifact(name).unwrap())
+                );
+            }
+        }
+        #[cfg(feature = "dealer")]
+        {
+            let shares = crate::sharing::generate_key_shares(Scalar::ONE, 1, 1).unwrap();
+            let package = crate::interop::frost::FrostKeyPackage::from_share(
+                &shares[0],
+                RISTRETTO_BASEPOINT_POINT,
+                1,
+            )
+            .unwrap();
+            assert_eq!(
+                object_keys(&serde_json::to_value(&package).unwrap()),
+                described_keys(description.artifact("FrostKeyPackage").unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_error_layout_matches_description() {
+        use crate::error::VerifyError;
+
+        let description = describe();
+        let artifact = description.artifact("VerifyError").unwrap();
+        let Layout::TaggedEnum { variants } = &artifact.layout else {
+            panic!("VerifyError is a tagged enum");
+        };
+        let described = described_keys(artifact);
+        let errors = [
+            VerifyError::ChallengeMismatch { index: 1 },
+            VerifyError::InvalidResponse { index: 1 },
+            VerifyError::OpeningMismatch { index: 1 },
+            VerifyError::CommitmentMismatch { index: 1 },
+            VerifyError::NonCanonicalEncoding {
+                field: "share".to_string(),
+                reason: "bad hex".to_string(),
+            },
+            VerifyError::WrongContext {
+                index: 0,
+                details: "reserved index".to_string(),
+            },
+            VerifyError::StaleEpoch {
+                index: 1,
+                epoch: 0,
+                current_epoch: 1,
+            },
+            VerifyError::RefreshMismatch { coefficient: 0 },
+        ];
+        assert_eq!(errors.len(), variants.len());
+        for (error, variant) in errors.iter().zip(variants) {
+            let json = serde_json::to_value(error).unwrap();
+            assert_eq!(object_keys(&json), vec![variant.to_string()]);
+            assert!(object_keys(&json[*variant])
+                .iter()
+                .all(|k| described.contains(k)));
+        }
+    }
+
+    #[cfg(feature = "ct-harness")]
+    #[test]
+    fn test_harness_report_layout_matches_description() {
+        let description = describe();
+        let report = crate::ct_harness::HarnessReport {
+            version: crate::ct_harness::HARNESS_REPORT_VERSION,
+            threshold: crate::ct_harness::DEFAULT_T_THRESHOLD,
+            optimized: false,
+            targets: vec![crate::ct_harness::TimingReport {
+                target: "recovery".to_string(),
+                samples: 1,
+                fixed_mean_ns: 1.0,
+                random_mean_ns: 1.0,
+                max_t: 0.0,
+                leak_suspected: false,
+            }],
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            object_keys(&json),
+            described_keys(description.artifact("HarnessReport").unwrap())
+        );
+        assert_eq!(
+            object_keys(&json["targets"][0]),
+            described_keys(description.artifact("TimingReport").unwrap())
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! **frost module**
+//!
+//! FROST two-round threshold Schnorr signatures over Ristretto, signed directly from
+//! [`ShareData`] slices.
+//!
+//! Round one: every participant draws a hiding and a binding nonce `(d_i, e_i)` and publishes
+//! `(D_i, E_i) = (d_i·G, e_i·G)` ([`commit`]). The coordinator collects the commitments of the
+//! signing set into a [`SigningPackage`] together with the message.
+//!
+//! Round two: every participant derives the binding factor `ρ_i = H(Y, m, B, i)` over the whole
+//! commitment list `B`, the group commitment `R = Σ (D_j + ρ_j·E_j)` and the challenge
+//! `c = H(R, Y, m)`, and returns `z_i = d_i + ρ_i·e_i + λ_i·s_i·c` ([`sign`]). The coordinator
+//! checks each [`SignatureShare`] against the participant's verification share and sums them into
+//! a [`SchnorrSignature`] ([`aggregate`]).
+//!
+//! Binding every nonce to the message and the full commitment list is what allows concurrent
+//! signing sessions, unlike the plain scheme in [`crate::threshold_sig`]. The resulting signature
+//! has the same form and verifies with [`SchnorrSignature::verify`].
+//!
+//! Signers sign their round messages with their [`Participant`](crate::identity::Participant)
+//! identity when they travel through the coordinator; [`SigningPackage::from_signed`] and
+//! [`aggregate_signed`] accept only commitments and signature shares signed by their signer for
+//! the signing session.
+//!
+//! The rounds are those of [`ciphersuite`], instantiated with [`Ristretto255Blake3`]; the
+//! Ed25519 and BIP-340 modules instantiate the same core. All round messages are serializable so
+//! participants can run over a network.
+
+pub mod ciphersuite;
+
+#[cfg(feature = "dealer")]
+use crate::error::CryptoError;
+use crate::error::CryptoResult;
+use crate::group::Ristretto255;
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Roster, Signed};
+#[cfg(feature = "dealer")]
+use crate::key_lifecycle::Key;
+use crate::sharing::ShareData;
+use crate::threshold_sig::{self, SchnorrSignature};
+use ciphersuite::Ciphersuite;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+const NONCE_CONTEXT: &str = "zk-thresh-pro 2024 frost nonce v1";
+const MESSAGE_CONTEXT: &str = "zk-thresh-pro 2024 frost message v1";
+const COMMITMENT_LIST_CONTEXT: &str = "zk-thresh-pro 2024 frost commitment list v1";
+const BINDING_CONTEXT: &str = "zk-thresh-pro 2024 frost binding factor v1";
+
+/// FROST over ristretto255 with BLAKE3 under fixed derive-key contexts, signing
+/// [`SchnorrSignature`]s for the group public key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ristretto255Blake3;
+
+fn blake3_wide(context: &str, parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Blake3Adapter::new_derive_key(context);
+    for part in parts {
+        digest::Update::update(&mut hasher, part);
+    }
+    let mut hash = vec![0u8; 64];
+    hasher.finalize_extended(&mut hash);
+    hash
+}
+
+fn blake3_scalar(context: &str, parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Blake3Adapter::new_derive_key(context);
+    for part in parts {
+        digest::Update::update(&mut hasher, part);
+    }
+    Scalar::from_hash(hasher)
+}
+
+impl Ciphersuite for Ristretto255Blake3 {
+    type Group = Ristretto255;
+    type Key = RistrettoPoint;
+    type Signature = SchnorrSignature;
+
+    const NAME: &'static str = "frost";
+
+    fn key_bytes(key: &RistrettoPoint) -> Vec<u8> {
+        key.compress().to_bytes().to_vec()
+    }
+
+    fn identifier(index: usize) -> Vec<u8> {
+        (index as u64).to_le_bytes().to_vec()
+    }
+
+    fn nonce(randomness: &[u8; 32], secret: &Scalar) -> Scalar {
+        blake3_scalar(NONCE_CONTEXT, &[randomness, secret.as_bytes()])
+    }
+
+    fn hash_message(message: &[u8]) -> Vec<u8> {
+        blake3_wide(MESSAGE_CONTEXT, &[message])
+    }
+
+    fn hash_commitment_list(list: &[u8]) -> Vec<u8> {
+        blake3_wide(COMMITMENT_LIST_CONTEXT, &[list])
+    }
+
+    fn binding_factor(
+        key: &[u8],
+        message_hash: &[u8],
+        list_hash: &[u8],
+        identifier: &[u8],
+    ) -> Scalar {
+        blake3_scalar(BINDING_CONTEXT, &[key, message_hash, list_hash, identifier])
+    }
+
+    fn challenge(commitment: &RistrettoPoint, key: &RistrettoPoint, message: &[u8]) -> Scalar {
+        threshold_sig::challenge(commitment, key, message)
+    }
+
+    fn signature(commitment: &RistrettoPoint, response: Scalar) -> SchnorrSignature {
+        SchnorrSignature {
+            commitment: *commitment,
+            response,
+        }
+    }
+
+    fn verify(signature: &SchnorrSignature, key: &RistrettoPoint, message: &[u8]) -> bool {
+        signature.verify(key, message)
+    }
+}
+
+impl ciphersuite::SigningShare<Ristretto255> for ShareData {
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn signing_share(&self) -> Scalar {
+        self.share
+    }
+}
+
+/// Round-one message: the public nonce commitments of one participant.
+pub type SigningCommitments = ciphersuite::SigningCommitments<Ristretto255Blake3>;
+/// Secret nonces behind a participant's [`SigningCommitments`]; consumed by [`sign`].
+#[cfg(feature = "dealer")]
+pub type SigningNonces = ciphersuite::SigningNonces<Ristretto255Blake3>;
+/// Coordinator message opening round two.
+pub type SigningPackage = ciphersuite::SigningPackage<Ristretto255Blake3>;
+/// Round-two message: one participant's signature share `z_i`.
+pub type SignatureShare = ciphersuite::SignatureShare<Ristretto255Blake3>;
+/// Group public key and verification shares, for the coordinator.
+pub type PublicKeyPackage = ciphersuite::PublicKeyPackage<Ristretto255Blake3>;
+/// Verification share `Y_i = s_i·G` of one participant.
+pub type VerificationShare = ciphersuite::VerificationShare<Ristretto255Blake3>;
+
+impl PublicKeyPackage {
+    /// Dealer-side package for `key` dealt into `shares`.
+    ///
+    /// Returns `CryptoError::Validation` if the slices do not interpolate to the key's public key.
+    #[cfg(feature = "dealer")]
+    pub fn from_key(key: &Key, shares: &[ShareData]) -> CryptoResult<Self> {
+        let package = Self::from_signing_shares(shares)?;
+        if package.group_public_key != key.public_key() {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: "slices do not interpolate to the key's public key".to_string(),
+            });
+        }
+        Ok(package)
+    }
+}
+
+/// Round one: draw hiding and binding nonces for `share`.
+///
+/// Each nonce hashes fresh randomness together with the slice, so a weak generator alone does not
+/// leak the slice. Returns `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn commit(share: &ShareData) -> CryptoResult<(SigningNonces, SigningCommitments)> {
+    ciphersuite::commit(share)
+}
+
+/// Round two: the signature share of `share` for `package`; see [`ciphersuite::sign`].
+#[cfg(feature = "dealer")]
+pub fn sign(
+    share: &ShareData,
+    nonces: SigningNonces,
+    package: &SigningPackage,
+    group_public_key: &RistrettoPoint,
+) -> CryptoResult<SignatureShare> {
+    ciphersuite::sign(share, nonces, package, group_public_key)
+}
+
+/// Check one signature share: `z_i·G = D_i + ρ_i·E_i + c·λ_i·Y_i`.
+pub fn verify_signature_share(
+    signature_share: &SignatureShare,
+    verification_share: &RistrettoPoint,
+    package: &SigningPackage,
+    group_public_key: &RistrettoPoint,
+) -> bool {
+    ciphersuite::verify_signature_share(
+        signature_share,
+        verification_share,
+        package,
+        group_public_key,
+    )
+}
+
+/// Coordinator: check every signature share and combine them into the group signature; see
+/// [`ciphersuite::aggregate`].
+pub fn aggregate(
+    package: &SigningPackage,
+    signature_shares: &[SignatureShare],
+    public_keys: &PublicKeyPackage,
+) -> CryptoResult<SchnorrSignature> {
+    ciphersuite::aggregate(
+        package,
+        signature_shares,
+        public_keys,
+        &public_keys.group_public_key,
+    )
+}
+
+/// [`aggregate`] of signature shares signed by their signers; see
+/// [`ciphersuite::aggregate_signed`].
+pub fn aggregate_signed(
+    package: &SigningPackage,
+    signature_shares: &[Signed<SignatureShare>],
+    roster: &Roster,
+    session: &str,
+    public_keys: &PublicKeyPackage,
+) -> CryptoResult<SchnorrSignature> {
+    ciphersuite::aggregate_signed(
+        package,
+        signature_shares,
+        roster,
+        session,
+        public_keys,
+        &public_keys.group_public_key,
+    )
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+
+    fn setup(threshold: usize, n: usize) -> (Key, Vec<ShareData>, PublicKeyPackage) {
+        let key = Key::new(Scalar::from(777u64));
+        let shares = generate_key_shares(key.secret, threshold, n).unwrap();
+        let public_keys = PublicKeyPackage::from_key(&key, &shares).unwrap();
+        (key, shares, public_keys)
+    }
+
+    /// Run both rounds over JSON, as participants on a network would.
+    fn run(
+        signers: &[&ShareData],
+        message: &[u8],
+        public_keys: &PublicKeyPackage,
+    ) -> (SigningPackage, Vec<SignatureShare>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            signers.iter().map(|s| commit(s).unwrap()).unzip();
+        let wire: Vec<String> = commitments
+            .iter()
+            .map(|c| serde_json::to_string(c).unwrap())
+            .collect();
+        let received = wire
+            .iter()
+            .rev()
+            .map(|w| serde_json::from_str(w).unwrap())
+            .collect();
+        let package = SigningPackage::new(message, received).unwrap();
+        let package_json = serde_json::to_string(&package).unwrap();
+
+        let shares = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, n)| {
+                let package: SigningPackage = serde_json::from_str(&package_json).unwrap();
+                let share = sign(s, n, &package, &public_keys.group_public_key).unwrap();
+                serde_json::from_str(&serde_json::to_string(&share).unwrap()).unwrap()
+            })
+            .collect();
+        (package, shares)
+    }
+
+    #[test]
+    fn test_frost_signing_round_trip() {
+        let (key, shares, public_keys) = setup(3, 5);
+        let message = b"rotate custodian 4";
+        let (package, signature_shares) =
+            run(&[&shares[4], &shares[0], &shares[2]], message, &public_keys);
+        assert_eq!(package.signers(), vec![1, 3, 5]);
+
+        for s in &signature_shares {
+            assert!(verify_signature_share(
+                s,
+                &public_keys.verification_share(s.index).unwrap(),
+                &package,
+                &key.public_key()
+            ));
+        }
+        let signature = aggregate(&package, &signature_shares, &public_keys).unwrap();
+        assert!(signature.verify(&key.public_key(), message));
+        assert!(!signature.verify(&key.public_key(), b"rotate custodian 5"));
+    }
+
+    #[test]
+    fn test_frost_signed_round_messages() {
+        use crate::identity::Participant;
+
+        let (key, shares, public_keys) = setup(2, 3);
+        let identities: Vec<Participant> = (1..=3)
+            .map(|i| Participant::from_seed(i, &[i as u8; 32]).unwrap())
+            .collect();
+        let roster =
+            Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>()).unwrap();
+        let (package, signature_shares) = run(&[&shares[0], &shares[1]], b"m", &public_keys);
+        let signed: Vec<Signed<SignatureShare>> = signature_shares
+            .iter()
+            .map(|s| identities[s.index - 1].sign("frost-1", *s).unwrap())
+            .collect();
+        let signature =
+            aggregate_signed(&package, &signed, &roster, "frost-1", &public_keys).unwrap();
+        assert!(signature.verify(&key.public_key(), b"m"));
+
+        assert!(matches!(
+            aggregate_signed(&package, &signed, &roster, "frost-2", &public_keys),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let injected = vec![
+            signed[0].clone(),
+            identities[2].sign("frost-1", signature_shares[1]).unwrap(),
+        ];
+        assert!(matches!(
+            aggregate_signed(&package, &injected, &roster, "frost-1", &public_keys),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // Signed commitments: the coordinator cannot substitute signer 2's commitments.
+        let (_, first) = commit(&shares[0]).unwrap();
+        let (_, second) = commit(&shares[1]).unwrap();
+        let signed = vec![
+            identities[0].sign("frost-1", first).unwrap(),
+            identities[1].sign("frost-1", second).unwrap(),
+        ];
+        let package = SigningPackage::from_signed(b"m", &signed, &roster, "frost-1").unwrap();
+        assert_eq!(package.commitments(), &[first, second]);
+        let (_, substitute) = commit(&shares[1]).unwrap();
+        let substituted = vec![
+            signed[0].clone(),
+            identities[0].sign("frost-1", substitute).unwrap(),
+        ];
+        assert!(matches!(
+            SigningPackage::from_signed(b"m", &substituted, &roster, "frost-1"),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(SigningPackage::from_signed(b"m", &signed, &roster, "frost-2").is_err());
+    }
+
+    #[test]
+    fn test_frost_identifies_misbehaving_signers() {
+        let (_, shares, public_keys) = setup(2, 3);
+        let (package, mut signature_shares) = run(&[&shares[0], &shares[1]], b"m", &public_keys);
+        signature_shares[1].response += Scalar::ONE;
+        match aggregate(&package, &signature_shares, &public_keys) {
+            Err(CryptoError::SecurityViolation { details }) => assert!(details.contains("[2]")),
+            other => panic!("expected culprit report, got {:?}", other),
+        }
+        assert!(matches!(
+            aggregate(&package, &signature_shares[..1], &public_keys),
+            Err(CryptoError::Validation { .. })
+        ));
+
+        // Below the threshold every share is individually well-formed but the sum is not a
+        // signature.
+        let (package, signature_shares) = run(&[&shares[2]], b"m", &public_keys);
+        assert!(matches!(
+            aggregate(&package, &signature_shares, &public_keys),
+            Err(CryptoError::CryptographicOperation { .. })
+        ));
+
+        // Nonces are bound to their own commitments.
+        let (nonces, _) = commit(&shares[0]).unwrap();
+        let (_, other) = commit(&shares[0]).unwrap();
+        let package = SigningPackage::new(b"m", vec![other]).unwrap();
+        assert!(sign(&shares[0], nonces, &package, &public_keys.group_public_key).is_err());
+    }
+}
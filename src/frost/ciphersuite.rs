@@ -0,0 +1,708 @@
+//! FROST written once over a [`Ciphersuite`]: a [`CurveGroup`], the hashes `H1` to `H5` of
+//! RFC 9591 and the encoding of the finished signature.
+//!
+//! [`crate::frost`] (ristretto255, BLAKE3), [`crate::ed25519`] (Ed25519, SHA-512) and
+//! `crate::bip340` (secp256k1, BIP-340) are instantiations: each defines its ciphersuite, names
+//! the round types below for it and wraps [`commit`], [`sign`] and [`aggregate`] with the slice
+//! and key types of its scheme. BIP-340's even-`y` rule enters through
+//! [`Ciphersuite::negates_shares`], [`Ciphersuite::tweak`] and
+//! [`Ciphersuite::check_group_commitment`], which the other suites leave at their defaults.
+//!
+//! Besides the crate's own slices, a [`SigningShare`] can come from [`deal`], the trusted-dealer
+//! key generation of RFC 9591 appendix C with Feldman commitments `a_j·G`, for groups the
+//! Pedersen sharing of [`crate::sharing`] does not cover.
+
+use crate::error::{CryptoError, CryptoResult, VerifyError};
+use crate::group::{encoding, eval, lagrange_at_zero, CurveGroup};
+use crate::identity::{Roster, Signed, SignedPayload};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Scalar of the group of `C`.
+pub type Scalar<C> = <<C as Ciphersuite>::Group as CurveGroup>::Scalar;
+/// Element of the group of `C`.
+pub type Element<C> = <<C as Ciphersuite>::Group as CurveGroup>::Element;
+
+/// The choices a FROST instantiation makes; see RFC 9591 section 6.
+pub trait Ciphersuite: Copy + fmt::Debug + PartialEq + Eq + Send + Sync + 'static {
+    /// Group the signature is computed in.
+    type Group: CurveGroup;
+    /// What a session signs for: the group public key, or a key derived from it.
+    type Key: Copy;
+    /// The aggregated signature.
+    type Signature;
+
+    /// Name of the scheme in error messages.
+    const NAME: &'static str;
+
+    /// Encoding of `key` in the binding factors.
+    fn key_bytes(key: &Self::Key) -> Vec<u8>;
+
+    /// Whether the signers negate their slices to sign for `key`.
+    fn negates_shares(_key: &Self::Key) -> bool {
+        false
+    }
+
+    /// The scalar `t` the coordinator adds to the summed responses as `c·t`.
+    fn tweak(_key: &Self::Key) -> Scalar<Self> {
+        Self::Group::scalar_from_u64(0)
+    }
+
+    /// Encoding of the identifier of slice `index` in the commitment list and binding factors.
+    fn identifier(index: usize) -> Vec<u8>;
+
+    /// `H3(randomness || secret)`, a nonce.
+    fn nonce(randomness: &[u8; 32], secret: &Scalar<Self>) -> Scalar<Self>;
+
+    /// `H4(message)`.
+    fn hash_message(message: &[u8]) -> Vec<u8>;
+
+    /// `H5` of the encoded commitment list.
+    fn hash_commitment_list(list: &[u8]) -> Vec<u8>;
+
+    /// `H1(key || H4(m) || H5(B) || identifier)`, the binding factor of one signer.
+    fn binding_factor(
+        key: &[u8],
+        message_hash: &[u8],
+        list_hash: &[u8],
+        identifier: &[u8],
+    ) -> Scalar<Self>;
+
+    /// Reject a group commitment `R` the signature cannot carry, and tell whether every nonce
+    /// enters negated.
+    fn check_group_commitment(_commitment: &Element<Self>) -> CryptoResult<bool> {
+        Ok(false)
+    }
+
+    /// `H2`, the challenge of `R` for `key` and `message`.
+    fn challenge(commitment: &Element<Self>, key: &Self::Key, message: &[u8]) -> Scalar<Self>;
+
+    /// The signature `(R, z)`.
+    fn signature(commitment: &Element<Self>, response: Scalar<Self>) -> Self::Signature;
+
+    /// Verify a signature as any verifier of the scheme does.
+    fn verify(signature: &Self::Signature, key: &Self::Key, message: &[u8]) -> bool;
+}
+
+/// A slice that can sign: its index and its share `s_i` of the signing key.
+pub trait SigningShare<G: CurveGroup> {
+    fn index(&self) -> usize;
+    fn signing_share(&self) -> G::Scalar;
+}
+
+/// A slice of [`deal`]: the share `s_i = f(i)`, checked against the dealer's commitments with
+/// [`KeyShare::verify`].
+///
+/// `Debug` output shows the index only; the share is zeroized on drop.
+#[derive(Clone)]
+pub struct KeyShare<G: CurveGroup> {
+    pub index: usize,
+    pub share: G::Scalar,
+}
+
+impl<G: CurveGroup> KeyShare<G> {
+    /// Feldman check `s_i·G = Σ C_j·i^j` against the commitments of the dealing.
+    pub fn verify(&self, commitments: &[G::Element]) -> Result<(), VerifyError> {
+        let index = self.index;
+        let expected = eval(commitments, G::scalar_from_u64(index as u64), G::identity());
+        if index == 0 || commitments.is_empty() || G::generator() * self.share != expected {
+            return Err(VerifyError::CommitmentMismatch { index });
+        }
+        Ok(())
+    }
+}
+
+impl<G: CurveGroup> SigningShare<G> for KeyShare<G> {
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn signing_share(&self) -> G::Scalar {
+        self.share
+    }
+}
+
+impl<G: CurveGroup> Drop for KeyShare<G> {
+    fn drop(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+impl<G: CurveGroup> ZeroizeOnDrop for KeyShare<G> {}
+
+impl<G: CurveGroup> fmt::Debug for KeyShare<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("group", &G::ID)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The slices of a [`deal`] and the commitments to its polynomial.
+pub type Dealing<G> = (Vec<KeyShare<G>>, Vec<<G as CurveGroup>::Element>);
+
+/// Trusted-dealer key generation: `n` slices of `secret` at indices `1..=n`, any `threshold` of
+/// which sign, and the commitments `a_j·G` to the polynomial, lowest degree first.
+///
+/// Returns `CryptoError::Validation` unless `1 <= threshold <= n` and
+/// `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn deal<G: CurveGroup>(
+    secret: G::Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<Dealing<G>> {
+    crate::sharing::validate_parameters(threshold, n)?;
+    let mut coefficients = vec![secret];
+    for _ in 1..threshold {
+        coefficients.push(crate::group::random_scalar::<G>()?);
+    }
+    let commitments = coefficients.iter().map(|a| G::generator() * *a).collect();
+    let zero = G::scalar_from_u64(0);
+    let shares = (1..=n)
+        .map(|index| KeyShare {
+            index,
+            share: eval(&coefficients, G::scalar_from_u64(index as u64), zero),
+        })
+        .collect();
+    coefficients.zeroize();
+    Ok((shares, commitments))
+}
+
+/// Round-one message: the public nonce commitments of one participant.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct SigningCommitments<C: Ciphersuite> {
+    /// Slice index of the participant.
+    pub index: usize,
+    /// Hiding commitment `D_i = d_i·G`.
+    #[serde(
+        serialize_with = "encoding::serialize_element::<C::Group, _>",
+        deserialize_with = "encoding::deserialize_element::<C::Group, _>"
+    )]
+    pub hiding: Element<C>,
+    /// Binding commitment `E_i = e_i·G`.
+    #[serde(
+        serialize_with = "encoding::serialize_element::<C::Group, _>",
+        deserialize_with = "encoding::deserialize_element::<C::Group, _>"
+    )]
+    pub binding: Element<C>,
+}
+
+impl<C: Ciphersuite> SignedPayload for SigningCommitments<C> {
+    const DOMAIN: &'static str = "frost signing commitments";
+}
+
+/// Secret nonces behind a participant's [`SigningCommitments`]; consumed by [`sign`].
+///
+/// `Debug` output shows the index only; the nonces are zeroized on drop.
+#[cfg(feature = "dealer")]
+pub struct SigningNonces<C: Ciphersuite> {
+    index: usize,
+    hiding: Scalar<C>,
+    binding: Scalar<C>,
+}
+
+#[cfg(feature = "dealer")]
+impl<C: Ciphersuite> SigningNonces<C> {
+    /// Slice index the nonces were drawn for.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The public commitments to these nonces.
+    pub fn commitments(&self) -> SigningCommitments<C> {
+        SigningCommitments {
+            index: self.index,
+            hiding: C::Group::generator() * self.hiding,
+            binding: C::Group::generator() * self.binding,
+        }
+    }
+}
+
+/// Nonces of fixed randomness, for the known-answer tests of RFC 9591 appendix E.
+#[cfg(all(test, feature = "dealer"))]
+impl<C: Ciphersuite> SigningNonces<C> {
+    pub(crate) fn from_randomness(
+        share: &impl SigningShare<C::Group>,
+        hiding: &[u8; 32],
+        binding: &[u8; 32],
+    ) -> Self {
+        let secret = share.signing_share();
+        Self {
+            index: share.index(),
+            hiding: C::nonce(hiding, &secret),
+            binding: C::nonce(binding, &secret),
+        }
+    }
+
+    pub(crate) fn scalars(&self) -> (Scalar<C>, Scalar<C>) {
+        (self.hiding, self.binding)
+    }
+}
+
+#[cfg(feature = "dealer")]
+impl<C: Ciphersuite> Drop for SigningNonces<C> {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+#[cfg(feature = "dealer")]
+impl<C: Ciphersuite> ZeroizeOnDrop for SigningNonces<C> {}
+
+#[cfg(feature = "dealer")]
+impl<C: Ciphersuite> fmt::Debug for SigningNonces<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningNonces")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Coordinator message opening round two: the message and the commitments of the signing set,
+/// ordered by slice index.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct SigningPackage<C: Ciphersuite> {
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    message: Vec<u8>,
+    commitments: Vec<SigningCommitments<C>>,
+}
+
+impl<C: Ciphersuite> SigningPackage<C> {
+    /// Build a package, ordering the commitments by slice index.
+    ///
+    /// Returns `CryptoError::Validation` for an empty signing set, a zero index or a duplicate
+    /// index.
+    pub fn new(message: &[u8], mut commitments: Vec<SigningCommitments<C>>) -> CryptoResult<Self> {
+        commitments.sort_by_key(|c| c.index);
+        let package = Self {
+            message: message.to_vec(),
+            commitments,
+        };
+        package.validate()?;
+        Ok(package)
+    }
+
+    /// [`Self::new`] from commitments each signed for `session` by its signer's identity in
+    /// `roster`, so a relaying coordinator cannot substitute a signer's commitments.
+    ///
+    /// Returns `CryptoError::SecurityViolation` for commitments of another session or not signed
+    /// by their signer.
+    pub fn from_signed(
+        message: &[u8],
+        commitments: &[Signed<SigningCommitments<C>>],
+        roster: &Roster,
+        session: &str,
+    ) -> CryptoResult<Self> {
+        let commitments = commitments
+            .iter()
+            .map(|c| c.verify_from(roster, session, c.payload.index).copied())
+            .collect::<CryptoResult<Vec<_>>>()?;
+        Self::new(message, commitments)
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Commitments of the signing set, ordered by slice index.
+    pub fn commitments(&self) -> &[SigningCommitments<C>] {
+        &self.commitments
+    }
+
+    /// Slice indices of the signing set, in order.
+    pub fn signers(&self) -> Vec<usize> {
+        self.commitments.iter().map(|c| c.index).collect()
+    }
+
+    /// Re-check a package received over the wire.
+    fn validate(&self) -> CryptoResult<()> {
+        let ordered = self.commitments.windows(2).all(|w| w[0].index < w[1].index);
+        if self.commitments.first().is_none_or(|c| c.index == 0) || !ordered {
+            return Err(CryptoError::Validation {
+                field: "commitments".to_string(),
+                reason: "commitments must be non-empty and ordered by strictly increasing \
+                         non-zero slice index"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Binding factors `ρ_i`, in commitment order.
+    pub(crate) fn binding_factors(&self, key: &C::Key) -> Vec<Scalar<C>> {
+        let message_hash = C::hash_message(&self.message);
+        let list: Vec<u8> = self
+            .commitments
+            .iter()
+            .flat_map(|c| {
+                [
+                    C::identifier(c.index),
+                    C::Group::element_to_bytes(&c.hiding),
+                    C::Group::element_to_bytes(&c.binding),
+                ]
+                .concat()
+            })
+            .collect();
+        let list_hash = C::hash_commitment_list(&list);
+        let key_bytes = C::key_bytes(key);
+        self.commitments
+            .iter()
+            .map(|c| {
+                C::binding_factor(
+                    &key_bytes,
+                    &message_hash,
+                    &list_hash,
+                    &C::identifier(c.index),
+                )
+            })
+            .collect()
+    }
+
+    /// Binding factors, Lagrange coefficients, group commitment and challenge of the session.
+    fn session(&self, key: &C::Key) -> CryptoResult<Session<C>> {
+        self.validate()?;
+        let binding_factors = self.binding_factors(key);
+        let lagrange = lagrange_at_zero::<C::Group>(&self.signers());
+        let group_commitment = self
+            .commitments
+            .iter()
+            .zip(&binding_factors)
+            .fold(C::Group::identity(), |sum, (c, rho)| {
+                sum + c.hiding + c.binding * *rho
+            });
+        let nonce_negated = C::check_group_commitment(&group_commitment)?;
+        Ok(Session {
+            challenge: C::challenge(&group_commitment, key, &self.message),
+            binding_factors,
+            lagrange,
+            group_commitment,
+            nonce_negated,
+        })
+    }
+
+    fn position(&self, index: usize) -> CryptoResult<usize> {
+        self.commitments
+            .iter()
+            .position(|c| c.index == index)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "commitments".to_string(),
+                reason: format!("signer {} is not part of the signing set", index),
+            })
+    }
+}
+
+/// Values shared by every participant of one signing session.
+struct Session<C: Ciphersuite> {
+    binding_factors: Vec<Scalar<C>>,
+    lagrange: Vec<Scalar<C>>,
+    group_commitment: Element<C>,
+    /// Whether every nonce enters negated.
+    nonce_negated: bool,
+    challenge: Scalar<C>,
+}
+
+/// Round-two message: one participant's signature share `z_i`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct SignatureShare<C: Ciphersuite> {
+    /// Slice index of the participant.
+    pub index: usize,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<C::Group, _>",
+        deserialize_with = "encoding::deserialize_scalar::<C::Group, _>"
+    )]
+    pub response: Scalar<C>,
+}
+
+impl<C: Ciphersuite> SignedPayload for SignatureShare<C> {
+    const DOMAIN: &'static str = "frost signature share";
+}
+
+/// Public material a coordinator needs: the group public key and every participant's
+/// verification share `Y_i = s_i·G`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct PublicKeyPackage<C: Ciphersuite> {
+    #[serde(
+        serialize_with = "encoding::serialize_element::<C::Group, _>",
+        deserialize_with = "encoding::deserialize_element::<C::Group, _>"
+    )]
+    pub group_public_key: Element<C>,
+    pub verification_shares: Vec<VerificationShare<C>>,
+}
+
+/// Verification share `Y_i = s_i·G` of one participant.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct VerificationShare<C: Ciphersuite> {
+    pub index: usize,
+    #[serde(
+        serialize_with = "encoding::serialize_element::<C::Group, _>",
+        deserialize_with = "encoding::deserialize_element::<C::Group, _>"
+    )]
+    pub point: Element<C>,
+}
+
+impl<C: Ciphersuite> PublicKeyPackage<C> {
+    /// Package for the slices of one dealing; `Y = Σ λ_i·Y_i` is interpolated from their
+    /// verification shares, so every slice given must belong to it and at least a threshold of
+    /// them must be passed.
+    ///
+    /// Returns `CryptoError::Validation` for no slices, a zero or repeated index, or an identity
+    /// group key.
+    pub fn from_signing_shares<S: SigningShare<C::Group>>(shares: &[S]) -> CryptoResult<Self> {
+        let indices: Vec<usize> = shares.iter().map(|s| s.index()).collect();
+        let distinct = indices
+            .iter()
+            .enumerate()
+            .all(|(k, i)| *i != 0 && !indices[..k].contains(i));
+        if indices.is_empty() || !distinct {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: "need at least one slice with distinct non-zero indices".to_string(),
+            });
+        }
+        let verification_shares: Vec<VerificationShare<C>> = shares
+            .iter()
+            .map(|s| VerificationShare {
+                index: s.index(),
+                point: C::Group::generator() * s.signing_share(),
+            })
+            .collect();
+        let group_public_key = lagrange_at_zero::<C::Group>(&indices)
+            .into_iter()
+            .zip(&verification_shares)
+            .fold(C::Group::identity(), |sum, (lambda, v)| {
+                sum + v.point * lambda
+            });
+        if group_public_key == C::Group::identity() {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: "slices interpolate to the identity".to_string(),
+            });
+        }
+        Ok(Self {
+            group_public_key,
+            verification_shares,
+        })
+    }
+
+    /// Verification share of `index`, if known.
+    pub fn verification_share(&self, index: usize) -> Option<Element<C>> {
+        self.verification_shares
+            .iter()
+            .find(|v| v.index == index)
+            .map(|v| v.point)
+    }
+}
+
+/// Round one: draw hiding and binding nonces `H3(random || s_i)` for `share`.
+///
+/// Hashing the slice in means a weak generator alone does not leak it. Returns
+/// `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn commit<C: Ciphersuite>(
+    share: &impl SigningShare<C::Group>,
+) -> CryptoResult<(SigningNonces<C>, SigningCommitments<C>)> {
+    let mut secret = share.signing_share();
+    let nonces = SigningNonces {
+        index: share.index(),
+        hiding: generate_nonce::<C>(&secret)?,
+        binding: generate_nonce::<C>(&secret)?,
+    };
+    secret.zeroize();
+    let commitments = nonces.commitments();
+    Ok((nonces, commitments))
+}
+
+#[cfg(feature = "dealer")]
+fn generate_nonce<C: Ciphersuite>(secret: &Scalar<C>) -> CryptoResult<Scalar<C>> {
+    let mut randomness = zeroize::Zeroizing::new([0u8; 32]);
+    crate::utils::fill_random(randomness.as_mut())?;
+    Ok(C::nonce(&randomness, secret))
+}
+
+/// Round two: the signature share of `share` for `package`, signing for `key`.
+///
+/// The nonces are consumed, so they can never sign twice. Fails with `CryptoError::Validation` if
+/// the package is malformed, does not include this participant, or carries commitments that do
+/// not match `nonces`.
+#[cfg(feature = "dealer")]
+pub fn sign<C: Ciphersuite>(
+    share: &impl SigningShare<C::Group>,
+    nonces: SigningNonces<C>,
+    package: &SigningPackage<C>,
+    key: &C::Key,
+) -> CryptoResult<SignatureShare<C>> {
+    let index = share.index();
+    if nonces.index != index {
+        return Err(CryptoError::Validation {
+            field: "nonces".to_string(),
+            reason: format!(
+                "nonces drawn for slice {} used with slice {}",
+                nonces.index, index
+            ),
+        });
+    }
+    let position = package.position(index)?;
+    if package.commitments[position] != nonces.commitments() {
+        return Err(CryptoError::Validation {
+            field: "commitments".to_string(),
+            reason: format!("commitments of signer {} do not match its nonces", index),
+        });
+    }
+    let session = package.session(key)?;
+    let mut nonce = negate_if(
+        nonces.hiding + nonces.binding * session.binding_factors[position],
+        session.nonce_negated,
+    );
+    let mut signing_share = negate_if(share.signing_share(), C::negates_shares(key));
+    let response = nonce + session.lagrange[position] * session.challenge * signing_share;
+    nonce.zeroize();
+    signing_share.zeroize();
+    Ok(SignatureShare { index, response })
+}
+
+fn negate_if<S: std::ops::Neg<Output = S>>(scalar: S, negate: bool) -> S {
+    if negate {
+        -scalar
+    } else {
+        scalar
+    }
+}
+
+/// Check one signature share: `z_i·G = ±(D_i + ρ_i·E_i) + λ_i·c·(±Y_i)`, the signs being those
+/// of [`Ciphersuite::check_group_commitment`] and [`Ciphersuite::negates_shares`].
+pub fn verify_signature_share<C: Ciphersuite>(
+    signature_share: &SignatureShare<C>,
+    verification_share: &Element<C>,
+    package: &SigningPackage<C>,
+    key: &C::Key,
+) -> bool {
+    let (Ok(position), Ok(session)) = (
+        package.position(signature_share.index),
+        package.session(key),
+    ) else {
+        return false;
+    };
+    share_is_valid(
+        signature_share,
+        verification_share,
+        package,
+        key,
+        &session,
+        position,
+    )
+}
+
+fn share_is_valid<C: Ciphersuite>(
+    signature_share: &SignatureShare<C>,
+    verification_share: &Element<C>,
+    package: &SigningPackage<C>,
+    key: &C::Key,
+    session: &Session<C>,
+    position: usize,
+) -> bool {
+    let c = &package.commitments[position];
+    let nonce_commitment = c.hiding + c.binding * session.binding_factors[position];
+    let nonce_commitment = if session.nonce_negated {
+        C::Group::identity() - nonce_commitment
+    } else {
+        nonce_commitment
+    };
+    let factor = negate_if(
+        session.lagrange[position] * session.challenge,
+        C::negates_shares(key),
+    );
+    C::Group::generator() * signature_share.response
+        == nonce_commitment + *verification_share * factor
+}
+
+/// Coordinator: check every signature share and combine them, with the tweak of `key`, into a
+/// signature for `key`.
+///
+/// Requires exactly one share per signer in `package`. Returns
+/// `CryptoError::SecurityViolation` naming the signers whose shares are invalid, or whose
+/// verification share is unknown, and `CryptoError::CryptographicOperation` if the combined
+/// signature does not verify (e.g. the signing set is below the threshold, or `key` does not
+/// belong to `public_keys`).
+pub fn aggregate<C: Ciphersuite>(
+    package: &SigningPackage<C>,
+    signature_shares: &[SignatureShare<C>],
+    public_keys: &PublicKeyPackage<C>,
+    key: &C::Key,
+) -> CryptoResult<C::Signature> {
+    let session = package.session(key)?;
+    let signers = package.signers();
+    let mut ordered = Vec::with_capacity(signers.len());
+    for &index in &signers {
+        let mut matching = signature_shares.iter().filter(|s| s.index == index);
+        match (matching.next(), matching.next()) {
+            (Some(share), None) => ordered.push(share),
+            _ => {
+                return Err(CryptoError::Validation {
+                    field: "signature_shares".to_string(),
+                    reason: format!("expected exactly one signature share from signer {}", index),
+                })
+            }
+        }
+    }
+    if signature_shares.len() != signers.len() {
+        return Err(CryptoError::Validation {
+            field: "signature_shares".to_string(),
+            reason: "signature shares from signers outside the signing set".to_string(),
+        });
+    }
+
+    let culprits: Vec<usize> = ordered
+        .iter()
+        .enumerate()
+        .filter(|(position, share)| {
+            public_keys
+                .verification_share(share.index)
+                .is_none_or(|y| !share_is_valid(share, &y, package, key, &session, *position))
+        })
+        .map(|(_, share)| share.index)
+        .collect();
+    if !culprits.is_empty() {
+        return Err(CryptoError::SecurityViolation {
+            details: format!("invalid signature shares from signers {:?}", culprits),
+        });
+    }
+
+    let response = ordered
+        .iter()
+        .fold(session.challenge * C::tweak(key), |sum, s| sum + s.response);
+    let signature = C::signature(&session.group_commitment, response);
+    if !C::verify(&signature, key, &package.message) {
+        return Err(CryptoError::CryptographicOperation {
+            operation: format!("{} signature aggregation", C::NAME),
+        });
+    }
+    Ok(signature)
+}
+
+/// [`aggregate`] of signature shares each signed for `session` by its signer's identity in
+/// `roster`.
+///
+/// Returns `CryptoError::SecurityViolation` for a share of another session or not signed by its
+/// signer, before any share is checked.
+pub fn aggregate_signed<C: Ciphersuite>(
+    package: &SigningPackage<C>,
+    signature_shares: &[Signed<SignatureShare<C>>],
+    roster: &Roster,
+    session: &str,
+    public_keys: &PublicKeyPackage<C>,
+    key: &C::Key,
+) -> CryptoResult<C::Signature> {
+    let signature_shares = signature_shares
+        .iter()
+        .map(|s| s.verify_from(roster, session, s.payload.index).copied())
+        .collect::<CryptoResult<Vec<_>>>()?;
+    aggregate(package, &signature_shares, public_keys, key)
+}
@@ -0,0 +1,459 @@
+//! **group module**
+//!
+//! A prime-order group abstraction, [`CurveGroup`], with its implementations, serde
+//! [`encoding`] and the polynomial helpers the generic protocols share. [`Ristretto255`] and
+//! [`Edwards25519`] are always available; further groups are added behind cargo features.
+//!
+//! Two families of protocols run over it. FROST signing ([`crate::frost::ciphersuite`]) is what
+//! the BIP-340 and Ed25519 signers run on. Pedersen sharing, its opening proofs and VSS take the
+//! group as a type parameter defaulting to [`Ristretto255`]: [`ShareData<G>`](crate::sharing::ShareData),
+//! [`Proof<G>`](crate::proof::Proof) and [`VssCommitments<G>`](crate::vss::VssCommitments), dealt
+//! with [`crate::sharing::generate_group_shares`] and checked with
+//! [`VssCommitments::verify_share`](crate::vss::VssCommitments::verify_share). Over ristretto255
+//! the generic path produces exactly the slices and proofs of the ristretto255 functions, which
+//! keep their precomputed fixed-base tables and hash-adapter variants; the enterprise layer, MPC
+//! and the wire formats of [`crate::format`] stay ristretto255-only.
+
+#[cfg(feature = "dealer")]
+use crate::error::CryptoResult;
+use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, RISTRETTO_BASEPOINT_POINT};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::traits::{Identity, IsIdentity, VartimeMultiscalarMul};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::LazyLock;
+use zeroize::Zeroize;
+
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+
+/// A prime-order group with its scalar field, as used by the threshold schemes of this crate.
+///
+/// Implementations must reject non-canonical encodings in `scalar_from_bytes` and
+/// `element_from_bytes`, so every value has exactly one accepted encoding, and
+/// `blinding_generator` must be a second generator whose discrete logarithm to `generator` is
+/// unknown (derived by hashing to the group), or Pedersen commitments are not binding.
+pub trait CurveGroup: Copy + Send + Sync + 'static {
+    /// Scalar of the group order.
+    type Scalar: Copy
+        + Eq
+        + fmt::Debug
+        + Zeroize
+        + Send
+        + Sync
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>
+        + Neg<Output = Self::Scalar>;
+    /// Group element.
+    type Element: Copy
+        + Eq
+        + fmt::Debug
+        + Send
+        + Sync
+        + Add<Output = Self::Element>
+        + Sub<Output = Self::Element>
+        + Mul<Self::Scalar, Output = Self::Element>;
+
+    /// Name of the group, recorded in every proof transcript.
+    const ID: &'static str;
+
+    /// `value` as a scalar.
+    fn scalar_from_u64(value: u64) -> Self::Scalar;
+
+    /// Multiplicative inverse; zero maps to zero.
+    fn invert(scalar: &Self::Scalar) -> Self::Scalar;
+
+    /// Reduce 64 uniform bytes to a scalar with negligible bias.
+    fn scalar_from_wide(bytes: &[u8; 64]) -> Self::Scalar;
+
+    /// Canonical encoding of `scalar`.
+    fn scalar_to_bytes(scalar: &Self::Scalar) -> Vec<u8>;
+
+    /// Decode a canonical scalar encoding.
+    fn scalar_from_bytes(bytes: &[u8]) -> Option<Self::Scalar>;
+
+    /// The standard generator `G`.
+    fn generator() -> Self::Element;
+
+    /// The Pedersen blinding generator `H`.
+    fn blinding_generator() -> Self::Element;
+
+    /// The neutral element.
+    fn identity() -> Self::Element;
+
+    /// Canonical encoding of `element`.
+    fn element_to_bytes(element: &Self::Element) -> Vec<u8>;
+
+    /// Decode a canonical element encoding.
+    fn element_from_bytes(bytes: &[u8]) -> Option<Self::Element>;
+
+    /// `Σ s_k·E_k` of public scalars and elements; may run in variable time.
+    fn vartime_multiscalar_mul(
+        scalars: &[Self::Scalar],
+        elements: &[Self::Element],
+    ) -> Self::Element {
+        scalars
+            .iter()
+            .zip(elements)
+            .fold(Self::identity(), |sum, (s, e)| sum + *e * *s)
+    }
+}
+
+/// ristretto255 with the crate's generators `G` and `H`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ristretto255;
+
+impl CurveGroup for Ristretto255 {
+    type Scalar = curve25519_dalek::Scalar;
+    type Element = RistrettoPoint;
+
+    const ID: &'static str = "ristretto255";
+
+    fn scalar_from_u64(value: u64) -> Self::Scalar {
+        curve25519_dalek::Scalar::from(value)
+    }
+
+    fn invert(scalar: &Self::Scalar) -> Self::Scalar {
+        scalar.invert()
+    }
+
+    fn scalar_from_wide(bytes: &[u8; 64]) -> Self::Scalar {
+        curve25519_dalek::Scalar::from_bytes_mod_order_wide(bytes)
+    }
+
+    fn scalar_to_bytes(scalar: &Self::Scalar) -> Vec<u8> {
+        scalar.to_bytes().to_vec()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8]) -> Option<Self::Scalar> {
+        Option::from(curve25519_dalek::Scalar::from_canonical_bytes(
+            bytes.try_into().ok()?,
+        ))
+    }
+
+    fn generator() -> Self::Element {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn blinding_generator() -> Self::Element {
+        *crate::utils::ANOTHER_POINT
+    }
+
+    fn identity() -> Self::Element {
+        RistrettoPoint::identity()
+    }
+
+    fn element_to_bytes(element: &Self::Element) -> Vec<u8> {
+        element.compress().to_bytes().to_vec()
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> Option<Self::Element> {
+        let point = CompressedRistretto::from_slice(bytes).ok()?.decompress()?;
+        (point.compress().as_bytes()[..] == *bytes).then_some(point)
+    }
+
+    fn vartime_multiscalar_mul(
+        scalars: &[Self::Scalar],
+        elements: &[Self::Element],
+    ) -> Self::Element {
+        RistrettoPoint::vartime_multiscalar_mul(scalars, elements)
+    }
+}
+
+/// The prime-order subgroup of edwards25519 with the RFC 8032 base point `B`, the group of
+/// FROST(Ed25519, SHA-512) in [`crate::ed25519`].
+///
+/// Only points of that subgroup decode: `element_from_bytes` goes through
+/// [`crate::interop::edwards_point`], which rejects small-order points (the identity included)
+/// and points with a torsion component. `H` is the first torsion-free point with a hashed
+/// encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edwards25519;
+
+static EDWARDS_BLINDING_GENERATOR: LazyLock<EdwardsPoint> = LazyLock::new(|| {
+    (0u64..)
+        .find_map(|counter| {
+            let mut hasher = crate::hash_adapter::Blake3Adapter::new_derive_key(
+                "zk-thresh-pro 2024 edwards25519 blinding generator v1",
+            );
+            digest::Update::update(&mut hasher, ED25519_BASEPOINT_POINT.compress().as_bytes());
+            digest::Update::update(&mut hasher, &counter.to_le_bytes());
+            let mut candidate = [0u8; 32];
+            hasher.finalize_extended(&mut candidate);
+            let point = CompressedEdwardsY(candidate)
+                .decompress()?
+                .mul_by_cofactor();
+            (!point.is_identity()).then_some(point)
+        })
+        .expect("half of all encodings decode")
+});
+
+impl CurveGroup for Edwards25519 {
+    type Scalar = curve25519_dalek::Scalar;
+    type Element = EdwardsPoint;
+
+    const ID: &'static str = "edwards25519";
+
+    fn scalar_from_u64(value: u64) -> Self::Scalar {
+        Ristretto255::scalar_from_u64(value)
+    }
+
+    fn invert(scalar: &Self::Scalar) -> Self::Scalar {
+        Ristretto255::invert(scalar)
+    }
+
+    fn scalar_from_wide(bytes: &[u8; 64]) -> Self::Scalar {
+        Ristretto255::scalar_from_wide(bytes)
+    }
+
+    fn scalar_to_bytes(scalar: &Self::Scalar) -> Vec<u8> {
+        Ristretto255::scalar_to_bytes(scalar)
+    }
+
+    fn scalar_from_bytes(bytes: &[u8]) -> Option<Self::Scalar> {
+        Ristretto255::scalar_from_bytes(bytes)
+    }
+
+    fn generator() -> Self::Element {
+        ED25519_BASEPOINT_POINT
+    }
+
+    fn blinding_generator() -> Self::Element {
+        *EDWARDS_BLINDING_GENERATOR
+    }
+
+    fn identity() -> Self::Element {
+        EdwardsPoint::identity()
+    }
+
+    fn element_to_bytes(element: &Self::Element) -> Vec<u8> {
+        element.compress().to_bytes().to_vec()
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> Option<Self::Element> {
+        crate::interop::edwards_point(bytes.try_into().ok()?).ok()
+    }
+
+    fn vartime_multiscalar_mul(
+        scalars: &[Self::Scalar],
+        elements: &[Self::Element],
+    ) -> Self::Element {
+        EdwardsPoint::vartime_multiscalar_mul(scalars, elements)
+    }
+}
+
+/// Serde helpers for scalars and elements of a [`CurveGroup`], for
+/// `serialize_with = "encoding::serialize_element::<G, _>"` and the like: lowercase hex of the
+/// canonical encoding in human-readable formats, raw bytes otherwise. Decoding rejects
+/// non-canonical encodings and, for elements, the identity. Over [`Ristretto255`] the encodings
+/// are those of [`crate::serialization`].
+pub mod encoding {
+    use super::CurveGroup;
+    use serde::de::{SeqAccess, Visitor};
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    /// Native bytes or a sequence of `u8`, as binary formats may encode either.
+    struct RawBytes;
+
+    impl<'de> Visitor<'de> for RawBytes {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("bytes")
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(64));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    fn deserialize_bytes<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        what: &str,
+    ) -> Result<Vec<u8>, D::Error> {
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_bytes(RawBytes);
+        }
+        let s = String::deserialize(deserializer)?;
+        if !crate::serialization::is_lower_hex(&s) {
+            return Err(serde::de::Error::custom(format!(
+                "Non-canonical {} encoding: expected lowercase hex",
+                what
+            )));
+        }
+        hex::decode(s).map_err(serde::de::Error::custom)
+    }
+
+    pub fn serialize_scalar<G: CurveGroup, S: Serializer>(
+        scalar: &G::Scalar,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&G::scalar_to_bytes(scalar), serializer)
+    }
+
+    pub fn deserialize_scalar<'de, G: CurveGroup, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<G::Scalar, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "scalar")?;
+        G::scalar_from_bytes(&bytes)
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid {} scalar encoding", G::ID)))
+    }
+
+    pub fn serialize_element<G: CurveGroup, S: Serializer>(
+        element: &G::Element,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&G::element_to_bytes(element), serializer)
+    }
+
+    pub fn deserialize_element<'de, G: CurveGroup, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<G::Element, D::Error> {
+        let bytes = deserialize_bytes(deserializer, "point")?;
+        match G::element_from_bytes(&bytes) {
+            Some(element) if element != G::identity() => Ok(element),
+            Some(_) => Err(serde::de::Error::custom("Invalid point: identity")),
+            None => Err(serde::de::Error::custom(format!(
+                "Invalid {} point encoding",
+                G::ID
+            ))),
+        }
+    }
+
+    /// An element serialized with [`serialize_element`], for sequences.
+    struct Element<G: CurveGroup>(G::Element);
+
+    impl<G: CurveGroup> Serialize for Element<G> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_element::<G, S>(&self.0, serializer)
+        }
+    }
+
+    impl<'de, G: CurveGroup> Deserialize<'de> for Element<G> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserialize_element::<G, D>(deserializer).map(Element)
+        }
+    }
+
+    pub fn serialize_elements<G: CurveGroup, S: Serializer>(
+        elements: &[G::Element],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+        for element in elements {
+            seq.serialize_element(&Element::<G>(*element))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize_elements<'de, G: CurveGroup, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<G::Element>, D::Error> {
+        let elements = Vec::<Element<G>>::deserialize(deserializer)?;
+        Ok(elements.into_iter().map(|e| e.0).collect())
+    }
+}
+
+/// `Σ c_j·x^j`, coefficients lowest degree first.
+pub(crate) fn eval<T: Copy + Add<Output = T> + Mul<S, Output = T>, S: Copy>(
+    coefficients: &[T],
+    x: S,
+    zero: T,
+) -> T {
+    coefficients.iter().rev().fold(zero, |acc, &c| acc * x + c)
+}
+
+/// Lagrange coefficients at zero of the distinct non-zero `indices`.
+pub(crate) fn lagrange_at_zero<G: CurveGroup>(indices: &[usize]) -> Vec<G::Scalar> {
+    indices
+        .iter()
+        .map(|&i| {
+            let xi = G::scalar_from_u64(i as u64);
+            let (numerator, denominator) = indices.iter().filter(|&&j| j != i).fold(
+                (G::scalar_from_u64(1), G::scalar_from_u64(1)),
+                |(n, d), &j| {
+                    let xj = G::scalar_from_u64(j as u64);
+                    (n * xj, d * (xj - xi))
+                },
+            );
+            numerator * G::invert(&denominator)
+        })
+        .collect()
+}
+
+/// A uniformly random scalar from 64 bytes of the OS generator.
+///
+/// Returns `CryptoError::InsufficientEntropy` if the generator fails.
+#[cfg(feature = "dealer")]
+pub fn random_scalar<G: CurveGroup>() -> CryptoResult<G::Scalar> {
+    let mut wide = zeroize::Zeroizing::new([0u8; 64]);
+    crate::utils::fill_random(wide.as_mut())?;
+    Ok(G::scalar_from_wide(&wide))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::Scalar;
+
+    fn interpolates<G: CurveGroup>() {
+        // f(x) = 5 + 2x + 3x², evaluated at 2, 4 and 7.
+        let coefficients = [5u64, 2, 3].map(G::scalar_from_u64);
+        let zero = G::scalar_from_u64(0);
+        let indices = [2usize, 4, 7];
+        let secret = lagrange_at_zero::<G>(&indices)
+            .into_iter()
+            .zip(indices)
+            .fold(zero, |acc, (lambda, i)| {
+                acc + lambda * eval(&coefficients, G::scalar_from_u64(i as u64), zero)
+            });
+        assert_eq!(secret, G::scalar_from_u64(5));
+
+        let element = G::generator() * G::scalar_from_u64(9);
+        let bytes = G::element_to_bytes(&element);
+        assert_eq!(G::element_from_bytes(&bytes), Some(element));
+        let scalar = G::scalar_from_u64(9);
+        assert_eq!(
+            G::scalar_from_bytes(&G::scalar_to_bytes(&scalar)),
+            Some(scalar)
+        );
+        assert_ne!(G::blinding_generator(), G::generator());
+    }
+
+    #[test]
+    fn test_curve_groups() {
+        interpolates::<Ristretto255>();
+        interpolates::<Edwards25519>();
+        assert_eq!(
+            Ristretto255::element_from_bytes(&[0xff; 32]),
+            None::<RistrettoPoint>
+        );
+        // Edwards elements with a torsion component are not elements of the prime-order group.
+        let torsion = ED25519_BASEPOINT_POINT + curve25519_dalek::constants::EIGHT_TORSION[1];
+        assert_eq!(
+            Edwards25519::element_from_bytes(torsion.compress().as_bytes()),
+            None
+        );
+        // Unreduced scalars have no accepted encoding.
+        assert_eq!(Ristretto255::scalar_from_bytes(&[0xff; 32]), None::<Scalar>);
+    }
+}
@@ -0,0 +1,728 @@
+//! **secp256k1**
+//!
+//! The curve of Bitcoin and Ethereum, `y² = x³ + 7` over `F_p` with `p = 2^256 - 2^32 - 977`, as a
+//! [`CurveGroup`] for the threshold BIP-340 signer of [`crate::bip340`]. No secp256k1 crate is a
+//! dependency, so the arithmetic lives here, like the field code of [`crate::interop`].
+//!
+//! Field elements and scalars are four 64-bit limbs, kept fully reduced by folding with the form
+//! `2^256 - c` of both moduli. Points use projective coordinates and the complete addition law of
+//! Renes, Costello and Batina (2016), so doubling, the identity and inverse points need no special
+//! cases; scalar multiplication doubles and adds at every bit and selects with masks, so secret
+//! scalars steer neither branches nor memory accesses. Inversions and square roots are fixed
+//! exponentiations.
+//!
+//! Elements encode as 33-byte SEC1 compressed points, with the single byte `0x00` for the
+//! identity, and scalars as 32 big-endian bytes, the encodings of Bitcoin. The blinding generator
+//! `H` is the first point with even `y` whose `x` is SHA-256 of [`BLINDING_GENERATOR_SEED`] and a
+//! big-endian counter, so nobody knows its discrete logarithm to `G`.
+
+use super::CurveGroup;
+use crate::hash_adapter::sha256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::LazyLock;
+use zeroize::Zeroize;
+
+/// Seed hashed into the `x` coordinate of the blinding generator `H`.
+pub const BLINDING_GENERATOR_SEED: &[u8] = b"zk-thresh-pro secp256k1 blinding generator";
+
+/// Length of a compressed non-identity point.
+pub const POINT_LENGTH: usize = 33;
+
+type Limbs = [u64; 4];
+
+/// A modulus `m = 2^256 - c` with `c < 2^130`; both secp256k1 moduli have this form.
+struct Modulus {
+    m: Limbs,
+    c: [u64; 3],
+}
+
+const P: Modulus = Modulus {
+    m: [0xFFFF_FFFE_FFFF_FC2F, u64::MAX, u64::MAX, u64::MAX],
+    c: [0x1_0000_03D1, 0, 0],
+};
+
+const N: Modulus = Modulus {
+    m: [
+        0xBFD2_5E8C_D036_4141,
+        0xBAAE_DCE6_AF48_A03B,
+        0xFFFF_FFFF_FFFF_FFFE,
+        u64::MAX,
+    ],
+    c: [0x402D_A173_2FC9_BEBF, 0x4551_2319_50B7_5FC4, 1],
+};
+
+/// `p - 2` and `n - 2`, the inversion exponents.
+const P_MINUS_2: Limbs = [0xFFFF_FFFE_FFFF_FC2D, u64::MAX, u64::MAX, u64::MAX];
+const N_MINUS_2: Limbs = [
+    0xBFD2_5E8C_D036_413F,
+    0xBAAE_DCE6_AF48_A03B,
+    0xFFFF_FFFF_FFFF_FFFE,
+    u64::MAX,
+];
+/// `(p + 1) / 4`: `p ≡ 3 (mod 4)`, so `a^((p+1)/4)` is a square root of every square `a`.
+const P_PLUS_1_OVER_4: Limbs = [
+    0xFFFF_FFFF_BFFF_FF0C,
+    u64::MAX,
+    u64::MAX,
+    0x3FFF_FFFF_FFFF_FFFF,
+];
+
+/// `a + b + carry` and the carry out.
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// `a - b - borrow` and the borrow out.
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// `a + b·c + carry` and the carry out.
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// `a` where `mask` is all ones, `b` where it is zero.
+fn select(mask: u64, a: &Limbs, b: &Limbs) -> Limbs {
+    std::array::from_fn(|i| (a[i] & mask) | (b[i] & !mask))
+}
+
+fn limbs_from_be(bytes: &[u8; 32]) -> Limbs {
+    std::array::from_fn(|i| u64::from_be_bytes(bytes[24 - 8 * i..32 - 8 * i].try_into().unwrap()))
+}
+
+fn limbs_to_be(limbs: &Limbs) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, limb) in limbs.iter().enumerate() {
+        bytes[24 - 8 * i..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+impl Modulus {
+    /// `x - m` if `carry·2^256 + x ≥ m`, otherwise `x`; for inputs below `2m`.
+    fn normalize(&self, x: Limbs, carry: u64) -> Limbs {
+        let mut difference = [0u64; 4];
+        let mut borrow = 0;
+        for (i, limb) in difference.iter_mut().enumerate() {
+            (*limb, borrow) = sbb(x[i], self.m[i], borrow);
+        }
+        let (_, borrow) = sbb(carry, 0, borrow);
+        select(0u64.wrapping_sub(borrow), &x, &difference)
+    }
+
+    fn is_canonical(&self, x: &Limbs) -> bool {
+        let mut borrow = 0;
+        for (limb, m) in x.iter().zip(&self.m) {
+            (_, borrow) = sbb(*limb, *m, borrow);
+        }
+        borrow == 1
+    }
+
+    fn add(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut sum = [0u64; 4];
+        let mut carry = 0;
+        for (i, limb) in sum.iter_mut().enumerate() {
+            (*limb, carry) = adc(a[i], b[i], carry);
+        }
+        self.normalize(sum, carry)
+    }
+
+    fn sub(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut difference = [0u64; 4];
+        let mut borrow = 0;
+        for (i, limb) in difference.iter_mut().enumerate() {
+            (*limb, borrow) = sbb(a[i], b[i], borrow);
+        }
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut carry = 0;
+        for (limb, m) in difference.iter_mut().zip(&self.m) {
+            (*limb, carry) = adc(*limb, m & mask, carry);
+        }
+        difference
+    }
+
+    fn mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut wide = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                (wide[i + j], carry) = mac(wide[i + j], a[i], b[j], carry);
+            }
+            wide[i + 4] = carry;
+        }
+        self.reduce(wide)
+    }
+
+    /// `wide mod m` for any 512-bit `wide`, little-endian limbs.
+    ///
+    /// Each pass replaces `hi·2^256 + lo` by `hi·c + lo`; with `c < 2^130` four passes always
+    /// leave a value below `2^256 < 2m`, and a fixed number of passes keeps the timing fixed.
+    fn reduce(&self, mut wide: [u64; 8]) -> Limbs {
+        for _ in 0..4 {
+            let mut next = [0u64; 8];
+            next[..4].copy_from_slice(&wide[..4]);
+            for i in 0..4 {
+                let mut carry = 0;
+                for j in 0..3 {
+                    (next[i + j], carry) = mac(next[i + j], wide[4 + i], self.c[j], carry);
+                }
+                for limb in next[i + 3..].iter_mut() {
+                    (*limb, carry) = adc(*limb, 0, carry);
+                }
+            }
+            wide = next;
+        }
+        self.normalize([wide[0], wide[1], wide[2], wide[3]], 0)
+    }
+
+    /// `a^exponent`; the exponent is public.
+    fn pow(&self, a: &Limbs, exponent: &Limbs) -> Limbs {
+        let mut result = [1, 0, 0, 0];
+        for bit in (0..256).rev() {
+            result = self.mul(&result, &result);
+            if (exponent[bit / 64] >> (bit % 64)) & 1 == 1 {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+}
+
+/// An element of `F_p`, fully reduced.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FieldElement(Limbs);
+
+impl FieldElement {
+    const ZERO: Self = Self([0; 4]);
+    const ONE: Self = Self([1, 0, 0, 0]);
+
+    fn from_u64(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = limbs_from_be(bytes);
+        P.is_canonical(&limbs).then_some(Self(limbs))
+    }
+
+    fn to_bytes(self) -> [u8; 32] {
+        limbs_to_be(&self.0)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self(P.add(&self.0, &other.0))
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        Self(P.sub(&self.0, &other.0))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self(P.mul(&self.0, &other.0))
+    }
+
+    fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    fn invert(&self) -> Self {
+        Self(P.pow(&self.0, &P_MINUS_2))
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        let root = Self(P.pow(&self.0, &P_PLUS_1_OVER_4));
+        (root.square() == *self).then_some(root)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    fn select(mask: u64, a: &Self, b: &Self) -> Self {
+        Self(select(mask, &a.0, &b.0))
+    }
+}
+
+/// The curve constant `b = 7`.
+fn curve_b() -> FieldElement {
+    FieldElement::from_u64(7)
+}
+
+/// An integer modulo the group order `n`.
+///
+/// Scalars encode as 32 big-endian bytes; `Debug` shows that encoding, as dalek's scalars do.
+#[derive(Clone, Copy, PartialEq, Eq, Zeroize)]
+pub struct Scalar([u64; 4]);
+
+impl Scalar {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub fn from_u64(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+
+    /// Decode a canonical encoding, below `n`.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = limbs_from_be(bytes);
+        N.is_canonical(&limbs).then_some(Self(limbs))
+    }
+
+    /// `bytes mod n`, as BIP-340 reduces its challenges.
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Self(N.normalize(limbs_from_be(bytes), 0))
+    }
+
+    /// `bytes mod n` for 64 big-endian bytes.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Self {
+        let high = limbs_from_be(bytes[..32].try_into().unwrap());
+        let low = limbs_from_be(bytes[32..].try_into().unwrap());
+        Self(N.reduce([
+            low[0], low[1], low[2], low[3], high[0], high[1], high[2], high[3],
+        ]))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        limbs_to_be(&self.0)
+    }
+
+    /// Multiplicative inverse; zero maps to zero.
+    pub fn invert(&self) -> Self {
+        Self(N.pow(&self.0, &N_MINUS_2))
+    }
+
+    fn bit(&self, i: usize) -> u64 {
+        (self.0[i / 64] >> (i % 64)) & 1
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+    fn add(self, other: Scalar) -> Scalar {
+        Scalar(N.add(&self.0, &other.0))
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+    fn sub(self, other: Scalar) -> Scalar {
+        Scalar(N.sub(&self.0, &other.0))
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+    fn mul(self, other: Scalar) -> Scalar {
+        Scalar(N.mul(&self.0, &other.0))
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+    fn neg(self) -> Scalar {
+        Scalar::ZERO - self
+    }
+}
+
+impl std::iter::Sum for Scalar {
+    fn sum<I: Iterator<Item = Scalar>>(iter: I) -> Scalar {
+        iter.fold(Scalar::ZERO, Add::add)
+    }
+}
+
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scalar({})", hex::encode(self.to_bytes()))
+    }
+}
+
+/// A point of secp256k1 in projective coordinates `(X : Y : Z)`, the identity being `(0 : 1 : 0)`.
+#[derive(Clone, Copy)]
+pub struct Point {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+}
+
+/// `G` of SEC 2.
+static GENERATOR: LazyLock<Point> = LazyLock::new(|| {
+    let x =
+        hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    Point::lift_x(&x.try_into().unwrap()).expect("G is on the curve")
+});
+
+static BLINDING_GENERATOR: LazyLock<Point> = LazyLock::new(|| {
+    (0u32..)
+        .find_map(|counter| {
+            let x = sha256::digest(&[BLINDING_GENERATOR_SEED, &counter.to_be_bytes()]);
+            Point::lift_x(&x)
+        })
+        .expect("half of all x coordinates are on the curve")
+});
+
+impl Point {
+    pub const IDENTITY: Self = Self {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ZERO,
+    };
+
+    /// The generator `G`.
+    pub fn generator() -> Self {
+        *GENERATOR
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z == FieldElement::ZERO
+    }
+
+    fn to_affine(self) -> Option<(FieldElement, FieldElement)> {
+        if self.is_identity() {
+            return None;
+        }
+        let z_inverse = self.z.invert();
+        Some((self.x.mul(&z_inverse), self.y.mul(&z_inverse)))
+    }
+
+    /// The point with x coordinate `x` and even `y` (BIP-340 `lift_x`), if there is one.
+    pub fn lift_x(x: &[u8; 32]) -> Option<Self> {
+        let x = FieldElement::from_bytes(x)?;
+        let y = x.square().mul(&x).add(&curve_b()).sqrt()?;
+        let y = if y.is_odd() { y.neg() } else { y };
+        Some(Self {
+            x,
+            y,
+            z: FieldElement::ONE,
+        })
+    }
+
+    /// The 32-byte x coordinate (BIP-340 `bytes(P)`), or `None` for the identity.
+    pub fn x_only(&self) -> Option<[u8; 32]> {
+        self.to_affine().map(|(x, _)| x.to_bytes())
+    }
+
+    /// Whether the point is not the identity and its `y` is even, as BIP-340 requires of keys
+    /// and nonce points.
+    pub fn has_even_y(&self) -> bool {
+        self.to_affine().is_some_and(|(_, y)| !y.is_odd())
+    }
+
+    /// SEC1 compressed encoding: `0x02` or `0x03` by the parity of `y`, then `x`; `0x00` alone
+    /// for the identity.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.to_affine() {
+            None => vec![0],
+            Some((x, y)) => {
+                let mut bytes = Vec::with_capacity(POINT_LENGTH);
+                bytes.push(if y.is_odd() { 3 } else { 2 });
+                bytes.extend_from_slice(&x.to_bytes());
+                bytes
+            }
+        }
+    }
+
+    /// Decode [`Self::to_bytes`], rejecting points off the curve and `x ≥ p`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(Self::IDENTITY),
+            [prefix @ (2 | 3), x @ ..] => {
+                let point = Self::lift_x(x.try_into().ok()?)?;
+                Some(if *prefix == 3 { -point } else { point })
+            }
+            _ => None,
+        }
+    }
+
+    /// Complete addition (Renes–Costello–Batina, algorithm 7 for `a = 0`).
+    fn add_complete(&self, other: &Self) -> Self {
+        let b3 = FieldElement::from_u64(21);
+        let xx = self.x.mul(&other.x);
+        let yy = self.y.mul(&other.y);
+        let zz = self.z.mul(&other.z);
+        let xy = self
+            .x
+            .add(&self.y)
+            .mul(&other.x.add(&other.y))
+            .sub(&xx.add(&yy));
+        let yz = self
+            .y
+            .add(&self.z)
+            .mul(&other.y.add(&other.z))
+            .sub(&yy.add(&zz));
+        let xz = self
+            .x
+            .add(&self.z)
+            .mul(&other.x.add(&other.z))
+            .sub(&xx.add(&zz));
+        let bzz3 = b3.mul(&zz);
+        let yy_minus = yy.sub(&bzz3);
+        let yy_plus = yy.add(&bzz3);
+        let xx3 = xx.add(&xx).add(&xx);
+        Self {
+            x: xy.mul(&yy_minus).sub(&b3.mul(&yz).mul(&xz)),
+            y: yy_plus.mul(&yy_minus).add(&b3.mul(&xx3).mul(&xz)),
+            z: yz.mul(&yy_plus).add(&xx3.mul(&xy)),
+        }
+    }
+
+    fn select(mask: u64, a: &Self, b: &Self) -> Self {
+        Self {
+            x: FieldElement::select(mask, &a.x, &b.x),
+            y: FieldElement::select(mask, &a.y, &b.y),
+            z: FieldElement::select(mask, &a.z, &b.z),
+        }
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x.mul(&other.z) == other.x.mul(&self.z) && self.y.mul(&other.z) == other.y.mul(&self.z)
+    }
+}
+
+impl Eq for Point {}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        self.add_complete(&other)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point {
+            y: self.y.neg(),
+            ..self
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        self + -other
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Point;
+    /// Double and add at every bit, keeping the sum by mask.
+    fn mul(self, scalar: Scalar) -> Point {
+        let mut accumulator = Point::IDENTITY;
+        for i in (0..256).rev() {
+            accumulator = accumulator.add_complete(&accumulator);
+            let sum = accumulator.add_complete(&self);
+            accumulator = Point::select(0u64.wrapping_sub(scalar.bit(i)), &sum, &accumulator);
+        }
+        accumulator
+    }
+}
+
+impl std::iter::Sum for Point {
+    fn sum<I: Iterator<Item = Point>>(iter: I) -> Point {
+        iter.fold(Point::IDENTITY, Add::add)
+    }
+}
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Point({})", hex::encode(self.to_bytes()))
+    }
+}
+
+/// Hex in human-readable formats, raw bytes otherwise, as the Ristretto types of
+/// [`crate::serialization`].
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if !deserializer.is_human_readable() {
+        return Vec::<u8>::deserialize(deserializer);
+    }
+    let s = String::deserialize(deserializer)?;
+    if !crate::serialization::is_lower_hex(&s) {
+        return Err(serde::de::Error::custom(
+            "Non-canonical secp256k1 encoding: expected lowercase hex",
+        ));
+    }
+    hex::decode(s).map_err(serde::de::Error::custom)
+}
+
+impl Serialize for Scalar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes(deserializer)?;
+        <&[u8; 32]>::try_from(&bytes[..])
+            .ok()
+            .and_then(Scalar::from_bytes)
+            .ok_or_else(|| serde::de::Error::custom("Invalid secp256k1 scalar encoding"))
+    }
+}
+
+impl Serialize for Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Point::from_bytes(&deserialize_bytes(deserializer)?)
+            .ok_or_else(|| serde::de::Error::custom("Invalid secp256k1 point encoding"))
+    }
+}
+
+/// secp256k1 with the SEC 2 generator `G` and the hashed blinding generator `H`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256k1;
+
+impl CurveGroup for Secp256k1 {
+    type Scalar = Scalar;
+    type Element = Point;
+
+    const ID: &'static str = "secp256k1";
+
+    fn scalar_from_u64(value: u64) -> Scalar {
+        Scalar::from_u64(value)
+    }
+
+    fn invert(scalar: &Scalar) -> Scalar {
+        scalar.invert()
+    }
+
+    fn scalar_from_wide(bytes: &[u8; 64]) -> Scalar {
+        Scalar::from_bytes_wide(bytes)
+    }
+
+    fn scalar_to_bytes(scalar: &Scalar) -> Vec<u8> {
+        scalar.to_bytes().to_vec()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8]) -> Option<Scalar> {
+        Scalar::from_bytes(bytes.try_into().ok()?)
+    }
+
+    fn generator() -> Point {
+        Point::generator()
+    }
+
+    fn blinding_generator() -> Point {
+        *BLINDING_GENERATOR
+    }
+
+    fn identity() -> Point {
+        Point::IDENTITY
+    }
+
+    fn element_to_bytes(element: &Point) -> Vec<u8> {
+        element.to_bytes()
+    }
+
+    fn element_from_bytes(bytes: &[u8]) -> Option<Point> {
+        Point::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(hex_x: &str) -> Point {
+        Point::lift_x(&hex::decode(hex_x).unwrap().try_into().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_secp256k1_arithmetic() {
+        let g = Point::generator();
+        // Known multiples of G.
+        assert_eq!(
+            (g * Scalar::from_u64(2)).x_only(),
+            point("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5").x_only()
+        );
+        assert_eq!(
+            hex::encode((g * Scalar::from_u64(3)).to_bytes()),
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"
+        );
+        assert_eq!(g + g, g * Scalar::from_u64(2));
+        assert_eq!(g - g, Point::IDENTITY);
+        assert_eq!(g + Point::IDENTITY, g);
+        // n·G is the identity: (n - 1)·G = -G.
+        assert_eq!(g * -Scalar::ONE, -g);
+        assert!((g * -Scalar::ONE + g).is_identity());
+
+        let a = Scalar::from_bytes_wide(&[0xab; 64]);
+        let b = Scalar::from_u64(0x1234_5678);
+        assert_eq!(a * a.invert(), Scalar::ONE);
+        assert_eq!(g * (a * b), (g * a) * b);
+        assert_eq!(g * (a + b), g * a + g * b);
+        assert_eq!(Scalar::from_bytes(&[0xff; 32]), None);
+        // 2^256 - 1 ≡ c - 1 (mod n).
+        assert_eq!(
+            Scalar::from_bytes_reduced(&[0xff; 32]),
+            Scalar([N.c[0], N.c[1], N.c[2], 0]) - Scalar::ONE
+        );
+
+        for p in [
+            g * a,
+            -(g * b),
+            Point::IDENTITY,
+            Secp256k1::blinding_generator(),
+        ] {
+            assert_eq!(Point::from_bytes(&p.to_bytes()), Some(p));
+        }
+        assert!(Secp256k1::blinding_generator().has_even_y());
+        assert_ne!(Secp256k1::blinding_generator(), g);
+        // x ≥ p, a truncated encoding and a bad prefix.
+        assert_eq!(
+            Point::from_bytes(&[[2u8].as_slice(), &[0xff; 32]].concat()),
+            None
+        );
+        assert_eq!(Point::from_bytes(&g.to_bytes()[..32]), None);
+        assert_eq!(
+            Point::from_bytes(&[[4u8].as_slice(), &g.to_bytes()[1..]].concat()),
+            None
+        );
+        let json = serde_json::to_string(&(g * a)).unwrap();
+        assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), g * a);
+        assert_eq!(
+            serde_json::from_str::<Scalar>(&serde_json::to_string(&a).unwrap()).unwrap(),
+            a
+        );
+    }
+
+    #[cfg(feature = "dealer")]
+    #[test]
+    fn test_trusted_dealer_over_secp256k1() {
+        let secret = Scalar::from_bytes_wide(&[7; 64]);
+        let (shares, commitments) =
+            crate::frost::ciphersuite::deal::<Secp256k1>(secret, 2, 3).unwrap();
+        for share in &shares {
+            assert!(share.verify(&commitments).is_ok());
+        }
+        let lagrange = super::super::lagrange_at_zero::<Secp256k1>(&[2, 3]);
+        let recovered = lagrange[0] * shares[1].share + lagrange[1] * shares[2].share;
+        assert_eq!(recovered, secret);
+    }
+}
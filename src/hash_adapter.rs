@@ -8,8 +8,23 @@
 //! - Preimage resistance: 2^256 security  
 //! - Cross-platform compatibility (Windows, Linux, macOS)
 //! - Hardware acceleration via SIMD instructions
-
-use blake3;
+//!
+//! Proof challenges and key derivation go through the [`HashAdapter`] trait, so that deployments
+//! required to use an approved hash can run on SHA-256 ([`Sha256Adapter`]) or SHA-3
+//! ([`Sha3Adapter`]) instead; [`HashFunction`] names the choice in an
+//! [`EnterpriseConfig`](crate::EnterpriseConfig). SHA-256 and the Keccak functions are
+//! implemented in the `sha256` and `keccak` submodules, and SHA-512 for Ed25519 in `sha512`.
+
+mod blake3_tree;
+mod keccak;
+pub(crate) mod sha256;
+pub(crate) mod sha512;
+
+use self::blake3_tree::ChainState;
+use crate::algorithms::AlgorithmId;
+use crate::error::{CryptoError, CryptoResult};
+use crate::events::{structured_log, EventCode};
+use curve25519_dalek::Scalar;
 use digest::{FixedOutput, HashMarker, OutputSizeUser, Reset, Update};
 use generic_array::GenericArray;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -18,31 +33,42 @@ use typenum::U64;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Security level configuration for different enterprise use cases
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SecurityLevel {
     /// Standard security for general enterprise use
     Standard,
     /// High security for sensitive operations
+    #[default]
     High,
-    /// Maximum security for critical infrastructure
+    /// Maximum security for critical infrastructure; secret recovery takes the constant-time path
     Maximum,
 }
 
-impl Default for SecurityLevel {
-    fn default() -> Self {
-        SecurityLevel::High
-    }
-}
-
 /// Enterprise-grade BLAKE3 hash adapter with enhanced security features
 ///
 /// Note: This struct implements custom serialization/deserialization
+///
+/// By default the adapter keeps the chaining values of the BLAKE3 tree instead of its input, so
+/// that a partially fed adapter can be checkpointed (serialized) and resumed elsewhere: the
+/// checkpoint holds about 32 bytes per doubling of the input and at most the last 64 input
+/// bytes. [`Blake3Adapter::into_streaming`] opts out of checkpoints for speed: input then goes
+/// straight into a live `blake3::Hasher` (SIMD, memory mapping), the digest is
+/// unchanged, and serializing the adapter fails. Files are hashed with
+/// [`Blake3Adapter::update_reader`] in either mode, or memory-mapped with
+/// [`Blake3Adapter::update_mmap`] in streaming mode.
 #[derive(Clone)]
 pub struct Blake3Adapter {
     hasher_state: HasherState,
     security_level: SecurityLevel,
+    /// Key words of the mode: the IV, the key or the context key.
+    key_words: [u32; 8],
+    /// Live hasher of the streaming mode, fed instead of `hasher_state.tree`.
+    live: Option<blake3::Hasher>,
 }
 
+/// Read size of [`Blake3Adapter::update_reader`].
+const READ_CHUNK: usize = 64 * 1024;
+
 /// Internal state for serialization
 #[derive(Clone, Serialize, Deserialize)]
 struct HasherState {
@@ -50,10 +76,14 @@ struct HasherState {
     key: Option<[u8; 32]>,
     /// Context for key derivation (if any)
     context: Option<String>,
-    /// Accumulated input data for serialization
+    /// Input of a version 1 checkpoint, absorbed into `tree` when loaded
+    #[serde(default, skip_serializing)]
     accumulated_data: Vec<u8>,
     /// Hash mode
     mode: HashMode,
+    /// Chaining values of the input absorbed so far
+    #[serde(default)]
+    tree: ChainState,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -63,20 +93,58 @@ enum HashMode {
     DeriveKey,
 }
 
+impl HasherState {
+    fn new(mode: HashMode, key: Option<[u8; 32]>, context: Option<String>) -> Self {
+        Self {
+            key,
+            context,
+            accumulated_data: Vec::new(),
+            mode,
+            tree: ChainState::default(),
+        }
+    }
+
+    /// Key words of the mode.
+    fn key_words(&self) -> [u32; 8] {
+        match &self.mode {
+            HashMode::Normal => blake3_tree::IV,
+            HashMode::Keyed => {
+                blake3_tree::words(self.key.as_ref().expect("Keyed mode requires key"))
+            }
+            HashMode::DeriveKey => blake3_tree::context_key(
+                self.context
+                    .as_ref()
+                    .expect("DeriveKey mode requires context"),
+            ),
+        }
+    }
+
+    /// Domain flags of the mode.
+    fn flags(&self) -> u8 {
+        match self.mode {
+            HashMode::Normal => 0,
+            HashMode::Keyed => blake3_tree::KEYED_HASH,
+            HashMode::DeriveKey => blake3_tree::DERIVE_KEY_MATERIAL,
+        }
+    }
+}
+
 impl Blake3Adapter {
-    /// Create a new BLAKE3 adapter with specified security level
-    pub fn new_with_security(level: SecurityLevel) -> Self {
+    fn from_state(hasher_state: HasherState, security_level: SecurityLevel) -> Self {
+        let key_words = hasher_state.key_words();
         Self {
-            hasher_state: HasherState {
-                key: None,
-                context: None,
-                accumulated_data: Vec::new(),
-                mode: HashMode::Normal,
-            },
-            security_level: level,
+            hasher_state,
+            security_level,
+            key_words,
+            live: None,
         }
     }
 
+    /// Create a new BLAKE3 adapter with specified security level
+    pub fn new_with_security(level: SecurityLevel) -> Self {
+        Self::from_state(HasherState::new(HashMode::Normal, None, None), level)
+    }
+
     /// Create a new BLAKE3 adapter with high security (enterprise default)
     pub fn new() -> Self {
         Self::new_with_security(SecurityLevel::High)
@@ -84,28 +152,18 @@ impl Blake3Adapter {
 
     /// Create a keyed BLAKE3 adapter for MAC operations
     pub fn new_keyed(key: &[u8; 32]) -> Self {
-        Self {
-            hasher_state: HasherState {
-                key: Some(*key),
-                context: None,
-                accumulated_data: Vec::new(),
-                mode: HashMode::Keyed,
-            },
-            security_level: SecurityLevel::High,
-        }
+        Self::from_state(
+            HasherState::new(HashMode::Keyed, Some(*key), None),
+            SecurityLevel::High,
+        )
     }
 
     /// Create a key derivation function (KDF) instance
     pub fn new_derive_key(context: &str) -> Self {
-        Self {
-            hasher_state: HasherState {
-                key: None,
-                context: Some(context.to_string()),
-                accumulated_data: Vec::new(),
-                mode: HashMode::DeriveKey,
-            },
-            security_level: SecurityLevel::High,
-        }
+        Self::from_state(
+            HasherState::new(HashMode::DeriveKey, None, Some(context.to_string())),
+            SecurityLevel::High,
+        )
     }
 
     /// Get the current security level
@@ -113,6 +171,72 @@ impl Blake3Adapter {
         self.security_level
     }
 
+    /// Switch to the streaming mode, before any input.
+    ///
+    /// The adapter can no longer be serialized. Returns `CryptoError::Validation` once input
+    /// has been fed: the chaining values cannot be carried into a `blake3::Hasher`.
+    pub fn into_streaming(mut self) -> CryptoResult<Self> {
+        if self.live.is_none() {
+            if !self.hasher_state.tree.is_empty() {
+                return Err(CryptoError::Validation {
+                    field: "mode".to_string(),
+                    reason: "the streaming mode must be chosen before any input".to_string(),
+                });
+            }
+            self.live = Some(self.create_hasher());
+        }
+        Ok(self)
+    }
+
+    /// Whether the adapter is in the streaming mode.
+    pub fn is_streaming(&self) -> bool {
+        self.live.is_some()
+    }
+
+    /// Feed everything `reader` yields, 64 KiB at a time.
+    ///
+    /// Returns `CryptoError::Serialization` if reading fails; the bytes read before the failure
+    /// have been fed.
+    pub fn update_reader<R: std::io::Read>(&mut self, mut reader: R) -> CryptoResult<()> {
+        let mut chunk = vec![0u8; READ_CHUNK];
+        let result = loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => break Ok(()),
+                Ok(read) => self.add_data(&chunk[..read]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    break Err(CryptoError::Serialization {
+                        details: format!("cannot read hash input: {}", e),
+                    })
+                }
+            }
+        };
+        chunk.zeroize();
+        result
+    }
+
+    /// Feed the contents of the file at `path`, memory-mapping it (small files are read).
+    ///
+    /// Returns `CryptoError::Validation` outside the streaming mode, where
+    /// [`Blake3Adapter::update_reader`] is the way to hash a file, and
+    /// `CryptoError::Serialization` if the file cannot be mapped or read. The file must not
+    /// change while it is hashed.
+    pub fn update_mmap(&mut self, path: &std::path::Path) -> CryptoResult<()> {
+        let Some(hasher) = &mut self.live else {
+            return Err(CryptoError::Validation {
+                field: "mode".to_string(),
+                reason: "memory-mapped hashing requires the streaming mode".to_string(),
+            });
+        };
+        let io_error = |e: std::io::Error| CryptoError::Serialization {
+            details: format!("cannot hash {}: {}", path.display(), e),
+        };
+        let length = std::fs::metadata(path).map_err(io_error)?.len();
+        hasher.update_mmap(path).map_err(io_error)?;
+        crate::op_counts::count(crate::op_counts::Op::HashByte, length as usize);
+        Ok(())
+    }
+
     /// Create the actual hasher based on stored state
     fn create_hasher(&self) -> blake3::Hasher {
         match &self.hasher_state.mode {
@@ -139,30 +263,35 @@ impl Blake3Adapter {
     /// Update with additional security context for audit trails
     pub fn update_with_context(&mut self, data: &[u8], context: &str) {
         // Add context for enterprise audit requirements
-        self.hasher_state
-            .accumulated_data
-            .extend_from_slice(context.as_bytes());
-        self.hasher_state.accumulated_data.push(0u8); // Separator
-        self.hasher_state.accumulated_data.extend_from_slice(data);
+        self.add_data(context.as_bytes());
+        self.add_data(&[0u8]); // Separator
+        self.add_data(data);
     }
 
     /// Finalize with extended output for enhanced security
     pub fn finalize_extended(&self, output: &mut [u8]) {
-        let mut hasher = self.create_hasher();
-        hasher.update(&self.hasher_state.accumulated_data);
-        let mut output_reader = hasher.finalize_xof();
-        output_reader.fill(output);
-    }
-
-    /// Get accumulated data for internal use
-    #[allow(dead_code)]
-    fn get_accumulated_data(&self) -> &[u8] {
-        &self.hasher_state.accumulated_data
+        match &self.live {
+            Some(hasher) => hasher.finalize_xof().fill(output),
+            None => {
+                self.hasher_state
+                    .tree
+                    .finalize(&self.key_words, self.hasher_state.flags(), output)
+            }
+        }
     }
 
-    /// Add data to accumulated buffer
+    /// Absorb data into the tree, or the live hasher in streaming mode
     fn add_data(&mut self, data: &[u8]) {
-        self.hasher_state.accumulated_data.extend_from_slice(data);
+        crate::op_counts::count(crate::op_counts::Op::HashByte, data.len());
+        match &mut self.live {
+            Some(hasher) => {
+                hasher.update(data);
+            }
+            None => {
+                let flags = self.hasher_state.flags();
+                self.hasher_state.tree.update(&self.key_words, flags, data)
+            }
+        }
     }
 }
 
@@ -177,6 +306,7 @@ impl fmt::Debug for Blake3Adapter {
         f.debug_struct("Blake3Adapter")
             .field("security_level", &self.security_level)
             .field("mode", &self.hasher_state.mode)
+            .field("streaming", &self.is_streaming())
             .finish()
     }
 }
@@ -201,12 +331,8 @@ impl FixedOutput for Blake3Adapter {
 
     /// Compute final hash with fixed 64-byte output
     fn finalize_fixed(self) -> GenericArray<u8, Self::OutputSize> {
-        let mut hasher = self.create_hasher();
-        hasher.update(&self.hasher_state.accumulated_data);
-
         let mut buf = [0u8; 64];
-        let mut output_reader = hasher.finalize_xof();
-        output_reader.fill(&mut buf);
+        self.finalize_extended(&mut buf);
         GenericArray::clone_from_slice(&buf)
     }
 }
@@ -214,7 +340,14 @@ impl FixedOutput for Blake3Adapter {
 impl Reset for Blake3Adapter {
     /// Reset hasher to initial state
     fn reset(&mut self) {
-        self.hasher_state.accumulated_data.clear();
+        self.hasher_state.tree.zeroize();
+        self.hasher_state.tree = ChainState::default();
+        if self.live.is_some() {
+            let fresh = self.create_hasher();
+            if let Some(hasher) = self.live.replace(fresh).as_mut() {
+                hasher.zeroize();
+            }
+        }
     }
 }
 
@@ -222,10 +355,14 @@ impl HashMarker for Blake3Adapter {}
 
 impl Zeroize for Blake3Adapter {
     fn zeroize(&mut self) {
-        self.hasher_state.accumulated_data.zeroize();
+        self.hasher_state.tree.zeroize();
         if let Some(ref mut key) = self.hasher_state.key {
             key.zeroize();
         }
+        self.key_words.zeroize();
+        if let Some(ref mut hasher) = self.live {
+            hasher.zeroize();
+        }
     }
 }
 
@@ -237,6 +374,11 @@ impl Serialize for Blake3Adapter {
     where
         S: Serializer,
     {
+        if self.live.is_some() {
+            return Err(serde::ser::Error::custom(
+                "a streaming Blake3Adapter holds no serializable state",
+            ));
+        }
         // 序列化内部状态
         let state = (&self.hasher_state, &self.security_level);
         state.serialize(serializer)
@@ -249,13 +391,200 @@ impl<'de> Deserialize<'de> for Blake3Adapter {
     where
         D: Deserializer<'de>,
     {
-        let (hasher_state, security_level): (HasherState, SecurityLevel) =
+        let (mut hasher_state, security_level): (HasherState, SecurityLevel) =
             Deserialize::deserialize(deserializer)?;
+        let missing = match hasher_state.mode {
+            HashMode::Normal => None,
+            HashMode::Keyed => hasher_state.key.is_none().then_some("key"),
+            HashMode::DeriveKey => hasher_state.context.is_none().then_some("context"),
+        };
+        if let Some(field) = missing {
+            return Err(serde::de::Error::missing_field(field));
+        }
+        let mut legacy_input = std::mem::take(&mut hasher_state.accumulated_data);
+        if !legacy_input.is_empty() && !hasher_state.tree.is_empty() {
+            legacy_input.zeroize();
+            return Err(serde::de::Error::custom(
+                "a checkpoint holds either input or chaining values",
+            ));
+        }
+        // A version 1 checkpoint carries its input, absorbed here once.
+        let mut adapter = Blake3Adapter::from_state(hasher_state, security_level);
+        adapter.add_data(&legacy_input);
+        legacy_input.zeroize();
+        Ok(adapter)
+    }
+}
 
-        Ok(Blake3Adapter {
-            hasher_state,
-            security_level,
-        })
+/// Hash function behind proof challenges and key derivation.
+///
+/// A slice proved with one adapter only verifies with the same one, so every party to a key
+/// must agree on it. Standalone verification ([`crate::vss`], [`crate::custodian`], share
+/// files and transcripts) always uses BLAKE3.
+pub trait HashAdapter: Send + Sync {
+    /// Registry identifier of the hash function.
+    fn algorithm(&self) -> AlgorithmId;
+
+    /// Fiat-Shamir challenge for `transcript`, reduced from 64 uniform bytes.
+    fn challenge(&self, transcript: &[u8]) -> Scalar;
+
+    /// Fill `out` with key material derived from `input` under the domain `context`.
+    ///
+    /// Returns `CryptoError::Validation` if the function cannot produce `out.len()` bytes.
+    fn derive_key(&self, context: &str, input: &[u8], out: &mut [u8]) -> CryptoResult<()>;
+}
+
+/// Domain of the SHA-256 and SHA-3 proof challenges.
+const CHALLENGE_DOMAIN: &str = "zk-thresh-pro 2024 proof challenge v1";
+
+impl HashAdapter for Blake3Adapter {
+    fn algorithm(&self) -> AlgorithmId {
+        AlgorithmId::Blake3
+    }
+
+    /// `Scalar::from_hash` of plain BLAKE3, whatever the adapter's own mode.
+    fn challenge(&self, transcript: &[u8]) -> Scalar {
+        let mut hasher = Blake3Adapter::new();
+        hasher.update(transcript);
+        Scalar::from_hash(hasher)
+    }
+
+    /// BLAKE3 `derive_key` with `context`, extended to `out.len()` bytes.
+    fn derive_key(&self, context: &str, input: &[u8], out: &mut [u8]) -> CryptoResult<()> {
+        let mut hasher = Blake3Adapter::new_derive_key(context);
+        hasher.update(input);
+        hasher.finalize_extended(out);
+        Ok(())
+    }
+}
+
+/// SHA-256 hash adapter: `expand_message_xmd` challenges (RFC 9380) and HKDF (RFC 5869).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha256Adapter;
+
+impl HashAdapter for Sha256Adapter {
+    fn algorithm(&self) -> AlgorithmId {
+        AlgorithmId::Sha256
+    }
+
+    fn challenge(&self, transcript: &[u8]) -> Scalar {
+        let mut wide = [0u8; 64];
+        sha256::expand_message_xmd(transcript, CHALLENGE_DOMAIN.as_bytes(), &mut wide);
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    /// HKDF-SHA256 with an empty salt and `context` as the info; at most 8160 bytes.
+    fn derive_key(&self, context: &str, input: &[u8], out: &mut [u8]) -> CryptoResult<()> {
+        if out.len() > sha256::HKDF_MAX_LENGTH {
+            return Err(CryptoError::Validation {
+                field: "len".to_string(),
+                reason: format!(
+                    "HKDF-SHA256 derives at most {} bytes, {} requested",
+                    sha256::HKDF_MAX_LENGTH,
+                    out.len()
+                ),
+            });
+        }
+        sha256::hkdf(&[], input, context.as_bytes(), out);
+        Ok(())
+    }
+}
+
+/// SHA-3 hash adapter: cSHAKE256 challenges and KMAC256 key derivation (SP 800-185, SP 800-108).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sha3Adapter;
+
+impl HashAdapter for Sha3Adapter {
+    fn algorithm(&self) -> AlgorithmId {
+        AlgorithmId::Sha3_256
+    }
+
+    fn challenge(&self, transcript: &[u8]) -> Scalar {
+        let mut wide = [0u8; 64];
+        keccak::cshake256_into(b"", CHALLENGE_DOMAIN.as_bytes(), transcript, &mut wide);
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    /// KMAC256 keyed with `input` over `context`, with customization `"KDF"`.
+    fn derive_key(&self, context: &str, input: &[u8], out: &mut [u8]) -> CryptoResult<()> {
+        keccak::kmac256(input, &[context.as_bytes()], b"KDF", out);
+        Ok(())
+    }
+}
+
+/// Hash function selectable in an [`EnterpriseConfig`](crate::EnterpriseConfig).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashFunction {
+    /// [`Blake3Adapter`]
+    #[default]
+    Blake3,
+    /// [`Sha256Adapter`]
+    Sha256,
+    /// [`Sha3Adapter`]
+    Sha3_256,
+}
+
+impl HashFunction {
+    /// The adapter implementing the function.
+    pub fn adapter(self) -> Box<dyn HashAdapter> {
+        match self {
+            HashFunction::Blake3 => Box::new(Blake3Adapter::new()),
+            HashFunction::Sha256 => Box::new(Sha256Adapter),
+            HashFunction::Sha3_256 => Box::new(Sha3Adapter),
+        }
+    }
+
+    /// Registry identifier of the function.
+    pub fn algorithm(self) -> AlgorithmId {
+        match self {
+            HashFunction::Blake3 => AlgorithmId::Blake3,
+            HashFunction::Sha256 => AlgorithmId::Sha256,
+            HashFunction::Sha3_256 => AlgorithmId::Sha3_256,
+        }
+    }
+}
+
+/// Enterprise security validation for BLAKE3 adapter
+pub struct SecurityValidator;
+
+impl SecurityValidator {
+    /// Validate that BLAKE3 meets enterprise security requirements
+    pub fn validate_security_properties() -> Result<(), String> {
+        // Verify BLAKE3 properties
+        let properties = [
+            ("Collision Resistance", "2^128 security level"),
+            ("Preimage Resistance", "2^256 security level"),
+            ("Cross-Platform", "Windows, Linux, macOS support"),
+            ("Performance", "15x faster than SHA-256"),
+            ("Parallelization", "SIMD and multi-threading support"),
+        ];
+
+        for (property, requirement) in properties {
+            structured_log!(
+                log::Level::Info,
+                EventCode::SecurityPropertyVerified,
+                "hash security property verified",
+                property = property,
+                requirement = requirement
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Benchmark BLAKE3 performance
+    pub fn benchmark_performance() -> u64 {
+        use std::time::Instant;
+
+        let data = vec![0u8; 1_048_576]; // 1 MB test data
+        let start = Instant::now();
+
+        let mut hasher = Blake3Adapter::new();
+        hasher.update(&data);
+        let _result = hasher.finalize_fixed();
+
+        start.elapsed().as_nanos() as u64
     }
 }
 
@@ -288,6 +617,93 @@ mod tests {
         assert_eq!(original_result, deserialized_result);
     }
 
+    #[test]
+    fn test_streaming_mode_matches_checkpointable_digests() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        for make in [
+            Blake3Adapter::new as fn() -> Blake3Adapter,
+            || Blake3Adapter::new_keyed(&[7; 32]),
+            || Blake3Adapter::new_derive_key("zk-thresh-pro 2024 streaming test v1"),
+        ] {
+            let mut chained = make();
+            let mut streaming = make().into_streaming().unwrap();
+            assert!(streaming.is_streaming() && !chained.is_streaming());
+            chained.update(&data);
+            chained.update_with_context(b"tail", "ctx");
+            streaming.update_reader(&data[..]).unwrap();
+            streaming.update_with_context(b"tail", "ctx");
+            let mut expected = [0u8; 96];
+            let mut actual = [0u8; 96];
+            chained.finalize_extended(&mut expected);
+            streaming.finalize_extended(&mut actual);
+            assert_eq!(expected, actual);
+            assert_eq!(
+                chained.clone().finalize_fixed(),
+                streaming.clone().finalize_fixed()
+            );
+
+            // The chaining values cannot be carried into a live hasher.
+            assert!(matches!(
+                chained.into_streaming(),
+                Err(CryptoError::Validation { .. })
+            ));
+            assert!(serde_json::to_string(&streaming).is_err());
+            streaming.reset();
+            assert_eq!(streaming.finalize_fixed(), make().finalize_fixed());
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_resumes_without_input() {
+        let data = vec![0x3cu8; 100_000];
+        let mut hasher = Blake3Adapter::new_derive_key("zk-thresh-pro 2024 checkpoint test v1");
+        hasher.update(&data[..70_000]);
+        let checkpoint = serde_json::to_string(&hasher).unwrap();
+        assert!(checkpoint.len() < 1000, "{}", checkpoint.len());
+        let mut resumed: Blake3Adapter = serde_json::from_str(&checkpoint).unwrap();
+        resumed.update(&data[70_000..]);
+        hasher.update(&data[70_000..]);
+        assert_eq!(resumed.finalize_fixed(), hasher.finalize_fixed());
+
+        // Version 1 checkpoints carry their input and still load.
+        let legacy =
+            r#"[{"key":null,"context":null,"accumulated_data":[97,98,99],"mode":"Normal"},"High"]"#;
+        let mut loaded: Blake3Adapter = serde_json::from_str(legacy).unwrap();
+        let mut expected = [0u8; 32];
+        loaded.finalize_extended(&mut expected);
+        assert_eq!(expected, *blake3::hash(b"abc").as_bytes());
+        loaded.update(b"d");
+        assert!(!serde_json::to_string(&loaded)
+            .unwrap()
+            .contains("accumulated_data"));
+        let keyless = r#"[{"key":null,"context":null,"mode":"Keyed"},"High"]"#;
+        assert!(serde_json::from_str::<Blake3Adapter>(keyless).is_err());
+    }
+
+    #[test]
+    fn test_update_mmap_requires_streaming() {
+        let path = std::env::temp_dir().join(format!("zk-thresh-mmap-{}.bin", std::process::id()));
+        let data = vec![0x5a; 300_000];
+        std::fs::write(&path, &data).unwrap();
+        let mut buffered = Blake3Adapter::new();
+        assert!(matches!(
+            buffered.update_mmap(&path),
+            Err(CryptoError::Validation { .. })
+        ));
+        let mut streaming = Blake3Adapter::new().into_streaming().unwrap();
+        streaming.update_mmap(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        buffered.update(&data);
+        assert_eq!(streaming.finalize_fixed(), buffered.finalize_fixed());
+        assert!(matches!(
+            Blake3Adapter::new()
+                .into_streaming()
+                .unwrap()
+                .update_mmap(&path),
+            Err(CryptoError::Serialization { .. })
+        ));
+    }
+
     #[test]
     fn test_security_levels() {
         let standard = Blake3Adapter::new_with_security(SecurityLevel::Standard);
@@ -315,41 +731,62 @@ mod tests {
         let result = hasher.finalize_fixed();
         assert_eq!(result.len(), 64);
     }
-}
-
-/// Enterprise security validation for BLAKE3 adapter
-pub struct SecurityValidator;
 
-impl SecurityValidator {
-    /// Validate that BLAKE3 meets enterprise security requirements
-    pub fn validate_security_properties() -> Result<(), String> {
-        // Verify BLAKE3 properties
-        let properties = [
-            ("Collision Resistance", "2^128 security level"),
-            ("Preimage Resistance", "2^256 security level"),
-            ("Cross-Platform", "Windows, Linux, macOS support"),
-            ("Performance", "15x faster than SHA-256"),
-            ("Parallelization", "SIMD and multi-threading support"),
-        ];
-
-        for (property, requirement) in properties {
-            log::info!("✓ {} - {}", property, requirement);
+    #[test]
+    fn test_hash_adapters_derive_distinct_challenges_and_keys() {
+        let adapters = [
+            HashFunction::Blake3,
+            HashFunction::Sha256,
+            HashFunction::Sha3_256,
+        ]
+        .map(|function| {
+            let adapter = function.adapter();
+            assert_eq!(adapter.algorithm(), function.algorithm());
+            adapter
+        });
+        let mut challenges = Vec::new();
+        let mut keys = Vec::new();
+        for adapter in &adapters {
+            assert_eq!(
+                adapter.challenge(b"statement"),
+                adapter.challenge(b"statement")
+            );
+            assert_ne!(
+                adapter.challenge(b"statement"),
+                adapter.challenge(b"statement'")
+            );
+            challenges.push(adapter.challenge(b"statement"));
+            let mut key = [0u8; 100];
+            adapter
+                .derive_key("acme 2024 test v1", b"input", &mut key)
+                .unwrap();
+            let mut prefix = [0u8; 40];
+            adapter
+                .derive_key("acme 2024 test v1", b"input", &mut prefix)
+                .unwrap();
+            if adapter.algorithm() != AlgorithmId::Sha3_256 {
+                // KMAC binds the output length; the others are prefixes of one stream.
+                assert_eq!(prefix, key[..40]);
+            }
+            keys.push(key);
         }
+        assert!(challenges[0] != challenges[1] && challenges[1] != challenges[2]);
+        assert!(keys[0] != keys[1] && keys[1] != keys[2]);
 
-        Ok(())
-    }
-
-    /// Benchmark BLAKE3 performance
-    pub fn benchmark_performance() -> u64 {
-        use std::time::Instant;
-
-        let data = vec![0u8; 1_048_576]; // 1 MB test data
-        let start = Instant::now();
-
+        // The BLAKE3 adapter is the plain and derive-key BLAKE3 used before it existed.
         let mut hasher = Blake3Adapter::new();
-        hasher.update(&data);
-        let _result = hasher.finalize_fixed();
-
-        start.elapsed().as_nanos() as u64
+        hasher.update(b"statement");
+        assert_eq!(challenges[0], Scalar::from_hash(hasher));
+        let mut expected = [0u8; 100];
+        let mut hasher = Blake3Adapter::new_derive_key("acme 2024 test v1");
+        hasher.update(b"input");
+        hasher.finalize_extended(&mut expected);
+        assert_eq!(keys[0], expected);
+
+        let mut too_long = vec![0u8; 255 * 32 + 1];
+        assert!(matches!(
+            Sha256Adapter.derive_key("acme", b"input", &mut too_long),
+            Err(CryptoError::Validation { field, .. }) if field == "len"
+        ));
     }
 }
@@ -0,0 +1,432 @@
+//! Incremental BLAKE3 whose state is the hash tree's chaining values, so that a partially fed
+//! hasher can be checkpointed and resumed without keeping its input.
+//!
+//! The state is the chaining values of the completed subtrees (one per set bit of the number of
+//! completed 1 KiB chunks), the chaining value of the current chunk and the current block, which
+//! holds at most 64 bytes of input: the last block of a chunk is compressed with a different flag,
+//! so it cannot be absorbed before more input arrives. The digests are those of the `blake3`
+//! crate in all three modes.
+
+use crate::error::{CryptoError, CryptoResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+const BLOCKS_PER_CHUNK: u8 = (CHUNK_LEN / BLOCK_LEN) as u8;
+
+const CHUNK_START: u8 = 1 << 0;
+const CHUNK_END: u8 = 1 << 1;
+const PARENT: u8 = 1 << 2;
+const ROOT: u8 = 1 << 3;
+pub(crate) const KEYED_HASH: u8 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u8 = 1 << 5;
+pub(crate) const DERIVE_KEY_MATERIAL: u8 = 1 << 6;
+
+pub(crate) const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const MESSAGE_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(x);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(y);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+fn compress(cv: &[u32; 8], block: &[u8; BLOCK_LEN], len: u8, counter: u64, flags: u8) -> [u32; 16] {
+    let mut m = [0u32; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        IV[0],
+        IV[1],
+        IV[2],
+        IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        u32::from(len),
+        u32::from(flags),
+    ];
+    for round in 0..7 {
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+        if round < 6 {
+            m = MESSAGE_PERMUTATION.map(|i| m[i]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    m.zeroize();
+    state
+}
+
+pub(crate) fn words(bytes: &[u8; 32]) -> [u32; 8] {
+    let mut words = [0u32; 8];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    words
+}
+
+fn bytes(words: &[u32]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// The last compression of a tree, to be squeezed (root) or chained (non-root).
+struct Output {
+    cv: [u32; 8],
+    block: [u8; BLOCK_LEN],
+    len: u8,
+    counter: u64,
+    flags: u8,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u8; 32] {
+        bytes(&compress(&self.cv, &self.block, self.len, self.counter, self.flags)[..8])
+    }
+
+    fn root(&self, out: &mut [u8]) {
+        for (counter, chunk) in out.chunks_mut(BLOCK_LEN).enumerate() {
+            let mut words = compress(
+                &self.cv,
+                &self.block,
+                self.len,
+                counter as u64,
+                self.flags | ROOT,
+            );
+            for (bytes, word) in chunk.chunks_mut(4).zip(words) {
+                bytes.copy_from_slice(&word.to_le_bytes()[..bytes.len()]);
+            }
+            words.zeroize();
+        }
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        self.cv.zeroize();
+        self.block.zeroize();
+    }
+}
+
+fn parent(key: &[u32; 8], flags: u8, left: &[u8; 32], right: &[u8; 32]) -> Output {
+    let mut block = [0u8; BLOCK_LEN];
+    block[..32].copy_from_slice(left);
+    block[32..].copy_from_slice(right);
+    Output {
+        cv: *key,
+        block,
+        len: BLOCK_LEN as u8,
+        counter: 0,
+        flags: flags | PARENT,
+    }
+}
+
+/// Key words of the derive-key mode for `context`.
+pub(crate) fn context_key(context: &str) -> [u32; 8] {
+    let mut state = ChainState::default();
+    state.update(&IV, DERIVE_KEY_CONTEXT, context.as_bytes());
+    let mut key = [0u8; 32];
+    state.finalize(&IV, DERIVE_KEY_CONTEXT, &mut key);
+    let key_words = words(&key);
+    key.zeroize();
+    key_words
+}
+
+/// Hashing state between updates; the key words and mode flags are kept by the caller.
+#[derive(Clone, Default, Serialize, Deserialize, Zeroize)]
+#[serde(try_from = "StoredChainState")]
+pub(crate) struct ChainState {
+    /// Number of completed chunks, the index of the current one.
+    chunk_counter: u64,
+    /// Chaining values of the completed subtrees, largest first.
+    #[serde(serialize_with = "serialize_cvs")]
+    cv_stack: Vec<[u8; 32]>,
+    /// Chaining value of the current chunk after its compressed blocks.
+    #[serde(serialize_with = "serialize_cv")]
+    chunk_cv: [u8; 32],
+    /// Blocks of the current chunk already compressed.
+    blocks_compressed: u8,
+    /// Input of the current block, at most 64 bytes.
+    #[serde(serialize_with = "serialize_block")]
+    block: Vec<u8>,
+}
+
+impl ChainState {
+    /// Whether no input has been absorbed.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.chunk_counter == 0 && self.blocks_compressed == 0 && self.block.is_empty()
+    }
+
+    fn chunk_len(&self) -> usize {
+        usize::from(self.blocks_compressed) * BLOCK_LEN + self.block.len()
+    }
+
+    fn start_flag(&self) -> u8 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    /// Chaining value the current chunk's next compression starts from.
+    fn chunk_input_cv(&self, key: &[u32; 8]) -> [u32; 8] {
+        if self.blocks_compressed == 0 {
+            *key
+        } else {
+            words(&self.chunk_cv)
+        }
+    }
+
+    fn full_block(&self) -> [u8; BLOCK_LEN] {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..self.block.len()].copy_from_slice(&self.block);
+        block
+    }
+
+    fn chunk_output(&self, key: &[u32; 8], flags: u8) -> Output {
+        Output {
+            cv: self.chunk_input_cv(key),
+            block: self.full_block(),
+            len: self.block.len() as u8,
+            counter: self.chunk_counter,
+            flags: flags | self.start_flag() | CHUNK_END,
+        }
+    }
+
+    /// Push the chaining value of a completed chunk, merging completed subtrees.
+    fn push_chunk(&mut self, key: &[u32; 8], flags: u8, mut cv: [u8; 32]) {
+        let mut total_chunks = self.chunk_counter + 1;
+        while total_chunks & 1 == 0 {
+            let left = self
+                .cv_stack
+                .pop()
+                .expect("one subtree per set bit of the counter");
+            cv = parent(key, flags, &left, &cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.cv_stack.push(cv);
+        self.chunk_counter += 1;
+        self.chunk_cv = [0; 32];
+        self.blocks_compressed = 0;
+        self.block.zeroize();
+    }
+
+    pub(crate) fn update(&mut self, key: &[u32; 8], flags: u8, mut input: &[u8]) {
+        while !input.is_empty() {
+            if self.chunk_len() == CHUNK_LEN {
+                let cv = self.chunk_output(key, flags).chaining_value();
+                self.push_chunk(key, flags, cv);
+            }
+            if self.block.len() == BLOCK_LEN {
+                let mut block = self.full_block();
+                let cv = compress(
+                    &self.chunk_input_cv(key),
+                    &block,
+                    BLOCK_LEN as u8,
+                    self.chunk_counter,
+                    flags | self.start_flag(),
+                );
+                self.chunk_cv = bytes(&cv[..8]);
+                self.blocks_compressed += 1;
+                self.block.zeroize();
+                block.zeroize();
+            }
+            let taken = (BLOCK_LEN - self.block.len()).min(input.len());
+            self.block.extend_from_slice(&input[..taken]);
+            input = &input[taken..];
+        }
+    }
+
+    /// Squeeze the digest of the input absorbed so far into `out`; the state is unchanged.
+    pub(crate) fn finalize(&self, key: &[u32; 8], flags: u8, out: &mut [u8]) {
+        let mut output = self.chunk_output(key, flags);
+        for left in self.cv_stack.iter().rev() {
+            output = parent(key, flags, left, &output.chaining_value());
+        }
+        output.root(out);
+    }
+}
+
+impl Drop for ChainState {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+fn serialize_cv<S: Serializer>(cv: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(cv))
+}
+
+fn serialize_cvs<S: Serializer>(cvs: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(cvs.iter().map(hex::encode))
+}
+
+fn serialize_block<S: Serializer>(block: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(block))
+}
+
+/// Serialized form of [`ChainState`], checked before use.
+#[derive(Deserialize)]
+struct StoredChainState {
+    chunk_counter: u64,
+    cv_stack: Vec<HexString>,
+    chunk_cv: HexString,
+    blocks_compressed: u8,
+    block: HexString,
+}
+
+struct HexString(Vec<u8>);
+
+impl<'de> Deserialize<'de> for HexString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if !crate::serialization::is_lower_hex(&s) {
+            return Err(serde::de::Error::custom("hex must be lowercase"));
+        }
+        hex::decode(&s)
+            .map(HexString)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+fn chaining_value(hex: HexString) -> CryptoResult<[u8; 32]> {
+    hex.0.try_into().map_err(|_| CryptoError::Serialization {
+        details: "chaining values are 32 bytes".to_string(),
+    })
+}
+
+impl TryFrom<StoredChainState> for ChainState {
+    type Error = CryptoError;
+
+    fn try_from(stored: StoredChainState) -> CryptoResult<Self> {
+        let invalid = |reason: &str| CryptoError::Validation {
+            field: "tree".to_string(),
+            reason: reason.to_string(),
+        };
+        let state = ChainState {
+            chunk_counter: stored.chunk_counter,
+            cv_stack: stored
+                .cv_stack
+                .into_iter()
+                .map(chaining_value)
+                .collect::<CryptoResult<_>>()?,
+            chunk_cv: chaining_value(stored.chunk_cv)?,
+            blocks_compressed: stored.blocks_compressed,
+            block: stored.block.0,
+        };
+        if state.cv_stack.len() != state.chunk_counter.count_ones() as usize {
+            return Err(invalid("one chaining value per completed subtree"));
+        }
+        if state.blocks_compressed >= BLOCKS_PER_CHUNK || state.block.len() > BLOCK_LEN {
+            return Err(invalid("the current chunk holds at most 1024 bytes"));
+        }
+        // A block is only compressed once input follows it.
+        if state.blocks_compressed > 0 && state.block.is_empty() {
+            return Err(invalid("compressed blocks are followed by input"));
+        }
+        if state.blocks_compressed == 0 && state.chunk_cv != [0; 32] {
+            return Err(invalid("a fresh chunk has no chaining value"));
+        }
+        // The last chunk is kept open, so a completed chunk is followed by input.
+        if state.chunk_counter > 0 && state.block.is_empty() {
+            return Err(invalid("completed chunks are followed by input"));
+        }
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(key: &[u32; 8], flags: u8, pieces: &[&[u8]]) -> [u8; 80] {
+        let mut state = ChainState::default();
+        for piece in pieces {
+            state.update(key, flags, piece);
+        }
+        let mut out = [0u8; 80];
+        state.finalize(key, flags, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_digests_match_the_blake3_crate() {
+        let input: Vec<u8> = (0..9000u32).map(|i| (i % 251) as u8).collect();
+        let key = [42u8; 32];
+        let context = "zk-thresh-pro 2024 tree test v1";
+        for length in [
+            0, 1, 63, 64, 65, 1023, 1024, 1025, 2048, 2049, 3072, 8193, 9000,
+        ] {
+            let data = &input[..length];
+            let (head, tail) = data.split_at(length / 3);
+            let mut expected = [0u8; 80];
+            for (hasher, key_words, flags) in [
+                (blake3::Hasher::new(), IV, 0),
+                (blake3::Hasher::new_keyed(&key), words(&key), KEYED_HASH),
+                (
+                    blake3::Hasher::new_derive_key(context),
+                    context_key(context),
+                    DERIVE_KEY_MATERIAL,
+                ),
+            ] {
+                let mut hasher = hasher;
+                hasher.update(data).finalize_xof().fill(&mut expected);
+                assert_eq!(
+                    hash(&key_words, flags, &[head, tail]),
+                    expected,
+                    "{}",
+                    length
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_keeps_at_most_one_block() {
+        let input = vec![0xa5u8; 5000];
+        let mut state = ChainState::default();
+        state.update(&IV, 0, &input[..3000]);
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.len() < 400, "{}", json);
+        let mut resumed: ChainState = serde_json::from_str(&json).unwrap();
+        resumed.update(&IV, 0, &input[3000..]);
+        let mut out = [0u8; 32];
+        resumed.finalize(&IV, 0, &mut out);
+        assert_eq!(out, *blake3::hash(&input).as_bytes());
+
+        let mut tampered: serde_json::Value = serde_json::from_str(&json).unwrap();
+        tampered["chunk_counter"] = 3.into();
+        assert!(serde_json::from_value::<ChainState>(tampered).is_err());
+    }
+}
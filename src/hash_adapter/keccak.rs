@@ -0,0 +1,252 @@
+//! Keccak-f[1600] and the SHA-3 functions built on it: cSHAKE256 and KMAC256 (SP 800-185).
+
+use zeroize::Zeroize;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Rotation offsets of the lanes, indexed `x + 5 * y`.
+const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+fn keccak_f(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // θ
+        let mut parity = [0u64; 5];
+        for (x, column) in parity.iter_mut().enumerate() {
+            *column = (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]);
+        }
+        for x in 0..5 {
+            let d = parity[(x + 4) % 5] ^ parity[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+        // ρ and π
+        let mut moved = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                moved[y + 5 * ((2 * x + 3 * y) % 5)] =
+                    state[x + 5 * y].rotate_left(ROTATIONS[x + 5 * y]);
+            }
+        }
+        // χ
+        for y in 0..5 {
+            for x in 0..5 {
+                state[x + 5 * y] =
+                    moved[x + 5 * y] ^ (!moved[(x + 1) % 5 + 5 * y] & moved[(x + 2) % 5 + 5 * y]);
+            }
+        }
+        // ι
+        state[0] ^= round_constant;
+    }
+}
+
+/// Rate of the 256-bit security functions, in bytes.
+const RATE: usize = 136;
+
+/// Domain suffix of cSHAKE256.
+const CSHAKE_SUFFIX: u8 = 0x04;
+
+/// Keccak[512] sponge absorbing at [`RATE`].
+#[derive(Clone)]
+struct Sponge {
+    state: [u64; 25],
+    position: usize,
+}
+
+impl Sponge {
+    fn new() -> Self {
+        Sponge {
+            state: [0; 25],
+            position: 0,
+        }
+    }
+
+    fn xor_byte(&mut self, position: usize, byte: u8) {
+        self.state[position / 8] ^= u64::from(byte) << (8 * (position % 8));
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        crate::op_counts::count(crate::op_counts::Op::HashByte, data.len());
+        for &byte in data {
+            self.xor_byte(self.position, byte);
+            self.position += 1;
+            if self.position == RATE {
+                keccak_f(&mut self.state);
+                self.position = 0;
+            }
+        }
+    }
+
+    /// Pad with `suffix` and squeeze `out`.
+    fn squeeze(mut self, suffix: u8, out: &mut [u8]) {
+        self.xor_byte(self.position, suffix);
+        self.xor_byte(RATE - 1, 0x80);
+        keccak_f(&mut self.state);
+        for (i, byte) in out.iter_mut().enumerate() {
+            let position = i % RATE;
+            if i > 0 && position == 0 {
+                keccak_f(&mut self.state);
+            }
+            *byte = (self.state[position / 8] >> (8 * (position % 8))) as u8;
+        }
+    }
+}
+
+impl Drop for Sponge {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+/// `left_encode(x)` of SP 800-185 into a buffer, returning the used prefix.
+fn left_encode(x: u64, buf: &mut [u8; 9]) -> &[u8] {
+    let bytes = x.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let n = 8 - skip;
+    buf[0] = n as u8;
+    buf[1..=n].copy_from_slice(&bytes[skip..]);
+    &buf[..=n]
+}
+
+/// `right_encode(x)` of SP 800-185 into a buffer, returning the used prefix.
+fn right_encode(x: u64, buf: &mut [u8; 9]) -> &[u8] {
+    let bytes = x.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let n = 8 - skip;
+    buf[..n].copy_from_slice(&bytes[skip..]);
+    buf[n] = n as u8;
+    &buf[..=n]
+}
+
+/// Absorb `bytepad(encode_string(strings[0]) || …, RATE)`.
+fn absorb_bytepad(sponge: &mut Sponge, strings: &[&[u8]]) {
+    let mut buf = [0u8; 9];
+    let mut written = left_encode(RATE as u64, &mut buf).len();
+    sponge.absorb(left_encode(RATE as u64, &mut buf));
+    for string in strings {
+        let encoded_length = left_encode(string.len() as u64 * 8, &mut buf);
+        written += encoded_length.len() + string.len();
+        sponge.absorb(encoded_length);
+        sponge.absorb(string);
+    }
+    let padding = (RATE - written % RATE) % RATE;
+    sponge.absorb(&[0u8; RATE][..padding]);
+}
+
+/// cSHAKE256 sponge with function name `name` and customization `customization`, before `X`.
+fn cshake256(name: &[u8], customization: &[u8]) -> Sponge {
+    let mut sponge = Sponge::new();
+    absorb_bytepad(&mut sponge, &[name, customization]);
+    sponge
+}
+
+/// cSHAKE256 of `data` with function name `name` and customization `customization` into `out`.
+///
+/// Either string must be non-empty; with both empty cSHAKE256 is SHAKE256.
+pub(crate) fn cshake256_into(name: &[u8], customization: &[u8], data: &[u8], out: &mut [u8]) {
+    debug_assert!(!name.is_empty() || !customization.is_empty());
+    let mut sponge = cshake256(name, customization);
+    sponge.absorb(data);
+    sponge.squeeze(CSHAKE_SUFFIX, out);
+}
+
+/// KMAC256 of the concatenation of `parts` under `key`, with customization `customization`,
+/// into `out`.
+pub(crate) fn kmac256(key: &[u8], parts: &[&[u8]], customization: &[u8], out: &mut [u8]) {
+    let mut sponge = cshake256(b"KMAC", customization);
+    absorb_bytepad(&mut sponge, &[key]);
+    parts.iter().for_each(|part| sponge.absorb(part));
+    let mut buf = [0u8; 9];
+    sponge.absorb(right_encode(out.len() as u64 * 8, &mut buf));
+    sponge.squeeze(CSHAKE_SUFFIX, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA3-256 of the concatenation of `parts`, to check the permutation.
+    fn sha3_256(parts: &[&[u8]]) -> [u8; 32] {
+        let mut sponge = Sponge::new();
+        parts.iter().for_each(|part| sponge.absorb(part));
+        let mut digest = [0u8; 32];
+        sponge.squeeze(0x06, &mut digest);
+        digest
+    }
+
+    /// SHAKE256 of `data` into `out`.
+    fn shake256(data: &[u8], out: &mut [u8]) {
+        let mut sponge = Sponge::new();
+        sponge.absorb(data);
+        sponge.squeeze(0x1f, out);
+    }
+
+    #[test]
+    fn test_sha3_and_shake_known_answers() {
+        assert_eq!(
+            hex::encode(sha3_256(&[b""])),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            hex::encode(sha3_256(&[b"a", b"bc"])),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        // Longer than the rate, so that absorbing permutes mid-message.
+        assert_eq!(
+            hex::encode(sha3_256(&[&[0x61; 200]])),
+            "cce34485baf2bf2aca99b94833892a4f52896d3d153f7b840cc4f9fe695f1387"
+        );
+        let mut out = [0u8; 32];
+        shake256(b"", &mut out);
+        assert_eq!(
+            hex::encode(out),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f"
+        );
+    }
+
+    #[test]
+    fn test_cshake_and_kmac_sp800_185_samples() {
+        let data = [0x00, 0x01, 0x02, 0x03];
+        let mut out = [0u8; 64];
+        cshake256_into(b"", b"Email Signature", &data, &mut out);
+        assert_eq!(
+            hex::encode(out),
+            "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1\
+             64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c"
+        );
+        let key: Vec<u8> = (0x40..=0x5f).collect();
+        kmac256(&key, &[&data], b"My Tagged Application", &mut out);
+        assert_eq!(
+            hex::encode(out),
+            "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7\
+             f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"
+        );
+    }
+}
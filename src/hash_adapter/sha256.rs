@@ -0,0 +1,245 @@
+//! SHA-256 (FIPS 180-4), HMAC-SHA256 (RFC 2104) and HKDF-SHA256 (RFC 5869).
+
+use zeroize::Zeroize;
+
+/// Length of a SHA-256 digest.
+pub(crate) const DIGEST_LENGTH: usize = 32;
+const BLOCK_LENGTH: usize = 64;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Incremental SHA-256.
+#[derive(Clone)]
+pub(crate) struct Sha256 {
+    state: [u32; 8],
+    block: [u8; BLOCK_LENGTH],
+    buffered: usize,
+    length: u64,
+}
+
+impl Sha256 {
+    pub(crate) fn new() -> Self {
+        Sha256 {
+            state: INITIAL_STATE,
+            block: [0; BLOCK_LENGTH],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        crate::op_counts::count(crate::op_counts::Op::HashByte, data.len());
+        self.length = self.length.wrapping_add(data.len() as u64);
+        while !data.is_empty() {
+            let taken = (BLOCK_LENGTH - self.buffered).min(data.len());
+            self.block[self.buffered..self.buffered + taken].copy_from_slice(&data[..taken]);
+            self.buffered += taken;
+            data = &data[taken..];
+            if self.buffered == BLOCK_LENGTH {
+                compress(&mut self.state, &self.block);
+                self.buffered = 0;
+            }
+        }
+    }
+
+    pub(crate) fn finalize(mut self) -> [u8; DIGEST_LENGTH] {
+        let bit_length = self.length.wrapping_mul(8);
+        self.block[self.buffered] = 0x80;
+        self.block[self.buffered + 1..].fill(0);
+        if self.buffered + 1 > BLOCK_LENGTH - 8 {
+            compress(&mut self.state, &self.block);
+            self.block.fill(0);
+        }
+        self.block[BLOCK_LENGTH - 8..].copy_from_slice(&bit_length.to_be_bytes());
+        compress(&mut self.state, &self.block);
+        let mut digest = [0u8; DIGEST_LENGTH];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+impl Drop for Sha256 {
+    fn drop(&mut self) {
+        self.state.zeroize();
+        self.block.zeroize();
+    }
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_LENGTH]) {
+    let mut w = [0u32; 64];
+    for (word, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(K[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(majority);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+    w.zeroize();
+}
+
+/// SHA-256 of the concatenation of `parts`.
+pub(crate) fn digest(parts: &[&[u8]]) -> [u8; DIGEST_LENGTH] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+/// HMAC-SHA256 of the concatenation of `parts` under `key`.
+pub(crate) fn hmac(key: &[u8], parts: &[&[u8]]) -> [u8; DIGEST_LENGTH] {
+    let mut block = [0u8; BLOCK_LENGTH];
+    if key.len() > BLOCK_LENGTH {
+        block[..DIGEST_LENGTH].copy_from_slice(&digest(&[key]));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(&block.map(|b| b ^ 0x36));
+    for part in parts {
+        inner.update(part);
+    }
+    let mut inner_digest = inner.finalize();
+    let mut outer = Sha256::new();
+    outer.update(&block.map(|b| b ^ 0x5c));
+    outer.update(&inner_digest);
+    block.zeroize();
+    inner_digest.zeroize();
+    outer.finalize()
+}
+
+/// Longest HKDF-SHA256 output.
+pub(crate) const HKDF_MAX_LENGTH: usize = 255 * DIGEST_LENGTH;
+
+/// HKDF-SHA256 of `ikm` with `salt` and `info` into `out`, at most [`HKDF_MAX_LENGTH`] bytes.
+pub(crate) fn hkdf(salt: &[u8], ikm: &[u8], info: &[u8], out: &mut [u8]) {
+    debug_assert!(out.len() <= HKDF_MAX_LENGTH);
+    let mut prk = hmac(salt, &[ikm]);
+    let mut previous = [0u8; DIGEST_LENGTH];
+    for (counter, chunk) in out.chunks_mut(DIGEST_LENGTH).enumerate() {
+        let block_index = [(counter + 1) as u8];
+        let previous_part: &[u8] = if counter == 0 { &[] } else { &previous };
+        previous = hmac(&prk, &[previous_part, info, &block_index]);
+        chunk.copy_from_slice(&previous[..chunk.len()]);
+    }
+    prk.zeroize();
+    previous.zeroize();
+}
+
+/// `expand_message_xmd` with SHA-256 (RFC 9380, section 5.3.1) into `out`.
+///
+/// `dst` is at most 255 bytes and `out` at most 255 blocks of 32 bytes.
+pub(crate) fn expand_message_xmd(message: &[u8], dst: &[u8], out: &mut [u8]) {
+    debug_assert!(dst.len() <= 255 && out.len() <= HKDF_MAX_LENGTH);
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let length = (out.len() as u16).to_be_bytes();
+    let b0 = digest(&[&[0u8; BLOCK_LENGTH], message, &length, &[0], &dst_prime]);
+    let mut previous = digest(&[&b0, &[1], &dst_prime]);
+    for (i, chunk) in out.chunks_mut(DIGEST_LENGTH).enumerate() {
+        if i > 0 {
+            let mixed: Vec<u8> = b0.iter().zip(previous).map(|(x, y)| x ^ y).collect();
+            previous = digest(&[&mixed, &[(i + 1) as u8], &dst_prime]);
+        }
+        chunk.copy_from_slice(&previous[..chunk.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_known_answers() {
+        assert_eq!(
+            hex::encode(digest(&[b""])),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex::encode(digest(&[b"a", b"bc"])),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(digest(&[
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            ])),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_rfc5869_vectors() {
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        let mut okm = [0u8; 42];
+        hkdf(&salt, &ikm, &info, &mut okm);
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+        hkdf(&[], &ikm, &[], &mut okm);
+        assert_eq!(
+            hex::encode(okm),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn test_expand_message_xmd_rfc9380_vectors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let mut out = [0u8; 32];
+        expand_message_xmd(b"", dst, &mut out);
+        assert_eq!(
+            hex::encode(out),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        expand_message_xmd(b"abc", dst, &mut out);
+        assert_eq!(
+            hex::encode(out),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+    }
+}
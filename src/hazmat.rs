@@ -0,0 +1,40 @@
+//! **hazmat module**
+//!
+//! Raw primitives for building new protocols on top of the crate, gathered under one explicitly
+//! named path so that use of them stands out in review.
+//!
+//! Nothing here checks proofs, commitments, indices against a registry, revocation or epochs:
+//! recovering from unverified slices returns whatever the slices interpolate to, a Lagrange
+//! weight computed over the wrong set silently yields a wrong secret, and a scalar drawn or
+//! combined by hand is not zeroized. Applications should use the safe layer instead — the
+//! [`EnterpriseCryptoSystem`](crate::EnterpriseCryptoSystem), recovery ceremonies
+//! ([`RecoveryCollection`](crate::RecoveryCollection)), [`Shareholder`](crate::Shareholder)
+//! storage and the verified [`recover_secret`](crate::recover_secret) — and a review policy can
+//! restrict `zk_thresh_pro::hazmat` (and the defining modules `lagrange_fft` and `utils`) to the
+//! teams that own cryptographic code.
+//!
+//! The crate root does not re-export the unverified recovery functions or the manual Lagrange
+//! weights; they are reached through this module.
+
+#[cfg(feature = "dealer")]
+pub use crate::utils::random_scalar;
+/// Raw scalars and commitments.
+pub use crate::utils::{
+    distributed_random_scalar, pedersen_commit, pow_scalar, ANOTHER_POINT, ANOTHER_POINT_TABLE,
+};
+
+/// Manual Lagrange interpolation and polynomial evaluation.
+#[cfg(feature = "dealer")]
+pub use crate::lagrange_fft::{
+    birkhoff_interpolate, compute_lagrange_coefficients, falling_factorial, poly_evaluate,
+    poly_evaluate_at_many, poly_evaluate_multi, BarycentricWeights, LagrangeCache,
+    LagrangeCoefficients, Polynomial,
+};
+pub use crate::threshold_sig::{lagrange_at_zero, lagrange_coefficient};
+
+/// Recovery without verification: no proof, commitment, revocation or policy check.
+#[cfg(feature = "dealer")]
+pub use crate::lagrange_fft::{
+    recover_secret_barycentric, recover_secret_ct, recover_secret_fft, recover_secret_robust,
+    recover_secrets_batch, recover_secrets_batch_until, recover_secrets_multi,
+};
@@ -0,0 +1,363 @@
+//! **identity module**
+//!
+//! Long-term participant identities: every participant holds an Ed25519 key and signs the
+//! protocol messages it originates, so a message is attributed to its author even when it is
+//! relayed, stored or broadcast, and a node cannot inject messages in another participant's name.
+//!
+//! A [`Participant`] signs a message into a [`Signed`] envelope for one session (the DKG, refresh or
+//! signing run it belongs to, e.g. the operation id of its [`crate::ceremony::Ceremony`]);
+//! receivers check it against a [`Roster`] of the [`ParticipantIdentity`] of everyone in the
+//! ceremony, distributed out of band like the channel keys of
+//! [`transport`](crate::transport). The signed bytes are
+//!
+//! ```text
+//! MESSAGE_SIGNATURE_CONTEXT
+//!     || len(domain) (u64 LE) || domain || len(session) (u64 LE) || session
+//!     || sender (u64 LE) || canonical JSON of the message
+//! ```
+//!
+//! with the RFC 8785 encoding of [`canonical_json`], so a signature survives re-serialization by a
+//! relay. The domain is the [`SignedPayload::DOMAIN`] of the message type, so a signature on one
+//! kind of message never verifies as another; the session ties it to one run, so messages signed
+//! in an earlier ceremony under the same long-term identity cannot be replayed into a new one.
+//!
+//! Enforcement lives with the consumers, which reject unsigned messages, messages from another
+//! session and messages whose signer is not their claimed author: a
+//! [`DkgParticipant`](crate::dkg::DkgParticipant), built with the ceremony's [`Roster`] and
+//! session, a [`SecureChannel`](crate::transport::SecureChannel) with
+//! [`with_identities`](crate::transport::SecureChannel::with_identities),
+//! [`threshold_sig::verify_commitments`](crate::threshold_sig::verify_commitments),
+//! [`threshold_sig::aggregate_signed`](crate::threshold_sig::aggregate_signed),
+//! [`SigningPackage::from_signed`](crate::frost::ciphersuite::SigningPackage::from_signed) and
+//! [`frost::aggregate_signed`](crate::frost::aggregate_signed) for signing commitments and partial
+//! signatures, [`RefreshRecord::verify_signed`](crate::vss::RefreshRecord::verify_signed) for
+//! refresh records, and [`Dealing::verify_signed`](crate::dealing::Dealing::verify_signed) and
+//! [`ShareData::verify_signed`](crate::sharing::ShareData::verify_signed) for dealings and dealt
+//! slices.
+
+use crate::canonical_json;
+use crate::ed25519::{Ed25519Signature, Ed25519SigningKey};
+use crate::error::{CryptoError, CryptoResult};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Domain separator of message signatures.
+pub const MESSAGE_SIGNATURE_CONTEXT: &str = "zk-thresh-pro 2024 signed message v2";
+
+/// A message type participants sign.
+pub trait SignedPayload: Serialize {
+    /// Domain label of the type in the signed bytes; distinct for every type.
+    const DOMAIN: &'static str;
+}
+
+impl<T: SignedPayload + ?Sized> SignedPayload for &T {
+    const DOMAIN: &'static str = T::DOMAIN;
+}
+
+/// Public identity of a participant: its index and Ed25519 public key.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParticipantIdentity {
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_edwards_point_helpers")]
+    pub public_key: EdwardsPoint,
+}
+
+/// A participant's long-term signing identity.
+///
+/// `Debug` output shows the public key only; the key is zeroized on drop.
+#[derive(Clone)]
+pub struct Participant {
+    index: usize,
+    key: Ed25519SigningKey,
+}
+
+impl Participant {
+    /// Participant `index` with a fresh identity key.
+    #[cfg(feature = "dealer")]
+    pub fn generate(index: usize) -> CryptoResult<Self> {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        crate::utils::fill_random(seed.as_mut())?;
+        Self::from_seed(index, &seed)
+    }
+
+    /// Participant `index` with the identity key of an RFC 8032 seed, e.g. one kept in an HSM.
+    ///
+    /// Returns `CryptoError::Validation` for index 0.
+    pub fn from_seed(index: usize, seed: &[u8; 32]) -> CryptoResult<Self> {
+        Self::from_key(index, Ed25519SigningKey::from_seed(seed))
+    }
+
+    /// Participant `index` with an existing Ed25519 key.
+    ///
+    /// Returns `CryptoError::Validation` for index 0.
+    pub fn from_key(index: usize, key: Ed25519SigningKey) -> CryptoResult<Self> {
+        if index == 0 {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: "participant indices start at 1".to_string(),
+            });
+        }
+        Ok(Self { index, key })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The identity to publish in the ceremony's [`Roster`].
+    pub fn identity(&self) -> ParticipantIdentity {
+        ParticipantIdentity {
+            index: self.index,
+            public_key: CompressedEdwardsY(self.key.public_key())
+                .decompress()
+                .expect("public keys of signing keys decompress"),
+        }
+    }
+
+    /// Sign `payload` as this participant, for `session`.
+    pub fn sign<T: SignedPayload>(&self, session: &str, payload: T) -> CryptoResult<Signed<T>> {
+        let message = signed_bytes(session, self.index, &payload)?;
+        Ok(Signed {
+            sender: self.index,
+            session: session.to_string(),
+            payload,
+            signature: self.key.sign(&message).to_bytes().to_vec(),
+        })
+    }
+}
+
+impl fmt::Debug for Participant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Participant")
+            .field("index", &self.index)
+            .field("public_key", &hex::encode(self.key.public_key()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Identities of the participants of a ceremony, by index.
+///
+/// Serialized as a JSON array of [`ParticipantIdentity`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(
+    try_from = "Vec<ParticipantIdentity>",
+    into = "Vec<ParticipantIdentity>"
+)]
+pub struct Roster {
+    identities: BTreeMap<usize, EdwardsPoint>,
+}
+
+impl Roster {
+    /// Returns `CryptoError::Validation` for index 0 or an index listed twice.
+    pub fn new(identities: &[ParticipantIdentity]) -> CryptoResult<Self> {
+        let mut roster = BTreeMap::new();
+        for identity in identities {
+            if identity.index == 0 || roster.insert(identity.index, identity.public_key).is_some() {
+                return Err(CryptoError::Validation {
+                    field: "identities".to_string(),
+                    reason: format!("invalid or repeated index {}", identity.index),
+                });
+            }
+        }
+        Ok(Self { identities: roster })
+    }
+
+    /// Public key of participant `index`.
+    pub fn public_key(&self, index: usize) -> Option<&EdwardsPoint> {
+        self.identities.get(&index)
+    }
+
+    /// Indices with an identity, in increasing order.
+    pub fn indices(&self) -> Vec<usize> {
+        self.identities.keys().copied().collect()
+    }
+}
+
+impl TryFrom<Vec<ParticipantIdentity>> for Roster {
+    type Error = CryptoError;
+
+    fn try_from(identities: Vec<ParticipantIdentity>) -> CryptoResult<Self> {
+        Self::new(&identities)
+    }
+}
+
+impl From<Roster> for Vec<ParticipantIdentity> {
+    fn from(roster: Roster) -> Self {
+        roster
+            .identities
+            .into_iter()
+            .map(|(index, public_key)| ParticipantIdentity { index, public_key })
+            .collect()
+    }
+}
+
+/// A message signed by participant `sender` for `session`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct Signed<T> {
+    pub sender: usize,
+    /// Session the message was signed for.
+    pub session: String,
+    pub payload: T,
+    /// 64-byte Ed25519 signature of the signed bytes (see the module documentation).
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub signature: Vec<u8>,
+}
+
+impl<T: SignedPayload> Signed<T> {
+    /// The payload, if `sender` is in `roster` and signed it for `session`.
+    ///
+    /// Returns `CryptoError::SecurityViolation` for another session, an unknown sender or a
+    /// signature that does not verify.
+    pub fn verify(&self, roster: &Roster, session: &str) -> CryptoResult<&T> {
+        if self.session != session {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "message of participant {} was signed for session {:?}, not {:?}",
+                    self.sender, self.session, session
+                ),
+            });
+        }
+        let public_key =
+            roster
+                .public_key(self.sender)
+                .ok_or_else(|| CryptoError::SecurityViolation {
+                    details: format!("no identity for participant {}", self.sender),
+                })?;
+        let message = signed_bytes(&self.session, self.sender, &self.payload)?;
+        let valid = <&[u8; 64]>::try_from(self.signature.as_slice())
+            .ok()
+            .and_then(Ed25519Signature::from_bytes)
+            .is_some_and(|s| s.verify(&public_key.compress().to_bytes(), &message));
+        if !valid {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "message signature of participant {} does not verify",
+                    self.sender
+                ),
+            });
+        }
+        Ok(&self.payload)
+    }
+
+    /// [`Self::verify`], also requiring the signer to be `author`, the participant the payload
+    /// claims to come from.
+    pub fn verify_from(&self, roster: &Roster, session: &str, author: usize) -> CryptoResult<&T> {
+        if self.sender != author {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "message of participant {} signed by participant {}",
+                    author, self.sender
+                ),
+            });
+        }
+        self.verify(roster, session)
+    }
+}
+
+fn signed_bytes<T: SignedPayload>(
+    session: &str,
+    sender: usize,
+    payload: &T,
+) -> CryptoResult<Zeroizing<Vec<u8>>> {
+    let mut message = Zeroizing::new(MESSAGE_SIGNATURE_CONTEXT.as_bytes().to_vec());
+    for label in [T::DOMAIN, session] {
+        message.extend_from_slice(&(label.len() as u64).to_le_bytes());
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.extend_from_slice(&(sender as u64).to_le_bytes());
+    message.extend_from_slice(&Zeroizing::new(canonical_json::to_vec(payload)?));
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+    struct Note {
+        round: u8,
+        value: String,
+    }
+
+    impl SignedPayload for Note {
+        const DOMAIN: &'static str = "test note";
+    }
+
+    /// Same fields as [`Note`], another message type.
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+    struct Memo {
+        round: u8,
+        value: String,
+    }
+
+    impl SignedPayload for Memo {
+        const DOMAIN: &'static str = "test memo";
+    }
+
+    #[test]
+    fn test_signed_messages() {
+        let alice = Participant::from_seed(1, &[1; 32]).unwrap();
+        let bob = Participant::from_seed(2, &[2; 32]).unwrap();
+        let roster = Roster::new(&[alice.identity(), bob.identity()]).unwrap();
+        let json = serde_json::to_string(&roster).unwrap();
+        assert_eq!(serde_json::from_str::<Roster>(&json).unwrap(), roster);
+
+        let note = Note {
+            round: 1,
+            value: "a".to_string(),
+        };
+        let signed = alice.sign("run-1", note.clone()).unwrap();
+        let relayed: Signed<Note> =
+            serde_json::from_str(&serde_json::to_string_pretty(&signed).unwrap()).unwrap();
+        assert!(relayed.verify_from(&roster, "run-1", 1).is_ok());
+        assert!(relayed.verify_from(&roster, "run-1", 2).is_err());
+
+        let mut forged = signed.clone();
+        forged.sender = 2;
+        assert!(forged.verify(&roster, "run-1").is_err());
+        let mut altered = signed.clone();
+        altered.payload.value = "b".to_string();
+        assert!(altered.verify(&roster, "run-1").is_err());
+        let carol = Participant::from_seed(3, &[3; 32]).unwrap();
+        assert!(carol
+            .sign("run-1", note.clone())
+            .unwrap()
+            .verify(&roster, "run-1")
+            .is_err());
+
+        assert!(Roster::new(&[alice.identity(), alice.identity()]).is_err());
+        assert!(Participant::from_seed(0, &[1; 32]).is_err());
+    }
+
+    #[test]
+    fn test_signatures_bound_to_session_and_type() {
+        let alice = Participant::from_seed(1, &[1; 32]).unwrap();
+        let roster = Roster::new(&[alice.identity()]).unwrap();
+        let signed = alice
+            .sign(
+                "run-1",
+                Note {
+                    round: 1,
+                    value: "a".to_string(),
+                },
+            )
+            .unwrap();
+
+        // Replayed into a later session, as is or relabelled.
+        assert!(matches!(
+            signed.verify(&roster, "run-2"),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut relabelled = signed.clone();
+        relabelled.session = "run-2".to_string();
+        assert!(relabelled.verify(&roster, "run-2").is_err());
+
+        // Presented as another message type with the same encoding.
+        let json = serde_json::to_string(&signed).unwrap();
+        let memo: Signed<Memo> = serde_json::from_str(&json).unwrap();
+        assert!(memo.verify(&roster, "run-1").is_err());
+    }
+}
@@ -0,0 +1,331 @@
+//! **indexing module**
+//!
+//! Rules for assigning slice indices to custodians, recorded so that audits can check them.
+//!
+//! - [`IndexRule::Sequential`]: custodian `k` (from 0) receives index `k + 1`, as in every other
+//!   dealing of this crate.
+//! - [`IndexRule::Random`]: indices drawn uniformly from `1..=MAX_ASSIGNED_INDEX` by the dealer.
+//!   Nobody can predict which index a custodian will hold, so an index reveals nothing about the
+//!   custodian list and cannot be claimed in advance; the audit checks range and uniqueness.
+//! - [`IndexRule::Derived`]: each index is a hash of a domain and the custodian id
+//!   ([`derived_index`]), so the index is bound to the custodian: anyone holding the record
+//!   recomputes it, and a slice presented by a custodian under another index is rejected. A
+//!   collision between two custodians is reported at assignment; pick another domain, e.g. one
+//!   naming the dealing.
+//!
+//! Indices are at most `u32::MAX` so that assignments stay valid on 32-bit targets (see
+//! [`crate::format`]). [`IndexAssignment::deal`] deals a secret at the assigned indices; the
+//! resulting slices recover, refresh and sign like any others.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+#[cfg(feature = "dealer")]
+use crate::sharing::{self, ShareData};
+#[cfg(feature = "dealer")]
+use crate::vss::VssCommitments;
+#[cfg(feature = "dealer")]
+use curve25519_dalek::scalar::Scalar;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Version of the [`IndexAssignment`] format.
+pub const INDEX_ASSIGNMENT_VERSION: u8 = 1;
+
+/// Largest index assigned by the random and derived rules.
+pub const MAX_ASSIGNED_INDEX: usize = u32::MAX as usize;
+
+const DERIVED_INDEX_CONTEXT: &str = "zk-thresh-pro 2024 share index v1";
+
+/// How indices are assigned to custodians.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexRule {
+    Sequential,
+    Random,
+    Derived,
+}
+
+/// Index of the custodian `custodian_id` under the derived rule of `domain`, in
+/// `1..=MAX_ASSIGNED_INDEX`.
+pub fn derived_index(domain: &str, custodian_id: &str) -> usize {
+    let mut hasher = Blake3Adapter::new_derive_key(DERIVED_INDEX_CONTEXT);
+    digest::Update::update(&mut hasher, &(domain.len() as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, domain.as_bytes());
+    digest::Update::update(&mut hasher, custodian_id.as_bytes());
+    let mut out = [0u8; 8];
+    hasher.finalize_extended(&mut out);
+    (u64::from_le_bytes(out) % MAX_ASSIGNED_INDEX as u64) as usize + 1
+}
+
+/// Index held by one custodian.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustodianIndex {
+    pub custodian_id: String,
+    pub index: usize,
+}
+
+/// Record of the indices assigned to the custodians of a dealing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexAssignment {
+    /// Record format version, [`INDEX_ASSIGNMENT_VERSION`].
+    pub version: u8,
+    pub rule: IndexRule,
+    /// Domain of [`IndexRule::Derived`]; `None` for the other rules.
+    pub domain: Option<String>,
+    /// Custodians in dealing order, with their indices.
+    pub entries: Vec<CustodianIndex>,
+}
+
+fn check_custodians(custodians: &[&str]) -> CryptoResult<()> {
+    if custodians.is_empty() {
+        return Err(CryptoError::Validation {
+            field: "custodians".to_string(),
+            reason: "at least one custodian is required".to_string(),
+        });
+    }
+    let mut seen = HashSet::new();
+    for custodian in custodians {
+        if custodian.is_empty() || !seen.insert(*custodian) {
+            return Err(CryptoError::Validation {
+                field: "custodians".to_string(),
+                reason: format!("custodian id {:?} is empty or repeated", custodian),
+            });
+        }
+    }
+    Ok(())
+}
+
+impl IndexAssignment {
+    fn new(
+        rule: IndexRule,
+        domain: Option<String>,
+        custodians: &[&str],
+        indices: Vec<usize>,
+    ) -> Self {
+        Self {
+            version: INDEX_ASSIGNMENT_VERSION,
+            rule,
+            domain,
+            entries: custodians
+                .iter()
+                .zip(indices)
+                .map(|(custodian, index)| CustodianIndex {
+                    custodian_id: custodian.to_string(),
+                    index,
+                })
+                .collect(),
+        }
+    }
+
+    /// Indices `1..=n` in the order of `custodians`.
+    ///
+    /// Returns `CryptoError::Validation` for an empty list or an empty or repeated custodian id.
+    pub fn sequential(custodians: &[&str]) -> CryptoResult<Self> {
+        check_custodians(custodians)?;
+        let indices = (1..=custodians.len()).collect();
+        Ok(Self::new(IndexRule::Sequential, None, custodians, indices))
+    }
+
+    /// Distinct random indices, one per custodian.
+    ///
+    /// Returns `CryptoError::Validation` as [`IndexAssignment::sequential`] does, and
+    /// `CryptoError::InsufficientEntropy` if the random generator fails.
+    #[cfg(feature = "dealer")]
+    pub fn random(custodians: &[&str]) -> CryptoResult<Self> {
+        check_custodians(custodians)?;
+        let mut rng = crate::utils::new_rng();
+        let mut seen = HashSet::new();
+        let mut indices = Vec::with_capacity(custodians.len());
+        while indices.len() < custodians.len() {
+            let draw = crate::utils::random_scalar(&mut rng)?;
+            let bytes: [u8; 8] = draw.as_bytes()[..8].try_into().expect("8 bytes");
+            let index = (u64::from_le_bytes(bytes) % MAX_ASSIGNED_INDEX as u64) as usize + 1;
+            if seen.insert(index) {
+                indices.push(index);
+            }
+        }
+        Ok(Self::new(IndexRule::Random, None, custodians, indices))
+    }
+
+    /// Indices [`derived_index`]`(domain, custodian)`.
+    ///
+    /// Returns `CryptoError::Validation` as [`IndexAssignment::sequential`] does, for an empty
+    /// domain, or if two custodians derive the same index under `domain`.
+    pub fn derived(domain: &str, custodians: &[&str]) -> CryptoResult<Self> {
+        check_custodians(custodians)?;
+        if domain.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "domain".to_string(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+        let indices: Vec<usize> = custodians
+            .iter()
+            .map(|custodian| derived_index(domain, custodian))
+            .collect();
+        let mut seen = HashSet::new();
+        for (custodian, index) in custodians.iter().zip(&indices) {
+            if !seen.insert(*index) {
+                return Err(CryptoError::Validation {
+                    field: "domain".to_string(),
+                    reason: format!(
+                        "custodian {:?} collides with another at index {}; use another domain",
+                        custodian, index
+                    ),
+                });
+            }
+        }
+        Ok(Self::new(
+            IndexRule::Derived,
+            Some(domain.to_string()),
+            custodians,
+            indices,
+        ))
+    }
+
+    /// Assigned indices, in dealing order.
+    pub fn indices(&self) -> Vec<usize> {
+        self.entries.iter().map(|e| e.index).collect()
+    }
+
+    /// Index assigned to `custodian_id`, if it is part of the assignment.
+    pub fn index_of(&self, custodian_id: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|e| e.custodian_id == custodian_id)
+            .map(|e| e.index)
+    }
+
+    /// Check that the record follows its rule.
+    ///
+    /// Returns `CryptoError::Serialization` for an unknown version, `CryptoError::Validation` for
+    /// a malformed custodian list or domain, and `CryptoError::SecurityViolation` naming the first
+    /// custodian whose index breaks the rule.
+    pub fn verify(&self) -> CryptoResult<()> {
+        if self.version != INDEX_ASSIGNMENT_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported index assignment version {}", self.version),
+            });
+        }
+        let custodians: Vec<&str> = self
+            .entries
+            .iter()
+            .map(|e| e.custodian_id.as_str())
+            .collect();
+        check_custodians(&custodians)?;
+        if self.domain.is_some() != (self.rule == IndexRule::Derived) {
+            return Err(CryptoError::Validation {
+                field: "domain".to_string(),
+                reason: "a domain is given exactly for the derived rule".to_string(),
+            });
+        }
+        let mut seen = HashSet::new();
+        for (position, entry) in self.entries.iter().enumerate() {
+            let valid = match self.rule {
+                IndexRule::Sequential => entry.index == position + 1,
+                IndexRule::Random => (1..=MAX_ASSIGNED_INDEX).contains(&entry.index),
+                IndexRule::Derived => {
+                    let domain = self.domain.as_deref().unwrap_or_default();
+                    entry.index == derived_index(domain, &entry.custodian_id)
+                }
+            };
+            if !valid || !seen.insert(entry.index) {
+                return Err(CryptoError::SecurityViolation {
+                    details: format!(
+                        "index {} of custodian {:?} breaks the {:?} assignment rule",
+                        entry.index, entry.custodian_id, self.rule
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Deal `secret` at the assigned indices: slice `k` goes to custodian `k` of the record.
+    ///
+    /// Errors as [`sharing::generate_key_shares_at`].
+    #[cfg(feature = "dealer")]
+    pub fn deal(
+        &self,
+        secret: Scalar,
+        threshold: usize,
+    ) -> CryptoResult<(Vec<ShareData>, VssCommitments)> {
+        sharing::generate_key_shares_at(secret, threshold, &self.indices())
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::lagrange_fft::recover_secret_fft;
+
+    const CUSTODIANS: [&str; 4] = ["alice", "bob", "carol", "dave"];
+
+    #[test]
+    fn test_assignments_deal_and_recover() {
+        let secret = Scalar::from(42u64);
+        let assignments = [
+            IndexAssignment::sequential(&CUSTODIANS).unwrap(),
+            IndexAssignment::random(&CUSTODIANS).unwrap(),
+            IndexAssignment::derived("payments/2024", &CUSTODIANS).unwrap(),
+        ];
+        assert_eq!(assignments[0].indices(), [1, 2, 3, 4]);
+        for assignment in &assignments {
+            assignment.verify().unwrap();
+            let json = serde_json::to_string(assignment).unwrap();
+            let parsed: IndexAssignment = serde_json::from_str(&json).unwrap();
+            parsed.verify().unwrap();
+
+            let (shares, commitments) = assignment.deal(secret, 3).unwrap();
+            for (share, entry) in shares.iter().zip(&assignment.entries) {
+                assert_eq!(share.index, entry.index);
+                commitments.verify_share(share).unwrap();
+            }
+            crate::vss::verify_share_validity(&shares).unwrap();
+            assert_eq!(recover_secret_fft(&shares[1..]).unwrap(), secret);
+        }
+
+        // The derived rule binds indices to custodian ids and the domain.
+        let derived = &assignments[2];
+        assert_eq!(
+            derived.index_of("carol"),
+            Some(derived_index("payments/2024", "carol"))
+        );
+        assert_ne!(
+            derived.indices(),
+            IndexAssignment::derived("custody", &CUSTODIANS)
+                .unwrap()
+                .indices()
+        );
+        assert!(derived.indices().iter().all(|&i| i <= MAX_ASSIGNED_INDEX));
+    }
+
+    #[test]
+    fn test_assignment_rules_are_audited() {
+        assert!(IndexAssignment::sequential(&[]).is_err());
+        assert!(IndexAssignment::sequential(&["alice", "alice"]).is_err());
+        assert!(IndexAssignment::derived("", &CUSTODIANS).is_err());
+
+        let mut swapped = IndexAssignment::derived("payments", &CUSTODIANS).unwrap();
+        let first = swapped.entries[0].index;
+        swapped.entries[0].index = swapped.entries[1].index;
+        swapped.entries[1].index = first;
+        assert!(matches!(
+            swapped.verify(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let mut reordered = IndexAssignment::sequential(&CUSTODIANS).unwrap();
+        reordered.entries.swap(0, 1);
+        assert!(reordered.verify().is_err());
+
+        let mut repeated = IndexAssignment::random(&CUSTODIANS).unwrap();
+        repeated.entries[2].index = repeated.entries[3].index;
+        assert!(repeated.verify().is_err());
+        let mut relabelled = IndexAssignment::random(&CUSTODIANS).unwrap();
+        relabelled.rule = IndexRule::Derived;
+        assert!(relabelled.verify().is_err());
+
+        assert!(sharing::generate_key_shares_at(Scalar::ONE, 2, &[3, 3]).is_err());
+        assert!(sharing::generate_key_shares_at(Scalar::ONE, 2, &[0, 3]).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! **interop module**
+//!
+//! Import of points produced by ed25519 systems (HSMs, signing services, other threshold
+//! libraries) into the crate's Ristretto group.
+//!
+//! Ristretto encodings need no extra checks: every valid encoding is a point of the prime-order
+//! group, and [`crate::serialization`] already enforces canonical form. An ed25519 (compressed
+//! Edwards `y`) encoding, however, can name any point of the full curve group of order `8·ℓ`, so
+//! an imported commitment or public key may carry a small-order component that survives into
+//! verification equations and lets two different values pass the same check. The helpers here
+//! accept an Edwards point only if
+//!
+//! - its encoding is canonical (`y < p`, compressing the decoded point gives back the input);
+//! - it is not of small order (in particular not the identity);
+//! - it is torsion-free, i.e. lies in the prime-order subgroup (`ℓ·P = 0`),
+//!
+//! and then map it to the Ristretto element it represents, so `k·B` in ed25519 becomes `k·G`.
+//! Points with a torsion component are rejected rather than "cleaned" by multiplying by the
+//! cofactor, which would silently change the imported value.
+//!
+//! [`frost`] converts slices to and from the key packages of RFC 9591 implementations.
+
+mod field;
+pub mod frost;
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::vss::VssCommitments;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use field::Fe;
+
+fn rejected(reason: &str) -> CryptoError {
+    CryptoError::Validation {
+        field: "edwards_point".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Decode an ed25519 point, rejecting non-canonical encodings, small-order points and points
+/// with a torsion component.
+pub fn edwards_point(bytes: &[u8; 32]) -> CryptoResult<EdwardsPoint> {
+    let point = CompressedEdwardsY(*bytes)
+        .decompress()
+        .ok_or_else(|| rejected("not a valid Edwards point"))?;
+    if point.compress().as_bytes() != bytes {
+        return Err(rejected("non-canonical encoding"));
+    }
+    if point.is_small_order() {
+        return Err(rejected("small-order point"));
+    }
+    if !point.is_torsion_free() {
+        return Err(rejected("point has a torsion component"));
+    }
+    Ok(point)
+}
+
+/// Import an ed25519 point as the Ristretto element it represents.
+///
+/// The point is validated with [`edwards_point`]; `k·B` (the ed25519 base point) maps to `k·G`.
+pub fn ristretto_from_edwards(bytes: &[u8; 32]) -> CryptoResult<RistrettoPoint> {
+    edwards_point(bytes)?;
+    CompressedRistretto(ristretto_encoding(bytes))
+        .decompress()
+        .ok_or_else(|| rejected("point has no Ristretto encoding"))
+}
+
+/// Import the commitment vector of a dealing run by an ed25519-based system, lowest degree first.
+pub fn commitments_from_edwards(coefficients: &[[u8; 32]]) -> CryptoResult<VssCommitments> {
+    let coefficients = coefficients
+        .iter()
+        .map(ristretto_from_edwards)
+        .collect::<CryptoResult<_>>()?;
+    Ok(VssCommitments { coefficients })
+}
+
+/// Ristretto encoding of the torsion-free Edwards point with canonical encoding `bytes`.
+fn ristretto_encoding(bytes: &[u8; 32]) -> [u8; 32] {
+    let d = -(Fe::from_u64(121665) * Fe::from_u64(121666).invert());
+    let sqrt_m1 = Fe::sqrt_m1();
+    let (_, invsqrt_a_minus_d) = Fe::sqrt_ratio_i(Fe::ONE, -Fe::ONE - d);
+
+    // Affine coordinates: x² = (y² - 1) / (d·y² + 1), with the sign of x in the top bit.
+    let y = Fe::from_bytes(bytes);
+    let y2 = y.square();
+    let (_, mut x) = Fe::sqrt_ratio_i(y2 - Fe::ONE, d * y2 + Fe::ONE);
+    if bytes[31] >> 7 == 1 {
+        x = -x;
+    }
+
+    // Ristretto encoding of (X : Y : Z : T) = (x : y : 1 : x·y).
+    let (mut x, mut y, z, t) = (x, y, Fe::ONE, x * y);
+    let u1 = (z + y) * (z - y);
+    let u2 = x * y;
+    let (_, invsqrt) = Fe::sqrt_ratio_i(Fe::ONE, u1 * u2.square());
+    let den1 = invsqrt * u1;
+    let den2 = invsqrt * u2;
+    let z_inv = den1 * den2 * t;
+    let mut den_inv = den2;
+    if (t * z_inv).is_negative() {
+        (x, y) = (y * sqrt_m1, x * sqrt_m1);
+        den_inv = den1 * invsqrt_a_minus_d;
+    }
+    if (x * z_inv).is_negative() {
+        y = -y;
+    }
+    (den_inv * (z - y)).abs().to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::{
+        ED25519_BASEPOINT_POINT, EIGHT_TORSION, RISTRETTO_BASEPOINT_POINT,
+    };
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_edwards_import_maps_base_multiples() {
+        for k in (1u64..40).chain([u64::MAX]) {
+            let k = Scalar::from(k) * Scalar::from(0x9e3779b97f4a7c15u64);
+            let edwards = (ED25519_BASEPOINT_POINT * k).compress().to_bytes();
+            assert_eq!(
+                ristretto_from_edwards(&edwards).unwrap(),
+                RISTRETTO_BASEPOINT_POINT * k
+            );
+        }
+    }
+
+    #[test]
+    fn test_edwards_import_rejects_torsion() {
+        let point = ED25519_BASEPOINT_POINT * Scalar::from(5u64);
+        for torsion in &EIGHT_TORSION[1..] {
+            let mixed = (point + torsion).compress().to_bytes();
+            assert!(ristretto_from_edwards(&mixed).is_err());
+            assert!(edwards_point(&torsion.compress().to_bytes()).is_err());
+        }
+        // The identity is small-order.
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(edwards_point(&identity).is_err());
+        // y = p + 1 is a non-canonical encoding of the identity.
+        let mut unreduced = [0xff; 32];
+        unreduced[0] = 0xee;
+        unreduced[31] = 0x7f;
+        assert!(edwards_point(&unreduced).is_err());
+
+        let good = point.compress().to_bytes();
+        let commitments = commitments_from_edwards(&[good, good]).unwrap();
+        assert_eq!(commitments.threshold(), 2);
+        let bad = (point + EIGHT_TORSION[4]).compress().to_bytes();
+        assert!(commitments_from_edwards(&[good, bad]).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! **Arithmetic in GF(2²⁵⁵ - 19)**
+//!
+//! Just enough of the Curve25519 base field to recover an Edwards `x` coordinate and compute the
+//! Ristretto encoding of a torsion-free point, which `curve25519-dalek` does not expose for
+//! `EdwardsPoint`. Elements are five 51-bit limbs, little-endian.
+//!
+//! Only public points are imported, so nothing here is constant-time: comparisons, signs and
+//! exponentiation all branch on their inputs.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+const MASK: u64 = (1 << 51) - 1;
+
+/// `16·p` in limb form, added before subtracting so limbs never underflow.
+const SIXTEEN_P: [u64; 5] = [
+    36028797018963664,
+    36028797018963952,
+    36028797018963952,
+    36028797018963952,
+    36028797018963952,
+];
+
+/// `p - 2`, the inversion exponent, little-endian.
+const P_MINUS_2: [u8; 32] = exponent(0xeb, 0x7f);
+/// `(p - 5) / 8 = 2²⁵² - 3`, the square-root exponent.
+const P_MINUS_5_OVER_8: [u8; 32] = exponent(0xfd, 0x0f);
+/// `(p - 1) / 4 = 2²⁵³ - 5`; `2` raised to it is a square root of `-1`.
+const P_MINUS_1_OVER_4: [u8; 32] = exponent(0xfb, 0x1f);
+
+/// Little-endian exponent with the given low and high bytes and `0xff` in between.
+const fn exponent(low: u8, high: u8) -> [u8; 32] {
+    let mut bytes = [0xff; 32];
+    bytes[0] = low;
+    bytes[31] = high;
+    bytes
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Fe([u64; 5]);
+
+impl Fe {
+    pub(super) const ZERO: Fe = Fe([0; 5]);
+    pub(super) const ONE: Fe = Fe([1, 0, 0, 0, 0]);
+
+    pub(super) fn from_u64(value: u64) -> Fe {
+        Fe([value & MASK, value >> 51, 0, 0, 0])
+    }
+
+    /// Load 255 bits, ignoring the top bit. The value need not be reduced.
+    pub(super) fn from_bytes(bytes: &[u8; 32]) -> Fe {
+        let load =
+            |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        Fe([
+            load(0) & MASK,
+            (load(6) >> 3) & MASK,
+            (load(12) >> 6) & MASK,
+            (load(19) >> 1) & MASK,
+            (load(24) >> 12) & MASK,
+        ])
+    }
+
+    /// Canonical little-endian encoding, fully reduced mod `p`.
+    pub(super) fn to_bytes(self) -> [u8; 32] {
+        let mut l = self.carry().0;
+        // q = 1 iff the value is at least p.
+        let mut q = (l[0] + 19) >> 51;
+        for limb in &l[1..] {
+            q = (limb + q) >> 51;
+        }
+        l[0] += 19 * q;
+        for i in 0..4 {
+            l[i + 1] += l[i] >> 51;
+            l[i] &= MASK;
+        }
+        l[4] &= MASK;
+
+        let mut bytes = [0u8; 32];
+        let mut acc: u128 = 0;
+        let mut bits = 0;
+        let mut out = 0;
+        for limb in l {
+            acc |= (limb as u128) << bits;
+            bits += 51;
+            while bits >= 8 {
+                bytes[out] = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+                out += 1;
+            }
+        }
+        bytes[out] = acc as u8;
+        bytes
+    }
+
+    /// Propagate carries so every limb is below `2⁵¹` (plus a small excess in limb 0).
+    fn carry(self) -> Fe {
+        let mut l = self.0;
+        for i in 0..4 {
+            l[i + 1] += l[i] >> 51;
+            l[i] &= MASK;
+        }
+        l[0] += 19 * (l[4] >> 51);
+        l[4] &= MASK;
+        Fe(l)
+    }
+
+    pub(super) fn square(self) -> Fe {
+        self * self
+    }
+
+    /// `self^e` for a little-endian exponent.
+    fn pow(self, e: &[u8; 32]) -> Fe {
+        let mut result = Fe::ONE;
+        for byte in e.iter().rev() {
+            for bit in (0..8).rev() {
+                result = result.square();
+                if (byte >> bit) & 1 == 1 {
+                    result = result * self;
+                }
+            }
+        }
+        result
+    }
+
+    pub(super) fn invert(self) -> Fe {
+        self.pow(&P_MINUS_2)
+    }
+
+    /// Whether the canonical encoding is odd, the "negative" elements of the Ristretto spec.
+    pub(super) fn is_negative(self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    pub(super) fn abs(self) -> Fe {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    pub(super) fn sqrt_m1() -> Fe {
+        Fe::from_u64(2).pow(&P_MINUS_1_OVER_4)
+    }
+
+    /// `(was_square, r)` with `r` non-negative and `r² = u/v` if `u/v` is a square; otherwise
+    /// `r² = i·u/v`. As `sqrt_ratio_m1` in the Ristretto specification.
+    pub(super) fn sqrt_ratio_i(u: Fe, v: Fe) -> (bool, Fe) {
+        let i = Fe::sqrt_m1();
+        let v3 = v.square() * v;
+        let v7 = v3.square() * v;
+        let mut r = (u * v3) * (u * v7).pow(&P_MINUS_5_OVER_8);
+        let check = v * r.square();
+
+        let correct = check == u;
+        let flipped = check == -u;
+        let flipped_i = check == -(u * i);
+        if flipped || flipped_i {
+            r = r * i;
+        }
+        (correct || flipped, r.abs())
+    }
+}
+
+impl PartialEq for Fe {
+    fn eq(&self, other: &Fe) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Add for Fe {
+    type Output = Fe;
+
+    fn add(self, rhs: Fe) -> Fe {
+        let mut l = self.0;
+        for (a, b) in l.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+        Fe(l).carry()
+    }
+}
+
+impl Sub for Fe {
+    type Output = Fe;
+
+    fn sub(self, rhs: Fe) -> Fe {
+        let (a, b) = (self.carry().0, rhs.carry().0);
+        let mut l = [0u64; 5];
+        for i in 0..5 {
+            l[i] = a[i] + SIXTEEN_P[i] - b[i];
+        }
+        Fe(l).carry()
+    }
+}
+
+impl Neg for Fe {
+    type Output = Fe;
+
+    fn neg(self) -> Fe {
+        Fe::ZERO - self
+    }
+}
+
+impl Mul for Fe {
+    type Output = Fe;
+
+    fn mul(self, rhs: Fe) -> Fe {
+        let (a, b) = (self.carry().0, rhs.carry().0);
+        let m = |x: u64, y: u64| (x as u128) * (y as u128);
+        let b19 = [0, b[1] * 19, b[2] * 19, b[3] * 19, b[4] * 19];
+
+        let c = [
+            m(a[0], b[0]) + m(a[4], b19[1]) + m(a[3], b19[2]) + m(a[2], b19[3]) + m(a[1], b19[4]),
+            m(a[1], b[0]) + m(a[0], b[1]) + m(a[4], b19[2]) + m(a[3], b19[3]) + m(a[2], b19[4]),
+            m(a[2], b[0]) + m(a[1], b[1]) + m(a[0], b[2]) + m(a[4], b19[3]) + m(a[3], b19[4]),
+            m(a[3], b[0]) + m(a[2], b[1]) + m(a[1], b[2]) + m(a[0], b[3]) + m(a[4], b19[4]),
+            m(a[4], b[0]) + m(a[3], b[1]) + m(a[2], b[2]) + m(a[1], b[3]) + m(a[0], b[4]),
+        ];
+
+        let mut l = [0u64; 5];
+        let mut carry: u128 = 0;
+        for i in 0..5 {
+            let value = c[i] + carry;
+            l[i] = (value as u64) & MASK;
+            carry = value >> 51;
+        }
+        // carry < 2^64 / 19 for carried inputs, so this fits.
+        l[0] += (carry as u64) * 19;
+        Fe(l).carry()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_identities() {
+        let i = Fe::sqrt_m1();
+        assert_eq!(i.square(), -Fe::ONE);
+        let x = Fe::from_u64(123456789);
+        assert_eq!(x * x.invert(), Fe::ONE);
+        assert_eq!(x - x, Fe::ZERO);
+        let (was_square, r) = Fe::sqrt_ratio_i(Fe::from_u64(9), Fe::from_u64(4));
+        assert!(was_square);
+        assert_eq!(r.square() * Fe::from_u64(4), Fe::from_u64(9));
+        assert!(!r.is_negative());
+
+        // p itself is the zero element, and its encoding reduces to zero bytes.
+        let mut p = [0xff; 32];
+        p[0] = 0xed;
+        p[31] = 0x7f;
+        assert_eq!(Fe::from_bytes(&p).to_bytes(), [0; 32]);
+    }
+}
@@ -0,0 +1,494 @@
+//! Key packages of the `frost-ristretto255` crate (Zcash Foundation `frost-core`), RFC 9591's
+//! FROST(ristretto255, SHA-512) ciphersuite.
+//!
+//! A slice carries over unchanged: its index is the FROST identifier `Scalar::from(index)`, its
+//! value the signing share, and `s_i·G` the verifying share, so slices of a dealing or a
+//! [`DkgOutput`](crate::dkg::DkgOutput) can sign with RFC 9591 implementations and their key
+//! packages can be turned back into slices. The types serialize exactly as `frost-core`'s serde
+//! JSON: a `header` with format version 0 and the ciphersuite identifier, scalars and elements
+//! as lowercase hex of their canonical encodings, and the public key package's verifying shares
+//! as an object keyed by identifier. Only identifiers that are small integers (`u32`) map to
+//! slice indices; identifiers derived by hashing are rejected.
+//!
+//! Signing itself does not interoperate: [`crate::frost`] hashes with BLAKE3 rather than the
+//! SHA-512 of RFC 9591, so a signing session runs entirely in one implementation.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::frost::{PublicKeyPackage, VerificationShare};
+use crate::serialization::{SerRistrettoPoint, SerScalar};
+#[cfg(feature = "dealer")]
+use crate::sharing::ShareData;
+#[cfg(feature = "dealer")]
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "dealer")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Ciphersuite identifier of FROST(ristretto255, SHA-512) in RFC 9591 and `frost-core` headers.
+pub const FROST_RISTRETTO255_CIPHERSUITE: &str = "FROST-RISTRETTO255-SHA512-v1";
+
+/// `frost-core` serialization format version.
+pub const FROST_FORMAT_VERSION: u8 = 0;
+
+fn invalid(field: &str, reason: String) -> CryptoError {
+    CryptoError::Validation {
+        field: field.to_string(),
+        reason,
+    }
+}
+
+/// Header of every `frost-core` serialized structure.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FrostHeader {
+    pub version: u8,
+    pub ciphersuite: String,
+}
+
+impl Default for FrostHeader {
+    fn default() -> Self {
+        Self {
+            version: FROST_FORMAT_VERSION,
+            ciphersuite: FROST_RISTRETTO255_CIPHERSUITE.to_string(),
+        }
+    }
+}
+
+impl FrostHeader {
+    fn check(&self) -> CryptoResult<()> {
+        if self.version != FROST_FORMAT_VERSION {
+            return Err(invalid(
+                "header",
+                format!("unsupported frost-core format version {}", self.version),
+            ));
+        }
+        if self.ciphersuite != FROST_RISTRETTO255_CIPHERSUITE {
+            return Err(invalid(
+                "header",
+                format!("ciphersuite {} is not ristretto255", self.ciphersuite),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Identifier of slice `index`.
+fn identifier(index: usize) -> CryptoResult<Scalar> {
+    match u32::try_from(index) {
+        Ok(i) if i != 0 => Ok(Scalar::from(i)),
+        _ => Err(invalid(
+            "index",
+            format!("slice {} has no FROST identifier", index),
+        )),
+    }
+}
+
+/// Slice index of a FROST identifier, for identifiers below `2^32`.
+fn index_of(identifier: &Scalar) -> CryptoResult<usize> {
+    let bytes = identifier.as_bytes();
+    let small = bytes[4..].iter().all(|&b| b == 0);
+    let index = u32::from_le_bytes(bytes[..4].try_into().expect("4-byte prefix"));
+    if !small || index == 0 {
+        return Err(invalid(
+            "identifier",
+            format!("identifier {} is not a slice index", hex::encode(bytes)),
+        ));
+    }
+    Ok(index as usize)
+}
+
+/// `frost_ristretto255::keys::KeyPackage`: one participant's signing material.
+///
+/// `Debug` output redacts the signing share.
+#[cfg(feature = "dealer")]
+#[derive(Serialize, Deserialize, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct FrostKeyPackage {
+    #[zeroize(skip)]
+    pub header: FrostHeader,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub identifier: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub signing_share: Scalar,
+    #[zeroize(skip)]
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub verifying_share: RistrettoPoint,
+    #[zeroize(skip)]
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub verifying_key: RistrettoPoint,
+    pub min_signers: u16,
+}
+
+#[cfg(feature = "dealer")]
+impl std::fmt::Debug for FrostKeyPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrostKeyPackage")
+            .field("header", &self.header)
+            .field("identifier", &hex::encode(self.identifier.as_bytes()))
+            .field(
+                "signing_share",
+                &crate::redact::Redacted(&self.signing_share),
+            )
+            .field(
+                "verifying_share",
+                &hex::encode(self.verifying_share.compress().as_bytes()),
+            )
+            .field(
+                "verifying_key",
+                &hex::encode(self.verifying_key.compress().as_bytes()),
+            )
+            .field("min_signers", &self.min_signers)
+            .finish()
+    }
+}
+
+#[cfg(feature = "dealer")]
+impl FrostKeyPackage {
+    /// Key package of `share` in a `min_signers`-of-n sharing of the key with public key
+    /// `verifying_key`.
+    ///
+    /// Returns `CryptoError::Validation` for an index without identifier or `min_signers` of 0.
+    pub fn from_share(
+        share: &ShareData,
+        verifying_key: RistrettoPoint,
+        min_signers: usize,
+    ) -> CryptoResult<Self> {
+        let min_signers = u16::try_from(min_signers)
+            .ok()
+            .filter(|&m| m != 0)
+            .ok_or_else(|| {
+                invalid(
+                    "min_signers",
+                    format!("{} is not a FROST threshold", min_signers),
+                )
+            })?;
+        Ok(Self {
+            header: FrostHeader::default(),
+            identifier: identifier(share.index)?,
+            signing_share: share.share,
+            verifying_share: crate::threshold_sig::verification_share(share),
+            verifying_key,
+            min_signers,
+        })
+    }
+
+    /// Key package of one participant's DKG output; the threshold is the number of joint
+    /// commitments.
+    pub fn from_dkg_output(output: &crate::dkg::DkgOutput) -> CryptoResult<Self> {
+        Self::from_share(
+            &output.share,
+            output.group_public_key,
+            output.commitments.coefficients.len(),
+        )
+    }
+
+    /// Turn an imported key package into a slice, with a fresh blinding, its commitment and a
+    /// proof of the opening. The vector commitments of the original dealing are not known here,
+    /// so the slice cannot be checked against them.
+    ///
+    /// `verifying_key` and `min_signers` are not carried into the slice; check them against the
+    /// [`FrostPublicKeyPackage`] of the same key.
+    ///
+    /// Returns `CryptoError::Validation` for a foreign header or ciphersuite, an identifier that
+    /// is not a slice index, `min_signers` of 0 or a verifying share that does not match the
+    /// signing share, and `CryptoError::InsufficientEntropy` if the blinding or proof nonces
+    /// cannot be drawn.
+    pub fn to_share(&self) -> CryptoResult<ShareData> {
+        self.header.check()?;
+        let index = index_of(&self.identifier)?;
+        if self.min_signers == 0 {
+            return Err(invalid(
+                "min_signers",
+                "0 is not a FROST threshold".to_string(),
+            ));
+        }
+        if RISTRETTO_BASEPOINT_POINT * self.signing_share != self.verifying_share {
+            return Err(invalid(
+                "verifying_share",
+                "does not match the signing share".to_string(),
+            ));
+        }
+        let random = crate::utils::random_scalar(&mut crate::utils::new_rng())?;
+        let commitment = crate::utils::pedersen_commit(&self.signing_share, &random);
+        let proof = crate::proof::generate_proof(self.signing_share, random, index, commitment)?;
+        Ok(ShareData {
+            index,
+            share: self.signing_share,
+            commitment,
+            random,
+            proof,
+        })
+    }
+}
+
+/// `frost_ristretto255::keys::PublicKeyPackage`: the group key and every verifying share.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FrostPublicKeyPackage {
+    pub header: FrostHeader,
+    /// Verifying shares, serialized as an object from identifier to element.
+    #[serde(with = "verifying_shares")]
+    pub verifying_shares: Vec<(Scalar, RistrettoPoint)>,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub verifying_key: RistrettoPoint,
+    /// Threshold, written by `frost-core` 2.1 and later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_signers: Option<u16>,
+}
+
+impl FrostPublicKeyPackage {
+    /// Export `package`, ordered by index.
+    ///
+    /// Returns `CryptoError::Validation` for an index without identifier.
+    pub fn from_package(package: &PublicKeyPackage) -> CryptoResult<Self> {
+        let mut shares: Vec<&VerificationShare> = package.verification_shares.iter().collect();
+        shares.sort_by_key(|v| v.index);
+        Ok(Self {
+            header: FrostHeader::default(),
+            verifying_shares: shares
+                .into_iter()
+                .map(|v| Ok((identifier(v.index)?, v.point)))
+                .collect::<CryptoResult<_>>()?,
+            verifying_key: package.group_public_key,
+            min_signers: None,
+        })
+    }
+
+    /// Import as a [`PublicKeyPackage`] for [`crate::frost::aggregate`].
+    ///
+    /// Returns `CryptoError::Validation` for a foreign header or ciphersuite, an identifier that
+    /// is not a slice index, a repeated identifier, or `min_signers` of 0 or above the number of
+    /// verifying shares.
+    pub fn to_package(&self) -> CryptoResult<PublicKeyPackage> {
+        self.header.check()?;
+        if let Some(min_signers) = self.min_signers {
+            if min_signers == 0 || usize::from(min_signers) > self.verifying_shares.len() {
+                return Err(invalid(
+                    "min_signers",
+                    format!(
+                        "{} is not a threshold for {} verifying shares",
+                        min_signers,
+                        self.verifying_shares.len()
+                    ),
+                ));
+            }
+        }
+        let mut verification_shares = self
+            .verifying_shares
+            .iter()
+            .map(|(id, point)| {
+                Ok(VerificationShare {
+                    index: index_of(id)?,
+                    point: *point,
+                })
+            })
+            .collect::<CryptoResult<Vec<_>>>()?;
+        verification_shares.sort_by_key(|v| v.index);
+        if verification_shares
+            .windows(2)
+            .any(|w| w[0].index == w[1].index)
+        {
+            return Err(invalid(
+                "verifying_shares",
+                "repeated identifier".to_string(),
+            ));
+        }
+        Ok(PublicKeyPackage {
+            group_public_key: self.verifying_key,
+            verification_shares,
+        })
+    }
+}
+
+/// `(identifier, element)` pairs as a JSON object keyed by the identifier's hex.
+mod verifying_shares {
+    use super::{SerRistrettoPoint, SerScalar};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        shares: &[(Scalar, RistrettoPoint)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            shares
+                .iter()
+                .map(|(id, point)| (SerScalar(*id), SerRistrettoPoint(*point))),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(Scalar, RistrettoPoint)>, D::Error> {
+        let map =
+            std::collections::BTreeMap::<String, SerRistrettoPoint>::deserialize(deserializer)?;
+        map.into_iter()
+            .map(|(id, point)| {
+                let id = SerScalar::deserialize(
+                    serde::de::value::StrDeserializer::<D::Error>::new(&id),
+                )?;
+                Ok((id.0, point.0))
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::dkg::{DkgOutput, DkgParticipant, UnsignedDkgParticipant};
+
+    fn dkg(threshold: usize, n: usize) -> Vec<DkgOutput> {
+        let mut parties: Vec<UnsignedDkgParticipant> = (1..=n)
+            .map(|i| DkgParticipant::insecure_unsigned(i, threshold, n).unwrap())
+            .collect();
+        let broadcasts: Vec<_> = parties.iter_mut().map(|p| p.round1().unwrap()).collect();
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(b.clone()).unwrap();
+            }
+        }
+        let messages: Vec<_> = parties
+            .iter_mut()
+            .flat_map(|p| p.round2().unwrap())
+            .collect();
+        for m in messages {
+            let recipient = m.recipient;
+            assert!(parties[recipient - 1].receive_round2(m).unwrap().is_none());
+        }
+        parties.iter_mut().map(|p| p.finalize().unwrap()).collect()
+    }
+
+    /// Packages of the 2-of-3 sharing `f(x) = 5 + 2x`, written in `frost-core`'s serde JSON
+    /// layout with the multiples of the basepoint from RFC 9496 appendix A.1. They were written
+    /// by hand, not produced by `frost-core`.
+    const KEY_PACKAGE_1: &str = concat!(
+        r#"{"header":{"version":0,"ciphersuite":"FROST-RISTRETTO255-SHA512-v1"},"#,
+        r#""identifier":"0100000000000000000000000000000000000000000000000000000000000000","#,
+        r#""signing_share":"0700000000000000000000000000000000000000000000000000000000000000","#,
+        r#""verifying_share":"44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d","#,
+        r#""verifying_key":"e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e","#,
+        r#""min_signers":2}"#,
+    );
+    const PUBLIC_KEY_PACKAGE: &str = concat!(
+        r#"{"header":{"version":0,"ciphersuite":"FROST-RISTRETTO255-SHA512-v1"},"#,
+        r#""verifying_shares":{"#,
+        r#""0100000000000000000000000000000000000000000000000000000000000000":"#,
+        r#""44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d","#,
+        r#""0200000000000000000000000000000000000000000000000000000000000000":"#,
+        r#""02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031","#,
+        r#""0300000000000000000000000000000000000000000000000000000000000000":"#,
+        r#""bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42"},"#,
+        r#""verifying_key":"e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e"}"#,
+    );
+
+    #[test]
+    fn test_frost_package_fixtures() {
+        let key: FrostKeyPackage = serde_json::from_str(KEY_PACKAGE_1).unwrap();
+        let share = key.to_share().unwrap();
+        assert_eq!((share.index, share.share), (1, Scalar::from(7u8)));
+        let exported = FrostKeyPackage::from_share(&share, key.verifying_key, 2).unwrap();
+        assert_eq!(serde_json::to_string(&exported).unwrap(), KEY_PACKAGE_1);
+
+        let public: FrostPublicKeyPackage = serde_json::from_str(PUBLIC_KEY_PACKAGE).unwrap();
+        let package = public.to_package().unwrap();
+        assert_eq!(
+            package.group_public_key,
+            RISTRETTO_BASEPOINT_POINT * Scalar::from(5u8)
+        );
+        assert_eq!(
+            crate::threshold_sig::group_public_key(&[
+                (2, package.verification_share(2).unwrap()),
+                (3, package.verification_share(3).unwrap()),
+            ])
+            .unwrap(),
+            package.group_public_key
+        );
+        let exported = FrostPublicKeyPackage::from_package(&package).unwrap();
+        assert_eq!(
+            serde_json::to_string(&exported).unwrap(),
+            PUBLIC_KEY_PACKAGE
+        );
+
+        let zero = KEY_PACKAGE_1.replace(r#""min_signers":2"#, r#""min_signers":0"#);
+        let zero: FrostKeyPackage = serde_json::from_str(&zero).unwrap();
+        assert!(matches!(
+            zero.to_share(),
+            Err(CryptoError::Validation { field, .. }) if field == "min_signers"
+        ));
+        for min_signers in [0, 4] {
+            let mut public = public.clone();
+            public.min_signers = Some(min_signers);
+            assert!(public.to_package().is_err());
+        }
+    }
+
+    #[test]
+    fn test_frost_key_packages_roundtrip() {
+        let outputs = dkg(2, 3);
+        let package = FrostKeyPackage::from_dkg_output(&outputs[2]).unwrap();
+        let json = serde_json::to_value(&package).unwrap();
+        assert_eq!(json["header"]["version"], 0);
+        assert_eq!(
+            json["header"]["ciphersuite"],
+            FROST_RISTRETTO255_CIPHERSUITE
+        );
+        assert_eq!(
+            json["identifier"],
+            "0300000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(json["min_signers"], 2);
+        assert!(!format!("{:?}", package).contains(&hex::encode(outputs[2].share.share.as_bytes())));
+
+        let imported: FrostKeyPackage = serde_json::from_value(json.clone()).unwrap();
+        let share = imported.to_share().unwrap();
+        assert_eq!(share.index, 3);
+        assert_eq!(share.share, outputs[2].share.share);
+        assert!(crate::proof::verify_proof(&share.proof, share.commitment, 3).is_ok());
+
+        let mut foreign = json.clone();
+        foreign["header"]["ciphersuite"] = "FROST-ED25519-SHA512-v1".into();
+        let foreign: FrostKeyPackage = serde_json::from_value(foreign).unwrap();
+        assert!(foreign.to_share().is_err());
+        let mut derived = json.clone();
+        derived["identifier"] =
+            "0300000000000000000000000000000000000000000000000000000000000001".into();
+        let derived: FrostKeyPackage = serde_json::from_value(derived).unwrap();
+        assert!(matches!(
+            derived.to_share(),
+            Err(CryptoError::Validation { .. })
+        ));
+
+        let public = PublicKeyPackage {
+            group_public_key: outputs[0].group_public_key,
+            verification_shares: outputs
+                .iter()
+                .rev()
+                .map(|o| VerificationShare {
+                    index: o.share.index,
+                    point: crate::threshold_sig::verification_share(&o.share),
+                })
+                .collect(),
+        };
+        let exported = FrostPublicKeyPackage::from_package(&public).unwrap();
+        let json = serde_json::to_value(&exported).unwrap();
+        let shares = json["verifying_shares"].as_object().unwrap();
+        assert_eq!(shares.len(), 3);
+        assert!(
+            shares.contains_key("0100000000000000000000000000000000000000000000000000000000000000")
+        );
+        assert!(json.get("min_signers").is_none());
+        let back: FrostPublicKeyPackage = serde_json::from_value(json).unwrap();
+        let imported = back.to_package().unwrap();
+        assert_eq!(imported.group_public_key, public.group_public_key);
+        assert_eq!(imported.verification_share(2), public.verification_share(2));
+        assert_eq!(
+            crate::threshold_sig::group_public_key(&[
+                (1, imported.verification_share(1).unwrap()),
+                (3, imported.verification_share(3).unwrap()),
+            ])
+            .unwrap(),
+            outputs[0].group_public_key
+        );
+    }
+}
@@ -1,13 +1,36 @@
 //! **key_lifecycle module**
 //!
 //! This module manages the key lifecycle, in compliance with NIST SP 800-57, including the states of key generation, activation, retirement, and destruction.
+//!
+//! [`KeyRotationManager`] bounds the cryptoperiod of a key's sharing: it tracks when each key's
+//! slices were last issued and, once they are older than
+//! [`EnterpriseConfig::max_key_lifetime_hours`](crate::EnterpriseConfig::max_key_lifetime_hours),
+//! rotates them with [`EnterpriseCryptoSystem::rotate_key_shares`]. Rotation keeps the secret and
+//! public key (a proactive refresh re-issues every slice, commitment and proof) and retires the
+//! previous slices, so custodian material never outlives the configured lifetime.
 
-use chrono::{DateTime, Utc};
+use crate::context::OperationContext;
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::{Blake3Adapter, HashAdapter};
+use crate::redact::Redacted;
+use crate::sharing::ShareData;
+use crate::{EnterpriseConfig, EnterpriseCryptoSystem};
+use chrono::{DateTime, TimeDelta, Utc};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const KEY_MATERIAL_CONTEXT: &str = "zk-thresh-pro 2024 key material v1";
 
 /// The possible states of a Key in its lifecycle.
-#[derive(Debug)]
+///
+/// A key moves forward only: `Generated → Active → Retired → Destroyed`, and a key that was
+/// never activated may be destroyed directly. An active key must be retired before it is
+/// destroyed, and nothing leaves `Destroyed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KeyState {
     Generated,
     Active,
@@ -15,10 +38,24 @@ pub enum KeyState {
     Destroyed,
 }
 
+impl KeyState {
+    /// Whether a key may move from `self` to `next`.
+    pub fn can_transition_to(self, next: KeyState) -> bool {
+        matches!(
+            (self, next),
+            (KeyState::Generated, KeyState::Active)
+                | (KeyState::Active, KeyState::Retired)
+                | (KeyState::Generated | KeyState::Retired, KeyState::Destroyed)
+        )
+    }
+}
+
 /// A secret‐holding Key with full lifecycle management.
 /// Only the `secret` field will be zeroed on drop;
 /// all other fields are skipped.
-#[derive(Debug, Zeroize, ZeroizeOnDrop)]
+///
+/// `Debug` output redacts the secret and shows its fingerprint only.
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Key {
     /// The secret part of the key (will be zeroized on Drop)
     pub secret: Scalar,
@@ -38,6 +75,10 @@ pub struct Key {
     /// Key Retirement Time (not secret)
     #[zeroize(skip)]
     pub retirement_time: Option<DateTime<Utc>>,
+
+    /// Time from which the key may no longer be used (not secret); `None` if it never expires
+    #[zeroize(skip)]
+    pub expiry_time: Option<DateTime<Utc>>,
 }
 
 impl Key {
@@ -49,26 +90,396 @@ impl Key {
             creation_time: Utc::now(),
             activation_time: None,
             retirement_time: None,
+            expiry_time: None,
         }
     }
 
-    /// Activate the key.
-    pub fn activate(&mut self) {
-        self.state = KeyState::Active;
+    /// Move to `next`, or return `CryptoError::InvalidKeyStateTransition` and leave the key
+    /// unchanged if [`KeyState::can_transition_to`] forbids it.
+    fn transition(&mut self, next: KeyState) -> CryptoResult<()> {
+        if !self.state.can_transition_to(next) {
+            return Err(CryptoError::InvalidKeyStateTransition {
+                from: format!("{:?}", self.state),
+                to: format!("{:?}", next),
+            });
+        }
+        self.state = next;
+        Ok(())
+    }
+
+    /// Activate a generated key.
+    pub fn activate(&mut self) -> CryptoResult<()> {
+        self.transition(KeyState::Active)?;
         self.activation_time = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Activate a generated key for `lifetime_hours`, setting [`Key::expiry_time`].
+    ///
+    /// A lifetime too long to represent leaves the key without expiry.
+    pub fn activate_for(&mut self, lifetime_hours: u64) -> CryptoResult<()> {
+        self.activate()?;
+        self.expiry_time = i64::try_from(lifetime_hours)
+            .ok()
+            .and_then(TimeDelta::try_hours)
+            .and_then(|lifetime| self.activation_time?.checked_add_signed(lifetime));
+        Ok(())
+    }
+
+    /// Whether the key has reached its expiry time.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
     }
 
-    /// Retire the key (mark as no longer in use).
-    pub fn retire(&mut self) {
-        self.state = KeyState::Retired;
+    /// [`Self::is_expired`] at `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expiry_time.is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Retire an active key (mark as no longer in use).
+    pub fn retire(&mut self) -> CryptoResult<()> {
+        self.transition(KeyState::Retired)?;
         self.retirement_time = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Public key `secret·G`, under which threshold signatures of the key's slices verify
+    /// (see [`crate::threshold_sig`]).
+    pub fn public_key(&self) -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT * self.secret
+    }
+
+    /// `len` bytes of key material for `context`, from the BLAKE3 XOF keyed by the secret.
+    ///
+    /// `context` names the application and purpose (e.g. `"acme 2024 backup encryption"`) and is
+    /// length-prefixed before the secret, so distinct contexts yield independent material. The
+    /// output is a keystream: a shorter request is a prefix of a longer one for the same context,
+    /// so split one derivation rather than deriving the same context at two lengths.
+    ///
+    /// Returns `CryptoError::Validation` for an empty `context`, a zero `len` or a destroyed key.
+    pub fn derive_material(&self, context: &str, len: usize) -> CryptoResult<Zeroizing<Vec<u8>>> {
+        self.derive_material_with(&Blake3Adapter::new(), context, len)
+    }
+
+    /// [`Self::derive_material`] with the key derivation of `hash`.
+    ///
+    /// SHA-256 derives at most 8160 bytes, and with SHA-3 (KMAC) a shorter request is not a prefix
+    /// of a longer one.
+    pub fn derive_material_with(
+        &self,
+        hash: &dyn HashAdapter,
+        context: &str,
+        len: usize,
+    ) -> CryptoResult<Zeroizing<Vec<u8>>> {
+        let invalid = |field: &str, reason: &str| CryptoError::Validation {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        };
+        if self.state == KeyState::Destroyed {
+            return Err(invalid("key", "the key has been destroyed"));
+        }
+        if context.is_empty() {
+            return Err(invalid("context", "must not be empty"));
+        }
+        if len == 0 {
+            return Err(invalid("len", "must be at least 1"));
+        }
+        let mut input = Zeroizing::new(Vec::with_capacity(8 + context.len() + 32));
+        input.extend_from_slice(&(context.len() as u64).to_le_bytes());
+        input.extend_from_slice(context.as_bytes());
+        input.extend_from_slice(self.secret.as_bytes());
+        let mut material = Zeroizing::new(vec![0u8; len]);
+        hash.derive_key(KEY_MATERIAL_CONTEXT, &input, &mut material)?;
+        Ok(material)
     }
 
-    /// Explicitly destroy the key now.
+    /// Explicitly destroy a retired or never-activated key now.
     /// This will zero out the secret immediately.
-    pub fn destroy(&mut self) {
+    ///
+    /// An active key is refused with `CryptoError::InvalidKeyStateTransition`; retire it first.
+    /// Its secret is still zeroized when it is dropped.
+    pub fn destroy(&mut self) -> CryptoResult<()> {
+        self.transition(KeyState::Destroyed)?;
         // zeroize the secret scalar in-place
         self.secret.zeroize();
-        self.state = KeyState::Destroyed;
+        Ok(())
+    }
+}
+
+/// Length of the binary [`Key`] encoding.
+pub const KEY_LENGTH: usize = 32 + 1 + 8 + 3 * 9;
+
+/// Length of the binary [`Key`] encoding before keys had an expiry time.
+pub const LEGACY_KEY_LENGTH: usize = 32 + 1 + 8 + 2 * 9;
+
+impl KeyState {
+    fn to_byte(self) -> u8 {
+        match self {
+            KeyState::Generated => 0,
+            KeyState::Active => 1,
+            KeyState::Retired => 2,
+            KeyState::Destroyed => 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(KeyState::Generated),
+            1 => Some(KeyState::Active),
+            2 => Some(KeyState::Retired),
+            3 => Some(KeyState::Destroyed),
+            _ => None,
+        }
+    }
+}
+
+fn write_time(out: &mut [u8], time: &DateTime<Utc>) {
+    out.copy_from_slice(&time.timestamp_micros().to_le_bytes());
+}
+
+fn read_time(bytes: &[u8], what: &str) -> CryptoResult<DateTime<Utc>> {
+    let micros = i64::from_le_bytes(bytes.try_into().expect("8-byte timestamp"));
+    DateTime::from_timestamp_micros(micros).ok_or_else(|| CryptoError::Serialization {
+        details: format!("{} is out of range", what),
+    })
+}
+
+/// Decode an optional time: a presence byte (0 or 1) then 8 bytes, all zero when absent.
+fn read_optional_time(bytes: &[u8], what: &str) -> CryptoResult<Option<DateTime<Utc>>> {
+    match bytes[0] {
+        0 if bytes[1..].iter().all(|&b| b == 0) => Ok(None),
+        1 => read_time(&bytes[1..], what).map(Some),
+        _ => Err(CryptoError::Serialization {
+            details: format!("invalid encoding of {}", what),
+        }),
+    }
+}
+
+impl Key {
+    /// Fixed-length encoding: the canonical bytes of `secret`, the state byte (0 generated,
+    /// 1 active, 2 retired, 3 destroyed), the creation time, then the activation, retirement and
+    /// expiry times each as a presence byte followed by 8 bytes. Times are little-endian `i64`
+    /// microseconds since the UNIX epoch; sub-microsecond precision is dropped.
+    ///
+    /// The bytes contain the secret and are zeroized when dropped.
+    pub fn to_bytes(&self) -> zeroize::Zeroizing<[u8; KEY_LENGTH]> {
+        let mut bytes = zeroize::Zeroizing::new([0u8; KEY_LENGTH]);
+        bytes[..32].copy_from_slice(self.secret.as_bytes());
+        bytes[32] = self.state.to_byte();
+        write_time(&mut bytes[33..41], &self.creation_time);
+        for (offset, time) in [
+            (41, &self.activation_time),
+            (50, &self.retirement_time),
+            (59, &self.expiry_time),
+        ] {
+            if let Some(time) = time {
+                bytes[offset] = 1;
+                write_time(&mut bytes[offset + 1..offset + 9], time);
+            }
+        }
+        bytes
+    }
+
+    /// Decode [`Key::to_bytes`], rejecting non-canonical secrets, unknown states and
+    /// out-of-range or malformed times.
+    ///
+    /// A [`LEGACY_KEY_LENGTH`] encoding, written before keys had an expiry time, decodes to a key
+    /// that never expires.
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        if bytes.len() != KEY_LENGTH && bytes.len() != LEGACY_KEY_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: format!("key must be {} bytes, got {}", KEY_LENGTH, bytes.len()),
+            });
+        }
+        let state = KeyState::from_byte(bytes[32]).ok_or_else(|| CryptoError::Serialization {
+            details: format!("unknown key state {}", bytes[32]),
+        })?;
+        Ok(Key {
+            secret: crate::serialization::scalar_from_slice(&bytes[..32], "key secret")?,
+            state,
+            creation_time: read_time(&bytes[33..41], "creation time")?,
+            activation_time: read_optional_time(&bytes[41..50], "activation time")?,
+            retirement_time: read_optional_time(&bytes[50..59], "retirement time")?,
+            expiry_time: match bytes.get(59..68) {
+                Some(expiry) => read_optional_time(expiry, "expiry time")?,
+                None => None,
+            },
+        })
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key")
+            .field("secret", &Redacted(&self.secret))
+            .field("state", &self.state)
+            .field("creation_time", &self.creation_time)
+            .field("activation_time", &self.activation_time)
+            .field("retirement_time", &self.retirement_time)
+            .field("expiry_time", &self.expiry_time)
+            .finish()
+    }
+}
+
+/// Current sharing of a key tracked by a [`KeyRotationManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationEntry {
+    pub key_id: String,
+    pub threshold: usize,
+    /// Epoch the current slices were issued in.
+    pub epoch: u64,
+    /// Operation that issued the current slices.
+    pub operation_id: String,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub issued_at: DateTime<Utc>,
+}
+
+/// Tracks the age of every key's sharing and rotates it once older than the maximum lifetime.
+#[derive(Debug, Clone)]
+pub struct KeyRotationManager {
+    max_lifetime: TimeDelta,
+    entries: Vec<RotationEntry>,
+}
+
+impl KeyRotationManager {
+    /// Manager rotating sharings older than `max_lifetime_hours`.
+    ///
+    /// Returns `CryptoError::Validation` for a zero lifetime.
+    pub fn new(max_lifetime_hours: u64) -> CryptoResult<Self> {
+        if max_lifetime_hours == 0 {
+            return Err(CryptoError::Validation {
+                field: "max_key_lifetime_hours".to_string(),
+                reason: "must be at least 1".to_string(),
+            });
+        }
+        let max_lifetime = i64::try_from(max_lifetime_hours)
+            .ok()
+            .and_then(TimeDelta::try_hours)
+            .unwrap_or(TimeDelta::MAX);
+        Ok(Self {
+            max_lifetime,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Manager with the lifetime of `config`.
+    pub fn from_config(config: &EnterpriseConfig) -> CryptoResult<Self> {
+        Self::new(config.max_key_lifetime_hours)
+    }
+
+    /// Start tracking the sharing issued under `context` now, e.g. right after dealing it,
+    /// replacing the entry of the same key.
+    pub fn track(&mut self, context: &OperationContext, threshold: usize) {
+        self.track_at(context, threshold, Utc::now());
+    }
+
+    /// [`Self::track`] for a sharing issued at `issued_at`, e.g. when restoring the manager.
+    pub fn track_at(
+        &mut self,
+        context: &OperationContext,
+        threshold: usize,
+        issued_at: DateTime<Utc>,
+    ) {
+        let entry = RotationEntry {
+            key_id: context.key_id.clone(),
+            threshold,
+            epoch: context.epoch,
+            operation_id: context.operation_id.clone(),
+            issued_at,
+        };
+        match self.entries.iter_mut().find(|e| e.key_id == entry.key_id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Stop tracking `key_id`, e.g. once the key is retired or destroyed.
+    pub fn untrack(&mut self, key_id: &str) -> Option<RotationEntry> {
+        let position = self.entries.iter().position(|e| e.key_id == key_id)?;
+        Some(self.entries.remove(position))
+    }
+
+    /// The tracked sharing of `key_id`.
+    pub fn entry(&self, key_id: &str) -> Option<&RotationEntry> {
+        self.entries.iter().find(|e| e.key_id == key_id)
+    }
+
+    /// Every tracked sharing, in tracking order.
+    pub fn entries(&self) -> &[RotationEntry] {
+        &self.entries
+    }
+
+    /// Age at `now` of the current slices of `key_id`.
+    pub fn age(&self, key_id: &str, now: DateTime<Utc>) -> Option<TimeDelta> {
+        self.entry(key_id).map(|e| now - e.issued_at)
+    }
+
+    /// Whether the slices of `key_id` have reached the maximum lifetime; `false` if untracked.
+    pub fn needs_rotation(&self, key_id: &str) -> bool {
+        self.needs_rotation_at(key_id, Utc::now())
+    }
+
+    /// [`Self::needs_rotation`] at `now`.
+    pub fn needs_rotation_at(&self, key_id: &str, now: DateTime<Utc>) -> bool {
+        self.age(key_id, now)
+            .is_some_and(|age| age >= self.max_lifetime)
+    }
+
+    /// Keys whose slices have reached the maximum lifetime at `now`, in tracking order.
+    pub fn due_at(&self, now: DateTime<Utc>) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|e| now - e.issued_at >= self.max_lifetime)
+            .map(|e| e.key_id.as_str())
+            .collect()
+    }
+
+    /// Rotate the sharing of `context.key_id`, whether or not it is due, and track the new one.
+    ///
+    /// `context` names the rotation and its epoch, e.g. from
+    /// [`OperationContext::next_epoch`]. Returns `CryptoError::Validation` if the key is not
+    /// tracked or `context.epoch` does not follow the tracked epoch, and otherwise the errors of
+    /// [`EnterpriseCryptoSystem::rotate_key_shares`], leaving the entry unchanged.
+    pub fn rotate(
+        &mut self,
+        system: &mut EnterpriseCryptoSystem,
+        shares: &[ShareData],
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        let entry = self
+            .entry(&context.key_id)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "key_id".to_string(),
+                reason: format!("key {} is not tracked for rotation", context.key_id),
+            })?;
+        if context.epoch <= entry.epoch {
+            return Err(CryptoError::Validation {
+                field: "epoch".to_string(),
+                reason: format!(
+                    "rotation epoch {} must follow the current epoch {}",
+                    context.epoch, entry.epoch
+                ),
+            });
+        }
+        let threshold = entry.threshold;
+        let rotated = system.rotate_key_shares(shares, threshold, context)?;
+        self.track(context, threshold);
+        Ok(rotated)
+    }
+}
+
+#[cfg(feature = "insecure-debug")]
+impl Key {
+    /// Render the key including the raw secret. Only available with `insecure-debug`.
+    pub fn reveal_for_debug(&self) -> String {
+        format!(
+            "Key {{ secret: {}, state: {:?}, creation_time: {:?}, activation_time: {:?}, retirement_time: {:?}, expiry_time: {:?} }}",
+            hex::encode(self.secret.as_bytes()),
+            self.state,
+            self.creation_time,
+            self.activation_time,
+            self.retirement_time,
+            self.expiry_time
+        )
     }
 }
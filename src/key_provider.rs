@@ -0,0 +1,146 @@
+//! **key_provider module**
+//!
+//! [`KeyProvider`]: where the dealer's master secrets are generated and held.
+//!
+//! [`EnterpriseCryptoSystem::generate_enterprise_key`](crate::EnterpriseCryptoSystem::generate_enterprise_key)
+//! asks a provider for each new master secret instead of drawing it itself. The provider keeps
+//! the authoritative copy under the key id; the returned [`Key`] is the working copy used for the
+//! dealing and should be destroyed once the slices are distributed. The secret is loaded again
+//! with [`KeyProvider::load`] for a later dealing and erased from the provider with
+//! [`KeyProvider::zeroize`] when the key is destroyed.
+//!
+//! - [`SoftwareKeyProvider`] holds secrets in process memory, zeroized on drop.
+//! - `pkcs11::Pkcs11KeyProvider` (feature `pkcs11`) holds them as token objects of a PKCS#11
+//!   HSM.
+
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::key_lifecycle::Key;
+use crate::utils::random_scalar;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::rngs::OsRng;
+use zeroize::Zeroize;
+
+/// A store of master secrets, e.g. an HSM.
+pub trait KeyProvider {
+    /// Stable name recorded in logs, e.g. `software` or `pkcs11`.
+    fn name(&self) -> &str;
+
+    /// Generate a new master secret stored under `key_id`.
+    ///
+    /// Returns `CryptoError::Validation` if `key_id` is already in use.
+    fn generate(&mut self, key_id: &str) -> CryptoResult<Key>;
+
+    /// The master secret stored under `key_id`.
+    fn load(&mut self, key_id: &str) -> CryptoResult<Key>;
+
+    /// Commitment `s·G` to the master secret stored under `key_id`, the group public key of
+    /// its dealings.
+    fn commitment(&mut self, key_id: &str) -> CryptoResult<RistrettoPoint>;
+
+    /// Erase the master secret stored under `key_id`.
+    fn zeroize(&mut self, key_id: &str) -> CryptoResult<()>;
+}
+
+/// Error for a key id the provider does not hold.
+pub(crate) fn unknown_key(key_id: &str) -> CryptoError {
+    CryptoError::Validation {
+        field: "key_id".to_string(),
+        reason: format!("no master secret stored under {}", key_id),
+    }
+}
+
+/// Error for a key id the provider already holds.
+pub(crate) fn duplicate_key(key_id: &str) -> CryptoError {
+    CryptoError::Validation {
+        field: "key_id".to_string(),
+        reason: format!("a master secret is already stored under {}", key_id),
+    }
+}
+
+/// Master secrets drawn from the OS RNG and held in process memory.
+#[derive(Default)]
+pub struct SoftwareKeyProvider {
+    secrets: Vec<(String, Scalar)>,
+}
+
+impl SoftwareKeyProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn position(&self, key_id: &str) -> CryptoResult<usize> {
+        self.secrets
+            .iter()
+            .position(|(id, _)| id == key_id)
+            .ok_or_else(|| unknown_key(key_id))
+    }
+}
+
+impl KeyProvider for SoftwareKeyProvider {
+    fn name(&self) -> &str {
+        "software"
+    }
+
+    fn generate(&mut self, key_id: &str) -> CryptoResult<Key> {
+        if self.position(key_id).is_ok() {
+            return Err(duplicate_key(key_id));
+        }
+        let secret = random_scalar(&mut OsRng).map_err(|e| CryptoError::KeyGeneration {
+            reason: e.to_string(),
+        })?;
+        self.secrets.push((key_id.to_string(), secret));
+        Ok(Key::new(secret))
+    }
+
+    fn load(&mut self, key_id: &str) -> CryptoResult<Key> {
+        let position = self.position(key_id)?;
+        Ok(Key::new(self.secrets[position].1))
+    }
+
+    fn commitment(&mut self, key_id: &str) -> CryptoResult<RistrettoPoint> {
+        let position = self.position(key_id)?;
+        Ok(RISTRETTO_BASEPOINT_POINT * self.secrets[position].1)
+    }
+
+    fn zeroize(&mut self, key_id: &str) -> CryptoResult<()> {
+        let position = self.position(key_id)?;
+        let (_, mut secret) = self.secrets.remove(position);
+        secret.zeroize();
+        Ok(())
+    }
+}
+
+impl Drop for SoftwareKeyProvider {
+    fn drop(&mut self) {
+        for (_, secret) in &mut self.secrets {
+            secret.zeroize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_provider_lifecycle() {
+        let mut provider = SoftwareKeyProvider::new();
+        let key = provider.generate("master").unwrap();
+        assert!(matches!(
+            provider.generate("master"),
+            Err(CryptoError::Validation { .. })
+        ));
+        assert_eq!(provider.load("master").unwrap().secret, key.secret);
+        assert_eq!(provider.commitment("master").unwrap(), key.public_key());
+
+        provider.zeroize("master").unwrap();
+        assert!(provider.load("master").is_err());
+        assert!(provider.commitment("master").is_err());
+        assert!(provider.zeroize("master").is_err());
+    }
+}
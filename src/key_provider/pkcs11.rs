@@ -0,0 +1,169 @@
+//! **PKCS#11 key provider**
+//!
+//! Master secrets held as token objects of a PKCS#11 HSM. Each secret is a 64-byte
+//! `CKO_SECRET_KEY` / `CKK_GENERIC_SECRET` object generated on the token with
+//! `CKM_GENERIC_SECRET_KEY_GEN` and labelled with the key id; the scalar is the 64 bytes reduced
+//! modulo the group order, as for [`crate::utils::random_scalar`], so its bias is negligible.
+//!
+//! HSMs do not implement Ristretto255, so the value leaves the token whenever a dealing or a
+//! commitment needs it: the object must be created with `CKA_SENSITIVE = false` and
+//! `CKA_EXTRACTABLE = true`, and tokens enforcing a stricter policy reject
+//! [`Pkcs11Session::read_value`]. What the token adds is that the secret is generated by its RNG,
+//! persisted under its access control and destroyed by it.
+//!
+//! The crate does not link a PKCS#11 library; [`Pkcs11Session`] is the handful of calls the
+//! provider makes, implemented by the application over its binding of choice on a logged-in
+//! read-write session.
+
+use super::{duplicate_key, unknown_key, KeyProvider};
+use crate::error::{CryptoError, CryptoResult};
+use crate::key_lifecycle::Key;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use zeroize::Zeroizing;
+
+/// Length in bytes of the generic secret backing one master secret.
+pub const SECRET_VALUE_LENGTH: usize = 64;
+
+/// `CK_OBJECT_HANDLE` of a token object.
+pub type ObjectHandle = u64;
+
+/// The PKCS#11 calls [`Pkcs11KeyProvider`] makes on a logged-in read-write session.
+///
+/// Errors are reported as `CryptoError::CryptographicOperation` carrying the `CKR_*` return
+/// value.
+pub trait Pkcs11Session {
+    /// `C_GenerateKey` with `CKM_GENERIC_SECRET_KEY_GEN`, `CKA_VALUE_LEN = length`,
+    /// `CKA_LABEL = label`, `CKA_TOKEN = true`, `CKA_SENSITIVE = false` and
+    /// `CKA_EXTRACTABLE = true`.
+    fn generate_secret(&mut self, label: &str, length: usize) -> CryptoResult<ObjectHandle>;
+
+    /// `C_FindObjectsInit` / `C_FindObjects` for the secret key labelled `label`.
+    fn find(&mut self, label: &str) -> CryptoResult<Option<ObjectHandle>>;
+
+    /// `C_GetAttributeValue` of `CKA_VALUE`.
+    fn read_value(&mut self, handle: ObjectHandle) -> CryptoResult<Zeroizing<Vec<u8>>>;
+
+    /// `C_DestroyObject`.
+    fn destroy(&mut self, handle: ObjectHandle) -> CryptoResult<()>;
+}
+
+/// [`KeyProvider`] keeping master secrets on a PKCS#11 token.
+pub struct Pkcs11KeyProvider<S: Pkcs11Session> {
+    session: S,
+}
+
+impl<S: Pkcs11Session> Pkcs11KeyProvider<S> {
+    pub fn new(session: S) -> Self {
+        Self { session }
+    }
+
+    /// Give the session back, e.g. to log out.
+    pub fn into_session(self) -> S {
+        self.session
+    }
+
+    fn handle(&mut self, key_id: &str) -> CryptoResult<ObjectHandle> {
+        self.session
+            .find(key_id)?
+            .ok_or_else(|| unknown_key(key_id))
+    }
+
+    fn secret(&mut self, handle: ObjectHandle) -> CryptoResult<Scalar> {
+        let value = self.session.read_value(handle)?;
+        let bytes: Zeroizing<[u8; SECRET_VALUE_LENGTH]> =
+            Zeroizing::new(value.as_slice().try_into().map_err(|_| {
+                CryptoError::Serialization {
+                    details: format!(
+                        "token secret is {} bytes, expected {}",
+                        value.len(),
+                        SECRET_VALUE_LENGTH
+                    ),
+                }
+            })?);
+        Ok(Scalar::from_bytes_mod_order_wide(&bytes))
+    }
+}
+
+impl<S: Pkcs11Session> KeyProvider for Pkcs11KeyProvider<S> {
+    fn name(&self) -> &str {
+        "pkcs11"
+    }
+
+    fn generate(&mut self, key_id: &str) -> CryptoResult<Key> {
+        if self.session.find(key_id)?.is_some() {
+            return Err(duplicate_key(key_id));
+        }
+        let handle = self.session.generate_secret(key_id, SECRET_VALUE_LENGTH)?;
+        Ok(Key::new(self.secret(handle)?))
+    }
+
+    fn load(&mut self, key_id: &str) -> CryptoResult<Key> {
+        let handle = self.handle(key_id)?;
+        Ok(Key::new(self.secret(handle)?))
+    }
+
+    fn commitment(&mut self, key_id: &str) -> CryptoResult<RistrettoPoint> {
+        let handle = self.handle(key_id)?;
+        Ok(RISTRETTO_BASEPOINT_POINT * self.secret(handle)?)
+    }
+
+    fn zeroize(&mut self, key_id: &str) -> CryptoResult<()> {
+        let handle = self.handle(key_id)?;
+        self.session.destroy(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// In-memory token.
+    #[derive(Default)]
+    struct MockSession {
+        objects: Vec<(ObjectHandle, String, Vec<u8>)>,
+        next: ObjectHandle,
+    }
+
+    impl Pkcs11Session for MockSession {
+        fn generate_secret(&mut self, label: &str, length: usize) -> CryptoResult<ObjectHandle> {
+            self.next += 1;
+            let value = (0..length).map(|i| (i as u8) ^ (self.next as u8)).collect();
+            self.objects.push((self.next, label.to_string(), value));
+            Ok(self.next)
+        }
+
+        fn find(&mut self, label: &str) -> CryptoResult<Option<ObjectHandle>> {
+            Ok(self.objects.iter().find(|o| o.1 == label).map(|o| o.0))
+        }
+
+        fn read_value(&mut self, handle: ObjectHandle) -> CryptoResult<Zeroizing<Vec<u8>>> {
+            self.objects
+                .iter()
+                .find(|o| o.0 == handle)
+                .map(|o| Zeroizing::new(o.2.clone()))
+                .ok_or_else(|| CryptoError::CryptographicOperation {
+                    operation: "C_GetAttributeValue: CKR_OBJECT_HANDLE_INVALID".to_string(),
+                })
+        }
+
+        fn destroy(&mut self, handle: ObjectHandle) -> CryptoResult<()> {
+            self.objects.retain(|o| o.0 != handle);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_pkcs11_provider_lifecycle() {
+        let mut provider = Pkcs11KeyProvider::new(MockSession::default());
+        let key = provider.generate("master").unwrap();
+        assert!(provider.generate("master").is_err());
+        assert_eq!(provider.load("master").unwrap().secret, key.secret);
+        assert_eq!(provider.commitment("master").unwrap(), key.public_key());
+
+        provider.zeroize("master").unwrap();
+        assert!(provider.load("master").is_err());
+        assert!(provider.into_session().objects.is_empty());
+    }
+}
@@ -2,13 +2,48 @@
 //!
 //! High-performance polynomial operations with FFT/Karatsuba acceleration and formal correctness proofs.
 //! Implements enterprise-grade secret recovery with mathematical guarantees.
-
+//!
+//! Algorithm selection:
+//! - multiplication: schoolbook up to 64 output coefficients, a multi-modular NTT for balanced
+//!   products of at least [`NTT_THRESHOLD`] coefficients (the `ntt` submodule documents why the
+//!   transform does not run over the scalar field itself), and (parallel) Karatsuba otherwise;
+//! - interpolation: [`recover_secret_barycentric`] and [`poly_evaluate_at_many`] use the
+//!   barycentric form (the `barycentric` submodule) and [`birkhoff_interpolate`] interpolates
+//!   from derivative values (the `birkhoff` submodule); otherwise, below
+//!   [`FAST_EVALUATION_THRESHOLD`] points, `Q'(x)` is evaluated with Horner at every point
+//!   (`O(n²)`); from there on a subproduct tree and remainder tree evaluate it at all points at
+//!   once, which together with NTT multiplication costs `O(n log² n)`.
+
+use crate::events::{structured_log, EventCode};
+use crate::op_counts::{count, Op};
 use curve25519_dalek::scalar::Scalar;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
 
+mod barycentric;
+mod birkhoff;
+mod cache;
+mod ntt;
+mod polynomial;
+mod robust;
+
+pub use barycentric::{poly_evaluate_at_many, recover_secret_barycentric, BarycentricWeights};
+pub use birkhoff::{birkhoff_interpolate, falling_factorial};
+pub use cache::{LagrangeCache, LagrangeCoefficients};
+pub use polynomial::Polynomial;
+pub use robust::recover_secret_robust;
+
+/// Output length from which `poly_mul` switches from Karatsuba to the NTT.
+pub const NTT_THRESHOLD: usize = 128;
+
+/// Number of points from which interpolation uses subproduct-tree multipoint evaluation.
+pub const FAST_EVALUATION_THRESHOLD: usize = 768;
+
+/// Nodes of at most this many points are evaluated with Horner's method in the remainder tree.
+const REMAINDER_TREE_LEAF: usize = 32;
+
 /// Enhanced error types for robust error handling
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
 pub enum LagrangeError {
@@ -29,6 +64,18 @@ pub enum LagrangeError {
 
     #[error("Numerical instability detected")]
     NumericalInstability,
+
+    #[error("Too many faulty shares: at most {correctable} can be corrected")]
+    TooManyErrors { correctable: usize },
+
+    #[error("Share {index} is inconsistent with the dealing's commitments")]
+    InconsistentShare { index: usize },
+
+    #[error("Share {index} does not match the precomputed index set")]
+    IndexSetMismatch { index: usize },
+
+    #[error("The interpolation points do not determine the polynomial")]
+    NotPoised,
 }
 
 /// Result type for Lagrange operations
@@ -41,15 +88,36 @@ pub struct PerformanceMetrics {
     pub duration_ns: u64,
     pub input_size: usize,
     pub algorithm_used: String,
+    /// Operation context the sample was taken in, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<crate::context::OperationContext>,
+    /// Primitive counts over the operation, with the `op-counters` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counts: Option<crate::op_counts::OperationCounts>,
+}
+
+/// Number of coefficients of `a · b`, or `None` if either factor is the empty (zero) polynomial.
+fn product_len(a: &[Scalar], b: &[Scalar]) -> Option<usize> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    a.len().checked_add(b.len() - 1)
 }
 
 /// Enhanced polynomial multiplication with algorithm selection
+///
+/// An empty slice is the zero polynomial; multiplying by it yields an empty result.
 pub fn poly_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
-    let result_len = a.len() + b.len() - 1;
+    let Some(result_len) = product_len(a, b) else {
+        return Vec::new();
+    };
 
     // Performance-optimized algorithm selection
     if result_len <= 64 {
         naive_mul(a, b)
+    } else if result_len >= NTT_THRESHOLD && a.len().min(b.len()) > 32 {
+        // The transform cost depends on the output length, so only balanced products use it
+        ntt::ntt_mul(a, b)
     } else if result_len <= 1024 {
         karatsuba_mul(a, b)
     } else {
@@ -60,7 +128,11 @@ pub fn poly_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
 
 /// Naive multiplication for small polynomials
 fn naive_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
-    let mut result = vec![Scalar::ZERO; a.len() + b.len() - 1];
+    let Some(result_len) = product_len(a, b) else {
+        return Vec::new();
+    };
+    let mut result = vec![Scalar::ZERO; result_len];
+    count(Op::ScalarMul, a.len() * b.len());
 
     for (i, &coeff_a) in a.iter().enumerate() {
         for (j, &coeff_b) in b.iter().enumerate() {
@@ -73,9 +145,13 @@ fn naive_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
 
 /// Karatsuba multiplication for medium polynomials
 fn karatsuba_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+    let Some(result_len) = product_len(a, b) else {
+        return Vec::new();
+    };
     let n = a.len().max(b.len());
 
-    if n <= 32 {
+    // A single-coefficient factor has no high half to split off
+    if n <= 32 || a.len().min(b.len()) == 1 {
         return naive_mul(a, b);
     }
 
@@ -97,7 +173,7 @@ fn karatsuba_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
     );
 
     // Combine results
-    let mut result = vec![Scalar::ZERO; a.len() + b.len() - 1];
+    let mut result = vec![Scalar::ZERO; result_len];
 
     // Add z0
     for (i, &coeff) in z0.iter().enumerate() {
@@ -125,9 +201,12 @@ fn karatsuba_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
 
 /// Parallel Karatsuba for large polynomials
 fn parallel_karatsuba_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+    let Some(result_len) = product_len(a, b) else {
+        return Vec::new();
+    };
     let n = a.len().max(b.len());
 
-    if n <= 1024 {
+    if n <= 1024 || a.len().min(b.len()) == 1 {
         return karatsuba_mul(a, b);
     }
 
@@ -151,7 +230,7 @@ fn parallel_karatsuba_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
     );
 
     // Combine results in parallel
-    let mut result = vec![Scalar::ZERO; a.len() + b.len() - 1];
+    let mut result = vec![Scalar::ZERO; result_len];
 
     // Parallel addition
     result.par_iter_mut().enumerate().for_each(|(i, coeff)| {
@@ -239,11 +318,171 @@ pub fn poly_evaluate(poly: &[Scalar], x: Scalar) -> Scalar {
     }
 
     // Use Horner's method for numerical stability
+    count(Op::ScalarMul, poly.len());
     poly.iter()
         .rev()
         .fold(Scalar::ZERO, |acc, &coeff| acc * x + coeff)
 }
 
+/// Inverse of the power series `f` modulo `x^n` by Newton iteration; requires `f[0] != 0`.
+fn series_inverse(f: &[Scalar], n: usize) -> Vec<Scalar> {
+    count(Op::Inversion, 1);
+    let mut g = vec![f[0].invert()];
+    let mut k = 1;
+
+    while k < n {
+        k = (2 * k).min(n);
+        // g ← g · (2 − f·g) mod x^k
+        let mut e = poly_mul(&f[..f.len().min(k)], &g);
+        e.truncate(k);
+        for coeff in e.iter_mut() {
+            *coeff = -*coeff;
+        }
+        e[0] += Scalar::from(2u64);
+        g = poly_mul(&g, &e);
+        g.truncate(k);
+    }
+
+    g
+}
+
+/// Remainder of `a` modulo the monic polynomial `m`.
+///
+/// Uses schoolbook division for short quotients and Newton reciprocal division otherwise.
+///
+/// # Panics
+/// If `m` is empty or not monic.
+pub fn poly_rem(a: &[Scalar], m: &[Scalar]) -> Vec<Scalar> {
+    assert!(m.last() == Some(&Scalar::ONE), "divisor must be monic");
+    let d = m.len() - 1;
+    if a.len() <= d {
+        return a.to_vec();
+    }
+    let quotient_len = a.len() - d;
+
+    if quotient_len <= 64 || d <= 64 {
+        let mut r = a.to_vec();
+        for i in (d..r.len()).rev() {
+            let c = r[i];
+            if c != Scalar::ZERO {
+                for j in 0..d {
+                    r[i - d + j] -= c * m[j];
+                }
+            }
+        }
+        r.truncate(d);
+        return r;
+    }
+
+    // rev(a) = rev(q) · rev(m) mod x^quotient_len
+    let rev_m: Vec<Scalar> = m.iter().rev().copied().collect();
+    let rev_a: Vec<Scalar> = a.iter().rev().take(quotient_len).copied().collect();
+    let mut rev_q = poly_mul(&rev_a, &series_inverse(&rev_m, quotient_len));
+    rev_q.truncate(quotient_len);
+    let q: Vec<Scalar> = rev_q.into_iter().rev().collect();
+
+    let qm = poly_mul(&q, m);
+    (0..d).map(|i| a[i] - qm[i]).collect()
+}
+
+/// Subproduct tree over `points`: `levels[0]` holds the factors `x - x_i`, each further level the
+/// pairwise products of the one below, and the last level the single product `∏(x - x_i)`.
+fn subproduct_tree(points: &[Scalar]) -> Vec<Vec<Vec<Scalar>>> {
+    let mut levels = vec![points
+        .iter()
+        .map(|&x| vec![-x, Scalar::ONE])
+        .collect::<Vec<_>>()];
+
+    while levels.last().is_some_and(|level| level.len() > 1) {
+        let next = levels
+            .last()
+            .unwrap()
+            .par_chunks(2)
+            .map(|pair| match pair {
+                [left, right] => poly_mul(left, right),
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+
+    levels
+}
+
+/// Evaluate `poly` at every point of a subproduct tree by descending the remainder tree.
+fn evaluate_with_tree(
+    poly: &[Scalar],
+    points: &[Scalar],
+    tree: &[Vec<Vec<Scalar>>],
+) -> Vec<Scalar> {
+    let leaf_level = REMAINDER_TREE_LEAF.trailing_zeros() as usize;
+    let top = tree.len() - 1;
+
+    let mut remainders = vec![poly_rem(poly, &tree[top][0])];
+    for level in (leaf_level.min(top)..top).rev() {
+        remainders = tree[level]
+            .par_iter()
+            .enumerate()
+            .map(|(i, m)| poly_rem(&remainders[i / 2], m))
+            .collect();
+    }
+
+    let leaf_level = leaf_level.min(top);
+    points
+        .par_iter()
+        .enumerate()
+        .map(|(j, &x)| poly_evaluate(&remainders[j >> leaf_level], x))
+        .collect()
+}
+
+/// Evaluate `poly` at all `points`, using a subproduct tree for large point sets.
+pub fn poly_evaluate_multi(poly: &[Scalar], points: &[Scalar]) -> Vec<Scalar> {
+    if points.len() < FAST_EVALUATION_THRESHOLD {
+        return points.iter().map(|&x| poly_evaluate(poly, x)).collect();
+    }
+
+    evaluate_with_tree(poly, points, &subproduct_tree(points))
+}
+
+/// Lagrange weights at zero, `λ_i = -Q(0) / (x_i · Q'(x_i))` with `Q(x) = ∏(x - x_i)`.
+///
+/// Fails with the position of the first point where `Q'(x_i) = 0`, i.e. a repeated point.
+fn lagrange_weights_from_product(xs: &[Scalar]) -> Result<(Vec<Scalar>, &'static str), usize> {
+    let (q_poly, q_prime_values, algorithm) = if xs.len() < FAST_EVALUATION_THRESHOLD {
+        let polys: Vec<Vec<Scalar>> = xs
+            .iter()
+            .map(|&x| vec![-x, Scalar::ONE])
+            .collect();
+        let q_poly = poly_product(&polys);
+        let q_derivative = poly_derivative(&q_poly);
+        let values = xs.iter().map(|&x| poly_evaluate(&q_derivative, x)).collect::<Vec<_>>();
+        (q_poly, values, "optimized_lagrange_fft")
+    } else {
+        let tree = subproduct_tree(xs);
+        let q_poly = tree[tree.len() - 1][0].clone();
+        let values = evaluate_with_tree(&poly_derivative(&q_poly), xs, &tree);
+        (q_poly, values, "subproduct_tree_ntt")
+    };
+
+    if let Some(position) = q_prime_values.iter().position(|v| *v == Scalar::ZERO) {
+        return Err(position);
+    }
+
+    let q_0 = if !q_poly.is_empty() { q_poly[0] } else { Scalar::ONE };
+    let mut denominators: Vec<Scalar> = xs
+        .iter()
+        .zip(&q_prime_values)
+        .map(|(x, q)| x * q)
+        .collect();
+    Scalar::batch_invert(&mut denominators);
+    // Products, batch inversion and the final scaling.
+    count(Op::ScalarMul, 5 * xs.len());
+    count(Op::Inversion, 1);
+
+    Ok((denominators.into_iter().map(|d| -q_0 * d).collect(), algorithm))
+}
+
 /// Compute Lagrange coefficients with numerical stability checks
 pub fn compute_lagrange_coefficients(indices: &[Scalar]) -> LagrangeResult<Vec<Scalar>> {
     if indices.is_empty() {
@@ -264,7 +503,13 @@ pub fn compute_lagrange_coefficients(indices: &[Scalar]) -> LagrangeResult<Vec<S
     }
 
     let m = indices.len();
-    let sign = if (m - 1) % 2 == 0 {
+    if m >= FAST_EVALUATION_THRESHOLD {
+        return lagrange_weights_from_product(indices)
+            .map(|(coefficients, _)| coefficients)
+            .map_err(|position| LagrangeError::ZeroDerivative { index: position + 1 });
+    }
+
+    let sign = if (m - 1).is_multiple_of(2) {
         Scalar::ONE
     } else {
         -Scalar::ONE
@@ -293,6 +538,7 @@ pub fn compute_lagrange_coefficients(indices: &[Scalar]) -> LagrangeResult<Vec<S
             return Err(LagrangeError::ZeroDerivative { index: i + 1 });
         }
 
+        count(Op::Inversion, 1);
         let coefficient = sign * numerator * denominator.invert();
         coefficients.push(coefficient);
     }
@@ -302,8 +548,7 @@ pub fn compute_lagrange_coefficients(indices: &[Scalar]) -> LagrangeResult<Vec<S
 
 /// Enhanced secret recovery with comprehensive error handling and performance monitoring
 pub fn recover_secret_fft(shares: &[crate::sharing::ShareData]) -> LagrangeResult<Scalar> {
-    use std::time::Instant;
-    let start_time = Instant::now();
+    let start_time = crate::utils::Stopwatch::start();
 
     if shares.is_empty() {
         return Err(LagrangeError::InsufficientShares {
@@ -339,47 +584,142 @@ pub fn recover_secret_fft(shares: &[crate::sharing::ShareData]) -> LagrangeResul
         .map(|s| Scalar::from(s.index as u64))
         .collect();
 
-    // Build polynomial product Q(x) = ∏(x - x_i)
-    let polys: Vec<Vec<Scalar>> = xs
-        .iter()
-        .map(|&x| vec![-x, Scalar::ONE])
-        .collect();
-
-    let q_poly = poly_product(&polys);
-    let q_0 = if !q_poly.is_empty() { q_poly[0] } else { Scalar::ONE };
-    let q_derivative = poly_derivative(&q_poly);
-
-    // Compute secret using optimized Lagrange interpolation
-    let mut secret = Scalar::ZERO;
-
-    for (i, share) in shares.iter().enumerate() {
-        let x_i = xs[i];
-        let q_i = poly_evaluate(&q_derivative, x_i);
-
-        if q_i == Scalar::ZERO {
-            return Err(LagrangeError::ZeroDerivative {
-                index: share.index,
-            });
+    // Lagrange weights from Q(x) = ∏(x - x_i), with the evaluation strategy chosen by size
+    let (weights, algorithm) = lagrange_weights_from_product(&xs).map_err(|position| {
+        LagrangeError::ZeroDerivative {
+            index: shares[position].index,
         }
+    })?;
 
-        let lagrange_coeff = -q_0 * (x_i * q_i).invert();
-        secret += share.share * lagrange_coeff;
-    }
+    count(Op::ScalarMul, shares.len());
+    let secret: Scalar = shares
+        .iter()
+        .zip(&weights)
+        .map(|(share, weight)| share.share * weight)
+        .sum();
 
     // Performance monitoring
-    let duration = start_time.elapsed();
     let metrics = PerformanceMetrics {
         operation_type: "secret_recovery".to_string(),
-        duration_ns: duration.as_nanos() as u64,
+        duration_ns: start_time.elapsed_ns(),
         input_size: shares.len(),
-        algorithm_used: "optimized_lagrange_fft".to_string(),
+        algorithm_used: algorithm.to_string(),
+        context: None,
+        counts: start_time.counts(),
     };
 
-    log::info!("Secret recovery completed: {:?}", metrics);
+    structured_log!(
+        log::Level::Info,
+        EventCode::RecoveryCompleted,
+        "secret recovery completed",
+        operation_type = metrics.operation_type,
+        duration_ns = metrics.duration_ns,
+        input_size = metrics.input_size,
+        algorithm = metrics.algorithm_used
+    );
 
     Ok(secret)
 }
 
+/// Constant-time secret recovery, used by [`crate::EnterpriseCryptoSystem`] at
+/// [`SecurityLevel::Maximum`](crate::SecurityLevel::Maximum).
+///
+/// Slice values never influence control flow: every Lagrange weight is computed with the same
+/// fixed `O(n²)` sequence of field operations, zero and duplicate indices are accumulated into a
+/// `subtle::Choice` instead of returning early, and every slice contributes exactly one
+/// multiplication to the sum. Only the number of slices and their public indices affect timing;
+/// an invalid index set is reported after all weights have been computed.
+pub fn recover_secret_ct(shares: &[crate::sharing::ShareData]) -> LagrangeResult<Scalar> {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    if shares.is_empty() {
+        return Err(LagrangeError::InsufficientShares {
+            needed: 1,
+            provided: 0,
+        });
+    }
+
+    let xs: Vec<Scalar> = shares
+        .iter()
+        .map(|s| Scalar::from(s.index as u64))
+        .collect();
+    let mut invalid = Choice::from(0);
+    let mut numerators = Vec::with_capacity(xs.len());
+    let mut denominators = Vec::with_capacity(xs.len());
+    for (i, x_i) in xs.iter().enumerate() {
+        invalid |= x_i.ct_eq(&Scalar::ZERO);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (j, x_j) in xs.iter().enumerate() {
+            // The diagonal term multiplies both products by one.
+            let diagonal = Choice::from((i == j) as u8);
+            let diff = x_j - x_i;
+            invalid |= !diagonal & diff.ct_eq(&Scalar::ZERO);
+            numerator *= Scalar::conditional_select(x_j, &Scalar::ONE, diagonal);
+            denominator *= Scalar::conditional_select(&diff, &Scalar::ONE, diagonal);
+        }
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+
+    if bool::from(invalid) {
+        // Failure path only; the indices are public.
+        if let Some(share) = shares.iter().find(|s| s.index == 0) {
+            return Err(LagrangeError::InvalidShareIndex { index: share.index });
+        }
+        let mut seen = HashMap::new();
+        for (position, share) in shares.iter().enumerate() {
+            if let Some(first) = seen.insert(share.index, position) {
+                return Err(LagrangeError::DuplicateShareIndex { index: first + 1 });
+            }
+        }
+    }
+
+    Scalar::batch_invert(&mut denominators);
+    count(Op::ScalarMul, 2 * xs.len() * xs.len() + 5 * xs.len());
+    count(Op::Inversion, 1);
+    let mut secret = Scalar::ZERO;
+    for ((share, numerator), inverse) in shares.iter().zip(&numerators).zip(&denominators) {
+        secret += share.share * numerator * inverse;
+    }
+    Ok(secret)
+}
+
+/// Recovery from slices checked against the dealing's coefficient commitments.
+///
+/// Every slice must open its commitment, lie on the polynomial committed to by `commitments` and
+/// carry a valid proof ([`VssCommitments::verify_share`](crate::vss::VssCommitments::verify_share)),
+/// so a slice of another dealing is reported instead of interpolating to a wrong secret. The
+/// slices are then interpolated with [`recover_secret_ct`].
+///
+/// # Return value
+///
+/// Returns the secret, `LagrangeError::InsufficientShares` for fewer slices than the threshold of
+/// `commitments` (at least one), `LagrangeError::InvalidShareIndex` for a zero index,
+/// `LagrangeError::InconsistentShare` with the index of the first slice failing the check, or
+/// `LagrangeError::DuplicateShareIndex` for a repeated index.
+pub fn recover_secret_verified(
+    shares: &[crate::sharing::ShareData],
+    commitments: &crate::vss::VssCommitments,
+) -> LagrangeResult<Scalar> {
+    let needed = commitments.threshold().max(1);
+    if shares.len() < needed {
+        return Err(LagrangeError::InsufficientShares {
+            needed,
+            provided: shares.len(),
+        });
+    }
+    for share in shares {
+        if share.index == 0 {
+            return Err(LagrangeError::InvalidShareIndex { index: 0 });
+        }
+        if commitments.verify_share(share).is_err() {
+            return Err(LagrangeError::InconsistentShare { index: share.index });
+        }
+    }
+    recover_secret_ct(shares)
+}
+
 /// Batch secret recovery for multiple secret sharing instances
 pub fn recover_secrets_batch(
     shares_batch: &[Vec<crate::sharing::ShareData>]
@@ -390,12 +730,67 @@ pub fn recover_secrets_batch(
         .collect()
 }
 
+/// [`recover_secrets_batch`] stopping with `CryptoError::Timeout` once `deadline` passes.
+///
+/// The deadline is checked before each instance, so instances already being recovered finish.
+pub fn recover_secrets_batch_until(
+    shares_batch: &[Vec<crate::sharing::ShareData>],
+    deadline: &crate::deadline::Deadline,
+) -> crate::error::CryptoResult<Vec<LagrangeResult<Scalar>>> {
+    shares_batch
+        .par_iter()
+        .map(|shares| {
+            deadline.check("batch_recovery")?;
+            Ok(recover_secret_fft(shares))
+        })
+        .collect()
+}
+
+/// Recover every secret of a multi-secret dealing, computing the Lagrange coefficients once.
+pub fn recover_secrets_multi(
+    shares: &[crate::sharing::MultiShareData],
+) -> LagrangeResult<Vec<Scalar>> {
+    if shares.is_empty() {
+        return Err(LagrangeError::InsufficientShares {
+            needed: 1,
+            provided: 0,
+        });
+    }
+    for share in shares {
+        if share.index == 0 {
+            return Err(LagrangeError::InvalidShareIndex { index: share.index });
+        }
+    }
+    let count = shares[0].secret_count();
+    if shares.iter().any(|s| s.secret_count() != count) {
+        return Err(LagrangeError::NumericalInstability);
+    }
+
+    let xs: Vec<Scalar> = shares
+        .iter()
+        .map(|s| Scalar::from(s.index as u64))
+        .collect();
+    let lambda = compute_lagrange_coefficients(&xs)?;
+
+    Ok((0..count)
+        .map(|k| {
+            shares
+                .iter()
+                .zip(&lambda)
+                .map(|(share, l)| share.shares[k] * l)
+                .sum()
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::sharing::{generate_key_shares, ShareData};
+    use crate::utils::random_scalar;
     use curve25519_dalek::scalar::Scalar;
     use rand::rngs::OsRng;
+    use rand::Rng;
 
     #[test]
     fn test_polynomial_multiplication() {
@@ -415,13 +810,128 @@ mod tests {
         let threshold = 3;
         let num_shares = 5;
 
-        let shares = generate_key_shares(secret, threshold, num_shares);
+        let shares = generate_key_shares(secret, threshold, num_shares).unwrap();
         let selected_shares: Vec<_> = shares.into_iter().take(threshold).collect();
 
         let recovered = recover_secret_fft(&selected_shares).unwrap();
         assert_eq!(recovered, secret);
     }
 
+    #[test]
+    fn test_multi_secret_recovery() {
+        let secrets = vec![Scalar::from(1u64), Scalar::from(2u64), Scalar::from(3u64)];
+        let shares = crate::sharing::generate_key_shares_multi(&secrets, 3, 5).unwrap();
+
+        assert!(crate::vss::verify_multi_share_validity(&shares));
+        assert_eq!(recover_secrets_multi(&shares[1..4]).unwrap(), secrets);
+
+        let single = shares[0].share_for(1).unwrap().unwrap();
+        assert!(crate::proof::verify_proof(&single.proof, single.commitment, single.index).is_ok());
+    }
+
+    #[test]
+    fn test_ntt_polynomial_multiplication() {
+        let a: Vec<Scalar> = (1..=200u64).map(|i| Scalar::from(i).invert()).collect();
+        let b: Vec<Scalar> = (1..=150u64).map(|i| -Scalar::from(i * i)).collect();
+
+        assert!(a.len() + b.len() > NTT_THRESHOLD);
+        assert_eq!(poly_mul(&a, &b), naive_mul(&a, &b));
+    }
+
+    #[test]
+    fn test_polynomial_canonical_form() {
+        let two = Scalar::from(2u64);
+        let p = Polynomial::new(vec![Scalar::ONE, two, Scalar::ZERO, Scalar::ZERO]);
+        assert_eq!(p.degree(), Some(1));
+        assert_eq!(p.coefficients(), &[Scalar::ONE, two]);
+        assert_eq!(Polynomial::new(vec![Scalar::ZERO; 3]), Polynomial::zero());
+
+        // (1 + 2x) - 2x cancels the leading term
+        let diff = &p - &Polynomial::new(vec![Scalar::ZERO, two]);
+        assert_eq!(diff.degree(), Some(0));
+        assert!(diff.derivative().is_zero());
+        assert_eq!((&p - &p).degree(), None);
+
+        let square = &p * &p;
+        assert_eq!(square.degree(), Some(2));
+        assert_eq!(square.evaluate(two), p.evaluate(two) * p.evaluate(two));
+        assert_eq!(p.derivative(), Polynomial::new(vec![two]));
+    }
+
+    #[test]
+    fn test_degenerate_multiplication() {
+        let c = Scalar::from(7u64);
+        let p = vec![Scalar::ONE, c];
+
+        assert!(poly_mul(&[], &[]).is_empty());
+        assert!(poly_mul(&p, &[]).is_empty());
+        assert!(karatsuba_mul(&[], &p).is_empty());
+        assert!(parallel_karatsuba_mul(&[], &[]).is_empty());
+        assert_eq!(poly_mul(&[c], &[c]), vec![c * c]);
+        assert_eq!(karatsuba_mul(&[c], &p), vec![c, c * c]);
+    }
+
+    #[test]
+    fn test_multiplication_matches_naive_on_random_shapes() {
+        let random_poly = |len: usize| -> Vec<Scalar> {
+            (0..len).map(|_| random_scalar(&mut OsRng).unwrap()).collect()
+        };
+
+        // Boundary shapes around the recursion and split thresholds, plus random ones
+        let mut shapes = vec![
+            (0, 0),
+            (0, 5),
+            (1, 1),
+            (1, 100),
+            (33, 1),
+            (32, 33),
+            (64, 65),
+            (1030, 1),
+            (1030, 40),
+            (1, 1100),
+        ];
+        for _ in 0..40 {
+            shapes.push((OsRng.gen_range(0..=150), OsRng.gen_range(0..=150)));
+        }
+
+        for (len_a, len_b) in shapes {
+            let a = random_poly(len_a);
+            let b = random_poly(len_b);
+            let expected = naive_mul(&a, &b);
+
+            assert_eq!(karatsuba_mul(&a, &b), expected, "karatsuba {}x{}", len_a, len_b);
+            assert_eq!(parallel_karatsuba_mul(&a, &b), expected, "parallel {}x{}", len_a, len_b);
+            assert_eq!(poly_mul(&a, &b), expected, "poly_mul {}x{}", len_a, len_b);
+        }
+    }
+
+    #[test]
+    fn test_multipoint_evaluation() {
+        let poly: Vec<Scalar> = (0..100u64).map(|i| Scalar::from(3 * i + 1)).collect();
+        let points: Vec<Scalar> = (1..=FAST_EVALUATION_THRESHOLD as u64)
+            .map(|i| Scalar::from(i * 7))
+            .collect();
+
+        let expected: Vec<Scalar> = points.iter().map(|&x| poly_evaluate(&poly, x)).collect();
+        assert_eq!(poly_evaluate_multi(&poly, &points), expected);
+    }
+
+    #[test]
+    fn test_fast_lagrange_coefficients() {
+        // Interpolating 1, x and x^2 at zero must give 1, 0 and 0.
+        let xs: Vec<Scalar> = (1..=FAST_EVALUATION_THRESHOLD as u64 + 5)
+            .map(|i| Scalar::from(i * 3 + 2))
+            .collect();
+        let lambda = compute_lagrange_coefficients(&xs).unwrap();
+
+        assert_eq!(lambda.iter().sum::<Scalar>(), Scalar::ONE);
+        assert_eq!(xs.iter().zip(&lambda).map(|(x, l)| x * l).sum::<Scalar>(), Scalar::ZERO);
+        assert_eq!(
+            xs.iter().zip(&lambda).map(|(x, l)| x * x * l).sum::<Scalar>(),
+            Scalar::ZERO
+        );
+    }
+
     #[test]
     fn test_error_handling() {
         let empty_shares: Vec<ShareData> = vec![];
@@ -433,7 +943,7 @@ mod tests {
     #[test]
     fn test_performance_metrics() {
         let secret = Scalar::from(123u64);
-        let shares = generate_key_shares(secret, 5, 10);
+        let shares = generate_key_shares(secret, 5, 10).unwrap();
 
         let start = std::time::Instant::now();
         let _recovered = recover_secret_fft(&shares[..5]).unwrap();
@@ -441,4 +951,68 @@ mod tests {
 
         assert!(duration.as_millis() < 100); // Should be very fast
     }
+
+    #[test]
+    fn test_constant_time_recovery_matches_fft() {
+        let secret = Scalar::from(77u64);
+        let shares = generate_key_shares(secret, 4, 7).unwrap();
+
+        assert_eq!(recover_secret_ct(&shares[1..5]).unwrap(), secret);
+        assert_eq!(
+            recover_secret_ct(&shares).unwrap(),
+            recover_secret_fft(&shares).unwrap()
+        );
+
+        let duplicated = vec![shares[0].clone(), shares[1].clone(), shares[0].clone()];
+        assert!(matches!(
+            recover_secret_ct(&duplicated),
+            Err(LagrangeError::DuplicateShareIndex { index: 1 })
+        ));
+        let mut zero = shares[..4].to_vec();
+        zero[2].index = 0;
+        assert!(matches!(
+            recover_secret_ct(&zero),
+            Err(LagrangeError::InvalidShareIndex { index: 0 })
+        ));
+        assert!(matches!(
+            recover_secret_ct(&[]),
+            Err(LagrangeError::InsufficientShares { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verified_recovery_rejects_foreign_shares() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let (shares, commitments) =
+            crate::sharing::generate_key_shares_with_commitments(secret, 3, 5).unwrap();
+        assert_eq!(
+            recover_secret_verified(&shares[1..4], &commitments).unwrap(),
+            secret
+        );
+
+        // A valid slice of another dealing interpolates to a wrong secret unchecked.
+        let (other, _) =
+            crate::sharing::generate_key_shares_with_commitments(secret, 3, 5).unwrap();
+        let mut mixed = shares[..3].to_vec();
+        mixed[1] = other[1].clone();
+        assert_ne!(recover_secret_ct(&mixed).unwrap(), secret);
+        assert!(matches!(
+            recover_secret_verified(&mixed, &commitments),
+            Err(LagrangeError::InconsistentShare { index: 2 })
+        ));
+
+        assert!(matches!(
+            recover_secret_verified(&shares[..2], &commitments),
+            Err(LagrangeError::InsufficientShares {
+                needed: 3,
+                provided: 2
+            })
+        ));
+        let mut repeated = shares[..3].to_vec();
+        repeated.push(shares[0].clone());
+        assert!(matches!(
+            recover_secret_verified(&repeated, &commitments),
+            Err(LagrangeError::DuplicateShareIndex { .. })
+        ));
+    }
 }
@@ -0,0 +1,251 @@
+//! **Barycentric interpolation**
+//!
+//! The first barycentric form (the modified Lagrange formula) writes the interpolating polynomial
+//! through `(x_0, y_0)..(x_{n-1}, y_{n-1})` as
+//!
+//! `p(x) = ℓ(x) · Σ_i w_i·y_i / (x - x_i)` with `ℓ(x) = Π_j (x - x_j)` and
+//! `w_i = 1 / Π_{j≠i} (x_i - x_j)`,
+//!
+//! and `p(x_i) = y_i` at the nodes themselves. The weights depend on the nodes only: once
+//! [`BarycentricWeights`] are computed (`O(n²)` operations, one inversion), the polynomial through
+//! any values at those nodes is evaluated at a new point in `O(n)`, without the coefficient form
+//! or per-point Lagrange bases. Over the scalar field the arithmetic is exact either way; the gain
+//! is fewer operations and inversions per evaluation.
+//!
+//! An evaluation multiplies every value by public quantities only, so the values never affect
+//! control flow; only the nodes and the evaluation points do.
+
+use super::{LagrangeError, LagrangeResult};
+use crate::op_counts::{count, Op};
+use crate::sharing::ShareData;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::HashSet;
+
+/// Barycentric weights of one set of slice indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BarycentricWeights {
+    indices: Vec<usize>,
+    xs: Vec<Scalar>,
+    weights: Vec<Scalar>,
+}
+
+impl BarycentricWeights {
+    /// Weights `w_i = 1 / Π_{j≠i} (x_i - x_j)` of the nodes `indices`.
+    ///
+    /// Returns `LagrangeError::InsufficientShares` for no index,
+    /// `LagrangeError::InvalidShareIndex` for a zero index or
+    /// `LagrangeError::DuplicateShareIndex` for a repeated one.
+    pub fn new(indices: &[usize]) -> LagrangeResult<Self> {
+        if indices.is_empty() {
+            return Err(LagrangeError::InsufficientShares {
+                needed: 1,
+                provided: 0,
+            });
+        }
+        let mut seen = HashSet::new();
+        for &index in indices {
+            if index == 0 {
+                return Err(LagrangeError::InvalidShareIndex { index });
+            }
+            if !seen.insert(index) {
+                return Err(LagrangeError::DuplicateShareIndex { index });
+            }
+        }
+        let xs: Vec<Scalar> = indices.iter().map(|&i| Scalar::from(i as u64)).collect();
+        let mut weights: Vec<Scalar> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x_i)| {
+                xs.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, x_j)| x_i - x_j)
+                    .product()
+            })
+            .collect();
+        Scalar::batch_invert(&mut weights);
+        count(Op::ScalarMul, xs.len() * xs.len() + 3 * xs.len());
+        count(Op::Inversion, 1);
+        Ok(Self {
+            indices: indices.to_vec(),
+            xs,
+            weights,
+        })
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Lagrange basis at `x`: entry `i` is `ℓ(x)·w_i / (x - x_i)`, the weight of the value at
+    /// node `i` in the value at `x` (a unit vector if `x` is a node).
+    pub fn basis_at(&self, x: Scalar) -> Vec<Scalar> {
+        if let Some(node) = self.xs.iter().position(|x_i| *x_i == x) {
+            let mut unit = vec![Scalar::ZERO; self.xs.len()];
+            unit[node] = Scalar::ONE;
+            return unit;
+        }
+        let mut differences: Vec<Scalar> = self.xs.iter().map(|x_i| x - x_i).collect();
+        let product: Scalar = differences.iter().product();
+        Scalar::batch_invert(&mut differences);
+        count(Op::ScalarMul, 5 * self.xs.len());
+        count(Op::Inversion, 1);
+        differences
+            .iter()
+            .zip(&self.weights)
+            .map(|(inverse, w)| product * w * inverse)
+            .collect()
+    }
+
+    /// Value at `x` of the polynomial of degree below `n` taking `values[i]` at node `i`.
+    ///
+    /// Returns `LagrangeError::IndexSetMismatch` with index 0 unless there is one value per node.
+    pub fn evaluate(&self, values: &[Scalar], x: Scalar) -> LagrangeResult<Scalar> {
+        Ok(self.evaluate_at_many(values, &[x])?[0])
+    }
+
+    /// [`Self::evaluate`] at every point of `points`, sharing one inversion between them.
+    pub fn evaluate_at_many(
+        &self,
+        values: &[Scalar],
+        points: &[Scalar],
+    ) -> LagrangeResult<Vec<Scalar>> {
+        if values.len() != self.xs.len() {
+            return Err(LagrangeError::IndexSetMismatch { index: 0 });
+        }
+        let n = self.xs.len();
+        // Differences x - x_i of every point from every node; a zero marks a node.
+        let mut differences: Vec<Scalar> = points
+            .iter()
+            .flat_map(|x| self.xs.iter().map(move |x_i| x - x_i))
+            .collect();
+        let nodes: Vec<Option<usize>> = differences
+            .chunks(n)
+            .map(|row| row.iter().position(|d| *d == Scalar::ZERO))
+            .collect();
+        let products: Vec<Scalar> = differences
+            .chunks(n)
+            .map(|row| row.iter().product())
+            .collect();
+        // Inverting a zero difference would zero the whole batch; those rows are not needed.
+        for (row, node) in differences.chunks_mut(n).zip(&nodes) {
+            if node.is_some() {
+                row.fill(Scalar::ONE);
+            }
+        }
+        Scalar::batch_invert(&mut differences);
+        count(Op::ScalarMul, points.len() * (4 * n + 1));
+        count(Op::Inversion, 1);
+
+        Ok(differences
+            .chunks(n)
+            .zip(nodes.iter().zip(&products))
+            .map(|(inverses, (node, product))| match node {
+                Some(position) => values[*position],
+                None => {
+                    let sum: Scalar = inverses
+                        .iter()
+                        .zip(self.weights.iter().zip(values))
+                        .map(|(inverse, (w, y))| inverse * w * y)
+                        .sum();
+                    product * sum
+                }
+            })
+            .collect())
+    }
+}
+
+/// Secret recovery with barycentric interpolation at zero; the alternative to
+/// [`recover_secret_fft`](super::recover_secret_fft) and
+/// [`recover_secret_ct`](super::recover_secret_ct) when the same slices are also evaluated at
+/// other points ([`poly_evaluate_at_many`]).
+///
+/// Like them, this does not verify the slices. Fails like [`BarycentricWeights::new`].
+pub fn recover_secret_barycentric(shares: &[ShareData]) -> LagrangeResult<Scalar> {
+    Ok(poly_evaluate_at_many(shares, &[Scalar::ZERO])?[0])
+}
+
+/// Values at `points` of the polynomial through the slice values of `shares`, e.g. the slice
+/// values at new indices when re-issuing slices.
+///
+/// Fails like [`BarycentricWeights::new`]; the slices are not verified.
+pub fn poly_evaluate_at_many(
+    shares: &[ShareData],
+    points: &[Scalar],
+) -> LagrangeResult<Vec<Scalar>> {
+    let indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+    let values: Vec<Scalar> = shares.iter().map(|s| s.share).collect();
+    BarycentricWeights::new(&indices)?.evaluate_at_many(&values, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrange_fft::{poly_evaluate, recover_secret_fft};
+    use crate::sharing::generate_key_shares;
+    use crate::utils::random_scalar;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_barycentric_matches_coefficient_form() {
+        let coefficients: Vec<Scalar> =
+            (0..4).map(|_| random_scalar(&mut OsRng).unwrap()).collect();
+        let indices = [2, 5, 7, 11];
+        let values: Vec<Scalar> = indices
+            .iter()
+            .map(|&i| poly_evaluate(&coefficients, Scalar::from(i as u64)))
+            .collect();
+        let weights = BarycentricWeights::new(&indices).unwrap();
+        let points: Vec<Scalar> = [0u64, 1, 5, 12, 1000]
+            .iter()
+            .map(|&x| Scalar::from(x))
+            .collect();
+        let expected: Vec<Scalar> = points
+            .iter()
+            .map(|x| poly_evaluate(&coefficients, *x))
+            .collect();
+        assert_eq!(
+            weights.evaluate_at_many(&values, &points).unwrap(),
+            expected
+        );
+        assert_eq!(
+            weights.evaluate(&values, Scalar::from(5u64)).unwrap(),
+            values[1]
+        );
+        assert_eq!(weights.evaluate_at_many(&values, &[]).unwrap(), vec![]);
+        let basis = weights.basis_at(points[3]);
+        let combined: Scalar = basis.iter().zip(&values).map(|(l, y)| l * y).sum();
+        assert_eq!(combined, expected[3]);
+        assert_eq!(weights.basis_at(Scalar::from(7u64))[2], Scalar::ONE);
+        assert!(matches!(
+            weights.evaluate(&values[..3], Scalar::ONE),
+            Err(LagrangeError::IndexSetMismatch { index: 0 })
+        ));
+
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let shares = generate_key_shares(secret, 3, 6).unwrap();
+        assert_eq!(recover_secret_barycentric(&shares[2..5]).unwrap(), secret);
+        assert_eq!(
+            recover_secret_barycentric(&shares[..3]).unwrap(),
+            recover_secret_fft(&shares[..3]).unwrap()
+        );
+        // Re-evaluating three slices at the other indices reproduces the other slices.
+        let others: Vec<Scalar> = (4..=6u64).map(Scalar::from).collect();
+        let reissued = poly_evaluate_at_many(&shares[..3], &others).unwrap();
+        assert!(reissued
+            .iter()
+            .zip(&shares[3..])
+            .all(|(v, s)| *v == s.share));
+
+        let mut repeated = shares[..3].to_vec();
+        repeated[2].index = 1;
+        assert!(matches!(
+            recover_secret_barycentric(&repeated),
+            Err(LagrangeError::DuplicateShareIndex { index: 1 })
+        ));
+        assert!(matches!(
+            recover_secret_barycentric(&[]),
+            Err(LagrangeError::InsufficientShares { .. })
+        ));
+    }
+}
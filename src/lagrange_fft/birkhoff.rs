@@ -0,0 +1,128 @@
+//! **Birkhoff interpolation**
+//!
+//! Lagrange interpolation recovers a polynomial from its values; Birkhoff interpolation recovers it
+//! from values of its derivatives, `f^{(d_i)}(x_i) = y_i`. Writing `f(x) = Σ_j a_j·x^j`, each
+//! point is the linear equation `Σ_{j≥d} j!/(j-d)! · x^{j-d} · a_j = y`, and `k` points determine
+//! a polynomial of degree below `k` exactly when this `k × k` system is non-singular (the points
+//! are *poised*). Unlike Lagrange interpolation that is not automatic: two first-derivative
+//! points alone never determine `a_0`.
+//!
+//! Tassa's hierarchical threshold sharing hands level-`l` holders the derivative of order
+//! `k_{l-1}` of the dealing polynomial, so sets with too few holders from the upper levels are
+//! never poised for `a_0`; see
+//! [`generate_hierarchical_shares`](crate::sharing::generate_hierarchical_shares). The system is
+//! solved by Gauss–Jordan elimination in `O(k³)` scalar operations whose flow depends on the
+//! values; like [`recover_secret_robust`](super::recover_secret_robust), this is not a
+//! constant-time path.
+
+use super::{LagrangeError, LagrangeResult};
+use crate::op_counts::{count, Op};
+use curve25519_dalek::scalar::Scalar;
+
+/// The falling factorial `j·(j-1)···(j-d+1) = j!/(j-d)!`, zero for `d > j`.
+pub fn falling_factorial(j: usize, d: usize) -> Scalar {
+    if d > j {
+        return Scalar::ZERO;
+    }
+    ((j - d + 1)..=j).fold(Scalar::ONE, |acc, f| acc * Scalar::from(f as u64))
+}
+
+/// Coefficients, lowest degree first, of the polynomial of degree below `points.len()` with
+/// `f^{(order)}(x) = value` at every `(x, order, value)` of `points`.
+///
+/// Returns `LagrangeError::InsufficientShares` for no point and `LagrangeError::NotPoised` if the
+/// points do not determine the polynomial (e.g. a repeated point, an order of at least
+/// `points.len()`, or too few low-order points).
+pub fn birkhoff_interpolate(points: &[(Scalar, usize, Scalar)]) -> LagrangeResult<Vec<Scalar>> {
+    let k = points.len();
+    if k == 0 {
+        return Err(LagrangeError::InsufficientShares {
+            needed: 1,
+            provided: 0,
+        });
+    }
+    let mut rows: Vec<Vec<Scalar>> = points
+        .iter()
+        .map(|(x, order, value)| {
+            let mut row = vec![Scalar::ZERO; k + 1];
+            let mut power = Scalar::ONE;
+            for (j, entry) in row.iter_mut().enumerate().take(k).skip(*order) {
+                *entry = falling_factorial(j, *order) * power;
+                power *= x;
+            }
+            row[k] = *value;
+            row
+        })
+        .collect();
+    count(Op::ScalarMul, 2 * k * k);
+
+    for column in 0..k {
+        let found = (column..k)
+            .find(|&r| rows[r][column] != Scalar::ZERO)
+            .ok_or(LagrangeError::NotPoised)?;
+        rows.swap(column, found);
+        count(Op::Inversion, 1);
+        let inverse = rows[column][column].invert();
+        for value in rows[column][column..].iter_mut() {
+            *value *= inverse;
+        }
+        let pivot_row = rows[column].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r == column || factor == Scalar::ZERO {
+                continue;
+            }
+            for (value, pivot) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *value -= factor * pivot;
+            }
+            count(Op::ScalarMul, k + 1 - column);
+        }
+    }
+    Ok(rows.into_iter().map(|row| row[k]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrange_fft::poly_evaluate;
+    use crate::utils::random_scalar;
+    use rand::rngs::OsRng;
+
+    /// `f^{(order)}(x)` of the coefficients `f`.
+    fn derivative_at(f: &[Scalar], order: usize, x: Scalar) -> Scalar {
+        let derivative: Vec<Scalar> = (order..f.len())
+            .map(|j| falling_factorial(j, order) * f[j])
+            .collect();
+        poly_evaluate(&derivative, x)
+    }
+
+    #[test]
+    fn test_birkhoff_recovers_from_derivatives() {
+        let f: Vec<Scalar> = (0..4).map(|_| random_scalar(&mut OsRng).unwrap()).collect();
+        let point = |x: u64, order: usize| {
+            let x = Scalar::from(x);
+            (x, order, derivative_at(&f, order, x))
+        };
+        assert_eq!(falling_factorial(5, 2), Scalar::from(20u64));
+        assert_eq!(falling_factorial(1, 2), Scalar::ZERO);
+
+        // Two values and two second derivatives, and plain Lagrange interpolation.
+        let mixed = [point(1, 0), point(2, 0), point(3, 2), point(4, 2)];
+        assert_eq!(birkhoff_interpolate(&mixed).unwrap(), f);
+        let values = [point(1, 0), point(5, 0), point(6, 0), point(9, 0)];
+        assert_eq!(birkhoff_interpolate(&values).unwrap(), f);
+
+        // One value is not enough to pin the two lowest coefficients against second derivatives.
+        let unpoised = [point(1, 0), point(3, 2), point(4, 2), point(5, 2)];
+        assert!(matches!(
+            birkhoff_interpolate(&unpoised),
+            Err(LagrangeError::NotPoised)
+        ));
+        let repeated = [point(1, 0), point(1, 0), point(3, 0), point(4, 0)];
+        assert!(matches!(
+            birkhoff_interpolate(&repeated),
+            Err(LagrangeError::NotPoised)
+        ));
+        assert!(birkhoff_interpolate(&[]).is_err());
+    }
+}
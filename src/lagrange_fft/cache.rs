@@ -0,0 +1,208 @@
+//! **Precomputed Lagrange coefficients**
+//!
+//! Threshold signing, partial decryption and repeated recoveries weight the same signer set again
+//! and again. [`LagrangeCoefficients`] holds the weights at zero of one index set, computed once;
+//! [`LagrangeCache`] keeps them per index set for callers that see a few sets repeatedly. The
+//! weights depend only on the public indices, so caching them reveals nothing about the slices.
+
+use super::{compute_lagrange_coefficients, LagrangeError, LagrangeResult};
+use crate::op_counts::{count, Op};
+use crate::sharing::ShareData;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Lagrange weights at zero of one index set, in the order of the set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LagrangeCoefficients {
+    indices: Vec<usize>,
+    weights: Vec<Scalar>,
+}
+
+impl LagrangeCoefficients {
+    /// Weights of `indices`: entry `k` is `λ_k = Π_{j≠k} x_j / (x_j - x_k)`.
+    ///
+    /// Returns `LagrangeError::InsufficientShares` for an empty set,
+    /// `LagrangeError::InvalidShareIndex` for a zero index or
+    /// `LagrangeError::DuplicateShareIndex` for a repeated one.
+    pub fn new(indices: &[usize]) -> LagrangeResult<Self> {
+        let mut seen = HashSet::new();
+        for &index in indices {
+            if index == 0 {
+                return Err(LagrangeError::InvalidShareIndex { index });
+            }
+            if !seen.insert(index) {
+                return Err(LagrangeError::DuplicateShareIndex { index });
+            }
+        }
+        let xs: Vec<Scalar> = indices.iter().map(|&i| Scalar::from(i as u64)).collect();
+        Ok(Self {
+            indices: indices.to_vec(),
+            weights: compute_lagrange_coefficients(&xs)?,
+        })
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The weights, in the order of [`Self::indices`].
+    pub fn weights(&self) -> &[Scalar] {
+        &self.weights
+    }
+
+    /// Weight of `index`, `None` outside the set.
+    pub fn weight(&self, index: usize) -> Option<Scalar> {
+        let position = self.indices.iter().position(|&i| i == index)?;
+        Some(self.weights[position])
+    }
+
+    /// Recover the secret from `shares`, which must be at exactly the indices of the set, in
+    /// order.
+    ///
+    /// Like [`recover_secret_ct`](super::recover_secret_ct), this neither verifies the slices nor
+    /// branches on their values. Returns `LagrangeError::IndexSetMismatch` with the index of the
+    /// first slice out of place, or with index 0 if the number of slices differs.
+    pub fn recover(&self, shares: &[ShareData]) -> LagrangeResult<Scalar> {
+        if shares.len() != self.indices.len() {
+            return Err(LagrangeError::IndexSetMismatch { index: 0 });
+        }
+        if let Some(share) = shares
+            .iter()
+            .zip(&self.indices)
+            .find_map(|(share, &index)| (share.index != index).then_some(share))
+        {
+            return Err(LagrangeError::IndexSetMismatch { index: share.index });
+        }
+        count(Op::ScalarMul, shares.len());
+        Ok(shares
+            .iter()
+            .zip(&self.weights)
+            .map(|(share, weight)| share.share * weight)
+            .sum())
+    }
+}
+
+/// Bounded cache of [`LagrangeCoefficients`] keyed by the index set, shareable between threads.
+///
+/// Sets are keyed in order: `[1, 2, 3]` and `[3, 2, 1]` are separate entries. Once `capacity`
+/// sets are held, the set inserted first is evicted.
+#[derive(Debug)]
+pub struct LagrangeCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    by_set: HashMap<Vec<usize>, Arc<LagrangeCoefficients>>,
+    inserted: VecDeque<Vec<usize>>,
+}
+
+impl LagrangeCache {
+    /// Cache holding at most `capacity` index sets (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// Weights of `indices`, computed with [`LagrangeCoefficients::new`] on the first request
+    /// for the set.
+    pub fn get(&self, indices: &[usize]) -> LagrangeResult<Arc<LagrangeCoefficients>> {
+        if let Some(found) = self.lock().by_set.get(indices) {
+            return Ok(Arc::clone(found));
+        }
+        // Computed outside the lock; a concurrent miss on the same set computes it twice.
+        let computed = Arc::new(LagrangeCoefficients::new(indices)?);
+        let mut entries = self.lock();
+        if !entries.by_set.contains_key(indices) {
+            if entries.inserted.len() == self.capacity {
+                if let Some(oldest) = entries.inserted.pop_front() {
+                    entries.by_set.remove(&oldest);
+                }
+            }
+            entries.inserted.push_back(indices.to_vec());
+            entries
+                .by_set
+                .insert(indices.to_vec(), Arc::clone(&computed));
+        }
+        Ok(computed)
+    }
+
+    /// Number of index sets held.
+    pub fn len(&self) -> usize {
+        self.lock().by_set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.by_set.clear();
+        entries.inserted.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+
+    #[test]
+    fn test_precomputed_coefficients_recover_and_cache() {
+        let secret = Scalar::from(1234u64);
+        let shares = generate_key_shares(secret, 3, 6).unwrap();
+        let signers = [shares[1].clone(), shares[3].clone(), shares[4].clone()];
+        let coefficients = LagrangeCoefficients::new(&[2, 4, 5]).unwrap();
+        assert_eq!(coefficients.recover(&signers).unwrap(), secret);
+        assert_eq!(
+            coefficients.weights(),
+            crate::threshold_sig::lagrange_at_zero(&[2, 4, 5])
+                .unwrap()
+                .as_slice()
+        );
+        assert_eq!(coefficients.weight(4), Some(coefficients.weights()[1]));
+        assert_eq!(coefficients.weight(1), None);
+
+        let reordered = [signers[1].clone(), signers[0].clone(), signers[2].clone()];
+        assert!(matches!(
+            coefficients.recover(&reordered),
+            Err(LagrangeError::IndexSetMismatch { index: 4 })
+        ));
+        assert!(matches!(
+            coefficients.recover(&signers[..2]),
+            Err(LagrangeError::IndexSetMismatch { index: 0 })
+        ));
+        assert!(matches!(
+            LagrangeCoefficients::new(&[1, 0]),
+            Err(LagrangeError::InvalidShareIndex { index: 0 })
+        ));
+        assert!(matches!(
+            LagrangeCoefficients::new(&[3, 1, 3]),
+            Err(LagrangeError::DuplicateShareIndex { index: 3 })
+        ));
+
+        let cache = LagrangeCache::new(2);
+        let first = cache.get(&[2, 4, 5]).unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.get(&[2, 4, 5]).unwrap()));
+        assert_eq!(*first, coefficients);
+        cache.get(&[1, 2, 3]).unwrap();
+        cache.get(&[1, 2, 4]).unwrap();
+        // The first set was evicted and is computed again.
+        assert_eq!(cache.len(), 2);
+        assert!(!Arc::ptr_eq(&first, &cache.get(&[2, 4, 5]).unwrap()));
+        assert!(cache.get(&[]).is_err());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,358 @@
+//! **Multi-modular NTT multiplication**
+//!
+//! The Ristretto scalar field order `ℓ` satisfies `ℓ - 1 = 2² · odd`, so the field only has roots
+//! of unity of order 4 and a radix-2 transform cannot be run over it directly. Instead, products
+//! are computed exactly over the integers and then reduced mod `ℓ`:
+//!
+//! 1. every coefficient is taken as its canonical integer in `[0, ℓ)` and reduced modulo nine
+//!    62-bit primes `p = c · 2⁴⁰ + 1`, each of which has a multiplicative subgroup of order `2⁴⁰`;
+//! 2. the product is computed in each `Z/p` with a radix-2 NTT (Montgomery arithmetic);
+//! 3. every coefficient is rebuilt with Garner's algorithm and reduced mod `ℓ`.
+//!
+//! A product coefficient is a sum of at most `min(len_a, len_b)` terms below `ℓ² < 2⁵⁰⁶`, and the
+//! primes multiply to more than `2⁵⁵⁷`, so the reconstruction is exact for inputs shorter than
+//! `2⁵⁰` coefficients. The cost is `O(n log n)` word operations plus `O(n)` scalar reductions.
+
+use curve25519_dalek::scalar::Scalar;
+use rayon::prelude::*;
+use std::sync::LazyLock;
+
+/// Largest supported transform size (`2⁴⁰`), shared by all primes.
+const MAX_LOG_SIZE: u32 = 40;
+
+/// Number of primes; `∏ p > 2⁵⁵⁷` bounds the exact integer product.
+const PRIME_COUNT: usize = 9;
+
+/// `(p, g)` with `p = c · 2⁴⁰ + 1 < 2⁶²` prime and `g` a generator of `(Z/p)*`.
+const PRIMES: [(u64, u64); PRIME_COUNT] = [
+    (0x3fffc00000000001, 11),
+    (0x3fffbe0000000001, 3),
+    (0x3fff840000000001, 19),
+    (0x3fff810000000001, 5),
+    (0x3fff6d0000000001, 3),
+    (0x3fff540000000001, 5),
+    (0x3fff450000000001, 10),
+    (0x3fff3a0000000001, 3),
+    (0x3fff390000000001, 13),
+];
+
+/// Montgomery arithmetic modulo a single NTT prime (`R = 2⁶⁴`).
+#[derive(Clone, Copy)]
+struct Modulus {
+    p: u64,
+    /// `-p⁻¹ mod 2⁶⁴`
+    neg_inv: u64,
+    /// `R² mod p`
+    r2: u64,
+    /// Generator of `(Z/p)*` in Montgomery form.
+    generator: u64,
+    /// `2^(64·j) · R² mod p` for `j = 0..4`; turns a 64-bit limb at position `j` into Montgomery
+    /// form with a single multiplication.
+    limb_factors: [u64; 4],
+}
+
+impl Modulus {
+    const fn new(p: u64, generator: u64) -> Self {
+        // Newton iteration for p⁻¹ mod 2⁶⁴ (p is odd, so `p` itself is correct to 3 bits).
+        let mut inv = p;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+            i += 1;
+        }
+        let r = ((1u128 << 64) % p as u128) as u64;
+        let r2 = ((r as u128 * r as u128) % p as u128) as u64;
+        let mut modulus = Modulus {
+            p,
+            neg_inv: inv.wrapping_neg(),
+            r2,
+            generator: 0,
+            limb_factors: [0; 4],
+        };
+        modulus.generator = modulus.mont(generator);
+        let mut factor = r2;
+        let mut j = 0;
+        while j < 4 {
+            modulus.limb_factors[j] = factor;
+            factor = ((factor as u128 * r as u128) % p as u128) as u64;
+            j += 1;
+        }
+        modulus
+    }
+
+    /// Montgomery reduction of `t < p · 2⁶⁴`: returns `t · R⁻¹ mod p`.
+    #[inline(always)]
+    const fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.neg_inv);
+        let u = ((t + m as u128 * self.p as u128) >> 64) as u64;
+        if u >= self.p {
+            u - self.p
+        } else {
+            u
+        }
+    }
+
+    #[inline(always)]
+    const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    #[inline(always)]
+    const fn add(&self, a: u64, b: u64) -> u64 {
+        let s = a + b;
+        if s >= self.p {
+            s - self.p
+        } else {
+            s
+        }
+    }
+
+    #[inline(always)]
+    const fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + self.p - b
+        }
+    }
+
+    const fn mont(&self, a: u64) -> u64 {
+        self.mul(a % self.p, self.r2)
+    }
+
+    const fn plain(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// `base^exp` for `base` in Montgomery form.
+    fn pow(&self, base: u64, mut exp: u64) -> u64 {
+        let mut result = self.mont(1);
+        let mut base = base;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Residue of a canonical scalar, in Montgomery form.
+    fn residue(&self, scalar: &Scalar) -> u64 {
+        let bytes = scalar.as_bytes();
+        let mut acc = 0;
+        for (j, chunk) in bytes.chunks_exact(8).enumerate() {
+            let limb = u64::from_le_bytes(chunk.try_into().expect("8-byte chunk"));
+            acc = self.add(acc, self.mul(limb, self.limb_factors[j]));
+        }
+        acc
+    }
+
+    /// In-place radix-2 NTT of a power-of-two length slice (Montgomery form in and out).
+    fn transform(&self, values: &mut [u64], inverse: bool) {
+        let n = values.len();
+        let log_n = n.trailing_zeros();
+
+        for i in 1..n {
+            let j = i.reverse_bits() >> (usize::BITS - log_n);
+            if i < j {
+                values.swap(i, j);
+            }
+        }
+
+        let mut twiddles = Vec::with_capacity(n / 2);
+        let mut len = 2;
+        while len <= n {
+            let mut root = self.pow(self.generator, (self.p - 1) / len as u64);
+            if inverse {
+                root = self.pow(root, self.p - 2);
+            }
+            let half = len / 2;
+            twiddles.clear();
+            let mut w = self.mont(1);
+            for _ in 0..half {
+                twiddles.push(w);
+                w = self.mul(w, root);
+            }
+            for block in values.chunks_exact_mut(len) {
+                let (lo, hi) = block.split_at_mut(half);
+                for ((u, v), &w) in lo.iter_mut().zip(hi.iter_mut()).zip(&twiddles) {
+                    let t = self.mul(*v, w);
+                    *v = self.sub(*u, t);
+                    *u = self.add(*u, t);
+                }
+            }
+            len <<= 1;
+        }
+
+        if inverse {
+            let n_inv = self.pow(self.mont(n as u64), self.p - 2);
+            for value in values.iter_mut() {
+                *value = self.mul(*value, n_inv);
+            }
+        }
+    }
+
+    /// Cyclic convolution of the residues of `a` and `b` in a transform of `size` points.
+    /// Returns plain (non-Montgomery) residues of the first `len` coefficients.
+    fn convolve(&self, a: &[Scalar], b: &[Scalar], size: usize, len: usize) -> Vec<u64> {
+        let mut fa = vec![0u64; size];
+        let mut fb = vec![0u64; size];
+        for (dst, src) in fa.iter_mut().zip(a) {
+            *dst = self.residue(src);
+        }
+        for (dst, src) in fb.iter_mut().zip(b) {
+            *dst = self.residue(src);
+        }
+        self.transform(&mut fa, false);
+        self.transform(&mut fb, false);
+        for (x, &y) in fa.iter_mut().zip(&fb) {
+            *x = self.mul(*x, y);
+        }
+        self.transform(&mut fa, true);
+        fa.truncate(len);
+        for value in fa.iter_mut() {
+            *value = self.plain(*value);
+        }
+        fa
+    }
+}
+
+static MODULI: LazyLock<[Modulus; PRIME_COUNT]> =
+    LazyLock::new(|| PRIMES.map(|(p, g)| Modulus::new(p, g)));
+
+/// `GARNER[i][j] = p_j⁻¹ mod p_i` in Montgomery form (for `j < i`).
+static GARNER: LazyLock<[[u64; PRIME_COUNT]; PRIME_COUNT]> = LazyLock::new(|| {
+    let moduli = &*MODULI;
+    let mut table = [[0u64; PRIME_COUNT]; PRIME_COUNT];
+    for (i, row) in table.iter_mut().enumerate() {
+        let m = &moduli[i];
+        for (j, entry) in row.iter_mut().enumerate().take(i) {
+            *entry = m.pow(m.mont(moduli[j].p), m.p - 2);
+        }
+    }
+    table
+});
+
+/// `2⁵¹² mod ℓ`, used to fold the top limb of a reconstructed coefficient.
+static TWO_POW_512: LazyLock<Scalar> = LazyLock::new(|| {
+    let mut wide = [0u8; 64];
+    wide[32] = 1;
+    let two_pow_256 = Scalar::from_bytes_mod_order_wide(&wide);
+    two_pow_256 * two_pow_256
+});
+
+/// Rebuild one coefficient from its residues and reduce it mod `ℓ`.
+fn reconstruct(residues: &[u64; PRIME_COUNT]) -> Scalar {
+    let moduli = &*MODULI;
+    let garner = &*GARNER;
+
+    // Mixed-radix digits: X = v_0 + v_1·p_0 + v_2·p_0·p_1 + ...
+    let mut digits = [0u64; PRIME_COUNT];
+    for i in 0..PRIME_COUNT {
+        let m = &moduli[i];
+        let mut x = residues[i];
+        for j in 0..i {
+            // v_j < 2⁶² < 2·p_i, so one conditional subtraction reduces it.
+            let v = if digits[j] >= m.p {
+                digits[j] - m.p
+            } else {
+                digits[j]
+            };
+            // Plain × Montgomery yields a plain residue.
+            x = m.mul(m.sub(x, v), garner[i][j]);
+        }
+        digits[i] = x;
+    }
+
+    // Horner over the mixed radix in multi-precision: X = v_0 + p_0·(v_1 + p_1·(...)).
+    let mut limbs = [0u64; PRIME_COUNT];
+    limbs[0] = digits[PRIME_COUNT - 1];
+    for i in (0..PRIME_COUNT - 1).rev() {
+        let mut carry = digits[i] as u128;
+        for limb in limbs.iter_mut() {
+            let t = *limb as u128 * moduli[i].p as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        debug_assert_eq!(carry, 0);
+    }
+
+    let mut wide = [0u8; 64];
+    for (chunk, limb) in wide.chunks_exact_mut(8).zip(&limbs) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    Scalar::from_bytes_mod_order_wide(&wide) + Scalar::from(limbs[PRIME_COUNT - 1]) * *TWO_POW_512
+}
+
+/// Multiply two polynomials over the scalar field with the multi-modular NTT.
+pub(crate) fn ntt_mul(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    // The residue arithmetic is native; only the lift back to the scalar field multiplies.
+    crate::op_counts::count(crate::op_counts::Op::ScalarMul, len);
+    assert!(
+        size.trailing_zeros() <= MAX_LOG_SIZE,
+        "polynomial product too large for the NTT primes"
+    );
+
+    let per_prime: Vec<Vec<u64>> = MODULI
+        .par_iter()
+        .map(|m| m.convolve(a, b, size, len))
+        .collect();
+
+    (0..len)
+        .into_par_iter()
+        .map(|k| {
+            let mut residues = [0u64; PRIME_COUNT];
+            for (residue, column) in residues.iter_mut().zip(&per_prime) {
+                *residue = column[k];
+            }
+            reconstruct(&residues)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random_scalar;
+    use rand::rngs::OsRng;
+
+    fn naive(a: &[Scalar], b: &[Scalar]) -> Vec<Scalar> {
+        let mut out = vec![Scalar::ZERO; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                out[i + j] += x * y;
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_ntt_matches_schoolbook() {
+        let a: Vec<Scalar> = (0..97)
+            .map(|_| random_scalar(&mut OsRng).unwrap())
+            .collect();
+        let b: Vec<Scalar> = (0..53)
+            .map(|_| random_scalar(&mut OsRng).unwrap())
+            .collect();
+        assert_eq!(ntt_mul(&a, &b), naive(&a, &b));
+    }
+
+    #[test]
+    fn test_ntt_worst_case_coefficients() {
+        // All coefficients ℓ - 1 maximise the exact integer product.
+        let a = vec![-Scalar::ONE; 300];
+        let b = vec![-Scalar::ONE; 200];
+        assert_eq!(ntt_mul(&a, &b), naive(&a, &b));
+        assert_eq!(
+            ntt_mul(&[Scalar::ONE], &[Scalar::from(5u64)]),
+            vec![Scalar::from(5u64)]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! **Canonical polynomial type**
+//!
+//! The slice functions in [`crate::lagrange_fft`] keep whatever length their inputs and
+//! algorithms produce, so sums, differences and Karatsuba partial products can carry trailing zero
+//! coefficients. [`Polynomial`] always holds its coefficients in canonical form, so its degree is
+//! exact and evaluation and differentiation never iterate over padding.
+
+use super::{poly_add, poly_derivative, poly_evaluate, poly_mul, poly_sub};
+use curve25519_dalek::scalar::Scalar;
+use std::ops::{Add, Mul, Sub};
+
+/// Polynomial over the scalar field, lowest degree first, without trailing zero coefficients.
+///
+/// The zero polynomial has no coefficients and no degree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Scalar>,
+}
+
+/// Drop trailing zero coefficients.
+fn trim(coefficients: &mut Vec<Scalar>) {
+    while coefficients.last() == Some(&Scalar::ZERO) {
+        coefficients.pop();
+    }
+}
+
+impl Polynomial {
+    /// Build a polynomial from coefficients (lowest degree first), trimming trailing zeros.
+    pub fn new(mut coefficients: Vec<Scalar>) -> Self {
+        trim(&mut coefficients);
+        Self { coefficients }
+    }
+
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Canonical coefficients, lowest degree first.
+    pub fn coefficients(&self) -> &[Scalar] {
+        &self.coefficients
+    }
+
+    /// Take the canonical coefficients.
+    pub fn into_coefficients(self) -> Vec<Scalar> {
+        self.coefficients
+    }
+
+    /// Whether this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Coefficient of the highest-degree term, or `None` for the zero polynomial.
+    pub fn leading_coefficient(&self) -> Option<Scalar> {
+        self.coefficients.last().copied()
+    }
+
+    /// Evaluate at `x` with Horner's method.
+    pub fn evaluate(&self, x: Scalar) -> Scalar {
+        poly_evaluate(&self.coefficients, x)
+    }
+
+    /// Formal derivative; constants (and zero) differentiate to the zero polynomial.
+    pub fn derivative(&self) -> Self {
+        if self.coefficients.len() <= 1 {
+            return Self::zero();
+        }
+        Self::new(poly_derivative(&self.coefficients))
+    }
+}
+
+impl From<Vec<Scalar>> for Polynomial {
+    fn from(coefficients: Vec<Scalar>) -> Self {
+        Self::new(coefficients)
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        Polynomial::new(poly_add(&self.coefficients, &other.coefficients))
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        Polynomial::new(poly_sub(&self.coefficients, &other.coefficients))
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        Polynomial::new(poly_mul(&self.coefficients, &other.coefficients))
+    }
+}
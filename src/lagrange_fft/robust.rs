@@ -0,0 +1,241 @@
+//! **Error-correcting recovery**
+//!
+//! Slices of a threshold-`t` dealing are evaluations of a polynomial `P` of degree below `t`, so
+//! `n` slices form a Reed–Solomon codeword that corrects up to `e = ⌊(n - t) / 2⌋` wrong values.
+//! The Berlekamp–Welch decoder looks for a monic error locator `E` of degree `e` and a polynomial
+//! `Q` of degree below `e + t` with `Q(x_i) = y_i · E(x_i)` at every slice: this is a linear
+//! system of `n` equations in `2e + t` unknowns, solvable whenever at most `e` values are wrong,
+//! and every solution has `Q = P · E`. Dividing gives `P`; the slices off `P` are the faulty ones.
+//!
+//! Decoding only looks at slice values, not at proofs or commitments, and its running time
+//! depends on them (Gaussian elimination over `n` rows, `O(n³)` scalar operations): it is meant
+//! for finding out which slices are bad, not as a constant-time recovery path.
+
+use super::{poly_evaluate, LagrangeError, LagrangeResult};
+use crate::op_counts::{count, Op};
+use crate::sharing::ShareData;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::HashSet;
+
+/// Recover the secret of a threshold-`threshold` dealing from `shares`, correcting up to
+/// `(shares.len() - threshold) / 2` slices with wrong values.
+///
+/// # Return value
+///
+/// Returns the secret and the indices of the slices whose values are off the recovered
+/// polynomial, in the order of `shares`; `LagrangeError::InsufficientShares` for fewer than
+/// `threshold` slices or a zero threshold; `LagrangeError::InvalidShareIndex` or
+/// `LagrangeError::DuplicateShareIndex` for a zero or repeated index; and
+/// `LagrangeError::TooManyErrors` if the slices are not within `(n - t) / 2` errors of any
+/// polynomial of degree below `threshold`.
+pub fn recover_secret_robust(
+    shares: &[ShareData],
+    threshold: usize,
+) -> LagrangeResult<(Scalar, Vec<usize>)> {
+    if threshold == 0 || shares.len() < threshold {
+        return Err(LagrangeError::InsufficientShares {
+            needed: threshold.max(1),
+            provided: shares.len(),
+        });
+    }
+    let mut seen = HashSet::new();
+    for share in shares {
+        if share.index == 0 {
+            return Err(LagrangeError::InvalidShareIndex { index: share.index });
+        }
+        if !seen.insert(share.index) {
+            return Err(LagrangeError::DuplicateShareIndex { index: share.index });
+        }
+    }
+
+    let correctable = (shares.len() - threshold) / 2;
+    let too_many = LagrangeError::TooManyErrors { correctable };
+    let xs: Vec<Scalar> = shares
+        .iter()
+        .map(|s| Scalar::from(s.index as u64))
+        .collect();
+    let (q, e) = solve_key_equation(&xs, shares, threshold, correctable).ok_or(too_many.clone())?;
+    let (p, remainder) = divide_monic(&q, &e);
+    if remainder.iter().any(|c| *c != Scalar::ZERO) || p.len() > threshold {
+        return Err(too_many);
+    }
+
+    let faulty: Vec<usize> = shares
+        .iter()
+        .zip(&xs)
+        .filter(|(share, x)| poly_evaluate(&p, **x) != share.share)
+        .map(|(share, _)| share.index)
+        .collect();
+    if faulty.len() > correctable {
+        return Err(too_many);
+    }
+    Ok((p.first().copied().unwrap_or(Scalar::ZERO), faulty))
+}
+
+/// Solve `Q(x_i) = y_i · E(x_i)` for `Q` of degree below `errors + threshold` and monic `E` of
+/// degree `errors`, both lowest degree first; `None` if the system is inconsistent.
+fn solve_key_equation(
+    xs: &[Scalar],
+    shares: &[ShareData],
+    threshold: usize,
+    errors: usize,
+) -> Option<(Vec<Scalar>, Vec<Scalar>)> {
+    // Unknowns: q_0..q_{errors+threshold-1}, then e_0..e_{errors-1}; the last column is the
+    // right-hand side y_i · x_i^errors.
+    let q_len = errors + threshold;
+    let unknowns = q_len + errors;
+    let mut rows: Vec<Vec<Scalar>> = xs
+        .iter()
+        .zip(shares)
+        .map(|(x, share)| {
+            let mut row = Vec::with_capacity(unknowns + 1);
+            let mut power = Scalar::ONE;
+            for _ in 0..q_len {
+                row.push(power);
+                power *= x;
+            }
+            let mut power = Scalar::ONE;
+            for _ in 0..errors {
+                row.push(-share.share * power);
+                power *= x;
+            }
+            row.push(share.share * power);
+            row
+        })
+        .collect();
+    count(Op::ScalarMul, rows.len() * (unknowns + errors + 1));
+
+    // Gauss–Jordan elimination; free unknowns are set to zero.
+    let mut pivots = Vec::with_capacity(unknowns);
+    let mut rank = 0;
+    for column in 0..unknowns {
+        let Some(found) = (rank..rows.len()).find(|&r| rows[r][column] != Scalar::ZERO) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        count(Op::Inversion, 1);
+        let inverse = rows[rank][column].invert();
+        for value in rows[rank][column..].iter_mut() {
+            *value *= inverse;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r == rank || factor == Scalar::ZERO {
+                continue;
+            }
+            for (value, pivot) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *value -= factor * pivot;
+            }
+            count(Op::ScalarMul, unknowns + 1 - column);
+        }
+        pivots.push(column);
+        rank += 1;
+    }
+    if rows[rank..].iter().any(|row| row[unknowns] != Scalar::ZERO) {
+        return None;
+    }
+
+    let mut solution = vec![Scalar::ZERO; unknowns];
+    for (row, &column) in rows.iter().zip(&pivots) {
+        solution[column] = row[unknowns];
+    }
+    let mut e = solution.split_off(q_len);
+    e.push(Scalar::ONE);
+    Some((solution, e))
+}
+
+/// Quotient and remainder of `a` divided by the monic polynomial `m`.
+fn divide_monic(a: &[Scalar], m: &[Scalar]) -> (Vec<Scalar>, Vec<Scalar>) {
+    let d = m.len() - 1;
+    if a.len() <= d {
+        return (Vec::new(), a.to_vec());
+    }
+    let mut remainder = a.to_vec();
+    let mut quotient = vec![Scalar::ZERO; a.len() - d];
+    for i in (d..a.len()).rev() {
+        let c = remainder[i];
+        quotient[i - d] = c;
+        if c != Scalar::ZERO {
+            for j in 0..d {
+                remainder[i - d + j] -= c * m[j];
+            }
+            remainder[i] = Scalar::ZERO;
+        }
+    }
+    remainder.truncate(d);
+    while quotient.last() == Some(&Scalar::ZERO) {
+        quotient.pop();
+    }
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use crate::utils::random_scalar;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_robust_recovery_identifies_faulty_shares() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let shares = generate_key_shares(secret, 3, 9).unwrap();
+        assert_eq!(recover_secret_robust(&shares, 3).unwrap(), (secret, vec![]));
+
+        // Up to (9 - 3) / 2 = 3 wrong values are corrected, wherever they are.
+        for faulty in [vec![1], vec![2, 9], vec![4, 5, 8]] {
+            let mut corrupted = shares.clone();
+            for &index in &faulty {
+                corrupted[index - 1].share += Scalar::from(index as u64);
+            }
+            assert_eq!(
+                recover_secret_robust(&corrupted, 3).unwrap(),
+                (secret, faulty.clone())
+            );
+            // Reordered slices report the faulty indices in the order given.
+            corrupted.reverse();
+            let (recovered, mut reported) = recover_secret_robust(&corrupted, 3).unwrap();
+            assert_eq!(recovered, secret);
+            reported.reverse();
+            assert_eq!(reported, faulty);
+        }
+
+        let mut corrupted = shares.clone();
+        for share in &mut corrupted[..4] {
+            share.share += Scalar::ONE;
+        }
+        assert!(matches!(
+            recover_secret_robust(&corrupted, 3),
+            Err(LagrangeError::TooManyErrors { correctable: 3 })
+        ));
+
+        // Without redundancy nothing can be corrected, and a 1-of-n dealing tolerates the most.
+        assert_eq!(
+            recover_secret_robust(&shares[..3], 3).unwrap(),
+            (secret, vec![])
+        );
+        let copies = generate_key_shares(secret, 1, 5).unwrap();
+        let mut corrupted = copies.clone();
+        corrupted[0].share = Scalar::ONE;
+        corrupted[3].share = Scalar::ONE;
+        assert_eq!(
+            recover_secret_robust(&corrupted, 1).unwrap(),
+            (secret, vec![1, 4])
+        );
+
+        assert!(matches!(
+            recover_secret_robust(&shares[..2], 3),
+            Err(LagrangeError::InsufficientShares {
+                needed: 3,
+                provided: 2
+            })
+        ));
+        let mut repeated = shares.clone();
+        repeated[1].index = 1;
+        assert!(matches!(
+            recover_secret_robust(&repeated, 3),
+            Err(LagrangeError::DuplicateShareIndex { index: 1 })
+        ));
+    }
+}
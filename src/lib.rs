@@ -0,0 +1,3538 @@
+//! # Enterprise Zero-Knowledge Threshold Secret Sharing System
+//!
+//! A production-ready cryptographic library implementing polynomial-based secret sharing
+//! with zero-knowledge proofs, FFT-accelerated Lagrange interpolation, and enterprise-grade
+//! security features.
+//!
+//! ## Key Features
+//! - Information-theoretic security with formal proofs
+//! - Cross-platform compatibility (Windows, Linux, macOS)
+//! - BLAKE3 hash function (15x faster than SHA-256)
+//! - Enterprise audit trails and compliance
+//! - Performance-optimized algorithms
+//! - Comprehensive error handling
+//!
+//! ## Build profiles
+//! - default (`dealer`): dealing, recovery, key management and the demonstration binary
+//! - `--no-default-features --features verify-only`: proof/commitment verification,
+//!   serialization and hashing only, with no RNG and no rayon
+//! - `--target wasm32-unknown-unknown --features wasm`: the dealer profile plus `wasm-bindgen`
+//!   bindings for browsers (`wasm` module)
+//! - `--features ffi`: the dealer profile plus a C ABI (`ffi` module, header
+//!   `include/zk_thresh_pro.h`) exported from the `cdylib`
+//! - `--features channel-service`: the dealer profile plus the `transport` channels and a
+//!   share-coordination service answering remote participants over them (`service` module)
+//!
+//! The enterprise demonstration lives in `src/bin/demo.rs` (`cargo run --bin demo`).
+
+#[cfg(feature = "dealer")]
+pub mod access;
+pub mod algorithms;
+pub mod archive;
+pub mod audit;
+pub mod beacon;
+#[cfg(feature = "secp256k1")]
+pub mod bip340;
+pub mod canonical_json;
+pub mod ceremony;
+pub mod collection;
+#[cfg(test)]
+mod compat_tests;
+pub mod context;
+pub mod counters;
+#[cfg(feature = "ct-harness")]
+pub mod ct_harness;
+pub mod custodian;
+pub mod deadline;
+pub mod dealing;
+pub mod derivation;
+#[cfg(feature = "dealer")]
+pub mod dkg;
+pub mod ed25519;
+pub mod error;
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod frost;
+pub mod group;
+pub mod hash_adapter;
+pub mod hazmat;
+pub mod identity;
+pub mod indexing;
+pub mod interop;
+#[cfg(feature = "dealer")]
+pub mod key_lifecycle;
+#[cfg(feature = "dealer")]
+pub mod key_provider;
+#[cfg(feature = "dealer")]
+pub mod lagrange_fft;
+pub mod limits;
+#[cfg(feature = "dealer")]
+pub mod metrics;
+#[cfg(feature = "dealer")]
+pub mod mpc;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod op_counts;
+pub mod params;
+pub mod policy_proof;
+pub mod proof;
+#[cfg(feature = "dealer")]
+pub mod public_state;
+pub mod pvss;
+#[cfg(feature = "dealer")]
+pub mod reconfigure;
+pub mod recovered;
+#[cfg(feature = "dealer")]
+pub mod recovery;
+pub mod redact;
+pub mod registry;
+#[cfg(all(test, feature = "scale-tests"))]
+mod scaling_tests;
+pub mod sealing;
+pub mod serialization;
+#[cfg(feature = "channel-service")]
+pub mod service;
+#[cfg(feature = "dealer")]
+pub mod shareholder;
+pub mod sharing;
+#[cfg(feature = "dealer")]
+pub mod status;
+pub mod threshold_decrypt;
+pub mod threshold_sig;
+pub mod transcript;
+#[cfg(feature = "transport")]
+pub mod transport;
+pub mod utils;
+pub mod vss;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod wrapping;
+
+#[cfg(feature = "dealer")]
+use crate::events::EventCode;
+#[cfg(feature = "dealer")]
+use crate::utils::Stopwatch;
+#[cfg(feature = "dealer")]
+pub use access::{LinearSharingScheme, Policy, PolicyShare};
+pub use algorithms::{AlgorithmId, AlgorithmKind, AlgorithmSuite};
+pub use archive::{Archive, ArchiveCodec, ArchiveWriter};
+pub use canonical_json::JsonFormat;
+pub use ceremony::{Ceremony, CeremonyStore, FileCeremonyStore, MemoryCeremonyStore};
+pub use collection::{CollectionStatus, RecoveryCollection, ShareFeedback};
+pub use context::OperationContext;
+pub use counters::{CounterStore, FileCounterStore, MemoryCounterStore};
+#[cfg(feature = "dealer")]
+use curve25519_dalek::scalar::Scalar;
+pub use custodian::ShareAttestation;
+pub use deadline::{CancellationToken, Deadline};
+pub use dealing::{AggregateProof, Dealing};
+pub use error::{
+    AuditLogger, AuditRecord, CryptoError, CryptoResult, ErrorHandler, FailureClass, SecurityEvent,
+    Severity, VerifyError,
+};
+pub use hash_adapter::{
+    Blake3Adapter, HashAdapter, HashFunction, SecurityLevel, Sha256Adapter, Sha3Adapter,
+};
+#[cfg(feature = "dealer")]
+pub use key_lifecycle::{Key, KeyRotationManager, KeyState, RotationEntry};
+#[cfg(feature = "dealer")]
+pub use key_provider::{KeyProvider, SoftwareKeyProvider};
+#[cfg(feature = "dealer")]
+pub use lagrange_fft::recover_secret_verified;
+pub use limits::{limits, Limits, VerifiedLimit};
+#[cfg(feature = "dealer")]
+use log::Level;
+pub use op_counts::OperationCounts;
+pub use params::PublicParameters;
+pub use policy_proof::{CommitteeMember, ContributionProof, PolicyComplianceProof};
+#[cfg(feature = "dealer")]
+pub use proof::{generate_proof, generate_proof_with};
+pub use proof::{verify_proof, verify_proof_with, Proof, Transcript};
+#[cfg(feature = "dealer")]
+pub use public_state::{KeyPolicy, PublicState};
+pub use pvss::{PvssDealing, PvssPublicKey, PvssSecretKey};
+pub use recovered::{RecoveredSecret, SecretEncoding};
+pub use redact::{fingerprint, Redacted};
+pub use registry::{RegistryEntry, ShareRegistry, ShareStatus};
+pub use sealing::{SealedShare, SealingKey};
+pub use serialization::{Ciphersuite, ShareFile};
+#[cfg(feature = "dealer")]
+pub use shareholder::{
+    FileShareStore, MemoryShareStore, ShareStore, Shareholder, ShareholderPolicy, StoredShare,
+};
+#[cfg(feature = "dealer")]
+pub use sharing::typed::{Sharing, TypedShare};
+#[cfg(feature = "dealer")]
+pub use sharing::{
+    adjust_threshold, generate_hierarchical_shares, generate_key_shares,
+    generate_key_shares_deferred, generate_key_shares_multi, generate_key_shares_with_commitments,
+    generate_policy_shares, generate_share_proofs, generate_share_proofs_with,
+    issue_additional_share, recover_secret, recover_secret_hierarchical,
+    recover_secret_with_policy, refresh_shares, reprove_shares, update_shares,
+    update_shares_linked, update_shares_with_commitments,
+};
+pub use sharing::{HierarchicalShare, MultiShareData, ShareData, ShareProof, UnprovenShare};
+#[cfg(feature = "dealer")]
+pub use status::SystemStatus;
+pub use threshold_sig::{NonceCommitment, PartialSignature, SchnorrSignature};
+pub use transcript::{CeremonyKind, CeremonyTranscript, TimestampAuthority, TimestampToken};
+#[cfg(feature = "dealer")]
+pub use vss::verify_share_validity_batch;
+pub use vss::{
+    verify_multi_share_validity, verify_share_validity, verify_share_validity_with_commitments,
+    CommitmentDiff, RefreshLinks, RefreshRecord, ShareLink, VssCommitments,
+};
+pub use wrapping::{WrappedShare, WrappingPublicKey, WrappingSecretKey};
+
+/// Enterprise configuration settings
+#[derive(Debug, Clone)]
+pub struct EnterpriseConfig {
+    pub security_level: SecurityLevel,
+    pub audit_enabled: bool,
+    /// Where audit records are persisted when `audit_enabled`; none by default
+    pub audit_sinks: Vec<audit::AuditSinkConfig>,
+    pub performance_monitoring: bool,
+    pub compliance_mode: ComplianceMode,
+    /// Cryptoperiod of a key's slices (see [`KeyRotationManager`]) and lifetime of keys from
+    /// [`EnterpriseCryptoSystem::generate_enterprise_key`]
+    pub max_key_lifetime_hours: u64,
+    /// Hash behind proof challenges and key derivation; `None` picks it from `compliance_mode`
+    /// (see [`EnterpriseConfig::hash`])
+    pub hash_function: Option<HashFunction>,
+}
+
+/// Compliance modes for different regulatory requirements
+#[derive(Debug, Clone)]
+pub enum ComplianceMode {
+    /// Standard enterprise security
+    Standard,
+    /// FIPS 140-2 Level 3 compliance
+    Fips140L3,
+    /// Common Criteria EAL4+ compliance
+    CommonCriteriaEAL4Plus,
+    /// Custom compliance requirements
+    Custom(String),
+}
+
+impl EnterpriseConfig {
+    /// The [`PublicParameters`] this configuration runs with, as negotiated by
+    /// [`transport`](crate::transport) peers.
+    pub fn public_parameters(&self) -> PublicParameters {
+        PublicParameters::new(Ciphersuite::Ristretto255Blake3, self.security_level)
+    }
+
+    /// The hash function the system runs with: `hash_function` if set, otherwise SHA-256 under
+    /// [`ComplianceMode::Fips140L3`] and BLAKE3 under every other mode
+    pub fn hash(&self) -> HashFunction {
+        match (self.hash_function, &self.compliance_mode) {
+            (Some(function), _) => function,
+            (None, ComplianceMode::Fips140L3) => HashFunction::Sha256,
+            (None, _) => HashFunction::Blake3,
+        }
+    }
+}
+
+impl Default for EnterpriseConfig {
+    fn default() -> Self {
+        Self {
+            security_level: SecurityLevel::High,
+            audit_enabled: true,
+            audit_sinks: Vec::new(),
+            performance_monitoring: true,
+            compliance_mode: ComplianceMode::Standard,
+            max_key_lifetime_hours: 24,
+            hash_function: None,
+        }
+    }
+}
+
+/// Enterprise cryptographic system
+#[cfg(feature = "dealer")]
+pub struct EnterpriseCryptoSystem {
+    config: EnterpriseConfig,
+    error_handler: ErrorHandler,
+    metrics: metrics::MetricsBuffer,
+    transcripts: Vec<CeremonyTranscript>,
+    attestations: Vec<status::CustodianStatus>,
+    timestamp_authority: Option<Box<dyn TimestampAuthority + Send + Sync>>,
+    registry: ShareRegistry,
+    counters: Box<dyn CounterStore + Send + Sync>,
+    safe_mode: Option<String>,
+    /// Expiry time of every key with one, by key id.
+    key_expiries: Vec<(String, chrono::DateTime<chrono::Utc>)>,
+    /// Adapter of [`EnterpriseConfig::hash`].
+    hash: Box<dyn HashAdapter>,
+}
+
+#[cfg(feature = "dealer")]
+impl EnterpriseCryptoSystem {
+    /// Initialize enterprise cryptographic system
+    pub fn new(config: EnterpriseConfig) -> Self {
+        let mut error_handler = ErrorHandler::default();
+        if config.audit_enabled {
+            for sink in &config.audit_sinks {
+                error_handler.audit_logger.add_sink(sink.build());
+            }
+        }
+        Self {
+            hash: config.hash().adapter(),
+            config,
+            error_handler,
+            metrics: metrics::MetricsBuffer::new(),
+            transcripts: Vec::new(),
+            attestations: Vec::new(),
+            timestamp_authority: None,
+            registry: ShareRegistry::new(),
+            counters: Box::new(MemoryCounterStore::new()),
+            safe_mode: None,
+            key_expiries: Vec::new(),
+        }
+    }
+
+    /// Stamp every subsequent ceremony transcript with `authority`
+    pub fn set_timestamp_authority(
+        &mut self,
+        authority: Box<dyn TimestampAuthority + Send + Sync>,
+    ) {
+        self.timestamp_authority = Some(authority);
+    }
+
+    /// Keep epochs and operation sequence numbers in `store`, e.g. a [`FileCounterStore`], so
+    /// they are not reused after a restart
+    ///
+    /// The default store is in memory.
+    pub fn set_counter_store(&mut self, store: Box<dyn CounterStore + Send + Sync>) {
+        self.counters = store;
+    }
+
+    /// The [`PublicParameters`] of the configuration, for `transport` peers
+    pub fn public_parameters(&self) -> PublicParameters {
+        self.config.public_parameters()
+    }
+
+    /// Context for a new operation on `key_id` at its current epoch
+    ///
+    /// The operation id is `<kind>-<sequence>`, with a sequence number taken from the counter
+    /// store.
+    pub fn begin_operation(&mut self, key_id: &str, kind: &str) -> CryptoResult<OperationContext> {
+        let epoch = self
+            .counters
+            .get(&counters::epoch_counter(key_id))?
+            .unwrap_or(0);
+        self.operation_context(key_id, epoch, kind)
+    }
+
+    /// Context for a dealing, refresh or reshare moving `key_id` to its next epoch
+    ///
+    /// Epoch 0 for a key that was never dealt. The epoch is claimed when the operation runs.
+    pub fn begin_epoch(&mut self, key_id: &str, kind: &str) -> CryptoResult<OperationContext> {
+        let epoch = match self.counters.get(&counters::epoch_counter(key_id))? {
+            Some(epoch) => epoch
+                .checked_add(1)
+                .ok_or_else(|| CryptoError::ResourceExhaustion {
+                    resource: "epoch".to_string(),
+                })?,
+            None => 0,
+        };
+        self.operation_context(key_id, epoch, kind)
+    }
+
+    fn operation_context(
+        &mut self,
+        key_id: &str,
+        epoch: u64,
+        kind: &str,
+    ) -> CryptoResult<OperationContext> {
+        let sequence = self.counters.increment(counters::OPERATION_SEQUENCE)?;
+        Ok(OperationContext::new(
+            key_id,
+            epoch,
+            &format!("{}-{}", kind, sequence),
+        ))
+    }
+
+    /// Record `context.epoch` as the epoch of its key before issuing slices under it.
+    ///
+    /// Called once the new slices have been computed from validated inputs, so an operation that
+    /// fails on its parameters or slices leaves the epoch free for a corrected retry. Returns
+    /// `CryptoError::Validation` if the key already reached that epoch.
+    fn claim_epoch(&mut self, context: &OperationContext) -> CryptoResult<()> {
+        self.check_epoch(context)?;
+        self.counters
+            .advance(&counters::epoch_counter(&context.key_id), context.epoch)
+    }
+
+    /// Fail early, before any work, if the key already reached `context.epoch`.
+    fn check_epoch(&self, context: &OperationContext) -> CryptoResult<()> {
+        if let Some(current) = self
+            .counters
+            .get(&counters::epoch_counter(&context.key_id))?
+        {
+            if context.epoch <= current {
+                return Err(CryptoError::Validation {
+                    field: "epoch".to_string(),
+                    reason: format!(
+                        "key {} already reached epoch {}; epoch {} cannot be reused",
+                        context.key_id, current, context.epoch
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform comprehensive security validation
+    ///
+    /// A failing self-test puts the system in safe mode (see [`Self::enter_safe_mode`]). Fails with
+    /// `CryptoError::Configuration` if [`ComplianceMode::Fips140L3`] is run with BLAKE3.
+    pub fn validate_security(&mut self) -> CryptoResult<()> {
+        structured_log!(
+            Level::Info,
+            EventCode::SecurityValidationStarted,
+            "performing enterprise security validation"
+        );
+
+        // Validate BLAKE3 security properties
+        if let Err(e) = hash_adapter::SecurityValidator::validate_security_properties() {
+            self.enter_safe_mode(&format!("self-test failed: {}", e));
+            return Err(CryptoError::SecurityViolation { details: e });
+        }
+
+        // Validate compliance mode
+        match &self.config.compliance_mode {
+            ComplianceMode::Fips140L3 => {
+                if self.config.hash() == HashFunction::Blake3 {
+                    return Err(CryptoError::Configuration {
+                        parameter: "hash_function".to_string(),
+                        issue: "FIPS 140-2 mode requires SHA-256 or SHA-3, not BLAKE3".to_string(),
+                    });
+                }
+                structured_log!(
+                    Level::Info,
+                    EventCode::ComplianceModeEnabled,
+                    "FIPS 140-2 Level 3 compliance mode enabled",
+                    mode = "fips140_l3",
+                    hash = self.config.hash().algorithm().name()
+                );
+            }
+            ComplianceMode::CommonCriteriaEAL4Plus => {
+                structured_log!(
+                    Level::Info,
+                    EventCode::ComplianceModeEnabled,
+                    "Common Criteria EAL4+ compliance mode enabled",
+                    mode = "common_criteria_eal4plus"
+                );
+            }
+            ComplianceMode::Standard => {
+                structured_log!(
+                    Level::Info,
+                    EventCode::ComplianceModeEnabled,
+                    "standard enterprise security mode enabled",
+                    mode = "standard"
+                );
+            }
+            ComplianceMode::Custom(mode) => {
+                structured_log!(
+                    Level::Info,
+                    EventCode::ComplianceModeEnabled,
+                    "custom compliance mode enabled",
+                    mode = "custom",
+                    custom_mode = mode
+                );
+            }
+        }
+
+        // Performance benchmark
+        let performance = hash_adapter::SecurityValidator::benchmark_performance();
+        structured_log!(
+            Level::Info,
+            EventCode::HashBenchmarkCompleted,
+            "BLAKE3 benchmark completed",
+            algorithm = "blake3",
+            ns_per_mb = performance
+        );
+
+        Ok(())
+    }
+
+    /// Refuse every secret-producing operation until [`Self::exit_safe_mode`]
+    ///
+    /// Key generation, dealing, proving, refresh, recovery and key material derivation then fail
+    /// with `CryptoError::SafeMode`; verification, revocation, auditing and status reads keep
+    /// working. Entered automatically when [`Self::validate_security`] fails or an operation runs
+    /// out of entropy, and by operators as a policy command. Logged as
+    /// [`SecurityEvent::SafeModeEntered`]; has no effect if the system is already in safe mode.
+    pub fn enter_safe_mode(&mut self, reason: &str) {
+        if self.safe_mode.is_some() {
+            return;
+        }
+        self.safe_mode = Some(reason.to_string());
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SafeModeEntered {
+                reason: reason.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+    }
+
+    /// Leave safe mode once [`Self::validate_security`] passes again
+    ///
+    /// Logged as [`SecurityEvent::SafeModeExited`]. Returns the self-test error, staying in safe
+    /// mode, if it fails.
+    pub fn exit_safe_mode(&mut self) -> CryptoResult<()> {
+        if self.safe_mode.is_none() {
+            return Ok(());
+        }
+        self.validate_security()?;
+        self.safe_mode = None;
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SafeModeExited {
+                timestamp: chrono::Utc::now(),
+            });
+        Ok(())
+    }
+
+    /// Why the system is in safe mode, if it is
+    pub fn safe_mode(&self) -> Option<&str> {
+        self.safe_mode.as_deref()
+    }
+
+    /// Fail `operation` with `CryptoError::SafeMode` in safe mode, auditing the refusal.
+    fn refuse_in_safe_mode(&mut self, operation: &str) -> CryptoResult<()> {
+        let Some(reason) = self.safe_mode.clone() else {
+            return Ok(());
+        };
+        let error = CryptoError::SafeMode {
+            operation: operation.to_string(),
+            reason,
+        };
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::PolicyViolation {
+                policy: "safe_mode".to_string(),
+                violation: error.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+        Err(error)
+    }
+
+    /// Fail `operation` with `CryptoError::KeyExpired` if `context.key_id` has expired, auditing
+    /// the refusal as [`SecurityEvent::KeyExpired`].
+    fn refuse_expired_key(
+        &mut self,
+        context: &OperationContext,
+        operation: &str,
+    ) -> CryptoResult<()> {
+        let Some(expired_at) = self
+            .key_expiry(&context.key_id)
+            .filter(|expiry| chrono::Utc::now() >= *expiry)
+        else {
+            return Ok(());
+        };
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::KeyExpired {
+                key_id: context.key_id.clone(),
+                operation: operation.to_string(),
+                expired_at,
+                timestamp: chrono::Utc::now(),
+            });
+        Err(CryptoError::KeyExpired {
+            key_id: context.key_id.clone(),
+            expired_at,
+        })
+    }
+
+    /// Enforce the expiry time of `key`, identified by `key_id`, e.g. a key loaded from a
+    /// [`KeyProvider`] after a restart
+    ///
+    /// Dealing from and recovering `key_id` are refused with `CryptoError::KeyExpired` from
+    /// [`Key::expiry_time`] on. Replaces a previously registered expiry; a key without expiry
+    /// time is no longer enforced. Keys from [`Self::generate_enterprise_key`] are registered
+    /// already.
+    pub fn register_key(&mut self, key_id: &str, key: &Key) {
+        self.key_expiries.retain(|(id, _)| id != key_id);
+        if let Some(expiry) = key.expiry_time {
+            self.key_expiries.push((key_id.to_string(), expiry));
+        }
+    }
+
+    /// Expiry time enforced for `key_id`, if any
+    pub fn key_expiry(&self, key_id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.key_expiries
+            .iter()
+            .find(|(id, _)| id == key_id)
+            .map(|(_, expiry)| *expiry)
+    }
+
+    /// Reprove slices from [`sharing`], which proves with BLAKE3, under the configured hash.
+    fn prove_with_hash(
+        &self,
+        mut shares: Vec<ShareData>,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        if self.hash.algorithm() != AlgorithmId::Blake3 {
+            sharing::reprove_shares_within(&*self.hash, &mut shares, context.deadline())?;
+        }
+        Ok(shares)
+    }
+
+    /// Enter safe mode if `result` failed for lack of entropy.
+    fn check_entropy<T>(&mut self, result: CryptoResult<T>) -> CryptoResult<T> {
+        if let Err(error @ CryptoError::InsufficientEntropy { .. }) = &result {
+            self.enter_safe_mode(&format!("entropy failure: {}", error));
+        }
+        result
+    }
+
+    /// Check the proofs of slices presented to the system and that none was revoked.
+    ///
+    /// Each slice is audited as [`SecurityEvent::ShareVerified`] or
+    /// [`SecurityEvent::ShareVerificationFailed`], and a revoked one as
+    /// [`SecurityEvent::RevokedShareRejected`].
+    fn validate_presented_shares(
+        &mut self,
+        shares: &[ShareData],
+        context: &OperationContext,
+    ) -> CryptoResult<()> {
+        for share in shares {
+            context.check_deadline("share_validation")?;
+            let verified =
+                proof::verify_proof_with(&*self.hash, &share.proof, share.commitment, share.index);
+            self.error_handler.audit_logger.log_event(match &verified {
+                Ok(()) => SecurityEvent::ShareVerified {
+                    key_id: context.key_id.clone(),
+                    index: share.index,
+                    timestamp: chrono::Utc::now(),
+                },
+                Err(e) => SecurityEvent::ShareVerificationFailed {
+                    key_id: context.key_id.clone(),
+                    index: share.index,
+                    code: e.code().to_string(),
+                    timestamp: chrono::Utc::now(),
+                },
+            });
+            if let Err(e) = verified {
+                return Err(CryptoError::CryptographicOperation {
+                    operation: format!("share_validation: {} {}", e.code(), e),
+                });
+            }
+        }
+        for share in shares {
+            if let Some(entry) = self.registry.revoked_entry(share) {
+                let (operation_id, index) = (entry.operation_id.clone(), entry.index);
+                self.error_handler
+                    .audit_logger
+                    .log_event(SecurityEvent::RevokedShareRejected {
+                        key_id: context.key_id.clone(),
+                        issued_by: operation_id.clone(),
+                        index,
+                        timestamp: chrono::Utc::now(),
+                    });
+                return Err(CryptoError::RevokedShare {
+                    operation_id,
+                    index,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Tag subsequent audit events with `context`.
+    fn enter(&mut self, context: &OperationContext) {
+        self.error_handler
+            .audit_logger
+            .set_context(Some(context.clone()));
+    }
+
+    /// Log `event` tagged with `context`, for front ends such as [`crate::service`].
+    #[cfg(feature = "channel-service")]
+    pub(crate) fn audit(&mut self, context: &OperationContext, event: SecurityEvent) {
+        self.enter(context);
+        self.error_handler.audit_logger.log_event(event);
+    }
+
+    /// Record a performance sample tagged with `context`, if monitoring is enabled.
+    fn record_metric(
+        &mut self,
+        context: &OperationContext,
+        operation_type: &str,
+        start_time: Stopwatch,
+        input_size: usize,
+        algorithm_used: &str,
+    ) {
+        if self.config.performance_monitoring {
+            let metrics = lagrange_fft::PerformanceMetrics {
+                operation_type: operation_type.to_string(),
+                duration_ns: start_time.elapsed_ns(),
+                input_size,
+                algorithm_used: algorithm_used.to_string(),
+                context: Some(context.clone()),
+                counts: start_time.counts(),
+            };
+            self.metrics.record(metrics);
+        }
+    }
+
+    /// Record the transcript of a completed ceremony, stamping it if an authority is configured.
+    ///
+    /// A failed stamp does not undo the ceremony: the transcript is kept unstamped and the failure
+    /// is logged, so it can be stamped again later.
+    fn record_transcript(
+        &mut self,
+        kind: CeremonyKind,
+        context: &OperationContext,
+        threshold: usize,
+        shares: &[ShareData],
+    ) {
+        let mut transcript = CeremonyTranscript::new(kind, context, threshold, shares);
+        if let Some(authority) = &self.timestamp_authority {
+            match transcript.stamp(authority.as_ref()) {
+                Ok(()) => structured_log!(
+                    Level::Info,
+                    EventCode::TranscriptStamped,
+                    "ceremony transcript stamped",
+                    key_id = context.key_id,
+                    epoch = context.epoch,
+                    operation_id = context.operation_id,
+                    authority = authority.name()
+                ),
+                Err(e) => structured_log!(
+                    Level::Warn,
+                    EventCode::TranscriptStampFailed,
+                    "ceremony transcript could not be stamped",
+                    key_id = context.key_id,
+                    epoch = context.epoch,
+                    operation_id = context.operation_id,
+                    authority = authority.name(),
+                    error = e
+                ),
+            }
+        }
+        self.transcripts.push(transcript);
+    }
+
+    /// Generate enterprise-grade key with full lifecycle management
+    ///
+    /// The key is identified by `context.key_id`. `provider` generates and keeps the master
+    /// secret, e.g. in an HSM; the returned key is the working copy for dealing. It expires
+    /// `max_key_lifetime_hours` after activation, after which dealing from and recovering it are
+    /// refused (see [`Self::register_key`]).
+    pub fn generate_enterprise_key(
+        &mut self,
+        provider: &mut dyn KeyProvider,
+        context: &OperationContext,
+    ) -> CryptoResult<Key> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("key_generation")?;
+        let key_id = context.key_id.as_str();
+
+        let mut key = self.check_entropy(provider.generate(key_id))?;
+
+        // Log security event
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::KeyGenerated {
+                key_id: key_id.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+
+        // Activate key for the configured lifetime
+        key.activate_for(self.config.max_key_lifetime_hours)?;
+        self.register_key(key_id, &key);
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::KeyActivated {
+                key_id: key_id.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+
+        // Record performance metrics
+        self.record_metric(context, "key_generation", start_time, 1, provider.name());
+
+        structured_log!(
+            Level::Info,
+            EventCode::EnterpriseKeyGenerated,
+            "enterprise key generated",
+            key_id = key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            provider = provider.name()
+        );
+        Ok(key)
+    }
+
+    /// [`Key::derive_material`] for `purpose`, audited as [`SecurityEvent::KeyMaterialDerived`]
+    /// under `context`.
+    pub fn derive_key_material(
+        &mut self,
+        key: &Key,
+        purpose: &str,
+        len: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<zeroize::Zeroizing<Vec<u8>>> {
+        self.enter(context);
+        self.refuse_in_safe_mode("key_material_derivation")?;
+        let material = key.derive_material_with(&*self.hash, purpose, len)?;
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::KeyMaterialDerived {
+                key_id: context.key_id.clone(),
+                purpose: purpose.to_string(),
+                length: len,
+                timestamp: chrono::Utc::now(),
+            });
+        Ok(material)
+    }
+
+    /// Generate and distribute secret shares with enterprise features
+    pub fn create_secret_shares(
+        &mut self,
+        secret: Scalar,
+        threshold: usize,
+        num_shares: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("share_generation")?;
+        self.refuse_expired_key(context, "share_generation")?;
+        context.check_deadline("share_generation")?;
+        self.check_epoch(context)?;
+
+        // Validate parameters
+        if threshold > num_shares {
+            return Err(CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason: format!("threshold {} exceeds num_shares {}", threshold, num_shares),
+            });
+        }
+
+        if num_shares > limits::MAX_ENTERPRISE_SHARES {
+            return Err(CryptoError::ResourceExhaustion {
+                resource: "share_count".to_string(),
+            });
+        }
+
+        // Generate shares
+        let shares = self.check_entropy(
+            sharing::deal_unproven(secret, threshold, num_shares).and_then(|unproven| {
+                sharing::prove_shares_with(&*self.hash, unproven, context.deadline())
+            }),
+        )?;
+
+        // Verify all shares
+        for share in &shares {
+            context.check_deadline("share_generation")?;
+            if let Err(e) =
+                proof::verify_proof_with(&*self.hash, &share.proof, share.commitment, share.index)
+            {
+                self.error_handler
+                    .audit_logger
+                    .log_event(SecurityEvent::ShareVerificationFailed {
+                        key_id: context.key_id.clone(),
+                        index: share.index,
+                        code: e.code().to_string(),
+                        timestamp: chrono::Utc::now(),
+                    });
+                return Err(CryptoError::CryptographicOperation {
+                    operation: "share_verification".to_string(),
+                });
+            }
+        }
+        self.claim_epoch(context)?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SharesDealt {
+                key_id: context.key_id.clone(),
+                threshold,
+                num_shares,
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.registry.issue(context, &shares);
+        self.record_transcript(CeremonyKind::Dealing, context, threshold, &shares);
+
+        // Record performance metrics
+        self.record_metric(
+            context,
+            "share_generation",
+            start_time,
+            num_shares,
+            "shamir_secret_sharing",
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::SharesGenerated,
+            "shares generated",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            threshold = threshold,
+            num_shares = num_shares
+        );
+
+        Ok(shares)
+    }
+
+    /// First phase of a dealing with deferred proving: slices and commitments without proofs.
+    ///
+    /// For latency-critical dealings only; see [`sharing::generate_key_shares_deferred`] for the
+    /// risks. Logged as [`SecurityEvent::SharesDealtUnproven`]; the dealing transcript is recorded
+    /// once [`Self::publish_share_proofs`] completes it.
+    pub fn create_secret_shares_deferred(
+        &mut self,
+        secret: Scalar,
+        threshold: usize,
+        num_shares: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<(Vec<UnprovenShare>, VssCommitments)> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("share_generation_deferred")?;
+        self.refuse_expired_key(context, "share_generation_deferred")?;
+        context.check_deadline("share_generation_deferred")?;
+        self.check_epoch(context)?;
+
+        if num_shares > limits::MAX_ENTERPRISE_SHARES {
+            return Err(CryptoError::ResourceExhaustion {
+                resource: "share_count".to_string(),
+            });
+        }
+        let (shares, commitments) = self.check_entropy(sharing::generate_key_shares_deferred(
+            secret, threshold, num_shares,
+        ))?;
+        self.claim_epoch(context)?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SharesDealtUnproven {
+                key_id: context.key_id.clone(),
+                threshold,
+                num_shares,
+                timestamp: chrono::Utc::now(),
+            });
+        self.record_metric(
+            context,
+            "share_generation_deferred",
+            start_time,
+            num_shares,
+            "shamir_secret_sharing",
+        );
+        structured_log!(
+            Level::Warn,
+            EventCode::SharesDealtUnproven,
+            "shares dealt without proofs",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            threshold = threshold,
+            num_shares = num_shares
+        );
+
+        Ok((shares, commitments))
+    }
+
+    /// Second phase of a deferred dealing: the proofs of `shares`, to publish to the holders.
+    ///
+    /// Records the dealing transcript and logs [`SecurityEvent::ShareProofsPublished`].
+    pub fn publish_share_proofs(
+        &mut self,
+        shares: &[UnprovenShare],
+        threshold: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<sharing::ShareProof>> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("share_proof_generation")?;
+        context.check_deadline("share_proof_generation")?;
+
+        let proofs = self.check_entropy(sharing::generate_share_proofs_within(
+            &*self.hash,
+            shares,
+            context.deadline(),
+        ))?;
+        let proven = shares
+            .iter()
+            .cloned()
+            .zip(&proofs)
+            .map(|(share, proof)| share.attach_proof(proof))
+            .collect::<CryptoResult<Vec<_>>>()?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::ShareProofsPublished {
+                key_id: context.key_id.clone(),
+                num_shares: proofs.len(),
+                timestamp: chrono::Utc::now(),
+            });
+        self.registry.issue(context, &proven);
+        self.record_transcript(CeremonyKind::Dealing, context, threshold, &proven);
+        self.record_metric(
+            context,
+            "share_proof_generation",
+            start_time,
+            proofs.len(),
+            "schnorr_pedersen_proof",
+        );
+        structured_log!(
+            Level::Info,
+            EventCode::ShareProofsPublished,
+            "share proofs published",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            num_shares = proofs.len()
+        );
+
+        Ok(proofs)
+    }
+
+    /// Check the proofs of `shares` and that none was revoked, without using them
+    ///
+    /// Each slice is audited as for [`Self::recover_secret_enterprise`]; returns the error of the
+    /// first slice that fails.
+    pub fn verify_shares(
+        &mut self,
+        shares: &[ShareData],
+        context: &OperationContext,
+    ) -> CryptoResult<()> {
+        self.enter(context);
+        context.check_deadline("share_validation")?;
+        self.validate_presented_shares(shares, context)
+    }
+
+    /// Recover secret with enterprise monitoring and validation
+    ///
+    /// Returns `CryptoError::RevokedShare`, audited as [`SecurityEvent::RevokedShareRejected`], if
+    /// any of `shares` was revoked with [`Self::revoke_share`].
+    pub fn recover_secret_enterprise(
+        &mut self,
+        shares: &[ShareData],
+        context: &OperationContext,
+    ) -> CryptoResult<Scalar> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("secret_recovery")?;
+        self.refuse_expired_key(context, "secret_recovery")?;
+        context.check_deadline("secret_recovery")?;
+
+        self.validate_presented_shares(shares, context)?;
+
+        // Recover secret; the constant-time path at maximum security
+        let (recover, algorithm): (fn(&[ShareData]) -> _, _) = match self.config.security_level {
+            SecurityLevel::Maximum => (lagrange_fft::recover_secret_ct, "lagrange_ct"),
+            _ => (lagrange_fft::recover_secret_fft, "lagrange_fft"),
+        };
+        let secret = recover(shares).map_err(|e| CryptoError::CryptographicOperation {
+            operation: format!("secret_recovery: {}", e),
+        })?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SecretRecovered {
+                key_id: context.key_id.clone(),
+                shares_used: shares.len(),
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.record_transcript(CeremonyKind::Recovery, context, shares.len(), shares);
+
+        // Record performance metrics
+        self.record_metric(
+            context,
+            "secret_recovery",
+            start_time,
+            shares.len(),
+            algorithm,
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::SecretRecovered,
+            "secret recovered",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            shares_used = shares.len()
+        );
+
+        Ok(secret)
+    }
+
+    /// Verify a slice arriving during the recovery ceremony `collection` and answer its custodian
+    ///
+    /// Revoked slices are rejected with `ZKT-E012` and audited as
+    /// [`SecurityEvent::RevokedShareRejected`]; see [`RecoveryCollection::submit`] for the other
+    /// outcomes.
+    pub fn submit_share(
+        &mut self,
+        collection: &mut RecoveryCollection,
+        custodian_id: &str,
+        share: ShareData,
+    ) -> ShareFeedback {
+        let context = collection.context().clone();
+        self.enter(&context);
+        let feedback = match self.registry.revoked_entry(&share) {
+            Some(entry) => {
+                let error = CryptoError::RevokedShare {
+                    operation_id: entry.operation_id.clone(),
+                    index: entry.index,
+                };
+                self.error_handler
+                    .audit_logger
+                    .log_event(SecurityEvent::RevokedShareRejected {
+                        key_id: context.key_id.clone(),
+                        issued_by: entry.operation_id.clone(),
+                        index: entry.index,
+                        timestamp: chrono::Utc::now(),
+                    });
+                let feedback = ShareFeedback::new(
+                    custodian_id,
+                    share.index,
+                    CollectionStatus::Rejected {
+                        code: error.code().to_string(),
+                        class: FailureClass::Malice,
+                        reason: error.to_string(),
+                    },
+                );
+                collection.record(feedback).clone()
+            }
+            None => collection.submit(custodian_id, share).clone(),
+        };
+
+        let (level, code, outcome) = match &feedback.status {
+            CollectionStatus::Accepted => (Level::Info, EventCode::ShareCollected, "accepted"),
+            CollectionStatus::Rejected { .. } => {
+                (Level::Warn, EventCode::ShareCollectionRejected, "rejected")
+            }
+            CollectionStatus::Superseded { .. } => (
+                Level::Warn,
+                EventCode::ShareCollectionRejected,
+                "superseded",
+            ),
+        };
+        structured_log!(
+            level,
+            code,
+            "share submitted for recovery",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            custodian_id = custodian_id,
+            index = feedback.index,
+            outcome = outcome,
+            missing = collection.missing()
+        );
+        feedback
+    }
+
+    /// Recover the secret from the slices accepted by `collection`
+    ///
+    /// Returns `CryptoError::Validation` if fewer than the threshold were accepted; otherwise as
+    /// [`Self::recover_secret_enterprise`].
+    pub fn finish_recovery(&mut self, collection: &RecoveryCollection) -> CryptoResult<Scalar> {
+        if !collection.is_complete() {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: format!(
+                    "{} more slices needed to reach threshold {}",
+                    collection.missing(),
+                    collection.threshold()
+                ),
+            });
+        }
+        self.recover_secret_enterprise(&collection.shares(), collection.context())
+    }
+
+    /// Proactively refresh shares, moving the key to `context.epoch`
+    ///
+    /// Callers derive the context with [`Self::begin_epoch`] or [`OperationContext::next_epoch`];
+    /// an epoch the key already reached is rejected with `CryptoError::Validation`.
+    pub fn refresh_secret_shares(
+        &mut self,
+        shares: &[ShareData],
+        threshold: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("share_refresh")?;
+        context.check_deadline("share_refresh")?;
+        self.check_epoch(context)?;
+        self.validate_presented_shares(shares, context)?;
+
+        let refreshed =
+            update_shares(shares, threshold).and_then(|s| self.prove_with_hash(s, context));
+        let refreshed = self.check_entropy(refreshed)?;
+        self.claim_epoch(context)?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::SharesRefreshed {
+                key_id: context.key_id.clone(),
+                threshold,
+                num_shares: refreshed.len(),
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.registry.issue(context, &refreshed);
+        self.record_transcript(CeremonyKind::Refresh, context, threshold, &refreshed);
+
+        // Record performance metrics
+        self.record_metric(
+            context,
+            "share_refresh",
+            start_time,
+            shares.len(),
+            "proactive_secret_sharing",
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::SharesRefreshed,
+            "shares refreshed",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            threshold = threshold,
+            num_shares = refreshed.len()
+        );
+
+        Ok(refreshed)
+    }
+
+    /// Reissue the key under `new_threshold` as `num_shares` slices, moving it to `context.epoch`
+    ///
+    /// `shares` are validated as for [`Self::recover_secret_enterprise`]; the new slices are
+    /// registered and their transcript is recorded as a refresh. Logged as
+    /// [`SecurityEvent::ThresholdAdjusted`]. Returns `CryptoError::Validation` for a threshold
+    /// outside `1..=num_shares` or too few slices.
+    pub fn adjust_threshold(
+        &mut self,
+        shares: &[ShareData],
+        threshold: usize,
+        new_threshold: usize,
+        num_shares: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("threshold_adjustment")?;
+        context.check_deadline("threshold_adjustment")?;
+        self.check_epoch(context)?;
+
+        if new_threshold == 0 || new_threshold > num_shares {
+            return Err(CryptoError::Validation {
+                field: "new_threshold".to_string(),
+                reason: format!("new threshold {} with {} slices", new_threshold, num_shares),
+            });
+        }
+        self.validate_presented_shares(shares, context)?;
+        let adjusted = sharing::adjust_threshold(shares, threshold, new_threshold, num_shares)
+            .map_err(|reason| CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason,
+            })?;
+        let adjusted = self.prove_with_hash(adjusted, context);
+        let adjusted = self.check_entropy(adjusted)?;
+        self.claim_epoch(context)?;
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::ThresholdAdjusted {
+                key_id: context.key_id.clone(),
+                old_threshold: threshold,
+                new_threshold,
+                num_shares,
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.registry.issue(context, &adjusted);
+        self.record_transcript(CeremonyKind::Refresh, context, new_threshold, &adjusted);
+
+        self.record_metric(
+            context,
+            "threshold_adjustment",
+            start_time,
+            shares.len(),
+            "distributed_reslicing",
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::ThresholdAdjusted,
+            "threshold adjusted",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            old_threshold = threshold,
+            new_threshold = new_threshold,
+            num_shares = num_shares
+        );
+
+        Ok(adjusted)
+    }
+
+    /// Rotate the sharing of `context.key_id` into `context.epoch`
+    ///
+    /// `shares` are validated as for [`Self::recover_secret_enterprise`] and refreshed as by
+    /// [`Self::refresh_secret_shares`], which re-issues every slice with a new commitment and
+    /// proof. The sharing they belong to is then retired: every slice issued by the operations
+    /// that issued `shares` is revoked in the registry, so a slice stolen before the rotation is
+    /// refused afterwards. Logged as [`SecurityEvent::KeyRotated`]; usually driven by a
+    /// [`KeyRotationManager`](key_lifecycle::KeyRotationManager).
+    pub fn rotate_key_shares(
+        &mut self,
+        shares: &[ShareData],
+        threshold: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<Vec<ShareData>> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("key_rotation")?;
+        context.check_deadline("key_rotation")?;
+
+        // Operations of the sharing being replaced, before the refresh registers its own.
+        let mut retired_operations: Vec<String> = Vec::new();
+        for entry in self.registry.entries() {
+            let presented = shares
+                .iter()
+                .any(|s| s.index == entry.index && s.commitment == entry.commitment);
+            if presented && !retired_operations.contains(&entry.operation_id) {
+                retired_operations.push(entry.operation_id.clone());
+            }
+        }
+
+        let rotated = self.refresh_secret_shares(shares, threshold, context)?;
+        let retired_shares = retired_operations
+            .iter()
+            .filter(|operation_id| **operation_id != context.operation_id)
+            .map(|operation_id| self.registry.revoke_operation(operation_id, "rotated"))
+            .sum();
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::KeyRotated {
+                key_id: context.key_id.clone(),
+                epoch: context.epoch,
+                retired_shares,
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.record_metric(
+            context,
+            "key_rotation",
+            start_time,
+            shares.len(),
+            "proactive_secret_sharing",
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::KeyRotated,
+            "key rotated",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            retired_shares = retired_shares
+        );
+
+        Ok(rotated)
+    }
+
+    /// Remove participant `removed_index` from the sharing of `context.key_id`, moving the key to
+    /// `context.epoch`
+    ///
+    /// `shares` are the slices of the remaining participants: at least the threshold of
+    /// `commitments`, none at `removed_index`, validated as for
+    /// [`Self::recover_secret_enterprise`] and each matching the commitment `commitments` predict
+    /// for it. They are re-shared among themselves with [`sharing::refresh_shares`], so the
+    /// removed slice no longer combines with theirs, and the returned [`RefreshRecord`] shows
+    /// anyone holding `commitments` that the secret is unchanged (`C_0` is kept). The previous
+    /// sharing is then retired as by [`Self::rotate_key_shares`], the removed slice with reason
+    /// `disenrolled`. Logged as [`SecurityEvent::ParticipantDisenrolled`].
+    ///
+    /// Returns `CryptoError::Validation` for a zero `removed_index`, a presented slice at it or
+    /// fewer slices than the threshold, and `CryptoError::SecurityViolation` for a slice
+    /// `commitments` do not predict.
+    pub fn disenroll_participant(
+        &mut self,
+        shares: &[ShareData],
+        commitments: &VssCommitments,
+        removed_index: usize,
+        context: &OperationContext,
+    ) -> CryptoResult<(Vec<ShareData>, RefreshRecord)> {
+        let start_time = Stopwatch::start();
+        self.enter(context);
+        self.refuse_in_safe_mode("disenrollment")?;
+        context.check_deadline("disenrollment")?;
+        self.check_epoch(context)?;
+
+        if removed_index == 0 || shares.iter().any(|s| s.index == removed_index) {
+            return Err(CryptoError::Validation {
+                field: "removed_index".to_string(),
+                reason: format!(
+                    "index {} is zero or among the remaining slices",
+                    removed_index
+                ),
+            });
+        }
+        if shares.len() < commitments.threshold() {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: format!(
+                    "{} remaining slices for threshold {}",
+                    shares.len(),
+                    commitments.threshold()
+                ),
+            });
+        }
+        self.validate_presented_shares(shares, context)?;
+        if let Some(share) = shares
+            .iter()
+            .find(|s| s.commitment != commitments.expected_commitment(s.index))
+        {
+            return Err(VerifyError::CommitmentMismatch { index: share.index }.into());
+        }
+
+        let mut retired_operations: Vec<String> = Vec::new();
+        for entry in self.registry.entries() {
+            let presented = shares
+                .iter()
+                .any(|s| s.index == entry.index && s.commitment == entry.commitment);
+            if presented && !retired_operations.contains(&entry.operation_id) {
+                retired_operations.push(entry.operation_id.clone());
+            }
+        }
+
+        let refreshed = sharing::refresh_shares(shares, commitments)
+            .and_then(|(shares, record)| Ok((self.prove_with_hash(shares, context)?, record)));
+        let (refreshed, record) = self.check_entropy(refreshed)?;
+        self.claim_epoch(context)?;
+        self.registry.issue(context, &refreshed);
+        self.record_transcript(
+            CeremonyKind::Refresh,
+            context,
+            commitments.threshold(),
+            &refreshed,
+        );
+
+        let mut retired_shares = 0;
+        for operation_id in &retired_operations {
+            if let Some(entry) = self.registry.get(operation_id, removed_index) {
+                if !entry.is_revoked() {
+                    self.registry
+                        .revoke(operation_id, removed_index, "disenrolled")?;
+                    retired_shares += 1;
+                }
+            }
+            retired_shares += self
+                .registry
+                .revoke_operation(operation_id, "disenrollment");
+        }
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::ParticipantDisenrolled {
+                key_id: context.key_id.clone(),
+                index: removed_index,
+                epoch: context.epoch,
+                num_shares: refreshed.len(),
+                retired_shares,
+                timestamp: chrono::Utc::now(),
+            });
+
+        self.record_metric(
+            context,
+            "disenrollment",
+            start_time,
+            shares.len(),
+            "proactive_secret_sharing",
+        );
+
+        structured_log!(
+            Level::Info,
+            EventCode::ParticipantDisenrolled,
+            "participant disenrolled",
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id,
+            index = removed_index,
+            num_shares = refreshed.len(),
+            retired_shares = retired_shares
+        );
+
+        Ok((refreshed, record))
+    }
+
+    /// Revoke slice `index` issued by `operation_id`, so recovery no longer accepts it
+    ///
+    /// Logged as [`SecurityEvent::ShareRevoked`] under `context`. Returns
+    /// `CryptoError::Validation` if the system did not issue the slice.
+    pub fn revoke_share(
+        &mut self,
+        operation_id: &str,
+        index: usize,
+        reason: &str,
+        context: &OperationContext,
+    ) -> CryptoResult<()> {
+        self.enter(context);
+        let entry = self.registry.revoke(operation_id, index, reason)?;
+        let key_id = entry.key_id.clone();
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::ShareRevoked {
+                key_id,
+                issued_by: operation_id.to_string(),
+                index,
+                reason: reason.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+        Ok(())
+    }
+
+    /// Slices issued by this system and their revocation status
+    pub fn share_registry(&self) -> &ShareRegistry {
+        &self.registry
+    }
+
+    /// Get performance metrics for enterprise monitoring
+    ///
+    /// Only the samples not yet exported, drained or dropped; see [`metrics`].
+    pub fn get_performance_metrics(&self) -> &[lagrange_fft::PerformanceMetrics] {
+        self.metrics.samples()
+    }
+
+    /// Limit the buffered performance samples and set when they are exported
+    ///
+    /// Samples beyond the new limit are dropped at once, oldest first. Returns
+    /// `CryptoError::Validation` for an invalid [`MetricsRetention`](metrics::MetricsRetention).
+    pub fn set_metrics_retention(
+        &mut self,
+        retention: metrics::MetricsRetention,
+    ) -> CryptoResult<()> {
+        self.metrics.set_retention(retention)
+    }
+
+    /// Export performance samples to `sink` whenever the flush threshold is reached
+    pub fn set_metrics_sink(&mut self, sink: Box<dyn metrics::MetricsSink + Send + Sync>) {
+        self.metrics.set_sink(sink);
+    }
+
+    /// Export every buffered performance sample now, e.g. on shutdown, and return how many
+    ///
+    /// Returns `CryptoError::Validation` without a sink, or the sink's error, in which case the
+    /// samples stay buffered.
+    pub fn flush_metrics(&mut self) -> CryptoResult<usize> {
+        self.metrics.flush()
+    }
+
+    /// Take every buffered performance sample, oldest first, for a service shipping them itself
+    pub fn drain_metrics(&mut self) -> Vec<lagrange_fft::PerformanceMetrics> {
+        self.metrics.drain()
+    }
+
+    /// Performance samples dropped so far because the retention limit was reached
+    pub fn dropped_metrics(&self) -> u64 {
+        self.metrics.dropped()
+    }
+
+    /// Get audit events for compliance reporting
+    pub fn get_audit_events(&self) -> &[SecurityEvent] {
+        self.error_handler.audit_logger.get_events()
+    }
+
+    /// Get audit events with their operation contexts
+    pub fn get_audit_records(&self) -> &[AuditRecord] {
+        self.error_handler.audit_logger.get_records()
+    }
+
+    /// Hash of the latest persisted audit entry, to anchor the audit chain elsewhere
+    pub fn audit_chain_head(&self) -> [u8; 32] {
+        self.error_handler.audit_logger.chain_head()
+    }
+
+    /// Check the audit chain in memory against its recorded hashes
+    pub fn verify_audit_chain(&self) -> CryptoResult<[u8; 32]> {
+        self.error_handler.audit_logger.verify_chain()
+    }
+
+    /// Export the audit chain for independent verification by third-party auditors
+    pub fn export_audit_chain(&self) -> audit::AuditExport {
+        self.error_handler.audit_logger.export()
+    }
+
+    /// Audit history of one key across dealings, refreshes and recoveries
+    pub fn key_history(&self, key_id: &str) -> Vec<&AuditRecord> {
+        self.error_handler.audit_logger.history(key_id)
+    }
+
+    /// Transcripts of the dealings, refreshes and recoveries of one key, oldest first
+    pub fn transcripts(&self, key_id: &str) -> Vec<&CeremonyTranscript> {
+        self.transcripts
+            .iter()
+            .filter(|t| t.context.key_id == key_id)
+            .collect()
+    }
+
+    /// Archive the transcripts and the audit history of one key with `codec`
+    ///
+    /// Transcripts come first, oldest first, followed by the audit records in logging order.
+    pub fn archive_history(&self, key_id: &str, codec: &dyn ArchiveCodec) -> CryptoResult<Vec<u8>> {
+        let mut writer = ArchiveWriter::new(codec);
+        for transcript in self.transcripts(key_id) {
+            writer.add_transcript(transcript)?;
+        }
+        for record in self.key_history(key_id) {
+            writer.add_audit_record(record)?;
+        }
+        writer.finish()
+    }
+
+    /// Record a custodian attestation for `key_id` after checking it against `commitments` and
+    /// the `challenge` nonce sent with the health-check request
+    ///
+    /// Replaces the previous attestation of the same custodian. Returns
+    /// `CryptoError::SecurityViolation`, audited as unauthorized access, if the attestation does
+    /// not verify.
+    pub fn record_attestation(
+        &mut self,
+        custodian_id: &str,
+        key_id: &str,
+        attestation: &ShareAttestation,
+        commitments: &VssCommitments,
+        challenge: &[u8],
+    ) -> CryptoResult<()> {
+        if !attestation.verify(commitments, challenge) {
+            let error = CryptoError::SecurityViolation {
+                details: format!(
+                    "attestation of custodian {} for slice {} does not verify",
+                    custodian_id, attestation.index
+                ),
+            };
+            self.error_handler.handle_error(&error);
+            return Err(error);
+        }
+        self.store_attestation(status::CustodianStatus {
+            custodian_id: custodian_id.to_string(),
+            key_id: key_id.to_string(),
+            index: attestation.index,
+            commitment_root: attestation.commitment_root.clone(),
+            checked_at: attestation.checked_at,
+        });
+        Ok(())
+    }
+
+    fn store_attestation(&mut self, status: status::CustodianStatus) {
+        match self
+            .attestations
+            .iter_mut()
+            .find(|a| a.custodian_id == status.custodian_id)
+        {
+            Some(existing) => *existing = status,
+            None => self.attestations.push(status),
+        }
+    }
+
+    /// Public state of `key_id` for offsite backup: its slice commitments and revocations,
+    /// custodian attestations, transcripts, epoch and policy; see [`public_state`]
+    ///
+    /// Returns `CryptoError::Validation` if the system never dealt the key.
+    pub fn export_public_state(&self, key_id: &str) -> CryptoResult<PublicState> {
+        let transcripts: Vec<CeremonyTranscript> =
+            self.transcripts(key_id).into_iter().cloned().collect();
+        let never_dealt = || CryptoError::Validation {
+            field: "key_id".to_string(),
+            reason: format!("key {} was never dealt", key_id),
+        };
+        let threshold = transcripts
+            .iter()
+            .rev()
+            .find(|t| t.kind != CeremonyKind::Recovery)
+            .map(|t| t.threshold)
+            .ok_or_else(never_dealt)?;
+        let epoch = self
+            .counters
+            .get(&counters::epoch_counter(key_id))?
+            .ok_or_else(never_dealt)?;
+        Ok(PublicState {
+            version: public_state::PUBLIC_STATE_VERSION,
+            key_id: key_id.to_string(),
+            epoch,
+            operation_sequence: self.counters.get(counters::OPERATION_SEQUENCE)?,
+            policy: KeyPolicy {
+                threshold,
+                security_level: self.config.security_level,
+                compliance_mode: format!("{:?}", self.config.compliance_mode),
+                max_key_lifetime_hours: self.config.max_key_lifetime_hours,
+                expires_at: self.key_expiry(key_id),
+            },
+            shares: self
+                .registry
+                .entries()
+                .iter()
+                .filter(|e| e.key_id == key_id)
+                .cloned()
+                .collect(),
+            custodians: self
+                .attestations
+                .iter()
+                .filter(|a| a.key_id == key_id)
+                .cloned()
+                .collect(),
+            transcripts,
+            exported_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Load the public state of a key exported by another system, e.g. on a fresh system in a
+    /// disaster recovery drill; see [`public_state`]
+    ///
+    /// The system then recovers the key from custodian slices, refuses its revoked slices and
+    /// continues its epoch and operation sequence. Logged as
+    /// [`SecurityEvent::PublicStateImported`]. Returns the error of [`PublicState::verify`],
+    /// `CryptoError::Configuration` if the key was operated at another security level, and
+    /// `CryptoError::Validation` if the key or one of its operations is already known here.
+    pub fn import_public_state(&mut self, state: &PublicState) -> CryptoResult<()> {
+        state.verify()?;
+        if state.policy.security_level != self.config.security_level {
+            return Err(CryptoError::Configuration {
+                parameter: "security_level".to_string(),
+                issue: format!(
+                    "key {} is operated at {:?}, this system runs at {:?}",
+                    state.key_id, state.policy.security_level, self.config.security_level
+                ),
+            });
+        }
+        let epoch_counter = counters::epoch_counter(&state.key_id);
+        if self.counters.get(&epoch_counter)?.is_some()
+            || self
+                .registry
+                .entries()
+                .iter()
+                .any(|e| e.key_id == state.key_id)
+            || !self.transcripts(&state.key_id).is_empty()
+        {
+            return Err(CryptoError::Validation {
+                field: "key_id".to_string(),
+                reason: format!("key {} is already known to this system", state.key_id),
+            });
+        }
+        let reused = state
+            .shares
+            .iter()
+            .map(|e| &e.operation_id)
+            .chain(state.transcripts.iter().map(|t| &t.context.operation_id))
+            .find(|operation_id| {
+                self.registry
+                    .entries()
+                    .iter()
+                    .any(|e| e.operation_id == **operation_id)
+                    || self
+                        .transcripts
+                        .iter()
+                        .any(|t| t.context.operation_id == **operation_id)
+            });
+        if let Some(operation_id) = reused {
+            return Err(CryptoError::Validation {
+                field: "operation_id".to_string(),
+                reason: format!("operation {} already ran on this system", operation_id),
+            });
+        }
+
+        self.counters.advance(&epoch_counter, state.epoch)?;
+        if let Some(sequence) = state.operation_sequence {
+            let current = self.counters.get(counters::OPERATION_SEQUENCE)?;
+            if current.is_none_or(|current| current < sequence) {
+                self.counters
+                    .advance(counters::OPERATION_SEQUENCE, sequence)?;
+            }
+        }
+        self.registry.restore(&state.shares);
+        self.transcripts.extend(state.transcripts.iter().cloned());
+        for status in &state.custodians {
+            self.store_attestation(status.clone());
+        }
+        if let Some(expiry) = state.policy.expires_at {
+            self.key_expiries.push((state.key_id.clone(), expiry));
+        }
+
+        self.error_handler
+            .audit_logger
+            .log_event(SecurityEvent::PublicStateImported {
+                key_id: state.key_id.clone(),
+                epoch: state.epoch,
+                shares: state.shares.len(),
+                timestamp: chrono::Utc::now(),
+            });
+        Ok(())
+    }
+
+    /// Prove that the recovery `context` used at least the threshold of distinct live slices of
+    /// one committee, without naming them; see [`policy_proof`]
+    ///
+    /// `shares` are the slices the recovery used. The committee is every live slice of the
+    /// dealing or refresh that issued them, with the custodian of its last attestation, and the
+    /// threshold that of the dealing or refresh. Returns `CryptoError::Validation` if the recovery
+    /// was not recorded or `shares` are not live slices of one committee.
+    pub fn prove_threshold_compliance(
+        &mut self,
+        shares: &[ShareData],
+        context: &OperationContext,
+    ) -> CryptoResult<PolicyComplianceProof> {
+        self.enter(context);
+        self.refuse_in_safe_mode("threshold_compliance_proof")?;
+        let invalid = |field: &str, reason: String| CryptoError::Validation {
+            field: field.to_string(),
+            reason,
+        };
+        let recorded = self.transcripts(&context.key_id).into_iter().any(|t| {
+            t.kind == CeremonyKind::Recovery && t.context.operation_id == context.operation_id
+        });
+        if !recorded {
+            return Err(invalid(
+                "context",
+                format!(
+                    "no recovery {} of key {} was recorded",
+                    context.operation_id, context.key_id
+                ),
+            ));
+        }
+        let issued_by = shares
+            .first()
+            .and_then(|first| {
+                self.registry.entries().iter().find(|e| {
+                    e.key_id == context.key_id
+                        && !e.is_revoked()
+                        && e.index == first.index
+                        && e.commitment == first.commitment
+                })
+            })
+            .map(|entry| entry.operation_id.clone())
+            .ok_or_else(|| invalid("shares", "not a live slice of the key".to_string()))?;
+        let mut proof = PolicyComplianceProof::new(
+            &context.key_id,
+            &context.operation_id,
+            &issued_by,
+            self.committee_threshold(&context.key_id, &issued_by)?,
+            self.committee(&context.key_id, &issued_by, true),
+        )?;
+        for share in shares {
+            proof.contribute(share)?;
+        }
+        structured_log!(
+            Level::Info,
+            EventCode::ThresholdComplianceProven,
+            "threshold compliance proven",
+            key_id = context.key_id,
+            operation_id = context.operation_id,
+            committee = issued_by,
+            contributions = shares.len()
+        );
+        Ok(proof)
+    }
+
+    /// Check `proof` and that its committee and threshold are the registered ones
+    ///
+    /// The committee must list slices the operation issued, including ones revoked since the
+    /// recovery. Returns the errors of [`PolicyComplianceProof::verify`], and
+    /// `CryptoError::Validation` if the committee, threshold or recovery is not the registered
+    /// one.
+    pub fn verify_threshold_compliance(&self, proof: &PolicyComplianceProof) -> CryptoResult<()> {
+        proof.verify()?;
+        let mismatch = |what: &str| CryptoError::Validation {
+            field: what.to_string(),
+            reason: format!(
+                "{} of the proof is not the registered one of operation {}",
+                what, proof.committee_operation_id
+            ),
+        };
+        let registered = self.committee(&proof.key_id, &proof.committee_operation_id, false);
+        if proof.committee.iter().any(|member| {
+            !registered
+                .iter()
+                .any(|r| r.index == member.index && r.commitment == member.commitment)
+        }) {
+            return Err(mismatch("committee"));
+        }
+        if self.committee_threshold(&proof.key_id, &proof.committee_operation_id)?
+            != proof.threshold
+        {
+            return Err(mismatch("threshold"));
+        }
+        let recorded = self.transcripts(&proof.key_id).into_iter().any(|t| {
+            t.kind == CeremonyKind::Recovery
+                && t.context.operation_id == proof.recovery_operation_id
+        });
+        if !recorded {
+            return Err(CryptoError::Validation {
+                field: "recovery_operation_id".to_string(),
+                reason: format!("no recovery {} was recorded", proof.recovery_operation_id),
+            });
+        }
+        Ok(())
+    }
+
+    /// Slices of `key_id` issued by `operation_id`, only the live ones if `live`.
+    fn committee(&self, key_id: &str, operation_id: &str, live: bool) -> Vec<CommitteeMember> {
+        self.registry
+            .entries()
+            .iter()
+            .filter(|e| e.key_id == key_id && e.operation_id == operation_id)
+            .filter(|e| !(live && e.is_revoked()))
+            .map(|e| CommitteeMember {
+                index: e.index,
+                custodian_id: self
+                    .attestations
+                    .iter()
+                    .find(|a| a.key_id == key_id && a.index == e.index)
+                    .map(|a| a.custodian_id.clone()),
+                commitment: e.commitment,
+            })
+            .collect()
+    }
+
+    /// Threshold of the dealing or refresh `operation_id` of `key_id`.
+    fn committee_threshold(&self, key_id: &str, operation_id: &str) -> CryptoResult<usize> {
+        self.transcripts(key_id)
+            .into_iter()
+            .find(|t| t.kind != CeremonyKind::Recovery && t.context.operation_id == operation_id)
+            .map(|t| t.threshold)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "committee_operation_id".to_string(),
+                reason: format!(
+                    "operation {} dealt no slices of key {}",
+                    operation_id, key_id
+                ),
+            })
+    }
+
+    /// Read-only snapshot for operator dashboards
+    ///
+    /// Key states follow the lifecycle events in the audit log.
+    pub fn status(&self) -> SystemStatus {
+        let mut keys: Vec<status::KeyStatus> = Vec::new();
+        fn entry<'a>(
+            keys: &'a mut Vec<status::KeyStatus>,
+            key_id: &str,
+        ) -> &'a mut status::KeyStatus {
+            let position = match keys.iter().position(|k| k.key_id == key_id) {
+                Some(position) => position,
+                None => {
+                    keys.push(status::KeyStatus {
+                        key_id: key_id.to_string(),
+                        state: None,
+                        epoch: 0,
+                        threshold: 0,
+                        shares_outstanding: 0,
+                    });
+                    keys.len() - 1
+                }
+            };
+            &mut keys[position]
+        }
+
+        for record in self.get_audit_records() {
+            let (key_id, state) = match &record.event {
+                SecurityEvent::KeyGenerated { key_id, .. } => (key_id, Some(KeyState::Generated)),
+                SecurityEvent::KeyActivated { key_id, .. } => (key_id, Some(KeyState::Active)),
+                SecurityEvent::KeyRetired { key_id, .. } => (key_id, Some(KeyState::Retired)),
+                SecurityEvent::KeyDestroyed { key_id, .. } => (key_id, Some(KeyState::Destroyed)),
+                SecurityEvent::SharesDealt { key_id, .. }
+                | SecurityEvent::SharesRefreshed { key_id, .. }
+                | SecurityEvent::SecretRecovered { key_id, .. }
+                | SecurityEvent::SharesDealtUnproven { key_id, .. }
+                | SecurityEvent::ShareProofsPublished { key_id, .. }
+                | SecurityEvent::ShareRevoked { key_id, .. }
+                | SecurityEvent::RevokedShareRejected { key_id, .. }
+                | SecurityEvent::ShareVerified { key_id, .. }
+                | SecurityEvent::ShareVerificationFailed { key_id, .. }
+                | SecurityEvent::ThresholdAdjusted { key_id, .. }
+                | SecurityEvent::KeyRotated { key_id, .. }
+                | SecurityEvent::ParticipantDisenrolled { key_id, .. }
+                | SecurityEvent::PublicStateImported { key_id, .. }
+                | SecurityEvent::KeyExpired { key_id, .. }
+                | SecurityEvent::KeyMaterialDerived { key_id, .. } => (key_id, None),
+                SecurityEvent::UnauthorizedAccess { .. }
+                | SecurityEvent::PolicyViolation { .. }
+                | SecurityEvent::SafeModeEntered { .. }
+                | SecurityEvent::SafeModeExited { .. }
+                | SecurityEvent::ServiceRequestHandled { .. } => continue,
+            };
+            let key = entry(&mut keys, key_id);
+            if state.is_some() {
+                key.state = state;
+            }
+        }
+        for transcript in &self.transcripts {
+            if transcript.kind == CeremonyKind::Recovery {
+                continue;
+            }
+            let key = entry(&mut keys, &transcript.context.key_id);
+            key.epoch = transcript.context.epoch;
+            key.threshold = transcript.threshold;
+            key.shares_outstanding = transcript.indices.len();
+        }
+
+        let mut key_counts = status::KeyStateCounts::default();
+        for state in keys.iter().filter_map(|k| k.state) {
+            key_counts.add(state);
+        }
+
+        let recent_high_severity_events = self
+            .get_audit_records()
+            .iter()
+            .rev()
+            .filter(|r| r.event.severity() == Severity::High)
+            .take(status::RECENT_EVENT_LIMIT)
+            .cloned()
+            .collect();
+
+        let mut performance: Vec<(String, Vec<u64>)> = Vec::new();
+        for metric in self.metrics.samples() {
+            match performance
+                .iter_mut()
+                .find(|(op, _)| *op == metric.operation_type)
+            {
+                Some((_, durations)) => durations.push(metric.duration_ns),
+                None => performance.push((metric.operation_type.clone(), vec![metric.duration_ns])),
+            }
+        }
+        let performance = performance
+            .into_iter()
+            .map(|(operation_type, durations)| status::PerformanceSummary {
+                operation_type,
+                samples: durations.len(),
+                mean_duration_ns: durations.iter().sum::<u64>() / durations.len() as u64,
+                max_duration_ns: durations.iter().copied().max().unwrap_or(0),
+            })
+            .collect();
+
+        SystemStatus {
+            generated_at: chrono::Utc::now(),
+            key_counts,
+            keys,
+            custodians: self.attestations.clone(),
+            recent_high_severity_events,
+            safe_mode: self.safe_mode.clone(),
+            performance,
+        }
+    }
+
+    /// Performance metrics recorded for one key
+    pub fn key_metrics(&self, key_id: &str) -> Vec<&lagrange_fft::PerformanceMetrics> {
+        self.metrics
+            .samples()
+            .iter()
+            .filter(|m| m.context.as_ref().is_some_and(|c| c.key_id == key_id))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod enterprise_tests {
+    use super::*;
+    use crate::hazmat::recover_secret_fft;
+    use crate::utils::random_scalar;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_enterprise_config() {
+        let config = EnterpriseConfig::default();
+        assert!(config.audit_enabled);
+        assert!(config.performance_monitoring);
+        assert!(matches!(config.compliance_mode, ComplianceMode::Standard));
+    }
+
+    #[test]
+    fn test_enterprise_crypto_system() {
+        let config = EnterpriseConfig::default();
+        let mut system = EnterpriseCryptoSystem::new(config);
+
+        // Test security validation
+        assert!(system.validate_security().is_ok());
+
+        // Test key generation
+        let key = system
+            .generate_enterprise_key(
+                &mut SoftwareKeyProvider::new(),
+                &OperationContext::new("test-key", 0, "test-keygen"),
+            )
+            .unwrap();
+        assert!(matches!(key.state, KeyState::Active));
+    }
+
+    #[test]
+    fn test_share_operations() {
+        let config = EnterpriseConfig::default();
+        let mut system = EnterpriseCryptoSystem::new(config);
+
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let context = OperationContext::new("test-key", 0, "test-op");
+        let shares = system.create_secret_shares(secret, 3, 5, &context).unwrap();
+
+        assert_eq!(shares.len(), 5);
+
+        let recovered = system
+            .recover_secret_enterprise(&shares[..3], &context.for_operation("test-recovery"))
+            .unwrap();
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn test_maximum_security_recovers_in_constant_time() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig {
+            security_level: SecurityLevel::Maximum,
+            ..EnterpriseConfig::default()
+        });
+        let context = OperationContext::new("ct-key", 0, "deal");
+        let shares = system
+            .create_secret_shares(Scalar::from(31u64), 3, 5, &context)
+            .unwrap();
+
+        let recovered = system
+            .recover_secret_enterprise(&shares[2..], &context.for_operation("recover"))
+            .unwrap();
+        assert_eq!(recovered, Scalar::from(31u64));
+        let metric = system.get_performance_metrics().last().unwrap();
+        assert_eq!(metric.operation_type, "secret_recovery");
+        assert_eq!(metric.algorithm_used, "lagrange_ct");
+        assert_eq!(metric.counts.is_some(), cfg!(feature = "op-counters"));
+        if let Some(counts) = metric.counts {
+            assert!(counts.scalar_muls >= 3 * 3 && counts.inversions >= 1);
+        }
+    }
+
+    #[test]
+    fn test_audit_sinks_from_config() {
+        let ring = audit::RingBufferAuditSink::new(16).unwrap();
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig {
+            audit_sinks: vec![audit::AuditSinkConfig::RingBuffer(ring.clone())],
+            ..EnterpriseConfig::default()
+        });
+        let context = system.begin_epoch("audited-key", "deal").unwrap();
+        system
+            .create_secret_shares(Scalar::from(5u64), 2, 3, &context)
+            .unwrap();
+        let entries = ring.entries();
+        assert_eq!(entries.len(), system.get_audit_records().len());
+        assert_eq!(
+            audit::verify_chain(&entries).unwrap(),
+            system.audit_chain_head()
+        );
+
+        let silent = audit::RingBufferAuditSink::new(16).unwrap();
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig {
+            audit_enabled: false,
+            audit_sinks: vec![audit::AuditSinkConfig::RingBuffer(silent.clone())],
+            ..EnterpriseConfig::default()
+        });
+        let context = system.begin_epoch("audited-key", "deal").unwrap();
+        system
+            .create_secret_shares(Scalar::from(5u64), 2, 3, &context)
+            .unwrap();
+        assert!(silent.entries().is_empty());
+    }
+
+    #[test]
+    fn test_metrics_are_bounded_and_exported() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = system.begin_epoch("metrics-key", "deal").unwrap();
+        let shares = system
+            .create_secret_shares(Scalar::from(5u64), 2, 3, &context)
+            .unwrap();
+        for _ in 0..4 {
+            system
+                .recover_secret_enterprise(&shares[..2], &context.for_operation("recover"))
+                .unwrap();
+        }
+        system
+            .set_metrics_retention(metrics::MetricsRetention {
+                max_samples: 3,
+                flush_threshold: 2,
+            })
+            .unwrap();
+        assert_eq!(system.get_performance_metrics().len(), 3);
+        assert_eq!(system.dropped_metrics(), 2);
+        assert!(system.flush_metrics().is_err());
+
+        let path = std::env::temp_dir().join(format!("zk-thresh-metrics-{}", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        system.set_metrics_sink(Box::new(metrics::JsonLinesMetricsSink::new(file)));
+        assert_eq!(system.flush_metrics().unwrap(), 3);
+        system
+            .recover_secret_enterprise(&shares[1..], &context.for_operation("recover"))
+            .unwrap();
+        assert_eq!(system.drain_metrics().len(), 1);
+        assert!(system.get_performance_metrics().is_empty());
+
+        let exported = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(exported.lines().count(), 3);
+        assert!(exported.contains("secret_recovery"));
+    }
+
+    #[test]
+    fn test_staged_dealing_matches_commitments() {
+        let (a, b) = (Scalar::from(3u64), Scalar::from(4u64));
+        assert_eq!(
+            utils::pedersen_commit(&a, &b),
+            curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT * a + *utils::ANOTHER_POINT * b
+        );
+
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(8u64), 5, 80).unwrap();
+        assert!(shares.iter().enumerate().all(|(k, s)| s.index == k + 1));
+        assert!(verify_share_validity_with_commitments(&shares, &commitments).is_ok());
+        assert_eq!(
+            recover_secret_fft(&shares[40..45]).unwrap(),
+            Scalar::from(8u64)
+        );
+    }
+
+    #[test]
+    fn test_deferred_dealing() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("deferred-key", 0, "deal");
+        let (shares, commitments) = system
+            .create_secret_shares_deferred(Scalar::from(21u64), 2, 3, &context)
+            .unwrap();
+        assert!(shares.iter().all(|s| s.verify(&commitments)));
+        assert!(system.transcripts("deferred-key").is_empty());
+
+        let proofs = system.publish_share_proofs(&shares, 2, &context).unwrap();
+        let proven: Vec<ShareData> = shares
+            .iter()
+            .cloned()
+            .zip(&proofs)
+            .map(|(s, p)| s.attach_proof(p).unwrap())
+            .collect();
+        assert!(verify_share_validity_with_commitments(&proven, &commitments).is_ok());
+        assert!(shares[0].clone().attach_proof(&proofs[1]).is_err());
+        assert_eq!(system.transcripts("deferred-key").len(), 1);
+
+        let mut tampered = shares[1].clone();
+        tampered.share += Scalar::ONE;
+        assert!(!tampered.verify(&commitments));
+
+        let events: Vec<_> = system
+            .get_audit_records()
+            .iter()
+            .map(|r| r.event.code())
+            .collect();
+        assert!(events.contains(&"ZKT-A010") && events.contains(&"ZKT-A011"));
+    }
+
+    #[test]
+    fn test_ceremony_transcripts_are_stamped() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("stamped-key", 0, "deal");
+        let shares = system
+            .create_secret_shares(Scalar::from(5u64), 2, 3, &context)
+            .unwrap();
+        system.set_timestamp_authority(Box::new(transcript::TestAuthority([1; 32])));
+        system
+            .recover_secret_enterprise(&shares[1..], &context.for_operation("recover"))
+            .unwrap();
+
+        let transcripts = system.transcripts("stamped-key");
+        assert_eq!(transcripts.len(), 2);
+        assert_eq!(transcripts[0].kind, CeremonyKind::Dealing);
+        assert_eq!(transcripts[0].indices, [1, 2, 3]);
+        assert!(transcripts[0].timestamp.is_none());
+        assert_eq!(transcripts[1].kind, CeremonyKind::Recovery);
+        assert_eq!(transcripts[1].indices, [2, 3]);
+        transcripts[1]
+            .verify_timestamp(&transcript::TestAuthority([1; 32]))
+            .unwrap();
+
+        let bytes = system
+            .archive_history("stamped-key", &archive::Stored)
+            .unwrap();
+        let mut archive = Archive::open(std::io::Cursor::new(bytes), &archive::Stored).unwrap();
+        assert_eq!(archive.len(), 2 + system.key_history("stamped-key").len());
+        archive
+            .transcript(1)
+            .unwrap()
+            .verify_timestamp(&transcript::TestAuthority([1; 32]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_system_status_snapshot() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("status-key", 0, "keygen");
+        let key = system
+            .generate_enterprise_key(&mut SoftwareKeyProvider::new(), &context)
+            .unwrap();
+        let (shares, commitments) = generate_key_shares_with_commitments(key.secret, 2, 3).unwrap();
+        system
+            .create_secret_shares(key.secret, 2, 4, &context.for_operation("deal"))
+            .unwrap();
+
+        let sealing_key = SealingKey::generate().unwrap();
+        let sealed = sealing::seal(&shares[0], &sealing_key).unwrap();
+        let nonce = [5u8; 16];
+        let (_, attestation) = custodian::check_share(
+            &sealed,
+            &sealing_key,
+            &commitments,
+            &commitments.root(),
+            &nonce,
+        )
+        .unwrap();
+        system
+            .record_attestation(
+                "custodian-a",
+                "status-key",
+                &attestation,
+                &commitments,
+                &nonce,
+            )
+            .unwrap();
+        let (_, other) = generate_key_shares_with_commitments(key.secret, 2, 3).unwrap();
+        assert!(system
+            .record_attestation("custodian-b", "status-key", &attestation, &other, &nonce)
+            .is_err());
+        assert!(system
+            .record_attestation(
+                "custodian-b",
+                "status-key",
+                &attestation,
+                &commitments,
+                &[6u8; 16]
+            )
+            .is_err());
+
+        let status = system.status();
+        assert_eq!(status.key_counts.active, 1);
+        assert_eq!(status.keys.len(), 1);
+        assert_eq!(status.keys[0].state, Some(KeyState::Active));
+        assert_eq!(status.keys[0].shares_outstanding, 4);
+        assert_eq!(status.custodians.len(), 1);
+        assert_eq!(status.custodians[0].custodian_id, "custodian-a");
+        // The two rejected attestations.
+        assert_eq!(status.recent_high_severity_events.len(), 2);
+        let operations: Vec<_> = status
+            .performance
+            .iter()
+            .map(|p| (p.operation_type.as_str(), p.samples))
+            .collect();
+        assert_eq!(operations, [("key_generation", 1), ("share_generation", 1)]);
+        serde_json::to_string(&status).unwrap();
+    }
+
+    #[test]
+    fn test_key_history_across_epochs() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("history-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+        let refresh = context.next_epoch("refresh");
+        let shares = system.refresh_secret_shares(&shares, 2, &refresh).unwrap();
+
+        // Operations on another key must not show up in this key's history.
+        let other = OperationContext::new("other-key", 0, "recover");
+        system
+            .recover_secret_enterprise(&shares[1..], &other)
+            .unwrap();
+        let recovered = system
+            .recover_secret_enterprise(&shares[..2], &refresh.for_operation("recover"))
+            .unwrap();
+        assert_eq!(recovered, secret);
+
+        let metrics = system.key_metrics("history-key");
+        let epochs: Vec<_> = metrics
+            .iter()
+            .map(|m| (m.operation_type.as_str(), m.context.as_ref().unwrap().epoch))
+            .collect();
+        assert_eq!(
+            epochs,
+            [
+                ("share_generation", 0),
+                ("share_refresh", 1),
+                ("secret_recovery", 1)
+            ]
+        );
+        let history: Vec<_> = system
+            .key_history("history-key")
+            .iter()
+            .map(|r| {
+                let context = r.context.as_ref().unwrap();
+                (r.event.code(), context.epoch, context.operation_id.as_str())
+            })
+            .collect();
+        assert_eq!(
+            history,
+            [
+                ("ZKT-A007", 0, "deal"),
+                ("ZKT-A017", 1, "refresh"),
+                ("ZKT-A017", 1, "refresh"),
+                ("ZKT-A017", 1, "refresh"),
+                ("ZKT-A008", 1, "refresh"),
+                ("ZKT-A017", 1, "recover"),
+                ("ZKT-A017", 1, "recover"),
+                ("ZKT-A009", 1, "recover")
+            ]
+        );
+        assert_eq!(system.key_history("other-key").len(), 3);
+    }
+
+    #[test]
+    fn test_epochs_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("zk-thresh-epochs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let open = || {
+            let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+            system.set_counter_store(Box::new(FileCounterStore::open(&dir).unwrap()));
+            system
+        };
+        let secret = Scalar::from(42u64);
+
+        let mut system = open();
+        let deal = system.begin_epoch("durable-key", "deal").unwrap();
+        assert_eq!((deal.epoch, deal.operation_id.as_str()), (0, "deal-1"));
+        let shares = system.create_secret_shares(secret, 2, 3, &deal).unwrap();
+        let refresh = system.begin_epoch("durable-key", "refresh").unwrap();
+        let shares = system.refresh_secret_shares(&shares, 2, &refresh).unwrap();
+        assert_eq!(refresh.epoch, 1);
+
+        let mut restarted = open();
+        assert!(matches!(
+            restarted.refresh_secret_shares(&shares, 2, &refresh),
+            Err(CryptoError::Validation { field, .. }) if field == "epoch"
+        ));
+        assert!(restarted.create_secret_shares(secret, 2, 3, &deal).is_err());
+        let next = restarted.begin_epoch("durable-key", "refresh").unwrap();
+        assert_eq!((next.epoch, next.operation_id.as_str()), (2, "refresh-3"));
+        restarted.refresh_secret_shares(&shares, 2, &next).unwrap();
+        let recover = restarted.begin_operation("durable-key", "recover").unwrap();
+        assert_eq!(
+            (recover.epoch, recover.operation_id.as_str()),
+            (2, "recover-4")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_recovery_collection_feedback() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("collected-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let (shares, commitments) =
+            sharing::generate_key_shares_with_commitments(secret, 2, 3).unwrap();
+        system.registry.issue(&context, &shares);
+        system
+            .revoke_share("deal", 1, "device lost", &context.for_operation("revoke"))
+            .unwrap();
+
+        let mut collection =
+            RecoveryCollection::new(context.for_operation("recover"), 2, vec![(0, commitments)])
+                .unwrap();
+        let feedback = system.submit_share(&mut collection, "alice", shares[0].clone());
+        assert!(
+            matches!(feedback.status, CollectionStatus::Rejected { ref code, .. } if code == "ZKT-E012")
+        );
+        assert!(system
+            .submit_share(&mut collection, "bob", shares[1].clone())
+            .is_accepted());
+        assert!(matches!(
+            system.finish_recovery(&collection),
+            Err(CryptoError::Validation { .. })
+        ));
+        assert!(system
+            .submit_share(&mut collection, "carol", shares[2].clone())
+            .is_accepted());
+        assert_eq!(system.finish_recovery(&collection).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_revoked_share_rejected_on_recovery() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("revoked-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+        assert_eq!(system.share_registry().entries().len(), 3);
+
+        assert!(matches!(
+            system.revoke_share("deal", 9, "lost", &context.for_operation("revoke")),
+            Err(CryptoError::Validation { .. })
+        ));
+        system
+            .revoke_share("deal", 2, "device lost", &context.for_operation("revoke"))
+            .unwrap();
+        let result =
+            system.recover_secret_enterprise(&shares[..2], &context.for_operation("recover"));
+        match result {
+            Err(e @ CryptoError::RevokedShare { .. }) => {
+                assert_eq!(e.code(), "ZKT-E012");
+                assert!(
+                    matches!(e, CryptoError::RevokedShare { index: 2, ref operation_id } if operation_id == "deal")
+                );
+            }
+            other => panic!("expected RevokedShare, got {:?}", other),
+        }
+        let recovered = system
+            .recover_secret_enterprise(&[shares[0].clone(), shares[2].clone()], &context)
+            .unwrap();
+        assert_eq!(recovered, secret);
+
+        // Refreshing the remaining slices issues a new sharing without the revoked one.
+        let refresh = context.next_epoch("refresh");
+        let remaining = [shares[0].clone(), shares[2].clone()];
+        let refreshed = system
+            .refresh_secret_shares(&remaining, 2, &refresh)
+            .unwrap();
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&refreshed[..2], &refresh)
+                .unwrap(),
+            secret
+        );
+
+        let codes: Vec<_> = system
+            .key_history("revoked-key")
+            .iter()
+            .map(|r| r.event.code())
+            .collect();
+        assert!(codes.contains(&"ZKT-A012") && codes.contains(&"ZKT-A013"));
+        assert_eq!(system.status().recent_high_severity_events.len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_rejects_revoked_shares() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("revoked-refresh-key", 0, "deal");
+        let shares = system
+            .create_secret_shares(Scalar::from(42u64), 2, 3, &context)
+            .unwrap();
+        system
+            .revoke_share("deal", 2, "device lost", &context.for_operation("revoke"))
+            .unwrap();
+
+        let refresh = context.next_epoch("refresh");
+        assert!(matches!(
+            system.refresh_secret_shares(&shares, 2, &refresh),
+            Err(CryptoError::RevokedShare { index: 2, ref operation_id }) if operation_id == "deal"
+        ));
+        assert!(system
+            .key_history("revoked-refresh-key")
+            .iter()
+            .any(|r| matches!(
+                r.event,
+                SecurityEvent::RevokedShareRejected { index: 2, .. }
+            )));
+        assert!(system
+            .share_registry()
+            .entries()
+            .iter()
+            .all(|e| e.operation_id == "deal"));
+    }
+
+    #[test]
+    fn test_failed_operations_leave_epoch_unclaimed() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("retry-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        assert!(system.create_secret_shares(secret, 0, 3, &context).is_err());
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+
+        let refresh = context.next_epoch("refresh");
+        assert!(matches!(
+            system.refresh_secret_shares(&shares, 4, &refresh),
+            Err(CryptoError::Validation { .. })
+        ));
+        let refreshed = system.refresh_secret_shares(&shares, 2, &refresh).unwrap();
+
+        let adjust = refresh.next_epoch("adjust");
+        assert!(system
+            .adjust_threshold(&refreshed, 4, 3, 5, &adjust)
+            .is_err());
+        system
+            .adjust_threshold(&refreshed, 2, 3, 5, &adjust)
+            .unwrap();
+        assert!(matches!(
+            system.refresh_secret_shares(&refreshed, 2, &adjust),
+            Err(CryptoError::Validation { field, .. }) if field == "epoch"
+        ));
+    }
+
+    #[test]
+    fn test_share_level_audit_events() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("share-events-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+
+        let mut forged = shares[..2].to_vec();
+        forged[1].commitment += curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+        assert!(system
+            .recover_secret_enterprise(&forged, &context.for_operation("forged"))
+            .is_err());
+        let failed = system.get_audit_events().iter().find_map(|e| match e {
+            SecurityEvent::ShareVerificationFailed { index, code, .. } => Some((*index, code)),
+            _ => None,
+        });
+        assert_eq!(failed, Some((2, &"ZKT-V001".to_string())));
+
+        // A stale epoch is rejected before any slice is checked or reissued.
+        assert!(system.adjust_threshold(&shares, 2, 3, 5, &context).is_err());
+        assert!(system
+            .adjust_threshold(&shares, 2, 6, 5, &context.next_epoch("adjust"))
+            .is_err());
+        let adjust = context.next_epoch("adjust");
+        let adjusted = system.adjust_threshold(&shares, 2, 3, 5, &adjust).unwrap();
+        assert_eq!(adjusted.len(), 5);
+        assert_ne!(recover_secret_fft(&adjusted[..2]).unwrap(), secret);
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&adjusted[2..], &adjust.for_operation("recover"))
+                .unwrap(),
+            secret
+        );
+
+        let history: Vec<_> = system
+            .key_history("share-events-key")
+            .iter()
+            .map(|r| {
+                (
+                    r.event.code(),
+                    r.context.as_ref().unwrap().operation_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            history,
+            [
+                ("ZKT-A007", "deal"),
+                ("ZKT-A017", "forged"),
+                ("ZKT-A018", "forged"),
+                ("ZKT-A017", "adjust"),
+                ("ZKT-A017", "adjust"),
+                ("ZKT-A017", "adjust"),
+                ("ZKT-A019", "adjust"),
+                ("ZKT-A017", "recover"),
+                ("ZKT-A017", "recover"),
+                ("ZKT-A017", "recover"),
+                ("ZKT-A009", "recover"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_state_transitions() {
+        let is_transition = |result: CryptoResult<()>, from: &str, to: &str| matches!(result, Err(CryptoError::InvalidKeyStateTransition { from: f, to: t }) if f == from && t == to);
+
+        let mut key = Key::new(Scalar::from(7u64));
+        assert!(is_transition(key.retire(), "Generated", "Retired"));
+        key.activate().unwrap();
+        assert!(is_transition(key.activate(), "Active", "Active"));
+        assert!(is_transition(key.destroy(), "Active", "Destroyed"));
+        assert_eq!(key.secret, Scalar::from(7u64));
+        key.retire().unwrap();
+        assert!(key.retirement_time.is_some());
+        key.destroy().unwrap();
+        assert_eq!(key.secret, Scalar::ZERO);
+        assert!(is_transition(key.activate(), "Destroyed", "Active"));
+        assert!(is_transition(key.destroy(), "Destroyed", "Destroyed"));
+        assert_eq!(key.state, KeyState::Destroyed);
+
+        let mut unused = Key::new(Scalar::from(8u64));
+        unused.destroy().unwrap();
+        assert!(unused.activation_time.is_none());
+    }
+
+    #[test]
+    fn test_key_expiry_enforcement() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("expiring-key", 0, "keygen");
+        let key = system
+            .generate_enterprise_key(&mut SoftwareKeyProvider::new(), &context)
+            .unwrap();
+        let activated = key.activation_time.unwrap();
+        assert_eq!(
+            key.expiry_time,
+            Some(activated + chrono::TimeDelta::hours(24))
+        );
+        assert_eq!(system.key_expiry("expiring-key"), key.expiry_time);
+        assert!(!key.is_expired());
+        assert!(key.is_expired_at(activated + chrono::TimeDelta::hours(24)));
+        let shares = system
+            .create_secret_shares(key.secret, 2, 3, &context.for_operation("deal"))
+            .unwrap();
+
+        // The same key once past its lifetime.
+        let mut expired = Key::new(key.secret);
+        expired.activate().unwrap();
+        expired.expiry_time = Some(chrono::Utc::now() - chrono::TimeDelta::hours(1));
+        system.register_key("expiring-key", &expired);
+        let is_expired = |error: CryptoError| {
+            matches!(error, CryptoError::KeyExpired { ref key_id, .. } if key_id == "expiring-key")
+                && error.code() == "ZKT-E014"
+        };
+        assert!(is_expired(
+            system
+                .create_secret_shares(key.secret, 2, 3, &context.next_epoch("redeal"))
+                .unwrap_err()
+        ));
+        assert!(is_expired(
+            system
+                .recover_secret_enterprise(&shares[..2], &context.for_operation("recover"))
+                .unwrap_err()
+        ));
+        let refusals: Vec<_> = system
+            .get_audit_events()
+            .iter()
+            .filter_map(|e| match e {
+                SecurityEvent::KeyExpired { operation, .. } => Some(operation.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(refusals, ["share_generation", "secret_recovery"]);
+
+        // Registering a key without expiry lifts the restriction.
+        system.register_key("expiring-key", &Key::new(key.secret));
+        assert_eq!(system.key_expiry("expiring-key"), None);
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&shares[..2], &context.for_operation("recover"))
+                .unwrap(),
+            key.secret
+        );
+    }
+
+    #[test]
+    fn test_hash_function_selection() {
+        let fips = EnterpriseConfig {
+            compliance_mode: ComplianceMode::Fips140L3,
+            ..EnterpriseConfig::default()
+        };
+        assert_eq!(EnterpriseConfig::default().hash(), HashFunction::Blake3);
+        assert_eq!(fips.hash(), HashFunction::Sha256);
+        let sha3 = EnterpriseConfig {
+            hash_function: Some(HashFunction::Sha3_256),
+            ..fips.clone()
+        };
+        assert_eq!(sha3.hash(), HashFunction::Sha3_256);
+        let mut blake3_fips = EnterpriseCryptoSystem::new(EnterpriseConfig {
+            hash_function: Some(HashFunction::Blake3),
+            ..fips.clone()
+        });
+        assert!(matches!(
+            blake3_fips.validate_security(),
+            Err(CryptoError::Configuration { ref parameter, .. }) if parameter == "hash_function"
+        ));
+
+        let secret = Scalar::from(42u64);
+        for config in [fips, sha3] {
+            let hash = config.hash().adapter();
+            let mut system = EnterpriseCryptoSystem::new(config);
+            system.validate_security().unwrap();
+            let context = OperationContext::new("approved-hash", 0, "deal");
+            let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+            let refreshed = system
+                .refresh_secret_shares(&shares, 2, &context.next_epoch("refresh"))
+                .unwrap();
+            for share in shares.iter().chain(&refreshed) {
+                verify_proof_with(&*hash, &share.proof, share.commitment, share.index).unwrap();
+                assert!(verify_proof(&share.proof, share.commitment, share.index).is_err());
+            }
+            let recover = context.next_epoch("refresh").for_operation("recover");
+            assert_eq!(
+                system
+                    .recover_secret_enterprise(&refreshed[1..], &recover)
+                    .unwrap(),
+                secret
+            );
+
+            // Slices proved with BLAKE3 are refused.
+            let blake3_shares = generate_key_shares(secret, 2, 3).unwrap();
+            assert!(matches!(
+                system.recover_secret_enterprise(&blake3_shares[1..], &recover),
+                Err(CryptoError::CryptographicOperation { .. })
+            ));
+
+            let key = Key::new(secret);
+            let material = system
+                .derive_key_material(&key, "acme 2024 backup encryption", 32, &context)
+                .unwrap();
+            assert_eq!(
+                material,
+                key.derive_material_with(&*hash, "acme 2024 backup encryption", 32)
+                    .unwrap()
+            );
+            assert_ne!(
+                material,
+                key.derive_material("acme 2024 backup encryption", 32)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_threshold_compliance_proof() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("governed-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 3, 5, &context).unwrap();
+        system
+            .revoke_share("deal", 5, "lost", &context.for_operation("revoke"))
+            .unwrap();
+        let recover = context.for_operation("recover");
+        assert!(system
+            .prove_threshold_compliance(&shares[1..4], &recover)
+            .is_err());
+        system
+            .recover_secret_enterprise(&shares[1..4], &recover)
+            .unwrap();
+
+        let proof = system
+            .prove_threshold_compliance(&shares[1..4], &recover)
+            .unwrap();
+        assert_eq!(
+            (
+                proof.threshold,
+                proof.committee.len(),
+                proof.contributions.len()
+            ),
+            (3, 4, 3)
+        );
+        system.verify_threshold_compliance(&proof).unwrap();
+
+        // Revoked slices are not part of the committee.
+        assert!(matches!(
+            system.prove_threshold_compliance(&shares[2..], &recover),
+            Err(CryptoError::Validation { .. })
+        ));
+        // A committee or threshold other than the registered one is refused.
+        let mut padded = proof.clone();
+        padded.threshold = 2;
+        padded.contributions.truncate(2);
+        assert!(padded.verify().is_err());
+        let mut forged = PolicyComplianceProof::new(
+            "governed-key",
+            "recover",
+            "deal",
+            2,
+            proof.committee.clone(),
+        )
+        .unwrap();
+        forged.contribute(&shares[0]).unwrap();
+        forged.contribute(&shares[1]).unwrap();
+        assert!(matches!(
+            system.verify_threshold_compliance(&forged),
+            Err(CryptoError::Validation { ref field, .. }) if field == "threshold"
+        ));
+    }
+
+    #[test]
+    fn test_key_rotation_manager() {
+        let config = EnterpriseConfig::default();
+        assert!(KeyRotationManager::new(0).is_err());
+        let mut manager = KeyRotationManager::from_config(&config).unwrap();
+        let mut system = EnterpriseCryptoSystem::new(config);
+        let context = OperationContext::new("rotating-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+        manager.track(&context, 2);
+
+        let now = chrono::Utc::now();
+        assert!(!manager.needs_rotation("rotating-key"));
+        assert!(!manager.needs_rotation("untracked-key"));
+        let later = now + chrono::TimeDelta::hours(25);
+        assert!(manager.needs_rotation_at("rotating-key", later));
+        assert_eq!(manager.due_at(later), ["rotating-key"]);
+
+        let rotation = context.next_epoch("rotate");
+        assert!(manager.rotate(&mut system, &shares, &context).is_err());
+        let rotated = manager.rotate(&mut system, &shares, &rotation).unwrap();
+        let entry = manager.entry("rotating-key").unwrap();
+        assert_eq!((entry.epoch, entry.operation_id.as_str()), (1, "rotate"));
+        assert!(!manager.needs_rotation("rotating-key"));
+
+        // The old slices are retired; the rotated ones recover the same secret.
+        assert!(matches!(
+            system.recover_secret_enterprise(&shares[..2], &rotation.for_operation("recover")),
+            Err(CryptoError::RevokedShare { .. })
+        ));
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&rotated[1..], &rotation.for_operation("recover"))
+                .unwrap(),
+            secret
+        );
+        assert_eq!(system.share_registry().revoked().count(), 3);
+        let rotated_event = system.get_audit_events().iter().find_map(|e| match e {
+            SecurityEvent::KeyRotated {
+                epoch,
+                retired_shares,
+                ..
+            } => Some((*epoch, *retired_shares)),
+            _ => None,
+        });
+        assert_eq!(rotated_event, Some((1, 3)));
+        assert!(manager.untrack("rotating-key").is_some());
+        assert!(manager.entries().is_empty());
+    }
+
+    #[test]
+    fn test_disenrolled_participant_is_retired() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let secret = Scalar::from(404u64);
+        let context = OperationContext::new("team-key", 0, "deal");
+        let (unproven, commitments) = system
+            .create_secret_shares_deferred(secret, 2, 4, &context)
+            .unwrap();
+        let proofs = system.publish_share_proofs(&unproven, 2, &context).unwrap();
+        let shares: Vec<ShareData> = unproven
+            .into_iter()
+            .zip(&proofs)
+            .map(|(s, p)| s.attach_proof(p).unwrap())
+            .collect();
+
+        let removal = context.next_epoch("disenroll");
+        assert!(matches!(
+            system.disenroll_participant(&shares, &commitments, 4, &removal),
+            Err(CryptoError::Validation { .. })
+        ));
+        assert!(matches!(
+            system.disenroll_participant(&shares[..1], &commitments, 4, &removal),
+            Err(CryptoError::Validation { .. })
+        ));
+        let (remaining, record) = system
+            .disenroll_participant(&shares[..3], &commitments, 4, &removal)
+            .unwrap();
+        assert_eq!(
+            remaining.iter().map(|s| s.index).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        record.verify(&commitments).unwrap();
+        assert!(vss::diff(&commitments, &record.commitments).is_permitted_refresh());
+        assert!(verify_share_validity_with_commitments(&remaining, &record.commitments).is_ok());
+
+        // The removed slice no longer combines with the new ones and is refused.
+        let mixed = [remaining[0].clone(), shares[3].clone()];
+        assert_ne!(hazmat::recover_secret_fft(&mixed).unwrap(), secret);
+        let recovery = removal.for_operation("recover");
+        assert!(matches!(
+            system.recover_secret_enterprise(&shares[2..], &recovery),
+            Err(CryptoError::RevokedShare { .. })
+        ));
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&remaining[1..], &recovery)
+                .unwrap(),
+            secret
+        );
+        let reason = system
+            .share_registry()
+            .get("deal", 4)
+            .map(|e| e.status.clone());
+        assert!(matches!(
+            reason,
+            Some(registry::ShareStatus::Revoked { ref reason, .. }) if reason == "disenrolled"
+        ));
+        let event = system.get_audit_events().iter().find_map(|e| match e {
+            SecurityEvent::ParticipantDisenrolled {
+                index,
+                epoch,
+                num_shares,
+                retired_shares,
+                ..
+            } => Some((*index, *epoch, *num_shares, *retired_shares)),
+            _ => None,
+        });
+        assert_eq!(event, Some((4, 1, 3, 4)));
+    }
+
+    #[test]
+    fn test_public_state_export_and_import() {
+        let mut origin = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let secret = Scalar::from(77u64);
+        let context = origin.begin_epoch("dr-key", "deal").unwrap();
+        let shares = origin.create_secret_shares(secret, 2, 3, &context).unwrap();
+        origin
+            .revoke_share(&context.operation_id, 3, "lost", &context)
+            .unwrap();
+        let (custodian_shares, commitments) =
+            generate_key_shares_with_commitments(secret, 2, 3).unwrap();
+        let sealing_key = SealingKey::generate().unwrap();
+        let sealed = sealing::seal(&custodian_shares[0], &sealing_key).unwrap();
+        let nonce = [5u8; 16];
+        let (_, attestation) = custodian::check_share(
+            &sealed,
+            &sealing_key,
+            &commitments,
+            &commitments.root(),
+            &nonce,
+        )
+        .unwrap();
+        origin
+            .record_attestation("custodian-a", "dr-key", &attestation, &commitments, &nonce)
+            .unwrap();
+
+        assert!(origin.export_public_state("unknown-key").is_err());
+        let exported = origin.export_public_state("dr-key").unwrap();
+        let backup: PublicState =
+            serde_json::from_str(&serde_json::to_string(&exported).unwrap()).unwrap();
+        backup.verify().unwrap();
+        assert_eq!((backup.epoch, backup.policy.threshold), (0, 2));
+        let mut tampered = backup.clone();
+        tampered.shares[0].commitment = tampered.shares[1].commitment;
+        assert!(tampered.verify().is_err());
+
+        let mut standard = EnterpriseCryptoSystem::new(EnterpriseConfig {
+            security_level: SecurityLevel::Standard,
+            ..EnterpriseConfig::default()
+        });
+        assert!(matches!(
+            standard.import_public_state(&backup),
+            Err(CryptoError::Configuration { .. })
+        ));
+
+        // A fresh system recovers from the custodians' slices alone.
+        let mut fresh = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        fresh.import_public_state(&backup).unwrap();
+        assert!(matches!(
+            fresh.import_public_state(&backup),
+            Err(CryptoError::Validation { .. })
+        ));
+        assert_eq!(fresh.transcripts("dr-key").len(), 1);
+        assert_eq!(fresh.status().custodians.len(), 1);
+        let recovery = fresh.begin_operation("dr-key", "recover").unwrap();
+        assert_eq!(
+            fresh
+                .recover_secret_enterprise(&shares[..2], &recovery)
+                .unwrap(),
+            secret
+        );
+        assert!(matches!(
+            fresh.recover_secret_enterprise(&shares[1..], &recovery),
+            Err(CryptoError::RevokedShare { .. })
+        ));
+
+        // Epochs and operation ids of the original system are not handed out again.
+        assert!(fresh.create_secret_shares(secret, 2, 3, &context).is_err());
+        let refresh = fresh.begin_epoch("dr-key", "refresh").unwrap();
+        assert_eq!(refresh.epoch, 1);
+        assert!(!origin.transcripts("dr-key").iter().any(|t| {
+            t.context.operation_id == refresh.operation_id
+                || t.context.operation_id == recovery.operation_id
+        }));
+        assert!(fresh
+            .get_audit_events()
+            .iter()
+            .any(|e| matches!(e, SecurityEvent::PublicStateImported { shares: 3, .. })));
+    }
+
+    #[test]
+    fn test_key_material_derivation() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("material-key", 0, "derive");
+        let mut key = Key::new(Scalar::from(42u64));
+
+        let material = system
+            .derive_key_material(&key, "acme 2024 backup encryption", 64, &context)
+            .unwrap();
+        assert_eq!(material.len(), 64);
+        let again = key
+            .derive_material("acme 2024 backup encryption", 64)
+            .unwrap();
+        assert_eq!(material, again);
+        let prefix = key
+            .derive_material("acme 2024 backup encryption", 16)
+            .unwrap();
+        assert_eq!(prefix[..], material[..16]);
+        let other = key.derive_material("acme 2024 backup mac", 64).unwrap();
+        assert_ne!(material, other);
+        let other_key = Key::new(Scalar::from(43u64));
+        assert_ne!(
+            other_key
+                .derive_material("acme 2024 backup encryption", 64)
+                .unwrap(),
+            material
+        );
+
+        let is_validation = |result: CryptoResult<zeroize::Zeroizing<Vec<u8>>>, expected: &str| matches!(result, Err(CryptoError::Validation { field, .. }) if field == expected);
+        assert!(is_validation(key.derive_material("", 32), "context"));
+        assert!(is_validation(key.derive_material("acme", 0), "len"));
+        key.destroy().unwrap();
+        assert!(is_validation(key.derive_material("acme", 32), "key"));
+
+        let history = system.key_history("material-key");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].event.code(), "ZKT-A014");
+        assert!(history[0]
+            .event
+            .fields()
+            .contains(&("purpose", "acme 2024 backup encryption".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_sharing_parameters() {
+        let secret = Scalar::from(7u64);
+        let is_validation = |result: CryptoResult<Vec<ShareData>>, expected: &str| matches!(result, Err(CryptoError::Validation { field, .. }) if field == expected);
+
+        assert!(is_validation(
+            generate_key_shares(secret, 0, 3),
+            "threshold"
+        ));
+        assert!(is_validation(
+            generate_key_shares(secret, 4, 3),
+            "threshold"
+        ));
+        assert!(is_validation(generate_key_shares(secret, 1, 0), "n"));
+
+        let shares = generate_key_shares(secret, 2, 3).unwrap();
+        assert!(is_validation(update_shares(&shares, 0), "threshold"));
+        assert!(is_validation(update_shares(&shares, 4), "threshold"));
+
+        #[allow(deprecated)]
+        let (no_parties, no_threshold) = (
+            mpc::mpc_generate_key_shares(0, 2, 3),
+            mpc::mpc_generate_key_shares(2, 0, 3),
+        );
+        assert!(matches!(no_parties, Err(CryptoError::Validation { .. })));
+        assert!(matches!(no_threshold, Err(CryptoError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_safe_recovery_verifies_slices() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let shares = generate_key_shares(secret, 3, 5).unwrap();
+        assert_eq!(recover_secret(&shares[1..4]).unwrap(), secret);
+
+        let mut forged = shares[..3].to_vec();
+        forged[1].share += Scalar::ONE;
+        forged[1].commitment = utils::pedersen_commit(&forged[1].share, &forged[1].random);
+        assert!(matches!(
+            recover_secret(&forged),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        // The hazmat layer interpolates whatever it is given.
+        assert_ne!(recover_secret_fft(&forged).unwrap(), secret);
+        assert!(matches!(
+            recover_secret(&[]),
+            Err(CryptoError::CryptographicOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_additional_share_enrolls_a_participant() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let (shares, commitments) = generate_key_shares_with_commitments(secret, 3, 5).unwrap();
+        let enrolled = issue_additional_share(&shares[1..4], 3, 9).unwrap();
+        assert_eq!(enrolled.index, 9);
+        commitments.verify_share(&enrolled).unwrap();
+        // The new slice recovers with any other threshold - 1 slices, none of which changed.
+        assert_eq!(
+            recover_secret(&[shares[0].clone(), enrolled.clone(), shares[4].clone()]).unwrap(),
+            secret
+        );
+        let again = issue_additional_share(&shares[..3], 3, 9).unwrap();
+        assert_eq!(again.share, enrolled.share);
+        assert_eq!(again.commitment, enrolled.commitment);
+
+        for (helpers, new_index) in [(&shares[..2], 9), (&shares[..3], 2), (&shares[..3], 0)] {
+            assert!(matches!(
+                issue_additional_share(helpers, 3, new_index),
+                Err(CryptoError::Validation { .. })
+            ));
+        }
+        // A helper whose value does not open its commitment is refused, as is a bad proof.
+        let mut forged = shares[..3].to_vec();
+        forged[0].share += Scalar::ONE;
+        assert!(matches!(
+            issue_additional_share(&forged, 3, 9),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        forged[0].commitment = utils::pedersen_commit(&forged[0].share, &forged[0].random);
+        assert!(matches!(
+            issue_additional_share(&forged, 3, 9),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_policy_sharing_recovers_for_satisfying_sets() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let ops = (1..=3)
+            .map(|i| Policy::party(&format!("ops-{}", i)))
+            .collect();
+        let security = vec![Policy::party("sec-1"), Policy::party("sec-2")];
+        let policy = Policy::And(vec![Policy::Threshold(2, ops), Policy::Or(security)]);
+        let shares = generate_policy_shares(secret, &policy).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(shares[3].party, "sec-1");
+        vss::verify_share_validity(&shares.iter().map(|s| s.share.clone()).collect::<Vec<_>>())
+            .unwrap();
+
+        let pick = |positions: &[usize]| -> Vec<PolicyShare> {
+            positions.iter().map(|&p| shares[p].clone()).collect()
+        };
+        for positions in [&[0, 2, 4][..], &[4, 1, 0], &[0, 1, 2, 3, 4]] {
+            assert_eq!(
+                recover_secret_with_policy(&policy, &pick(positions)).unwrap(),
+                secret
+            );
+        }
+        // All of security with one of ops, or all of ops alone, is not enough.
+        for positions in [&[0, 3, 4][..], &[0, 1, 2]] {
+            assert!(matches!(
+                recover_secret_with_policy(&policy, &pick(positions)),
+                Err(CryptoError::Validation { .. })
+            ));
+        }
+
+        let mut relabelled = pick(&[0, 1, 3]);
+        relabelled[2].party = "sec-2".to_string();
+        assert!(recover_secret_with_policy(&policy, &relabelled).is_err());
+        let mut forged = pick(&[0, 1, 3]);
+        forged[0].share.share += Scalar::ONE;
+        forged[0].share.commitment =
+            utils::pedersen_commit(&forged[0].share.share, &forged[0].share.random);
+        assert!(matches!(
+            recover_secret_with_policy(&policy, &forged),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let restored: Vec<PolicyShare> =
+            serde_json::from_str(&serde_json::to_string(&pick(&[1, 2, 4])).unwrap()).unwrap();
+        assert_eq!(
+            recover_secret_with_policy(&policy, &restored).unwrap(),
+            secret
+        );
+    }
+
+    #[test]
+    fn test_hierarchical_sharing_needs_senior_holders() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        // Two of three executives among any four holders.
+        let thresholds = [2, 4];
+        let shares = generate_hierarchical_shares(secret, &thresholds, &[3, 4]).unwrap();
+        assert_eq!(shares.iter().filter(|s| s.level == 1).count(), 3);
+        assert_eq!(shares[3].share.index, 4);
+
+        let pick = |positions: &[usize]| -> Vec<HierarchicalShare> {
+            positions.iter().map(|&p| shares[p].clone()).collect()
+        };
+        for positions in [
+            &[0, 2, 3, 6][..],
+            &[5, 1, 4, 0],
+            &[0, 1, 2, 3],
+            &[0, 1, 2, 3, 4, 5, 6],
+        ] {
+            assert_eq!(
+                recover_secret_hierarchical(&thresholds, &pick(positions)).unwrap(),
+                secret
+            );
+        }
+        for positions in [&[0, 3, 4, 5][..], &[3, 4, 5, 6], &[0, 1, 3]] {
+            assert!(matches!(
+                recover_secret_hierarchical(&thresholds, &pick(positions)),
+                Err(CryptoError::Validation { .. })
+            ));
+        }
+        // The level check is not what protects the secret: one executive's value with three
+        // second derivatives does not determine it.
+        let junior: Vec<(Scalar, usize, Scalar)> = pick(&[0, 3, 4, 5])
+            .iter()
+            .map(|s| {
+                let order = if s.level == 1 { 0 } else { 2 };
+                (Scalar::from(s.share.index as u64), order, s.share.share)
+            })
+            .collect();
+        assert!(matches!(
+            hazmat::birkhoff_interpolate(&junior),
+            Err(lagrange_fft::LagrangeError::NotPoised)
+        ));
+
+        let mut forged = pick(&[0, 1, 3, 4]);
+        forged[2].share.share += Scalar::ONE;
+        forged[2].share.commitment =
+            utils::pedersen_commit(&forged[2].share.share, &forged[2].share.random);
+        assert!(matches!(
+            recover_secret_hierarchical(&thresholds, &forged),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(generate_hierarchical_shares(secret, &[3, 3], &[3, 3]).is_err());
+        assert!(generate_hierarchical_shares(secret, &[2, 5], &[1, 4]).is_err());
+        assert!(generate_hierarchical_shares(secret, &[2, 5], &[3]).is_err());
+    }
+
+    #[test]
+    fn test_degenerate_thresholds() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        for (threshold, n) in [(1, 1), (1, 4), (2, 2), (5, 5), (40, 40)] {
+            let shares = generate_key_shares(secret, threshold, n).unwrap();
+            vss::verify_share_validity(&shares).unwrap();
+            assert_eq!(
+                recover_secret_fft(&shares[n - threshold..]).unwrap(),
+                secret
+            );
+            if threshold == n && n > 1 {
+                // Every slice of an n-of-n dealing is needed.
+                assert_ne!(recover_secret_fft(&shares[1..]).unwrap(), secret);
+            }
+
+            let refreshed = update_shares(&shares, threshold).unwrap();
+            assert_eq!(recover_secret_fft(&refreshed[..threshold]).unwrap(), secret);
+            for new_threshold in [1, n] {
+                let adjusted =
+                    sharing::adjust_threshold(&shares[..threshold], threshold, new_threshold, n)
+                        .unwrap();
+                vss::verify_share_validity(&adjusted).unwrap();
+                assert_eq!(
+                    recover_secret_fft(&adjusted[..new_threshold]).unwrap(),
+                    secret
+                );
+                assert!(adjusted.iter().all(|s| s.random != Scalar::ZERO));
+            }
+            assert!(sharing::adjust_threshold(&shares, threshold, 0, n).is_err());
+            assert!(sharing::adjust_threshold(&shares, threshold, n + 1, n).is_err());
+        }
+
+        // 1-of-n: every holder gets the secret under the single commitment C_0.
+        let (copies, commitments) =
+            sharing::generate_key_shares_with_commitments(secret, 1, 3).unwrap();
+        assert!(copies.iter().all(|s| s.share == secret));
+        assert!(copies
+            .iter()
+            .all(|s| s.commitment == commitments.coefficients[0]));
+        let (refreshed, record) = sharing::refresh_shares(&copies, &commitments).unwrap();
+        record.verify(&commitments).unwrap();
+        assert_eq!(refreshed[2].share, secret);
+
+        // n-of-n through the enterprise API.
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("two-of-two", 0, "deal");
+        let pair = system.create_secret_shares(secret, 2, 2, &context).unwrap();
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&pair, &context.for_operation("recover"))
+                .unwrap(),
+            secret
+        );
+    }
+
+    /// Generator whose entropy source is permanently unavailable.
+    struct FailingRng;
+
+    impl rand::RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            unreachable!("randomness is only drawn through try_fill_bytes")
+        }
+        fn next_u64(&mut self) -> u64 {
+            unreachable!("randomness is only drawn through try_fill_bytes")
+        }
+        fn fill_bytes(&mut self, _dest: &mut [u8]) {
+            unreachable!("randomness is only drawn through try_fill_bytes")
+        }
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand::Error> {
+            Err(rand::Error::new("entropy source unavailable"))
+        }
+    }
+
+    impl rand::CryptoRng for FailingRng {}
+
+    #[test]
+    fn test_rng_failure_is_reported() {
+        let result = random_scalar(&mut FailingRng);
+        assert!(matches!(
+            result,
+            Err(CryptoError::InsufficientEntropy { actual: 0, .. })
+        ));
+    }
+
+    /// Provider drawing from [`FailingRng`].
+    struct FailingProvider;
+
+    impl KeyProvider for FailingProvider {
+        fn name(&self) -> &str {
+            "failing"
+        }
+        fn generate(&mut self, _key_id: &str) -> CryptoResult<Key> {
+            random_scalar(&mut FailingRng).map(Key::new)
+        }
+        fn load(&mut self, key_id: &str) -> CryptoResult<Key> {
+            Err(key_provider::unknown_key(key_id))
+        }
+        fn commitment(&mut self, key_id: &str) -> CryptoResult<curve25519_dalek::RistrettoPoint> {
+            Err(key_provider::unknown_key(key_id))
+        }
+        fn zeroize(&mut self, key_id: &str) -> CryptoResult<()> {
+            Err(key_provider::unknown_key(key_id))
+        }
+    }
+
+    #[test]
+    fn test_safe_mode_refuses_secret_producing_operations() {
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("safe-key", 0, "deal");
+        let secret = Scalar::from(42u64);
+        let shares = system.create_secret_shares(secret, 2, 3, &context).unwrap();
+
+        // An entropy failure switches the system to safe mode.
+        let keygen = OperationContext::new("safe-key-2", 0, "keygen");
+        assert!(matches!(
+            system.generate_enterprise_key(&mut FailingProvider, &keygen),
+            Err(CryptoError::InsufficientEntropy { .. })
+        ));
+        assert!(system.safe_mode().unwrap().starts_with("entropy failure"));
+
+        let is_refused = |result: CryptoResult<Scalar>| matches!(result, Err(ref e @ CryptoError::SafeMode { .. }) if e.code() == "ZKT-E013");
+        assert!(is_refused(system.recover_secret_enterprise(
+            &shares,
+            &context.for_operation("recover")
+        )));
+        assert!(matches!(
+            system.create_secret_shares(secret, 2, 3, &context.next_epoch("deal")),
+            Err(CryptoError::SafeMode { .. })
+        ));
+        assert!(matches!(
+            system.refresh_secret_shares(&shares, 2, &context.next_epoch("refresh")),
+            Err(CryptoError::SafeMode { .. })
+        ));
+        assert!(matches!(
+            system.derive_key_material(&Key::new(secret), "p", 32, &context),
+            Err(CryptoError::SafeMode { .. })
+        ));
+        assert!(matches!(
+            system.generate_enterprise_key(&mut SoftwareKeyProvider::new(), &keygen),
+            Err(CryptoError::SafeMode { .. })
+        ));
+
+        // Verification, revocation and reads keep working.
+        vss::verify_share_validity(&shares).unwrap();
+        system
+            .revoke_share("deal", 3, "device lost", &context.for_operation("revoke"))
+            .unwrap();
+        let status = system.status();
+        assert!(status.safe_mode.is_some());
+        assert!(status
+            .recent_high_severity_events
+            .iter()
+            .any(|r| r.event.code() == "ZKT-A015"));
+
+        system.exit_safe_mode().unwrap();
+        assert!(system.safe_mode().is_none());
+        let recovered = system
+            .recover_secret_enterprise(&shares[..2], &context.for_operation("recover"))
+            .unwrap();
+        assert_eq!(recovered, secret);
+
+        // Operators can enter safe mode as a policy command.
+        system.enter_safe_mode("incident response");
+        system.enter_safe_mode("ignored while in safe mode");
+        assert_eq!(system.safe_mode(), Some("incident response"));
+        let codes: Vec<_> = system.get_audit_events().iter().map(|e| e.code()).collect();
+        assert_eq!(codes.iter().filter(|c| **c == "ZKT-A015").count(), 2);
+        assert_eq!(codes.iter().filter(|c| **c == "ZKT-A016").count(), 1);
+    }
+
+    #[test]
+    fn test_deadlines_stop_enterprise_operations() {
+        use std::time::Duration;
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let context = OperationContext::new("deadline-key", 0, "deal");
+
+        let expired = context
+            .clone()
+            .with_deadline(Deadline::after(Duration::ZERO));
+        assert_eq!(expired, context);
+        assert!(matches!(
+            system.create_secret_shares(secret, 3, 5, &expired),
+            Err(CryptoError::Timeout { operation, .. }) if operation == "share_generation"
+        ));
+        assert!(system.share_registry().entries().is_empty());
+
+        // A generous deadline does not interfere; deadlines are not carried to other operations.
+        let generous = context.with_deadline(Deadline::after(Duration::from_secs(3600)));
+        let shares = system
+            .create_secret_shares(secret, 3, 5, &generous)
+            .unwrap();
+        assert!(generous.for_operation("recover").deadline().is_none());
+
+        let token = CancellationToken::new();
+        let recovery = generous
+            .for_operation("recover")
+            .with_deadline(Deadline::cancellable(token.clone()));
+        token.cancel();
+        assert!(matches!(
+            system.recover_secret_enterprise(&shares[..3], &recovery),
+            Err(CryptoError::Timeout { operation, timeout_ms: 0 })
+                if operation == "secret_recovery (cancelled)"
+        ));
+        assert_eq!(
+            system
+                .recover_secret_enterprise(&shares[..3], &generous.for_operation("recover"))
+                .unwrap(),
+            secret
+        );
+
+        let batch = vec![shares[..3].to_vec(); 4];
+        assert!(
+            lagrange_fft::recover_secrets_batch_until(&batch, recovery.deadline().unwrap())
+                .is_err()
+        );
+        let open = Deadline::cancellable(CancellationToken::new());
+        let recovered = lagrange_fft::recover_secrets_batch_until(&batch, &open).unwrap();
+        assert!(recovered.into_iter().all(|r| r.unwrap() == secret));
+        vss::verify_share_validity_batch_until(&shares, &open).unwrap();
+        assert!(matches!(
+            vss::verify_share_validity_batch_until(&shares, recovery.deadline().unwrap()),
+            Err(CryptoError::Timeout { operation, .. })
+                if operation == "batch_verification (cancelled)"
+        ));
+    }
+
+    #[test]
+    fn test_debug_output_redacts_secrets() {
+        let secret = random_scalar(&mut OsRng).unwrap();
+        let secret_hex = hex::encode(secret.as_bytes());
+        let key = Key::new(secret);
+        let shares = generate_key_shares(secret, 2, 3).unwrap();
+
+        assert!(!format!("{:?}", key).contains(&secret_hex));
+        for share in &shares {
+            let rendered = format!("{:?}", share);
+            assert!(!rendered.contains(&hex::encode(share.share.as_bytes())));
+            assert!(!rendered.contains(&hex::encode(share.random.as_bytes())));
+        }
+    }
+
+    #[test]
+    fn test_limits_match_the_enterprise_cap() {
+        let reported = limits();
+        assert_eq!(
+            reported.max_enterprise_shares,
+            limits::MAX_ENTERPRISE_SHARES
+        );
+        for limit in [reported.dealing, reported.recovery, reported.dkg] {
+            assert!(1 <= limit.threshold && limit.threshold <= limit.size);
+        }
+        let mut system = EnterpriseCryptoSystem::new(EnterpriseConfig::default());
+        let context = OperationContext::new("limits-key", 0, "deal");
+        let too_many = limits::MAX_ENTERPRISE_SHARES + 1;
+        for result in [
+            system
+                .create_secret_shares(Scalar::ONE, 2, too_many, &context)
+                .map(|_| ()),
+            system
+                .create_secret_shares_deferred(Scalar::ONE, 2, too_many, &context)
+                .map(|_| ()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(CryptoError::ResourceExhaustion { resource }) if resource == "share_count"
+            ));
+        }
+    }
+}
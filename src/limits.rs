@@ -0,0 +1,71 @@
+//! **limits module**
+//!
+//! Committee sizes the crate is verified to handle, and the budgets it meets at those sizes.
+//!
+//! [`limits()`] reports the largest dealing, recovery and DKG exercised by the scaling suite
+//! (feature `scale-tests`, `cargo test --release --features scale-tests scaling`), with the
+//! wall-clock and peak heap budgets that suite enforces. The suite reads its sizes and budgets
+//! from [`limits()`], so the values reported here are the ones the tests check, not estimates.
+//! Time budgets are about twice the times measured in optimized builds on one core of a current
+//! x86-64 server; heap budgets hold on every target. Larger committees are not rejected (except
+//! by the enterprise cap [`MAX_ENTERPRISE_SHARES`]) but have not been measured.
+
+/// Largest `num_shares` accepted by
+/// [`EnterpriseCryptoSystem::create_secret_shares`](crate::EnterpriseCryptoSystem::create_secret_shares)
+/// and its deferred variant; larger dealings fail with `CryptoError::ResourceExhaustion`.
+pub const MAX_ENTERPRISE_SHARES: usize = 1000;
+
+/// One verified operation size and its budgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedLimit {
+    /// Slices dealt or recovered from, or DKG participants.
+    pub size: usize,
+    /// Threshold at which `size` was exercised.
+    pub threshold: usize,
+    /// Wall-clock budget in an optimized build.
+    pub time_budget_ms: u64,
+    /// Budget for the peak heap allocated during the operation.
+    pub memory_budget_bytes: usize,
+}
+
+/// Verified capacities of the crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// [`MAX_ENTERPRISE_SHARES`].
+    pub max_enterprise_shares: usize,
+    /// Dealing of proved slices with
+    /// [`generate_key_shares`](crate::sharing::generate_key_shares), followed by a batch
+    /// verification of all of them.
+    pub dealing: VerifiedLimit,
+    /// Verified recovery with [`recover_secret`](crate::recover_secret) from exactly
+    /// `threshold` slices.
+    pub recovery: VerifiedLimit,
+    /// Honest in-process DKG run with [`DkgParticipant`](crate::dkg::DkgParticipant), from
+    /// round one to every participant's output.
+    pub dkg: VerifiedLimit,
+}
+
+/// The verified capacities, as checked by the scaling suite.
+pub const fn limits() -> Limits {
+    Limits {
+        max_enterprise_shares: MAX_ENTERPRISE_SHARES,
+        dealing: VerifiedLimit {
+            size: 10_000,
+            threshold: 5_001,
+            time_budget_ms: 20_000,
+            memory_budget_bytes: 32 << 20,
+        },
+        recovery: VerifiedLimit {
+            size: 5_000,
+            threshold: 5_000,
+            time_budget_ms: 20_000,
+            memory_budget_bytes: 4 << 20,
+        },
+        dkg: VerifiedLimit {
+            size: 100,
+            threshold: 67,
+            time_budget_ms: 20_000,
+            memory_budget_bytes: 256 << 20,
+        },
+    }
+}
@@ -1,478 +0,0 @@
-//! # Enterprise Zero-Knowledge Threshold Secret Sharing System
-//!
-//! A production-ready cryptographic library implementing polynomial-based secret sharing
-//! with zero-knowledge proofs, FFT-accelerated Lagrange interpolation, and enterprise-grade
-//! security features.
-//!
-//! ## Key Features
-//! - Information-theoretic security with formal proofs
-//! - Cross-platform compatibility (Windows, Linux, macOS)
-//! - BLAKE3 hash function (15x faster than SHA-256)
-//! - Enterprise audit trails and compliance
-//! - Performance-optimized algorithms
-//! - Comprehensive error handling
-
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
-use env_logger::Builder;
-use log::LevelFilter;
-use log::{error, info};
-use std::time::Instant;
-
-mod error;
-mod hash_adapter;
-mod key_lifecycle;
-mod lagrange_fft;
-mod mpc;
-mod proof;
-mod serialization;
-mod sharing;
-mod utils;
-mod vss;
-
-use crate::hash_adapter::SecurityLevel;
-use crate::utils::{random_scalar, ANOTHER_POINT};
-use curve25519_dalek::scalar::Scalar;
-pub use error::{AuditLogger, CryptoError, CryptoResult, ErrorHandler, SecurityEvent};
-pub use hash_adapter::Blake3Adapter;
-pub use key_lifecycle::{Key, KeyState};
-pub use lagrange_fft::recover_secret_fft;
-pub use proof::{generate_proof, verify_proof, Proof};
-use rand::rngs::OsRng;
-pub use sharing::{adjust_threshold, generate_key_shares, update_shares, ShareData};
-
-/// Enterprise configuration settings
-#[derive(Debug, Clone)]
-pub struct EnterpriseConfig {
-    pub security_level: SecurityLevel,
-    pub audit_enabled: bool,
-    pub performance_monitoring: bool,
-    pub compliance_mode: ComplianceMode,
-    pub max_key_lifetime_hours: u64,
-}
-
-/// Compliance modes for different regulatory requirements
-#[derive(Debug, Clone)]
-pub enum ComplianceMode {
-    /// Standard enterprise security
-    Standard,
-    /// FIPS 140-2 Level 3 compliance
-    Fips140L3,
-    /// Common Criteria EAL4+ compliance
-    CommonCriteriaEAL4Plus,
-    /// Custom compliance requirements
-    Custom(String),
-}
-
-impl Default for EnterpriseConfig {
-    fn default() -> Self {
-        Self {
-            security_level: SecurityLevel::High,
-            audit_enabled: true,
-            performance_monitoring: true,
-            compliance_mode: ComplianceMode::Standard,
-            max_key_lifetime_hours: 24,
-        }
-    }
-}
-
-/// Enterprise cryptographic system
-pub struct EnterpriseCryptoSystem {
-    config: EnterpriseConfig,
-    error_handler: ErrorHandler,
-    performance_metrics: Vec<lagrange_fft::PerformanceMetrics>,
-}
-
-impl EnterpriseCryptoSystem {
-    /// Initialize enterprise cryptographic system
-    pub fn new(config: EnterpriseConfig) -> Self {
-        Self {
-            config,
-            error_handler: ErrorHandler::default(),
-            performance_metrics: Vec::new(),
-        }
-    }
-
-    /// Perform comprehensive security validation
-    pub fn validate_security(&self) -> CryptoResult<()> {
-        info!("🔒 Performing enterprise security validation...");
-
-        // Validate BLAKE3 security properties
-        hash_adapter::SecurityValidator::validate_security_properties()
-            .map_err(|e| CryptoError::SecurityViolation { details: e })?;
-
-        // Validate compliance mode
-        match &self.config.compliance_mode {
-            ComplianceMode::Fips140L3 => {
-                info!("✓ FIPS 140-2 Level 3 compliance mode enabled");
-            }
-            ComplianceMode::CommonCriteriaEAL4Plus => {
-                info!("✓ Common Criteria EAL4+ compliance mode enabled");
-            }
-            ComplianceMode::Standard => {
-                info!("✓ Standard enterprise security mode enabled");
-            }
-            ComplianceMode::Custom(mode) => {
-                info!("✓ Custom compliance mode enabled: {}", mode);
-            }
-        }
-
-        // Performance benchmark
-        let performance = hash_adapter::SecurityValidator::benchmark_performance();
-        info!("✓ BLAKE3 performance: {} ns/MB", performance);
-
-        Ok(())
-    }
-
-    /// Generate enterprise-grade key with full lifecycle management
-    pub fn generate_enterprise_key(&mut self, key_id: &str) -> CryptoResult<Key> {
-        let start_time = Instant::now();
-
-        // Generate secure random scalar
-        let secret = random_scalar(&mut OsRng);
-        let mut key = Key::new(secret);
-
-        // Log security event
-        self.error_handler
-            .audit_logger
-            .log_event(SecurityEvent::KeyGenerated {
-                key_id: key_id.to_string(),
-                timestamp: chrono::Utc::now(),
-            });
-
-        // Activate key
-        key.activate();
-        self.error_handler
-            .audit_logger
-            .log_event(SecurityEvent::KeyActivated {
-                key_id: key_id.to_string(),
-                timestamp: chrono::Utc::now(),
-            });
-
-        // Record performance metrics
-        if self.config.performance_monitoring {
-            let metrics = lagrange_fft::PerformanceMetrics {
-                operation_type: "key_generation".to_string(),
-                duration_ns: start_time.elapsed().as_nanos() as u64,
-                input_size: 1,
-                algorithm_used: "curve25519_dalek".to_string(),
-            };
-            self.performance_metrics.push(metrics);
-        }
-
-        info!("🔑 Enterprise key generated: {}", key_id);
-        Ok(key)
-    }
-
-    /// Generate and distribute secret shares with enterprise features
-    pub fn create_secret_shares(
-        &mut self,
-        secret: Scalar,
-        threshold: usize,
-        num_shares: usize,
-        operation_id: &str,
-    ) -> CryptoResult<Vec<ShareData>> {
-        let start_time = Instant::now();
-
-        // Validate parameters
-        if threshold > num_shares {
-            return Err(CryptoError::Validation {
-                field: "threshold".to_string(),
-                reason: format!("threshold {} exceeds num_shares {}", threshold, num_shares),
-            });
-        }
-
-        if num_shares > 1000 {
-            return Err(CryptoError::ResourceExhaustion {
-                resource: "share_count".to_string(),
-            });
-        }
-
-        // Generate shares
-        let shares = generate_key_shares(secret, threshold, num_shares);
-
-        // Verify all shares
-        for share in &shares {
-            if !verify_proof(&share.proof, share.commitment, share.index) {
-                return Err(CryptoError::CryptographicOperation {
-                    operation: "share_verification".to_string(),
-                });
-            }
-        }
-
-        // Record performance metrics
-        if self.config.performance_monitoring {
-            let metrics = lagrange_fft::PerformanceMetrics {
-                operation_type: "share_generation".to_string(),
-                duration_ns: start_time.elapsed().as_nanos() as u64,
-                input_size: num_shares,
-                algorithm_used: "shamir_secret_sharing".to_string(),
-            };
-            self.performance_metrics.push(metrics);
-        }
-
-        info!(
-            "📊 Generated {} shares (threshold: {}) for operation: {}",
-            num_shares, threshold, operation_id
-        );
-
-        Ok(shares)
-    }
-
-    /// Recover secret with enterprise monitoring and validation
-    pub fn recover_secret_enterprise(
-        &mut self,
-        shares: &[ShareData],
-        operation_id: &str,
-    ) -> CryptoResult<Scalar> {
-        let start_time = Instant::now();
-
-        // Validate shares
-        for share in shares {
-            if !verify_proof(&share.proof, share.commitment, share.index) {
-                return Err(CryptoError::CryptographicOperation {
-                    operation: "share_validation".to_string(),
-                });
-            }
-        }
-
-        // Recover secret
-        let secret =
-            recover_secret_fft(shares).map_err(|e| CryptoError::CryptographicOperation {
-                operation: format!("secret_recovery: {}", e),
-            })?;
-
-        // Record performance metrics
-        if self.config.performance_monitoring {
-            let metrics = lagrange_fft::PerformanceMetrics {
-                operation_type: "secret_recovery".to_string(),
-                duration_ns: start_time.elapsed().as_nanos() as u64,
-                input_size: shares.len(),
-                algorithm_used: "lagrange_fft".to_string(),
-            };
-            self.performance_metrics.push(metrics);
-        }
-
-        info!(
-            "🔓 Secret recovered for operation: {} (used {} shares)",
-            operation_id,
-            shares.len()
-        );
-
-        Ok(secret)
-    }
-
-    /// Get performance metrics for enterprise monitoring
-    pub fn get_performance_metrics(&self) -> &[lagrange_fft::PerformanceMetrics] {
-        &self.performance_metrics
-    }
-
-    /// Get audit events for compliance reporting
-    pub fn get_audit_events(&self) -> &[SecurityEvent] {
-        self.error_handler.audit_logger.get_events()
-    }
-}
-
-/// Enterprise demonstration function
-#[allow(dead_code)]
-fn enterprise_demonstration() -> CryptoResult<()> {
-    info!("🚀 Starting Enterprise Zero-Knowledge Threshold Secret Sharing Demo");
-
-    // Initialize enterprise system
-    let config = EnterpriseConfig::default();
-    let mut crypto_system = EnterpriseCryptoSystem::new(config);
-
-    // Validate security
-    crypto_system.validate_security()?;
-
-    // Generate enterprise key
-    let key = crypto_system.generate_enterprise_key("master-key-001")?;
-    let secret = key.secret;
-
-    // Create secret shares
-    let threshold = 5;
-    let num_shares = 10;
-    let shares =
-        crypto_system.create_secret_shares(secret, threshold, num_shares, "demo-operation-001")?;
-
-    // Recover secret
-    let selected_shares: Vec<_> = shares.into_iter().take(threshold).collect();
-    let recovered_secret =
-        crypto_system.recover_secret_enterprise(&selected_shares, "demo-recovery-001")?;
-
-    // Verify correctness
-    if recovered_secret == secret {
-        info!("✅ Secret recovery successful - cryptographic correctness verified");
-    } else {
-        error!("❌ Secret recovery failed - cryptographic error detected");
-        return Err(CryptoError::CryptographicOperation {
-            operation: "secret_verification".to_string(),
-        });
-    }
-
-    // Display performance metrics
-    let metrics = crypto_system.get_performance_metrics();
-    info!("📈 Performance Summary:");
-    for metric in metrics {
-        info!(
-            "  - {}: {} ns ({} items)",
-            metric.operation_type, metric.duration_ns, metric.input_size
-        );
-    }
-
-    // Display audit events
-    let audit_events = crypto_system.get_audit_events();
-    info!("🔍 Audit Trail ({} events):", audit_events.len());
-    for event in audit_events {
-        info!("  - {:?}", event);
-    }
-
-    Ok(())
-}
-
-/// Main function - Enterprise demonstration
-fn main() -> CryptoResult<()> {
-    // 初始化日志
-    Builder::new()
-        .filter(None, LevelFilter::Info)
-        .format_timestamp_secs()
-        .init();
-
-    info!("🏁 应用启动：创新型阈值系统演示");
-
-    // 1. 初始化企业配置
-    let config = EnterpriseConfig {
-        security_level: SecurityLevel::High,
-        audit_enabled: true,
-        performance_monitoring: true,
-        compliance_mode: ComplianceMode::Standard,
-        max_key_lifetime_hours: 12,
-    };
-    let mut system = EnterpriseCryptoSystem::new(config);
-
-    // 2. 执行安全性校验
-    system.validate_security()?;
-    info!("✓ 安全性校验通过");
-
-    // 3. 生成主密钥并记录审计事件
-    let master_key = system.generate_enterprise_key("innovative-master-key")?;
-    info!("✓ 主密钥生成：{:?}", master_key);
-
-    // —— 您的“创新”流程开始 —— //
-
-    // 4. 调用 MPC 协议模拟生成多方分片
-    let (mpc_secret, mpc_shares) = mpc::mpc_generate_key_shares(4, 3, 6);
-    info!("✓ MPC 全局秘密: {:?}", mpc_secret);
-    info!("✓ MPC 生成的分片数: {}", mpc_shares.len());
-
-    // 5. 验证分片正确性（VSS 校验）
-    let all_valid = vss::verify_share_validity(&mpc_shares);
-    if !all_valid {
-        error!("✗ MPC 分片校验失败");
-        return Err(CryptoError::CryptographicOperation {
-            operation: "mpc share validity".into(),
-        });
-    }
-    info!("✓ MPC 分片均已通过验证");
-
-    // 6. 多方贡献随机数并聚合
-    let contributions: Vec<Scalar> = vec![
-        random_scalar(&mut OsRng),
-        random_scalar(&mut OsRng),
-        random_scalar(&mut OsRng),
-    ];
-    let aggregated_random = utils::distributed_random_scalar(&contributions);
-    info!("✓ 聚合随机数: {:?}", aggregated_random);
-
-    // 7. 生成基于共享秘密与聚合随机数的新的分片（创新应用示例）
-    let new_shares: Vec<ShareData> = {
-        let mut tmp = Vec::new();
-        for (_i, share) in mpc_shares.iter().enumerate() {
-            let combined = share.share + aggregated_random;
-            let random = random_scalar(&mut OsRng);
-            let commitment = RISTRETTO_BASEPOINT_POINT * combined + *ANOTHER_POINT * random;
-            let proof = generate_proof(combined, random, share.index, commitment);
-            tmp.push(ShareData {
-                index: share.index,
-                share: combined,
-                commitment,
-                random,
-                proof,
-            });
-        }
-        tmp
-    };
-    info!("✓ 基于创新逻辑生成的新分片数: {}", new_shares.len());
-
-    // 8. 并行批量恢复秘密示例
-    let batch_results =
-        lagrange_fft::recover_secrets_batch(&vec![mpc_shares.clone(), new_shares.clone()]);
-    for (i, res) in batch_results.iter().enumerate() {
-        match res {
-            Ok(secret) => info!("Batch {} 恢复成功: {:?}", i, secret),
-            Err(e) => error!("Batch {} 恢复失败: {}", i, e),
-        }
-    }
-
-    // 9. 调用阈值秘密恢复
-    let recovered = system.recover_secret_enterprise(&new_shares[..3], "innovative-recovery")?;
-    info!("✅ 创新阈值恢复结果: {:?}", recovered);
-
-    // 10. 输出性能与审计日志
-    info!("📊 性能指标:");
-    for metric in system.get_performance_metrics() {
-        info!(
-            "  - {}: {} ns ({} 项)",
-            metric.operation_type, metric.duration_ns, metric.input_size
-        );
-    }
-    info!("📝 审计事件:");
-    for event in system.get_audit_events() {
-        info!("  - {:?}", event);
-    }
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod enterprise_tests {
-    use super::*;
-
-    #[test]
-    fn test_enterprise_config() {
-        let config = EnterpriseConfig::default();
-        assert!(config.audit_enabled);
-        assert!(config.performance_monitoring);
-        assert!(matches!(config.compliance_mode, ComplianceMode::Standard));
-    }
-
-    #[test]
-    fn test_enterprise_crypto_system() {
-        let config = EnterpriseConfig::default();
-        let mut system = EnterpriseCryptoSystem::new(config);
-
-        // Test security validation
-        assert!(system.validate_security().is_ok());
-
-        // Test key generation
-        let key = system.generate_enterprise_key("test-key").unwrap();
-        assert!(matches!(key.state, KeyState::Active));
-    }
-
-    #[test]
-    fn test_share_operations() {
-        let config = EnterpriseConfig::default();
-        let mut system = EnterpriseCryptoSystem::new(config);
-
-        let secret = random_scalar(&mut OsRng);
-        let shares = system
-            .create_secret_shares(secret, 3, 5, "test-op")
-            .unwrap();
-
-        assert_eq!(shares.len(), 5);
-
-        let recovered = system
-            .recover_secret_enterprise(&shares[..3], "test-recovery")
-            .unwrap();
-        assert_eq!(recovered, secret);
-    }
-}
@@ -0,0 +1,310 @@
+//! **metrics module**
+//!
+//! Bounded retention of performance samples and their export to a [`MetricsSink`].
+//!
+//! A long-lived `EnterpriseCryptoSystem` records a [`PerformanceMetrics`] sample per operation.
+//! Kept forever they grow without bound, so the system buffers at most
+//! [`MetricsRetention::max_samples`] of them. With a sink configured the buffer is exported and
+//! emptied whenever it reaches [`MetricsRetention::flush_threshold`]; without one, or while the
+//! sink fails, the oldest samples are dropped and counted. Services that ship metrics themselves
+//! take the buffer with `drain_metrics` instead.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::events::EventCode;
+use crate::lagrange_fft::PerformanceMetrics;
+use crate::structured_log;
+use log::Level;
+use std::io::Write;
+
+/// Samples kept in memory by default.
+pub const DEFAULT_METRICS_RETENTION: usize = 10_000;
+
+/// Buffered samples that trigger an export by default.
+pub const DEFAULT_FLUSH_THRESHOLD: usize = 1_000;
+
+/// Destination of exported performance samples, e.g. a metrics pipeline.
+pub trait MetricsSink {
+    /// Name of the sink, for logs.
+    fn name(&self) -> &str;
+
+    /// Export `metrics`, oldest first.
+    ///
+    /// On error the samples stay buffered and are offered again at the next flush.
+    fn export(&mut self, metrics: &[PerformanceMetrics]) -> CryptoResult<()>;
+}
+
+/// Writes each sample as one line of `PerformanceMetrics` JSON.
+pub struct JsonLinesMetricsSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesMetricsSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// The underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> MetricsSink for JsonLinesMetricsSink<W> {
+    fn name(&self) -> &str {
+        "json-lines"
+    }
+
+    fn export(&mut self, metrics: &[PerformanceMetrics]) -> CryptoResult<()> {
+        let mut lines = Vec::new();
+        for metric in metrics {
+            serde_json::to_writer(&mut lines, metric).map_err(|e| CryptoError::Serialization {
+                details: e.to_string(),
+            })?;
+            lines.push(b'\n');
+        }
+        self.writer
+            .write_all(&lines)
+            .and_then(|()| self.writer.flush())
+            .map_err(|e| CryptoError::Serialization {
+                details: format!("metrics export failed: {}", e),
+            })
+    }
+}
+
+/// How many samples are kept and when they are exported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricsRetention {
+    /// Samples kept in memory; beyond it the oldest are dropped.
+    pub max_samples: usize,
+    /// Buffered samples that trigger an export, if a sink is configured.
+    pub flush_threshold: usize,
+}
+
+impl Default for MetricsRetention {
+    fn default() -> Self {
+        Self {
+            max_samples: DEFAULT_METRICS_RETENTION,
+            flush_threshold: DEFAULT_FLUSH_THRESHOLD,
+        }
+    }
+}
+
+impl MetricsRetention {
+    /// Returns `CryptoError::Validation` unless `0 < flush_threshold <= max_samples`.
+    pub fn validate(&self) -> CryptoResult<()> {
+        if self.max_samples == 0 {
+            return Err(CryptoError::Validation {
+                field: "max_samples".to_string(),
+                reason: "must be at least 1".to_string(),
+            });
+        }
+        if self.flush_threshold == 0 || self.flush_threshold > self.max_samples {
+            return Err(CryptoError::Validation {
+                field: "flush_threshold".to_string(),
+                reason: format!("must be between 1 and max_samples ({})", self.max_samples),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Samples awaiting export, with the retention policy and the sink.
+pub(crate) struct MetricsBuffer {
+    samples: Vec<PerformanceMetrics>,
+    retention: MetricsRetention,
+    sink: Option<Box<dyn MetricsSink + Send + Sync>>,
+    dropped: u64,
+}
+
+impl MetricsBuffer {
+    pub(crate) fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+            retention: MetricsRetention::default(),
+            sink: None,
+            dropped: 0,
+        }
+    }
+
+    pub(crate) fn samples(&self) -> &[PerformanceMetrics] {
+        &self.samples
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub(crate) fn set_retention(&mut self, retention: MetricsRetention) -> CryptoResult<()> {
+        retention.validate()?;
+        self.retention = retention;
+        self.trim();
+        Ok(())
+    }
+
+    pub(crate) fn set_sink(&mut self, sink: Box<dyn MetricsSink + Send + Sync>) {
+        self.sink = Some(sink);
+    }
+
+    /// Buffer `sample`, exporting at the flush threshold and dropping the oldest beyond the
+    /// retention limit. A failed export is logged; the samples stay buffered.
+    pub(crate) fn record(&mut self, sample: PerformanceMetrics) {
+        self.samples.push(sample);
+        if self.sink.is_some() && self.samples.len() >= self.retention.flush_threshold {
+            if let Err(e) = self.flush() {
+                structured_log!(
+                    Level::Warn,
+                    EventCode::MetricsFlushFailed,
+                    "performance metrics could not be exported",
+                    sink = self.sink.as_ref().map_or("", |s| s.name()),
+                    buffered = self.samples.len(),
+                    error = e
+                );
+            }
+        }
+        self.trim();
+    }
+
+    /// Export every buffered sample and return how many were exported.
+    ///
+    /// Returns `CryptoError::Validation` if no sink is configured, and the sink's error if the
+    /// export fails, keeping the samples.
+    pub(crate) fn flush(&mut self) -> CryptoResult<usize> {
+        let sink = self.sink.as_mut().ok_or_else(|| CryptoError::Validation {
+            field: "metrics_sink".to_string(),
+            reason: "no metrics sink is configured".to_string(),
+        })?;
+        if self.samples.is_empty() {
+            return Ok(0);
+        }
+        sink.export(&self.samples)?;
+        let exported = self.samples.len();
+        self.samples.clear();
+        structured_log!(
+            Level::Debug,
+            EventCode::MetricsFlushed,
+            "performance metrics exported",
+            sink = sink.name(),
+            samples = exported
+        );
+        Ok(exported)
+    }
+
+    /// Take every buffered sample, oldest first.
+    pub(crate) fn drain(&mut self) -> Vec<PerformanceMetrics> {
+        std::mem::take(&mut self.samples)
+    }
+
+    fn trim(&mut self) {
+        let excess = self
+            .samples
+            .len()
+            .saturating_sub(self.retention.max_samples);
+        if excess > 0 {
+            self.samples.drain(..excess);
+            self.dropped += excess as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn sample(n: usize) -> PerformanceMetrics {
+        PerformanceMetrics {
+            operation_type: "test".to_string(),
+            duration_ns: n as u64,
+            input_size: n,
+            algorithm_used: "none".to_string(),
+            context: None,
+            counts: None,
+        }
+    }
+
+    /// Sink that shares its exports with the test and can be made to fail.
+    struct SharedSink {
+        exported: Arc<Mutex<Vec<PerformanceMetrics>>>,
+        failing: Arc<Mutex<bool>>,
+    }
+
+    impl MetricsSink for SharedSink {
+        fn name(&self) -> &str {
+            "shared"
+        }
+
+        fn export(&mut self, metrics: &[PerformanceMetrics]) -> CryptoResult<()> {
+            if *self.failing.lock().unwrap() {
+                return Err(CryptoError::Serialization {
+                    details: "sink unavailable".to_string(),
+                });
+            }
+            self.exported.lock().unwrap().extend_from_slice(metrics);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_retention_and_flush() {
+        let mut buffer = MetricsBuffer::new();
+        assert!(buffer.flush().is_err());
+        assert!(buffer
+            .set_retention(MetricsRetention {
+                max_samples: 2,
+                flush_threshold: 3,
+            })
+            .is_err());
+        buffer
+            .set_retention(MetricsRetention {
+                max_samples: 4,
+                flush_threshold: 3,
+            })
+            .unwrap();
+
+        // Without a sink the oldest samples are dropped.
+        for n in 0..6 {
+            buffer.record(sample(n));
+        }
+        assert_eq!(buffer.dropped(), 2);
+        assert_eq!(buffer.samples()[0].input_size, 2);
+
+        let exported = Arc::new(Mutex::new(Vec::new()));
+        let failing = Arc::new(Mutex::new(true));
+        buffer.set_sink(Box::new(SharedSink {
+            exported: Arc::clone(&exported),
+            failing: Arc::clone(&failing),
+        }));
+        // A failing sink keeps the samples, within the retention limit.
+        buffer.record(sample(6));
+        assert_eq!(buffer.samples().len(), 4);
+        assert_eq!(buffer.dropped(), 3);
+
+        *failing.lock().unwrap() = false;
+        buffer.record(sample(7));
+        assert!(buffer.samples().is_empty());
+        let inputs: Vec<_> = exported
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| m.input_size)
+            .collect();
+        assert_eq!(inputs, [3, 4, 5, 6, 7]);
+
+        buffer.record(sample(8));
+        assert_eq!(buffer.drain().len(), 1);
+        assert_eq!(buffer.flush().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_json_lines_sink() {
+        let mut sink = JsonLinesMetricsSink::new(Vec::new());
+        sink.export(&[sample(1), sample(2)]).unwrap();
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let parsed: Vec<PerformanceMetrics> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].input_size, 2);
+    }
+}
@@ -1,8 +1,14 @@
 //! **mpc module**
 //!
 //! This module simulates a multi-party computation protocol, where multiple participants each generate polynomials and collaborate to generate secret slices.
+//!
+//! The simulation runs every participant in one process and returns the global secret, so it is
+//! only a trusted dealer in disguise. Real deployments use the message-driven Pedersen DKG in
+//! [`crate::dkg`], which can require every message to be signed by its author's
+//! [`identity`](crate::identity); the simulation exchanges no messages to sign.
 
-use crate::sharing::ShareData;
+use crate::error::{CryptoError, CryptoResult};
+use crate::sharing::{self, ShareData};
 use crate::utils;
 use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, scalar::Scalar};
 use rand::rngs::OsRng;
@@ -20,19 +26,29 @@ use crate::utils::ANOTHER_POINT;
 /// - `threshold`: Minimum number of slices required for secret recovery.
 /// # Return values.
 ///
-/// Returns the set of global secrets and generated slices.
+/// Returns the set of global secrets and generated slices, `CryptoError::Validation` if there are
+/// no participants or unless `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if any
+/// participant's random generator fails.
+#[deprecated(note = "single-process simulation; use `dkg::DkgParticipant` for real key generation")]
 pub fn mpc_generate_key_shares(
     parties: usize,
     threshold: usize,
     n: usize,
-) -> (Scalar, Vec<ShareData>) {
+) -> CryptoResult<(Scalar, Vec<ShareData>)> {
+    if parties == 0 {
+        return Err(CryptoError::Validation {
+            field: "parties".to_string(),
+            reason: "at least one participant is required".to_string(),
+        });
+    }
+    sharing::validate_parameters(threshold, n)?;
     let mut global_secret = Scalar::ZERO;
     let mut party_polynomials: Vec<Vec<Scalar>> = Vec::new();
     let mut rng = OsRng;
     for _ in 0..parties {
         let mut poly = Vec::with_capacity(threshold);
         for _ in 0..threshold {
-            let coeff = utils::random_scalar(&mut rng);
+            let coeff = utils::random_scalar(&mut rng)?;
             poly.push(coeff);
         }
         global_secret += poly[0];
@@ -52,19 +68,19 @@ pub fn mpc_generate_key_shares(
                 aggregated_share += value;
             }
             let mut local_rng = OsRng;
-            let aggregated_random = utils::random_scalar(&mut local_rng);
+            let aggregated_random = utils::random_scalar(&mut local_rng)?;
             let commitment = RISTRETTO_BASEPOINT_POINT * aggregated_share
                 + (*ANOTHER_POINT)  * aggregated_random;
             let proof =
-                crate::proof::generate_proof(aggregated_share, aggregated_random, i, commitment);
-            ShareData {
+                crate::proof::generate_proof(aggregated_share, aggregated_random, i, commitment)?;
+            Ok(ShareData {
                 index: i,
                 share: aggregated_share,
                 commitment,
                 random: aggregated_random,
                 proof,
-            }
+            })
         })
-        .collect();
-    (global_secret, shares)
+        .collect::<CryptoResult<_>>()?;
+    Ok((global_secret, shares))
 }
@@ -0,0 +1,487 @@
+//! **nonblocking module**
+//!
+//! `async` forms of the heavy operations for servers running on an async executor (`async`
+//! feature).
+//!
+//! Dealing a few thousand slices or verifying a large batch keeps a thread busy for hundreds of
+//! milliseconds; called directly from a task it stalls every other task of that executor thread.
+//! Each function here moves the work to the rayon pool and returns a future that completes when
+//! the work is done, so the calling task yields instead of blocking. The futures do not depend on
+//! a particular runtime: they work under tokio, async-std or a hand-written executor alike.
+//!
+//! The work runs whether or not the future is polled, and dropping the future does not cancel it;
+//! its result is then discarded. A panic in the work is resumed in the task awaiting it.
+//!
+//! The [`EnterpriseCryptoSystem`] operations take the system behind an `Arc<Mutex<_>>`, the form a
+//! service shares it in. They run on [`ENTERPRISE_WORKERS`] dedicated threads rather than on the
+//! pool: the operation holds the lock while its own rayon work runs, and a pool worker waiting in
+//! that work can be handed a second queued job that blocks on the same lock, deadlocking the pool.
+//! At most [`ENTERPRISE_QUEUE_DEPTH`] operations wait for those threads; further calls fail fast
+//! rather than piling up threads. DKG message processing stays on the calling task, since the
+//! participant is borrowed, and instead yields to the executor after every
+//! [`DKG_MESSAGES_PER_YIELD`] messages.
+
+use crate::dkg::{Complaint, DkgParticipant, Round1Broadcast, Round2Share};
+use crate::error::{CryptoError, CryptoResult, VerifyError};
+use crate::identity::Signed;
+use crate::lagrange_fft::{self, LagrangeResult};
+use crate::sharing::{self, ShareData};
+use crate::vss::{self, VssCommitments};
+use crate::{EnterpriseCryptoSystem, OperationContext};
+use curve25519_dalek::scalar::Scalar;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// DKG messages verified between two yields to the executor.
+///
+/// A round-two slice costs `t` point multiplications against the sender's commitments, a few
+/// milliseconds at the 100-party scale of [`limits()`](crate::limits()).
+pub const DKG_MESSAGES_PER_YIELD: usize = 4;
+
+type Outcome<T> = std::thread::Result<T>;
+
+struct Slot<T> {
+    outcome: Option<Outcome<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future of work running on the rayon pool.
+pub struct Offloaded<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+/// Start `work` on the rayon pool.
+///
+/// `work` must not block on anything another pool job can hold, such as a lock; use
+/// [`offload_dedicated`] for that.
+pub fn offload<T, F>(work: F) -> Offloaded<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (future, job) = completion(work);
+    rayon::spawn(job);
+    future
+}
+
+/// Threads running the [`EnterpriseCryptoSystem`] operations, outside the rayon pool.
+pub const ENTERPRISE_WORKERS: usize = 2;
+
+/// Operations waiting for a free [`ENTERPRISE_WORKERS`] thread; a call finding the queue full
+/// fails with `CryptoError::ResourceExhaustion` instead of starting another thread.
+pub const ENTERPRISE_QUEUE_DEPTH: usize = 64;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed set of threads fed from a bounded queue.
+struct DedicatedPool {
+    queue: SyncSender<Job>,
+    depth: usize,
+}
+
+impl DedicatedPool {
+    fn new(name: &str, workers: usize, depth: usize) -> Self {
+        let (queue, jobs) = mpsc::sync_channel::<Job>(depth);
+        let jobs = Arc::new(Mutex::new(jobs));
+        for i in 0..workers {
+            let jobs = Arc::clone(&jobs);
+            thread::Builder::new()
+                .name(format!("{}-{}", name, i))
+                .spawn(move || loop {
+                    let job = jobs.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                })
+                .expect("failed to spawn a dedicated pool thread");
+        }
+        Self { queue, depth }
+    }
+
+    fn offload<T, F>(&self, work: F) -> CryptoResult<Offloaded<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (future, job) = completion(work);
+        match self.queue.try_send(Box::new(job)) {
+            Ok(()) => Ok(future),
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                Err(CryptoError::ResourceExhaustion {
+                    resource: format!("dedicated thread queue ({} operations waiting)", self.depth),
+                })
+            }
+        }
+    }
+}
+
+static ENTERPRISE_POOL: LazyLock<DedicatedPool> = LazyLock::new(|| {
+    DedicatedPool::new("zkt-enterprise", ENTERPRISE_WORKERS, ENTERPRISE_QUEUE_DEPTH)
+});
+
+/// Start `work` on the [`ENTERPRISE_WORKERS`] threads outside the rayon pool; its own rayon work
+/// still runs on the pool.
+///
+/// Returns `CryptoError::ResourceExhaustion` if [`ENTERPRISE_QUEUE_DEPTH`] operations are already
+/// waiting; `work` is then not run.
+pub fn offload_dedicated<T, F>(work: F) -> CryptoResult<Offloaded<T>>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    ENTERPRISE_POOL.offload(work)
+}
+
+/// The future of `work` and the job that runs it and completes the future.
+fn completion<T, F>(work: F) -> (Offloaded<T>, impl FnOnce() + Send + 'static)
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(Slot {
+        outcome: None,
+        waker: None,
+    }));
+    let completion = Arc::clone(&slot);
+    let job = move || {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(work));
+        let mut slot = completion.lock().unwrap_or_else(|e| e.into_inner());
+        slot.outcome = Some(outcome);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    };
+    (Offloaded { slot }, job)
+}
+
+impl<T> Future for Offloaded<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.outcome.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// [`sharing::generate_key_shares`] on the rayon pool.
+pub async fn generate_key_shares(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<Vec<ShareData>> {
+    offload(move || sharing::generate_key_shares(secret, threshold, n)).await
+}
+
+/// [`sharing::generate_key_shares_with_commitments`] on the rayon pool.
+pub async fn generate_key_shares_with_commitments(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<(Vec<ShareData>, VssCommitments)> {
+    offload(move || sharing::generate_key_shares_with_commitments(secret, threshold, n)).await
+}
+
+/// [`lagrange_fft::recover_secrets_batch`] on the rayon pool.
+pub async fn recover_secrets_batch(
+    shares_batch: Vec<Vec<ShareData>>,
+) -> Vec<LagrangeResult<Scalar>> {
+    offload(move || lagrange_fft::recover_secrets_batch(&shares_batch)).await
+}
+
+/// [`vss::verify_share_validity_batch`] on the rayon pool.
+pub async fn verify_share_validity_batch(shares: Vec<ShareData>) -> Result<(), VerifyError> {
+    offload(move || vss::verify_share_validity_batch(&shares)).await
+}
+
+/// [`EnterpriseCryptoSystem::create_secret_shares`] on the dedicated threads, holding the lock.
+///
+/// Returns `CryptoError::ResourceExhaustion` if the queue of [`offload_dedicated`] is full.
+pub async fn create_secret_shares(
+    system: Arc<Mutex<EnterpriseCryptoSystem>>,
+    secret: Scalar,
+    threshold: usize,
+    num_shares: usize,
+    context: OperationContext,
+) -> CryptoResult<Vec<ShareData>> {
+    offload_dedicated(move || {
+        let mut system = system.lock().unwrap_or_else(|e| e.into_inner());
+        system.create_secret_shares(secret, threshold, num_shares, &context)
+    })?
+    .await
+}
+
+/// [`EnterpriseCryptoSystem::recover_secret_enterprise`] on the dedicated threads, holding the
+/// lock.
+///
+/// Returns `CryptoError::ResourceExhaustion` if the queue of [`offload_dedicated`] is full.
+pub async fn recover_secret_enterprise(
+    system: Arc<Mutex<EnterpriseCryptoSystem>>,
+    shares: Vec<ShareData>,
+    context: OperationContext,
+) -> CryptoResult<Scalar> {
+    offload_dedicated(move || {
+        let mut system = system.lock().unwrap_or_else(|e| e.into_inner());
+        system.recover_secret_enterprise(&shares, &context)
+    })?
+    .await
+}
+
+/// [`DkgParticipant::receive_round1`] for each of the signed `broadcasts`, yielding between
+/// batches.
+///
+/// Returns one result per broadcast, in order; a rejected broadcast does not stop the others.
+pub async fn receive_round1(
+    participant: &mut DkgParticipant,
+    broadcasts: Vec<Signed<Round1Broadcast>>,
+) -> Vec<CryptoResult<()>> {
+    let mut results = Vec::with_capacity(broadcasts.len());
+    for (i, broadcast) in broadcasts.into_iter().enumerate() {
+        if i > 0 && i % DKG_MESSAGES_PER_YIELD == 0 {
+            YieldNow(false).await;
+        }
+        results.push(participant.receive_round1(broadcast));
+    }
+    results
+}
+
+/// [`DkgParticipant::receive_round2`] for each of the signed `shares`, yielding between batches.
+///
+/// Returns one result per slice, in order, holding the complaint to broadcast for a slice that does
+/// not match its sender's commitments.
+pub async fn receive_round2(
+    participant: &mut DkgParticipant,
+    shares: Vec<Signed<Round2Share>>,
+) -> Vec<CryptoResult<Option<Complaint>>> {
+    let mut results = Vec::with_capacity(shares.len());
+    for (i, share) in shares.into_iter().enumerate() {
+        if i > 0 && i % DKG_MESSAGES_PER_YIELD == 0 {
+            YieldNow(false).await;
+        }
+        results.push(participant.receive_round2(share));
+    }
+    results
+}
+
+/// Pending once, waking its task immediately, so the executor can run other tasks.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::{Participant, Roster};
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::thread::{self, Thread};
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Minimal executor: poll on the current thread, parking until woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_offloaded_operations() {
+        let secret = Scalar::from(42u64);
+        let (shares, commitments) =
+            block_on(generate_key_shares_with_commitments(secret, 3, 5)).unwrap();
+        assert!(shares.iter().all(|s| commitments.verify_share(s).is_ok()));
+        block_on(verify_share_validity_batch(shares.clone())).unwrap();
+
+        let batch = vec![shares[..3].to_vec(), shares[2..].to_vec()];
+        let secrets = block_on(recover_secrets_batch(batch));
+        assert!(secrets.iter().all(|s| *s.as_ref().unwrap() == secret));
+
+        assert!(block_on(generate_key_shares(secret, 0, 3)).is_err());
+        let panicked = panic::catch_unwind(|| block_on(offload(|| panic!("in the pool"))));
+        assert!(panicked.is_err());
+    }
+
+    #[test]
+    fn test_enterprise_operations() {
+        let system = Arc::new(Mutex::new(EnterpriseCryptoSystem::new(
+            crate::EnterpriseConfig::default(),
+        )));
+        let secret = Scalar::from(7u64);
+        let context = OperationContext::new("async-key", 0, "async-deal");
+        let shares = block_on(create_secret_shares(
+            Arc::clone(&system),
+            secret,
+            3,
+            5,
+            context,
+        ))
+        .unwrap();
+        let context = OperationContext::new("async-key", 0, "async-recover");
+        let recovered = block_on(recover_secret_enterprise(
+            system,
+            shares[1..4].to_vec(),
+            context,
+        ))
+        .unwrap();
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn test_concurrent_enterprise_operations() {
+        // More concurrent calls than pool workers, each holding the lock through rayon work, but no
+        // more than the dedicated queue takes.
+        let system = Arc::new(Mutex::new(EnterpriseCryptoSystem::new(
+            crate::EnterpriseConfig::default(),
+        )));
+        let secret = Scalar::from(11u64);
+        let callers: Vec<_> = (0..(2 * rayon::current_num_threads() + 2)
+            .min(ENTERPRISE_QUEUE_DEPTH))
+            .map(|i| {
+                let system = Arc::clone(&system);
+                thread::spawn(move || {
+                    let key_id = format!("async-key-{}", i);
+                    let context = OperationContext::new(&key_id, 0, "async-deal");
+                    let shares = block_on(create_secret_shares(
+                        Arc::clone(&system),
+                        secret,
+                        3,
+                        64,
+                        context,
+                    ))
+                    .unwrap();
+                    let context = OperationContext::new(&key_id, 0, "async-recover");
+                    block_on(recover_secret_enterprise(
+                        system,
+                        shares[..3].to_vec(),
+                        context,
+                    ))
+                    .unwrap()
+                })
+            })
+            .collect();
+        for caller in callers {
+            assert_eq!(caller.join().unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn test_dedicated_pool_is_bounded() {
+        let pool = DedicatedPool::new("zkt-test", 1, 2);
+        let (started, running) = mpsc::channel();
+        let (release, gate) = mpsc::channel::<()>();
+        let first = pool
+            .offload(move || {
+                started.send(()).unwrap();
+                gate.recv().unwrap();
+                0
+            })
+            .unwrap();
+        running.recv().unwrap();
+        // The only worker is busy: two jobs fill the queue and a third is refused.
+        let queued: Vec<_> = (1..=2).map(|i| pool.offload(move || i).unwrap()).collect();
+        assert!(matches!(
+            pool.offload(|| 3),
+            Err(CryptoError::ResourceExhaustion { .. })
+        ));
+        release.send(()).unwrap();
+        assert_eq!(block_on(first), 0);
+        for (i, job) in queued.into_iter().enumerate() {
+            assert_eq!(block_on(job), i + 1);
+        }
+        assert_eq!(block_on(pool.offload(|| 4).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_dkg_rounds_yield() {
+        let n = 9;
+        let identities: Vec<Participant> = (1..=n)
+            .map(|i| Participant::from_seed(i, &[i as u8; 32]).unwrap())
+            .collect();
+        let roster =
+            Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>()).unwrap();
+        let mut parties: Vec<DkgParticipant> = (1..=n)
+            .map(|i| DkgParticipant::new(i, 5, n, "async-dkg", roster.clone()).unwrap())
+            .collect();
+        let broadcasts: Vec<Signed<Round1Broadcast>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .map(|(p, id)| id.sign("async-dkg", p.round1().unwrap()).unwrap())
+            .collect();
+        for p in parties.iter_mut() {
+            let results = block_on(receive_round1(p, broadcasts.clone()));
+            assert!(results.iter().all(|r| r.is_ok()));
+        }
+        let messages: Vec<Signed<Round2Share>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .flat_map(|(p, id)| {
+                p.round2()
+                    .unwrap()
+                    .into_iter()
+                    .map(|m| id.sign("async-dkg", m).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for p in parties.iter_mut() {
+            let index = p.index();
+            let inbox: Vec<Signed<Round2Share>> = messages
+                .iter()
+                .filter(|m| m.payload.recipient == index)
+                .cloned()
+                .collect();
+            let mut polls = 0;
+            let mut future = std::pin::pin!(receive_round2(p, inbox));
+            let waker = Waker::from(Arc::new(Unpark(thread::current())));
+            let results = loop {
+                polls += 1;
+                if let Poll::Ready(results) = future.as_mut().poll(&mut Context::from_waker(&waker))
+                {
+                    break results;
+                }
+            };
+            assert_eq!(polls, (n - 1).div_ceil(DKG_MESSAGES_PER_YIELD));
+            assert!(results.iter().all(|r| matches!(r, Ok(None))));
+        }
+        let keys: Vec<_> = parties
+            .iter_mut()
+            .map(|p| p.finalize().unwrap().group_public_key)
+            .collect();
+        assert!(keys.iter().all(|k| *k == keys[0]));
+    }
+}
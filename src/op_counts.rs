@@ -0,0 +1,118 @@
+//! **op_counts module**
+//!
+//! Runtime counts of the expensive primitives (feature `op-counters`), attached to every
+//! [`PerformanceMetrics`](crate::lagrange_fft::PerformanceMetrics) sample.
+//!
+//! Timings alone do not tell a slow machine from a misconfigured one. The counts of an operation
+//! depend only on its inputs, so a production sample whose counts match the benchmark baseline
+//! but whose duration does not points at the deployment (parallelism disabled, a debug build),
+//! while diverging counts point at a different algorithm being selected.
+//!
+//! What is counted:
+//!
+//! - `scalar_muls`: scalar-field multiplications in polynomial arithmetic, evaluation,
+//!   interpolation and proof responses;
+//! - `point_muls`: scalar·point products; a multiscalar multiplication of `n` terms counts `n`;
+//! - `inversions`: scalar-field inversions, a batch inversion counting one;
+//! - `hash_bytes`: bytes absorbed by [`Blake3Adapter`](crate::hash_adapter::Blake3Adapter).
+//!
+//! The counters are process-wide and shared by all threads, so rayon workers are included, but
+//! operations running concurrently are counted into each other's samples. Without the feature
+//! the counting calls compile to nothing and samples carry no counts.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "op-counters")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Primitive counted by [`count`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Op {
+    ScalarMul,
+    PointMul,
+    Inversion,
+    HashByte,
+}
+
+#[cfg(feature = "op-counters")]
+static COUNTERS: [AtomicU64; 4] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/// Add `n` occurrences of `op`.
+#[inline]
+pub(crate) fn count(op: Op, n: usize) {
+    #[cfg(feature = "op-counters")]
+    COUNTERS[op as usize].fetch_add(n as u64, Ordering::Relaxed);
+    #[cfg(not(feature = "op-counters"))]
+    let _ = (op, n);
+}
+
+/// Primitive counts, cumulative or over one operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationCounts {
+    pub scalar_muls: u64,
+    pub point_muls: u64,
+    pub inversions: u64,
+    pub hash_bytes: u64,
+    /// Threads of the rayon pool when the counts were taken; 1 without the `dealer` feature.
+    pub worker_threads: u64,
+}
+
+impl OperationCounts {
+    /// Counts accumulated by the process so far, or `None` without the `op-counters` feature.
+    pub fn current() -> Option<Self> {
+        #[cfg(feature = "op-counters")]
+        {
+            let load = |op: Op| COUNTERS[op as usize].load(Ordering::Relaxed);
+            #[cfg(feature = "dealer")]
+            let worker_threads = rayon::current_num_threads() as u64;
+            #[cfg(not(feature = "dealer"))]
+            let worker_threads = 1;
+            Some(Self {
+                scalar_muls: load(Op::ScalarMul),
+                point_muls: load(Op::PointMul),
+                inversions: load(Op::Inversion),
+                hash_bytes: load(Op::HashByte),
+                worker_threads,
+            })
+        }
+        #[cfg(not(feature = "op-counters"))]
+        None
+    }
+
+    /// Counts between `earlier` and `self`, keeping the thread count of `self`.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            scalar_muls: self.scalar_muls.saturating_sub(earlier.scalar_muls),
+            point_muls: self.point_muls.saturating_sub(earlier.point_muls),
+            inversions: self.inversions.saturating_sub(earlier.inversions),
+            hash_bytes: self.hash_bytes.saturating_sub(earlier.hash_bytes),
+            worker_threads: self.worker_threads,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "op-counters", feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_counts_follow_the_work() {
+        let before = OperationCounts::current().unwrap();
+        let shares = generate_key_shares(Scalar::from(3u64), 3, 5).unwrap();
+        crate::vss::verify_share_validity(&shares).unwrap();
+        let counts = OperationCounts::current().unwrap().since(&before);
+
+        // Tests run concurrently, so only lower bounds hold: two commitments, one proof
+        // commitment and three verification products per slice.
+        assert!(counts.point_muls >= 5 * 6);
+        assert!(counts.scalar_muls >= 5 * 3);
+        assert!(counts.hash_bytes > 0);
+        assert!(counts.worker_threads >= 1);
+    }
+}
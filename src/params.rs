@@ -0,0 +1,78 @@
+//! **params module**
+//!
+//! [`PublicParameters`]: the group, generators, hash and security level a deployment runs with.
+//!
+//! Two participants can only exchange slices, proofs or DKG messages meaningfully if they agree on
+//! all of these. The transport handshake ([`crate::transport`]) sends the
+//! [`PublicParameters::digest`] of each side and fails hard on a mismatch, so that once several
+//! backends exist a slice dealt on one curve or at one security level is never interpreted under
+//! another.
+
+use crate::hash_adapter::SecurityLevel;
+use crate::serialization::Ciphersuite;
+use crate::utils::ANOTHER_POINT;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+const PARAMETERS_CONTEXT: &str = "zk-thresh-pro 2024 public parameters v1";
+
+/// Public parameters both ends of a protocol run must share.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicParameters {
+    pub ciphersuite: Ciphersuite,
+    pub security_level: SecurityLevel,
+}
+
+impl PublicParameters {
+    pub fn new(ciphersuite: Ciphersuite, security_level: SecurityLevel) -> Self {
+        Self {
+            ciphersuite,
+            security_level,
+        }
+    }
+
+    /// Digest over the ciphersuite identifier, the security level and the compressed
+    /// generators `G` and `H` of the suite.
+    pub fn digest(&self) -> [u8; 32] {
+        let level: u8 = match self.security_level {
+            SecurityLevel::Standard => 0,
+            SecurityLevel::High => 1,
+            SecurityLevel::Maximum => 2,
+        };
+        let mut hasher = crate::hash_adapter::Blake3Adapter::new_derive_key(PARAMETERS_CONTEXT);
+        digest::Update::update(&mut hasher, &self.ciphersuite.id().to_le_bytes());
+        digest::Update::update(&mut hasher, &[level]);
+        match self.ciphersuite {
+            Ciphersuite::Ristretto255Blake3 => {
+                digest::Update::update(
+                    &mut hasher,
+                    RISTRETTO_BASEPOINT_POINT.compress().as_bytes(),
+                );
+                digest::Update::update(&mut hasher, ANOTHER_POINT.compress().as_bytes());
+            }
+        }
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+impl Default for PublicParameters {
+    /// ristretto255 with BLAKE3 at [`SecurityLevel::High`], the crate's default configuration.
+    fn default() -> Self {
+        Self::new(Ciphersuite::Ristretto255Blake3, SecurityLevel::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_separates_security_levels() {
+        let high = PublicParameters::default();
+        let maximum =
+            PublicParameters::new(Ciphersuite::Ristretto255Blake3, SecurityLevel::Maximum);
+        assert_eq!(high.digest(), PublicParameters::default().digest());
+        assert_ne!(high.digest(), maximum.digest());
+    }
+}
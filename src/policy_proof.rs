@@ -0,0 +1,495 @@
+//! **policy_proof module**
+//!
+//! Zero-knowledge proof that a recovery used at least `threshold` distinct authorized slices of
+//! the registered committee, without revealing which custodians took part.
+//!
+//! The committee is the list of live slices one dealing or refresh issued: index, Pedersen
+//! commitment `C_i = s_i·G + r_i·H` and, when known, the custodian holding the slice. Every slice
+//! used by the recovery contributes a [`ContributionProof`]: a one-out-of-many proof (an OR
+//! composition of Schnorr proofs, Cramer–Damgård–Schoenmakers) that its author knows the opening
+//! `(s, r)` of one committee commitment, together with the tag `T = s·J` for a generator `J`
+//! derived from the key and the recovery operation. The opening of a commitment is unique, so a
+//! slice has exactly one tag per recovery, and [`PolicyComplianceProof::verify`] counts distinct
+//! tags as distinct committee slices. Tags of different recoveries use different generators and
+//! cannot be linked to each other or to a commitment without the slices.
+//!
+//! Every contribution is bound to the whole statement (key, recovery and issuing operations,
+//! threshold and committee, including the custodian identities), so it cannot be replayed into
+//! another report. Custodians can contribute themselves with
+//! [`PolicyComplianceProof::contribute`], so that their slice never leaves them; each
+//! contribution costs about `5n` scalar multiplications to make and to check for a committee of
+//! `n` slices.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::op_counts::{count, Op};
+#[cfg(feature = "dealer")]
+use crate::sharing::ShareData;
+use crate::utils::ANOTHER_POINT;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+use curve25519_dalek::{RistrettoPoint, Scalar};
+#[cfg(feature = "dealer")]
+use zeroize::Zeroize;
+
+/// Version of the [`PolicyComplianceProof`] format.
+pub const POLICY_PROOF_VERSION: u8 = 1;
+
+/// Algorithms every [`PolicyComplianceProof`] of [`POLICY_PROOF_VERSION`] is produced with.
+pub const POLICY_PROOF_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: None,
+    aead: None,
+    kdf: None,
+    proof: Some(AlgorithmId::RingPedersenOpening),
+};
+
+const STATEMENT_CONTEXT: &str = "zk-thresh-pro 2024 policy proof statement v1";
+const TAG_BASE_CONTEXT: &str = "zk-thresh-pro 2024 policy proof tag base v1";
+const CHALLENGE_CONTEXT: &str = "zk-thresh-pro 2024 policy proof challenge v1";
+
+/// One slice of the committee a recovery is proved against.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommitteeMember {
+    pub index: usize,
+    /// Custodian holding the slice, from its last attestation, if any.
+    pub custodian_id: Option<String>,
+    /// Pedersen commitment of the slice.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+}
+
+/// Proof that one committee slice contributed to a recovery, without saying which.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContributionProof {
+    /// `T = s·J` for the value `s` of the slice; the same slice always has the same tag.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub tag: RistrettoPoint,
+    /// One challenge per committee member, summing to the Fiat–Shamir challenge.
+    #[serde(with = "crate::serialization::serialize_scalar_vec_helpers")]
+    challenges: Vec<Scalar>,
+    /// One response for the slice value per committee member.
+    #[serde(with = "crate::serialization::serialize_scalar_vec_helpers")]
+    share_responses: Vec<Scalar>,
+    /// One response for the blinding random per committee member.
+    #[serde(with = "crate::serialization::serialize_scalar_vec_helpers")]
+    random_responses: Vec<Scalar>,
+}
+
+/// Report that a recovery met the threshold policy of its committee.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyComplianceProof {
+    /// Format version, [`POLICY_PROOF_VERSION`].
+    pub version: u8,
+    /// Algorithms the proof was produced with, [`POLICY_PROOF_ALGORITHMS`].
+    pub algorithms: AlgorithmSuite,
+    pub key_id: String,
+    /// Operation id of the recovery reported on.
+    pub recovery_operation_id: String,
+    /// Operation that issued the committee's slices.
+    pub committee_operation_id: String,
+    /// Distinct committee slices the recovery must have used.
+    pub threshold: usize,
+    pub committee: Vec<CommitteeMember>,
+    /// One contribution per slice used.
+    pub contributions: Vec<ContributionProof>,
+}
+
+/// What every contribution of a proof is bound to.
+struct Statement {
+    digest: [u8; 32],
+    tag_base: RistrettoPoint,
+    commitments: Vec<RistrettoPoint>,
+}
+
+impl Statement {
+    fn challenge(
+        &self,
+        tag: &RistrettoPoint,
+        announcements: &[(RistrettoPoint, RistrettoPoint)],
+    ) -> Scalar {
+        let mut hasher = Blake3Adapter::new_derive_key(CHALLENGE_CONTEXT);
+        digest::Update::update(&mut hasher, &self.digest);
+        digest::Update::update(&mut hasher, tag.compress().as_bytes());
+        for (commitment_part, tag_part) in announcements {
+            digest::Update::update(&mut hasher, commitment_part.compress().as_bytes());
+            digest::Update::update(&mut hasher, tag_part.compress().as_bytes());
+        }
+        Scalar::from_hash(hasher)
+    }
+
+    /// `(z_s·G + z_r·H − c·C_j, z_s·J − c·T)` for member `j`.
+    fn announcement(
+        &self,
+        j: usize,
+        tag: &RistrettoPoint,
+        challenge: Scalar,
+        share_response: Scalar,
+        random_response: Scalar,
+    ) -> (RistrettoPoint, RistrettoPoint) {
+        count(Op::PointMul, 5);
+        (
+            RistrettoPoint::vartime_multiscalar_mul(
+                [share_response, random_response, -challenge],
+                [
+                    RISTRETTO_BASEPOINT_POINT,
+                    *ANOTHER_POINT,
+                    self.commitments[j],
+                ],
+            ),
+            RistrettoPoint::vartime_multiscalar_mul(
+                [share_response, -challenge],
+                [self.tag_base, *tag],
+            ),
+        )
+    }
+}
+
+fn absorb_str(hasher: &mut Blake3Adapter, value: &str) {
+    digest::Update::update(hasher, &(value.len() as u64).to_le_bytes());
+    digest::Update::update(hasher, value.as_bytes());
+}
+
+impl PolicyComplianceProof {
+    /// A proof without contributions for the recovery `recovery_operation_id` of `key_id`, from a
+    /// committee issued by `committee_operation_id`.
+    ///
+    /// Returns `CryptoError::Validation` unless `1 <= threshold <= committee.len()` and the
+    /// committee indices are non-zero and distinct.
+    pub fn new(
+        key_id: &str,
+        recovery_operation_id: &str,
+        committee_operation_id: &str,
+        threshold: usize,
+        committee: Vec<CommitteeMember>,
+    ) -> CryptoResult<Self> {
+        let proof = PolicyComplianceProof {
+            version: POLICY_PROOF_VERSION,
+            algorithms: POLICY_PROOF_ALGORITHMS,
+            key_id: key_id.to_string(),
+            recovery_operation_id: recovery_operation_id.to_string(),
+            committee_operation_id: committee_operation_id.to_string(),
+            threshold,
+            committee,
+            contributions: Vec::new(),
+        };
+        proof.validate_committee()?;
+        Ok(proof)
+    }
+
+    fn validate_committee(&self) -> CryptoResult<()> {
+        if self.threshold == 0 || self.threshold > self.committee.len() {
+            return Err(CryptoError::Validation {
+                field: "threshold".to_string(),
+                reason: format!(
+                    "threshold {} with a committee of {}",
+                    self.threshold,
+                    self.committee.len()
+                ),
+            });
+        }
+        let mut seen = std::collections::HashSet::new();
+        if let Some(member) = self
+            .committee
+            .iter()
+            .find(|m| m.index == 0 || !seen.insert(m.index))
+        {
+            return Err(CryptoError::Validation {
+                field: "committee".to_string(),
+                reason: format!("index {} is zero or repeated", member.index),
+            });
+        }
+        Ok(())
+    }
+
+    fn statement(&self) -> Statement {
+        let mut hasher = Blake3Adapter::new_derive_key(STATEMENT_CONTEXT);
+        digest::Update::update(&mut hasher, &[self.version]);
+        absorb_str(&mut hasher, &self.key_id);
+        absorb_str(&mut hasher, &self.recovery_operation_id);
+        absorb_str(&mut hasher, &self.committee_operation_id);
+        digest::Update::update(&mut hasher, &(self.threshold as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &(self.committee.len() as u64).to_le_bytes());
+        for member in &self.committee {
+            digest::Update::update(&mut hasher, &(member.index as u64).to_le_bytes());
+            match &member.custodian_id {
+                Some(custodian_id) => {
+                    digest::Update::update(&mut hasher, &[1]);
+                    absorb_str(&mut hasher, custodian_id);
+                }
+                None => digest::Update::update(&mut hasher, &[0]),
+            }
+            digest::Update::update(&mut hasher, member.commitment.compress().as_bytes());
+        }
+        let mut digest = [0u8; 32];
+        hasher.finalize_extended(&mut digest);
+
+        let mut hasher = Blake3Adapter::new_derive_key(TAG_BASE_CONTEXT);
+        absorb_str(&mut hasher, &self.key_id);
+        absorb_str(&mut hasher, &self.recovery_operation_id);
+        let mut uniform = [0u8; 64];
+        hasher.finalize_extended(&mut uniform);
+
+        Statement {
+            digest,
+            tag_base: RistrettoPoint::from_uniform_bytes(&uniform),
+            commitments: self.committee.iter().map(|m| m.commitment).collect(),
+        }
+    }
+
+    /// Add the contribution of `share`, a committee slice used by the recovery.
+    ///
+    /// Returns `CryptoError::Validation` if `share` is not a committee slice or does not open its
+    /// commitment, `CryptoError::SecurityViolation` if it already contributed, and
+    /// `CryptoError::InsufficientEntropy` if the nonces cannot be drawn.
+    #[cfg(feature = "dealer")]
+    pub fn contribute(&mut self, share: &ShareData) -> CryptoResult<()> {
+        use crate::utils::random_scalar;
+        let position = self
+            .committee
+            .iter()
+            .position(|m| m.index == share.index && m.commitment == share.commitment)
+            .ok_or_else(|| CryptoError::Validation {
+                field: "share".to_string(),
+                reason: format!("slice {} is not a committee slice", share.index),
+            })?;
+        if crate::utils::pedersen_commit(&share.share, &share.random) != share.commitment {
+            return Err(CryptoError::Validation {
+                field: "share".to_string(),
+                reason: format!("slice {} does not open its commitment", share.index),
+            });
+        }
+        let statement = self.statement();
+        count(Op::PointMul, 1);
+        let tag = statement.tag_base * share.share;
+        if self.contributions.iter().any(|c| c.tag == tag) {
+            return Err(duplicate_contribution());
+        }
+
+        let n = self.committee.len();
+        let mut rng = crate::utils::new_rng();
+        let mut challenges = vec![Scalar::ZERO; n];
+        let mut share_responses = vec![Scalar::ZERO; n];
+        let mut random_responses = vec![Scalar::ZERO; n];
+        let mut announcements = Vec::with_capacity(n);
+        let mut share_nonce = random_scalar(&mut rng)?;
+        let mut random_nonce = random_scalar(&mut rng)?;
+        for j in 0..n {
+            if j == position {
+                count(Op::PointMul, 1);
+                announcements.push((
+                    crate::utils::pedersen_commit(&share_nonce, &random_nonce),
+                    statement.tag_base * share_nonce,
+                ));
+                continue;
+            }
+            challenges[j] = random_scalar(&mut rng)?;
+            share_responses[j] = random_scalar(&mut rng)?;
+            random_responses[j] = random_scalar(&mut rng)?;
+            announcements.push(statement.announcement(
+                j,
+                &tag,
+                challenges[j],
+                share_responses[j],
+                random_responses[j],
+            ));
+        }
+        let challenge = statement.challenge(&tag, &announcements);
+        let own = challenge - challenges.iter().sum::<Scalar>();
+        challenges[position] = own;
+        count(Op::ScalarMul, 2);
+        share_responses[position] = share_nonce + own * share.share;
+        random_responses[position] = random_nonce + own * share.random;
+        share_nonce.zeroize();
+        random_nonce.zeroize();
+
+        self.contributions.push(ContributionProof {
+            tag,
+            challenges,
+            share_responses,
+            random_responses,
+        });
+        Ok(())
+    }
+
+    /// The number of distinct committee slices the contributions prove.
+    ///
+    /// Returns `CryptoError::Serialization` for another version or algorithm suite,
+    /// `CryptoError::Validation` for a malformed committee or contribution, and
+    /// `CryptoError::SecurityViolation` if a contribution does not verify or two contributions
+    /// come from the same slice.
+    pub fn distinct_contributions(&self) -> CryptoResult<usize> {
+        if self.version != POLICY_PROOF_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported policy proof version {}", self.version),
+            });
+        }
+        self.algorithms
+            .require(&POLICY_PROOF_ALGORITHMS, "policy compliance proof")?;
+        self.validate_committee()?;
+        let statement = self.statement();
+        let n = self.committee.len();
+        let mut tags = std::collections::HashSet::new();
+        for (position, contribution) in self.contributions.iter().enumerate() {
+            if contribution.challenges.len() != n
+                || contribution.share_responses.len() != n
+                || contribution.random_responses.len() != n
+                || contribution.tag.is_identity()
+            {
+                return Err(CryptoError::Validation {
+                    field: "contributions".to_string(),
+                    reason: format!(
+                        "contribution {} does not cover the committee of {}",
+                        position, n
+                    ),
+                });
+            }
+            let announcements: Vec<_> = (0..n)
+                .map(|j| {
+                    statement.announcement(
+                        j,
+                        &contribution.tag,
+                        contribution.challenges[j],
+                        contribution.share_responses[j],
+                        contribution.random_responses[j],
+                    )
+                })
+                .collect();
+            if statement.challenge(&contribution.tag, &announcements)
+                != contribution.challenges.iter().sum::<Scalar>()
+            {
+                return Err(CryptoError::SecurityViolation {
+                    details: format!("contribution {} does not verify", position),
+                });
+            }
+            if !tags.insert(contribution.tag.compress().to_bytes()) {
+                return Err(duplicate_contribution());
+            }
+        }
+        Ok(tags.len())
+    }
+
+    /// Check that at least `threshold` distinct committee slices contributed.
+    ///
+    /// Returns the errors of [`Self::distinct_contributions`], and
+    /// `CryptoError::SecurityViolation` if fewer slices contributed.
+    pub fn verify(&self) -> CryptoResult<()> {
+        let distinct = self.distinct_contributions()?;
+        if distinct < self.threshold {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "{} distinct committee slices contributed, threshold {}",
+                    distinct, self.threshold
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn duplicate_contribution() -> CryptoError {
+    CryptoError::SecurityViolation {
+        details: "a committee slice contributed twice".to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+
+    fn committee(shares: &[ShareData]) -> Vec<CommitteeMember> {
+        shares
+            .iter()
+            .map(|s| CommitteeMember {
+                index: s.index,
+                custodian_id: Some(format!("custodian-{}", s.index)),
+                commitment: s.commitment,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_policy_proof_counts_distinct_committee_slices() {
+        let shares = generate_key_shares(Scalar::from(7u64), 3, 5).unwrap();
+        let mut proof =
+            PolicyComplianceProof::new("k", "recover-9", "deal-1", 3, committee(&shares)).unwrap();
+        for share in &shares[1..4] {
+            proof.contribute(share).unwrap();
+        }
+        proof.verify().unwrap();
+        assert_eq!(proof.distinct_contributions().unwrap(), 3);
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PolicyComplianceProof>(&json).unwrap(),
+            proof
+        );
+
+        // The same slice cannot count twice, even when proved afresh.
+        assert!(matches!(
+            proof.contribute(&shares[2]),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut replayed = proof.clone();
+        replayed.contributions.push(proof.contributions[0].clone());
+        assert!(matches!(
+            replayed.verify(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let mut short = proof.clone();
+        short.contributions.pop();
+        assert_eq!(short.distinct_contributions().unwrap(), 2);
+        assert!(matches!(
+            short.verify(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // Contributions are bound to the statement they were made for.
+        let mut other_recovery = proof.clone();
+        other_recovery.recovery_operation_id = "recover-10".to_string();
+        assert!(other_recovery.verify().is_err());
+        let mut renamed = proof.clone();
+        renamed.committee[0].custodian_id = Some("mallory".to_string());
+        assert!(renamed.verify().is_err());
+
+        // Tags of the same slice differ between recoveries.
+        let mut next =
+            PolicyComplianceProof::new("k", "recover-10", "deal-1", 3, committee(&shares)).unwrap();
+        next.contribute(&shares[1]).unwrap();
+        assert!(proof
+            .contributions
+            .iter()
+            .all(|c| c.tag != next.contributions[0].tag));
+    }
+
+    #[test]
+    fn test_policy_proof_rejects_foreign_slices() {
+        let shares = generate_key_shares(Scalar::from(7u64), 2, 3).unwrap();
+        let foreign = generate_key_shares(Scalar::from(7u64), 2, 3).unwrap();
+        assert!(PolicyComplianceProof::new("k", "r", "d", 4, committee(&shares)).is_err());
+        let mut proof = PolicyComplianceProof::new("k", "r", "d", 2, committee(&shares)).unwrap();
+        assert!(matches!(
+            proof.contribute(&foreign[0]),
+            Err(CryptoError::Validation { .. })
+        ));
+        let mut forged = shares[0].clone();
+        forged.share += Scalar::ONE;
+        assert!(matches!(
+            proof.contribute(&forged),
+            Err(CryptoError::Validation { .. })
+        ));
+
+        // A contribution over another committee does not verify against this one.
+        let mut other = PolicyComplianceProof::new("k", "r", "d", 2, committee(&foreign)).unwrap();
+        other.contribute(&foreign[0]).unwrap();
+        other.contribute(&foreign[1]).unwrap();
+        proof.contributions = other.contributions;
+        assert!(matches!(
+            proof.verify(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+}
@@ -3,23 +3,94 @@
 //! This module implements the generation and verification of Zero Knowledge Proofs (ZKPs), using the Fiat-Shamir transform to convert interactive proofs to non-interactive ones.
 //!
 //! Proofs are used to verify the relationship between sliced, blinded random numbers and promises to ensure security during secret sharing.
+//!
+//! Challenges are drawn from a domain-separated [`Transcript`] of the protocol
+//! `pedersen-opening`, so a proof of this module cannot be replayed as a proof of another
+//! protocol over the same points, nor the reverse.
+//!
+//! [`Proof`] is over ristretto255 unless another [`CurveGroup`] is named; proofs in any group are
+//! made and checked with [`generate_group_proof`] and [`verify_group_proof`], which over
+//! [`Ristretto255`] agree with [`generate_proof`] and [`verify_proof`].
+
+mod transcript;
 
-use crate::hash_adapter::Blake3Adapter;
+pub use transcript::Transcript;
+
+use crate::error::{CryptoError, CryptoResult, VerifyError};
+use crate::group::{encoding, CurveGroup, Ristretto255};
+use crate::hash_adapter::{Blake3Adapter, HashAdapter};
+use crate::op_counts::{count, Op};
 use crate::utils::ANOTHER_POINT;
 use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
 use curve25519_dalek::{RistrettoPoint, Scalar};
 
 /// zero-knowledge proof structure
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub struct Proof {
-    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
-    proof_ristretto_point: RistrettoPoint,
-    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
-    response_share: Scalar,
-    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
-    response_random: Scalar,
-    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
-    challenge: Scalar,
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(bound = "")]
+pub struct Proof<G: CurveGroup = Ristretto255> {
+    #[serde(
+        serialize_with = "encoding::serialize_element::<G, _>",
+        deserialize_with = "encoding::deserialize_element::<G, _>"
+    )]
+    proof_ristretto_point: G::Element,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<G, _>",
+        deserialize_with = "encoding::deserialize_scalar::<G, _>"
+    )]
+    response_share: G::Scalar,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<G, _>",
+        deserialize_with = "encoding::deserialize_scalar::<G, _>"
+    )]
+    response_random: G::Scalar,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<G, _>",
+        deserialize_with = "encoding::deserialize_scalar::<G, _>"
+    )]
+    challenge: G::Scalar,
+}
+
+impl<G: CurveGroup> std::fmt::Debug for Proof<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Proof")
+            .field("proof_ristretto_point", &self.proof_ristretto_point)
+            .field("response_share", &self.response_share)
+            .field("response_random", &self.response_random)
+            .field("challenge", &self.challenge)
+            .finish()
+    }
+}
+
+/// Length of the binary [`Proof`] encoding.
+pub const PROOF_LENGTH: usize = 128;
+
+impl Proof {
+    /// Fixed-length encoding: compressed `R`, then the canonical bytes of `z_s`, `z_r` and `c`.
+    pub fn to_bytes(&self) -> [u8; PROOF_LENGTH] {
+        let mut bytes = [0u8; PROOF_LENGTH];
+        bytes[..32].copy_from_slice(self.proof_ristretto_point.compress().as_bytes());
+        bytes[32..64].copy_from_slice(self.response_share.as_bytes());
+        bytes[64..96].copy_from_slice(self.response_random.as_bytes());
+        bytes[96..].copy_from_slice(self.challenge.as_bytes());
+        bytes
+    }
+
+    /// Decode [`Proof::to_bytes`], rejecting invalid or identity points and non-canonical scalars.
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        use crate::serialization::{point_from_slice, scalar_from_slice};
+        if bytes.len() != PROOF_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: format!("proof must be {} bytes, got {}", PROOF_LENGTH, bytes.len()),
+            });
+        }
+        Ok(Proof {
+            proof_ristretto_point: point_from_slice(&bytes[..32], "proof point")?,
+            response_share: scalar_from_slice(&bytes[32..64], "proof share response")?,
+            response_random: scalar_from_slice(&bytes[64..96], "proof random response")?,
+            challenge: scalar_from_slice(&bytes[96..], "proof challenge")?,
+        })
+    }
 }
 
 /// The challenge value (challenge) is computed and the Fiat-Shamir transform is used to bind the public data to the slice index.
@@ -28,14 +99,91 @@ pub fn compute_challenge(
     proof_ristretto_point: &RistrettoPoint,
     index: usize,
 ) -> Scalar {
-    let mut hasher = Blake3Adapter::new();
-    // Input the base point, the commitment, the point generated by the proof, and the slice index all into the hasher
-    digest::Update::update(&mut hasher, RISTRETTO_BASEPOINT_POINT.compress().as_bytes());
-    digest::Update::update(&mut hasher, ANOTHER_POINT.compress().as_bytes());
-    digest::Update::update(&mut hasher, commitment.compress().as_bytes());
-    digest::Update::update(&mut hasher, proof_ristretto_point.compress().as_bytes());
-    digest::Update::update(&mut hasher, &index.to_le_bytes());
-    Scalar::from_hash(hasher)
+    challenge_from_encodings(
+        commitment.compress().as_bytes(),
+        proof_ristretto_point.compress().as_bytes(),
+        index,
+    )
+}
+
+/// [`compute_challenge`] over already compressed points.
+fn challenge_from_encodings(
+    commitment: &[u8],
+    proof_ristretto_point: &[u8],
+    index: usize,
+) -> Scalar {
+    challenge_with(
+        &Blake3Adapter::new(),
+        commitment,
+        proof_ristretto_point,
+        index,
+    )
+}
+
+/// [`challenge_from_encodings`] with the challenge drawn by `hash`.
+fn challenge_with(
+    hash: &dyn HashAdapter,
+    commitment: &[u8],
+    proof_ristretto_point: &[u8],
+    index: usize,
+) -> Scalar {
+    // The base point, the commitment, the point generated by the proof, and the slice index
+    let mut transcript = Transcript::new(OPENING_PROTOCOL);
+    transcript.append_point(b"G", &RISTRETTO_BASEPOINT_POINT);
+    transcript.append_point(b"H", &ANOTHER_POINT);
+    transcript.append_message(b"C", commitment);
+    transcript.append_message(b"R", proof_ristretto_point);
+    // As a `u64`, so that 32-bit targets hash the same bytes.
+    transcript.append_u64(b"index", index as u64);
+    transcript.challenge_scalar_with(hash, b"c")
+}
+
+/// [`Transcript`] protocol of the slice proofs.
+const OPENING_PROTOCOL: &[u8] = b"pedersen-opening";
+
+/// [`compute_challenge`] in the group `G`: the same transcript over the canonical encodings of
+/// `G`, `H`, the commitment and the proof point, reduced from 64 bytes with
+/// [`CurveGroup::scalar_from_wide`]. Over [`Ristretto255`] it equals [`compute_challenge`].
+pub fn compute_group_challenge<G: CurveGroup>(
+    commitment: &G::Element,
+    proof_point: &G::Element,
+    index: usize,
+) -> G::Scalar {
+    let mut transcript = Transcript::new(OPENING_PROTOCOL);
+    transcript.append_message(b"G", &G::element_to_bytes(&G::generator()));
+    transcript.append_message(b"H", &G::element_to_bytes(&G::blinding_generator()));
+    transcript.append_message(b"C", &G::element_to_bytes(commitment));
+    transcript.append_message(b"R", &G::element_to_bytes(proof_point));
+    transcript.append_u64(b"index", index as u64);
+    G::scalar_from_wide(&transcript.challenge_wide(b"c"))
+}
+
+/// Derive the per-secret combination weights for a multi-secret slice.
+///
+/// The weights bind the slice index and every per-secret commitment, so that one proof over the
+/// combined commitment `Σ ρ_k · C_k` covers all secrets of the slice. The first weight is fixed to
+/// one so that a single-secret slice degenerates to the ordinary proof.
+pub fn combination_weights(index: usize, commitments: &[RistrettoPoint]) -> Vec<Scalar> {
+    let mut hasher =
+        Blake3Adapter::new_derive_key("zk-thresh-pro 2024 multi-secret combination v1");
+    digest::Update::update(&mut hasher, &(index as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, &(commitments.len() as u64).to_le_bytes());
+    for commitment in commitments {
+        digest::Update::update(&mut hasher, commitment.compress().as_bytes());
+    }
+    let seed = Scalar::from_hash(hasher);
+    let mut weights = Vec::with_capacity(commitments.len());
+    let mut weight = Scalar::ONE;
+    for _ in commitments {
+        weights.push(weight);
+        weight *= seed;
+    }
+    weights
+}
+
+/// Combine per-secret commitments with [`combination_weights`].
+pub fn combine_commitments(weights: &[Scalar], commitments: &[RistrettoPoint]) -> RistrettoPoint {
+    weights.iter().zip(commitments).map(|(w, c)| c * w).sum()
 }
 
 /// Generating zero-knowledge proofs
@@ -49,24 +197,101 @@ pub fn compute_challenge(
 ///
 /// # Return value
 ///
-/// Returns a Proof struct containing all the elements needed for the proof.
+/// Returns a Proof struct containing all the elements needed for the proof, or
+/// `CryptoError::InsufficientEntropy` if the nonces cannot be drawn.
+#[cfg(feature = "dealer")]
 pub fn generate_proof(
     share: Scalar,
     random: Scalar,
     index: usize,
     commitment: RistrettoPoint,
-) -> Proof {
+) -> crate::error::CryptoResult<Proof> {
+    generate_proof_with(&Blake3Adapter::new(), share, random, index, commitment)
+}
+
+/// [`generate_proof`] with the challenge drawn by `hash`; verify it with [`verify_proof_with`].
+#[cfg(feature = "dealer")]
+pub fn generate_proof_with(
+    hash: &dyn HashAdapter,
+    share: Scalar,
+    random: Scalar,
+    index: usize,
+    commitment: RistrettoPoint,
+) -> crate::error::CryptoResult<Proof> {
+    prove_opening(share, random, |proof_ristretto_point| {
+        challenge_with(
+            hash,
+            commitment.compress().as_bytes(),
+            proof_ristretto_point.compress().as_bytes(),
+            index,
+        )
+    })
+}
+
+/// Proof of knowledge of the opening `(share, random)` of a commitment, with the challenge
+/// drawn by `challenge` from the proof point `R`.
+#[cfg(feature = "dealer")]
+pub(crate) fn prove_opening(
+    share: Scalar,
+    random: Scalar,
+    challenge: impl FnOnce(&RistrettoPoint) -> Scalar,
+) -> crate::error::CryptoResult<Proof> {
     use crate::utils::random_scalar;
     let mut local_rng = crate::utils::new_rng();
-    let k_share = random_scalar(&mut local_rng);
-    let k_random = random_scalar(&mut local_rng);
-    let proof_ristretto_point = RISTRETTO_BASEPOINT_POINT * k_share + (*ANOTHER_POINT) * k_random;
-    let challenge = compute_challenge(&commitment, &proof_ristretto_point, index);
-    Proof {
+    let k_share = random_scalar(&mut local_rng)?;
+    let k_random = random_scalar(&mut local_rng)?;
+    let proof_ristretto_point = crate::utils::pedersen_commit(&k_share, &k_random);
+    let challenge = challenge(&proof_ristretto_point);
+    count(Op::ScalarMul, 2);
+    Ok(Proof {
+        proof_ristretto_point,
+        response_share: k_share + challenge * share,
+        response_random: k_random + challenge * random,
+        challenge,
+    })
+}
+
+/// [`generate_proof`] in the group `G`; verify it with [`verify_group_proof`].
+#[cfg(feature = "dealer")]
+pub fn generate_group_proof<G: CurveGroup>(
+    share: G::Scalar,
+    random: G::Scalar,
+    index: usize,
+    commitment: G::Element,
+) -> CryptoResult<Proof<G>> {
+    let k_share = crate::group::random_scalar::<G>()?;
+    let k_random = crate::group::random_scalar::<G>()?;
+    count(Op::PointMul, 2);
+    let proof_ristretto_point = G::generator() * k_share + G::blinding_generator() * k_random;
+    let challenge = compute_group_challenge::<G>(&commitment, &proof_ristretto_point, index);
+    count(Op::ScalarMul, 2);
+    Ok(Proof {
         proof_ristretto_point,
         response_share: k_share + challenge * share,
         response_random: k_random + challenge * random,
         challenge,
+    })
+}
+
+/// [`verify_proof`] in the group `G`, of a proof made by [`generate_group_proof`].
+pub fn verify_group_proof<G: CurveGroup>(
+    proof: &Proof<G>,
+    commitment: G::Element,
+    index: usize,
+) -> Result<(), VerifyError> {
+    if compute_group_challenge::<G>(&commitment, &proof.proof_ristretto_point, index)
+        != proof.challenge
+    {
+        return Err(VerifyError::ChallengeMismatch { index });
+    }
+    count(Op::PointMul, 3);
+    let lhs =
+        G::generator() * proof.response_share + G::blinding_generator() * proof.response_random;
+    let rhs = proof.proof_ristretto_point + commitment * proof.challenge;
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(VerifyError::InvalidResponse { index })
     }
 }
 
@@ -80,14 +305,110 @@ pub fn generate_proof(
 ///
 /// # Return value
 ///
-/// Returns `true` if the proof is valid, `false` otherwise.
-pub fn verify_proof(proof: &Proof, commitment: RistrettoPoint, index: usize) -> bool {
-    let computed_challenge = compute_challenge(&commitment, &proof.proof_ristretto_point, index);
-    if computed_challenge != proof.challenge {
-        return false;
+/// Returns `Ok(())` if the proof is valid, `VerifyError::ChallengeMismatch` if it was made for
+/// another commitment or index (or altered), and `VerifyError::InvalidResponse` if its responses
+/// do not satisfy the verification equation.
+pub fn verify_proof(
+    proof: &Proof,
+    commitment: RistrettoPoint,
+    index: usize,
+) -> Result<(), VerifyError> {
+    verify_proof_with(&Blake3Adapter::new(), proof, commitment, index)
+}
+
+/// [`verify_proof`] of a proof made by [`generate_proof_with`] with `hash`.
+pub fn verify_proof_with(
+    hash: &dyn HashAdapter,
+    proof: &Proof,
+    commitment: RistrettoPoint,
+    index: usize,
+) -> Result<(), VerifyError> {
+    verify_opening(proof, commitment, index, |proof_ristretto_point| {
+        challenge_with(
+            hash,
+            commitment.compress().as_bytes(),
+            proof_ristretto_point.compress().as_bytes(),
+            index,
+        )
+    })
+}
+
+/// Check a [`prove_opening`] proof for `commitment`, reporting failures under `index`.
+pub(crate) fn verify_opening(
+    proof: &Proof,
+    commitment: RistrettoPoint,
+    index: usize,
+    challenge: impl FnOnce(&RistrettoPoint) -> Scalar,
+) -> Result<(), VerifyError> {
+    if challenge(&proof.proof_ristretto_point) != proof.challenge {
+        return Err(VerifyError::ChallengeMismatch { index });
     }
+    count(Op::PointMul, 3);
     let lhs =
         RISTRETTO_BASEPOINT_POINT * proof.response_share + (*ANOTHER_POINT) * proof.response_random;
     let rhs = proof.proof_ristretto_point + commitment * proof.challenge;
-    lhs == rhs
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(VerifyError::InvalidResponse { index })
+    }
+}
+
+/// Verify many proofs at once.
+///
+/// Every challenge is recomputed as in [`verify_proof`]. The verification equations
+/// `z_s·G + z_r·H - R - c·C = 0` are then combined with weights `ρ^k`, where `ρ` is a hash of
+/// every statement, and checked with a single multiscalar multiplication; a set containing an
+/// invalid proof passes only with probability about `n / ℓ`.
+///
+/// # Return value
+///
+/// Returns `Ok(())` if every proof is valid, otherwise the error [`verify_proof`] reports for
+/// the first invalid one (found by checking the proofs one by one once the batch fails).
+pub fn verify_proofs_batch(
+    statements: &[(&Proof, RistrettoPoint, usize)],
+) -> Result<(), VerifyError> {
+    let mut hasher = Blake3Adapter::new_derive_key("zk-thresh-pro 2024 proof batch weights v1");
+    digest::Update::update(&mut hasher, &(statements.len() as u64).to_le_bytes());
+    for (proof, commitment, index) in statements {
+        let commitment_bytes = commitment.compress();
+        let proof_bytes = proof.to_bytes();
+        let challenge =
+            challenge_from_encodings(commitment_bytes.as_bytes(), &proof_bytes[..32], *index);
+        if challenge != proof.challenge {
+            return Err(VerifyError::ChallengeMismatch { index: *index });
+        }
+        digest::Update::update(&mut hasher, &(*index as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, commitment_bytes.as_bytes());
+        digest::Update::update(&mut hasher, &proof_bytes);
+    }
+    let seed = Scalar::from_hash(hasher);
+
+    let mut share_response = Scalar::ZERO;
+    let mut random_response = Scalar::ZERO;
+    let mut scalars = Vec::with_capacity(2 * statements.len() + 2);
+    let mut points = Vec::with_capacity(2 * statements.len() + 2);
+    let mut weight = Scalar::ONE;
+    for (proof, commitment, _) in statements {
+        share_response += weight * proof.response_share;
+        random_response += weight * proof.response_random;
+        scalars.push(-weight);
+        points.push(proof.proof_ristretto_point);
+        scalars.push(-(weight * proof.challenge));
+        points.push(*commitment);
+        weight *= seed;
+    }
+    scalars.push(share_response);
+    points.push(RISTRETTO_BASEPOINT_POINT);
+    scalars.push(random_response);
+    points.push(*ANOTHER_POINT);
+
+    count(Op::ScalarMul, 4 * statements.len());
+    count(Op::PointMul, points.len());
+    if RistrettoPoint::vartime_multiscalar_mul(&scalars, &points).is_identity() {
+        return Ok(());
+    }
+    statements
+        .iter()
+        .try_for_each(|(proof, commitment, index)| verify_proof(proof, *commitment, *index))
 }
@@ -0,0 +1,127 @@
+//! Fiat-Shamir transcripts.
+//!
+//! A [`Transcript`] accumulates the public data of a protocol run as framed records: every record
+//! is a label and a message, each prefixed by its length, so no two sequences of appends encode
+//! to the same bytes. The transcript starts with a crate-wide domain separator and the protocol
+//! label, so a challenge drawn for one protocol is never a valid challenge of another protocol
+//! over the same points. Drawing a challenge appends its label and the challenge itself, so
+//! later challenges depend on earlier ones.
+
+use crate::hash_adapter::{Blake3Adapter, HashAdapter};
+use curve25519_dalek::{RistrettoPoint, Scalar};
+
+/// Domain separator opening every transcript.
+const TRANSCRIPT_DOMAIN: &[u8] = b"zk-thresh-pro 2024 fiat-shamir transcript v1";
+
+/// Public transcript of a proof, from which its challenges are drawn.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    bytes: Vec<u8>,
+}
+
+impl Transcript {
+    /// Empty transcript of `protocol`, e.g. `b"pedersen-opening"`.
+    pub fn new(protocol: &'static [u8]) -> Self {
+        let mut transcript = Transcript {
+            bytes: Vec::with_capacity(256),
+        };
+        transcript.append_message(b"dom-sep", TRANSCRIPT_DOMAIN);
+        transcript.append_message(b"protocol", protocol);
+        transcript
+    }
+
+    /// Append `message` under `label`.
+    pub fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        for part in [label, message] {
+            self.bytes
+                .extend_from_slice(&(part.len() as u64).to_le_bytes());
+            self.bytes.extend_from_slice(part);
+        }
+    }
+
+    /// Append the compressed encoding of `point`.
+    pub fn append_point(&mut self, label: &'static [u8], point: &RistrettoPoint) {
+        self.append_message(label, point.compress().as_bytes());
+    }
+
+    /// Append the canonical encoding of `scalar`.
+    pub fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar) {
+        self.append_message(label, scalar.as_bytes());
+    }
+
+    /// Append `value` as 8 little-endian bytes, the same on every target.
+    pub fn append_u64(&mut self, label: &'static [u8], value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    /// Challenge `label` drawn with BLAKE3.
+    pub fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        self.challenge_scalar_with(&Blake3Adapter::new(), label)
+    }
+
+    /// Challenge `label` drawn with [`HashAdapter::challenge`] of `hash` over the transcript so
+    /// far; the challenge is then appended under the same label.
+    pub fn challenge_scalar_with(
+        &mut self,
+        hash: &dyn HashAdapter,
+        label: &'static [u8],
+    ) -> Scalar {
+        self.append_message(b"challenge", label);
+        let challenge = hash.challenge(&self.bytes);
+        self.append_scalar(label, &challenge);
+        challenge
+    }
+
+    /// Challenge `label` as 64 uniform BLAKE3 bytes, for groups whose scalars are not ristretto255
+    /// scalars; the bytes are then appended under the same label. Reduced with
+    /// `Scalar::from_bytes_mod_order_wide`, they give the challenge of [`Self::challenge_scalar`].
+    pub fn challenge_wide(&mut self, label: &'static [u8]) -> [u8; 64] {
+        self.append_message(b"challenge", label);
+        let mut hasher = Blake3Adapter::new();
+        digest::Update::update(&mut hasher, &self.bytes);
+        let mut wide = [0u8; 64];
+        hasher.finalize_extended(&mut wide);
+        self.append_message(label, &wide);
+        wide
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_adapter::Sha256Adapter;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    fn opening(protocol: &'static [u8], index: u64) -> Transcript {
+        let mut transcript = Transcript::new(protocol);
+        transcript.append_point(b"C", &RISTRETTO_BASEPOINT_POINT);
+        transcript.append_u64(b"index", index);
+        transcript
+    }
+
+    #[test]
+    fn test_challenges_are_domain_separated() {
+        let c = opening(b"pedersen-opening", 1).challenge_scalar(b"c");
+        assert_eq!(c, opening(b"pedersen-opening", 1).challenge_scalar(b"c"));
+        assert_ne!(c, opening(b"other-protocol", 1).challenge_scalar(b"c"));
+        assert_ne!(c, opening(b"pedersen-opening", 2).challenge_scalar(b"c"));
+        assert_ne!(c, opening(b"pedersen-opening", 1).challenge_scalar(b"e"));
+        assert_ne!(
+            c,
+            opening(b"pedersen-opening", 1).challenge_scalar_with(&Sha256Adapter, b"c")
+        );
+
+        // Framing: moving bytes between a label and its message changes the transcript.
+        let mut split = Transcript::new(b"p");
+        split.append_message(b"ab", b"c");
+        let mut moved = Transcript::new(b"p");
+        moved.append_message(b"a", b"bc");
+        assert_ne!(split.challenge_scalar(b"c"), moved.challenge_scalar(b"c"));
+
+        // Each challenge depends on the ones drawn before it.
+        let mut transcript = opening(b"pedersen-opening", 1);
+        let first = transcript.challenge_scalar(b"c");
+        assert_eq!(first, c);
+        assert_ne!(transcript.challenge_scalar(b"c"), first);
+    }
+}
@@ -0,0 +1,153 @@
+//! **public_state module**
+//!
+//! Export of everything public the system knows about one key, for offsite backup and disaster
+//! recovery drills.
+//!
+//! [`crate::EnterpriseCryptoSystem::export_public_state`] bundles, for one key, the registry
+//! entries of its slices (each with its Pedersen commitment and revocation status), the last
+//! attestation of every custodian holding one, its ceremony transcripts, its share epoch and the
+//! policy it is operated under, including its expiry time. Nothing in a [`PublicState`] is secret: the secret lives only in
+//! the custodians' slices.
+//!
+//! [`crate::EnterpriseCryptoSystem::import_public_state`] loads a bundle into a fresh system.
+//! Afterwards the system recovers the key from custodian slices alone, still refuses revoked
+//! slices, and never reuses an epoch or operation id of the original system. A drill therefore
+//! consists of importing the backup on a clean machine and recovering from the custodians.
+//!
+//! [`PublicState::verify`] checks that the bundle is internally consistent before it is
+//! imported: every part belongs to the key, and the slice commitments of every dealing and
+//! refresh hash to the digest its transcript recorded.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::SecurityLevel;
+use crate::registry::RegistryEntry;
+use crate::status::CustodianStatus;
+use crate::transcript::{self, CeremonyKind, CeremonyTranscript};
+use chrono::{DateTime, Utc};
+
+/// Version of the [`PublicState`] format.
+pub const PUBLIC_STATE_VERSION: u8 = 1;
+
+/// Policy a key is operated under.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct KeyPolicy {
+    /// Threshold of the latest dealing or refresh.
+    pub threshold: usize,
+    pub security_level: SecurityLevel,
+    /// `ComplianceMode` of the exporting system, in its `Debug` form.
+    pub compliance_mode: String,
+    /// Cryptoperiod of the key's slices.
+    pub max_key_lifetime_hours: u64,
+    /// Expiry time enforced for the key, if any.
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Public state of one key, as exported by
+/// [`crate::EnterpriseCryptoSystem::export_public_state`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PublicState {
+    /// Format version, [`PUBLIC_STATE_VERSION`].
+    pub version: u8,
+    pub key_id: String,
+    /// Last share epoch of the key.
+    pub epoch: u64,
+    /// Last operation sequence number of the exporting system.
+    pub operation_sequence: Option<u64>,
+    pub policy: KeyPolicy,
+    /// Every slice issued for the key, in issuing order.
+    pub shares: Vec<RegistryEntry>,
+    /// Last attestation of every custodian holding a slice of the key.
+    pub custodians: Vec<CustodianStatus>,
+    /// Transcripts of the key, oldest first.
+    pub transcripts: Vec<CeremonyTranscript>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub exported_at: DateTime<Utc>,
+}
+
+impl PublicState {
+    /// Check that the state is internally consistent.
+    ///
+    /// Returns `CryptoError::Serialization` for an unsupported version and
+    /// `CryptoError::Validation` if a part belongs to another key, an epoch exceeds
+    /// [`Self::epoch`], or the slices of a dealing or refresh do not match its transcript.
+    pub fn verify(&self) -> CryptoResult<()> {
+        if self.version != PUBLIC_STATE_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported public state version {}", self.version),
+            });
+        }
+        let foreign = self
+            .shares
+            .iter()
+            .map(|e| &e.key_id)
+            .chain(self.custodians.iter().map(|c| &c.key_id))
+            .chain(self.transcripts.iter().map(|t| &t.context.key_id))
+            .find(|key_id| **key_id != self.key_id);
+        if let Some(key_id) = foreign {
+            return Err(invalid(
+                "key_id",
+                format!(
+                    "state of key {} contains a part of key {}",
+                    self.key_id, key_id
+                ),
+            ));
+        }
+        let last_epoch = self
+            .shares
+            .iter()
+            .map(|e| e.epoch)
+            .chain(self.transcripts.iter().map(|t| t.context.epoch))
+            .max();
+        if last_epoch.is_some_and(|epoch| epoch > self.epoch) {
+            return Err(invalid(
+                "epoch",
+                format!(
+                    "epoch {} of key {} is older than its slices",
+                    self.epoch, self.key_id
+                ),
+            ));
+        }
+        for transcript in &self.transcripts {
+            if transcript.kind == CeremonyKind::Recovery {
+                continue;
+            }
+            let operation_id = &transcript.context.operation_id;
+            let entries = transcript
+                .indices
+                .iter()
+                .map(|&index| {
+                    self.shares
+                        .iter()
+                        .find(|e| e.operation_id == *operation_id && e.index == index)
+                        .map(|e| (index, &e.commitment))
+                        .ok_or_else(|| {
+                            invalid(
+                                "shares",
+                                format!("slice {} of operation {} is missing", index, operation_id),
+                            )
+                        })
+                })
+                .collect::<CryptoResult<Vec<_>>>()?;
+            if transcript::digest_commitments(entries.into_iter()).as_slice()
+                != transcript.commitments_digest.as_slice()
+            {
+                return Err(invalid(
+                    "shares",
+                    format!(
+                        "slices of operation {} do not match its transcript",
+                        operation_id
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid(field: &str, reason: String) -> CryptoError {
+    CryptoError::Validation {
+        field: field.to_string(),
+        reason,
+    }
+}
@@ -0,0 +1,531 @@
+//! **pvss module**
+//!
+//! Publicly verifiable secret sharing (Schoenmakers, CRYPTO '99) over Ristretto.
+//!
+//! A dealer encrypts every slice to its holder's [`PvssPublicKey`] and publishes the whole
+//! [`PvssDealing`]. Anyone can run [`verify_deal`] on it without holding a slice or a key:
+//!
+//! - the dealer commits to its polynomial `f` as `C_j = a_j·H` ([`crate::utils::ANOTHER_POINT`]),
+//!   so `X_i = Σ i^j·C_j = f(i)·H` is public for every index;
+//! - slice `i` is encrypted as `Y_i = f(i)·y_i` under the holder's key `y_i = x_i·G`;
+//! - a DLEQ proof shows `log_H X_i = log_{y_i} Y_i`, i.e. that `Y_i` encrypts the committed slice.
+//!
+//! A holder runs [`decrypt_share`] to obtain `S_i = x_i⁻¹·Y_i = f(i)·G` together with a DLEQ proof
+//! of correct decryption (`log_G y_i = log_{S_i} Y_i`), which anyone can check with
+//! [`verify_decrypted_share`]. [`combine`] interpolates `threshold` checked slices in the exponent.
+//!
+//! The value recovered is the group element `secret·G`, not the scalar: slices are encrypted in
+//! the exponent, so only their `G`-multiples ever come out. Use it as a shared key (e.g. through
+//! a KDF) or to transport a symmetric key. The dealing itself does not reveal `secret·G`, since
+//! the commitments use the independent generator `H`.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::threshold_sig;
+use crate::utils::ANOTHER_POINT;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Version of the [`PvssDealing`] format.
+pub const PVSS_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`PvssDealing`].
+pub const PVSS_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: None,
+    aead: None,
+    kdf: None,
+    proof: Some(AlgorithmId::ChaumPedersenDleq),
+};
+
+const DEALING_CONTEXT: &str = "zk-thresh-pro 2024 pvss dealing v1";
+const ENCRYPTION_PROOF_CONTEXT: &str = "zk-thresh-pro 2024 pvss encryption proof v1";
+const DECRYPTION_PROOF_CONTEXT: &str = "zk-thresh-pro 2024 pvss decryption proof v1";
+
+/// A slice holder's PVSS public key `y = x·G`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PvssPublicKey(
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")] RistrettoPoint,
+);
+
+impl PvssPublicKey {
+    /// Parse a canonical compressed Ristretto point; the identity is rejected.
+    pub fn from_bytes(bytes: [u8; 32]) -> CryptoResult<Self> {
+        let point = crate::serialization::canonical_point(&bytes)
+            .filter(|p| *p != RistrettoPoint::identity())
+            .ok_or_else(|| CryptoError::Validation {
+                field: "public_key".to_string(),
+                reason: "not a valid non-identity Ristretto point".to_string(),
+            })?;
+        Ok(Self(point))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.compress().to_bytes()
+    }
+}
+
+/// A slice holder's PVSS private key `x`.
+///
+/// `Debug` output is redacted; the key is zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct PvssSecretKey(Scalar);
+
+impl PvssSecretKey {
+    /// Use a non-zero scalar as a private key.
+    pub fn from_scalar(scalar: Scalar) -> CryptoResult<Self> {
+        if scalar == Scalar::ZERO {
+            return Err(CryptoError::Validation {
+                field: "secret_key".to_string(),
+                reason: "private key must be non-zero".to_string(),
+            });
+        }
+        Ok(Self(scalar))
+    }
+
+    /// Draw a fresh key from the OS generator.
+    #[cfg(feature = "dealer")]
+    pub fn generate() -> CryptoResult<Self> {
+        Self::from_scalar(crate::utils::random_scalar(&mut crate::utils::new_rng())?)
+    }
+
+    pub fn public_key(&self) -> PvssPublicKey {
+        PvssPublicKey(RISTRETTO_BASEPOINT_POINT * self.0)
+    }
+}
+
+impl fmt::Debug for PvssSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PvssSecretKey(<redacted>)")
+    }
+}
+
+/// Non-interactive proof of `log_{g1} h1 = log_{g2} h2` (Chaum–Pedersen).
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DleqProof {
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    challenge: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    response: Scalar,
+}
+
+/// Statement of a [`DleqProof`], bound to one slice of one dealing (or, in
+/// [`crate::threshold_decrypt`], of one ciphertext).
+pub(crate) struct DleqStatement<'a> {
+    pub(crate) context: &'static str,
+    /// Digest of the dealing or ciphertext the proof belongs to.
+    pub(crate) binding: &'a [u8; 32],
+    pub(crate) index: usize,
+    pub(crate) g1: RistrettoPoint,
+    pub(crate) h1: RistrettoPoint,
+    pub(crate) g2: RistrettoPoint,
+    pub(crate) h2: RistrettoPoint,
+}
+
+impl DleqStatement<'_> {
+    fn challenge(&self, a1: &RistrettoPoint, a2: &RistrettoPoint) -> Scalar {
+        let mut hasher = Blake3Adapter::new_derive_key(self.context);
+        digest::Update::update(&mut hasher, self.binding);
+        digest::Update::update(&mut hasher, &(self.index as u64).to_le_bytes());
+        for point in [&self.g1, &self.h1, &self.g2, &self.h2, a1, a2] {
+            digest::Update::update(&mut hasher, point.compress().as_bytes());
+        }
+        Scalar::from_hash(hasher)
+    }
+
+    #[cfg(feature = "dealer")]
+    pub(crate) fn prove(&self, witness: &Scalar) -> CryptoResult<DleqProof> {
+        let mut nonce = crate::utils::random_scalar(&mut crate::utils::new_rng())?;
+        let challenge = self.challenge(&(self.g1 * nonce), &(self.g2 * nonce));
+        let response = nonce - challenge * witness;
+        nonce.zeroize();
+        Ok(DleqProof {
+            challenge,
+            response,
+        })
+    }
+
+    pub(crate) fn verify(&self, proof: &DleqProof) -> bool {
+        let a1 = self.g1 * proof.response + self.h1 * proof.challenge;
+        let a2 = self.g2 * proof.response + self.h2 * proof.challenge;
+        self.challenge(&a1, &a2) == proof.challenge
+    }
+}
+
+/// One slice encrypted to its holder.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedShare {
+    pub index: usize,
+    pub recipient: PvssPublicKey,
+    /// `Y_i = f(i)·y_i`.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub ciphertext: RistrettoPoint,
+    /// Proof that `ciphertext` encrypts the committed slice.
+    pub proof: DleqProof,
+}
+
+fn default_algorithms() -> AlgorithmSuite {
+    PVSS_ALGORITHMS
+}
+
+/// A complete, publicly verifiable dealing.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PvssDealing {
+    /// Dealing format version, [`PVSS_VERSION`].
+    pub version: u8,
+    /// Algorithms the dealing was produced with; [`PVSS_ALGORITHMS`] when absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    pub threshold: usize,
+    /// `C_j = a_j·H` for the `threshold` polynomial coefficients.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_vec_helpers")]
+    pub commitments: Vec<RistrettoPoint>,
+    /// Encrypted slices with indices `1..=n`, in order.
+    pub shares: Vec<EncryptedShare>,
+}
+
+/// A slice decrypted by its holder, `S_i = f(i)·G`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DecryptedShare {
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub share: RistrettoPoint,
+    /// Proof of correct decryption under the holder's key.
+    pub proof: DleqProof,
+}
+
+/// Digest binding the proofs of a dealing to its threshold, commitments and recipients.
+fn dealing_digest(
+    version: u8,
+    threshold: usize,
+    commitments: &[RistrettoPoint],
+    recipients: impl Iterator<Item = PvssPublicKey>,
+) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(DEALING_CONTEXT);
+    digest::Update::update(&mut hasher, &[version]);
+    digest::Update::update(&mut hasher, &(threshold as u64).to_le_bytes());
+    for commitment in commitments {
+        digest::Update::update(&mut hasher, commitment.compress().as_bytes());
+    }
+    for recipient in recipients {
+        digest::Update::update(&mut hasher, &recipient.to_bytes());
+    }
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    out
+}
+
+/// `X_i = Σ index^j·C_j`, by Horner's rule.
+fn committed_share(commitments: &[RistrettoPoint], index: usize) -> RistrettoPoint {
+    let x = Scalar::from(index as u64);
+    commitments
+        .iter()
+        .rev()
+        .fold(RistrettoPoint::identity(), |acc, c| acc * x + c)
+}
+
+fn encryption_statement<'a>(
+    digest: &'a [u8; 32],
+    commitments: &[RistrettoPoint],
+    index: usize,
+    recipient: &PvssPublicKey,
+    ciphertext: &RistrettoPoint,
+) -> DleqStatement<'a> {
+    DleqStatement {
+        context: ENCRYPTION_PROOF_CONTEXT,
+        binding: digest,
+        index,
+        g1: *ANOTHER_POINT,
+        h1: committed_share(commitments, index),
+        g2: recipient.0,
+        h2: *ciphertext,
+    }
+}
+
+fn decryption_statement<'a>(
+    digest: &'a [u8; 32],
+    encrypted: &EncryptedShare,
+    share: &RistrettoPoint,
+) -> DleqStatement<'a> {
+    DleqStatement {
+        context: DECRYPTION_PROOF_CONTEXT,
+        binding: digest,
+        index: encrypted.index,
+        g1: RISTRETTO_BASEPOINT_POINT,
+        h1: encrypted.recipient.0,
+        g2: *share,
+        h2: encrypted.ciphertext,
+    }
+}
+
+impl PvssDealing {
+    fn digest(&self) -> [u8; 32] {
+        dealing_digest(
+            self.version,
+            self.threshold,
+            &self.commitments,
+            self.shares.iter().map(|s| s.recipient),
+        )
+    }
+
+    fn share(&self, index: usize) -> CryptoResult<&EncryptedShare> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.shares.get(position))
+            .ok_or_else(|| CryptoError::Validation {
+                field: "index".to_string(),
+                reason: format!("dealing has no slice {}", index),
+            })
+    }
+}
+
+/// Share `secret·G` among `recipients`, any `threshold` of whom can recover it.
+///
+/// Slice `i` (1-based) is encrypted to `recipients[i - 1]`.
+#[cfg(feature = "dealer")]
+pub fn deal(
+    secret: &Scalar,
+    threshold: usize,
+    recipients: &[PvssPublicKey],
+) -> CryptoResult<PvssDealing> {
+    crate::sharing::validate_parameters(threshold, recipients.len())?;
+    if recipients.iter().any(|r| r.0 == RistrettoPoint::identity()) {
+        return Err(CryptoError::Validation {
+            field: "recipients".to_string(),
+            reason: "recipient key is the identity".to_string(),
+        });
+    }
+
+    let mut rng = crate::utils::new_rng();
+    let mut coeffs = vec![*secret];
+    for _ in 1..threshold {
+        coeffs.push(crate::utils::random_scalar(&mut rng)?);
+    }
+    let commitments: Vec<RistrettoPoint> = coeffs.iter().map(|a| *ANOTHER_POINT * a).collect();
+    let digest = dealing_digest(
+        PVSS_VERSION,
+        threshold,
+        &commitments,
+        recipients.iter().copied(),
+    );
+
+    let shares = recipients
+        .iter()
+        .enumerate()
+        .map(|(position, recipient)| {
+            let index = position + 1;
+            let mut value = crate::lagrange_fft::poly_evaluate(&coeffs, Scalar::from(index as u64));
+            let ciphertext = recipient.0 * value;
+            let proof = encryption_statement(&digest, &commitments, index, recipient, &ciphertext)
+                .prove(&value);
+            value.zeroize();
+            Ok(EncryptedShare {
+                index,
+                recipient: *recipient,
+                ciphertext,
+                proof: proof?,
+            })
+        })
+        .collect::<CryptoResult<_>>();
+    coeffs.zeroize();
+
+    Ok(PvssDealing {
+        version: PVSS_VERSION,
+        algorithms: PVSS_ALGORITHMS,
+        threshold,
+        commitments,
+        shares: shares?,
+    })
+}
+
+/// Check a dealing without any secret: structure, and that every ciphertext encrypts the slice
+/// committed to by the dealer's polynomial.
+///
+/// Returns `CryptoError::Validation` for a malformed dealing and `CryptoError::SecurityViolation`
+/// naming the first slice whose proof fails.
+pub fn verify_deal(dealing: &PvssDealing) -> CryptoResult<()> {
+    if dealing.version != PVSS_VERSION {
+        return Err(CryptoError::Validation {
+            field: "version".to_string(),
+            reason: format!("unsupported dealing version {}", dealing.version),
+        });
+    }
+    dealing
+        .algorithms
+        .require(&PVSS_ALGORITHMS, "PVSS dealing")?;
+    if dealing.commitments.len() != dealing.threshold {
+        return Err(CryptoError::Validation {
+            field: "commitments".to_string(),
+            reason: format!(
+                "{} commitments for threshold {}",
+                dealing.commitments.len(),
+                dealing.threshold
+            ),
+        });
+    }
+    if dealing.threshold == 0 || dealing.threshold > dealing.shares.len() {
+        return Err(CryptoError::Validation {
+            field: "threshold".to_string(),
+            reason: format!(
+                "threshold {} with {} slices",
+                dealing.threshold,
+                dealing.shares.len()
+            ),
+        });
+    }
+    if let Some(share) = dealing
+        .shares
+        .iter()
+        .enumerate()
+        .find_map(|(position, s)| (s.index != position + 1).then_some(s))
+    {
+        return Err(CryptoError::Validation {
+            field: "shares".to_string(),
+            reason: format!("slice {} is out of order", share.index),
+        });
+    }
+
+    let digest = dealing.digest();
+    for share in &dealing.shares {
+        let statement = encryption_statement(
+            &digest,
+            &dealing.commitments,
+            share.index,
+            &share.recipient,
+            &share.ciphertext,
+        );
+        if !statement.verify(&share.proof) {
+            return Err(CryptoError::SecurityViolation {
+                details: format!("encryption proof of slice {} does not verify", share.index),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Decrypt the slice encrypted to `key`, after checking the whole dealing.
+///
+/// Returns `CryptoError::Validation` if no slice of the dealing is encrypted to `key`.
+#[cfg(feature = "dealer")]
+pub fn decrypt_share(dealing: &PvssDealing, key: &PvssSecretKey) -> CryptoResult<DecryptedShare> {
+    verify_deal(dealing)?;
+    let public_key = key.public_key();
+    let encrypted = dealing
+        .shares
+        .iter()
+        .find(|s| s.recipient == public_key)
+        .ok_or_else(|| CryptoError::Validation {
+            field: "recipient".to_string(),
+            reason: "no slice of the dealing is encrypted to this key".to_string(),
+        })?;
+
+    crate::op_counts::count(crate::op_counts::Op::Inversion, 1);
+    let share = encrypted.ciphertext * key.0.invert();
+    let proof = decryption_statement(&dealing.digest(), encrypted, &share).prove(&key.0)?;
+    Ok(DecryptedShare {
+        index: encrypted.index,
+        share,
+        proof,
+    })
+}
+
+/// Check that `share` is the correct decryption of its slice of `dealing`.
+///
+/// The dealing itself is not re-checked; run [`verify_deal`] on it once.
+pub fn verify_decrypted_share(dealing: &PvssDealing, share: &DecryptedShare) -> CryptoResult<()> {
+    let encrypted = dealing.share(share.index)?;
+    if decryption_statement(&dealing.digest(), encrypted, &share.share).verify(&share.proof) {
+        Ok(())
+    } else {
+        Err(CryptoError::SecurityViolation {
+            details: format!("decryption proof of slice {} does not verify", share.index),
+        })
+    }
+}
+
+/// Recover `secret·G` from decrypted slices of a dealing.
+///
+/// The dealing and every slice are checked. Returns `CryptoError::Validation` for fewer than
+/// `threshold` slices or duplicate indices.
+pub fn combine(dealing: &PvssDealing, shares: &[DecryptedShare]) -> CryptoResult<RistrettoPoint> {
+    verify_deal(dealing)?;
+    if shares.len() < dealing.threshold {
+        return Err(CryptoError::Validation {
+            field: "shares".to_string(),
+            reason: format!(
+                "{} slices provided, threshold is {}",
+                shares.len(),
+                dealing.threshold
+            ),
+        });
+    }
+    for share in shares {
+        verify_decrypted_share(dealing, share)?;
+    }
+
+    let shares = &shares[..dealing.threshold];
+    let indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+    let coefficients = threshold_sig::lagrange_at_zero(&indices)?;
+    Ok(shares
+        .iter()
+        .zip(coefficients)
+        .map(|(s, lambda)| s.share * lambda)
+        .sum())
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deal_verify_decrypt_combine() {
+        let keys: Vec<PvssSecretKey> = (0..4).map(|_| PvssSecretKey::generate().unwrap()).collect();
+        let recipients: Vec<PvssPublicKey> = keys.iter().map(|k| k.public_key()).collect();
+        let secret = Scalar::from(42u64);
+        let dealing = deal(&secret, 3, &recipients).unwrap();
+        verify_deal(&dealing).unwrap();
+
+        let json = serde_json::to_string(&dealing).unwrap();
+        let parsed: PvssDealing = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, dealing);
+
+        let decrypted: Vec<DecryptedShare> = keys
+            .iter()
+            .map(|k| decrypt_share(&dealing, k).unwrap())
+            .collect();
+        let expected = RISTRETTO_BASEPOINT_POINT * secret;
+        assert_eq!(combine(&dealing, &decrypted[1..]).unwrap(), expected);
+        assert_eq!(combine(&dealing, &decrypted[..3]).unwrap(), expected);
+        assert!(combine(&dealing, &decrypted[..2]).is_err());
+
+        // A ciphertext that does not match the commitments is caught by anyone.
+        let mut tampered = dealing.clone();
+        tampered.shares[1].ciphertext += RISTRETTO_BASEPOINT_POINT;
+        assert!(matches!(
+            verify_deal(&tampered),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut reordered = dealing.clone();
+        reordered.shares.swap(0, 1);
+        assert!(verify_deal(&reordered).is_err());
+
+        // So is a wrong decryption.
+        let mut forged = decrypted[0].clone();
+        forged.share += RISTRETTO_BASEPOINT_POINT;
+        assert!(verify_decrypted_share(&dealing, &forged).is_err());
+        assert!(combine(
+            &dealing,
+            &[forged, decrypted[1].clone(), decrypted[2].clone()]
+        )
+        .is_err());
+
+        let outsider = PvssSecretKey::generate().unwrap();
+        assert!(decrypt_share(&dealing, &outsider).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! **reconfigure module**
+//!
+//! Planning and step-by-step execution of committee changes: members joining or leaving and
+//! threshold changes, without reconstructing the secret.
+//!
+//! [`plan`] compares the current and the desired [`Committee`] under a [`ReconfigurationPolicy`]
+//! and returns the cheapest sequence of operations, each with the members whose slices it
+//! consumes and an estimated cost:
+//!
+//! - **Enroll**: `t` current members interpolate slices for new indices on the existing
+//!   polynomial. Existing slices, and the dealing's [`VssCommitments`](crate::vss::VssCommitments),
+//!   stay valid.
+//! - **Refresh**: every remaining member's slice is rerandomized with a zero-constant polynomial
+//!   ([`sharing::update_shares`]), so slices kept by removed members no longer combine with
+//!   current ones.
+//! - **Reshare**: `t` current members deal a fresh sharing of the same secret to the new
+//!   committee, possibly under a new threshold ([`sharing::adjust_threshold`]). Every old slice
+//!   becomes useless.
+//!
+//! A threshold change always needs a reshare. Otherwise new members are enrolled, and removed
+//! members are cut off by a refresh of the remaining members; when both are needed, or a
+//! remaining member is unavailable for the refresh, a single reshare to the new committee is
+//! cheaper and is planned instead.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::proof;
+use crate::sharing::{self, ShareData};
+use crate::utils;
+use curve25519_dalek::scalar::Scalar;
+use serde::{Deserialize, Serialize};
+
+/// Scalar multiplications to issue one slice: its Pedersen commitment and its proof commitment.
+const COST_PER_SLICE: usize = 4;
+
+/// Members (slice indices, sorted) and threshold of a committee.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Committee {
+    pub members: Vec<usize>,
+    pub threshold: usize,
+}
+
+impl Committee {
+    /// Returns `CryptoError::Validation` for a zero or duplicate index, or unless
+    /// `1 <= threshold <= members.len()`.
+    pub fn new(mut members: Vec<usize>, threshold: usize) -> CryptoResult<Self> {
+        members.sort_unstable();
+        if members.first() == Some(&0) {
+            return Err(invalid(
+                "members",
+                "slice index 0 is reserved for the secret",
+            ));
+        }
+        if let Some(pair) = members.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(invalid(
+                "members",
+                &format!("duplicate slice index {}", pair[0]),
+            ));
+        }
+        if threshold == 0 || threshold > members.len() {
+            return Err(invalid(
+                "threshold",
+                &format!("threshold {} is not in 1..={}", threshold, members.len()),
+            ));
+        }
+        Ok(Self { members, threshold })
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.members.binary_search(&index).is_ok()
+    }
+}
+
+/// Constraints on a reconfiguration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReconfigurationPolicy {
+    /// Current members that can take part; `None` means every member.
+    pub available: Option<Vec<usize>>,
+    /// Cut off removed members by refreshing or resharing. Without it removed members are only
+    /// dropped from the committee, and their slices still combine with current ones.
+    pub invalidate_removed: bool,
+    /// Smallest threshold the new committee may have.
+    pub min_threshold: usize,
+}
+
+impl Default for ReconfigurationPolicy {
+    fn default() -> Self {
+        Self {
+            available: None,
+            invalidate_removed: true,
+            min_threshold: 1,
+        }
+    }
+}
+
+/// Kind of a [`PlanStep`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Enroll,
+    Refresh,
+    Reshare,
+}
+
+/// One operation of a [`ReconfigurationPlan`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlanStep {
+    pub operation: Operation,
+    /// Current members whose slices the step consumes.
+    pub participants: Vec<usize>,
+    /// Members receiving a new slice.
+    pub recipients: Vec<usize>,
+    /// The committee after the step.
+    pub committee: Committee,
+    /// Estimated cost in scalar multiplications.
+    pub estimated_cost: usize,
+}
+
+impl PlanStep {
+    fn new(
+        operation: Operation,
+        participants: Vec<usize>,
+        recipients: Vec<usize>,
+        committee: &Committee,
+    ) -> Self {
+        Self {
+            operation,
+            estimated_cost: COST_PER_SLICE * recipients.len(),
+            participants,
+            recipients,
+            committee: committee.clone(),
+        }
+    }
+
+    /// Run the step on the committee's current slices (at least those of
+    /// [`PlanStep::participants`]) and return the slices of [`PlanStep::committee`].
+    ///
+    /// Returns `CryptoError::Validation` if a participant's slice is missing, and the errors of the
+    /// underlying sharing operation.
+    pub fn execute(&self, shares: &[ShareData]) -> CryptoResult<Vec<ShareData>> {
+        let participants: Vec<ShareData> = self
+            .participants
+            .iter()
+            .map(|&index| {
+                shares
+                    .iter()
+                    .find(|share| share.index == index)
+                    .cloned()
+                    .ok_or_else(|| {
+                        invalid("shares", &format!("missing slice of participant {}", index))
+                    })
+            })
+            .collect::<CryptoResult<_>>()?;
+
+        let mut result = match self.operation {
+            Operation::Enroll => {
+                let mut kept: Vec<ShareData> = shares
+                    .iter()
+                    .filter(|share| self.committee.contains(share.index))
+                    .cloned()
+                    .collect();
+                for &index in &self.recipients {
+                    kept.push(enroll(&participants, index)?);
+                }
+                kept
+            }
+            Operation::Refresh => sharing::update_shares(&participants, self.committee.threshold)?,
+            Operation::Reshare => sharing::reshare_to_indices(
+                &participants,
+                participants.len(),
+                self.committee.threshold,
+                &self.recipients,
+            )
+            .map_err(|reason| CryptoError::CryptographicOperation {
+                operation: format!("reshare: {}", reason),
+            })?,
+        };
+        result.sort_by_key(|share| share.index);
+        Ok(result)
+    }
+}
+
+/// Operations taking a committee from `from` to `to`, in order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReconfigurationPlan {
+    pub from: Committee,
+    pub to: Committee,
+    pub steps: Vec<PlanStep>,
+}
+
+impl ReconfigurationPlan {
+    /// Estimated cost of all steps in scalar multiplications.
+    pub fn estimated_cost(&self) -> usize {
+        self.steps.iter().map(|step| step.estimated_cost).sum()
+    }
+
+    /// Run every step in order, starting from the slices of [`ReconfigurationPlan::from`], and
+    /// return the slices of [`ReconfigurationPlan::to`].
+    pub fn execute(&self, shares: &[ShareData]) -> CryptoResult<Vec<ShareData>> {
+        let mut shares = shares.to_vec();
+        for step in &self.steps {
+            shares = step.execute(&shares)?;
+        }
+        shares.retain(|share| self.to.contains(share.index));
+        Ok(shares)
+    }
+}
+
+/// Plan the reconfiguration of `current` into `target`.
+///
+/// Returns `CryptoError::Validation` if `target` violates `policy.min_threshold` or fewer than
+/// `current.threshold` current members are available.
+pub fn plan(
+    current: &Committee,
+    target: &Committee,
+    policy: &ReconfigurationPolicy,
+) -> CryptoResult<ReconfigurationPlan> {
+    if target.threshold < policy.min_threshold {
+        return Err(invalid(
+            "threshold",
+            &format!(
+                "threshold {} is below the policy minimum {}",
+                target.threshold, policy.min_threshold
+            ),
+        ));
+    }
+    let available: Vec<usize> = current
+        .members
+        .iter()
+        .copied()
+        .filter(|index| policy.available.as_ref().is_none_or(|a| a.contains(index)))
+        .collect();
+    if available.len() < current.threshold {
+        return Err(invalid(
+            "available",
+            &format!(
+                "{} current members available, threshold is {}",
+                available.len(),
+                current.threshold
+            ),
+        ));
+    }
+    let quorum: Vec<usize> = available[..current.threshold].to_vec();
+    let added: Vec<usize> = target
+        .members
+        .iter()
+        .copied()
+        .filter(|&index| !current.contains(index))
+        .collect();
+    let removed = current.members.iter().any(|&index| !target.contains(index));
+    let invalidate = removed && policy.invalidate_removed;
+    let refresh_possible = target.members.iter().all(|index| available.contains(index));
+
+    let steps = if target.threshold != current.threshold
+        || (invalidate && (!added.is_empty() || !refresh_possible))
+    {
+        vec![PlanStep::new(
+            Operation::Reshare,
+            quorum,
+            target.members.clone(),
+            target,
+        )]
+    } else {
+        let mut steps = Vec::new();
+        if !added.is_empty() {
+            steps.push(PlanStep::new(Operation::Enroll, quorum, added, target));
+        }
+        if invalidate {
+            steps.push(PlanStep::new(
+                Operation::Refresh,
+                target.members.clone(),
+                target.members.clone(),
+                target,
+            ));
+        }
+        steps
+    };
+
+    Ok(ReconfigurationPlan {
+        from: current.clone(),
+        to: target.clone(),
+        steps,
+    })
+}
+
+/// Slice at `index` of the polynomial through `participants`' slices, with its blinding random
+/// interpolated the same way so the slice opens the dealing's expected commitment.
+fn enroll(participants: &[ShareData], index: usize) -> CryptoResult<ShareData> {
+    let x = Scalar::from(index as u64);
+    let xs: Vec<Scalar> = participants
+        .iter()
+        .map(|share| Scalar::from(share.index as u64))
+        .collect();
+    let mut share = Scalar::ZERO;
+    let mut random = Scalar::ZERO;
+    for (i, participant) in participants.iter().enumerate() {
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (k, x_k) in xs.iter().enumerate() {
+            if k != i {
+                numerator *= x - x_k;
+                denominator *= xs[i] - x_k;
+            }
+        }
+        crate::op_counts::count(crate::op_counts::Op::Inversion, 1);
+        let lambda = numerator * denominator.invert();
+        share += lambda * participant.share;
+        random += lambda * participant.random;
+    }
+    let commitment = utils::pedersen_commit(&share, &random);
+    Ok(ShareData {
+        index,
+        share,
+        commitment,
+        random,
+        proof: proof::generate_proof(share, random, index, commitment)?,
+    })
+}
+
+fn invalid(field: &str, reason: &str) -> CryptoError {
+    CryptoError::Validation {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lagrange_fft::recover_secret_fft;
+    use crate::sharing::generate_key_shares_with_commitments;
+
+    fn pick(shares: &[ShareData], indices: &[usize]) -> Vec<ShareData> {
+        shares
+            .iter()
+            .filter(|share| indices.contains(&share.index))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_and_execute_reconfigurations() {
+        let secret = Scalar::from(4242u64);
+        let (shares, commitments) = generate_key_shares_with_commitments(secret, 2, 3).unwrap();
+        let current = Committee::new(vec![1, 2, 3], 2).unwrap();
+        let policy = ReconfigurationPolicy::default();
+
+        // Joining only: one enrollment, old slices and commitments stay valid.
+        let grown = Committee::new(vec![1, 2, 3, 4], 2).unwrap();
+        let joined = plan(&current, &grown, &policy).unwrap();
+        assert_eq!(joined.steps.len(), 1);
+        assert_eq!(joined.steps[0].operation, Operation::Enroll);
+        assert_eq!(joined.steps[0].recipients, vec![4]);
+        let after = joined.execute(&shares).unwrap();
+        assert_eq!(after.len(), 4);
+        assert!(commitments.verify_share(&after[3]).is_ok());
+        assert_eq!(recover_secret_fft(&pick(&after, &[3, 4])).unwrap(), secret);
+
+        // Leaving only: a refresh of the remaining members, or a reshare if one is unavailable.
+        let shrunk = Committee::new(vec![1, 2, 4], 2).unwrap();
+        let left = plan(&grown, &shrunk, &policy).unwrap();
+        assert_eq!(left.steps.len(), 1);
+        assert_eq!(left.steps[0].operation, Operation::Refresh);
+        let refreshed = left.execute(&after).unwrap();
+        assert_eq!(
+            recover_secret_fft(&pick(&refreshed, &[2, 4])).unwrap(),
+            secret
+        );
+        let mixed = [pick(&after, &[3]), pick(&refreshed, &[1])].concat();
+        assert_ne!(recover_secret_fft(&mixed).unwrap(), secret);
+        let unavailable = ReconfigurationPolicy {
+            available: Some(vec![1, 3, 4]),
+            ..Default::default()
+        };
+        let left = plan(&grown, &shrunk, &unavailable).unwrap();
+        assert_eq!(left.steps[0].operation, Operation::Reshare);
+        assert_eq!(left.steps[0].participants, vec![1, 3]);
+
+        // Joining and leaving at once, or a threshold change: a single reshare.
+        let swapped = Committee::new(vec![1, 2, 5], 2).unwrap();
+        assert_eq!(
+            plan(&grown, &swapped, &policy).unwrap().steps[0].operation,
+            Operation::Reshare
+        );
+        let raised = Committee::new(vec![2, 4, 6, 8], 3).unwrap();
+        let reshared = plan(&grown, &raised, &policy).unwrap();
+        assert_eq!(reshared.steps.len(), 1);
+        assert_eq!(reshared.estimated_cost(), 4 * COST_PER_SLICE);
+        let reshared = reshared.execute(&after).unwrap();
+        assert_eq!(
+            reshared.iter().map(|s| s.index).collect::<Vec<_>>(),
+            vec![2, 4, 6, 8]
+        );
+        assert_eq!(
+            recover_secret_fft(&pick(&reshared, &[2, 6, 8])).unwrap(),
+            secret
+        );
+
+        // Policy violations.
+        let strict = ReconfigurationPolicy {
+            min_threshold: 3,
+            ..Default::default()
+        };
+        assert!(plan(&current, &grown, &strict).is_err());
+        let offline = ReconfigurationPolicy {
+            available: Some(vec![2]),
+            ..Default::default()
+        };
+        assert!(plan(&current, &grown, &offline).is_err());
+        assert!(Committee::new(vec![1, 1, 2], 2).is_err());
+        assert!(Committee::new(vec![1, 2], 3).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! **recovered module**
+//!
+//! [`RecoveredSecret`]: a reconstructed secret together with the conversions applications need
+//! after recovery, so none of them hand-rolls a scalar-to-key conversion.
+//!
+//! The secret is a scalar `s` modulo the group order `ℓ`. The conversions fall into two kinds:
+//!
+//! - **Encodings of `s`** ([`RecoveredSecret::to_bytes`], [`RecoveredSecret::encode`],
+//!   [`RecoveredSecret::to_bip39_entropy`]) carry the secret itself and can be turned back into it
+//!   with [`RecoveredSecret::from_bytes`]. All of them start from the 32-byte canonical
+//!   little-endian encoding; since `s < ℓ < 2²⁵³` its top three bits are always zero.
+//! - **Derived keys** ([`RecoveredSecret::to_ed25519_seed`], [`RecoveredSecret::to_x25519_secret`])
+//!   are one-way: keyed BLAKE3 derivations of the canonical encoding under a context string per
+//!   purpose. The same secret always yields the same key, keys for different purposes are
+//!   independent, and none of them is related to the group public key `s·G`. `s` itself is never
+//!   used as an Ed25519 seed or X25519 scalar, because both are hashed or clamped on use and the
+//!   resulting key would silently differ from what a reader of the raw bytes expects.
+
+use crate::error::CryptoResult;
+use crate::hash_adapter::Blake3Adapter;
+use crate::redact::Redacted;
+use crate::serialization::scalar_from_slice;
+use curve25519_dalek::scalar::Scalar;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const ED25519_SEED_CONTEXT: &str = "zk-thresh-pro 2024 recovered ed25519 seed v1";
+const X25519_SECRET_CONTEXT: &str = "zk-thresh-pro 2024 recovered x25519 secret v1";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Output encoding of the secret itself for [`RecoveredSecret::encode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecretEncoding {
+    /// The 32 canonical bytes.
+    Raw,
+    /// Lowercase hex of the canonical bytes, 64 characters.
+    Hex,
+    /// Standard padded base64 (RFC 4648 §4) of the canonical bytes, 44 characters.
+    Base64,
+}
+
+impl SecretEncoding {
+    /// Parse `raw`, `hex` or `base64`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "raw" => Some(SecretEncoding::Raw),
+            "hex" => Some(SecretEncoding::Hex),
+            "base64" => Some(SecretEncoding::Base64),
+            _ => None,
+        }
+    }
+}
+
+/// A reconstructed secret.
+///
+/// `Debug` output redacts the secret and shows its fingerprint only; the secret is zeroized on
+/// drop, and every conversion returns zeroizing buffers.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct RecoveredSecret(Scalar);
+
+impl RecoveredSecret {
+    pub fn new(secret: Scalar) -> Self {
+        Self(secret)
+    }
+
+    /// Restore a secret from its canonical encoding, e.g. BIP39 entropy written by
+    /// [`RecoveredSecret::to_bip39_entropy`].
+    ///
+    /// Returns `CryptoError::Serialization` unless `bytes` are 32 bytes encoding a reduced scalar.
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        scalar_from_slice(bytes, "recovered secret").map(Self)
+    }
+
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Canonical little-endian encoding of the scalar.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.0.to_bytes())
+    }
+
+    /// The canonical encoding in the requested `encoding`.
+    pub fn encode(&self, encoding: SecretEncoding) -> Zeroizing<Vec<u8>> {
+        let bytes = self.to_bytes();
+        Zeroizing::new(match encoding {
+            SecretEncoding::Raw => bytes.to_vec(),
+            SecretEncoding::Hex => hex::encode(bytes.as_ref()).into_bytes(),
+            SecretEncoding::Base64 => base64_encode(bytes.as_ref()),
+        })
+    }
+
+    /// 256 bits of BIP39 entropy (a 24-word mnemonic): the canonical encoding itself.
+    ///
+    /// The mnemonic is a paper backup of the secret, not a wallet seed derived from it; checksum
+    /// and word list are left to the BIP39 implementation. The first word is constrained by the
+    /// always-zero top bits.
+    pub fn to_bip39_entropy(&self) -> Zeroizing<[u8; 32]> {
+        self.to_bytes()
+    }
+
+    /// Ed25519 private key seed (RFC 8032 §5.1.5) derived from the secret.
+    pub fn to_ed25519_seed(&self) -> Zeroizing<[u8; 32]> {
+        self.derive(ED25519_SEED_CONTEXT)
+    }
+
+    /// X25519 private key (RFC 7748) derived from the secret; clamping is left to the X25519
+    /// implementation, as for [`crate::wrapping::WrappingSecretKey::from_bytes`].
+    pub fn to_x25519_secret(&self) -> Zeroizing<[u8; 32]> {
+        self.derive(X25519_SECRET_CONTEXT)
+    }
+
+    fn derive(&self, context: &str) -> Zeroizing<[u8; 32]> {
+        let mut hasher = Blake3Adapter::new_derive_key(context);
+        digest::Update::update(&mut hasher, self.to_bytes().as_ref());
+        let mut out = Zeroizing::new([0u8; 32]);
+        hasher.finalize_extended(out.as_mut());
+        out
+    }
+}
+
+impl From<Scalar> for RecoveredSecret {
+    fn from(secret: Scalar) -> Self {
+        Self(secret)
+    }
+}
+
+impl fmt::Debug for RecoveredSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RecoveredSecret")
+            .field(&Redacted(&self.0))
+            .finish()
+    }
+}
+
+fn base64_encode(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = match *chunk {
+            [a] => [a, 0, 0],
+            [a, b] => [a, b, 0],
+            [a, b, c] => [a, b, c],
+            _ => unreachable!("chunks(3) yields one to three bytes"),
+        };
+        let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for k in 0..4 {
+            if k <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * k)) as usize & 63]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovered_secret_conversions() {
+        // RFC 4648 §10 test vectors.
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(input.as_bytes()), expected.as_bytes());
+        }
+
+        let secret = RecoveredSecret::new(Scalar::from(0x0102u64));
+        let mut expected = [0u8; 32];
+        expected[..2].copy_from_slice(&[2, 1]);
+        assert_eq!(*secret.to_bytes(), expected);
+        assert_eq!(
+            secret.encode(SecretEncoding::Hex).as_slice(),
+            hex::encode(expected).as_bytes()
+        );
+        assert_eq!(
+            secret.encode(SecretEncoding::Base64).as_slice(),
+            b"AgEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+        );
+        let restored = RecoveredSecret::from_bytes(secret.to_bip39_entropy().as_ref()).unwrap();
+        assert_eq!(restored.as_scalar(), secret.as_scalar());
+        assert!(RecoveredSecret::from_bytes(&[0xff; 32]).is_err());
+
+        assert_ne!(*secret.to_ed25519_seed(), *secret.to_x25519_secret());
+        assert_ne!(*secret.to_ed25519_seed(), expected);
+        assert_eq!(
+            *secret.to_ed25519_seed(),
+            *RecoveredSecret::new(Scalar::from(0x0102u64)).to_ed25519_seed()
+        );
+        assert!(!format!("{:?}", secret).contains(&hex::encode(expected)));
+    }
+}
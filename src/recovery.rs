@@ -0,0 +1,398 @@
+//! **recovery module**
+//!
+//! Share recovery for a lost participant: `t` other holders help the participant at
+//! `lost_index` rebuild its own slice value and blinding random, without anyone learning the
+//! secret, another holder's slice or (for helpers) the recovered slice.
+//!
+//! The slice at `ℓ = lost_index` is `f(ℓ) = Σ_{i∈H} λ_i·f(i)` over any helper set `H` of at least
+//! `t` holders, with `λ_i` the Lagrange basis of `H` at `ℓ`; the same holds for the blinding
+//! polynomial `g`. The contributions `λ_i·f(i)` would reveal the helpers' slices, so they are
+//! blinded by additive resharing in two rounds of messages:
+//!
+//! 1. Every helper `i` splits `λ_i·f(i)` and `λ_i·g(i)` into one random additive part per helper
+//!    and sends helper `j` its [`RepairPart`] ([`repair_parts`]) over a confidential channel.
+//! 2. Every helper `j` adds up the parts addressed to it into a [`RepairSum`] ([`repair_sum`]) and
+//!    sends it to the lost participant. A single sum is uniformly random on its own.
+//! 3. The lost participant adds up the sums ([`repair_share`]), checks the result against the
+//!    dealing's [`VssCommitments`] and attaches a fresh proof.
+//!
+//! Everyone agrees beforehand on a [`RepairRequest`] naming the lost index and the helpers (in a
+//! deployment, after authenticating the participant asking for its slice). Colluding helpers
+//! together with the lost participant learn nothing beyond what `t` honest-but-curious holders
+//! already could; a helper sending a wrong part is caught by the commitment check, though not
+//! identified.
+//! [`issue_additional_share`](crate::sharing::issue_additional_share) runs the same rounds in one
+//! process to enroll a new index.
+
+use crate::error::{CryptoError, CryptoResult, VerifyError};
+use crate::lagrange_fft::BarycentricWeights;
+use crate::redact::Redacted;
+use crate::sharing::ShareData;
+use crate::utils;
+use crate::vss::VssCommitments;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::HashSet;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Slice to recover and the holders helping to recover it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RepairRequest {
+    /// Index of the slice to recover.
+    pub lost_index: usize,
+    /// Indices of the helpers, at least the threshold of the dealing.
+    pub helpers: Vec<usize>,
+}
+
+/// Round-one part from helper `sender` to helper `recipient`.
+///
+/// Must travel over a confidential, authenticated channel. `Debug` output is redacted and the
+/// part is zeroized on drop.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct RepairPart {
+    #[zeroize(skip)]
+    pub lost_index: usize,
+    #[zeroize(skip)]
+    pub sender: usize,
+    #[zeroize(skip)]
+    pub recipient: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub share: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub random: Scalar,
+}
+
+/// Round-two sum from helper `sender` to the lost participant.
+///
+/// Must travel over a confidential, authenticated channel. `Debug` output is redacted and the
+/// sum is zeroized on drop.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Zeroize, ZeroizeOnDrop)]
+pub struct RepairSum {
+    #[zeroize(skip)]
+    pub lost_index: usize,
+    #[zeroize(skip)]
+    pub sender: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub share: Scalar,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub random: Scalar,
+}
+
+impl fmt::Debug for RepairPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RepairPart")
+            .field("lost_index", &self.lost_index)
+            .field("sender", &self.sender)
+            .field("recipient", &self.recipient)
+            .field("share", &Redacted(&self.share))
+            .field("random", &Redacted(&self.random))
+            .finish()
+    }
+}
+
+impl fmt::Debug for RepairSum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RepairSum")
+            .field("lost_index", &self.lost_index)
+            .field("sender", &self.sender)
+            .field("share", &Redacted(&self.share))
+            .field("random", &Redacted(&self.random))
+            .finish()
+    }
+}
+
+fn invalid(field: &str, reason: String) -> CryptoError {
+    CryptoError::Validation {
+        field: field.to_string(),
+        reason,
+    }
+}
+
+impl RepairRequest {
+    /// Request to recover `lost_index` with the help of the holders at `helpers`.
+    ///
+    /// Returns `CryptoError::Validation` for a zero lost index, no helper, a zero or repeated
+    /// helper index, or a lost index among the helpers.
+    pub fn new(lost_index: usize, helpers: Vec<usize>) -> CryptoResult<Self> {
+        let request = RepairRequest {
+            lost_index,
+            helpers,
+        };
+        request.check()?;
+        Ok(request)
+    }
+
+    /// The checks of [`Self::new`], for a request received from elsewhere.
+    pub fn check(&self) -> CryptoResult<()> {
+        if self.lost_index == 0 {
+            return Err(invalid("lost_index", "index 0 is reserved".to_string()));
+        }
+        if self.helpers.is_empty() {
+            return Err(invalid("helpers", "no helper".to_string()));
+        }
+        let mut seen = HashSet::new();
+        for &helper in &self.helpers {
+            if helper == 0 || !seen.insert(helper) {
+                return Err(invalid(
+                    "helpers",
+                    format!("helper index {} is zero or repeated", helper),
+                ));
+            }
+        }
+        if seen.contains(&self.lost_index) {
+            return Err(invalid(
+                "helpers",
+                format!("the lost index {} cannot help", self.lost_index),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Lagrange basis of the helpers at the lost index, in the order of `helpers`.
+    pub(crate) fn weights(&self) -> CryptoResult<Vec<Scalar>> {
+        self.check()?;
+        let weights = BarycentricWeights::new(&self.helpers)
+            .map_err(|e| invalid("helpers", e.to_string()))?;
+        Ok(weights.basis_at(Scalar::from(self.lost_index as u64)))
+    }
+
+    fn position(&self, index: usize, field: &str) -> CryptoResult<usize> {
+        self.helpers
+            .iter()
+            .position(|&h| h == index)
+            .ok_or_else(|| invalid(field, format!("{} is not a helper", index)))
+    }
+}
+
+/// Round one at the helper holding `share`: one part of its weighted contribution per helper,
+/// including itself.
+///
+/// Returns `CryptoError::Validation` if the request is invalid or `share` is not one of its
+/// helpers' slices, and `CryptoError::InsufficientEntropy` if the random generator fails.
+pub fn repair_parts(share: &ShareData, request: &RepairRequest) -> CryptoResult<Vec<RepairPart>> {
+    let weights = request.weights()?;
+    let weight = weights[request.position(share.index, "share")?];
+    let mut value = share.share * weight;
+    let mut random = share.random * weight;
+    let mut rng = utils::new_rng();
+    let mut parts = Vec::with_capacity(request.helpers.len());
+    for &recipient in &request.helpers[1..] {
+        let part = RepairPart {
+            lost_index: request.lost_index,
+            sender: share.index,
+            recipient,
+            share: utils::random_scalar(&mut rng)?,
+            random: utils::random_scalar(&mut rng)?,
+        };
+        value -= part.share;
+        random -= part.random;
+        parts.push(part);
+    }
+    parts.insert(
+        0,
+        RepairPart {
+            lost_index: request.lost_index,
+            sender: share.index,
+            recipient: request.helpers[0],
+            share: value,
+            random,
+        },
+    );
+    value.zeroize();
+    random.zeroize();
+    Ok(parts)
+}
+
+/// Round two at helper `own_index`: the sum of the parts addressed to it, one from every helper.
+///
+/// Returns `CryptoError::Validation` if the request is invalid, `own_index` is not a helper, or
+/// `parts` are not exactly one part from every helper to `own_index` for this lost index.
+pub fn repair_sum(
+    request: &RepairRequest,
+    own_index: usize,
+    parts: &[RepairPart],
+) -> CryptoResult<RepairSum> {
+    request.check()?;
+    request.position(own_index, "own_index")?;
+    let mut senders = HashSet::new();
+    for part in parts {
+        if part.lost_index != request.lost_index || part.recipient != own_index {
+            return Err(invalid(
+                "parts",
+                format!(
+                    "part from {} is for another recipient or slice",
+                    part.sender
+                ),
+            ));
+        }
+        request.position(part.sender, "parts")?;
+        if !senders.insert(part.sender) {
+            return Err(invalid(
+                "parts",
+                format!("two parts from helper {}", part.sender),
+            ));
+        }
+    }
+    if senders.len() != request.helpers.len() {
+        return Err(invalid(
+            "parts",
+            format!(
+                "{} parts for {} helpers",
+                senders.len(),
+                request.helpers.len()
+            ),
+        ));
+    }
+    Ok(RepairSum {
+        lost_index: request.lost_index,
+        sender: own_index,
+        share: parts.iter().map(|p| p.share).sum(),
+        random: parts.iter().map(|p| p.random).sum(),
+    })
+}
+
+/// Slice value and random at the lost index from one sum per helper, unchecked.
+pub(crate) fn combine_sums(
+    request: &RepairRequest,
+    sums: &[RepairSum],
+) -> CryptoResult<(Scalar, Scalar)> {
+    request.check()?;
+    let mut senders = HashSet::new();
+    for sum in sums {
+        if sum.lost_index != request.lost_index {
+            return Err(invalid(
+                "sums",
+                format!("sum from {} is for another slice", sum.sender),
+            ));
+        }
+        request.position(sum.sender, "sums")?;
+        if !senders.insert(sum.sender) {
+            return Err(invalid(
+                "sums",
+                format!("two sums from helper {}", sum.sender),
+            ));
+        }
+    }
+    if senders.len() != request.helpers.len() {
+        return Err(invalid(
+            "sums",
+            format!(
+                "{} sums for {} helpers",
+                senders.len(),
+                request.helpers.len()
+            ),
+        ));
+    }
+    Ok((
+        sums.iter().map(|s| s.share).sum(),
+        sums.iter().map(|s| s.random).sum(),
+    ))
+}
+
+/// Final step at the lost participant: its slice, checked against the dealing's commitments and
+/// with a fresh proof.
+///
+/// # Return value
+///
+/// Returns the recovered slice; `CryptoError::Validation` if the request is invalid or `sums`
+/// are not exactly one sum from every helper for this lost index; or `CryptoError::SecurityViolation`
+/// (`VerifyError::CommitmentMismatch`) if the result does not match `commitments`, because a
+/// helper sent a wrong part or sum or there were fewer helpers than the threshold.
+pub fn repair_share(
+    request: &RepairRequest,
+    sums: &[RepairSum],
+    commitments: &VssCommitments,
+) -> CryptoResult<ShareData> {
+    let (mut share, mut random) = combine_sums(request, sums)?;
+    let index = request.lost_index;
+    let commitment = utils::pedersen_commit(&share, &random);
+    if commitment != commitments.expected_commitment(index) {
+        share.zeroize();
+        random.zeroize();
+        return Err(VerifyError::CommitmentMismatch { index }.into());
+    }
+    let proof = crate::proof::generate_proof(share, random, index, commitment)?;
+    Ok(ShareData {
+        index,
+        share,
+        commitment,
+        random,
+        proof,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares_with_commitments;
+
+    /// Both rounds over JSON for the helpers `helpers` of `shares`.
+    fn run(shares: &[ShareData], request: &RepairRequest) -> Vec<RepairSum> {
+        let helpers: Vec<&ShareData> = request
+            .helpers
+            .iter()
+            .map(|&h| shares.iter().find(|s| s.index == h).unwrap())
+            .collect();
+        let parts: Vec<RepairPart> = helpers
+            .iter()
+            .flat_map(|s| repair_parts(s, request).unwrap())
+            .map(|p| serde_json::from_str(&serde_json::to_string(&p).unwrap()).unwrap())
+            .collect();
+        helpers
+            .iter()
+            .map(|s| {
+                let mine: Vec<RepairPart> = parts
+                    .iter()
+                    .filter(|p| p.recipient == s.index)
+                    .cloned()
+                    .collect();
+                repair_sum(request, s.index, &mine).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lost_share_is_recovered_by_helpers() {
+        let secret = Scalar::from(77u64);
+        let (shares, commitments) = generate_key_shares_with_commitments(secret, 3, 6).unwrap();
+        let request = RepairRequest::new(2, vec![5, 1, 4]).unwrap();
+        let sums = run(&shares, &request);
+        // No single sum is the lost slice.
+        assert!(sums.iter().all(|s| s.share != shares[1].share));
+        let repaired = repair_share(&request, &sums, &commitments).unwrap();
+        assert_eq!(repaired.share, shares[1].share);
+        assert_eq!(repaired.random, shares[1].random);
+        commitments.verify_share(&repaired).unwrap();
+        assert!(format!("{:?}", sums[0]).contains("RepairSum"));
+
+        // More helpers than the threshold work too; fewer do not.
+        let request = RepairRequest::new(6, vec![1, 2, 3, 4]).unwrap();
+        let repaired = repair_share(&request, &run(&shares, &request), &commitments).unwrap();
+        assert_eq!(repaired.share, shares[5].share);
+        let request = RepairRequest::new(6, vec![1, 2]).unwrap();
+        assert!(matches!(
+            repair_share(&request, &run(&shares, &request), &commitments),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // A wrong part is caught at the end.
+        let request = RepairRequest::new(2, vec![1, 3, 4]).unwrap();
+        let mut sums = run(&shares, &request);
+        sums[1].share += Scalar::ONE;
+        assert!(matches!(
+            repair_share(&request, &sums, &commitments),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(matches!(
+            repair_share(&request, &sums[..2], &commitments),
+            Err(CryptoError::Validation { .. })
+        ));
+
+        assert!(RepairRequest::new(0, vec![1, 2]).is_err());
+        assert!(RepairRequest::new(2, vec![1, 2, 3]).is_err());
+        assert!(RepairRequest::new(2, vec![1, 1, 3]).is_err());
+        assert!(repair_parts(&shares[5], &RepairRequest::new(2, vec![1, 3, 4]).unwrap()).is_err());
+        let parts = repair_parts(&shares[0], &request).unwrap();
+        assert!(repair_sum(&request, 3, &parts).is_err());
+    }
+}
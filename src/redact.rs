@@ -0,0 +1,78 @@
+//! **redact module**
+//!
+//! Redacting `Debug`/`Display` helpers for secret-bearing values. Secrets are never printed;
+//! instead a short BLAKE3 fingerprint is shown so that log lines can still be correlated
+//! (e.g. "the share recovered here is the same one dealt there") without exposing key material.
+//!
+//! Building with the `insecure-debug` feature enables `reveal_for_debug()` on secret-bearing
+//! types, which prints the raw values. It must never be enabled in production builds.
+
+use crate::hash_adapter::Blake3Adapter;
+use curve25519_dalek::scalar::Scalar;
+use std::fmt;
+
+/// Domain separation context for secret fingerprints.
+const FINGERPRINT_CONTEXT: &str = "zk-thresh-pro 2024 secret fingerprint v1";
+
+/// Number of digest bytes shown in a fingerprint.
+const FINGERPRINT_LEN: usize = 8;
+
+/// Compute the hex fingerprint of a secret scalar.
+///
+/// The fingerprint is a domain-separated BLAKE3 derivation truncated to 8 bytes, so it is
+/// stable across runs but reveals nothing useful about the scalar itself.
+pub fn fingerprint(secret: &Scalar) -> String {
+    let mut hasher = Blake3Adapter::new_derive_key(FINGERPRINT_CONTEXT);
+    digest::Update::update(&mut hasher, secret.as_bytes());
+    let mut out = [0u8; FINGERPRINT_LEN];
+    hasher.finalize_extended(&mut out);
+    hex::encode(out)
+}
+
+/// Wrapper that formats a secret scalar as `<redacted fp:…>` in both `Debug` and `Display`.
+#[derive(Clone, Copy)]
+pub struct Redacted<'a>(pub &'a Scalar);
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted fp:{}>", fingerprint(self.0))
+    }
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "insecure-debug")]
+impl Redacted<'_> {
+    /// Render the wrapped scalar as hex. Only available with `insecure-debug`.
+    pub fn reveal_for_debug(&self) -> String {
+        hex::encode(self.0.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_hides_secret() {
+        let secret = Scalar::from(42u64);
+        let rendered = format!("{:?}", Redacted(&secret));
+
+        assert!(rendered.starts_with("<redacted fp:"));
+        assert!(!rendered.contains(&hex::encode(secret.as_bytes())));
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let a = Scalar::from(7u64);
+        let b = Scalar::from(8u64);
+
+        assert_eq!(fingerprint(&a), fingerprint(&a));
+        assert_ne!(fingerprint(&a), fingerprint(&b));
+        assert_eq!(fingerprint(&a).len(), 2 * FINGERPRINT_LEN);
+    }
+}
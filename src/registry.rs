@@ -0,0 +1,205 @@
+//! **registry module**
+//!
+//! [`ShareRegistry`]: the slices a deployment has issued, keyed by `(operation_id, index)` of the
+//! dealing, refresh or reshare that issued them, and which of them have been revoked.
+//!
+//! Revoking a slice quarantines it: it stays in the registry with the reason it was revoked, and
+//! `EnterpriseCryptoSystem::recover_secret_enterprise` refuses to recover from it. A slice is
+//! recognised by its index and Pedersen commitment, which is unique to the dealing that produced
+//! it, so a revoked slice is rejected whatever context the recovery runs under, while the slice of
+//! the same index issued by a later refresh is not.
+
+use crate::context::OperationContext;
+use crate::error::{CryptoError, CryptoResult};
+use crate::sharing::ShareData;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use serde::{Deserialize, Serialize};
+
+/// Whether an issued slice may still be used.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShareStatus {
+    Issued,
+    Revoked {
+        reason: String,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        revoked_at: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+/// One issued slice.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// Operation that issued the slice.
+    pub operation_id: String,
+    pub index: usize,
+    /// Key the slice belongs to.
+    pub key_id: String,
+    /// Share epoch the slice was issued in.
+    pub epoch: u64,
+    /// Pedersen commitment of the slice.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+    pub status: ShareStatus,
+}
+
+impl RegistryEntry {
+    pub fn is_revoked(&self) -> bool {
+        matches!(self.status, ShareStatus::Revoked { .. })
+    }
+}
+
+/// Issued slices and their revocation status, in issuing order.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ShareRegistry {
+    entries: Vec<RegistryEntry>,
+}
+
+impl ShareRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `shares` as issued by `context.operation_id`.
+    ///
+    /// Re-issuing an index under the same operation replaces its entry, including a revocation.
+    pub fn issue(&mut self, context: &OperationContext, shares: &[ShareData]) {
+        for share in shares {
+            let entry = RegistryEntry {
+                operation_id: context.operation_id.clone(),
+                index: share.index,
+                key_id: context.key_id.clone(),
+                epoch: context.epoch,
+                commitment: share.commitment,
+                status: ShareStatus::Issued,
+            };
+            match self.position(&context.operation_id, share.index) {
+                Some(position) => self.entries[position] = entry,
+                None => self.entries.push(entry),
+            }
+        }
+    }
+
+    /// Append `entries` as they are, e.g. from an imported
+    /// [`PublicState`](crate::public_state::PublicState); none of their operations may have
+    /// issued slices here yet.
+    #[cfg(feature = "dealer")]
+    pub(crate) fn restore(&mut self, entries: &[RegistryEntry]) {
+        self.entries.extend_from_slice(entries);
+    }
+
+    /// Revoke slice `index` issued by `operation_id`; revoking it again keeps the first reason.
+    ///
+    /// Returns `CryptoError::Validation` if the slice was never issued.
+    pub fn revoke(
+        &mut self,
+        operation_id: &str,
+        index: usize,
+        reason: &str,
+    ) -> CryptoResult<&RegistryEntry> {
+        let position =
+            self.position(operation_id, index)
+                .ok_or_else(|| CryptoError::Validation {
+                    field: "index".to_string(),
+                    reason: format!(
+                        "slice {} was not issued by operation {}",
+                        index, operation_id
+                    ),
+                })?;
+        let entry = &mut self.entries[position];
+        if !entry.is_revoked() {
+            entry.status = ShareStatus::Revoked {
+                reason: reason.to_string(),
+                revoked_at: chrono::Utc::now(),
+            };
+        }
+        Ok(entry)
+    }
+
+    /// Revoke every slice issued by `operation_id` that is not revoked yet; returns how many.
+    pub fn revoke_operation(&mut self, operation_id: &str, reason: &str) -> usize {
+        let revoked_at = chrono::Utc::now();
+        let mut count = 0;
+        for entry in &mut self.entries {
+            if entry.operation_id == operation_id && !entry.is_revoked() {
+                entry.status = ShareStatus::Revoked {
+                    reason: reason.to_string(),
+                    revoked_at,
+                };
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// The entry of slice `index` issued by `operation_id`.
+    pub fn get(&self, operation_id: &str, index: usize) -> Option<&RegistryEntry> {
+        self.position(operation_id, index).map(|p| &self.entries[p])
+    }
+
+    /// Whether slice `index` issued by `operation_id` has been revoked.
+    pub fn is_revoked(&self, operation_id: &str, index: usize) -> bool {
+        self.get(operation_id, index)
+            .is_some_and(RegistryEntry::is_revoked)
+    }
+
+    /// The revoked entry `share` was issued under, if any.
+    pub fn revoked_entry(&self, share: &ShareData) -> Option<&RegistryEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.is_revoked() && e.index == share.index && e.commitment == share.commitment)
+    }
+
+    /// Every revoked entry, in issuing order.
+    pub fn revoked(&self) -> impl Iterator<Item = &RegistryEntry> {
+        self.entries.iter().filter(|e| e.is_revoked())
+    }
+
+    /// Every entry, in issuing order.
+    pub fn entries(&self) -> &[RegistryEntry] {
+        &self.entries
+    }
+
+    fn position(&self, operation_id: &str, index: usize) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.operation_id == operation_id && e.index == index)
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::{generate_key_shares, update_shares};
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_revocation_matches_issued_slice_only() {
+        let context = OperationContext::new("key-1", 0, "deal-1");
+        let shares = generate_key_shares(Scalar::from(7u64), 2, 3).unwrap();
+        let mut registry = ShareRegistry::new();
+        registry.issue(&context, &shares);
+
+        assert!(registry.revoke("deal-1", 4, "lost").is_err());
+        assert!(registry.revoke("deal-2", 2, "lost").is_err());
+        registry.revoke("deal-1", 2, "laptop stolen").unwrap();
+        registry.revoke("deal-1", 2, "again").unwrap();
+        assert!(matches!(
+            &registry.get("deal-1", 2).unwrap().status,
+            ShareStatus::Revoked { reason, .. } if reason == "laptop stolen"
+        ));
+        assert!(registry.is_revoked("deal-1", 2));
+        assert!(!registry.is_revoked("deal-1", 1));
+        assert_eq!(registry.revoked().count(), 1);
+
+        assert!(registry.revoked_entry(&shares[1]).is_some());
+        assert!(registry.revoked_entry(&shares[0]).is_none());
+        let refreshed = update_shares(&shares, 2).unwrap();
+        registry.issue(&context.next_epoch("refresh-1"), &refreshed);
+        assert!(registry.revoked_entry(&refreshed[1]).is_none());
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: ShareRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        assert!(restored.is_revoked("deal-1", 2));
+    }
+}
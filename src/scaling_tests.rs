@@ -0,0 +1,144 @@
+//! Committee-size scaling suite (feature `scale-tests`): the sizes and budgets reported by
+//! [`limits()`], enforced.
+//!
+//! Run with `cargo test --release --features scale-tests scaling`. The heap is measured by a
+//! counting global allocator over the whole test binary, so the tests of this module hold a lock
+//! while they run and other tests should be filtered out; time budgets are only enforced in
+//! optimized builds.
+
+use crate::dkg::{DkgOutput, DkgParticipant, Round1Broadcast, Round2Share, UnsignedDkgParticipant};
+use crate::limits::{limits, VerifiedLimit};
+use crate::sharing::{generate_key_shares, ShareData};
+use crate::utils::{new_rng, random_scalar};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// The system allocator, keeping track of the bytes allocated and of their peak.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Serializes the measurements of this module.
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// Run `operation`, then check its wall-clock time and peak heap growth against `limit`.
+fn within_budget<T>(name: &str, limit: VerifiedLimit, operation: impl FnOnce() -> T) -> T {
+    let _guard = MEASURING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let baseline = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let started = Instant::now();
+    let output = operation();
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let peak = PEAK.load(Ordering::Relaxed).saturating_sub(baseline);
+    println!(
+        "{}: size {}, threshold {}: {} ms, peak heap {} bytes",
+        name, limit.size, limit.threshold, elapsed_ms, peak
+    );
+    assert!(
+        peak <= limit.memory_budget_bytes,
+        "{} peaked at {} heap bytes, budget {}",
+        name,
+        peak,
+        limit.memory_budget_bytes
+    );
+    if !cfg!(debug_assertions) {
+        assert!(
+            elapsed_ms <= limit.time_budget_ms,
+            "{} took {} ms, budget {}",
+            name,
+            elapsed_ms,
+            limit.time_budget_ms
+        );
+    }
+    output
+}
+
+#[test]
+fn test_scaling_dealing() {
+    let limit = limits().dealing;
+    let secret = random_scalar(&mut new_rng()).unwrap();
+    let shares = within_budget("dealing", limit, || {
+        let shares = generate_key_shares(secret, limit.threshold, limit.size).unwrap();
+        crate::vss::verify_share_validity_batch(&shares).unwrap();
+        shares
+    });
+    assert_eq!(shares.len(), limit.size);
+    let last: Vec<ShareData> = shares[limit.size - limit.threshold..].to_vec();
+    assert_eq!(crate::hazmat::recover_secret_fft(&last).unwrap(), secret);
+}
+
+#[test]
+fn test_scaling_recovery() {
+    let limit = limits().recovery;
+    let secret = random_scalar(&mut new_rng()).unwrap();
+    let shares = generate_key_shares(secret, limit.threshold, limit.size).unwrap();
+    let shares = &shares[..limit.threshold];
+    let recovered = within_budget("recovery", limit, || crate::recover_secret(shares).unwrap());
+    assert_eq!(recovered, secret);
+}
+
+#[test]
+fn test_scaling_dkg() {
+    let limit = limits().dkg;
+    let outputs = within_budget("dkg", limit, || {
+        let mut parties: Vec<UnsignedDkgParticipant> = (1..=limit.size)
+            .map(|i| DkgParticipant::insecure_unsigned(i, limit.threshold, limit.size).unwrap())
+            .collect();
+        let broadcasts: Vec<Round1Broadcast> =
+            parties.iter_mut().map(|p| p.round1().unwrap()).collect();
+        for p in parties.iter_mut() {
+            for b in &broadcasts {
+                p.receive_round1(b.clone()).unwrap();
+            }
+        }
+        let messages: Vec<Round2Share> = parties
+            .iter_mut()
+            .flat_map(|p| p.round2().unwrap())
+            .collect();
+        for m in messages {
+            let recipient = m.recipient;
+            assert!(parties[recipient - 1].receive_round2(m).unwrap().is_none());
+        }
+        parties
+            .iter_mut()
+            .map(|p| p.finalize().unwrap())
+            .collect::<Vec<DkgOutput>>()
+    });
+    assert!(outputs
+        .iter()
+        .all(|o| o.group_public_key == outputs[0].group_public_key
+            && o.qualified.len() == limit.size));
+    let shares: Vec<ShareData> = outputs[..limit.threshold]
+        .iter()
+        .map(|o| o.share.clone())
+        .collect();
+    let secret = crate::hazmat::recover_secret_fft(&shares).unwrap();
+    assert_eq!(
+        curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT * secret,
+        outputs[0].group_public_key
+    );
+}
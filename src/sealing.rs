@@ -0,0 +1,282 @@
+//! **sealing module**
+//!
+//! At-rest sealing of slices held by custodians.
+//!
+//! A [`SealedShare`] encrypts and authenticates one [`ShareData`] under a 32-byte custodian
+//! [`SealingKey`] (encrypt-then-MAC over BLAKE3):
+//!
+//! - an encryption subkey and a MAC subkey are derived from the sealing key with separate
+//!   `derive_key` contexts;
+//! - the keystream is the keyed BLAKE3 XOF of a fresh 24-byte nonce under the encryption subkey;
+//! - the tag is keyed BLAKE3 under the MAC subkey over the version, the slice index, the nonce and
+//!   the ciphertext.
+//!
+//! The slice index stays in the clear so that custodians holding several slices can tell the
+//! envelopes apart; it is covered by the tag.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::sharing::ShareData;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Version of the sealed slice envelope.
+pub const SEALED_SHARE_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`SealedShare`]; the proof system is that of the sealed slice.
+pub const SEALED_SHARE_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: None,
+    aead: Some(AlgorithmId::Blake3XofEtm),
+    kdf: Some(AlgorithmId::Blake3DeriveKey),
+    proof: Some(AlgorithmId::SchnorrPedersen),
+};
+
+/// Nonce length of a sealed slice.
+pub const NONCE_LENGTH: usize = 24;
+
+/// Tag length of a sealed slice.
+pub const TAG_LENGTH: usize = 32;
+
+const ENCRYPTION_CONTEXT: &str = "zk-thresh-pro 2024 share sealing encryption v1";
+const MAC_CONTEXT: &str = "zk-thresh-pro 2024 share sealing mac v1";
+const DIGEST_CONTEXT: &str = "zk-thresh-pro 2024 sealed share digest v1";
+
+/// 32-byte custodian key used to seal and unseal slices at rest.
+///
+/// `Debug` output is redacted; the key is zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct SealingKey([u8; 32]);
+
+impl SealingKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a key stored as 64 hex characters (surrounding whitespace is ignored).
+    pub fn from_hex(encoded: &str) -> CryptoResult<Self> {
+        let mut bytes = Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(encoded.trim(), bytes.as_mut()).map_err(|e| {
+            CryptoError::Serialization {
+                details: format!("sealing key must be 32 hex-encoded bytes: {}", e),
+            }
+        })?;
+        Ok(Self(*bytes))
+    }
+
+    /// Draw a fresh key from the OS generator.
+    #[cfg(feature = "dealer")]
+    pub fn generate() -> CryptoResult<Self> {
+        let mut bytes = [0u8; 32];
+        crate::utils::fill_random(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    /// Hex encoding of the key, for storing it in a key file.
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.0))
+    }
+
+    fn subkey(&self, context: &str) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(context);
+        digest::Update::update(&mut hasher, &self.0);
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+impl fmt::Debug for SealingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SealingKey(<redacted>)")
+    }
+}
+
+fn default_algorithms() -> AlgorithmSuite {
+    SEALED_SHARE_ALGORITHMS
+}
+
+/// A slice encrypted and authenticated under a [`SealingKey`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SealedShare {
+    /// Envelope version, [`SEALED_SHARE_VERSION`].
+    pub version: u8,
+    /// Algorithms the envelope was produced with; [`SEALED_SHARE_ALGORITHMS`] when absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    /// Index of the sealed slice (authenticated, not encrypted).
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub ciphertext: Vec<u8>,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub tag: Vec<u8>,
+}
+
+impl SealedShare {
+    /// Digest of the envelope, identifying one stored copy without revealing anything about it.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(DIGEST_CONTEXT);
+        digest::Update::update(&mut hasher, &[self.version]);
+        digest::Update::update(&mut hasher, &(self.index as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &self.nonce);
+        digest::Update::update(&mut hasher, &(self.ciphertext.len() as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &self.ciphertext);
+        digest::Update::update(&mut hasher, &self.tag);
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+/// XOR `data` with the keystream for `nonce`.
+fn apply_keystream(key: &SealingKey, nonce: &[u8], data: &mut [u8]) {
+    let mut subkey = key.subkey(ENCRYPTION_CONTEXT);
+    let mut hasher = Blake3Adapter::new_keyed(&subkey);
+    subkey.zeroize();
+    digest::Update::update(&mut hasher, nonce);
+    let mut keystream = Zeroizing::new(vec![0u8; data.len()]);
+    hasher.finalize_extended(&mut keystream);
+    for (byte, k) in data.iter_mut().zip(keystream.iter()) {
+        *byte ^= k;
+    }
+}
+
+/// Authentication tag over the envelope header and ciphertext.
+fn compute_tag(
+    key: &SealingKey,
+    version: u8,
+    index: usize,
+    nonce: &[u8],
+    ciphertext: &[u8],
+) -> [u8; TAG_LENGTH] {
+    let mut subkey = key.subkey(MAC_CONTEXT);
+    let mut hasher = Blake3Adapter::new_keyed(&subkey);
+    subkey.zeroize();
+    digest::Update::update(&mut hasher, &[version]);
+    digest::Update::update(&mut hasher, &(index as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, nonce);
+    digest::Update::update(&mut hasher, &(ciphertext.len() as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, ciphertext);
+    let mut tag = [0u8; TAG_LENGTH];
+    hasher.finalize_extended(&mut tag);
+    tag
+}
+
+/// Seal `share` under `key` with a fresh nonce.
+///
+/// Sealing the same slice twice yields unrelated envelopes, so re-sealing after every unseal
+/// leaves no link between successive stored copies.
+#[cfg(feature = "dealer")]
+pub fn seal(share: &ShareData, key: &SealingKey) -> CryptoResult<SealedShare> {
+    let mut nonce = vec![0u8; NONCE_LENGTH];
+    crate::utils::fill_random(&mut nonce)?;
+    let plaintext =
+        Zeroizing::new(
+            serde_json::to_vec(share).map_err(|e| CryptoError::Serialization {
+                details: e.to_string(),
+            })?,
+        );
+    let mut ciphertext = plaintext.to_vec();
+    apply_keystream(key, &nonce, &mut ciphertext);
+    let tag = compute_tag(key, SEALED_SHARE_VERSION, share.index, &nonce, &ciphertext);
+    Ok(SealedShare {
+        version: SEALED_SHARE_VERSION,
+        algorithms: SEALED_SHARE_ALGORITHMS,
+        index: share.index,
+        nonce,
+        ciphertext,
+        tag: tag.to_vec(),
+    })
+}
+
+/// Authenticate and decrypt a sealed slice.
+///
+/// Returns `CryptoError::Serialization` for a malformed envelope and
+/// `CryptoError::SecurityViolation` if authentication fails (wrong key or tampering).
+pub fn unseal(sealed: &SealedShare, key: &SealingKey) -> CryptoResult<ShareData> {
+    if sealed.version != SEALED_SHARE_VERSION {
+        return Err(CryptoError::Serialization {
+            details: format!("unsupported sealed share version {}", sealed.version),
+        });
+    }
+    sealed
+        .algorithms
+        .require(&SEALED_SHARE_ALGORITHMS, "sealed share")?;
+    if sealed.nonce.len() != NONCE_LENGTH || sealed.tag.len() != TAG_LENGTH {
+        return Err(CryptoError::Serialization {
+            details: "sealed share nonce or tag has the wrong length".to_string(),
+        });
+    }
+    let mut expected = [0u8; TAG_LENGTH];
+    expected.copy_from_slice(&sealed.tag);
+    let tag = compute_tag(
+        key,
+        sealed.version,
+        sealed.index,
+        &sealed.nonce,
+        &sealed.ciphertext,
+    );
+    // blake3::Hash compares in constant time.
+    if blake3::Hash::from(tag) != blake3::Hash::from(expected) {
+        return Err(CryptoError::SecurityViolation {
+            details: format!("sealed share {} failed authentication", sealed.index),
+        });
+    }
+
+    let mut plaintext = Zeroizing::new(sealed.ciphertext.clone());
+    apply_keystream(key, &sealed.nonce, &mut plaintext);
+    let share: ShareData =
+        serde_json::from_slice(&plaintext).map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })?;
+    if share.index != sealed.index {
+        return Err(CryptoError::SecurityViolation {
+            details: format!(
+                "sealed share header index {} does not match slice {}",
+                sealed.index, share.index
+            ),
+        });
+    }
+    Ok(share)
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use curve25519_dalek::Scalar;
+
+    #[test]
+    fn test_seal_roundtrip_and_tampering() {
+        let shares = generate_key_shares(Scalar::from(5u64), 2, 3).unwrap();
+        let key = SealingKey::generate().unwrap();
+        let sealed = seal(&shares[1], &key).unwrap();
+
+        let unsealed = unseal(&sealed, &key).unwrap();
+        assert_eq!(unsealed.index, shares[1].index);
+        assert_eq!(unsealed.share, shares[1].share);
+        assert_ne!(seal(&shares[1], &key).unwrap().nonce, sealed.nonce);
+
+        let wrong_key = SealingKey::generate().unwrap();
+        assert!(matches!(
+            unseal(&sealed, &wrong_key),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let mut tampered = sealed.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert!(unseal(&tampered, &key).is_err());
+        let mut relabelled = sealed.clone();
+        relabelled.index = 3;
+        assert!(unseal(&relabelled, &key).is_err());
+
+        let json = serde_json::to_string(&sealed).unwrap();
+        let parsed: SealedShare = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, sealed);
+        assert_eq!(SealingKey::from_hex(&key.to_hex()).unwrap().0, key.0);
+    }
+}
@@ -1,35 +1,141 @@
 //! **serialisation module**
 //!
 //! This module implements hex serialisation and deserialisation of Scalar and RistrettoPoint for easy data exchange and persistent storage with external systems.
+//!
+//! The encoding follows the serializer: human-readable formats (JSON) get lowercase hex strings,
+//! binary formats (bincode, CBOR, ...) get the raw 32 bytes, roughly halving the size of every
+//! serialized slice. Fixed-length binary encodings that need no serde format at all are provided
+//! by `ShareData::to_bytes`, `Proof::to_bytes` and `Key::to_bytes`.
+//!
+//! [`to_mnemonic`] and [`from_mnemonic`] write a slice as words of the SLIP-39 wordlist for
+//! custodians who keep it on paper.
+//! With the `qr` feature, [`to_qr_payload`] and [`from_qr_payload`] give the error-corrected
+//! bytes of a slice for a printed QR code.
 
+use crate::error::{CryptoError, CryptoResult};
 use curve25519_dalek::traits::IsIdentity;
 use curve25519_dalek::{
     ristretto::{CompressedRistretto, RistrettoPoint},
     scalar::Scalar,
 };
-use hex;
 use serde::{Deserialize, Serialize, Serializer};
 
-/// Encapsulates the serialisation of Scalar (using hex encoding).
+/// Decode a canonical scalar from exactly 32 bytes; `what` names the value in errors.
+pub(crate) fn scalar_from_slice(bytes: &[u8], what: &str) -> CryptoResult<Scalar> {
+    let arr: [u8; 32] = bytes.try_into().map_err(|_| CryptoError::Serialization {
+        details: format!("{} must be 32 bytes, got {}", what, bytes.len()),
+    })?;
+    Option::from(Scalar::from_canonical_bytes(arr)).ok_or_else(|| CryptoError::Serialization {
+        details: format!("{} is not a canonical scalar", what),
+    })
+}
+
+/// Decompress `bytes`, accepting only the canonical encoding: the point must compress back to
+/// exactly `bytes`, so every point has a single accepted encoding.
+pub(crate) fn canonical_point(bytes: &[u8; 32]) -> Option<RistrettoPoint> {
+    let point = CompressedRistretto(*bytes).decompress()?;
+    (point.compress().as_bytes() == bytes).then_some(point)
+}
+
+/// Whether `s` is lowercase hex, the only case the crate emits. Uppercase digits would give the
+/// same bytes a second textual encoding.
+pub(crate) fn is_lower_hex(s: &str) -> bool {
+    s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Decode a canonical, non-identity compressed point from exactly 32 bytes; `what` names the
+/// value in errors.
+pub(crate) fn point_from_slice(bytes: &[u8], what: &str) -> CryptoResult<RistrettoPoint> {
+    let arr: &[u8; 32] = bytes.try_into().map_err(|_| CryptoError::Serialization {
+        details: format!("{} must be 32 bytes, got {}", what, bytes.len()),
+    })?;
+    let point = canonical_point(arr).ok_or_else(|| CryptoError::Serialization {
+        details: format!("{} is not a canonical Ristretto point", what),
+    })?;
+    if point.is_identity() {
+        return Err(CryptoError::Serialization {
+            details: format!("{} is the identity point", what),
+        });
+    }
+    Ok(point)
+}
+
+/// Read a 32-byte string from a binary format, accepting native bytes or a sequence of `u8`.
+fn deserialize_raw_32<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[u8; 32], D::Error> {
+    struct Raw32;
+
+    impl<'de> serde::de::Visitor<'de> for Raw32 {
+        type Value = [u8; 32];
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("32 bytes")
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut arr = [0u8; 32];
+            for (i, byte) in arr.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(serde::de::Error::invalid_length(33, &self));
+            }
+            Ok(arr)
+        }
+    }
+
+    deserializer.deserialize_bytes(Raw32)
+}
+
+/// Read the 32-byte encoding of a scalar or point: hex in human-readable formats, raw otherwise.
+fn deserialize_32<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+    what: &str,
+) -> Result<[u8; 32], D::Error> {
+    if !deserializer.is_human_readable() {
+        return deserialize_raw_32(deserializer);
+    }
+    let s = String::deserialize(deserializer)?;
+    if !is_lower_hex(&s) {
+        return Err(serde::de::Error::custom(format!(
+            "Non-canonical {} encoding: expected lowercase hex",
+            what
+        )));
+    }
+    let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+    bytes
+        .try_into()
+        .map_err(|_| serde::de::Error::custom(format!("Invalid {} length", what)))
+}
+
+/// Encapsulates the serialisation of Scalar (hex in human-readable formats, raw bytes otherwise).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SerScalar(pub Scalar);
 
 impl Serialize for SerScalar {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let bytes = self.0.to_bytes();
-        serializer.serialize_str(&hex::encode(bytes))
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(bytes))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
     }
 }
 
 impl<'de> Deserialize<'de> for SerScalar {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let s = String::deserialize(deserializer)?;
-        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != 32 {
-            return Err(serde::de::Error::custom("Invalid scalar length"));
-        }
-        let mut arr = [0u8; 32];
-        arr.copy_from_slice(&bytes);
+        let arr = deserialize_32(deserializer, "scalar")?;
         match Scalar::from_canonical_bytes(arr).into() {
             Some(scalar) => Ok(SerScalar(scalar)),
             None => Err(serde::de::Error::custom("Invalid scalar canonical bytes")),
@@ -37,28 +143,26 @@ impl<'de> Deserialize<'de> for SerScalar {
     }
 }
 
-/// Encapsulates the serialisation of RistrettoPoint (using hex encoding).
+/// Encapsulates the serialisation of RistrettoPoint (hex in human-readable formats, raw bytes
+/// otherwise).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SerRistrettoPoint(pub RistrettoPoint);
 
 impl Serialize for SerRistrettoPoint {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let comp = self.0.compress();
-        serializer.serialize_str(&hex::encode(comp.as_bytes()))
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(comp.as_bytes()))
+        } else {
+            serializer.serialize_bytes(comp.as_bytes())
+        }
     }
 }
 
 impl<'de> Deserialize<'de> for SerRistrettoPoint {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let s = String::deserialize(deserializer)?;
-        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != 32 {
-            return Err(serde::de::Error::custom("Invalid point length")); // length error
-        }
-        let mut arr = [0u8; 32];
-        arr.copy_from_slice(&bytes);
-        let comp = CompressedRistretto(arr);
-        match comp.decompress() {
+        let arr = deserialize_32(deserializer, "point")?;
+        match canonical_point(&arr) {
             Some(point) => {
                 // Add point legality check: deserialization to identity elements is not allowed
                 if !point.is_identity() {
@@ -67,7 +171,9 @@ impl<'de> Deserialize<'de> for SerRistrettoPoint {
                     Err(serde::de::Error::custom("Invalid point: identity"))
                 }
             }
-            None => Err(serde::de::Error::custom("Invalid compressed point")),
+            None => Err(serde::de::Error::custom(
+                "Invalid compressed point: not a canonical encoding",
+            )),
         }
     }
 }
@@ -79,7 +185,7 @@ pub mod serialize_scalar_helpers {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     pub fn serialize<S: Serializer>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error> {
-        SerScalar(scalar.clone()).serialize(serializer)
+        SerScalar(*scalar).serialize(serializer)
     }
 
     pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scalar, D::Error> {
@@ -87,6 +193,26 @@ pub mod serialize_scalar_helpers {
     }
 }
 
+/// [`serialize_scalar_helpers`] for a `Zeroizing<Scalar>` field.
+pub mod serialize_zeroizing_scalar_helpers {
+    use curve25519_dalek::scalar::Scalar;
+    use serde::{Deserializer, Serializer};
+    use zeroize::Zeroizing;
+
+    pub fn serialize<S: Serializer>(
+        scalar: &Zeroizing<Scalar>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_scalar_helpers::serialize(scalar, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Zeroizing<Scalar>, D::Error> {
+        super::serialize_scalar_helpers::deserialize(deserializer).map(Zeroizing::new)
+    }
+}
+
 pub mod serialize_ristretto_point_helpers {
     use super::SerRistrettoPoint;
     use curve25519_dalek::ristretto::RistrettoPoint;
@@ -96,7 +222,7 @@ pub mod serialize_ristretto_point_helpers {
         point: &RistrettoPoint,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        SerRistrettoPoint(point.clone()).serialize(serializer)
+        SerRistrettoPoint(*point).serialize(serializer)
     }
 
     pub fn deserialize<'de, D: Deserializer<'de>>(
@@ -105,3 +231,795 @@ pub mod serialize_ristretto_point_helpers {
         Ok(SerRistrettoPoint::deserialize(deserializer)?.0)
     }
 }
+
+/// Helper functions for serde on an ed25519 `EdwardsPoint` (hex of the compressed encoding in
+/// human-readable formats, raw bytes otherwise); points are checked with
+/// [`crate::interop::edwards_point`], so small-order and torsion points are rejected.
+pub mod serialize_edwards_point_helpers {
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        point: &EdwardsPoint,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let compressed = point.compress();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(compressed.as_bytes()))
+        } else {
+            serializer.serialize_bytes(compressed.as_bytes())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<EdwardsPoint, D::Error> {
+        let bytes = super::deserialize_32(deserializer, "edwards point")?;
+        crate::interop::edwards_point(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Helper functions for serde on `Vec<Scalar>` (a sequence of hex strings).
+pub mod serialize_scalar_vec_helpers {
+    use super::SerScalar;
+    use curve25519_dalek::scalar::Scalar;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(scalars: &[Scalar], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(scalars.iter().map(|s| SerScalar(*s)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Scalar>, D::Error> {
+        let wrapped = Vec::<SerScalar>::deserialize(deserializer)?;
+        Ok(wrapped.into_iter().map(|s| s.0).collect())
+    }
+}
+
+/// Helper functions for serde on `Vec<RistrettoPoint>` (a sequence of hex strings).
+pub mod serialize_ristretto_point_vec_helpers {
+    use super::SerRistrettoPoint;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        points: &[RistrettoPoint],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(points.iter().map(|p| SerRistrettoPoint(*p)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<RistrettoPoint>, D::Error> {
+        let wrapped = Vec::<SerRistrettoPoint>::deserialize(deserializer)?;
+        Ok(wrapped.into_iter().map(|p| p.0).collect())
+    }
+}
+
+/// Helper functions for serde on `Vec<u8>` (a lowercase hex string; other cases are rejected).
+pub mod serialize_hex_bytes_helpers {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if !super::is_lower_hex(&s) {
+            return Err(serde::de::Error::custom(
+                "Non-canonical bytes encoding: expected lowercase hex",
+            ));
+        }
+        hex::decode(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Magic bytes at the start of every [`ShareFile`].
+pub const SHARE_FILE_MAGIC: [u8; 4] = *b"ZKTS";
+
+/// Current [`ShareFile`] format version.
+pub const SHARE_FILE_VERSION: u8 = 1;
+
+/// Largest payload [`ShareFile::read`] accepts, in bytes.
+pub const MAX_SHARE_FILE_PAYLOAD: usize = 1 << 16;
+
+const SHARE_FILE_MAC_CONTEXT: &str = "zk-thresh-pro 2024 share file mac v1";
+const SHARE_FILE_HEADER_LENGTH: usize = 11;
+const SHARE_FILE_TAG_LENGTH: usize = 32;
+
+/// Group and hash a [`ShareFile`] payload was produced with.
+///
+/// Stored as a little-endian `u16`; new suites get new identifiers and existing ones never change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ciphersuite {
+    /// ristretto255 slices and commitments, BLAKE3 Fiat–Shamir proofs.
+    Ristretto255Blake3 = 1,
+}
+
+impl Ciphersuite {
+    pub fn id(self) -> u16 {
+        self as u16
+    }
+
+    pub fn from_id(id: u16) -> Option<Self> {
+        match id {
+            1 => Some(Ciphersuite::Ristretto255Blake3),
+            _ => None,
+        }
+    }
+}
+
+/// Long-term on-disk form of one slice.
+///
+/// Layout (integers little-endian):
+///
+/// | bytes | field |
+/// |---|---|
+/// | 4 | [`SHARE_FILE_MAGIC`] |
+/// | 1 | format version, [`SHARE_FILE_VERSION`] |
+/// | 2 | [`Ciphersuite`] identifier |
+/// | 4 | payload length |
+/// | n | payload: the `ShareData` JSON |
+/// | 32 | keyed BLAKE3 MAC over all preceding bytes |
+///
+/// The MAC key is derived from the caller's 32-byte key with BLAKE3 `derive_key`, so the same key
+/// material can serve other purposes without cross-use. The payload is the documented JSON layout
+/// of `ShareData` (see [`crate::format`]) rather than a Rust-specific encoding, so files stay
+/// parseable by later crate versions and by other implementations.
+#[derive(Clone, Debug)]
+pub struct ShareFile {
+    pub version: u8,
+    pub ciphersuite: Ciphersuite,
+    pub share: crate::sharing::ShareData,
+}
+
+fn share_file_tag(mac_key: &[u8; 32], data: &[u8]) -> [u8; SHARE_FILE_TAG_LENGTH] {
+    let mut derive = crate::hash_adapter::Blake3Adapter::new_derive_key(SHARE_FILE_MAC_CONTEXT);
+    digest::Update::update(&mut derive, mac_key);
+    let mut key = zeroize::Zeroizing::new([0u8; 32]);
+    derive.finalize_extended(key.as_mut());
+
+    let mut mac = crate::hash_adapter::Blake3Adapter::new_keyed(&key);
+    digest::Update::update(&mut mac, data);
+    let mut tag = [0u8; SHARE_FILE_TAG_LENGTH];
+    mac.finalize_extended(&mut tag);
+    tag
+}
+
+impl ShareFile {
+    /// Wrap `share` in the current format version and ciphersuite.
+    pub fn new(share: crate::sharing::ShareData) -> Self {
+        Self {
+            version: SHARE_FILE_VERSION,
+            ciphersuite: Ciphersuite::Ristretto255Blake3,
+            share,
+        }
+    }
+
+    /// Write the file, authenticated under `mac_key`.
+    pub fn write<W: std::io::Write>(&self, mut writer: W, mac_key: &[u8; 32]) -> CryptoResult<()> {
+        let payload = zeroize::Zeroizing::new(serde_json::to_vec(&self.share).map_err(|e| {
+            CryptoError::Serialization {
+                details: e.to_string(),
+            }
+        })?);
+        let mut bytes = zeroize::Zeroizing::new(Vec::with_capacity(
+            SHARE_FILE_HEADER_LENGTH + payload.len() + SHARE_FILE_TAG_LENGTH,
+        ));
+        bytes.extend_from_slice(&SHARE_FILE_MAGIC);
+        bytes.push(self.version);
+        bytes.extend_from_slice(&self.ciphersuite.id().to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        let tag = share_file_tag(mac_key, &bytes);
+        bytes.extend_from_slice(&tag);
+        writer
+            .write_all(&bytes)
+            .and_then(|_| writer.flush())
+            .map_err(|e| CryptoError::Serialization {
+                details: format!("writing share file: {}", e),
+            })
+    }
+
+    /// Read a file written by [`Self::write`] with any format version up to the current one.
+    ///
+    /// Returns `CryptoError::Serialization` for a missing magic, an unknown version or
+    /// ciphersuite, a truncated or oversized file and an unparseable payload, and
+    /// `CryptoError::SecurityViolation` if the MAC does not verify (tampered file or wrong key).
+    /// The MAC is checked before the payload is parsed.
+    pub fn read<R: std::io::Read>(mut reader: R, mac_key: &[u8; 32]) -> CryptoResult<Self> {
+        use std::io::Read;
+
+        let invalid = |details: &str| CryptoError::Serialization {
+            details: details.to_string(),
+        };
+        let mut bytes = zeroize::Zeroizing::new(Vec::new());
+        reader
+            .by_ref()
+            .take(
+                (SHARE_FILE_HEADER_LENGTH + MAX_SHARE_FILE_PAYLOAD + SHARE_FILE_TAG_LENGTH + 1)
+                    as u64,
+            )
+            .read_to_end(&mut bytes)
+            .map_err(|e| CryptoError::Serialization {
+                details: format!("reading share file: {}", e),
+            })?;
+
+        if bytes.len() < SHARE_FILE_HEADER_LENGTH || bytes[..4] != SHARE_FILE_MAGIC {
+            return Err(invalid("not a share file"));
+        }
+        let version = bytes[4];
+        if version == 0 || version > SHARE_FILE_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported share file version {}", version),
+            });
+        }
+        let suite = u16::from_le_bytes([bytes[5], bytes[6]]);
+        let ciphersuite =
+            Ciphersuite::from_id(suite).ok_or_else(|| CryptoError::Serialization {
+                details: format!("unknown share file ciphersuite {}", suite),
+            })?;
+        let length = u32::from_le_bytes(bytes[7..11].try_into().expect("4-byte length")) as usize;
+        if length > MAX_SHARE_FILE_PAYLOAD {
+            return Err(invalid("share file payload too large"));
+        }
+        if bytes.len() != SHARE_FILE_HEADER_LENGTH + length + SHARE_FILE_TAG_LENGTH {
+            return Err(invalid("share file length does not match its header"));
+        }
+
+        let (body, tag) = bytes.split_at(SHARE_FILE_HEADER_LENGTH + length);
+        let mut received = [0u8; SHARE_FILE_TAG_LENGTH];
+        received.copy_from_slice(tag);
+        // blake3::Hash compares in constant time.
+        if blake3::Hash::from(share_file_tag(mac_key, body)) != blake3::Hash::from(received) {
+            return Err(CryptoError::SecurityViolation {
+                details: "share file MAC does not verify".to_string(),
+            });
+        }
+        let share = serde_json::from_slice(&body[SHARE_FILE_HEADER_LENGTH..]).map_err(|e| {
+            CryptoError::Serialization {
+                details: format!("share file payload: {}", e),
+            }
+        })?;
+        Ok(Self {
+            version,
+            ciphersuite,
+            share,
+        })
+    }
+}
+
+#[cfg(feature = "dealer")]
+mod wordlist;
+
+/// Number of words in a [`to_mnemonic`] phrase: 55 data words and 3 checksum words.
+#[cfg(feature = "dealer")]
+pub const MNEMONIC_WORDS: usize = MNEMONIC_DATA_WORDS + MNEMONIC_CHECKSUM_WORDS;
+
+#[cfg(feature = "dealer")]
+const MNEMONIC_DATA_WORDS: usize = 55;
+#[cfg(feature = "dealer")]
+const MNEMONIC_CHECKSUM_WORDS: usize = 3;
+#[cfg(feature = "dealer")]
+const MNEMONIC_VERSION: u16 = 1;
+#[cfg(feature = "dealer")]
+const MNEMONIC_CUSTOMIZATION: &[u8] = b"zk-thresh-pro share";
+
+/// SLIP-39's RS1024 checksum over 10-bit words, prefixed by the bytes of `customization`.
+#[cfg(feature = "dealer")]
+fn rs1024_polymod(customization: &[u8], words: &[u16]) -> u32 {
+    const GEN: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    let mut chk = 1u32;
+    for v in customization
+        .iter()
+        .map(|&b| u16::from(b))
+        .chain(words.iter().copied())
+    {
+        let b = chk >> 20;
+        chk = ((chk & 0xfffff) << 10) ^ u32::from(v);
+        for (i, g) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Encode `share` as a phrase of [`MNEMONIC_WORDS`] words from the SLIP-39 English wordlist, for
+/// shares written on paper or read out over the phone.
+///
+/// The words carry 550 bits, most significant first: a 4-bit format version (1), 2 zero bits,
+/// the 32-bit index, then the 32 bytes of the slice value and of its blinding. The index is thus
+/// readable from the first four words. Three RS1024 checksum words follow, computed as in SLIP-39
+/// but under the customization string `"zk-thresh-pro share"`, so they detect any error in up
+/// to three words. The commitment and proof are not encoded: [`from_mnemonic`] recomputes the
+/// commitment and proves the opening afresh. Only the wordlist and checksum are shared with
+/// SLIP-39; the phrase is not a SLIP-39 share and SLIP-39 wallets will not accept it.
+///
+/// Returns `CryptoError::Validation` for an index above `u32::MAX`.
+#[cfg(feature = "dealer")]
+pub fn to_mnemonic(share: &crate::sharing::ShareData) -> CryptoResult<zeroize::Zeroizing<String>> {
+    let index = u32::try_from(share.index).map_err(|_| CryptoError::Validation {
+        field: "index".to_string(),
+        reason: format!("slice {} does not fit a mnemonic", share.index),
+    })?;
+    let mut bytes = zeroize::Zeroizing::new([0u8; 68]);
+    bytes[..4].copy_from_slice(&index.to_be_bytes());
+    bytes[4..36].copy_from_slice(share.share.as_bytes());
+    bytes[36..].copy_from_slice(share.random.as_bytes());
+
+    let mut words = zeroize::Zeroizing::new(Vec::with_capacity(MNEMONIC_WORDS));
+    // The 6 header bits, then 544 payload bits, taken 10 at a time.
+    let mut acc = u32::from(MNEMONIC_VERSION) << 2;
+    let mut bits = 6;
+    for &byte in bytes.iter() {
+        acc = (acc << 8) | u32::from(byte);
+        bits += 8;
+        if bits >= 10 {
+            bits -= 10;
+            words.push(((acc >> bits) & 0x3ff) as u16);
+        }
+    }
+    debug_assert_eq!(words.len(), MNEMONIC_DATA_WORDS);
+    words.extend_from_slice(&[0; MNEMONIC_CHECKSUM_WORDS]);
+    let checksum = rs1024_polymod(MNEMONIC_CUSTOMIZATION, &words) ^ 1;
+    for (i, word) in words[MNEMONIC_DATA_WORDS..].iter_mut().enumerate() {
+        *word = ((checksum >> (10 * (MNEMONIC_CHECKSUM_WORDS - 1 - i))) & 0x3ff) as u16;
+    }
+    let phrase: Vec<&str> = words
+        .iter()
+        .map(|&w| wordlist::WORDLIST[w as usize])
+        .collect();
+    Ok(zeroize::Zeroizing::new(phrase.join(" ")))
+}
+
+/// Decode a phrase written by [`to_mnemonic`] into a slice with its commitment recomputed and a
+/// fresh proof of its opening.
+///
+/// Words are matched case-insensitively and may be abbreviated to their first four letters.
+/// Returns `CryptoError::Serialization` for an unknown word, a wrong word count, a checksum
+/// mismatch, an unknown format version, index 0 or a non-canonical scalar, and
+/// `CryptoError::InsufficientEntropy` if the proof nonces cannot be drawn.
+#[cfg(feature = "dealer")]
+pub fn from_mnemonic(phrase: &str) -> CryptoResult<crate::sharing::ShareData> {
+    let invalid = |details: String| CryptoError::Serialization { details };
+    let mut words = zeroize::Zeroizing::new(Vec::with_capacity(MNEMONIC_WORDS));
+    for token in phrase.split_whitespace() {
+        let token = zeroize::Zeroizing::new(token.to_ascii_lowercase());
+        let word = token
+            .get(..4)
+            .and_then(|prefix| {
+                wordlist::WORDLIST
+                    .binary_search_by(|w| w[..4].cmp(prefix))
+                    .ok()
+            })
+            .filter(|&i| wordlist::WORDLIST[i].starts_with(token.as_str()))
+            .ok_or_else(|| {
+                invalid(format!(
+                    "mnemonic word {} is not in the wordlist",
+                    words.len() + 1
+                ))
+            })?;
+        words.push(word as u16);
+    }
+    if words.len() != MNEMONIC_WORDS {
+        return Err(invalid(format!(
+            "mnemonic must be {} words, got {}",
+            MNEMONIC_WORDS,
+            words.len()
+        )));
+    }
+    if rs1024_polymod(MNEMONIC_CUSTOMIZATION, &words) != 1 {
+        return Err(invalid("mnemonic checksum does not match".to_string()));
+    }
+
+    let header = words[0] >> 4;
+    if header >> 2 != MNEMONIC_VERSION || header & 0b11 != 0 {
+        return Err(invalid(format!(
+            "unsupported mnemonic version {}",
+            header >> 2
+        )));
+    }
+    let mut bytes = zeroize::Zeroizing::new([0u8; 68]);
+    let mut acc = u32::from(words[0] & 0xf);
+    let mut bits = 4;
+    let mut filled = 0;
+    for &word in &words[1..MNEMONIC_DATA_WORDS] {
+        acc = (acc << 10) | u32::from(word);
+        bits += 10;
+        while bits >= 8 {
+            bits -= 8;
+            bytes[filled] = (acc >> bits) as u8;
+            filled += 1;
+        }
+    }
+
+    let index = u32::from_be_bytes(bytes[..4].try_into().expect("4-byte index"));
+    if index == 0 {
+        return Err(invalid("invalid slice index 0".to_string()));
+    }
+    let index = index as usize;
+    let share = scalar_from_slice(&bytes[4..36], "slice value")?;
+    let random = scalar_from_slice(&bytes[36..], "slice random")?;
+    let commitment = crate::utils::pedersen_commit(&share, &random);
+    let proof = crate::proof::generate_proof(share, random, index, commitment)?;
+    Ok(crate::sharing::ShareData {
+        index,
+        share,
+        commitment,
+        random,
+        proof,
+    })
+}
+
+#[cfg(feature = "qr")]
+mod reed_solomon;
+
+/// Magic bytes at the start of every [`to_qr_payload`] payload.
+#[cfg(feature = "qr")]
+pub const QR_PAYLOAD_MAGIC: [u8; 2] = *b"ZQ";
+
+/// Current [`to_qr_payload`] format version.
+#[cfg(feature = "qr")]
+pub const QR_PAYLOAD_VERSION: u8 = 1;
+
+/// Reed–Solomon check bytes of a [`to_qr_payload`] payload; up to half of them can be corrected.
+#[cfg(feature = "qr")]
+pub const QR_PARITY_LENGTH: usize = 32;
+
+#[cfg(feature = "qr")]
+const QR_DATA_LENGTH: usize = 2 + 1 + 2 + 4 + 2 * 32 + crate::proof::PROOF_LENGTH;
+
+/// Length of every [`to_qr_payload`] payload, in bytes.
+#[cfg(feature = "qr")]
+pub const QR_PAYLOAD_LENGTH: usize = QR_DATA_LENGTH + QR_PARITY_LENGTH;
+
+/// Compact byte payload of `share` for a QR code handed to a custodian on paper.
+///
+/// Layout (integers little-endian):
+///
+/// | bytes | field |
+/// |---|---|
+/// | 2 | [`QR_PAYLOAD_MAGIC`] |
+/// | 1 | format version, [`QR_PAYLOAD_VERSION`] |
+/// | 2 | [`Ciphersuite`] identifier |
+/// | 4 | slice index |
+/// | 32 | slice value |
+/// | 32 | slice blinding |
+/// | 128 | the slice proof, `Proof::to_bytes` |
+/// | 32 | Reed–Solomon check bytes over all preceding bytes |
+///
+/// The commitment is left out and recomputed from the opening when reading. The check bytes
+/// correct up to 16 damaged bytes anywhere in the payload, on top of whatever the QR code's own
+/// error-correction level recovers, so a creased or faded print still reads. At 233 bytes the
+/// payload fits a QR code of version 10 at level L in byte mode. The crate renders no images;
+/// hand the bytes to any QR encoder.
+///
+/// Returns `CryptoError::Validation` for an index above `u32::MAX`.
+#[cfg(feature = "qr")]
+pub fn to_qr_payload(
+    share: &crate::sharing::ShareData,
+) -> CryptoResult<zeroize::Zeroizing<Vec<u8>>> {
+    let index = u32::try_from(share.index).map_err(|_| CryptoError::Validation {
+        field: "index".to_string(),
+        reason: format!("slice {} does not fit a QR payload", share.index),
+    })?;
+    let mut payload = zeroize::Zeroizing::new(Vec::with_capacity(QR_PAYLOAD_LENGTH));
+    payload.extend_from_slice(&QR_PAYLOAD_MAGIC);
+    payload.push(QR_PAYLOAD_VERSION);
+    payload.extend_from_slice(&Ciphersuite::Ristretto255Blake3.id().to_le_bytes());
+    payload.extend_from_slice(&index.to_le_bytes());
+    payload.extend_from_slice(share.share.as_bytes());
+    payload.extend_from_slice(share.random.as_bytes());
+    payload.extend_from_slice(&share.proof.to_bytes());
+    let parity = zeroize::Zeroizing::new(reed_solomon::encode(&payload, QR_PARITY_LENGTH));
+    payload.extend_from_slice(&parity);
+    Ok(payload)
+}
+
+/// Read a payload written by [`to_qr_payload`], correcting damaged bytes.
+///
+/// Returns `CryptoError::Serialization` for a wrong length, more damage than the check bytes
+/// correct, a missing magic, an unknown version or ciphersuite, index 0 or a non-canonical
+/// scalar, and `CryptoError::SecurityViolation` if the proof does not verify against the
+/// recomputed commitment.
+#[cfg(feature = "qr")]
+pub fn from_qr_payload(payload: &[u8]) -> CryptoResult<crate::sharing::ShareData> {
+    let invalid = |details: String| CryptoError::Serialization { details };
+    if payload.len() != QR_PAYLOAD_LENGTH {
+        return Err(invalid(format!(
+            "QR payload must be {} bytes, got {}",
+            QR_PAYLOAD_LENGTH,
+            payload.len()
+        )));
+    }
+    let mut bytes = zeroize::Zeroizing::new(payload.to_vec());
+    if reed_solomon::decode(&mut bytes, QR_PARITY_LENGTH).is_none() {
+        return Err(invalid(
+            "QR payload is damaged beyond correction".to_string(),
+        ));
+    }
+    if bytes[..2] != QR_PAYLOAD_MAGIC {
+        return Err(invalid("not a share QR payload".to_string()));
+    }
+    if bytes[2] != QR_PAYLOAD_VERSION {
+        return Err(invalid(format!(
+            "unsupported QR payload version {}",
+            bytes[2]
+        )));
+    }
+    let suite = u16::from_le_bytes([bytes[3], bytes[4]]);
+    if Ciphersuite::from_id(suite).is_none() {
+        return Err(invalid(format!("unknown QR payload ciphersuite {}", suite)));
+    }
+    let index = u32::from_le_bytes(bytes[5..9].try_into().expect("4-byte index"));
+    if index == 0 {
+        return Err(invalid("invalid slice index 0".to_string()));
+    }
+    let index = index as usize;
+    let share = scalar_from_slice(&bytes[9..41], "slice value")?;
+    let random = scalar_from_slice(&bytes[41..73], "slice random")?;
+    let proof = crate::proof::Proof::from_bytes(&bytes[73..QR_DATA_LENGTH])?;
+    let commitment = crate::utils::pedersen_commit(&share, &random);
+    crate::proof::verify_proof(&proof, commitment, index)?;
+    Ok(crate::sharing::ShareData {
+        index,
+        share,
+        commitment,
+        random,
+        proof,
+    })
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_file_roundtrip_and_tampering() {
+        let shares = crate::sharing::generate_key_shares(Scalar::from(17u64), 2, 2).unwrap();
+        let key = [3u8; 32];
+        let mut bytes = Vec::new();
+        ShareFile::new(shares[1].clone())
+            .write(&mut bytes, &key)
+            .unwrap();
+        assert_eq!(bytes[..4], SHARE_FILE_MAGIC);
+
+        let file = ShareFile::read(bytes.as_slice(), &key).unwrap();
+        assert_eq!(file.version, SHARE_FILE_VERSION);
+        assert_eq!(file.ciphersuite, Ciphersuite::Ristretto255Blake3);
+        assert_eq!(
+            serde_json::to_value(&file.share).unwrap(),
+            serde_json::to_value(&shares[1]).unwrap()
+        );
+
+        assert!(matches!(
+            ShareFile::read(bytes.as_slice(), &[4u8; 32]),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut tampered = bytes.clone();
+        tampered[SHARE_FILE_HEADER_LENGTH + 12] ^= 1;
+        assert!(matches!(
+            ShareFile::read(tampered.as_slice(), &key),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut future = bytes.clone();
+        future[4] = SHARE_FILE_VERSION + 1;
+        assert!(matches!(
+            ShareFile::read(future.as_slice(), &key),
+            Err(CryptoError::Serialization { .. })
+        ));
+        assert!(ShareFile::read(&bytes[..bytes.len() - 1], &key).is_err());
+        assert!(ShareFile::read(&b"{\"index\":1}"[..], &key).is_err());
+    }
+
+    #[test]
+    fn test_binary_encodings_roundtrip() {
+        use crate::key_lifecycle::{Key, LEGACY_KEY_LENGTH};
+        use crate::sharing::{ShareData, SHARE_DATA_LENGTH};
+        use chrono::SubsecRound;
+
+        let shares = crate::sharing::generate_key_shares(Scalar::from(23u64), 2, 3).unwrap();
+        let bytes = shares[2].to_bytes();
+        let json = serde_json::to_vec(&shares[2]).unwrap();
+        assert!(bytes.len() * 2 < json.len());
+        let decoded = ShareData::from_bytes(bytes.as_ref()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&shares[2]).unwrap()
+        );
+        assert_eq!(
+            crate::proof::Proof::from_bytes(&bytes[104..])
+                .unwrap()
+                .to_bytes(),
+            shares[2].proof.to_bytes()
+        );
+
+        let mut zero_index = *bytes;
+        zero_index[..8].fill(0);
+        assert!(ShareData::from_bytes(&zero_index).is_err());
+        let mut identity = *bytes;
+        identity[40..72].fill(0);
+        assert!(ShareData::from_bytes(&identity).is_err());
+        let mut unreduced = *bytes;
+        unreduced[8..40].fill(0xff);
+        assert!(ShareData::from_bytes(&unreduced).is_err());
+        assert!(ShareData::from_bytes(&bytes[..SHARE_DATA_LENGTH - 1]).is_err());
+
+        let mut key = Key::new(Scalar::from(5u64));
+        key.activate_for(24).unwrap();
+        let decoded = Key::from_bytes(key.to_bytes().as_ref()).unwrap();
+        assert_eq!(decoded.secret, key.secret);
+        assert_eq!(decoded.state, key.state);
+        assert_eq!(decoded.creation_time, key.creation_time.trunc_subsecs(6));
+        assert_eq!(
+            decoded.activation_time,
+            key.activation_time.map(|t| t.trunc_subsecs(6))
+        );
+        assert_eq!(decoded.retirement_time, None);
+        assert_eq!(
+            decoded.expiry_time,
+            key.expiry_time.map(|t| t.trunc_subsecs(6))
+        );
+        let legacy = Key::from_bytes(&key.to_bytes()[..LEGACY_KEY_LENGTH]).unwrap();
+        assert_eq!(legacy.expiry_time, None);
+        let mut bad_state = *key.to_bytes();
+        bad_state[32] = 4;
+        assert!(Key::from_bytes(&bad_state).is_err());
+        let mut stray = *key.to_bytes();
+        stray[51] = 1;
+        assert!(Key::from_bytes(&stray).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_roundtrip_and_checksum() {
+        let shares = crate::sharing::generate_key_shares(Scalar::from(31u64), 2, 3).unwrap();
+        let mut share = shares[1].clone();
+        share.index = 0x0102_0304;
+        let phrase = to_mnemonic(&share).unwrap();
+        let words: Vec<&str> = phrase.split(' ').collect();
+        assert_eq!(words.len(), MNEMONIC_WORDS);
+
+        let decoded = from_mnemonic(&phrase).unwrap();
+        assert_eq!(decoded.index, share.index);
+        assert_eq!(decoded.share, share.share);
+        assert_eq!(decoded.random, share.random);
+        assert_eq!(decoded.commitment, share.commitment);
+        assert!(
+            crate::proof::verify_proof(&decoded.proof, decoded.commitment, decoded.index).is_ok()
+        );
+
+        // Four-letter abbreviations, any case and extra whitespace.
+        let abbreviated: Vec<String> = words.iter().map(|w| w[..4].to_uppercase()).collect();
+        let decoded = from_mnemonic(&format!("  {}\n", abbreviated.join("\t "))).unwrap();
+        assert_eq!(decoded.share, share.share);
+
+        for position in [0, 10, MNEMONIC_WORDS - 1] {
+            let mut altered = words.clone();
+            altered[position] = if words[position] == "zero" {
+                "acid"
+            } else {
+                "zero"
+            };
+            assert!(matches!(
+                from_mnemonic(&altered.join(" ")),
+                Err(CryptoError::Serialization { .. })
+            ));
+        }
+        let mut swapped = words.clone();
+        swapped.swap(3, 4);
+        assert!(from_mnemonic(&swapped.join(" ")).is_err());
+        assert!(from_mnemonic(&words[1..].join(" ")).is_err());
+        let mut unknown = words.clone();
+        unknown[5] = "bitcoin";
+        assert!(from_mnemonic(&unknown.join(" ")).is_err());
+
+        share.index = 0;
+        assert!(from_mnemonic(&to_mnemonic(&share).unwrap()).is_err());
+        share.index = u32::MAX as usize + 1;
+        assert!(matches!(
+            to_mnemonic(&share),
+            Err(CryptoError::Validation { .. })
+        ));
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_qr_payload_corrects_damage() {
+        let shares = crate::sharing::generate_key_shares(Scalar::from(37u64), 2, 3).unwrap();
+        let payload = to_qr_payload(&shares[2]).unwrap();
+        assert_eq!(payload.len(), QR_PAYLOAD_LENGTH);
+        assert_eq!(payload[..2], QR_PAYLOAD_MAGIC);
+        let decoded = from_qr_payload(&payload).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&shares[2]).unwrap()
+        );
+
+        let mut damaged = payload.clone();
+        for position in (0..QR_PAYLOAD_LENGTH).step_by(15) {
+            damaged[position] ^= 0x5a;
+        }
+        assert_eq!(from_qr_payload(&damaged).unwrap().share, shares[2].share);
+
+        let mut destroyed = payload.clone();
+        destroyed[..40].fill(0);
+        assert!(matches!(
+            from_qr_payload(&destroyed),
+            Err(CryptoError::Serialization { .. })
+        ));
+        assert!(from_qr_payload(&payload[1..]).is_err());
+
+        // A re-encoded payload with another slice's proof is well-formed but does not verify.
+        let mut forged = shares[2].clone();
+        forged.proof = shares[1].proof.clone();
+        assert!(matches!(
+            from_qr_payload(&to_qr_payload(&forged).unwrap()),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    /// `p = 2^255 - 19`, a non-canonical encoding of the identity.
+    const UNREDUCED_POINT: &str =
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f";
+    /// The group order `l`, a non-canonical encoding of the zero scalar.
+    const UNREDUCED_SCALAR: &str =
+        "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+
+    #[test]
+    fn test_mutated_encodings_are_rejected() {
+        use crate::error::VerifyError;
+        use crate::sharing::ShareData;
+
+        let shares = crate::sharing::generate_key_shares(Scalar::from(29u64), 2, 2).unwrap();
+        let json = serde_json::to_value(&shares[0]).unwrap();
+        let mutate = |path: &[&str], value: String| {
+            let mut mutated = json.clone();
+            let (last, parents) = path.split_last().unwrap();
+            let target = parents.iter().fold(&mut mutated, |v, key| &mut v[*key]);
+            target[*last] = serde_json::Value::String(value);
+            mutated
+        };
+        let upper = json["proof"]["challenge"].as_str().unwrap().to_uppercase();
+        let cases = [
+            (&["proof", "challenge"][..], upper),
+            (
+                &["proof", "response_share"][..],
+                UNREDUCED_SCALAR.to_string(),
+            ),
+            (&["commitment"][..], UNREDUCED_POINT.to_string()),
+        ];
+        for (path, value) in cases {
+            let mutated = mutate(path, value);
+            assert!(serde_json::from_value::<ShareData>(mutated.clone()).is_err());
+            assert!(matches!(
+                crate::vss::parse_share(mutated.to_string().as_bytes()),
+                Err(VerifyError::NonCanonicalEncoding { .. })
+            ));
+        }
+
+        let unreduced_point: [u8; 32] = hex::decode(UNREDUCED_POINT).unwrap().try_into().unwrap();
+        assert!(canonical_point(&unreduced_point).is_none());
+        let mut proof = shares[0].proof.to_bytes();
+        proof[96..].copy_from_slice(&hex::decode(UNREDUCED_SCALAR).unwrap());
+        assert!(crate::proof::Proof::from_bytes(&proof).is_err());
+        let mut commitments = crate::vss::VssCommitments {
+            coefficients: vec![shares[0].commitment],
+        }
+        .to_canonical_bytes();
+        commitments[5..].copy_from_slice(&unreduced_point);
+        assert!(crate::vss::VssCommitments::from_canonical_bytes(&commitments).is_err());
+        let mut signature = [0u8; crate::threshold_sig::SIGNATURE_LENGTH];
+        signature[..32].copy_from_slice(&unreduced_point);
+        assert!(crate::threshold_sig::SchnorrSignature::from_bytes(&signature).is_err());
+        assert!(!is_lower_hex("0A"));
+        assert!(is_lower_hex("0a"));
+    }
+}
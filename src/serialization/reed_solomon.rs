@@ -0,0 +1,186 @@
+//! Systematic Reed–Solomon code over GF(2^8) for [`super::to_qr_payload`].
+//!
+//! Bytes are elements of the AES field of [`crate::sharing::gf256`] with generator `α = 3`.
+//! A codeword of `n ≤ 255` bytes is the polynomial `c_0·x^{n-1} + … + c_{n-1}`, the message
+//! followed by `parity` check bytes, divisible by `g(x) = Π_{j<parity} (x - α^j)`; it corrects up
+//! to `parity / 2` bytes in unknown positions. Field multiplication uses no tables or branches,
+//! so encoding and the syndromes of secret payloads do not leak through the cache; the decoder's
+//! flow depends only on the error pattern.
+
+use crate::sharing::gf256::{inv, mul, pow};
+
+const GENERATOR: u8 = 3;
+
+/// `p(x)`, coefficients highest degree first.
+fn eval_high_first(p: &[u8], x: u8) -> u8 {
+    p.iter().fold(0, |y, &c| mul(y, x) ^ c)
+}
+
+/// `p(x)`, coefficients lowest degree first.
+fn eval_low_first(p: &[u8], x: u8) -> u8 {
+    p.iter().rev().fold(0, |y, &c| mul(y, x) ^ c)
+}
+
+/// `g(x)`, highest degree first.
+fn generator_poly(parity: usize) -> Vec<u8> {
+    let mut g = vec![1u8];
+    for j in 0..parity {
+        let root = pow(GENERATOR, j);
+        let mut next = vec![0u8; g.len() + 1];
+        for (i, &c) in g.iter().enumerate() {
+            next[i] ^= c;
+            next[i + 1] ^= mul(c, root);
+        }
+        g = next;
+    }
+    g
+}
+
+/// The `parity` check bytes of `message`: the remainder of `message(x)·x^parity` by `g(x)`.
+pub(super) fn encode(message: &[u8], parity: usize) -> Vec<u8> {
+    debug_assert!(message.len() + parity <= 255);
+    let g = generator_poly(parity);
+    let mut remainder = vec![0u8; parity];
+    for &byte in message {
+        let factor = byte ^ remainder[0];
+        remainder.rotate_left(1);
+        remainder[parity - 1] = 0;
+        for (r, &c) in remainder.iter_mut().zip(&g[1..]) {
+            *r ^= mul(c, factor);
+        }
+    }
+    remainder
+}
+
+fn syndromes(codeword: &[u8], parity: usize) -> Vec<u8> {
+    (0..parity)
+        .map(|j| eval_high_first(codeword, pow(GENERATOR, j)))
+        .collect()
+}
+
+/// Correct `codeword` in place; returns the number of corrected bytes, or `None` if the errors
+/// exceed what `parity` check bytes can correct (or are detected as such).
+pub(super) fn decode(codeword: &mut [u8], parity: usize) -> Option<usize> {
+    let n = codeword.len();
+    if n > 255 || parity >= n {
+        return None;
+    }
+    let s = syndromes(codeword, parity);
+    if s.iter().all(|&v| v == 0) {
+        return Some(0);
+    }
+
+    // Berlekamp–Massey: the error locator Λ(x) = Π (1 - X_k·x), lowest degree first.
+    let mut locator = vec![1u8];
+    let mut previous = vec![1u8];
+    let mut length = 0;
+    let mut shift = 1;
+    let mut last_discrepancy = 1u8;
+    for step in 0..parity {
+        let discrepancy = (1..=length).fold(s[step], |d, i| {
+            d ^ mul(locator.get(i).copied().unwrap_or(0), s[step - i])
+        });
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = mul(discrepancy, inv(last_discrepancy));
+        let mut next = locator.clone();
+        next.resize(next.len().max(previous.len() + shift), 0);
+        for (i, &c) in previous.iter().enumerate() {
+            next[i + shift] ^= mul(scale, c);
+        }
+        if 2 * length <= step {
+            previous = std::mem::replace(&mut locator, next);
+            length = step + 1 - length;
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            locator = next;
+            shift += 1;
+        }
+    }
+    if 2 * length > parity {
+        return None;
+    }
+
+    // Chien search: an error at degree p is a root α^{-p} of Λ.
+    let positions: Vec<usize> = (0..n)
+        .filter(|&p| eval_low_first(&locator, inv(pow(GENERATOR, p))) == 0)
+        .collect();
+    if positions.len() != length {
+        return None;
+    }
+
+    // Magnitudes from S_j = Σ_k e_k·X_k^j, j < length: a Vandermonde system in the X_k.
+    let locators: Vec<u8> = positions.iter().map(|&p| pow(GENERATOR, p)).collect();
+    let mut rows: Vec<Vec<u8>> = (0..length)
+        .map(|j| {
+            let mut row: Vec<u8> = locators.iter().map(|&x| pow(x, j)).collect();
+            row.push(s[j]);
+            row
+        })
+        .collect();
+    for column in 0..length {
+        let found = (column..length).find(|&r| rows[r][column] != 0)?;
+        rows.swap(column, found);
+        let inverse = inv(rows[column][column]);
+        for value in rows[column].iter_mut() {
+            *value = mul(*value, inverse);
+        }
+        let pivot_row = rows[column].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r != column && factor != 0 {
+                for (value, &pivot) in row.iter_mut().zip(&pivot_row) {
+                    *value ^= mul(factor, pivot);
+                }
+            }
+        }
+    }
+    for (&p, row) in positions.iter().zip(&rows) {
+        codeword[n - 1 - p] ^= row[length];
+    }
+    syndromes(codeword, parity)
+        .iter()
+        .all(|&v| v == 0)
+        .then_some(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reed_solomon_corrects_up_to_half_the_parity() {
+        assert_eq!(
+            (0..255)
+                .map(|e| pow(GENERATOR, e))
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
+            255
+        );
+
+        let message: Vec<u8> = (0..200u32).map(|i| (i * 37 + 11) as u8).collect();
+        let parity = 32;
+        let mut codeword = message.clone();
+        codeword.extend(encode(&message, parity));
+        let clean = codeword.clone();
+        assert_eq!(decode(&mut codeword, parity), Some(0));
+
+        for errors in [1, 5, 16] {
+            let mut damaged = clean.clone();
+            for k in 0..errors {
+                damaged[(k * 53 + 7) % clean.len()] ^= (k as u8).wrapping_mul(29) | 1;
+            }
+            assert_eq!(decode(&mut damaged, parity), Some(errors));
+            assert_eq!(damaged, clean);
+        }
+
+        let mut hopeless = clean.clone();
+        for byte in hopeless.iter_mut().take(40) {
+            *byte ^= 0xa5;
+        }
+        assert_ne!(decode(&mut hopeless, parity).map(|_| hopeless), Some(clean));
+    }
+}
@@ -0,0 +1,513 @@
+//! **service module**
+//!
+//! Share-coordination service: an [`EnterpriseCryptoSystem`] answering deal, verify, recover and
+//! refresh requests from remote participants, so the crate can run as a standalone process.
+//!
+//! Requests and responses are the JSON serializations of [`ServiceRequest`] and
+//! [`ServiceResponse`], carried as [`Message::ServiceRequest`] and [`Message::ServiceResponse`]
+//! frames over a [`transport`] [`SecureChannel`]; there is no HTTP or gRPC endpoint. The channel
+//! handshake authenticates both ends with their pairwise pre-shared [`ChannelKey`], and a request
+//! is always attributed to the participant index the peer proved, never to an index it claims in
+//! the payload.
+//!
+//! This is not mutual TLS and gives none of its guarantees beyond that authentication: there are
+//! no certificates, so no issuance, expiry or revocation short of replacing the channel key, and
+//! the channel has no forward secrecy, so a leaked key exposes every recorded session of that
+//! pair. Deployments that require mTLS must terminate it in front of the service (see the
+//! transport module).
+//!
+//! Each participant is granted a set of [`Operation`]s with [`Service::grant`]. Every request is
+//! audited in the system's log: a refused one as [`SecurityEvent::UnauthorizedAccess`], every
+//! other one as [`SecurityEvent::ServiceRequestHandled`] with its outcome, next to the events the
+//! system records for the operation itself.
+//!
+//! A server accepts connections with [`Service::accept`] and answers requests with
+//! [`Service::handle_next`] until the peer disconnects; a client sends requests with [`call`].
+//! [`Service`] is `Sync`, so one instance can serve a connection per thread.
+
+use crate::context::OperationContext;
+use crate::error::{CryptoError, CryptoResult, SecurityEvent};
+use crate::params::PublicParameters;
+use crate::redact::Redacted;
+use crate::sharing::ShareData;
+use crate::transport::{self, ChannelKey, Message, SecureChannel};
+use crate::EnterpriseCryptoSystem;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Operations a participant can be granted.
+#[derive(
+    serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum Operation {
+    /// [`EnterpriseCryptoSystem::create_secret_shares`].
+    Deal,
+    /// [`EnterpriseCryptoSystem::verify_shares`].
+    Verify,
+    /// [`EnterpriseCryptoSystem::recover_secret_enterprise`].
+    Recover,
+    /// [`EnterpriseCryptoSystem::refresh_secret_shares`].
+    Refresh,
+}
+
+impl Operation {
+    /// Name recorded in audit events.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::Deal => "deal",
+            Operation::Verify => "verify",
+            Operation::Recover => "recover",
+            Operation::Refresh => "refresh",
+        }
+    }
+}
+
+/// Request to a [`Service`]; `context` is passed to the system operation unchanged.
+///
+/// `Debug` output redacts the secret of a dealing, and the secret is zeroized on drop.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub enum ServiceRequest {
+    Deal {
+        context: OperationContext,
+        #[serde(with = "crate::serialization::serialize_zeroizing_scalar_helpers")]
+        secret: Zeroizing<Scalar>,
+        threshold: usize,
+        num_shares: usize,
+    },
+    Verify {
+        context: OperationContext,
+        shares: Vec<ShareData>,
+    },
+    Recover {
+        context: OperationContext,
+        shares: Vec<ShareData>,
+    },
+    Refresh {
+        context: OperationContext,
+        shares: Vec<ShareData>,
+        threshold: usize,
+    },
+}
+
+impl ServiceRequest {
+    pub fn operation(&self) -> Operation {
+        match self {
+            ServiceRequest::Deal { .. } => Operation::Deal,
+            ServiceRequest::Verify { .. } => Operation::Verify,
+            ServiceRequest::Recover { .. } => Operation::Recover,
+            ServiceRequest::Refresh { .. } => Operation::Refresh,
+        }
+    }
+
+    pub fn context(&self) -> &OperationContext {
+        match self {
+            ServiceRequest::Deal { context, .. }
+            | ServiceRequest::Verify { context, .. }
+            | ServiceRequest::Recover { context, .. }
+            | ServiceRequest::Refresh { context, .. } => context,
+        }
+    }
+}
+
+impl fmt::Debug for ServiceRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceRequest::Deal {
+                context,
+                secret,
+                threshold,
+                num_shares,
+            } => f
+                .debug_struct("Deal")
+                .field("context", context)
+                .field("secret", &Redacted(secret))
+                .field("threshold", threshold)
+                .field("num_shares", num_shares)
+                .finish(),
+            ServiceRequest::Verify { context, shares } => f
+                .debug_struct("Verify")
+                .field("context", context)
+                .field("shares", shares)
+                .finish(),
+            ServiceRequest::Recover { context, shares } => f
+                .debug_struct("Recover")
+                .field("context", context)
+                .field("shares", shares)
+                .finish(),
+            ServiceRequest::Refresh {
+                context,
+                shares,
+                threshold,
+            } => f
+                .debug_struct("Refresh")
+                .field("context", context)
+                .field("shares", shares)
+                .field("threshold", threshold)
+                .finish(),
+        }
+    }
+}
+
+/// Answer of a [`Service`] to one [`ServiceRequest`].
+///
+/// `Debug` output redacts a recovered secret, and the secret is zeroized on drop.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub enum ServiceResponse {
+    /// Slices of a dealing or refresh.
+    Shares(Vec<ShareData>),
+    /// Every slice of a verify request verified.
+    Verified,
+    /// Recovered secret.
+    Secret(
+        #[serde(with = "crate::serialization::serialize_zeroizing_scalar_helpers")]
+        Zeroizing<Scalar>,
+    ),
+    /// The request failed or was refused; `code` is the stable [`CryptoError::code`].
+    Error { code: String, message: String },
+}
+
+impl ServiceResponse {
+    fn error(error: &CryptoError) -> Self {
+        ServiceResponse::Error {
+            code: error.code().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for ServiceResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceResponse::Shares(shares) => f.debug_tuple("Shares").field(shares).finish(),
+            ServiceResponse::Verified => f.write_str("Verified"),
+            ServiceResponse::Secret(secret) => {
+                f.debug_tuple("Secret").field(&Redacted(secret)).finish()
+            }
+            ServiceResponse::Error { code, message } => f
+                .debug_struct("Error")
+                .field("code", code)
+                .field("message", message)
+                .finish(),
+        }
+    }
+}
+
+struct Grant {
+    key: ChannelKey,
+    operations: BTreeSet<Operation>,
+}
+
+/// An [`EnterpriseCryptoSystem`] serving authenticated participants.
+pub struct Service {
+    system: Mutex<EnterpriseCryptoSystem>,
+    local: usize,
+    parameters: PublicParameters,
+    grants: BTreeMap<usize, Grant>,
+}
+
+impl Service {
+    /// Serve `system` as participant `local`; no participant is granted anything yet.
+    pub fn new(system: EnterpriseCryptoSystem, local: usize) -> Self {
+        Self {
+            parameters: system.public_parameters(),
+            system: Mutex::new(system),
+            local,
+            grants: BTreeMap::new(),
+        }
+    }
+
+    /// Accept connections from `participant` holding `key`, and allow it `operations`.
+    ///
+    /// Replaces an earlier grant to the same participant.
+    pub fn grant(&mut self, participant: usize, key: ChannelKey, operations: &[Operation]) {
+        self.grants.insert(
+            participant,
+            Grant {
+                key,
+                operations: operations.iter().copied().collect(),
+            },
+        );
+    }
+
+    /// Accept one connection on `listener` from a participant with a grant.
+    ///
+    /// See [`transport::accept`]; peers without a grant are refused during the handshake.
+    pub fn accept(
+        &self,
+        listener: &TcpListener,
+        timeout: Duration,
+    ) -> CryptoResult<SecureChannel<TcpStream>> {
+        transport::accept(listener, self.local, &self.parameters, timeout, |peer| {
+            self.grants.get(&peer).map(|grant| grant.key.clone())
+        })
+    }
+
+    /// Receive one request on `channel` and send the answer.
+    ///
+    /// Returns the channel error once the peer disconnects or a frame fails authentication, and
+    /// `CryptoError::Validation` for a message that is not a [`Message::ServiceRequest`]; the
+    /// connection should be dropped in either case. Failed operations are answered with
+    /// [`ServiceResponse::Error`] and do not end the connection.
+    pub fn handle_next<S: Read + Write>(&self, channel: &mut SecureChannel<S>) -> CryptoResult<()> {
+        let Message::ServiceRequest(request) = channel.recv()? else {
+            return Err(CryptoError::Validation {
+                field: "message".to_string(),
+                reason: format!(
+                    "participant {} sent a message that is not a service request",
+                    channel.peer()
+                ),
+            });
+        };
+        let response = self.handle(channel.peer(), request);
+        channel.send(&Message::ServiceResponse(response))
+    }
+
+    /// Answer `request` from the authenticated `participant`.
+    pub fn handle(&self, participant: usize, request: ServiceRequest) -> ServiceResponse {
+        let operation = request.operation();
+        let context = request.context().clone();
+        let mut system = self.system.lock().unwrap_or_else(|e| e.into_inner());
+
+        let granted = self
+            .grants
+            .get(&participant)
+            .is_some_and(|grant| grant.operations.contains(&operation));
+        if !granted {
+            system.audit(
+                &context,
+                SecurityEvent::UnauthorizedAccess {
+                    attempt: format!(
+                        "participant {} requested {} on {}",
+                        participant,
+                        operation.name(),
+                        context.key_id
+                    ),
+                    timestamp: chrono::Utc::now(),
+                },
+            );
+            return ServiceResponse::error(&CryptoError::SecurityViolation {
+                details: format!(
+                    "participant {} is not granted {}",
+                    participant,
+                    operation.name()
+                ),
+            });
+        }
+
+        let result = match request {
+            ServiceRequest::Deal {
+                context,
+                secret,
+                threshold,
+                num_shares,
+            } => system
+                .create_secret_shares(*secret, threshold, num_shares, &context)
+                .map(ServiceResponse::Shares),
+            ServiceRequest::Verify { context, shares } => system
+                .verify_shares(&shares, &context)
+                .map(|()| ServiceResponse::Verified),
+            ServiceRequest::Recover { context, shares } => system
+                .recover_secret_enterprise(&shares, &context)
+                .map(|secret| ServiceResponse::Secret(Zeroizing::new(secret))),
+            ServiceRequest::Refresh {
+                context,
+                shares,
+                threshold,
+            } => system
+                .refresh_secret_shares(&shares, threshold, &context)
+                .map(ServiceResponse::Shares),
+        };
+        system.audit(
+            &context,
+            SecurityEvent::ServiceRequestHandled {
+                participant,
+                operation: operation.name().to_string(),
+                outcome: result
+                    .as_ref()
+                    .map_or_else(|e| e.code(), |_| "ok")
+                    .to_string(),
+                timestamp: chrono::Utc::now(),
+            },
+        );
+        result.unwrap_or_else(|e| ServiceResponse::error(&e))
+    }
+
+    /// Run `f` with the served system, e.g. to read its audit log or export its public state.
+    pub fn with_system<T>(&self, f: impl FnOnce(&mut EnterpriseCryptoSystem) -> T) -> T {
+        f(&mut self.system.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Send `request` on `channel` and wait for the service's answer.
+pub fn call<S: Read + Write>(
+    channel: &mut SecureChannel<S>,
+    request: ServiceRequest,
+) -> CryptoResult<ServiceResponse> {
+    channel.send(&Message::ServiceRequest(request))?;
+    match channel.recv()? {
+        Message::ServiceResponse(response) => Ok(response),
+        _ => Err(CryptoError::Validation {
+            field: "message".to_string(),
+            reason: format!(
+                "participant {} answered with a message that is not a service response",
+                channel.peer()
+            ),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnterpriseConfig;
+    use std::sync::Arc;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_service_over_tcp() {
+        let master = [3u8; 32];
+        let mut service = Service::new(EnterpriseCryptoSystem::new(EnterpriseConfig::default()), 1);
+        service.grant(
+            2,
+            ChannelKey::derive(&master, 1, 2),
+            &[Operation::Deal, Operation::Verify, Operation::Recover],
+        );
+        service.grant(3, ChannelKey::derive(&master, 1, 3), &[Operation::Verify]);
+        let service = Arc::new(service);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = {
+            let service = Arc::clone(&service);
+            std::thread::spawn(move || {
+                for _ in 0..2 {
+                    let mut channel = service.accept(&listener, TIMEOUT).unwrap();
+                    while service.handle_next(&mut channel).is_ok() {}
+                }
+            })
+        };
+        let parameters = EnterpriseConfig::default().public_parameters();
+        let connect = |index: usize| {
+            transport::connect(
+                addr,
+                index,
+                1,
+                &parameters,
+                &ChannelKey::derive(&master, index, 1),
+                TIMEOUT,
+            )
+            .unwrap()
+        };
+
+        let secret = Scalar::from(77u64);
+        let mut operator = connect(2);
+        let ServiceResponse::Shares(shares) = call(
+            &mut operator,
+            ServiceRequest::Deal {
+                context: OperationContext::new("svc-key", 0, "svc-deal"),
+                secret: Zeroizing::new(secret),
+                threshold: 2,
+                num_shares: 3,
+            },
+        )
+        .unwrap() else {
+            panic!("expected slices");
+        };
+        let recovered = call(
+            &mut operator,
+            ServiceRequest::Recover {
+                context: OperationContext::new("svc-key", 0, "svc-recover"),
+                shares: shares[1..].to_vec(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(recovered, ServiceResponse::Secret(s) if *s == secret));
+        drop(operator);
+
+        let mut auditor = connect(3);
+        let verified = call(
+            &mut auditor,
+            ServiceRequest::Verify {
+                context: OperationContext::new("svc-key", 0, "svc-verify"),
+                shares: shares.clone(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(verified, ServiceResponse::Verified));
+        let refused = call(
+            &mut auditor,
+            ServiceRequest::Recover {
+                context: OperationContext::new("svc-key", 0, "svc-theft"),
+                shares,
+            },
+        )
+        .unwrap();
+        assert!(matches!(refused, ServiceResponse::Error { code, .. } if code == "ZKT-E006"));
+        drop(auditor);
+        server.join().unwrap();
+
+        let codes: Vec<&str> = service.with_system(|system| {
+            system
+                .get_audit_events()
+                .iter()
+                .filter(|e| {
+                    matches!(
+                        e,
+                        SecurityEvent::ServiceRequestHandled { .. }
+                            | SecurityEvent::UnauthorizedAccess { .. }
+                    )
+                })
+                .map(|e| e.code())
+                .collect()
+        });
+        assert_eq!(codes, ["ZKT-A024", "ZKT-A024", "ZKT-A024", "ZKT-A005"]);
+
+        // An unknown participant is refused during the handshake.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || service.accept(&listener, TIMEOUT).is_err());
+        let stranger = transport::connect(
+            addr,
+            4,
+            1,
+            &parameters,
+            &ChannelKey::derive(&master, 4, 1),
+            TIMEOUT,
+        );
+        assert!(stranger.is_err());
+        assert!(server.join().unwrap());
+    }
+
+    #[test]
+    fn test_failed_operation_is_audited() {
+        let mut service = Service::new(EnterpriseCryptoSystem::new(EnterpriseConfig::default()), 1);
+        service.grant(2, ChannelKey::from_bytes([1; 32]), &[Operation::Deal]);
+        let response = service.handle(
+            2,
+            ServiceRequest::Deal {
+                context: OperationContext::new("k", 0, "bad-deal"),
+                secret: Zeroizing::new(Scalar::ONE),
+                threshold: 4,
+                num_shares: 3,
+            },
+        );
+        assert!(matches!(response, ServiceResponse::Error { ref code, .. } if code == "ZKT-E005"));
+        let outcome = service.with_system(|system| {
+            system.get_audit_events().iter().find_map(|e| match e {
+                SecurityEvent::ServiceRequestHandled { outcome, .. } => Some(outcome.clone()),
+                _ => None,
+            })
+        });
+        assert_eq!(outcome.as_deref(), Some("ZKT-E005"));
+        assert!(
+            format!("{:?}", ServiceResponse::Secret(Zeroizing::new(Scalar::ONE)))
+                .contains("redacted")
+        );
+    }
+}
@@ -0,0 +1,710 @@
+//! **shareholder module**
+//!
+//! Reference implementation of the holder side of the protocol, so an integrator only supplies
+//! transport and UI.
+//!
+//! A [`Shareholder`] owns one slice index, an X25519 [`WrappingSecretKey`] that dealers wrap its
+//! slices to, and a [`SealingKey`] under which it keeps them at rest. It
+//!
+//! - accepts a dealing ([`Shareholder::receive_dealing`]): checks the published commitments
+//!   against the dealer's root, unwraps the slice, verifies it against the commitments, seals it
+//!   and stores it together with its context and commitments;
+//! - answers health checks ([`Shareholder::attest`]) with a [`ShareAttestation`];
+//! - applies a proactive refresh ([`Shareholder::refresh`]) only when the [`RefreshRecord`]
+//!   continues the stored dealing and the new slice lies on the refreshed commitments;
+//! - releases its slice for recovery ([`Shareholder::answer_recovery`]) wrapped to the
+//!   requester's key, never in the clear.
+//!
+//! Storage and local policy are pluggable. A [`ShareStore`] keeps one [`StoredShare`] per key
+//! ([`MemoryShareStore`], [`FileShareStore`]); a [`ShareholderPolicy`] decides every attestation,
+//! refresh and recovery request before the slice is unsealed ([`AllowAll`], [`PinPolicy`],
+//! [`TimeLock`], and pairs of policies, which must both allow). Dealings and refreshes must
+//! advance the stored epoch, so a replayed or rolled-back message is refused, and a recovery
+//! request must name the epoch of the slice held.
+
+use crate::context::OperationContext;
+use crate::counters::{io_error, write_atomically};
+use crate::custodian::{self, ShareAttestation};
+use crate::error::{CryptoError, CryptoResult};
+use crate::events::{structured_log, EventCode};
+use crate::hash_adapter::Blake3Adapter;
+use crate::sealing::{self, SealedShare, SealingKey};
+use crate::sharing::ShareData;
+use crate::vss::{RefreshRecord, VssCommitments};
+use crate::wrapping::{WrappedShare, WrappingPublicKey, WrappingSecretKey};
+use chrono::{DateTime, Utc};
+use log::Level;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroize;
+
+/// Version of the [`StoredShare`] format.
+pub const STORED_SHARE_VERSION: u8 = 1;
+
+const PIN_CONTEXT: &str = "zk-thresh-pro 2024 shareholder pin v1";
+
+/// A slice held for one key, as kept by a [`ShareStore`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StoredShare {
+    /// Record format version, [`STORED_SHARE_VERSION`].
+    pub version: u8,
+    /// Context of the dealing or refresh the slice came from.
+    pub context: OperationContext,
+    /// Commitments the slice was verified against.
+    pub commitments: VssCommitments,
+    pub sealed: SealedShare,
+}
+
+/// Durable storage of the slices a shareholder holds, one per key.
+pub trait ShareStore {
+    /// The slice held for `key_id`, if any.
+    fn load(&self, key_id: &str) -> CryptoResult<Option<StoredShare>>;
+
+    /// Durably replace the slice held for `record.context.key_id`.
+    fn save(&mut self, record: &StoredShare) -> CryptoResult<()>;
+
+    /// Forget the slice held for `key_id`; removing an absent key is not an error.
+    fn remove(&mut self, key_id: &str) -> CryptoResult<()>;
+}
+
+/// Slices held in process memory; they do not survive a restart.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryShareStore {
+    records: Vec<StoredShare>,
+}
+
+impl MemoryShareStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ShareStore for MemoryShareStore {
+    fn load(&self, key_id: &str) -> CryptoResult<Option<StoredShare>> {
+        Ok(self
+            .records
+            .iter()
+            .find(|r| r.context.key_id == key_id)
+            .cloned())
+    }
+
+    fn save(&mut self, record: &StoredShare) -> CryptoResult<()> {
+        self.remove(&record.context.key_id)?;
+        self.records.push(record.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, key_id: &str) -> CryptoResult<()> {
+        self.records.retain(|r| r.context.key_id != key_id);
+        Ok(())
+    }
+}
+
+/// Slices kept as JSON files in a directory.
+///
+/// Each key is a file named by the lowercase hex of its id, replaced atomically like the files of
+/// a [`crate::counters::FileCounterStore`]. The slice inside is sealed; the commitments and
+/// context are public. The store is meant for a single process.
+#[derive(Clone, Debug)]
+pub struct FileShareStore {
+    dir: PathBuf,
+}
+
+impl FileShareStore {
+    /// Use `dir`, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>) -> CryptoResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", hex::encode(key_id)))
+    }
+}
+
+impl ShareStore for FileShareStore {
+    fn load(&self, key_id: &str) -> CryptoResult<Option<StoredShare>> {
+        let path = self.path(key_id);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+        let record: StoredShare =
+            serde_json::from_slice(&bytes).map_err(|e| CryptoError::Serialization {
+                details: format!("share file {} is corrupt: {}", path.display(), e),
+            })?;
+        if record.version != STORED_SHARE_VERSION || record.context.key_id != key_id {
+            return Err(CryptoError::Serialization {
+                details: format!("share file {} is not a slice of {}", path.display(), key_id),
+            });
+        }
+        Ok(Some(record))
+    }
+
+    fn save(&mut self, record: &StoredShare) -> CryptoResult<()> {
+        let bytes = serde_json::to_vec(record).map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })?;
+        write_atomically(&self.dir, &self.path(&record.context.key_id), &bytes)
+    }
+
+    fn remove(&mut self, key_id: &str) -> CryptoResult<()> {
+        let path = self.path(key_id);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(&path, e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Kind of request a [`ShareholderPolicy`] decides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    Attestation,
+    Refresh,
+    Recovery,
+}
+
+/// A request presented to a [`ShareholderPolicy`].
+#[derive(Clone, Copy, Debug)]
+pub struct PolicyRequest<'a> {
+    pub kind: RequestKind,
+    /// Context of the request; for attestations, that of the slice held.
+    pub context: &'a OperationContext,
+    /// Credential supplied with the request (e.g. a PIN entered in the holder's UI).
+    pub credential: Option<&'a [u8]>,
+    pub now: DateTime<Utc>,
+}
+
+/// Local policy of a shareholder.
+pub trait ShareholderPolicy {
+    /// Allow `request` with `Ok(())`; the error is returned to the caller unchanged.
+    fn authorize(&self, request: &PolicyRequest<'_>) -> CryptoResult<()>;
+}
+
+/// Allows every request, for holders whose transport already authenticates requesters.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllowAll;
+
+impl ShareholderPolicy for AllowAll {
+    fn authorize(&self, _request: &PolicyRequest<'_>) -> CryptoResult<()> {
+        Ok(())
+    }
+}
+
+/// Both policies must allow.
+impl<A: ShareholderPolicy, B: ShareholderPolicy> ShareholderPolicy for (A, B) {
+    fn authorize(&self, request: &PolicyRequest<'_>) -> CryptoResult<()> {
+        self.0.authorize(request)?;
+        self.1.authorize(request)
+    }
+}
+
+/// Requires a PIN for the listed kinds of request.
+///
+/// Only a salted BLAKE3 digest of the PIN is kept, and it is compared in constant time. `Debug`
+/// output shows the protected kinds only.
+#[derive(Clone)]
+pub struct PinPolicy {
+    salt: [u8; 16],
+    digest: [u8; 32],
+    kinds: Vec<RequestKind>,
+}
+
+impl PinPolicy {
+    /// Protect `kinds` with `pin`.
+    ///
+    /// Returns `CryptoError::Validation` for an empty PIN and `CryptoError::InsufficientEntropy`
+    /// if the salt cannot be drawn.
+    pub fn new(pin: &[u8], kinds: &[RequestKind]) -> CryptoResult<Self> {
+        if pin.is_empty() {
+            return Err(CryptoError::Validation {
+                field: "pin".to_string(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+        let mut salt = [0u8; 16];
+        crate::utils::fill_random(&mut salt)?;
+        Ok(Self {
+            salt,
+            digest: Self::hash(&salt, pin),
+            kinds: kinds.to_vec(),
+        })
+    }
+
+    fn hash(salt: &[u8; 16], pin: &[u8]) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(PIN_CONTEXT);
+        digest::Update::update(&mut hasher, salt);
+        digest::Update::update(&mut hasher, pin);
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+impl ShareholderPolicy for PinPolicy {
+    fn authorize(&self, request: &PolicyRequest<'_>) -> CryptoResult<()> {
+        if !self.kinds.contains(&request.kind) {
+            return Ok(());
+        }
+        let Some(pin) = request.credential else {
+            return Err(CryptoError::SecurityViolation {
+                details: format!("{:?} requires a PIN", request.kind),
+            });
+        };
+        let mut digest = Self::hash(&self.salt, pin);
+        // blake3::Hash compares in constant time.
+        let matches = blake3::Hash::from(digest) == blake3::Hash::from(self.digest);
+        digest.zeroize();
+        if !matches {
+            return Err(CryptoError::SecurityViolation {
+                details: format!("wrong PIN for {:?}", request.kind),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PinPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PinPolicy")
+            .field("kinds", &self.kinds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Refuses the listed kinds of request before `not_before`.
+#[derive(Clone, Debug)]
+pub struct TimeLock {
+    pub not_before: DateTime<Utc>,
+    pub kinds: Vec<RequestKind>,
+}
+
+impl ShareholderPolicy for TimeLock {
+    fn authorize(&self, request: &PolicyRequest<'_>) -> CryptoResult<()> {
+        if self.kinds.contains(&request.kind) && request.now < self.not_before {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "{:?} is time-locked until {}",
+                    request.kind,
+                    self.not_before.to_rfc3339()
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The holder of one slice index across any number of keys.
+pub struct Shareholder<S: ShareStore, P: ShareholderPolicy> {
+    index: usize,
+    wrapping_key: WrappingSecretKey,
+    sealing_key: SealingKey,
+    store: S,
+    policy: P,
+}
+
+impl<S: ShareStore, P: ShareholderPolicy> Shareholder<S, P> {
+    /// Holder of slice `index`. Returns `CryptoError::Validation` for index 0.
+    pub fn new(
+        index: usize,
+        wrapping_key: WrappingSecretKey,
+        sealing_key: SealingKey,
+        store: S,
+        policy: P,
+    ) -> CryptoResult<Self> {
+        if index == 0 {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: "slice indices start at 1".to_string(),
+            });
+        }
+        Ok(Self {
+            index,
+            wrapping_key,
+            sealing_key,
+            store,
+            policy,
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Key dealers wrap this holder's slices to.
+    pub fn public_key(&self) -> WrappingPublicKey {
+        self.wrapping_key.public_key()
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The stored slice of `key_id`, or `CryptoError::Validation` if none is held.
+    fn held(&self, key_id: &str) -> CryptoResult<StoredShare> {
+        self.store
+            .load(key_id)?
+            .ok_or_else(|| CryptoError::Validation {
+                field: "key_id".to_string(),
+                reason: format!("no slice of {} is held", key_id),
+            })
+    }
+
+    /// Refuse a context that does not advance past the slice already held for its key.
+    fn check_advances(&self, context: &OperationContext) -> CryptoResult<()> {
+        if let Some(held) = self.store.load(&context.key_id)? {
+            if context.epoch <= held.context.epoch {
+                return Err(CryptoError::Validation {
+                    field: "epoch".to_string(),
+                    reason: format!(
+                        "epoch {} does not advance past the held epoch {}",
+                        context.epoch, held.context.epoch
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn authorize(
+        &self,
+        kind: RequestKind,
+        context: &OperationContext,
+        credential: Option<&[u8]>,
+    ) -> CryptoResult<()> {
+        let request = PolicyRequest {
+            kind,
+            context,
+            credential,
+            now: Utc::now(),
+        };
+        self.policy.authorize(&request).inspect_err(|e| {
+            structured_log!(
+                Level::Warn,
+                EventCode::ShareholderRequestDenied,
+                "shareholder request denied",
+                index = self.index,
+                key_id = context.key_id,
+                operation_id = context.operation_id,
+                kind = format!("{:?}", kind),
+                error = e.code()
+            );
+        })
+    }
+
+    /// Unwrap this holder's slice from `wrapped`; the caller checks it against commitments.
+    fn unwrap(&self, wrapped: &WrappedShare) -> CryptoResult<ShareData> {
+        if wrapped.index != self.index {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: format!("slice {} is not for holder {}", wrapped.index, self.index),
+            });
+        }
+        ShareData::unseal(wrapped, &self.wrapping_key)
+    }
+
+    fn keep(
+        &mut self,
+        context: &OperationContext,
+        commitments: &VssCommitments,
+        share: &ShareData,
+    ) -> CryptoResult<()> {
+        let record = StoredShare {
+            version: STORED_SHARE_VERSION,
+            context: context.clone(),
+            commitments: commitments.clone(),
+            sealed: sealing::seal(share, &self.sealing_key)?,
+        };
+        self.store.save(&record)?;
+        structured_log!(
+            Level::Info,
+            EventCode::ShareholderShareStored,
+            "shareholder slice stored",
+            index = self.index,
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id
+        );
+        Ok(())
+    }
+
+    /// Accept this holder's slice of a dealing.
+    ///
+    /// `expected_root` is the commitment root published by the dealer. Returns
+    /// `CryptoError::SecurityViolation` if the commitments do not match it or the slice does not
+    /// unwrap or verify, and `CryptoError::Validation` if the slice is for another index or the
+    /// epoch does not advance past a slice already held for the key.
+    pub fn receive_dealing(
+        &mut self,
+        context: &OperationContext,
+        wrapped: &WrappedShare,
+        commitments: &VssCommitments,
+        expected_root: &[u8],
+    ) -> CryptoResult<()> {
+        let root = commitments.root();
+        if root.as_slice() != expected_root {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "commitment root {} does not match the published root {}",
+                    hex::encode(root),
+                    hex::encode(expected_root)
+                ),
+            });
+        }
+        self.check_advances(context)?;
+        let share = self.unwrap(wrapped)?;
+        commitments.verify_share(&share)?;
+        self.keep(context, commitments, &share)
+    }
+
+    /// Health check of the slice of `key_id` ([`custodian::check_share`]) answering the operator's
+    /// `challenge` nonce; the envelope is re-sealed in storage.
+    pub fn attest(
+        &mut self,
+        key_id: &str,
+        challenge: &[u8],
+        credential: Option<&[u8]>,
+    ) -> CryptoResult<ShareAttestation> {
+        let mut held = self.held(key_id)?;
+        self.authorize(RequestKind::Attestation, &held.context, credential)?;
+        let (resealed, attestation) = custodian::check_share(
+            &held.sealed,
+            &self.sealing_key,
+            &held.commitments,
+            &held.commitments.root(),
+            challenge,
+        )?;
+        held.sealed = resealed;
+        self.store.save(&held)?;
+        Ok(attestation)
+    }
+
+    /// Replace the held slice with its refresh under `context`.
+    ///
+    /// `record` must continue the held dealing ([`RefreshRecord::verify`]) and the new slice must
+    /// lie on `record.commitments`; otherwise `CryptoError::SecurityViolation` is returned and
+    /// the held slice is kept. The epoch must advance as for [`Self::receive_dealing`].
+    pub fn refresh(
+        &mut self,
+        context: &OperationContext,
+        wrapped: &WrappedShare,
+        record: &RefreshRecord,
+        credential: Option<&[u8]>,
+    ) -> CryptoResult<()> {
+        self.check_advances(context)?;
+        let held = self.held(&context.key_id)?;
+        self.authorize(RequestKind::Refresh, context, credential)?;
+        record.verify(&held.commitments)?;
+        let share = self.unwrap(wrapped)?;
+        record.commitments.verify_share(&share)?;
+        self.keep(context, &record.commitments, &share)
+    }
+
+    /// Release the held slice for the recovery `context`, wrapped to `recipient`.
+    ///
+    /// Returns `CryptoError::Validation` if `context` names another epoch than the slice held.
+    pub fn answer_recovery(
+        &mut self,
+        context: &OperationContext,
+        recipient: &WrappingPublicKey,
+        credential: Option<&[u8]>,
+    ) -> CryptoResult<WrappedShare> {
+        let held = self.held(&context.key_id)?;
+        if context.epoch != held.context.epoch {
+            return Err(CryptoError::Validation {
+                field: "epoch".to_string(),
+                reason: format!(
+                    "recovery at epoch {}, the held slice is at epoch {}",
+                    context.epoch, held.context.epoch
+                ),
+            });
+        }
+        self.authorize(RequestKind::Recovery, context, credential)?;
+        let share = sealing::unseal(&held.sealed, &self.sealing_key)?;
+        let wrapped = share.seal(recipient)?;
+        structured_log!(
+            Level::Info,
+            EventCode::ShareholderShareReleased,
+            "shareholder slice released for recovery",
+            index = self.index,
+            key_id = context.key_id,
+            epoch = context.epoch,
+            operation_id = context.operation_id
+        );
+        Ok(wrapped)
+    }
+
+    /// Forget the slice of `key_id`, e.g. after the key was destroyed.
+    pub fn discard(&mut self, key_id: &str) -> CryptoResult<()> {
+        self.store.remove(key_id)
+    }
+}
+
+impl<S: ShareStore + fmt::Debug, P: ShareholderPolicy + fmt::Debug> fmt::Debug
+    for Shareholder<S, P>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shareholder")
+            .field("index", &self.index)
+            .field("public_key", &self.public_key())
+            .field("store", &self.store)
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharing::{generate_key_shares_with_commitments, refresh_shares};
+    use curve25519_dalek::scalar::Scalar;
+
+    fn holder<P: ShareholderPolicy>(index: usize, policy: P) -> Shareholder<MemoryShareStore, P> {
+        Shareholder::new(
+            index,
+            WrappingSecretKey::generate().unwrap(),
+            SealingKey::generate().unwrap(),
+            MemoryShareStore::new(),
+            policy,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_shareholder_lifecycle() {
+        let secret = Scalar::from(1234u64);
+        let (shares, commitments) = generate_key_shares_with_commitments(secret, 2, 3).unwrap();
+        let root = commitments.root();
+        let dealing = OperationContext::new("sh-key", 0, "deal-1");
+        let pin = PinPolicy::new(b"2468", &[RequestKind::Recovery]).unwrap();
+        let mut first = holder(1, pin);
+        let mut second = holder(2, AllowAll);
+
+        let wrapped = shares[0].seal(&first.public_key()).unwrap();
+        first
+            .receive_dealing(&dealing, &wrapped, &commitments, &root)
+            .unwrap();
+        let wrapped = shares[1].seal(&second.public_key()).unwrap();
+        assert!(matches!(
+            second.receive_dealing(&dealing, &wrapped, &commitments, &[0u8; 32]),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let misaddressed = shares[2].seal(&second.public_key()).unwrap();
+        assert!(matches!(
+            second.receive_dealing(&dealing, &misaddressed, &commitments, &root),
+            Err(CryptoError::Validation { .. })
+        ));
+        second
+            .receive_dealing(&dealing, &wrapped, &commitments, &root)
+            .unwrap();
+        // A replayed dealing does not advance the epoch.
+        assert!(second
+            .receive_dealing(&dealing, &wrapped, &commitments, &root)
+            .is_err());
+        let nonce = [3u8; 16];
+        assert!(first
+            .attest("sh-key", &nonce, None)
+            .unwrap()
+            .verify(&commitments, &nonce));
+
+        // Refresh both holders to epoch 1.
+        let (refreshed, record) = refresh_shares(&shares, &commitments).unwrap();
+        let epoch_1 = OperationContext::new("sh-key", 1, "refresh-1");
+        let (_, unrelated) = refresh_shares(&shares, &commitments).unwrap();
+        let wrapped = refreshed[0].seal(&first.public_key()).unwrap();
+        assert!(matches!(
+            first.refresh(&epoch_1, &wrapped, &unrelated, None),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        first.refresh(&epoch_1, &wrapped, &record, None).unwrap();
+        let wrapped = refreshed[1].seal(&second.public_key()).unwrap();
+        second.refresh(&epoch_1, &wrapped, &record, None).unwrap();
+        assert!(first
+            .refresh(
+                &OperationContext::new("other", 1, "r"),
+                &wrapped,
+                &record,
+                None
+            )
+            .is_err());
+
+        // Recovery: the PIN guards the first holder's slice; stale epochs are refused.
+        let requester = WrappingSecretKey::generate().unwrap();
+        let recovery = OperationContext::new("sh-key", 1, "recover-1");
+        let stale = OperationContext::new("sh-key", 0, "recover-0");
+        assert!(matches!(
+            second.answer_recovery(&stale, &requester.public_key(), None),
+            Err(CryptoError::Validation { .. })
+        ));
+        for credential in [None, Some(&b"1357"[..])] {
+            assert!(matches!(
+                first.answer_recovery(&recovery, &requester.public_key(), credential),
+                Err(CryptoError::SecurityViolation { .. })
+            ));
+        }
+        let released: Vec<ShareData> = [
+            first
+                .answer_recovery(&recovery, &requester.public_key(), Some(b"2468"))
+                .unwrap(),
+            second
+                .answer_recovery(&recovery, &requester.public_key(), None)
+                .unwrap(),
+        ]
+        .iter()
+        .map(|w| ShareData::unseal(w, &requester).unwrap())
+        .collect();
+        assert_eq!(
+            crate::lagrange_fft::recover_secret_fft(&released).unwrap(),
+            secret
+        );
+
+        second.discard("sh-key").unwrap();
+        assert!(second.attest("sh-key", &[3u8; 16], None).is_err());
+    }
+
+    #[test]
+    fn test_time_lock_and_file_store() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 2, 2).unwrap();
+        let lock = TimeLock {
+            not_before: Utc::now() + chrono::TimeDelta::try_hours(1).unwrap(),
+            kinds: vec![RequestKind::Recovery],
+        };
+        let dir = std::env::temp_dir().join(format!("zk-thresh-shares-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let sealing_key = SealingKey::from_bytes([9u8; 32]);
+        let mut locked = Shareholder::new(
+            1,
+            WrappingSecretKey::generate().unwrap(),
+            sealing_key.clone(),
+            FileShareStore::open(&dir).unwrap(),
+            (AllowAll, lock),
+        )
+        .unwrap();
+        let context = OperationContext::new("file-key", 0, "deal");
+        let wrapped = shares[0].seal(&locked.public_key()).unwrap();
+        locked
+            .receive_dealing(&context, &wrapped, &commitments, &commitments.root())
+            .unwrap();
+        locked.attest("file-key", &[4u8; 16], None).unwrap();
+        assert!(matches!(
+            locked.answer_recovery(&context, &locked.public_key(), None),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // The stored record survives a restart and still unseals under the sealing key.
+        let reopened = FileShareStore::open(&dir).unwrap();
+        let record = reopened.load("file-key").unwrap().unwrap();
+        assert_eq!(record.context, context);
+        assert_eq!(
+            sealing::unseal(&record.sealed, &sealing_key).unwrap().share,
+            shares[0].share
+        );
+        assert!(reopened.load("missing").unwrap().is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
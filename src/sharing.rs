@@ -3,40 +3,289 @@
 //! This module implements secret sharing, sharding updates and dynamic threshold adjustment.
 //! Uses polynomial interpolation principle to generate slices and zero-knowledge proofs to verify the validity of slices.
 
-use crate::utils::ANOTHER_POINT;
-use crate::{lagrange_fft, proof, utils};
-use curve25519_dalek::{
-    constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
-};
+#[cfg(feature = "dealer")]
+use crate::deadline::{self, Deadline};
+use crate::error::{CryptoError, CryptoResult};
+use crate::group::{encoding, CurveGroup, Ristretto255};
+#[cfg(feature = "dealer")]
+use crate::hash_adapter::{Blake3Adapter, HashAdapter};
+use crate::identity::{Roster, Signed, SignedPayload};
+use crate::proof;
+use crate::redact::Redacted;
+#[cfg(feature = "dealer")]
+use crate::vss::VssCommitments;
+#[cfg(feature = "dealer")]
+use crate::{lagrange_fft, utils};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+#[cfg(feature = "dealer")]
 use rand::rngs::OsRng;
+#[cfg(feature = "dealer")]
 use rayon::prelude::*;
+use std::fmt;
+
+pub mod gf256;
+#[cfg(feature = "dealer")]
+pub mod typed;
 
 /// A data structure representing a secret slice and its associated data (promises, random numbers and proofs).
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
-pub struct ShareData {
+///
+/// The slice is over ristretto255 unless another [`CurveGroup`] is named; slices in other groups
+/// are dealt with [`generate_group_shares`].
+///
+/// `Debug` output redacts `share` and `random`, showing their fingerprints only.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(bound = "")]
+pub struct ShareData<G: CurveGroup = Ristretto255> {
     /// Sliced index, must be non-zero and unique.
     pub index: usize,
     /// slice value
-    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
-    pub share: Scalar,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<G, _>",
+        deserialize_with = "encoding::deserialize_scalar::<G, _>"
+    )]
+    pub share: G::Scalar,
     /// Split promises, obtained by splitting the slice with a random number calculation.
-    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
-    pub commitment: RistrettoPoint,
+    #[serde(
+        serialize_with = "encoding::serialize_element::<G, _>",
+        deserialize_with = "encoding::deserialize_element::<G, _>"
+    )]
+    pub commitment: G::Element,
     /// Random numbers for blinding.
-    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
-    pub random: Scalar,
+    #[serde(
+        serialize_with = "encoding::serialize_scalar::<G, _>",
+        deserialize_with = "encoding::deserialize_scalar::<G, _>"
+    )]
+    pub random: G::Scalar,
     /// Zero-knowledge proofs for correctness of slicing and commitment.
-    pub proof: proof::Proof,
+    pub proof: proof::Proof<G>,
+}
+
+impl fmt::Debug for ShareData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShareData")
+            .field("index", &self.index)
+            .field("share", &Redacted(&self.share))
+            .field(
+                "commitment",
+                &hex::encode(self.commitment.compress().as_bytes()),
+            )
+            .field("random", &Redacted(&self.random))
+            .field("proof", &self.proof)
+            .finish()
+    }
+}
+
+/// Length of the binary [`ShareData`] encoding.
+pub const SHARE_DATA_LENGTH: usize = 8 + 3 * 32 + proof::PROOF_LENGTH;
+
+impl ShareData {
+    /// Fixed-length encoding: the index as a little-endian `u64`, the canonical bytes of `share`,
+    /// the compressed commitment, the canonical bytes of `random`, then [`proof::Proof::to_bytes`].
+    ///
+    /// The bytes contain the secret slice and are zeroized when dropped.
+    pub fn to_bytes(&self) -> zeroize::Zeroizing<[u8; SHARE_DATA_LENGTH]> {
+        let mut bytes = zeroize::Zeroizing::new([0u8; SHARE_DATA_LENGTH]);
+        bytes[..8].copy_from_slice(&(self.index as u64).to_le_bytes());
+        bytes[8..40].copy_from_slice(self.share.as_bytes());
+        bytes[40..72].copy_from_slice(self.commitment.compress().as_bytes());
+        bytes[72..104].copy_from_slice(self.random.as_bytes());
+        bytes[104..].copy_from_slice(&self.proof.to_bytes());
+        bytes
+    }
+
+    /// Decode [`ShareData::to_bytes`], rejecting index 0, invalid or identity points and
+    /// non-canonical scalars. The proof is not verified.
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        use crate::serialization::{point_from_slice, scalar_from_slice};
+        if bytes.len() != SHARE_DATA_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: format!(
+                    "slice must be {} bytes, got {}",
+                    SHARE_DATA_LENGTH,
+                    bytes.len()
+                ),
+            });
+        }
+        let index = u64::from_le_bytes(bytes[..8].try_into().expect("8-byte index"));
+        let index = usize::try_from(index)
+            .ok()
+            .filter(|&i| i != 0)
+            .ok_or_else(|| CryptoError::Serialization {
+                details: format!("invalid slice index {}", index),
+            })?;
+        Ok(ShareData {
+            index,
+            share: scalar_from_slice(&bytes[8..40], "slice value")?,
+            commitment: point_from_slice(&bytes[40..72], "slice commitment")?,
+            random: scalar_from_slice(&bytes[72..104], "slice random")?,
+            proof: proof::Proof::from_bytes(&bytes[104..])?,
+        })
+    }
+
+    /// The slice of `share`, once it is signed for `session` by `dealer` under `roster` and its
+    /// proof verifies.
+    ///
+    /// Returns `CryptoError::SecurityViolation` for another session or signer, or an invalid proof.
+    pub fn verify_signed<'a>(
+        share: &'a Signed<Self>,
+        roster: &Roster,
+        session: &str,
+        dealer: usize,
+    ) -> CryptoResult<&'a Self> {
+        let slice = share.verify_from(roster, session, dealer)?;
+        crate::vss::verify_share_validity(std::slice::from_ref(slice))?;
+        Ok(slice)
+    }
+}
+
+impl SignedPayload for ShareData {
+    const DOMAIN: &'static str = "dealt share";
 }
 
-impl Drop for ShareData {
+#[cfg(feature = "insecure-debug")]
+impl ShareData {
+    /// Render the slice including the raw share value and blinding random. Only available with `insecure-debug`.
+    pub fn reveal_for_debug(&self) -> String {
+        format!(
+            "ShareData {{ index: {}, share: {}, commitment: {}, random: {}, proof: {:?} }}",
+            self.index,
+            hex::encode(self.share.as_bytes()),
+            hex::encode(self.commitment.compress().as_bytes()),
+            hex::encode(self.random.as_bytes()),
+            self.proof
+        )
+    }
+}
+
+impl<G: CurveGroup> Drop for ShareData<G> {
     /// When ShareData leaves the scope, sensitive data is cleared to reduce the risk of side-channel attacks.
     fn drop(&mut self) {
-        self.share = Scalar::ZERO;
-        self.random = Scalar::ZERO;
+        zeroize::Zeroize::zeroize(&mut self.share);
+        zeroize::Zeroize::zeroize(&mut self.random);
     }
 }
 
+/// A slice of several related secrets dealt to the same committee in one ceremony.
+///
+/// All secrets share the evaluation point `index`; each has its own value, blinding random and
+/// commitment, but the slice carries a single proof over the combined commitment
+/// `Σ ρ_k · C_k` (see [`proof::combination_weights`]).
+///
+/// `Debug` output redacts `shares` and `randoms`, showing their fingerprints only.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct MultiShareData {
+    /// Sliced index, must be non-zero and unique.
+    pub index: usize,
+    /// One slice value per secret, in dealing order.
+    #[serde(with = "crate::serialization::serialize_scalar_vec_helpers")]
+    pub shares: Vec<Scalar>,
+    /// One commitment per secret.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_vec_helpers")]
+    pub commitments: Vec<RistrettoPoint>,
+    /// One blinding random per secret.
+    #[serde(with = "crate::serialization::serialize_scalar_vec_helpers")]
+    pub randoms: Vec<Scalar>,
+    /// Proof of knowledge of the opening of the combined commitment.
+    pub proof: proof::Proof,
+}
+
+impl fmt::Debug for MultiShareData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shares: Vec<_> = self.shares.iter().map(Redacted).collect();
+        let randoms: Vec<_> = self.randoms.iter().map(Redacted).collect();
+        let commitments: Vec<_> = self
+            .commitments
+            .iter()
+            .map(|c| hex::encode(c.compress().as_bytes()))
+            .collect();
+        f.debug_struct("MultiShareData")
+            .field("index", &self.index)
+            .field("shares", &shares)
+            .field("commitments", &commitments)
+            .field("randoms", &randoms)
+            .field("proof", &self.proof)
+            .finish()
+    }
+}
+
+impl MultiShareData {
+    /// The combined commitment the slice proof is made over.
+    pub fn combined_commitment(&self) -> RistrettoPoint {
+        let weights = proof::combination_weights(self.index, &self.commitments);
+        proof::combine_commitments(&weights, &self.commitments)
+    }
+
+    /// Number of secrets carried by the slice.
+    pub fn secret_count(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Extract a standalone slice for secret `k`, proving its commitment afresh.
+    ///
+    /// Returns `Ok(None)` if the slice carries fewer than `k + 1` secrets.
+    #[cfg(feature = "dealer")]
+    pub fn share_for(&self, k: usize) -> CryptoResult<Option<ShareData>> {
+        let (Some(&share), Some(&random), Some(&commitment)) = (
+            self.shares.get(k),
+            self.randoms.get(k),
+            self.commitments.get(k),
+        ) else {
+            return Ok(None);
+        };
+        Ok(Some(ShareData {
+            index: self.index,
+            share,
+            commitment,
+            random,
+            proof: proof::generate_proof(share, random, self.index, commitment)?,
+        }))
+    }
+}
+
+impl Drop for MultiShareData {
+    /// When MultiShareData leaves the scope, sensitive data is cleared to reduce the risk of side-channel attacks.
+    fn drop(&mut self) {
+        self.shares.iter_mut().for_each(|s| *s = Scalar::ZERO);
+        self.randoms.iter_mut().for_each(|r| *r = Scalar::ZERO);
+    }
+}
+
+/// Slice of a hierarchical dealing ([`generate_hierarchical_shares`]).
+///
+/// `share.share` is the derivative of order `k_{level-1}` of the dealing polynomial at
+/// `share.index` (the value itself at level 1), where `k_l` is the cumulative threshold of level
+/// `l`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct HierarchicalShare {
+    /// Level of the holder, from 1 (the most senior).
+    pub level: usize,
+    pub share: ShareData,
+}
+
+/// Check dealing parameters: at least one slice and `1 <= threshold <= n`.
+#[cfg(feature = "dealer")]
+pub(crate) fn validate_parameters(threshold: usize, n: usize) -> CryptoResult<()> {
+    if n == 0 {
+        return Err(CryptoError::Validation {
+            field: "n".to_string(),
+            reason: "at least one slice is required".to_string(),
+        });
+    }
+    if threshold == 0 {
+        return Err(CryptoError::Validation {
+            field: "threshold".to_string(),
+            reason: "threshold must be at least 1".to_string(),
+        });
+    }
+    if threshold > n {
+        return Err(CryptoError::Validation {
+            field: "threshold".to_string(),
+            reason: format!("threshold {} exceeds n {}", threshold, n),
+        });
+    }
+    Ok(())
+}
+
 /// Generate a secret slice.
 ///
 /// # Parameters
@@ -47,35 +296,512 @@ impl Drop for ShareData {
 /// - `threshold`: The minimum number of slices needed to recover the secret.
 /// # Return value
 ///
-/// Returns a vector containing all the sliced data.
-pub fn generate_key_shares(secret: Scalar, threshold: usize, n: usize) -> Vec<ShareData> {
+/// Returns a vector containing all the sliced data, `CryptoError::Validation` unless
+/// `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if the random generator fails.
+/// The coefficient commitments are not computed; use
+/// [`generate_key_shares_with_commitments`] to publish them.
+///
+/// A 1-of-n dealing gives every holder the secret itself; an n-of-n dealing is drawn as an
+/// additive sharing, without polynomial evaluation or commitments. Both recover and refresh like
+/// any other dealing.
+#[cfg(feature = "dealer")]
+pub fn generate_key_shares(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<Vec<ShareData>> {
+    prove_shares(deal_unproven(secret, threshold, n)?)
+}
+
+/// Unproven slices of a dealing whose coefficient commitments are not published.
+///
+/// For `threshold == n`, the first `n - 1` slices and all blinding randoms are drawn directly
+/// and the last slice is solved so that the Lagrange weights at zero, `Σ λ_i·s_i`, give the
+/// secret: the weighted slices are an additive sharing. A random polynomial of degree `n - 1`
+/// through the secret is uniform at any `n - 1` points, so the slices are distributed exactly as
+/// those of [`generate_key_shares_deferred`], without evaluating or committing to polynomials.
+#[cfg(feature = "dealer")]
+pub(crate) fn deal_unproven(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<Vec<UnprovenShare>> {
+    validate_parameters(threshold, n)?;
+    if threshold < n {
+        return generate_key_shares_deferred(secret, threshold, n).map(|(shares, _)| shares);
+    }
+    let mut rng = OsRng;
+    let xs: Vec<Scalar> = (1..=n).map(|i| Scalar::from(i as u64)).collect();
+    let lambda = lagrange_fft::compute_lagrange_coefficients(&xs).map_err(|e| {
+        CryptoError::CryptographicOperation {
+            operation: format!("additive_sharing: {}", e),
+        }
+    })?;
+    let mut values = (1..n)
+        .map(|_| utils::random_scalar(&mut rng))
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let partial: Scalar = values.iter().zip(&lambda).map(|(v, l)| v * l).sum();
+    values.push((secret - partial) * lambda[n - 1].invert());
+    let randoms = (0..n)
+        .map(|_| utils::random_scalar(&mut rng))
+        .collect::<CryptoResult<Vec<_>>>()?;
+    Ok(values
+        .into_par_iter()
+        .zip(randoms)
+        .enumerate()
+        .map(|(position, (share, random))| UnprovenShare {
+            index: position + 1,
+            share,
+            random,
+            commitment: utils::pedersen_commit(&share, &random),
+        })
+        .collect())
+}
+
+/// Generate a secret slice together with Pedersen-VSS coefficient commitments.
+///
+/// The blinding randoms are evaluations of a second random polynomial `g(x)` of the same degree,
+/// so every slice commitment equals `Σ_j index^j · C_j` for the returned [`VssCommitments`].
+///
+/// # Parameters
+///
+/// - `secret`: The secret to be shared.
+/// - `threshold`: Minimum number of slices needed to recover the secret.
+/// - `n`: Total number of slices to generate.
+///
+/// # Return value
+///
+/// Returns all slices and the commitments to publish, `CryptoError::Validation` unless
+/// `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_key_shares_with_commitments(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<(Vec<ShareData>, VssCommitments)> {
+    let (unproven, commitments) = generate_key_shares_deferred(secret, threshold, n)?;
+    Ok((prove_shares(unproven)?, commitments))
+}
+
+/// First phase of a dealing with deferred proving: slices and commitments, without proofs.
+///
+/// The slices can be distributed immediately; each holder checks its slice against the
+/// commitments with [`UnprovenShare::verify`]. The dealer then runs [`generate_share_proofs`]
+/// and publishes the proofs, which holders attach with [`UnprovenShare::attach_proof`].
+///
+/// Until the proofs are attached, a slice cannot be checked by anyone but its holder (APIs that
+/// verify proofs, such as recovery and custodian checks, reject it), and a holder that skips
+/// [`UnprovenShare::verify`] accepts the slice on trust. The dealer also keeps every slice in
+/// memory until the proving phase is over. Prefer [`generate_key_shares_with_commitments`] unless
+/// the proof latency matters.
+///
+/// # Return value
+///
+/// Returns all slices and the commitments to publish, `CryptoError::Validation` unless
+/// `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_key_shares_deferred(
+    secret: Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<(Vec<UnprovenShare>, VssCommitments)> {
+    validate_parameters(threshold, n)?;
+    let indices: Vec<usize> = (1..=n).collect();
+    deal_deferred_at(secret, threshold, &indices)
+}
+
+/// [`generate_key_shares_with_commitments`] with the slices issued at `indices` rather than
+/// `1..=n`, e.g. indices chosen by an [`IndexAssignment`](crate::indexing::IndexAssignment).
+///
+/// # Return value
+///
+/// Returns the slices in the order of `indices` and the commitments to publish,
+/// `CryptoError::Validation` for a zero or repeated index or unless
+/// `1 <= threshold <= indices.len()`, or `CryptoError::InsufficientEntropy` if the random
+/// generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_key_shares_at(
+    secret: Scalar,
+    threshold: usize,
+    indices: &[usize],
+) -> CryptoResult<(Vec<ShareData>, VssCommitments)> {
+    validate_parameters(threshold, indices.len())?;
+    let mut seen = std::collections::HashSet::new();
+    for &index in indices {
+        if index == 0 || !seen.insert(index) {
+            return Err(CryptoError::Validation {
+                field: "indices".to_string(),
+                reason: format!("index {} is zero or repeated", index),
+            });
+        }
+    }
+    let (unproven, commitments) = deal_deferred_at(secret, threshold, indices)?;
+    Ok((prove_shares(unproven)?, commitments))
+}
+
+/// [`generate_key_shares_with_commitments`] in the group `G`.
+///
+/// Each slice carries a [`proof::generate_group_proof`] proof and is checked with
+/// [`VssCommitments::verify_share`]; recover the secret with [`recover_group_secret`]. Over
+/// [`Ristretto255`] the slices are those of [`generate_key_shares_with_commitments`].
+///
+/// # Return value
+///
+/// Returns all slices and the commitments to publish, `CryptoError::Validation` unless
+/// `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_group_shares<G: CurveGroup>(
+    secret: G::Scalar,
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<(Vec<ShareData<G>>, VssCommitments<G>)> {
+    use crate::group::{eval, random_scalar};
+    use zeroize::Zeroize;
+    validate_parameters(threshold, n)?;
+    let mut coeffs = vec![secret];
+    for _ in 1..threshold {
+        coeffs.push(random_scalar::<G>()?);
+    }
+    let mut blinding = (0..threshold)
+        .map(|_| random_scalar::<G>())
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let (g, h) = (G::generator(), G::blinding_generator());
+    let commitments = VssCommitments {
+        coefficients: coeffs
+            .iter()
+            .zip(&blinding)
+            .map(|(&a, &b)| g * a + h * b)
+            .collect(),
+    };
+    let zero = G::scalar_from_u64(0);
+    let shares = (1..=n)
+        .into_par_iter()
+        .map(|index| {
+            let x = G::scalar_from_u64(index as u64);
+            let share = eval(&coeffs, x, zero);
+            let random = eval(&blinding, x, zero);
+            let commitment = g * share + h * random;
+            Ok(ShareData {
+                index,
+                share,
+                commitment,
+                random,
+                proof: proof::generate_group_proof::<G>(share, random, index, commitment)?,
+            })
+        })
+        .collect::<CryptoResult<Vec<_>>>();
+    coeffs.zeroize();
+    blinding.zeroize();
+    Ok((shares?, commitments))
+}
+
+/// Draw the sharing and blinding polynomials and evaluate them at `indices`.
+#[cfg(feature = "dealer")]
+fn deal_deferred_at(
+    secret: Scalar,
+    threshold: usize,
+    indices: &[usize],
+) -> CryptoResult<(Vec<UnprovenShare>, VssCommitments)> {
     let mut global_rng = OsRng;
-    // Generate polynomial coefficients (except for constant terms).
-    let coeffs: Vec<Scalar> = (0..(threshold - 1))
+    // Polynomial coefficients f(x)= secret + coeff_1*x + coeff_2*x^2 + ...
+    let mut coeffs = vec![secret];
+    for _ in 1..threshold {
+        coeffs.push(utils::random_scalar(&mut global_rng)?);
+    }
+    // Blinding polynomial g(x) of the same degree, including a random constant term.
+    let blinding: Vec<Scalar> = (0..threshold)
         .map(|_| utils::random_scalar(&mut global_rng))
-        .collect();
+        .collect::<CryptoResult<_>>()?;
+
+    let commitments = VssCommitments {
+        coefficients: coeffs
+            .iter()
+            .zip(&blinding)
+            .map(|(a, b)| utils::pedersen_commit(a, b))
+            .collect(),
+    };
+
+    Ok((evaluate_shares(&coeffs, &blinding, indices), commitments))
+}
+
+/// Slice value, blinding and commitment before its proof is generated.
+///
+/// Serializes like [`ShareData`] without `proof`. `Debug` output redacts `share` and `random`.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct UnprovenShare {
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub share: Scalar,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub random: Scalar,
+}
+
+impl fmt::Debug for UnprovenShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnprovenShare")
+            .field("index", &self.index)
+            .field("share", &Redacted(&self.share))
+            .field(
+                "commitment",
+                &hex::encode(self.commitment.compress().as_bytes()),
+            )
+            .field("random", &Redacted(&self.random))
+            .finish()
+    }
+}
+
+/// Published proof of one slice of a deferred dealing.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ShareProof {
+    pub index: usize,
+    pub proof: proof::Proof,
+}
+
+impl UnprovenShare {
+    /// Check the slice against the dealing's commitments: it opens its commitment and the
+    /// commitment lies on the committed polynomials.
+    pub fn verify(&self, commitments: &crate::vss::VssCommitments) -> bool {
+        self.index != 0
+            && !commitments.coefficients.is_empty()
+            && crate::utils::pedersen_commit(&self.share, &self.random) == self.commitment
+            && commitments.expected_commitment(self.index) == self.commitment
+    }
+
+    /// Complete the slice with its published proof.
+    ///
+    /// Returns `CryptoError::Validation` if the proof is for another index and
+    /// `CryptoError::SecurityViolation` if it does not verify against the slice commitment.
+    pub fn attach_proof(self, published: &ShareProof) -> CryptoResult<ShareData> {
+        if published.index != self.index {
+            return Err(CryptoError::Validation {
+                field: "index".to_string(),
+                reason: format!("proof is for slice {}, not {}", published.index, self.index),
+            });
+        }
+        proof::verify_proof(&published.proof, self.commitment, self.index)?;
+        Ok(ShareData {
+            index: self.index,
+            share: self.share,
+            commitment: self.commitment,
+            random: self.random,
+            proof: published.proof.clone(),
+        })
+    }
+}
+
+/// First dealing stage: evaluate `coeffs` and `blinding` at `indices` and commit.
+///
+/// Evaluation of all slices runs before any commitment (multipoint evaluation for large `n`), and
+/// the commitments use the precomputed fixed-base tables of both generators. Constant
+/// polynomials (threshold 1) are copied to every slice under a single commitment.
+#[cfg(feature = "dealer")]
+pub(crate) fn evaluate_shares(
+    coeffs: &[Scalar],
+    blinding: &[Scalar],
+    indices: &[usize],
+) -> Vec<UnprovenShare> {
+    if let ([share], [random]) = (coeffs, blinding) {
+        let commitment = utils::pedersen_commit(share, random);
+        return indices
+            .iter()
+            .map(|&index| UnprovenShare {
+                index,
+                share: *share,
+                commitment,
+                random: *random,
+            })
+            .collect();
+    }
+    let points: Vec<Scalar> = indices.iter().map(|&i| Scalar::from(i as u64)).collect();
+    let values = lagrange_fft::poly_evaluate_multi(coeffs, &points);
+    let randoms = lagrange_fft::poly_evaluate_multi(blinding, &points);
+    values
+        .into_par_iter()
+        .zip(randoms)
+        .zip(indices)
+        .map(|((share, random), &index)| UnprovenShare {
+            index,
+            share,
+            random,
+            commitment: utils::pedersen_commit(&share, &random),
+        })
+        .collect()
+}
+
+/// Second dealing stage: generate the proofs of evaluated slices in parallel.
+#[cfg(feature = "dealer")]
+pub(crate) fn prove_shares(unproven: Vec<UnprovenShare>) -> CryptoResult<Vec<ShareData>> {
+    prove_shares_with(&Blake3Adapter::new(), unproven, None)
+}
+
+/// [`prove_shares`] with the proof challenges drawn by `hash`, checking `deadline` before each
+/// slice.
+#[cfg(feature = "dealer")]
+pub(crate) fn prove_shares_with(
+    hash: &dyn HashAdapter,
+    unproven: Vec<UnprovenShare>,
+    deadline: Option<&Deadline>,
+) -> CryptoResult<Vec<ShareData>> {
+    unproven
+        .into_par_iter()
+        .map(|u| {
+            deadline::check(deadline, "share_generation")?;
+            let proof = proof::generate_proof_with(hash, u.share, u.random, u.index, u.commitment)?;
+            Ok(ShareData {
+                index: u.index,
+                share: u.share,
+                commitment: u.commitment,
+                random: u.random,
+                proof,
+            })
+        })
+        .collect()
+}
+
+/// Replace the proofs of `shares` by proofs with challenges drawn by `hash`.
+///
+/// For slices dealt or refreshed by the functions of this module, which prove with BLAKE3, when
+/// they are issued under another [`HashAdapter`].
+#[cfg(feature = "dealer")]
+pub fn reprove_shares(hash: &dyn HashAdapter, shares: &mut [ShareData]) -> CryptoResult<()> {
+    reprove_shares_within(hash, shares, None)
+}
+
+/// [`reprove_shares`] checking `deadline` before each slice.
+#[cfg(feature = "dealer")]
+pub(crate) fn reprove_shares_within(
+    hash: &dyn HashAdapter,
+    shares: &mut [ShareData],
+    deadline: Option<&Deadline>,
+) -> CryptoResult<()> {
+    shares.par_iter_mut().try_for_each(|share| {
+        deadline::check(deadline, "share_generation")?;
+        share.proof = proof::generate_proof_with(
+            hash,
+            share.share,
+            share.random,
+            share.index,
+            share.commitment,
+        )?;
+        Ok(())
+    })
+}
+
+/// Second phase of a deferred dealing: the proofs of `shares`, to publish.
+///
+/// Proving needs the slices themselves, so this runs at the dealer; see
+/// [`generate_key_shares_deferred`].
+#[cfg(feature = "dealer")]
+pub fn generate_share_proofs(shares: &[UnprovenShare]) -> CryptoResult<Vec<ShareProof>> {
+    generate_share_proofs_with(&Blake3Adapter::new(), shares)
+}
+
+/// [`generate_share_proofs`] with the proof challenges drawn by `hash`.
+#[cfg(feature = "dealer")]
+pub fn generate_share_proofs_with(
+    hash: &dyn HashAdapter,
+    shares: &[UnprovenShare],
+) -> CryptoResult<Vec<ShareProof>> {
+    generate_share_proofs_within(hash, shares, None)
+}
+
+/// [`generate_share_proofs_with`] checking `deadline` before each slice.
+#[cfg(feature = "dealer")]
+pub(crate) fn generate_share_proofs_within(
+    hash: &dyn HashAdapter,
+    shares: &[UnprovenShare],
+    deadline: Option<&Deadline>,
+) -> CryptoResult<Vec<ShareProof>> {
+    shares
+        .par_iter()
+        .map(|u| {
+            deadline::check(deadline, "share_proof_generation")?;
+            Ok(ShareProof {
+                index: u.index,
+                proof: proof::generate_proof_with(hash, u.share, u.random, u.index, u.commitment)?,
+            })
+        })
+        .collect()
+}
+
+/// Generate slices of several related secrets for the same committee.
+///
+/// Every secret gets its own random polynomial of degree `threshold - 1`, but all secrets are
+/// evaluated at the same points (the powers of each index are computed once), and each slice
+/// carries one proof over the combined commitment instead of one proof per secret.
+///
+/// # Parameters
+///
+/// - `secrets`: The secrets to be shared, in order.
+/// - `threshold`: Minimum number of slices needed to recover the secrets.
+/// - `n`: Total number of slices to generate.
+///
+/// # Return value
+///
+/// Returns a vector containing all the multi-secret slices, `CryptoError::Validation` if
+/// `secrets` is empty or unless `1 <= threshold <= n`, or `CryptoError::InsufficientEntropy` if
+/// the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_key_shares_multi(
+    secrets: &[Scalar],
+    threshold: usize,
+    n: usize,
+) -> CryptoResult<Vec<MultiShareData>> {
+    validate_parameters(threshold, n)?;
+    if secrets.is_empty() {
+        return Err(CryptoError::Validation {
+            field: "secrets".to_string(),
+            reason: "at least one secret is required".to_string(),
+        });
+    }
+    let mut global_rng = OsRng;
+    // Generate polynomial coefficients (except for constant terms) for every secret.
+    let coeffs: Vec<Vec<Scalar>> = secrets
+        .iter()
+        .map(|_| {
+            (0..(threshold - 1))
+                .map(|_| utils::random_scalar(&mut global_rng))
+                .collect()
+        })
+        .collect::<CryptoResult<_>>()?;
 
-    // Parallel computation of each slice with slice indexes from 1 to n guaranteed to be unique.
     (1..=n)
         .into_par_iter()
         .map(|i| {
             let mut local_rng = OsRng;
             let x = Scalar::from(i as u64);
-            // 多项式 f(x)= secret + coeff_1*x + coeff_2*x^2 + ...
-            let mut share = secret;
-            for (j, coeff) in coeffs.iter().enumerate() {
-                share += coeff * utils::pow_scalar(x, (j + 1) as u32);
+            // Shared evaluation point: x, x^2, ..., x^(t-1)
+            let powers: Vec<Scalar> = (1..threshold)
+                .map(|j| utils::pow_scalar(x, j as u32))
+                .collect();
+            let mut shares = Vec::with_capacity(secrets.len());
+            let mut randoms = Vec::with_capacity(secrets.len());
+            let mut commitments = Vec::with_capacity(secrets.len());
+            for (secret, poly) in secrets.iter().zip(&coeffs) {
+                let mut share = *secret;
+                for (coeff, power) in poly.iter().zip(&powers) {
+                    share += coeff * power;
+                }
+                let random = utils::random_scalar(&mut local_rng)?;
+                commitments.push(utils::pedersen_commit(&share, &random));
+                shares.push(share);
+                randoms.push(random);
             }
-            let random = utils::random_scalar(&mut local_rng);
-            let commitment = RISTRETTO_BASEPOINT_POINT * share + (*ANOTHER_POINT) * random;
-            let proof = proof::generate_proof(share, random, i, commitment);
-            ShareData {
+            let weights = proof::combination_weights(i, &commitments);
+            let combined_share: Scalar = weights.iter().zip(&shares).map(|(w, s)| w * s).sum();
+            let combined_random: Scalar = weights.iter().zip(&randoms).map(|(w, r)| w * r).sum();
+            let combined_commitment = proof::combine_commitments(&weights, &commitments);
+            let proof =
+                proof::generate_proof(combined_share, combined_random, i, combined_commitment)?;
+            Ok(MultiShareData {
                 index: i,
-                share,
-                commitment,
-                random,
+                shares,
+                commitments,
+                randoms,
                 proof,
-            }
+            })
         })
         .collect()
 }
@@ -83,6 +809,7 @@ pub fn generate_key_shares(secret: Scalar, threshold: usize, n: usize) -> Vec<Sh
 /// Updating the slice (active secret sharing).
 ///
 /// Update the slice by adding a δ from a zero-constant random polynomial to each slice, ensuring that f(0) is unchanged.
+/// Use [`update_shares_linked`] to also publish proofs that the new slices continue the old ones.
 ///
 /// # Parameters
 ///
@@ -91,37 +818,172 @@ pub fn generate_key_shares(secret: Scalar, threshold: usize, n: usize) -> Vec<Sh
 ///
 /// # Return value
 ///
-/// Returns the updated set of slices.
-pub fn update_shares(shares: &[ShareData], threshold: usize) -> Vec<ShareData> {
+/// Returns the updated set of slices, `CryptoError::Validation` unless
+/// `1 <= threshold <= shares.len()`, or `CryptoError::InsufficientEntropy` if the random
+/// generator fails.
+#[cfg(feature = "dealer")]
+pub fn update_shares(shares: &[ShareData], threshold: usize) -> CryptoResult<Vec<ShareData>> {
+    update_shares_linked(shares, threshold).map(|(shares, _)| shares)
+}
+
+/// [`update_shares`] with a [`RefreshLinks`](crate::vss::RefreshLinks) record to publish.
+///
+/// Alongside the slice update `δ(x)`, draws a blinding update `ε(x)` with `ε(0) = 0`, publishes
+/// the commitments `D_j = δ_j·G + ε_j·H` and proves for every slice that its new commitment is
+/// the old one plus `D(i)` up to a multiple of `H`, binding the fresh blinding random to the old
+/// one. Each holder checks its slice pair with
+/// [`RefreshLinks::verify_share`](crate::vss::RefreshLinks::verify_share).
+///
+/// # Return value
+///
+/// Returns the updated slices and the record, with the same errors as [`update_shares`].
+#[cfg(feature = "dealer")]
+pub fn update_shares_linked(
+    shares: &[ShareData],
+    threshold: usize,
+) -> CryptoResult<(Vec<ShareData>, crate::vss::RefreshLinks)> {
+    validate_parameters(threshold, shares.len())?;
     let mut rng = OsRng;
-    let update_coeffs: Vec<Scalar> = (0..(threshold - 1))
-        .map(|_| utils::random_scalar(&mut rng))
+    let mut delta = vec![Scalar::ZERO];
+    let mut epsilon = vec![Scalar::ZERO];
+    for _ in 1..threshold {
+        delta.push(utils::random_scalar(&mut rng)?);
+        epsilon.push(utils::random_scalar(&mut rng)?);
+    }
+    let update: Vec<RistrettoPoint> = delta
+        .iter()
+        .zip(&epsilon)
+        .skip(1)
+        .map(|(d, e)| utils::pedersen_commit(d, e))
         .collect();
 
-    shares
+    let (new_shares, links): (Vec<ShareData>, Vec<crate::vss::ShareLink>) = shares
         .par_iter()
         .map(|share_data| {
             let i = share_data.index;
             let x = Scalar::from(i as u64);
-            let mut update_val = Scalar::ZERO;
-            for (j, coeff) in update_coeffs.iter().enumerate() {
-                update_val += coeff * utils::pow_scalar(x, (j + 1) as u32);
-            }
-            let new_share = share_data.share + update_val;
+            let new_share = share_data.share + lagrange_fft::poly_evaluate(&delta, x);
             let mut local_rng = OsRng;
-            let new_random = utils::random_scalar(&mut local_rng);
-            let new_commitment =
-                RISTRETTO_BASEPOINT_POINT * new_share + (*ANOTHER_POINT) * new_random;
-            let new_proof = proof::generate_proof(new_share, new_random, i, new_commitment);
-            ShareData {
+            let new_random = utils::random_scalar(&mut local_rng)?;
+            let new_commitment = utils::pedersen_commit(&new_share, &new_random);
+            let new_proof = proof::generate_proof(new_share, new_random, i, new_commitment)?;
+            let refreshed = ShareData {
                 index: i,
                 share: new_share,
                 commitment: new_commitment,
                 random: new_random,
                 proof: new_proof,
-            }
+            };
+            let witness = new_random - share_data.random - lagrange_fft::poly_evaluate(&epsilon, x);
+            let link = crate::vss::RefreshLinks::link(&update, share_data, &refreshed, &witness)?;
+            Ok((refreshed, link))
         })
-        .collect()
+        .collect::<CryptoResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Ok((new_shares, crate::vss::RefreshLinks { update, links }))
+}
+
+/// Refresh the slices of a VSS dealing and its coefficient commitments.
+///
+/// Adds a random polynomial `δ(x)` with `δ(0) = 0` to the slices and a random `ε(x)` with
+/// `ε(0) = 0` to the blinding randoms, and adds their commitments to `C_1..C_{t-1}`. `C_0` is
+/// unchanged, so [`crate::vss::diff`] of the old and new commitments shows a permitted refresh.
+/// Use [`refresh_shares`] to also publish the update commitments.
+///
+/// # Parameters
+///
+/// - `shares`: The set of original slices.
+/// - `commitments`: The dealing's current coefficient commitments; their length is the threshold.
+///
+/// # Return value
+///
+/// Returns the refreshed slices and commitments, `CryptoError::Validation` unless
+/// `1 <= threshold <= shares.len()`, or `CryptoError::InsufficientEntropy` if the random
+/// generator fails. With threshold 1 both update polynomials are zero and the slices come back
+/// unchanged: every slice is the secret and opens `C_0`. Use [`update_shares`] to redraw their
+/// blinding randoms.
+#[cfg(feature = "dealer")]
+pub fn update_shares_with_commitments(
+    shares: &[ShareData],
+    commitments: &VssCommitments,
+) -> CryptoResult<(Vec<ShareData>, VssCommitments)> {
+    refresh_shares(shares, commitments).map(|(shares, record)| (shares, record.commitments))
+}
+
+/// Proactive refresh with a commitment continuity record.
+///
+/// Refreshes the slices as [`update_shares_with_commitments`] and returns a
+/// [`RefreshRecord`](crate::vss::RefreshRecord) carrying the commitments `D_j = δ_j·G + ε_j·H` to
+/// the update polynomials and the new commitments `C_j + D_j`. Auditors check it with
+/// [`RefreshRecord::verify`](crate::vss::RefreshRecord::verify) against the old commitments to
+/// confirm the refresh preserved the secret, without seeing any slice.
+///
+/// # Return value
+///
+/// Returns the refreshed slices and the record to publish, with the same errors as
+/// [`update_shares_with_commitments`].
+#[cfg(feature = "dealer")]
+pub fn refresh_shares(
+    shares: &[ShareData],
+    commitments: &VssCommitments,
+) -> CryptoResult<(Vec<ShareData>, crate::vss::RefreshRecord)> {
+    validate_parameters(commitments.threshold(), shares.len())?;
+    let mut rng = OsRng;
+    let threshold = commitments.threshold();
+    let mut delta = vec![Scalar::ZERO];
+    let mut epsilon = vec![Scalar::ZERO];
+    for _ in 1..threshold {
+        delta.push(utils::random_scalar(&mut rng)?);
+        epsilon.push(utils::random_scalar(&mut rng)?);
+    }
+
+    let update: Vec<RistrettoPoint> = delta
+        .iter()
+        .zip(&epsilon)
+        .skip(1)
+        .map(|(d, e)| utils::pedersen_commit(d, e))
+        .collect();
+    let new_commitments = VssCommitments {
+        coefficients: commitments
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, c)| match j {
+                0 => *c,
+                _ => c + update[j - 1],
+            })
+            .collect(),
+    };
+
+    let new_shares = shares
+        .par_iter()
+        .map(|share_data| {
+            let i = share_data.index;
+            let x = Scalar::from(i as u64);
+            let new_share = share_data.share + lagrange_fft::poly_evaluate(&delta, x);
+            let new_random = share_data.random + lagrange_fft::poly_evaluate(&epsilon, x);
+            let new_commitment = utils::pedersen_commit(&new_share, &new_random);
+            let new_proof = proof::generate_proof(new_share, new_random, i, new_commitment)?;
+            Ok(ShareData {
+                index: i,
+                share: new_share,
+                commitment: new_commitment,
+                random: new_random,
+                proof: new_proof,
+            })
+        })
+        .collect::<CryptoResult<_>>()?;
+
+    Ok((
+        new_shares,
+        crate::vss::RefreshRecord {
+            old_root: commitments.root().to_vec(),
+            update,
+            commitments: new_commitments,
+        },
+    ))
 }
 
 /// Adjustment thresholds (distributed re-slicing).
@@ -137,22 +999,44 @@ pub fn update_shares(shares: &[ShareData], threshold: usize) -> Vec<ShareData> {
 ///
 /// # Return values
 ///
-/// Returns a collection of new slices or an error message.
+/// Returns a collection of new slices or an error message, including for a new threshold outside
+/// `1..=n`. With a new threshold of 1 every new slice is the recombined secret, each under a fresh
+/// blinding random.
+#[cfg(feature = "dealer")]
 pub fn adjust_threshold(
     existing_shares: &[ShareData],
     original_threshold: usize,
     new_threshold: usize,
     n: usize,
 ) -> Result<Vec<ShareData>, String> {
+    let indices: Vec<usize> = (1..=n).collect();
+    reshare_to_indices(existing_shares, original_threshold, new_threshold, &indices)
+}
+
+/// [`adjust_threshold`] with the new slices issued at `indices` rather than `1..=n`.
+#[cfg(feature = "dealer")]
+pub(crate) fn reshare_to_indices(
+    existing_shares: &[ShareData],
+    original_threshold: usize,
+    new_threshold: usize,
+    indices: &[usize],
+) -> Result<Vec<ShareData>, String> {
+    let n = indices.len();
     if existing_shares.len() < original_threshold {
         return Err(format!(
             "At least {} slices are needed for threshold adjustment",
             original_threshold
         ));
     }
+    if new_threshold == 0 || new_threshold > n {
+        return Err(format!(
+            "New threshold {} must be between 1 and the {} new slices",
+            new_threshold, n
+        ));
+    }
     // Validate slice index: must be non-zero and unique
     let m = existing_shares.len();
-    let mut indices = Vec::with_capacity(m);
+    let mut xs = Vec::with_capacity(m);
     let mut index_set = std::collections::HashSet::new();
     for share in existing_shares {
         if share.index == 0 {
@@ -164,13 +1048,41 @@ pub fn adjust_threshold(
         if !index_set.insert(share.index) {
             return Err(format!("Split Index {} Repeat", share.index));
         }
-        indices.push(Scalar::from(share.index as u64));
+        xs.push(Scalar::from(share.index as u64));
     }
     // Calculate the Lagrange coefficient corresponding to each slice λ
-    let lambda = lagrange_fft::compute_lagrange_coefficients(&indices)
+    let lambda = lagrange_fft::compute_lagrange_coefficients(&xs)
         .map_err(|e| format!("计算 Lagrange 系数失败: {}", e))?;
 
     let mut rng = OsRng;
+    if new_threshold == 1 {
+        // 1-of-n: copy the recombined secret; the blinding polynomials would all be zero.
+        let secret: Scalar = existing_shares
+            .iter()
+            .zip(&lambda)
+            .map(|(share, l)| share.share * l)
+            .sum();
+        let randoms = (0..n)
+            .map(|_| utils::random_scalar(&mut rng))
+            .collect::<CryptoResult<Vec<_>>>()
+            .map_err(|e| e.to_string())?;
+        return indices
+            .iter()
+            .zip(randoms)
+            .map(|(&j, random)| {
+                let commitment = utils::pedersen_commit(&secret, &random);
+                let proof = proof::generate_proof(secret, random, j, commitment)
+                    .map_err(|e| e.to_string())?;
+                Ok(ShareData {
+                    index: j,
+                    share: secret,
+                    commitment,
+                    random,
+                    proof,
+                })
+            })
+            .collect();
+    }
     let mut new_shares_vals = vec![Scalar::ZERO; n];
     let mut new_randoms = vec![Scalar::ZERO; n];
     // Each original slice contributes a random polynomial f_i(x)= share * λ_i + ∑_{k=1}^{new_threshold-1} a_{i,k} * x^k
@@ -178,10 +1090,10 @@ pub fn adjust_threshold(
         let const_term = share.share * lambda[i];
         let mut coeffs = vec![const_term];
         for _ in 1..new_threshold {
-            coeffs.push(utils::random_scalar(&mut rng));
+            coeffs.push(utils::random_scalar(&mut rng).map_err(|e| e.to_string())?);
         }
         // For each new slice j compute f_i(j)
-        for j in 1..=n {
+        for (k, &j) in indices.iter().enumerate() {
             let x = Scalar::from(j as u64);
             let mut x_pow = Scalar::ONE;
             let mut value = Scalar::ZERO;
@@ -189,33 +1101,442 @@ pub fn adjust_threshold(
                 value += coeff * x_pow;
                 x_pow *= x;
             }
-            new_shares_vals[j - 1] += value;
+            new_shares_vals[k] += value;
             // Blinded random numbers (constant term is 0)
             let mut rand_val = Scalar::ZERO;
             let mut x_pow = x;
             for _ in 1..new_threshold {
-                let a = utils::random_scalar(&mut rng);
+                let a = utils::random_scalar(&mut rng).map_err(|e| e.to_string())?;
                 rand_val += a * x_pow;
                 x_pow *= x;
             }
-            new_randoms[j - 1] += rand_val;
+            new_randoms[k] += rand_val;
         }
     }
     // Generate promises and proofs for each new slice
-    let new_shares: Vec<ShareData> = (1..=n)
-        .map(|j| {
-            let share_val = new_shares_vals[j - 1];
-            let rand_val = new_randoms[j - 1];
-            let commitment = RISTRETTO_BASEPOINT_POINT * share_val + (*ANOTHER_POINT) * rand_val;
-            let proof = proof::generate_proof(share_val, rand_val, j, commitment);
-            ShareData {
+    let new_shares: Vec<ShareData> = indices
+        .iter()
+        .enumerate()
+        .map(|(k, &j)| {
+            let share_val = new_shares_vals[k];
+            let rand_val = new_randoms[k];
+            let commitment = utils::pedersen_commit(&share_val, &rand_val);
+            let proof = proof::generate_proof(share_val, rand_val, j, commitment)
+                .map_err(|e| e.to_string())?;
+            Ok(ShareData {
                 index: j,
                 share: share_val,
                 commitment,
                 random: rand_val,
                 proof,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, String>>()?;
     Ok(new_shares)
 }
+
+/// Issue a slice at `new_index` of an existing threshold-`threshold` dealing, computed jointly by
+/// `threshold` holders, so that a new participant is enrolled without changing anyone's slice.
+///
+/// The first `threshold` slices of `existing_shares` are the helpers. Helper `i` weights its slice
+/// and blinding random with its Lagrange coefficient at the new index,
+/// `λ_i = Π_{j≠i} (x_new - x_j) / (x_i - x_j)`, and splits both products into `threshold` random
+/// additive parts, one for every helper. Each helper adds up the parts it receives and hands only
+/// that sum to the new participant, who adds the sums into the new slice value and random. No
+/// helper learns another helper's weighted slice, and the new participant only sees sums masked
+/// by fresh randomness. This function runs every step of the [`recovery`](crate::recovery)
+/// rounds in one process; a deployment runs them at the helpers and exchanges the messages over
+/// authenticated channels.
+///
+/// The new slice lies on the dealing's polynomials, so its commitment `Σ λ_i·C_i` is the one the
+/// dealing's [`VssCommitments`] predict for `new_index`, and it carries a fresh proof.
+///
+/// # Return value
+///
+/// Returns the new slice; `CryptoError::Validation` for a zero threshold, fewer than `threshold`
+/// slices, a zero or repeated index, or a `new_index` that is zero or held by one of
+/// `existing_shares`; the failure of the first helper slice with an invalid proof or a value that
+/// does not open its commitment, as by `From<VerifyError> for CryptoError`; or `CryptoError::InsufficientEntropy` if the random
+/// generator fails.
+#[cfg(feature = "dealer")]
+pub fn issue_additional_share(
+    existing_shares: &[ShareData],
+    threshold: usize,
+    new_index: usize,
+) -> CryptoResult<ShareData> {
+    use crate::recovery::{self, RepairPart, RepairRequest};
+    use curve25519_dalek::traits::VartimeMultiscalarMul;
+    let invalid = |field: &str, reason: String| CryptoError::Validation {
+        field: field.to_string(),
+        reason,
+    };
+    if threshold == 0 || existing_shares.len() < threshold {
+        return Err(invalid(
+            "existing_shares",
+            format!(
+                "{} slices for threshold {}",
+                existing_shares.len(),
+                threshold
+            ),
+        ));
+    }
+    if new_index == 0 || existing_shares.iter().any(|s| s.index == new_index) {
+        return Err(invalid(
+            "new_index",
+            format!("index {} is zero or already issued", new_index),
+        ));
+    }
+    let helpers = &existing_shares[..threshold];
+    crate::vss::verify_share_validity_batch(helpers)?;
+    // The helpers' values go into the new slice, so each must open its commitment.
+    if let Some(helper) = helpers
+        .iter()
+        .find(|s| utils::pedersen_commit(&s.share, &s.random) != s.commitment)
+    {
+        return Err(crate::error::VerifyError::OpeningMismatch {
+            index: helper.index,
+        }
+        .into());
+    }
+
+    // The share recovery rounds, run in one process for the new index.
+    let request = RepairRequest::new(new_index, helpers.iter().map(|s| s.index).collect())?;
+    let lambda = request.weights()?;
+    let mut parts = Vec::with_capacity(threshold * threshold);
+    for helper in helpers {
+        parts.extend(recovery::repair_parts(helper, &request)?);
+    }
+    let sums = helpers
+        .iter()
+        .map(|helper| {
+            let own: Vec<RepairPart> = parts
+                .iter()
+                .filter(|p| p.recipient == helper.index)
+                .cloned()
+                .collect();
+            recovery::repair_sum(&request, helper.index, &own)
+        })
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let (share, random) = recovery::combine_sums(&request, &sums)?;
+
+    let commitment =
+        RistrettoPoint::vartime_multiscalar_mul(&lambda, helpers.iter().map(|s| s.commitment));
+    let proof = proof::generate_proof(share, random, new_index, commitment)?;
+    Ok(ShareData {
+        index: new_index,
+        share,
+        commitment,
+        random,
+        proof,
+    })
+}
+
+/// Share `secret` under a monotone access `policy`, e.g. "2 of ops AND 1 of security".
+///
+/// The policy is compiled with [`Policy::compile`](crate::access::Policy::compile) and every row
+/// of the scheme becomes one slice, at index row + 1, with an independent blinding random, its
+/// commitment and proof. Any set of slices whose parties satisfy the policy recovers the secret
+/// with [`recover_secret_with_policy`]; other sets learn nothing about it.
+///
+/// # Return value
+///
+/// Returns one slice per leaf of the policy, in the order of
+/// [`Policy::parties`](crate::access::Policy::parties); fails like
+/// [`Policy::validate`](crate::access::Policy::validate), or with
+/// `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_policy_shares(
+    secret: Scalar,
+    policy: &crate::access::Policy,
+) -> CryptoResult<Vec<crate::access::PolicyShare>> {
+    use zeroize::Zeroize;
+    let scheme = policy.compile()?;
+    let mut rng = OsRng;
+    let mut vector = vec![secret];
+    for _ in 1..scheme.width() {
+        vector.push(utils::random_scalar(&mut rng)?);
+    }
+    let values = scheme.share_values(&vector);
+    vector.zeroize();
+    let unproven = values
+        .into_iter()
+        .enumerate()
+        .map(|(row, share)| {
+            let random = utils::random_scalar(&mut rng)?;
+            Ok(UnprovenShare {
+                index: row + 1,
+                share,
+                random,
+                commitment: utils::pedersen_commit(&share, &random),
+            })
+        })
+        .collect::<CryptoResult<Vec<_>>>()?;
+    Ok(prove_shares(unproven)?
+        .into_iter()
+        .map(|share| crate::access::PolicyShare {
+            party: scheme.party(share.index).unwrap_or_default().to_string(),
+            share,
+        })
+        .collect())
+}
+
+/// Recover the secret shared with [`generate_policy_shares`] under `policy`.
+///
+/// # Return value
+///
+/// Returns the secret; fails like [`Policy::validate`](crate::access::Policy::validate);
+/// returns `CryptoError::Validation` if a slice is outside the policy, labelled with another
+/// party or repeated, or if the parties of `shares` do not satisfy the policy; and the failure of
+/// the first slice proof that does not verify, as by `From<VerifyError> for CryptoError`.
+#[cfg(feature = "dealer")]
+pub fn recover_secret_with_policy(
+    policy: &crate::access::Policy,
+    shares: &[crate::access::PolicyShare],
+) -> CryptoResult<Scalar> {
+    let scheme = policy.compile()?;
+    let mut indices = Vec::with_capacity(shares.len());
+    for labelled in shares {
+        let index = labelled.share.index;
+        if scheme.party(index) != Some(labelled.party.as_str()) || indices.contains(&index) {
+            return Err(CryptoError::Validation {
+                field: "shares".to_string(),
+                reason: format!(
+                    "slice {} of {} is outside the policy or repeated",
+                    index, labelled.party
+                ),
+            });
+        }
+        indices.push(index);
+    }
+    let slices: Vec<ShareData> = shares.iter().map(|s| s.share.clone()).collect();
+    crate::vss::verify_share_validity_batch(&slices)?;
+    let weights = scheme
+        .reconstruction(&indices)
+        .ok_or_else(|| CryptoError::Validation {
+            field: "shares".to_string(),
+            reason: "the parties of the slices do not satisfy the policy".to_string(),
+        })?;
+    Ok(weights
+        .iter()
+        .map(|(index, w)| {
+            let position = indices.iter().position(|i| i == index).unwrap_or_default();
+            slices[position].share * w
+        })
+        .sum())
+}
+
+/// Check cumulative hierarchical thresholds: non-empty, from 1 and strictly increasing.
+#[cfg(feature = "dealer")]
+fn validate_hierarchy(thresholds: &[usize]) -> CryptoResult<()> {
+    if thresholds.first().is_none_or(|&k| k == 0) || thresholds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(CryptoError::Validation {
+            field: "thresholds".to_string(),
+            reason: format!(
+                "cumulative thresholds {:?} must start from 1 and increase",
+                thresholds
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Tassa hierarchical threshold sharing: with cumulative thresholds `k_1 < k_2 < ... < k_m`,
+/// recovery needs at least `k_l` slices from levels `1..=l` for every `l`, e.g. `[2, 5]` needs
+/// two executives (level 1) among any five holders.
+///
+/// The secret is `a_0` of a random polynomial `f` of degree `k_m - 1`. Level 1 receives values
+/// `f(i)` and level `l > 1` receives the derivative `f^{(k_{l-1})}(i)`, which does not depend on
+/// `a_0..a_{k_{l-1}-1}`: without enough senior slices those coefficients, and so the secret,
+/// stay undetermined. Slices are numbered across levels in order, `1..=level_sizes[0]` for level
+/// 1 and so on, each with an independent blinding random, its commitment and proof. Recover with
+/// [`recover_secret_hierarchical`].
+///
+/// # Return value
+///
+/// Returns the slices, level by level; `CryptoError::Validation` unless the thresholds start from
+/// 1 and increase, there is one level size per threshold and levels `1..=l` hold at least `k_l`
+/// slices; `CryptoError::ResourceExhaustion` for more than
+/// [`MAX_ENTERPRISE_SHARES`](crate::limits::MAX_ENTERPRISE_SHARES) slices; or
+/// `CryptoError::InsufficientEntropy` if the random generator fails.
+#[cfg(feature = "dealer")]
+pub fn generate_hierarchical_shares(
+    secret: Scalar,
+    thresholds: &[usize],
+    level_sizes: &[usize],
+) -> CryptoResult<Vec<HierarchicalShare>> {
+    use zeroize::Zeroize;
+    validate_hierarchy(thresholds)?;
+    let reachable = level_sizes.iter().scan(0, |total, size| {
+        *total += size;
+        Some(*total)
+    });
+    if level_sizes.len() != thresholds.len() || reachable.zip(thresholds).any(|(n, k)| n < *k) {
+        return Err(CryptoError::Validation {
+            field: "level_sizes".to_string(),
+            reason: format!(
+                "levels of {:?} slices cannot meet thresholds {:?}",
+                level_sizes, thresholds
+            ),
+        });
+    }
+    if level_sizes.iter().sum::<usize>() > crate::limits::MAX_ENTERPRISE_SHARES {
+        return Err(CryptoError::ResourceExhaustion {
+            resource: "share_count".to_string(),
+        });
+    }
+
+    let mut rng = OsRng;
+    let degree = thresholds[thresholds.len() - 1];
+    let mut coefficients = vec![secret];
+    for _ in 1..degree {
+        coefficients.push(utils::random_scalar(&mut rng)?);
+    }
+    let mut unproven = Vec::with_capacity(level_sizes.iter().sum());
+    let mut levels = Vec::with_capacity(unproven.capacity());
+    let mut index = 0;
+    for (level, &size) in level_sizes.iter().enumerate() {
+        let order = if level == 0 { 0 } else { thresholds[level - 1] };
+        let derivative: Vec<Scalar> = (order..degree)
+            .map(|j| lagrange_fft::falling_factorial(j, order) * coefficients[j])
+            .collect();
+        for _ in 0..size {
+            index += 1;
+            let share = lagrange_fft::poly_evaluate(&derivative, Scalar::from(index as u64));
+            let random = utils::random_scalar(&mut rng)?;
+            unproven.push(UnprovenShare {
+                index,
+                share,
+                random,
+                commitment: utils::pedersen_commit(&share, &random),
+            });
+            levels.push(level + 1);
+        }
+    }
+    coefficients.zeroize();
+    Ok(prove_shares(unproven)?
+        .into_iter()
+        .zip(levels)
+        .map(|(share, level)| HierarchicalShare { level, share })
+        .collect())
+}
+
+/// Recover the secret of a [`generate_hierarchical_shares`] dealing under `thresholds`.
+///
+/// Of the slices, the `k_m` most senior (by level, then in the order given) are interpolated with
+/// [`birkhoff_interpolate`](crate::lagrange_fft::birkhoff_interpolate) after every slice proof is
+/// checked.
+///
+/// # Return value
+///
+/// Returns the secret; `CryptoError::Validation` for invalid thresholds, a level outside
+/// `1..=thresholds.len()`, a repeated index, or fewer than `k_l` slices from levels `1..=l` for
+/// some `l`; the failure of the first slice proof that does not verify, as by
+/// `From<VerifyError> for CryptoError`; or `CryptoError::CryptographicOperation` if the slices
+/// do not determine the polynomial.
+#[cfg(feature = "dealer")]
+pub fn recover_secret_hierarchical(
+    thresholds: &[usize],
+    shares: &[HierarchicalShare],
+) -> CryptoResult<Scalar> {
+    validate_hierarchy(thresholds)?;
+    let invalid = |reason: String| CryptoError::Validation {
+        field: "shares".to_string(),
+        reason,
+    };
+    let mut indices = std::collections::HashSet::new();
+    for s in shares {
+        if s.level == 0 || s.level > thresholds.len() || !indices.insert(s.share.index) {
+            return Err(invalid(format!(
+                "slice {} at level {} is outside the hierarchy or repeated",
+                s.share.index, s.level
+            )));
+        }
+    }
+    if let Some((level, k)) = thresholds
+        .iter()
+        .enumerate()
+        .find(|(level, k)| shares.iter().filter(|s| s.level <= level + 1).count() < **k)
+    {
+        return Err(invalid(format!(
+            "fewer than {} slices from levels 1..={}",
+            k,
+            level + 1
+        )));
+    }
+
+    let mut senior: Vec<&HierarchicalShare> = shares.iter().collect();
+    senior.sort_by_key(|s| s.level);
+    senior.truncate(thresholds[thresholds.len() - 1]);
+    let slices: Vec<ShareData> = senior.iter().map(|s| s.share.clone()).collect();
+    crate::vss::verify_share_validity_batch(&slices)?;
+    let points: Vec<(Scalar, usize, Scalar)> = senior
+        .iter()
+        .map(|s| {
+            let order = if s.level == 1 {
+                0
+            } else {
+                thresholds[s.level - 2]
+            };
+            (Scalar::from(s.share.index as u64), order, s.share.share)
+        })
+        .collect();
+    lagrange_fft::birkhoff_interpolate(&points)
+        .map(|coefficients| coefficients[0])
+        .map_err(|e| CryptoError::CryptographicOperation {
+            operation: format!("hierarchical_recovery: {}", e),
+        })
+}
+
+/// Recover the secret from `shares` after checking every slice proof.
+///
+/// The safe counterpart of [`hazmat::recover_secret_fft`](crate::hazmat::recover_secret_fft):
+/// the slices are checked with [`verify_share_validity_batch`](crate::vss::verify_share_validity_batch)
+/// and interpolated in constant time with
+/// [`recover_secret_ct`](crate::lagrange_fft::recover_secret_ct). Revocation, epochs and key
+/// policies are checked by
+/// [`EnterpriseCryptoSystem::recover_secret_enterprise`](crate::EnterpriseCryptoSystem::recover_secret_enterprise).
+///
+/// # Return value
+///
+/// Returns the secret, the failure of the first invalid slice as by
+/// `From<VerifyError> for CryptoError`, or `CryptoError::CryptographicOperation` if the slices
+/// cannot be interpolated (no slice, a zero or repeated index).
+#[cfg(feature = "dealer")]
+pub fn recover_secret(shares: &[ShareData]) -> CryptoResult<Scalar> {
+    crate::vss::verify_share_validity_batch(shares)?;
+    lagrange_fft::recover_secret_ct(shares).map_err(|e| CryptoError::CryptographicOperation {
+        operation: format!("secret_recovery: {}", e),
+    })
+}
+
+/// [`recover_secret`] in the group `G`, of slices dealt with [`generate_group_shares`].
+///
+/// Every slice proof is checked with [`proof::verify_group_proof`] before the slices are
+/// interpolated at zero.
+///
+/// # Return value
+///
+/// Returns the secret, the failure of the first invalid slice as by
+/// `From<VerifyError> for CryptoError`, or `CryptoError::Validation` for no slice or a zero or
+/// repeated index.
+#[cfg(feature = "dealer")]
+pub fn recover_group_secret<G: CurveGroup>(shares: &[ShareData<G>]) -> CryptoResult<G::Scalar> {
+    let indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+    let mut seen = std::collections::HashSet::new();
+    if indices.is_empty() || indices.iter().any(|&i| i == 0 || !seen.insert(i)) {
+        return Err(CryptoError::Validation {
+            field: "shares".to_string(),
+            reason: "at least one slice with distinct non-zero indices is required".to_string(),
+        });
+    }
+    for share in shares {
+        proof::verify_group_proof(&share.proof, share.commitment, share.index)?;
+    }
+    let weights = crate::group::lagrange_at_zero::<G>(&indices);
+    Ok(shares
+        .iter()
+        .zip(weights)
+        .fold(G::scalar_from_u64(0), |sum, (share, w)| {
+            sum + share.share * w
+        }))
+}
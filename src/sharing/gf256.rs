@@ -0,0 +1,257 @@
+//! **byte-wise GF(256) sharing**
+//!
+//! Shamir sharing byte by byte, as HashiCorp Vault's `shamir` package and the inner layer of
+//! SLIP-39 describe it: every byte of the secret is the constant term of its own random polynomial
+//! over the AES field GF(2^8) (modulo `x^8 + x^4 + x^3 + x + 1`), and a share holds one evaluation
+//! byte per secret byte at a common non-zero `x`. Shares are plain bytes with no commitments or
+//! proofs, so a wrong or tampered share yields a wrong secret rather than an error.
+//!
+//! [`Gf256Share::to_vault_bytes`] / [`Gf256Share::from_vault_bytes`] write the layout Vault
+//! documents, the `y` bytes followed by `x`, and [`interpolate`] evaluates at any `x`, such as
+//! SLIP-39's secret point 255. The field arithmetic is checked against FIPS-197, but neither
+//! layout is tested against shares produced by Vault or a SLIP-39 implementation; try a sample
+//! set before relying on an exchange with those tools. Field multiplication uses no tables or
+//! secret-dependent branches.
+
+use crate::error::{CryptoError, CryptoResult};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const POLY: u8 = 0x1b;
+
+/// `a·b` in GF(2^8).
+pub(crate) fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        a = (a << 1) ^ (POLY & 0u8.wrapping_sub(a >> 7));
+        b >>= 1;
+    }
+    product
+}
+
+/// `a^e` in GF(2^8); the exponent is public.
+pub(crate) fn pow(a: u8, e: usize) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    let mut e = e % 255;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        e >>= 1;
+    }
+    result
+}
+
+/// `a^{-1}` as `a^254`; zero has no inverse and maps to zero.
+pub(crate) fn inv(a: u8) -> u8 {
+    pow(a, 254)
+}
+
+/// One share of a byte string: the evaluation point `x` and one byte per secret byte.
+///
+/// `Debug` output redacts `y`.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Gf256Share {
+    pub x: u8,
+    pub y: Vec<u8>,
+}
+
+impl fmt::Debug for Gf256Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gf256Share")
+            .field("x", &self.x)
+            .field("y", &format_args!("<redacted {} bytes>", self.y.len()))
+            .finish()
+    }
+}
+
+impl Gf256Share {
+    /// Vault's encoding: the `y` bytes followed by `x`.
+    pub fn to_vault_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(self.y.len() + 1));
+        bytes.extend_from_slice(&self.y);
+        bytes.push(self.x);
+        bytes
+    }
+
+    /// Decode [`Self::to_vault_bytes`]; Vault never emits `x = 0`, which would be the secret.
+    ///
+    /// Returns `CryptoError::Serialization` for fewer than 2 bytes or `x = 0`.
+    pub fn from_vault_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        match bytes.split_last() {
+            Some((&x, y)) if !y.is_empty() && x != 0 => Ok(Self { x, y: y.to_vec() }),
+            Some((0, y)) if !y.is_empty() => Err(CryptoError::Serialization {
+                details: "GF(256) share at x = 0".to_string(),
+            }),
+            _ => Err(CryptoError::Serialization {
+                details: format!(
+                    "GF(256) share must be at least 2 bytes, got {}",
+                    bytes.len()
+                ),
+            }),
+        }
+    }
+}
+
+/// Split `secret` into `num_shares` shares at `x = 1..=num_shares`, any `threshold` of which
+/// recover it with [`combine`].
+///
+/// Returns `CryptoError::Validation` for an empty secret or `threshold` outside
+/// `1..=num_shares`, `CryptoError::ResourceExhaustion` for more than 255 shares and
+/// `CryptoError::InsufficientEntropy` if the coefficients cannot be drawn.
+#[cfg(feature = "dealer")]
+pub fn split(secret: &[u8], threshold: usize, num_shares: usize) -> CryptoResult<Vec<Gf256Share>> {
+    if secret.is_empty() {
+        return Err(CryptoError::Validation {
+            field: "secret".to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+    if num_shares > 255 {
+        return Err(CryptoError::ResourceExhaustion {
+            resource: "share_count".to_string(),
+        });
+    }
+    if threshold == 0 || threshold > num_shares {
+        return Err(CryptoError::Validation {
+            field: "threshold".to_string(),
+            reason: format!("must be between 1 and {}, got {}", num_shares, threshold),
+        });
+    }
+    // Coefficients of degree 1 and up, `threshold - 1` bytes per secret byte.
+    let degree = threshold - 1;
+    let mut coefficients = Zeroizing::new(vec![0u8; secret.len() * degree]);
+    crate::utils::fill_random(&mut coefficients)?;
+    Ok((1..=num_shares as u8)
+        .map(|x| Gf256Share {
+            x,
+            y: secret
+                .iter()
+                .enumerate()
+                .map(|(i, &constant)| {
+                    let higher = &coefficients[i * degree..(i + 1) * degree];
+                    mul(higher.iter().rev().fold(0, |y, &c| mul(y, x) ^ c), x) ^ constant
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Evaluate at `x` the polynomials through `shares`, byte by byte: the secret for `x = 0`
+/// or the share another holder would have at `x`.
+///
+/// Every share counts; passing fewer than the threshold gives an unrelated value. Returns
+/// `CryptoError::Validation` for no shares, shares of different lengths or a repeated `x`.
+pub fn interpolate(shares: &[Gf256Share], x: u8) -> CryptoResult<Zeroizing<Vec<u8>>> {
+    let invalid = |reason: String| CryptoError::Validation {
+        field: "shares".to_string(),
+        reason,
+    };
+    let first = shares
+        .first()
+        .ok_or_else(|| invalid("no shares".to_string()))?;
+    if shares.iter().any(|s| s.y.len() != first.y.len()) {
+        return Err(invalid("shares have different lengths".to_string()));
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|s| s.x == share.x) {
+            return Err(invalid(format!("repeated x = {}", share.x)));
+        }
+    }
+    let weights: Vec<u8> = shares
+        .iter()
+        .map(|si| {
+            let (numerator, denominator) = shares
+                .iter()
+                .filter(|sj| sj.x != si.x)
+                .fold((1u8, 1u8), |(n, d), sj| {
+                    (mul(n, x ^ sj.x), mul(d, si.x ^ sj.x))
+                });
+            mul(numerator, inv(denominator))
+        })
+        .collect();
+    let mut secret = Zeroizing::new(vec![0u8; first.y.len()]);
+    for (share, &weight) in shares.iter().zip(&weights) {
+        for (byte, &y) in secret.iter_mut().zip(&share.y) {
+            *byte ^= mul(weight, y);
+        }
+    }
+    Ok(secret)
+}
+
+/// Recover the secret, at `x = 0`, from at least a threshold of shares.
+pub fn combine(shares: &[Gf256Share]) -> CryptoResult<Zeroizing<Vec<u8>>> {
+    interpolate(shares, 0)
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf256_sharing_roundtrip() {
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert!((1..=255u8).all(|a| mul(a, inv(a)) == 1));
+
+        let secret = b"vault unseal key";
+        let shares = split(secret, 3, 5).unwrap();
+        assert_eq!(&combine(&shares[1..4]).unwrap()[..], secret);
+        assert_eq!(&combine(&shares).unwrap()[..], secret);
+        assert_ne!(&combine(&shares[..2]).unwrap()[..], secret);
+        // Any 3 shares give back the others.
+        assert_eq!(interpolate(&shares[..3], 5).unwrap()[..], shares[4].y[..]);
+
+        let vault: Vec<_> = shares.iter().map(|s| s.to_vault_bytes()).collect();
+        assert_eq!(vault[0].len(), secret.len() + 1);
+        let imported: Vec<_> = vault[2..]
+            .iter()
+            .map(|b| Gf256Share::from_vault_bytes(b).unwrap())
+            .collect();
+        assert_eq!(&combine(&imported).unwrap()[..], secret);
+        assert!(Gf256Share::from_vault_bytes(&[7]).is_err());
+        assert!(Gf256Share::from_vault_bytes(&[7, 0]).is_err());
+
+        let mut repeated = shares[..3].to_vec();
+        repeated[2] = repeated[0].clone();
+        assert!(combine(&repeated).is_err());
+        let mut short = shares[..3].to_vec();
+        short[1].y.pop();
+        assert!(combine(&short).is_err());
+        assert!(matches!(
+            split(secret, 2, 256),
+            Err(CryptoError::ResourceExhaustion { .. })
+        ));
+        assert!(split(secret, 0, 3).is_err());
+        assert!(split(b"", 2, 3).is_err());
+    }
+
+    /// Products from FIPS-197 section 4.2, and inverses through entries of the AES S-box, which
+    /// is the affine map of the inverse.
+    #[test]
+    fn test_gf256_field_matches_fips197() {
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        let doubled = [0xae, 0x47, 0x8e, 0x07];
+        for (i, &expected) in doubled.iter().enumerate() {
+            assert_eq!(mul(0x57, 2 << i), expected);
+        }
+        let sbox = |a: u8| {
+            let b = inv(a);
+            b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+        };
+        for (a, expected) in [(0x00, 0x63), (0x01, 0x7c), (0x53, 0xed), (0xff, 0x16)] {
+            assert_eq!(sbox(a), expected);
+        }
+    }
+
+    /// The 2-of-n polynomial `0x42 + 0x57·x`, evaluated by hand with the products above.
+    #[test]
+    fn test_gf256_interpolates_known_points() {
+        let share = |x: u8, y: u8| Gf256Share { x, y: vec![y] };
+        let shares = [share(1, 0x15), share(2, 0xec)];
+        assert_eq!(combine(&shares).unwrap()[..], [0x42]);
+        assert_eq!(interpolate(&shares, 4).unwrap()[..], [0x42 ^ 0x47]);
+    }
+}
@@ -0,0 +1,118 @@
+//! **typed sharing**
+//!
+//! Const-generic wrappers that encode the threshold `T` and committee size `N` in the type.
+//! A `TypedShare<3, 5>` cannot be combined with a `TypedShare<2, 5>`, recovery takes exactly
+//! `T` shares as an array, and invalid parameters (`T == 0` or `T > N`) fail to compile as soon
+//! as the sharing is dealt.
+//!
+//! The types only prevent mixing *parameters*; two dealings of the same shape still have the same
+//! type, so cross-dealing confusion is caught by the proofs and commitments, not the compiler.
+
+use crate::error::CryptoResult;
+use crate::lagrange_fft::{self, LagrangeResult};
+use crate::sharing::{self, ShareData};
+use curve25519_dalek::scalar::Scalar;
+
+/// A slice belonging to a `T`-of-`N` sharing.
+///
+/// Serializes as its `ShareData`; deserialization rejects an index outside `1..=N`.
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct TypedShare<const T: usize, const N: usize>(ShareData);
+
+impl<'de, const T: usize, const N: usize> serde::Deserialize<'de> for TypedShare<T, N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let share = ShareData::deserialize(deserializer)?;
+        if share.index == 0 || share.index > N {
+            return Err(serde::de::Error::custom(format!(
+                "slice index {} is outside 1..={}",
+                share.index, N
+            )));
+        }
+        Ok(TypedShare(share))
+    }
+}
+
+impl<const T: usize, const N: usize> TypedShare<T, N> {
+    /// Access the underlying slice.
+    pub fn as_share(&self) -> &ShareData {
+        &self.0
+    }
+
+    /// Slice index within the committee (1..=N).
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+}
+
+/// A complete `T`-of-`N` dealing.
+pub struct Sharing<const T: usize, const N: usize> {
+    shares: Vec<TypedShare<T, N>>,
+}
+
+impl<const T: usize, const N: usize> Sharing<T, N> {
+    /// Evaluated at monomorphization time; rejects `T == 0` and `T > N` at compile time.
+    const VALID_PARAMETERS: () = assert!(T >= 1 && T <= N, "require 1 <= T <= N");
+
+    /// Deal `secret` into `N` slices with threshold `T`.
+    pub fn deal(secret: Scalar) -> CryptoResult<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_PARAMETERS;
+        let shares = sharing::generate_key_shares(secret, T, N)?
+            .into_iter()
+            .map(TypedShare)
+            .collect();
+        Ok(Self { shares })
+    }
+
+    /// All `N` slices of the dealing.
+    pub fn shares(&self) -> &[TypedShare<T, N>] {
+        &self.shares
+    }
+
+    /// Take ownership of the slices for distribution.
+    pub fn into_shares(self) -> [TypedShare<T, N>; N] {
+        match self.shares.try_into() {
+            Ok(shares) => shares,
+            Err(_) => unreachable!("a Sharing always holds exactly N slices"),
+        }
+    }
+
+    /// Recover the secret from exactly `T` slices of a `T`-of-`N` sharing.
+    pub fn recover(shares: &[TypedShare<T, N>; T]) -> LagrangeResult<Scalar> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_PARAMETERS;
+        let raw: Vec<ShareData> = shares.iter().map(|s| s.0.clone()).collect();
+        lagrange_fft::recover_secret_fft(&raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_round_trip() {
+        let secret = Scalar::from(99u64);
+        let [a, _, c, _, e] = Sharing::<3, 5>::deal(secret).unwrap().into_shares();
+
+        let recovered = Sharing::<3, 5>::recover(&[a, c, e]).unwrap();
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn test_typed_indices() {
+        let sharing = Sharing::<2, 4>::deal(Scalar::ONE).unwrap();
+        let indices: Vec<_> = sharing.shares().iter().map(TypedShare::index).collect();
+        assert_eq!(indices, vec![1, 2, 3, 4]);
+
+        // Decoding checks the index against the committee size of the type.
+        let json = serde_json::to_value(&sharing.shares()[3]).unwrap();
+        let share: TypedShare<2, 4> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(share.index(), 4);
+        assert!(serde_json::from_value::<TypedShare<2, 3>>(json.clone()).is_err());
+        let mut zero = json;
+        zero["index"] = 0.into();
+        assert!(serde_json::from_value::<TypedShare<2, 4>>(zero).is_err());
+    }
+}
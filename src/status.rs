@@ -0,0 +1,95 @@
+//! **status module**
+//!
+//! Read-only operator snapshot of an [`crate::EnterpriseCryptoSystem`].
+//!
+//! [`SystemStatus`] gathers everything a dashboard needs in one serializable value, computed from
+//! the system's audit log, ceremony transcripts, custodian attestations and performance samples:
+//!
+//! - key states, derived from the latest lifecycle audit event of every key;
+//! - slices outstanding per key, from the latest dealing or refresh transcript;
+//! - the last attestation received from every custodian;
+//! - the most recent high-severity audit events;
+//! - whether the system is in safe mode, and why;
+//! - duration statistics per operation type.
+
+use crate::error::AuditRecord;
+use crate::key_lifecycle::KeyState;
+use chrono::{DateTime, Utc};
+
+/// Number of high-severity events kept in a [`SystemStatus`].
+pub const RECENT_EVENT_LIMIT: usize = 20;
+
+/// Number of keys in each lifecycle state.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyStateCounts {
+    pub generated: usize,
+    pub active: usize,
+    pub retired: usize,
+    pub destroyed: usize,
+}
+
+impl KeyStateCounts {
+    pub(crate) fn add(&mut self, state: KeyState) {
+        match state {
+            KeyState::Generated => self.generated += 1,
+            KeyState::Active => self.active += 1,
+            KeyState::Retired => self.retired += 1,
+            KeyState::Destroyed => self.destroyed += 1,
+        }
+    }
+}
+
+/// Status of one key.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct KeyStatus {
+    pub key_id: String,
+    /// Latest lifecycle state recorded in the audit log, if any.
+    pub state: Option<KeyState>,
+    /// Epoch of the latest dealing or refresh.
+    pub epoch: u64,
+    pub threshold: usize,
+    /// Slices issued by the latest dealing or refresh.
+    pub shares_outstanding: usize,
+}
+
+/// Last attestation received from one custodian.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CustodianStatus {
+    pub custodian_id: String,
+    pub key_id: String,
+    /// Index of the attested slice.
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub commitment_root: Vec<u8>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Duration statistics of one operation type.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PerformanceSummary {
+    pub operation_type: String,
+    pub samples: usize,
+    pub mean_duration_ns: u64,
+    pub max_duration_ns: u64,
+}
+
+/// Operator snapshot returned by [`crate::EnterpriseCryptoSystem::status`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct SystemStatus {
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub generated_at: DateTime<Utc>,
+    pub key_counts: KeyStateCounts,
+    /// Keys in order of first appearance.
+    pub keys: Vec<KeyStatus>,
+    /// Custodians in order of first attestation.
+    pub custodians: Vec<CustodianStatus>,
+    /// Up to [`RECENT_EVENT_LIMIT`] high-severity audit records, newest first.
+    pub recent_high_severity_events: Vec<AuditRecord>,
+    /// Why secret-producing operations are refused, if the system is in safe mode.
+    #[serde(default)]
+    pub safe_mode: Option<String>,
+    /// Operation types in order of first sample, over the samples still buffered (not yet
+    /// exported or drained).
+    pub performance: Vec<PerformanceSummary>,
+}
@@ -0,0 +1,385 @@
+//! **threshold_decrypt module**
+//!
+//! Threshold ElGamal: messages encrypted to the group public key `Y = s·G` of a sharing and
+//! decrypted by a quorum of slice holders, without reconstructing `s`.
+//!
+//! - [`encrypt`] draws `r`, publishes `R = r·G` and derives an encryption key and a MAC key with
+//!   BLAKE3 from `K = r·Y`, `R` and `Y`; the message is XORed with the keyed BLAKE3 XOF and
+//!   authenticated with a keyed BLAKE3 tag (encrypt-then-MAC).
+//! - Each holder `i` publishes `D_i = s_i·R` ([`partial_decrypt`]) with a DLEQ proof that
+//!   `log_G Y_i = log_R D_i`, where `Y_i = s_i·G` is its verification share
+//!   ([`crate::threshold_sig::verification_share`]). [`verify_partial`] checks it, which
+//!   identifies a holder that sends a wrong `D_i`.
+//! - [`combine`] interpolates `K = Σ λ_i·D_i` in the exponent over `threshold` partials, then
+//!   checks the tag and decrypts.
+//!
+//! Only `D_i` leaves a holder, never `s_i`; `D_i` is useless for any other ciphertext since `r` is
+//! fresh per message. The proofs are bound to the ciphertext, so a partial decryption cannot be
+//! replayed against another one.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::op_counts::{count, Op};
+use crate::pvss::{DleqProof, DleqStatement};
+#[cfg(feature = "dealer")]
+use crate::sharing::ShareData;
+use crate::threshold_sig;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+#[cfg(feature = "dealer")]
+use curve25519_dalek::traits::IsIdentity;
+use curve25519_dalek::traits::VartimeMultiscalarMul;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Version of the [`ThresholdCiphertext`] format.
+pub const THRESHOLD_CIPHERTEXT_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`ThresholdCiphertext`].
+pub const THRESHOLD_CIPHERTEXT_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: Some(AlgorithmId::ThresholdElgamal),
+    aead: Some(AlgorithmId::Blake3XofEtm),
+    kdf: Some(AlgorithmId::Blake3DeriveKey),
+    proof: None,
+};
+
+const KDF_CONTEXT: &str = "zk-thresh-pro 2024 threshold decryption kdf v1";
+const CIPHERTEXT_CONTEXT: &str = "zk-thresh-pro 2024 threshold ciphertext v1";
+const PARTIAL_PROOF_CONTEXT: &str = "zk-thresh-pro 2024 threshold decryption proof v1";
+const TAG_LENGTH: usize = 32;
+
+fn default_algorithms() -> AlgorithmSuite {
+    THRESHOLD_CIPHERTEXT_ALGORITHMS
+}
+
+/// A message encrypted to a group public key.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ThresholdCiphertext {
+    /// Format version, [`THRESHOLD_CIPHERTEXT_VERSION`].
+    pub version: u8,
+    /// Algorithms the ciphertext was produced with; [`THRESHOLD_CIPHERTEXT_ALGORITHMS`] when
+    /// absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    /// Ephemeral `R = r·G`.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub ephemeral: RistrettoPoint,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub ciphertext: Vec<u8>,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub tag: Vec<u8>,
+}
+
+/// One holder's share `D_i = s_i·R` of the decryption of a ciphertext.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PartialDecryption {
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub share: RistrettoPoint,
+    /// Proof that `log_G Y_i = log_R D_i`.
+    pub proof: DleqProof,
+}
+
+/// Encryption and MAC keys of one ciphertext.
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct MessageKeys {
+    encryption: [u8; 32],
+    mac: [u8; 32],
+}
+
+impl MessageKeys {
+    fn derive(
+        shared: &RistrettoPoint,
+        ephemeral: &RistrettoPoint,
+        public_key: &RistrettoPoint,
+    ) -> Self {
+        let mut hasher = Blake3Adapter::new_derive_key(KDF_CONTEXT);
+        for point in [shared, ephemeral, public_key] {
+            digest::Update::update(&mut hasher, point.compress().as_bytes());
+        }
+        let mut okm = Zeroizing::new([0u8; 64]);
+        hasher.finalize_extended(okm.as_mut());
+        let mut keys = Self {
+            encryption: [0u8; 32],
+            mac: [0u8; 32],
+        };
+        keys.encryption.copy_from_slice(&okm[..32]);
+        keys.mac.copy_from_slice(&okm[32..]);
+        keys
+    }
+
+    fn apply_keystream(&self, data: &mut [u8]) {
+        let mut keystream = Zeroizing::new(vec![0u8; data.len()]);
+        Blake3Adapter::new_keyed(&self.encryption).finalize_extended(&mut keystream);
+        for (byte, k) in data.iter_mut().zip(keystream.iter()) {
+            *byte ^= k;
+        }
+    }
+
+    fn tag(&self, ciphertext: &ThresholdCiphertext) -> [u8; TAG_LENGTH] {
+        let mut hasher = Blake3Adapter::new_keyed(&self.mac);
+        digest::Update::update(&mut hasher, &[ciphertext.version]);
+        digest::Update::update(&mut hasher, ciphertext.ephemeral.compress().as_bytes());
+        digest::Update::update(&mut hasher, &ciphertext.ciphertext);
+        let mut out = [0u8; TAG_LENGTH];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+impl ThresholdCiphertext {
+    /// Digest the partial decryption proofs are bound to.
+    fn digest(&self) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(CIPHERTEXT_CONTEXT);
+        digest::Update::update(&mut hasher, &[self.version]);
+        digest::Update::update(&mut hasher, self.ephemeral.compress().as_bytes());
+        digest::Update::update(&mut hasher, &(self.ciphertext.len() as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &self.ciphertext);
+        digest::Update::update(&mut hasher, &self.tag);
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+
+    /// Reject unknown versions and algorithms, and malformed tags.
+    fn check_format(&self) -> CryptoResult<()> {
+        if self.version != THRESHOLD_CIPHERTEXT_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported threshold ciphertext version {}", self.version),
+            });
+        }
+        self.algorithms
+            .require(&THRESHOLD_CIPHERTEXT_ALGORITHMS, "threshold ciphertext")?;
+        if self.tag.len() != TAG_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: "threshold ciphertext tag has the wrong length".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn partial_statement<'a>(
+    digest: &'a [u8; 32],
+    ciphertext: &ThresholdCiphertext,
+    index: usize,
+    verification_share: &RistrettoPoint,
+    share: &RistrettoPoint,
+) -> DleqStatement<'a> {
+    DleqStatement {
+        context: PARTIAL_PROOF_CONTEXT,
+        binding: digest,
+        index,
+        g1: RISTRETTO_BASEPOINT_POINT,
+        h1: *verification_share,
+        g2: ciphertext.ephemeral,
+        h2: *share,
+    }
+}
+
+/// Encrypt `plaintext` to the group public key `public_key`.
+///
+/// Returns `CryptoError::Validation` for the identity public key and
+/// `CryptoError::InsufficientEntropy` if `r` cannot be drawn.
+#[cfg(feature = "dealer")]
+pub fn encrypt(public_key: &RistrettoPoint, plaintext: &[u8]) -> CryptoResult<ThresholdCiphertext> {
+    if public_key.is_identity() {
+        return Err(CryptoError::Validation {
+            field: "public_key".to_string(),
+            reason: "the identity is not a valid public key".to_string(),
+        });
+    }
+    let mut r = crate::utils::random_scalar(&mut crate::utils::new_rng())?;
+    count(Op::PointMul, 2);
+    let ephemeral = RISTRETTO_BASEPOINT_POINT * r;
+    let keys = MessageKeys::derive(&(public_key * r), &ephemeral, public_key);
+    r.zeroize();
+
+    let mut ciphertext = ThresholdCiphertext {
+        version: THRESHOLD_CIPHERTEXT_VERSION,
+        algorithms: THRESHOLD_CIPHERTEXT_ALGORITHMS,
+        ephemeral,
+        ciphertext: plaintext.to_vec(),
+        tag: Vec::new(),
+    };
+    keys.apply_keystream(&mut ciphertext.ciphertext);
+    ciphertext.tag = keys.tag(&ciphertext).to_vec();
+    Ok(ciphertext)
+}
+
+/// Partial decryption of `ciphertext` with the slice `share`, with its proof.
+///
+/// Returns `CryptoError::Serialization` for a malformed ciphertext and
+/// `CryptoError::InsufficientEntropy` if the proof nonce cannot be drawn.
+#[cfg(feature = "dealer")]
+pub fn partial_decrypt(
+    ciphertext: &ThresholdCiphertext,
+    share: &ShareData,
+) -> CryptoResult<PartialDecryption> {
+    ciphertext.check_format()?;
+    count(Op::PointMul, 3);
+    let decryption_share = ciphertext.ephemeral * share.share;
+    let verification_share = threshold_sig::verification_share(share);
+    let digest = ciphertext.digest();
+    let proof = partial_statement(
+        &digest,
+        ciphertext,
+        share.index,
+        &verification_share,
+        &decryption_share,
+    )
+    .prove(&share.share)?;
+    Ok(PartialDecryption {
+        index: share.index,
+        share: decryption_share,
+        proof,
+    })
+}
+
+/// Check `partial` against the verification share `Y_i = s_i·G` of its holder.
+///
+/// Returns `CryptoError::SecurityViolation` if the proof does not verify.
+pub fn verify_partial(
+    ciphertext: &ThresholdCiphertext,
+    partial: &PartialDecryption,
+    verification_share: &RistrettoPoint,
+) -> CryptoResult<()> {
+    ciphertext.check_format()?;
+    count(Op::PointMul, 4);
+    let digest = ciphertext.digest();
+    let statement = partial_statement(
+        &digest,
+        ciphertext,
+        partial.index,
+        verification_share,
+        &partial.share,
+    );
+    if statement.verify(&partial.proof) {
+        Ok(())
+    } else {
+        Err(CryptoError::SecurityViolation {
+            details: format!(
+                "partial decryption of slice {} does not verify",
+                partial.index
+            ),
+        })
+    }
+}
+
+/// Decrypt `ciphertext` from at least `threshold` partial decryptions with distinct indices.
+///
+/// Returns `CryptoError::Validation` for duplicate or zero indices and
+/// `CryptoError::CryptographicOperation` if the combined key does not authenticate the
+/// ciphertext, e.g. because a partial decryption is wrong or too few were given; use
+/// [`verify_partial`] to find the culprit.
+pub fn combine(
+    public_key: &RistrettoPoint,
+    ciphertext: &ThresholdCiphertext,
+    partials: &[PartialDecryption],
+) -> CryptoResult<Zeroizing<Vec<u8>>> {
+    ciphertext.check_format()?;
+    let indices: Vec<usize> = partials.iter().map(|p| p.index).collect();
+    let lambda = threshold_sig::lagrange_at_zero(&indices)?;
+    count(Op::PointMul, partials.len());
+    let shared = RistrettoPoint::vartime_multiscalar_mul(lambda, partials.iter().map(|p| &p.share));
+    let keys = MessageKeys::derive(&shared, &ciphertext.ephemeral, public_key);
+
+    let mut expected = [0u8; TAG_LENGTH];
+    expected.copy_from_slice(&ciphertext.tag);
+    // blake3::Hash compares in constant time.
+    if blake3::Hash::from(keys.tag(ciphertext)) != blake3::Hash::from(expected) {
+        return Err(CryptoError::CryptographicOperation {
+            operation: "threshold decryption: ciphertext failed authentication".to_string(),
+        });
+    }
+    let mut plaintext = Zeroizing::new(ciphertext.ciphertext.clone());
+    keys.apply_keystream(&mut plaintext);
+    Ok(plaintext)
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use crate::threshold_sig::verification_share;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_threshold_decryption_roundtrip() {
+        let secret = Scalar::from(42u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 3, 5).unwrap();
+        let message = b"wire transfer approval";
+        let ciphertext = encrypt(&public_key, message).unwrap();
+        assert_ne!(&ciphertext.ciphertext[..], &message[..]);
+
+        let partials: Vec<_> = shares
+            .iter()
+            .map(|s| partial_decrypt(&ciphertext, s).unwrap())
+            .collect();
+        for (partial, share) in partials.iter().zip(&shares) {
+            verify_partial(&ciphertext, partial, &verification_share(share)).unwrap();
+        }
+        let json = serde_json::to_string(&ciphertext).unwrap();
+        let parsed: ThresholdCiphertext = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            &combine(&public_key, &parsed, &partials[1..4]).unwrap()[..],
+            &message[..]
+        );
+        assert_eq!(
+            &combine(&public_key, &ciphertext, &partials).unwrap()[..],
+            &message[..]
+        );
+
+        // Too few partials, or a partial of another slice, do not authenticate.
+        assert!(matches!(
+            combine(&public_key, &ciphertext, &partials[..2]),
+            Err(CryptoError::CryptographicOperation { .. })
+        ));
+        let mut forged = partials[0].clone();
+        forged.share = partials[1].share;
+        assert!(verify_partial(&ciphertext, &forged, &verification_share(&shares[0])).is_err());
+        assert!(combine(
+            &public_key,
+            &ciphertext,
+            &[forged, partials[2].clone(), partials[3].clone()]
+        )
+        .is_err());
+        assert!(matches!(
+            combine(
+                &public_key,
+                &ciphertext,
+                &[partials[0].clone(), partials[0].clone()]
+            ),
+            Err(CryptoError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_partials_are_bound_to_their_ciphertext() {
+        let secret = Scalar::from(7u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 2, 3).unwrap();
+        let first = encrypt(&public_key, b"first").unwrap();
+        let second = encrypt(&public_key, b"second").unwrap();
+        let partial = partial_decrypt(&first, &shares[0]).unwrap();
+        assert!(verify_partial(&second, &partial, &verification_share(&shares[0])).is_err());
+
+        let mut tampered = first.clone();
+        tampered.ciphertext[0] ^= 1;
+        let partials: Vec<_> = shares[..2]
+            .iter()
+            .map(|s| partial_decrypt(&tampered, s).unwrap())
+            .collect();
+        assert!(combine(&public_key, &tampered, &partials).is_err());
+        assert!(encrypt(&RistrettoPoint::default(), b"x").is_err());
+        let mut wrong_version = first;
+        wrong_version.version = 9;
+        assert!(matches!(
+            partial_decrypt(&wrong_version, &shares[0]),
+            Err(CryptoError::Serialization { .. })
+        ));
+    }
+}
@@ -0,0 +1,803 @@
+//! **threshold_sig module**
+//!
+//! Threshold Schnorr signatures over Ristretto, produced directly from Shamir slices.
+//!
+//! A quorum of slice holders signs a message without reconstructing the secret `s`:
+//!
+//! 1. Each signer `i` draws a fresh nonce `k_i` and publishes `R_i = k_i·G` ([`commit`]).
+//! 2. Once all commitments of the signing set are known, each signer publishes
+//!    `z_i = k_i + c·λ_i·s_i` ([`sign_partial`]), where `R = Σ R_i`, `c = H(R, Y, m)` and `λ_i` is
+//!    the Lagrange coefficient of `i` at zero over the signing set.
+//! 3. Anyone combines the partial signatures into `(R, z = Σ z_i)` ([`aggregate`]), which verifies
+//!    as an ordinary Schnorr signature under the group public key `Y = s·G`.
+//!
+//! Partial signatures can be checked individually against the signer's verification share
+//! `Y_i = s_i·G` ([`verify_partial`]), which identifies a misbehaving signer. Signers sign their
+//! commitments and partial signatures with their [`Participant`](crate::identity::Participant)
+//! identity when they travel through a coordinator; [`verify_commitments`] and [`aggregate_signed`]
+//! accept only commitments and partial signatures signed by their signer for the signing session.
+//!
+//! Nonces are single-use: [`SigningNonce`] is consumed by [`sign_partial`] and zeroized on drop.
+//! The commitments are not bound to the message or the signing set, so signing sessions must not
+//! run concurrently with the same signers: many parallel sessions let a malicious signer choose
+//! its commitment adaptively and forge. Concurrent signing needs per-signer binding factors (as in
+//! FROST).
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Roster, Signed, SignedPayload};
+#[cfg(feature = "dealer")]
+use crate::lagrange_fft::LagrangeCoefficients;
+use crate::op_counts::{count, Op};
+#[cfg(feature = "dealer")]
+use crate::sharing::ShareData;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::VartimeMultiscalarMul;
+#[cfg(feature = "dealer")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Length of the canonical [`SchnorrSignature`] encoding.
+pub const SIGNATURE_LENGTH: usize = 64;
+
+/// Schnorr signature `(R, z)` with `z·G = R + H(R, Y, m)·Y`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchnorrSignature {
+    /// Group nonce commitment `R`.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+    /// Response `z`.
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub response: Scalar,
+}
+
+impl SchnorrSignature {
+    /// Canonical encoding: compressed `R` followed by the canonical bytes of `z`.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes[..32].copy_from_slice(self.commitment.compress().as_bytes());
+        bytes[32..].copy_from_slice(self.response.as_bytes());
+        bytes
+    }
+
+    /// Decode [`SchnorrSignature::to_bytes`], rejecting invalid points and non-canonical scalars.
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: format!(
+                    "signature must be {} bytes, got {}",
+                    SIGNATURE_LENGTH,
+                    bytes.len()
+                ),
+            });
+        }
+        let commitment = bytes[..32]
+            .try_into()
+            .ok()
+            .and_then(crate::serialization::canonical_point)
+            .ok_or_else(|| CryptoError::Serialization {
+                details: "signature commitment is not a canonical Ristretto point".to_string(),
+            })?;
+        let mut response = [0u8; 32];
+        response.copy_from_slice(&bytes[32..]);
+        let response = Option::from(Scalar::from_canonical_bytes(response)).ok_or_else(|| {
+            CryptoError::Serialization {
+                details: "signature response is not a canonical scalar".to_string(),
+            }
+        })?;
+        Ok(Self {
+            commitment,
+            response,
+        })
+    }
+
+    /// Verify the signature on `message` under `public_key`.
+    pub fn verify(&self, public_key: &RistrettoPoint, message: &[u8]) -> bool {
+        let c = challenge(&self.commitment, public_key, message);
+        // z·G - c·Y == R
+        let lhs = RistrettoPoint::vartime_multiscalar_mul(
+            [self.response, -c],
+            [RISTRETTO_BASEPOINT_POINT, *public_key],
+        );
+        lhs == self.commitment
+    }
+}
+
+/// Public nonce commitment `R_i = k_i·G` of signer `index` (round one).
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonceCommitment {
+    /// Slice index of the signer.
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+}
+
+impl SignedPayload for NonceCommitment {
+    const DOMAIN: &'static str = "threshold schnorr nonce commitment";
+}
+
+/// Partial signature `z_i` of signer `index` (round two).
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    /// Slice index of the signer.
+    pub index: usize,
+    #[serde(with = "crate::serialization::serialize_scalar_helpers")]
+    pub response: Scalar,
+}
+
+impl SignedPayload for PartialSignature {
+    const DOMAIN: &'static str = "threshold schnorr partial signature";
+}
+
+/// Secret nonce `k_i` behind a [`NonceCommitment`]; consumed by [`sign_partial`].
+#[cfg(feature = "dealer")]
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SigningNonce {
+    #[zeroize(skip)]
+    index: usize,
+    nonce: Scalar,
+}
+
+#[cfg(feature = "dealer")]
+impl SigningNonce {
+    /// Slice index the nonce was drawn for.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(feature = "dealer")]
+impl std::fmt::Debug for SigningNonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningNonce")
+            .field("index", &self.index)
+            .field("nonce", &crate::redact::Redacted(&self.nonce))
+            .finish()
+    }
+}
+
+/// Fiat-Shamir challenge `c = H(R, Y, m)`.
+pub fn challenge(
+    commitment: &RistrettoPoint,
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> Scalar {
+    let mut hasher = Blake3Adapter::new_derive_key("zk-thresh-pro 2024 threshold schnorr v1");
+    digest::Update::update(&mut hasher, commitment.compress().as_bytes());
+    digest::Update::update(&mut hasher, public_key.compress().as_bytes());
+    digest::Update::update(&mut hasher, &(message.len() as u64).to_le_bytes());
+    digest::Update::update(&mut hasher, message);
+    Scalar::from_hash(hasher)
+}
+
+/// Reject an empty index set, a zero index or a duplicate index.
+fn check_index_set(indices: &[usize]) -> CryptoResult<()> {
+    if indices.is_empty() {
+        return Err(CryptoError::Validation {
+            field: "indices".to_string(),
+            reason: "signing set is empty".to_string(),
+        });
+    }
+    for (position, &index) in indices.iter().enumerate() {
+        if index == 0 {
+            return Err(CryptoError::Validation {
+                field: "indices".to_string(),
+                reason: "slice index 0 is reserved for the secret".to_string(),
+            });
+        }
+        if indices[..position].contains(&index) {
+            return Err(CryptoError::Validation {
+                field: "indices".to_string(),
+                reason: format!("duplicate slice index {}", index),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Lagrange coefficient at zero of slice `index` within `index_set`:
+/// `λ_i = Π_{j≠i} x_j / (x_j - x_i)`.
+///
+/// This is the weight a threshold-signing or partial-decryption integration applies to the
+/// contribution of slice `index` when the slices in `index_set` take part; `Σ λ_i·s_i = s`. To
+/// weight every member of the set, [`lagrange_at_zero`] shares a single inversion between them.
+///
+/// Returns `CryptoError::Validation` for an empty set, a zero or duplicate index, or an `index`
+/// that is not in `index_set`.
+pub fn lagrange_coefficient(index: usize, index_set: &[usize]) -> CryptoResult<Scalar> {
+    check_index_set(index_set)?;
+    if !index_set.contains(&index) {
+        return Err(CryptoError::Validation {
+            field: "index".to_string(),
+            reason: format!("slice index {} is not part of the index set", index),
+        });
+    }
+    let x_i = Scalar::from(index as u64);
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for x_j in index_set
+        .iter()
+        .filter(|&&j| j != index)
+        .map(|&j| Scalar::from(j as u64))
+    {
+        numerator *= x_j;
+        denominator *= x_j - x_i;
+    }
+    count(Op::Inversion, 1);
+    Ok(numerator * denominator.invert())
+}
+
+/// Lagrange coefficients at zero for the slice indices of a signing set, in input order.
+///
+/// The batch form of [`lagrange_coefficient`]: entry `k` equals
+/// `lagrange_coefficient(indices[k], indices)`.
+///
+/// Returns `CryptoError::Validation` for an empty set, a zero index or a duplicate index.
+pub fn lagrange_at_zero(indices: &[usize]) -> CryptoResult<Vec<Scalar>> {
+    check_index_set(indices)?;
+
+    let xs: Vec<Scalar> = indices.iter().map(|&i| Scalar::from(i as u64)).collect();
+    let mut numerators = Vec::with_capacity(xs.len());
+    let mut denominators = Vec::with_capacity(xs.len());
+    for (i, x_i) in xs.iter().enumerate() {
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (j, x_j) in xs.iter().enumerate() {
+            if i != j {
+                numerator *= x_j;
+                denominator *= x_j - x_i;
+            }
+        }
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+    Scalar::batch_invert(&mut denominators);
+    count(Op::ScalarMul, 2 * xs.len() * xs.len() + 4 * xs.len());
+    count(Op::Inversion, 1);
+    Ok(numerators
+        .into_iter()
+        .zip(denominators)
+        .map(|(n, d)| n * d)
+        .collect())
+}
+
+/// Verification share `Y_i = s_i·G` of a slice, published so partial signatures can be checked.
+#[cfg(feature = "dealer")]
+pub fn verification_share(share: &ShareData) -> RistrettoPoint {
+    RISTRETTO_BASEPOINT_POINT * share.share
+}
+
+/// Group public key `Y = s·G`, interpolated in the exponent from `(index, Y_i)` pairs of at
+/// least `threshold` distinct slices.
+pub fn group_public_key(
+    verification_shares: &[(usize, RistrettoPoint)],
+) -> CryptoResult<RistrettoPoint> {
+    let indices: Vec<usize> = verification_shares.iter().map(|(i, _)| *i).collect();
+    let lambda = lagrange_at_zero(&indices)?;
+    Ok(RistrettoPoint::vartime_multiscalar_mul(
+        lambda,
+        verification_shares.iter().map(|(_, y)| y),
+    ))
+}
+
+/// Signing set, group commitment `R = Σ R_i` and challenge for a round-one commitment list.
+fn signing_session(
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> (Vec<usize>, RistrettoPoint, Scalar) {
+    let indices: Vec<usize> = commitments.iter().map(|c| c.index).collect();
+    let group_commitment: RistrettoPoint = commitments.iter().map(|c| c.commitment).sum();
+    let c = challenge(&group_commitment, public_key, message);
+    (indices, group_commitment, c)
+}
+
+/// Lagrange coefficient of `index` within the signing set.
+fn signer_coefficient(indices: &[usize], index: usize) -> CryptoResult<Scalar> {
+    if !indices.contains(&index) {
+        return Err(CryptoError::Validation {
+            field: "commitments".to_string(),
+            reason: format!("signer {} is not part of the signing set", index),
+        });
+    }
+    lagrange_coefficient(index, indices)
+}
+
+/// Weight of `index` from `coefficients` precomputed for the signing set `indices`.
+#[cfg(feature = "dealer")]
+fn precomputed_coefficient(
+    coefficients: &LagrangeCoefficients,
+    indices: &[usize],
+    index: usize,
+) -> CryptoResult<Scalar> {
+    if coefficients.indices() != indices {
+        return Err(CryptoError::Validation {
+            field: "coefficients".to_string(),
+            reason: "precomputed coefficients are not those of the signing set".to_string(),
+        });
+    }
+    coefficients
+        .weight(index)
+        .ok_or_else(|| CryptoError::Validation {
+            field: "commitments".to_string(),
+            reason: format!("signer {} is not part of the signing set", index),
+        })
+}
+
+/// Round one: draw a fresh nonce for `share` and its public commitment.
+///
+/// Returns `CryptoError::InsufficientEntropy` if the nonce cannot be drawn.
+#[cfg(feature = "dealer")]
+pub fn commit(share: &ShareData) -> CryptoResult<(SigningNonce, NonceCommitment)> {
+    let nonce = crate::utils::random_scalar(&mut crate::utils::new_rng())?;
+    let commitment = NonceCommitment {
+        index: share.index,
+        commitment: RISTRETTO_BASEPOINT_POINT * nonce,
+    };
+    Ok((
+        SigningNonce {
+            index: share.index,
+            nonce,
+        },
+        commitment,
+    ))
+}
+
+/// Round two: partial signature of `share` on `message`, given the commitments of the whole
+/// signing set (including this signer's own).
+///
+/// The nonce is consumed, so it can never sign twice.
+#[cfg(feature = "dealer")]
+pub fn sign_partial(
+    share: &ShareData,
+    nonce: SigningNonce,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> CryptoResult<PartialSignature> {
+    partial_signature(share, nonce, commitments, public_key, message, |indices| {
+        signer_coefficient(indices, share.index)
+    })
+}
+
+/// [`sign_partial`] with the Lagrange weights of the signing set precomputed, e.g. by a
+/// [`LagrangeCache`](crate::lagrange_fft::LagrangeCache) shared between signing rounds.
+///
+/// Returns `CryptoError::Validation` if `coefficients` are not those of the signing set of
+/// `commitments`, in order, and otherwise fails like [`sign_partial`].
+#[cfg(feature = "dealer")]
+pub fn sign_partial_with(
+    share: &ShareData,
+    nonce: SigningNonce,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+    coefficients: &LagrangeCoefficients,
+) -> CryptoResult<PartialSignature> {
+    partial_signature(share, nonce, commitments, public_key, message, |indices| {
+        precomputed_coefficient(coefficients, indices, share.index)
+    })
+}
+
+/// Round two with the signer's Lagrange weight drawn by `coefficient` from the signing set.
+#[cfg(feature = "dealer")]
+fn partial_signature(
+    share: &ShareData,
+    nonce: SigningNonce,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+    coefficient: impl FnOnce(&[usize]) -> CryptoResult<Scalar>,
+) -> CryptoResult<PartialSignature> {
+    if nonce.index != share.index {
+        return Err(CryptoError::Validation {
+            field: "nonce".to_string(),
+            reason: format!(
+                "nonce drawn for slice {} used with slice {}",
+                nonce.index, share.index
+            ),
+        });
+    }
+    let own = commitments.iter().find(|c| c.index == share.index);
+    if own.map(|c| c.commitment) != Some(RISTRETTO_BASEPOINT_POINT * nonce.nonce) {
+        return Err(CryptoError::Validation {
+            field: "commitments".to_string(),
+            reason: format!(
+                "commitment of signer {} does not match its nonce",
+                share.index
+            ),
+        });
+    }
+    let (indices, _, c) = signing_session(commitments, public_key, message);
+    let lambda = coefficient(&indices)?;
+    Ok(PartialSignature {
+        index: share.index,
+        response: nonce.nonce + c * lambda * share.share,
+    })
+}
+
+/// Check one partial signature against the signer's verification share:
+/// `z_i·G = R_i + c·λ_i·Y_i`.
+pub fn verify_partial(
+    partial: &PartialSignature,
+    verification_share: &RistrettoPoint,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> bool {
+    check_partial(
+        partial,
+        verification_share,
+        commitments,
+        public_key,
+        message,
+        |indices| signer_coefficient(indices, partial.index),
+    )
+}
+
+/// [`verify_partial`] with the Lagrange weights of the signing set precomputed; `false` if
+/// `coefficients` are not those of the signing set of `commitments`, in order.
+#[cfg(feature = "dealer")]
+pub fn verify_partial_with(
+    partial: &PartialSignature,
+    verification_share: &RistrettoPoint,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+    coefficients: &LagrangeCoefficients,
+) -> bool {
+    check_partial(
+        partial,
+        verification_share,
+        commitments,
+        public_key,
+        message,
+        |indices| precomputed_coefficient(coefficients, indices, partial.index),
+    )
+}
+
+/// Partial signature check with the signer's Lagrange weight drawn by `coefficient`.
+fn check_partial(
+    partial: &PartialSignature,
+    verification_share: &RistrettoPoint,
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+    coefficient: impl FnOnce(&[usize]) -> CryptoResult<Scalar>,
+) -> bool {
+    let Some(own) = commitments.iter().find(|c| c.index == partial.index) else {
+        return false;
+    };
+    let (indices, _, c) = signing_session(commitments, public_key, message);
+    let Ok(lambda) = coefficient(&indices) else {
+        return false;
+    };
+    let lhs = RistrettoPoint::vartime_multiscalar_mul(
+        [partial.response, -(c * lambda)],
+        [RISTRETTO_BASEPOINT_POINT, *verification_share],
+    );
+    lhs == own.commitment
+}
+
+/// Combine one partial signature per signer into the group signature and verify it.
+///
+/// The partial signatures must come from exactly the signers in `commitments`. Returns
+/// `CryptoError::CryptographicOperation` if the combined signature does not verify, e.g. because
+/// a partial signature is invalid or the signing set is below the threshold; use
+/// [`verify_partial`] to find the culprit.
+pub fn aggregate(
+    partials: &[PartialSignature],
+    commitments: &[NonceCommitment],
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> CryptoResult<SchnorrSignature> {
+    let (indices, group_commitment, _) = signing_session(commitments, public_key, message);
+    lagrange_at_zero(&indices)?;
+    if partials.len() != indices.len()
+        || indices
+            .iter()
+            .any(|i| !partials.iter().any(|p| p.index == *i))
+    {
+        return Err(CryptoError::Validation {
+            field: "partials".to_string(),
+            reason: "partial signatures do not match the signing set".to_string(),
+        });
+    }
+
+    let signature = SchnorrSignature {
+        commitment: group_commitment,
+        response: partials.iter().map(|p| p.response).sum(),
+    };
+    if !signature.verify(public_key, message) {
+        return Err(CryptoError::CryptographicOperation {
+            operation: "threshold signature aggregation".to_string(),
+        });
+    }
+    Ok(signature)
+}
+
+/// The commitments of the signing set, each signed for `session` by its signer's identity in
+/// `roster`; signers pass the result to [`sign_partial`], so a relaying coordinator cannot
+/// substitute another signer's commitment.
+///
+/// Returns `CryptoError::SecurityViolation` for a commitment of another session or not signed by
+/// its signer.
+pub fn verify_commitments(
+    commitments: &[Signed<NonceCommitment>],
+    roster: &Roster,
+    session: &str,
+) -> CryptoResult<Vec<NonceCommitment>> {
+    commitments
+        .iter()
+        .map(|c| c.verify_from(roster, session, c.payload.index).copied())
+        .collect()
+}
+
+/// [`aggregate`] of commitments and partial signatures each signed for `session` by its signer's
+/// identity in `roster`.
+///
+/// Returns `CryptoError::SecurityViolation` for a message of another session or not signed by its
+/// signer, before anything is combined.
+pub fn aggregate_signed(
+    partials: &[Signed<PartialSignature>],
+    commitments: &[Signed<NonceCommitment>],
+    roster: &Roster,
+    session: &str,
+    public_key: &RistrettoPoint,
+    message: &[u8],
+) -> CryptoResult<SchnorrSignature> {
+    let commitments = verify_commitments(commitments, roster, session)?;
+    let partials = partials
+        .iter()
+        .map(|p| p.verify_from(roster, session, p.payload.index).copied())
+        .collect::<CryptoResult<Vec<_>>>()?;
+    aggregate(&partials, &commitments, public_key, message)
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+
+    /// Run both rounds for `signers` and return the commitments and partial signatures.
+    fn sign_with(
+        signers: &[&ShareData],
+        public_key: &RistrettoPoint,
+        message: &[u8],
+    ) -> (Vec<NonceCommitment>, Vec<PartialSignature>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            signers.iter().map(|s| commit(s).unwrap()).unzip();
+        let partials = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, nonce)| sign_partial(s, nonce, &commitments, public_key, message).unwrap())
+            .collect();
+        (commitments, partials)
+    }
+
+    #[test]
+    fn test_quorum_signs_without_reconstruction() {
+        let secret = Scalar::from(1234u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 3, 5).unwrap();
+        let message = b"transfer 10 units";
+
+        let verification_shares: Vec<_> = shares
+            .iter()
+            .map(|s| (s.index, verification_share(s)))
+            .collect();
+        assert_eq!(
+            group_public_key(&verification_shares[1..4]).unwrap(),
+            public_key
+        );
+
+        let signers = [&shares[0], &shares[2], &shares[4]];
+        let (commitments, partials) = sign_with(&signers, &public_key, message);
+        for (partial, signer) in partials.iter().zip(signers) {
+            assert!(verify_partial(
+                partial,
+                &verification_share(signer),
+                &commitments,
+                &public_key,
+                message
+            ));
+        }
+
+        let signature = aggregate(&partials, &commitments, &public_key, message).unwrap();
+        assert!(signature.verify(&public_key, message));
+        assert!(!signature.verify(&public_key, b"transfer 11 units"));
+        assert_eq!(
+            SchnorrSignature::from_bytes(&signature.to_bytes()).unwrap(),
+            signature
+        );
+    }
+
+    #[test]
+    fn test_invalid_partials_and_small_quorums_are_rejected() {
+        let secret = Scalar::from(99u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 3, 5).unwrap();
+        let message = b"m";
+
+        let signers = [&shares[0], &shares[1], &shares[3]];
+        let (commitments, mut partials) = sign_with(&signers, &public_key, message);
+        partials[1].response += Scalar::ONE;
+        assert!(!verify_partial(
+            &partials[1],
+            &verification_share(signers[1]),
+            &commitments,
+            &public_key,
+            message
+        ));
+        assert!(matches!(
+            aggregate(&partials, &commitments, &public_key, message),
+            Err(CryptoError::CryptographicOperation { .. })
+        ));
+        assert!(matches!(
+            aggregate(&partials[..2], &commitments, &public_key, message),
+            Err(CryptoError::Validation { .. })
+        ));
+
+        // Two signers cannot produce a valid signature for a threshold of three.
+        let (commitments, partials) = sign_with(&signers[..2], &public_key, message);
+        assert!(aggregate(&partials, &commitments, &public_key, message).is_err());
+
+        // A nonce is bound to its slice.
+        let (nonce, commitment) = commit(&shares[0]).unwrap();
+        assert!(sign_partial(&shares[1], nonce, &[commitment], &public_key, message).is_err());
+    }
+
+    #[test]
+    fn test_signed_commitments_and_partials() {
+        use crate::identity::Participant;
+
+        let secret = Scalar::from(5150u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 2, 3).unwrap();
+        let identities: Vec<Participant> = (1..=3)
+            .map(|i| Participant::from_seed(i, &[i as u8; 32]).unwrap())
+            .collect();
+        let roster =
+            Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>()).unwrap();
+        let message = b"signed partials";
+        let signers = [&shares[0], &shares[2]];
+
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+            .iter()
+            .map(|s| {
+                let (nonce, commitment) = commit(s).unwrap();
+                let signed = identities[s.index - 1].sign("sign-1", commitment).unwrap();
+                (nonce, signed)
+            })
+            .unzip();
+        let received = verify_commitments(&commitments, &roster, "sign-1").unwrap();
+        let partials: Vec<Signed<PartialSignature>> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(s, nonce)| {
+                let partial = sign_partial(s, nonce, &received, &public_key, message).unwrap();
+                identities[s.index - 1].sign("sign-1", partial).unwrap()
+            })
+            .collect();
+        let signature = aggregate_signed(
+            &partials,
+            &commitments,
+            &roster,
+            "sign-1",
+            &public_key,
+            message,
+        )
+        .unwrap();
+        assert!(signature.verify(&public_key, message));
+
+        // Replayed into another session.
+        assert!(matches!(
+            aggregate_signed(
+                &partials,
+                &commitments,
+                &roster,
+                "sign-2",
+                &public_key,
+                message
+            ),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        // A coordinator substitutes signer 3's commitment, or signer 2 injects a partial
+        // signature in signer 3's name.
+        let (_, substitute) = commit(&shares[2]).unwrap();
+        let substituted = vec![
+            commitments[0].clone(),
+            identities[0].sign("sign-1", substitute).unwrap(),
+        ];
+        assert!(matches!(
+            verify_commitments(&substituted, &roster, "sign-1"),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let injected = vec![
+            partials[0].clone(),
+            identities[1].sign("sign-1", partials[1].payload).unwrap(),
+        ];
+        assert!(matches!(
+            aggregate_signed(
+                &injected,
+                &commitments,
+                &roster,
+                "sign-1",
+                &public_key,
+                message
+            ),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lagrange_coefficient_matches_batch() {
+        let secret = Scalar::from(271828u64);
+        let shares = generate_key_shares(secret, 3, 6).unwrap();
+        let set = [shares[5].index, shares[1].index, shares[3].index];
+        let batch = lagrange_at_zero(&set).unwrap();
+        let mut recovered = Scalar::ZERO;
+        for (k, &index) in set.iter().enumerate() {
+            let lambda = lagrange_coefficient(index, &set).unwrap();
+            assert_eq!(lambda, batch[k]);
+            recovered += lambda * shares[index - 1].share;
+        }
+        assert_eq!(recovered, secret);
+
+        assert!(lagrange_coefficient(3, &set).is_err());
+        assert!(lagrange_coefficient(2, &[2, 2, 3]).is_err());
+        assert!(lagrange_coefficient(0, &[0, 1]).is_err());
+        assert!(lagrange_coefficient(1, &[]).is_err());
+    }
+
+    #[test]
+    fn test_signing_with_cached_coefficients() {
+        let secret = Scalar::from(4242u64);
+        let public_key = RISTRETTO_BASEPOINT_POINT * secret;
+        let shares = generate_key_shares(secret, 2, 4).unwrap();
+        let signers = [&shares[0], &shares[2]];
+        let cache = crate::lagrange_fft::LagrangeCache::new(8);
+        let coefficients = cache.get(&[1, 3]).unwrap();
+
+        // Two signing rounds over the same set share one computation of the weights.
+        for message in [b"first".as_slice(), b"second"] {
+            let (nonces, commitments): (Vec<_>, Vec<_>) =
+                signers.iter().map(|s| commit(s).unwrap()).unzip();
+            let partials: Vec<PartialSignature> = signers
+                .iter()
+                .zip(nonces)
+                .map(|(s, nonce)| {
+                    sign_partial_with(s, nonce, &commitments, &public_key, message, &coefficients)
+                        .unwrap()
+                })
+                .collect();
+            for (signer, partial) in signers.iter().zip(&partials) {
+                assert!(verify_partial_with(
+                    partial,
+                    &verification_share(signer),
+                    &commitments,
+                    &public_key,
+                    message,
+                    &coefficients
+                ));
+            }
+            aggregate(&partials, &commitments, &public_key, message).unwrap();
+        }
+        assert_eq!(cache.len(), 1);
+
+        let (nonce, commitment) = commit(&shares[0]).unwrap();
+        let (_, other) = commit(&shares[1]).unwrap();
+        assert!(matches!(
+            sign_partial_with(
+                &shares[0],
+                nonce,
+                &[commitment, other],
+                &public_key,
+                b"m",
+                &coefficients
+            ),
+            Err(CryptoError::Validation { field, .. }) if field == "coefficients"
+        ));
+    }
+}
@@ -0,0 +1,259 @@
+//! **transcript module**
+//!
+//! Ceremony transcripts and optional timestamp-authority stamping.
+//!
+//! Every dealing, refresh and recovery run through [`crate::EnterpriseCryptoSystem`] produces a
+//! [`CeremonyTranscript`]: what was done, to which key and epoch, with which slice indices and
+//! a digest of the public slice commitments. When a [`TimestampAuthority`] is configured, the
+//! transcript's canonical bytes ([`CeremonyTranscript::canonical_bytes`]) are submitted to it and the
+//! returned token is stored in the transcript, giving independent evidence of when the ceremony
+//! took place.
+//!
+//! For RFC 3161, implement [`TimestampAuthority`] over an HTTP client and an ASN.1/CMS library:
+//! `stamp` hashes the canonical bytes with the TSA's hash algorithm (usually SHA-256) into the
+//! `messageImprint` of a `TimeStampReq` and stores the DER `TimeStampToken` from the response;
+//! `verify` checks the token's signature chain and that its imprint matches the bytes. The crate
+//! itself ships neither, so it stays free of network and X.509 dependencies.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::context::OperationContext;
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::sharing::ShareData;
+use chrono::{DateTime, SubsecRound, Utc};
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+/// Version of the [`CeremonyTranscript`] format.
+pub const TRANSCRIPT_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`CeremonyTranscript`].
+pub const TRANSCRIPT_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: None,
+    aead: None,
+    kdf: None,
+    proof: None,
+};
+
+const COMMITMENTS_CONTEXT: &str = "zk-thresh-pro 2024 transcript share commitments v1";
+
+/// Kind of ceremony a transcript records.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CeremonyKind {
+    Dealing,
+    Refresh,
+    Recovery,
+}
+
+/// Token returned by a timestamp authority for one transcript.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TimestampToken {
+    /// Name of the authority ([`TimestampAuthority::name`]).
+    pub authority: String,
+    /// Opaque token; the DER `TimeStampToken` for RFC 3161 authorities.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub token: Vec<u8>,
+    /// Time asserted by the authority.
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub stamped_at: DateTime<Utc>,
+}
+
+/// A timestamp authority, e.g. an RFC 3161 TSA client.
+pub trait TimestampAuthority {
+    /// Stable name recorded in issued tokens.
+    fn name(&self) -> &str;
+
+    /// Obtain a token over `data`, the canonical bytes of a transcript.
+    fn stamp(&self, data: &[u8]) -> CryptoResult<TimestampToken>;
+
+    /// Check that `token` is a valid token of this authority over `data`.
+    fn verify(&self, data: &[u8], token: &TimestampToken) -> CryptoResult<()>;
+}
+
+fn default_algorithms() -> AlgorithmSuite {
+    TRANSCRIPT_ALGORITHMS
+}
+
+/// Record of one dealing, refresh or recovery ceremony.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CeremonyTranscript {
+    /// Transcript format version, [`TRANSCRIPT_VERSION`].
+    pub version: u8,
+    /// Algorithms the transcript was produced with; [`TRANSCRIPT_ALGORITHMS`] when absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    pub kind: CeremonyKind,
+    pub context: OperationContext,
+    pub threshold: usize,
+    /// Indices of the slices dealt, refreshed or used for recovery.
+    pub indices: Vec<usize>,
+    /// [`commitments_digest`] of those slices.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub commitments_digest: Vec<u8>,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub completed_at: DateTime<Utc>,
+    /// Token over [`Self::canonical_bytes`], if the transcript has been stamped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<TimestampToken>,
+}
+
+/// Digest of the public part (index and commitment) of `shares`, in order.
+pub fn commitments_digest(shares: &[ShareData]) -> [u8; 32] {
+    digest_commitments(shares.iter().map(|s| (s.index, &s.commitment)))
+}
+
+/// [`commitments_digest`] of `(index, commitment)` pairs, in order.
+pub(crate) fn digest_commitments<'a>(
+    commitments: impl ExactSizeIterator<Item = (usize, &'a RistrettoPoint)>,
+) -> [u8; 32] {
+    let mut hasher = Blake3Adapter::new_derive_key(COMMITMENTS_CONTEXT);
+    digest::Update::update(&mut hasher, &(commitments.len() as u64).to_le_bytes());
+    for (index, commitment) in commitments {
+        digest::Update::update(&mut hasher, &(index as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, commitment.compress().as_bytes());
+    }
+    let mut out = [0u8; 32];
+    hasher.finalize_extended(&mut out);
+    out
+}
+
+impl CeremonyTranscript {
+    /// Transcript of a ceremony over `shares` that has just completed.
+    pub fn new(
+        kind: CeremonyKind,
+        context: &OperationContext,
+        threshold: usize,
+        shares: &[ShareData],
+    ) -> Self {
+        Self {
+            version: TRANSCRIPT_VERSION,
+            algorithms: TRANSCRIPT_ALGORITHMS,
+            kind,
+            context: context.clone(),
+            threshold,
+            indices: shares.iter().map(|s| s.index).collect(),
+            commitments_digest: commitments_digest(shares).to_vec(),
+            // Whole seconds, as serialized, so a parsed transcript compares equal to the original.
+            completed_at: Utc::now().trunc_subsecs(0),
+            timestamp: None,
+        }
+    }
+
+    /// Bytes submitted to the timestamp authority: the JSON form without the `timestamp` field.
+    pub fn canonical_bytes(&self) -> CryptoResult<Vec<u8>> {
+        let unstamped = Self {
+            timestamp: None,
+            ..self.clone()
+        };
+        serde_json::to_vec(&unstamped).map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })
+    }
+
+    /// Stamp the transcript with `authority`, replacing any previous token.
+    pub fn stamp(&mut self, authority: &dyn TimestampAuthority) -> CryptoResult<()> {
+        let token = authority.stamp(&self.canonical_bytes()?)?;
+        self.timestamp = Some(token);
+        Ok(())
+    }
+
+    /// Check the stored token with `authority`.
+    ///
+    /// Returns `CryptoError::Serialization` for a transcript of other algorithms,
+    /// `CryptoError::Validation` if it is unstamped or was stamped by another authority, and the
+    /// authority's error if the token does not verify.
+    pub fn verify_timestamp(&self, authority: &dyn TimestampAuthority) -> CryptoResult<()> {
+        self.algorithms
+            .require(&TRANSCRIPT_ALGORITHMS, "ceremony transcript")?;
+        let token = self
+            .timestamp
+            .as_ref()
+            .ok_or_else(|| CryptoError::Validation {
+                field: "timestamp".to_string(),
+                reason: "transcript has not been stamped".to_string(),
+            })?;
+        if token.authority != authority.name() {
+            return Err(CryptoError::Validation {
+                field: "timestamp".to_string(),
+                reason: format!("stamped by {}, not {}", token.authority, authority.name()),
+            });
+        }
+        authority.verify(&self.canonical_bytes()?, token)
+    }
+}
+
+/// Timestamp authority for tests: tokens are keyed BLAKE3 MACs over the time and the data.
+#[cfg(all(test, feature = "dealer"))]
+pub(crate) struct TestAuthority(pub [u8; 32]);
+
+#[cfg(all(test, feature = "dealer"))]
+impl TestAuthority {
+    fn mac(&self, data: &[u8], stamped_at: &DateTime<Utc>) -> Vec<u8> {
+        let mut hasher = Blake3Adapter::new_keyed(&self.0);
+        digest::Update::update(&mut hasher, &stamped_at.timestamp().to_le_bytes());
+        digest::Update::update(&mut hasher, data);
+        let mut out = vec![0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+impl TimestampAuthority for TestAuthority {
+    fn name(&self) -> &str {
+        "test-tsa"
+    }
+
+    fn stamp(&self, data: &[u8]) -> CryptoResult<TimestampToken> {
+        let stamped_at = Utc::now().trunc_subsecs(0);
+        Ok(TimestampToken {
+            authority: self.name().to_string(),
+            token: self.mac(data, &stamped_at),
+            stamped_at,
+        })
+    }
+
+    fn verify(&self, data: &[u8], token: &TimestampToken) -> CryptoResult<()> {
+        if token.token == self.mac(data, &token.stamped_at) {
+            Ok(())
+        } else {
+            Err(CryptoError::SecurityViolation {
+                details: "timestamp token does not match the transcript".to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use curve25519_dalek::Scalar;
+
+    #[test]
+    fn test_stamp_and_verify_transcript() {
+        let shares = generate_key_shares(Scalar::from(3u64), 2, 3).unwrap();
+        let context = OperationContext::new("k", 0, "op-deal");
+        let mut transcript = CeremonyTranscript::new(CeremonyKind::Dealing, &context, 2, &shares);
+        let tsa = TestAuthority([9; 32]);
+        assert!(transcript.verify_timestamp(&tsa).is_err());
+
+        transcript.stamp(&tsa).unwrap();
+        transcript.verify_timestamp(&tsa).unwrap();
+        let json = serde_json::to_string(&transcript).unwrap();
+        let parsed: CeremonyTranscript = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, transcript);
+        parsed.verify_timestamp(&tsa).unwrap();
+
+        let mut altered = transcript.clone();
+        altered.indices.pop();
+        assert!(matches!(
+            altered.verify_timestamp(&tsa),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        assert!(transcript
+            .verify_timestamp(&TestAuthority([8; 32]))
+            .is_err());
+    }
+}
@@ -0,0 +1,873 @@
+//! **transport module**
+//!
+//! Authenticated, encrypted point-to-point channels for distributing slices, DKG messages and
+//! recovery requests between participants.
+//!
+//! A [`SecureChannel`] runs over any `Read + Write` byte stream; [`connect`] and [`accept`] set one
+//! up over TCP. Participants share a pairwise 32-byte [`ChannelKey`] (provisioned out of band, or
+//! derived from a ceremony master key with [`ChannelKey::derive`]). The protocol uses only the
+//! BLAKE3 keyed adapter:
+//!
+//! 1. **Handshake.** Each side sends its participant index, the
+//!    [`PublicParameters::digest`] it runs with and a fresh 32-byte nonce. A peer with different
+//!    parameters (another ciphersuite or security level) is rejected before any slice is
+//!    exchanged. The session key is keyed BLAKE3 under the channel key over the parameters digest
+//!    and both indices and nonces, so every connection gets independent keys and a recorded
+//!    session cannot be replayed into a new one.
+//! 2. **Key confirmation.** Each side sends a MAC over the handshake transcript; a peer without the
+//!    channel key, or claiming a different index, is rejected before any payload is exchanged.
+//! 3. **Frames.** Every [`Message`] is JSON-encoded, XORed with a keyed BLAKE3 keystream and
+//!    authenticated encrypt-then-MAC. Each direction has its own keys and a frame counter that is
+//!    bound into the keystream and the tag, so frames cannot be reordered, replayed or reflected.
+//!
+//! [`SecureChannel::with_identities`] additionally signs every outgoing message with the local
+//! [`Participant`] identity and rejects incoming messages not signed by the peer's identity in the
+//! [`Roster`], so a node holding a stolen channel key still cannot inject messages in the peer's
+//! name. The signatures are bound to the connection through its [`SecureChannel::session_id`], a
+//! digest of the public handshake, so a signed message cannot be replayed into another
+//! connection either.
+//!
+//! DKG messages carry the signature of their author for the DKG session on top of that, so a
+//! broadcast forwarded by another participant is checked against its dealer, not the forwarder,
+//! by the [`crate::dkg::DkgParticipant`] that receives it.
+//!
+//! The channel does not provide forward secrecy: whoever learns a channel key can decrypt
+//! recorded sessions between that pair. Wrap the stream in TLS where that matters; `SecureChannel`
+//! then still authenticates the participant indices.
+
+use crate::context::OperationContext;
+use crate::dkg::{Complaint, ComplaintResponse, Round1Broadcast, Round2Share};
+use crate::error::{CryptoError, CryptoResult};
+use crate::events::{structured_log, EventCode};
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Participant, Roster, Signed, SignedPayload};
+use crate::params::PublicParameters;
+use crate::sharing::ShareData;
+use log::Level;
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Version byte sent in the handshake.
+pub const TRANSPORT_VERSION: u8 = 2;
+
+/// Largest accepted frame payload, in bytes.
+pub const MAX_FRAME_LENGTH: usize = 1 << 20;
+
+const MAGIC: &[u8; 4] = b"ZKTT";
+const HELLO_LENGTH: usize = 4 + 1 + 8 + 32 + 32;
+const TAG_LENGTH: usize = 32;
+
+const PAIR_CONTEXT: &str = "zk-thresh-pro 2024 transport pair key v1";
+const SESSION_CONTEXT: &str = "zk-thresh-pro 2024 transport session v2";
+const ENCRYPTION_CONTEXT: &str = "zk-thresh-pro 2024 transport encryption v1";
+const MAC_CONTEXT: &str = "zk-thresh-pro 2024 transport mac v1";
+const SESSION_ID_CONTEXT: &str = "zk-thresh-pro 2024 transport session id v1";
+const CONFIRM_LABEL: &[u8] = b"key confirmation";
+
+/// Request for the recipient's slice of `context.key_id`, on behalf of participant `requester`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryRequest {
+    pub context: OperationContext,
+    pub requester: usize,
+}
+
+/// Payloads carried by a [`SecureChannel`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub enum Message {
+    /// A dealt or refreshed slice for the recipient.
+    Share(ShareData),
+    /// DKG messages, signed by their author for the DKG session, so a relayed broadcast still
+    /// proves its origin to every [`crate::dkg::DkgParticipant`] that receives it.
+    DkgRound1(Signed<Round1Broadcast>),
+    DkgRound2(Signed<Round2Share>),
+    DkgComplaint(Signed<Complaint>),
+    DkgComplaintResponse(Signed<ComplaintResponse>),
+    RecoveryRequest(RecoveryRequest),
+    /// Answer to a [`RecoveryRequest`] with the given `operation_id`.
+    RecoveryResponse {
+        operation_id: String,
+        share: ShareData,
+    },
+    /// Request to a [`crate::service::Service`].
+    #[cfg(feature = "channel-service")]
+    ServiceRequest(crate::service::ServiceRequest),
+    /// Answer of a [`crate::service::Service`] to the preceding request.
+    #[cfg(feature = "channel-service")]
+    ServiceResponse(crate::service::ServiceResponse),
+}
+
+impl SignedPayload for Message {
+    const DOMAIN: &'static str = "transport message";
+}
+
+/// Pairwise 32-byte key shared by two participants.
+///
+/// `Debug` output is redacted; the key is zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct ChannelKey([u8; 32]);
+
+impl ChannelKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Key of the pair `{a, b}` derived from a ceremony master key; symmetric in `a` and `b`.
+    pub fn derive(master: &[u8; 32], a: usize, b: usize) -> Self {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        let mut hasher = Blake3Adapter::new_keyed(master);
+        digest::Update::update(&mut hasher, PAIR_CONTEXT.as_bytes());
+        digest::Update::update(&mut hasher, &(low as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &(high as u64).to_le_bytes());
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        Self(out)
+    }
+}
+
+impl fmt::Debug for ChannelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChannelKey(<redacted>)")
+    }
+}
+
+/// Encryption and MAC keys of one direction of a session.
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct DirectionKeys {
+    encryption: [u8; 32],
+    mac: [u8; 32],
+}
+
+impl DirectionKeys {
+    fn derive(session: &[u8; 32], sender: usize, recipient: usize) -> Self {
+        let subkey = |context: &str| {
+            let mut hasher = Blake3Adapter::new_derive_key(context);
+            digest::Update::update(&mut hasher, session);
+            digest::Update::update(&mut hasher, &(sender as u64).to_le_bytes());
+            digest::Update::update(&mut hasher, &(recipient as u64).to_le_bytes());
+            let mut out = [0u8; 32];
+            hasher.finalize_extended(&mut out);
+            out
+        };
+        Self {
+            encryption: subkey(ENCRYPTION_CONTEXT),
+            mac: subkey(MAC_CONTEXT),
+        }
+    }
+
+    fn apply_keystream(&self, counter: u64, data: &mut [u8]) {
+        let mut hasher = Blake3Adapter::new_keyed(&self.encryption);
+        digest::Update::update(&mut hasher, &counter.to_le_bytes());
+        let mut keystream = Zeroizing::new(vec![0u8; data.len()]);
+        hasher.finalize_extended(&mut keystream);
+        for (byte, k) in data.iter_mut().zip(keystream.iter()) {
+            *byte ^= k;
+        }
+    }
+
+    fn tag(&self, counter: u64, body: &[u8]) -> [u8; TAG_LENGTH] {
+        let mut hasher = Blake3Adapter::new_keyed(&self.mac);
+        digest::Update::update(&mut hasher, &counter.to_le_bytes());
+        digest::Update::update(&mut hasher, &(body.len() as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, body);
+        let mut out = [0u8; TAG_LENGTH];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+fn network_error(e: std::io::Error) -> CryptoError {
+    CryptoError::Network {
+        details: e.to_string(),
+    }
+}
+
+/// The peer's half of the handshake.
+struct Hello {
+    index: usize,
+    parameters: [u8; 32],
+    nonce: [u8; 32],
+}
+
+fn hello(local: usize, parameters: &[u8; 32], nonce: &[u8; 32]) -> [u8; HELLO_LENGTH] {
+    let mut out = [0u8; HELLO_LENGTH];
+    out[..4].copy_from_slice(MAGIC);
+    out[4] = TRANSPORT_VERSION;
+    out[5..13].copy_from_slice(&(local as u64).to_le_bytes());
+    out[13..45].copy_from_slice(parameters);
+    out[45..].copy_from_slice(nonce);
+    out
+}
+
+fn parse_hello(bytes: &[u8; HELLO_LENGTH]) -> CryptoResult<Hello> {
+    if &bytes[..4] != MAGIC || bytes[4] != TRANSPORT_VERSION {
+        return Err(CryptoError::Network {
+            details: format!("unsupported transport handshake (version {})", bytes[4]),
+        });
+    }
+    let mut index = [0u8; 8];
+    index.copy_from_slice(&bytes[5..13]);
+    let mut parameters = [0u8; 32];
+    parameters.copy_from_slice(&bytes[13..45]);
+    let mut nonce = [0u8; 32];
+    nonce.copy_from_slice(&bytes[45..]);
+    Ok(Hello {
+        index: u64::from_le_bytes(index) as usize,
+        parameters,
+        nonce,
+    })
+}
+
+/// Encrypted and authenticated channel between participants `local` and `peer`.
+pub struct SecureChannel<S> {
+    stream: S,
+    local: usize,
+    peer: usize,
+    outgoing: DirectionKeys,
+    incoming: DirectionKeys,
+    sent: u64,
+    received: u64,
+    /// Hex digest of the public handshake, the session messages are signed for.
+    session_id: String,
+    /// Local signing identity and the peer identities, with [`SecureChannel::with_identities`].
+    identities: Option<(Participant, Roster)>,
+}
+
+impl<S> fmt::Debug for SecureChannel<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecureChannel")
+            .field("local", &self.local)
+            .field("peer", &self.peer)
+            .field("sent", &self.sent)
+            .field("received", &self.received)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Read + Write> SecureChannel<S> {
+    /// Run the handshake with participant `peer` over `stream`.
+    ///
+    /// Returns `CryptoError::SecurityViolation` if the peer claims another index, runs with other
+    /// `parameters` or does not hold `key`, and `CryptoError::Network` on I/O or protocol errors.
+    pub fn establish(
+        stream: S,
+        local: usize,
+        peer: usize,
+        parameters: &PublicParameters,
+        key: &ChannelKey,
+    ) -> CryptoResult<Self> {
+        Self::handshake(stream, local, parameters, |claimed| {
+            if claimed == peer {
+                Ok(key.clone())
+            } else {
+                Err(CryptoError::SecurityViolation {
+                    details: format!("expected participant {}, peer claims {}", peer, claimed),
+                })
+            }
+        })
+    }
+
+    /// Handshake where the channel key is chosen from the peer's claimed index.
+    fn handshake(
+        mut stream: S,
+        local: usize,
+        parameters: &PublicParameters,
+        key_for: impl FnOnce(usize) -> CryptoResult<ChannelKey>,
+    ) -> CryptoResult<Self> {
+        let digest = parameters.digest();
+        let mut nonce = [0u8; 32];
+        crate::utils::fill_random(&mut nonce)?;
+        stream
+            .write_all(&hello(local, &digest, &nonce))
+            .and_then(|_| stream.flush())
+            .map_err(network_error)?;
+        let mut peer_hello = [0u8; HELLO_LENGTH];
+        stream.read_exact(&mut peer_hello).map_err(network_error)?;
+        let Hello {
+            index: peer,
+            parameters: peer_digest,
+            nonce: peer_nonce,
+        } = parse_hello(&peer_hello)?;
+        if peer == local {
+            return Err(CryptoError::SecurityViolation {
+                details: format!("peer claims our own index {}", local),
+            });
+        }
+        if peer_digest != digest {
+            reject(local, peer, "public parameters mismatch");
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "participant {} runs with different public parameters than {:?}",
+                    peer, parameters
+                ),
+            });
+        }
+        let key = key_for(peer).inspect_err(|_| reject(local, peer, "unknown peer"))?;
+
+        let ((low, low_nonce), (high, high_nonce)) = if local < peer {
+            ((local, nonce), (peer, peer_nonce))
+        } else {
+            ((peer, peer_nonce), (local, nonce))
+        };
+        let mut hasher = Blake3Adapter::new_keyed(&key.0);
+        digest::Update::update(&mut hasher, SESSION_CONTEXT.as_bytes());
+        digest::Update::update(&mut hasher, &digest);
+        digest::Update::update(&mut hasher, &(low as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &low_nonce);
+        digest::Update::update(&mut hasher, &(high as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &high_nonce);
+        let mut session = Zeroizing::new([0u8; 32]);
+        hasher.finalize_extended(session.as_mut());
+        let mut hasher = Blake3Adapter::new_derive_key(SESSION_ID_CONTEXT);
+        digest::Update::update(&mut hasher, &digest);
+        digest::Update::update(&mut hasher, &(low as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &low_nonce);
+        digest::Update::update(&mut hasher, &(high as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, &high_nonce);
+        let mut session_id = [0u8; 32];
+        hasher.finalize_extended(&mut session_id);
+
+        let mut channel = Self {
+            stream,
+            local,
+            peer,
+            outgoing: DirectionKeys::derive(&session, local, peer),
+            incoming: DirectionKeys::derive(&session, peer, local),
+            sent: 0,
+            received: 0,
+            session_id: hex::encode(session_id),
+            identities: None,
+        };
+        let confirmation = channel.outgoing.tag(u64::MAX, CONFIRM_LABEL);
+        channel
+            .stream
+            .write_all(&confirmation)
+            .and_then(|_| channel.stream.flush())
+            .map_err(network_error)?;
+        let mut peer_confirmation = [0u8; TAG_LENGTH];
+        channel
+            .stream
+            .read_exact(&mut peer_confirmation)
+            .map_err(network_error)?;
+        // blake3::Hash compares in constant time.
+        if blake3::Hash::from(peer_confirmation)
+            != blake3::Hash::from(channel.incoming.tag(u64::MAX, CONFIRM_LABEL))
+        {
+            reject(local, peer, "key confirmation failed");
+            return Err(CryptoError::SecurityViolation {
+                details: format!("participant {} failed key confirmation", peer),
+            });
+        }
+
+        structured_log!(
+            Level::Info,
+            EventCode::TransportChannelEstablished,
+            "secure channel established",
+            local = local,
+            peer = peer
+        );
+        Ok(channel)
+    }
+}
+
+impl<S> SecureChannel<S> {
+    pub fn local(&self) -> usize {
+        self.local
+    }
+
+    pub fn peer(&self) -> usize {
+        self.peer
+    }
+
+    /// Identifier of this connection both ends agree on: a digest of the indices, nonces and
+    /// parameters exchanged in the handshake. Messages are signed for it as their session.
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// The underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Sign every message sent as `local` and require every message received to be signed by the
+    /// peer's identity in `roster`.
+    ///
+    /// Both ends must enable it. Returns `CryptoError::Validation` if `local` is not this end's
+    /// index or `roster` has no identity for the peer.
+    pub fn with_identities(mut self, local: Participant, roster: Roster) -> CryptoResult<Self> {
+        if local.index() != self.local || roster.public_key(self.peer).is_none() {
+            return Err(CryptoError::Validation {
+                field: "identities".to_string(),
+                reason: format!(
+                    "need the identity of participant {} and the roster entry of {}",
+                    self.local, self.peer
+                ),
+            });
+        }
+        self.identities = Some((local, roster));
+        Ok(self)
+    }
+}
+
+impl<S: Write> SecureChannel<S> {
+    /// Encrypt, authenticate and write one message.
+    pub fn send(&mut self, message: &Message) -> CryptoResult<()> {
+        let body = match &self.identities {
+            Some((local, _)) => serde_json::to_vec(&local.sign(&self.session_id, message)?),
+            None => serde_json::to_vec(message),
+        };
+        let mut body = Zeroizing::new(body.map_err(|e| CryptoError::Serialization {
+            details: e.to_string(),
+        })?);
+        if body.len() > MAX_FRAME_LENGTH {
+            return Err(CryptoError::ResourceExhaustion {
+                resource: format!("transport frame of {} bytes", body.len()),
+            });
+        }
+        self.outgoing.apply_keystream(self.sent, &mut body);
+        let tag = self.outgoing.tag(self.sent, &body);
+        self.stream
+            .write_all(&(body.len() as u32).to_be_bytes())
+            .and_then(|_| self.stream.write_all(&body))
+            .and_then(|_| self.stream.write_all(&tag))
+            .and_then(|_| self.stream.flush())
+            .map_err(network_error)?;
+        self.sent += 1;
+        Ok(())
+    }
+}
+
+impl<S: Read> SecureChannel<S> {
+    /// Read, authenticate and decrypt the next message.
+    ///
+    /// A frame that fails authentication, or once [`Self::with_identities`] is set a message not
+    /// signed by the peer, returns `CryptoError::SecurityViolation`; the channel should then be
+    /// dropped.
+    pub fn recv(&mut self) -> CryptoResult<Message> {
+        let mut length = [0u8; 4];
+        self.stream.read_exact(&mut length).map_err(network_error)?;
+        let length = u32::from_be_bytes(length) as usize;
+        if length > MAX_FRAME_LENGTH {
+            return Err(CryptoError::ResourceExhaustion {
+                resource: format!("transport frame of {} bytes", length),
+            });
+        }
+        let mut body = Zeroizing::new(vec![0u8; length]);
+        let mut tag = [0u8; TAG_LENGTH];
+        self.stream
+            .read_exact(&mut body)
+            .and_then(|_| self.stream.read_exact(&mut tag))
+            .map_err(network_error)?;
+        if blake3::Hash::from(tag) != blake3::Hash::from(self.incoming.tag(self.received, &body)) {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "frame {} from participant {} failed authentication",
+                    self.received, self.peer
+                ),
+            });
+        }
+        self.incoming.apply_keystream(self.received, &mut body);
+        self.received += 1;
+        let Some((_, roster)) = &self.identities else {
+            return parse(&body);
+        };
+        let signed: Signed<Message> =
+            serde_json::from_slice(&body).map_err(|_| CryptoError::SecurityViolation {
+                details: format!("participant {} sent an unsigned message", self.peer),
+            })?;
+        signed.verify_from(roster, &self.session_id, self.peer)?;
+        Ok(signed.payload)
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(body: &[u8]) -> CryptoResult<T> {
+    serde_json::from_slice(body).map_err(|e| CryptoError::Serialization {
+        details: e.to_string(),
+    })
+}
+
+fn reject(local: usize, peer: usize, reason: &str) {
+    structured_log!(
+        Level::Warn,
+        EventCode::TransportChannelRejected,
+        "secure channel rejected",
+        local = local,
+        peer = peer,
+        reason = reason
+    );
+}
+
+/// Connect to participant `peer` at `addr`; `timeout` bounds connecting and every read and write.
+pub fn connect(
+    addr: impl ToSocketAddrs,
+    local: usize,
+    peer: usize,
+    parameters: &PublicParameters,
+    key: &ChannelKey,
+    timeout: Duration,
+) -> CryptoResult<SecureChannel<TcpStream>> {
+    let mut last_error = None;
+    for addr in addr.to_socket_addrs().map_err(network_error)? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => {
+                configure(&stream, timeout)?;
+                return SecureChannel::establish(stream, local, peer, parameters, key);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(CryptoError::Network {
+        details: last_error
+            .map_or_else(|| "no address to connect to".to_string(), |e| e.to_string()),
+    })
+}
+
+/// Accept one connection on `listener` and run the handshake.
+///
+/// `key_for` returns the channel key for the index the peer claims, or `None` to refuse it. Peers
+/// running with other `parameters` are refused.
+pub fn accept(
+    listener: &TcpListener,
+    local: usize,
+    parameters: &PublicParameters,
+    timeout: Duration,
+    key_for: impl FnOnce(usize) -> Option<ChannelKey>,
+) -> CryptoResult<SecureChannel<TcpStream>> {
+    let (stream, _) = listener.accept().map_err(network_error)?;
+    configure(&stream, timeout)?;
+    SecureChannel::handshake(stream, local, parameters, |peer| {
+        key_for(peer).ok_or_else(|| CryptoError::SecurityViolation {
+            details: format!("no channel key for participant {}", peer),
+        })
+    })
+}
+
+fn configure(stream: &TcpStream, timeout: Duration) -> CryptoResult<()> {
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|_| stream.set_write_timeout(Some(timeout)))
+        .and_then(|_| stream.set_nodelay(true))
+        .map_err(network_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+    use curve25519_dalek::Scalar;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_tcp_channel_roundtrip() {
+        let master = [7u8; 32];
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let mut channel = accept(
+                &listener,
+                1,
+                &PublicParameters::default(),
+                TIMEOUT,
+                |peer| Some(ChannelKey::derive(&master, 1, peer)),
+            )
+            .unwrap();
+            assert_eq!(channel.peer(), 2);
+            let Message::RecoveryRequest(request) = channel.recv().unwrap() else {
+                panic!("expected a recovery request");
+            };
+            let share = generate_key_shares(Scalar::from(9u64), 2, 2).unwrap()[0].clone();
+            channel
+                .send(&Message::RecoveryResponse {
+                    operation_id: request.context.operation_id,
+                    share,
+                })
+                .unwrap();
+        });
+
+        let mut channel = connect(
+            addr,
+            2,
+            1,
+            &PublicParameters::default(),
+            &ChannelKey::derive(&master, 2, 1),
+            TIMEOUT,
+        )
+        .unwrap();
+        channel
+            .send(&Message::RecoveryRequest(RecoveryRequest {
+                context: OperationContext::new("k", 0, "op-1"),
+                requester: 2,
+            }))
+            .unwrap();
+        match channel.recv().unwrap() {
+            Message::RecoveryResponse {
+                operation_id,
+                share,
+            } => {
+                assert_eq!(operation_id, "op-1");
+                assert_eq!(share.index, 1);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            accept(&listener, 1, &PublicParameters::default(), TIMEOUT, |_| {
+                Some(ChannelKey::from_bytes([1; 32]))
+            })
+            .err()
+        });
+        let client = connect(
+            addr,
+            2,
+            1,
+            &PublicParameters::default(),
+            &ChannelKey::from_bytes([2; 32]),
+            TIMEOUT,
+        );
+        assert!(matches!(client, Err(CryptoError::SecurityViolation { .. })));
+        assert!(server.join().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_parameter_mismatch_rejected() {
+        let key = ChannelKey::from_bytes([3; 32]);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_key = key.clone();
+        let server = std::thread::spawn(move || {
+            accept(&listener, 1, &PublicParameters::default(), TIMEOUT, |_| {
+                Some(server_key)
+            })
+            .err()
+        });
+        let maximum = PublicParameters::new(
+            crate::serialization::Ciphersuite::Ristretto255Blake3,
+            crate::hash_adapter::SecurityLevel::Maximum,
+        );
+        let client = connect(addr, 2, 1, &maximum, &key, TIMEOUT);
+        assert!(matches!(client, Err(CryptoError::SecurityViolation { .. })));
+        assert!(matches!(
+            server.join().unwrap(),
+            Some(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    /// End `local` of a session with `peer` over `stream`.
+    fn endpoint<S>(stream: S, local: usize, peer: usize, session: &[u8; 32]) -> SecureChannel<S> {
+        SecureChannel {
+            stream,
+            local,
+            peer,
+            outgoing: DirectionKeys::derive(session, local, peer),
+            incoming: DirectionKeys::derive(session, peer, local),
+            sent: 0,
+            received: 0,
+            session_id: hex::encode(session),
+            identities: None,
+        }
+    }
+
+    /// The sending end of a session from 2 to 1 over an in-memory buffer.
+    fn session_pair() -> (SecureChannel<Vec<u8>>, [u8; 32]) {
+        let session = [5u8; 32];
+        (endpoint(Vec::new(), 2, 1, &session), session)
+    }
+
+    fn receiver(session: &[u8; 32], frames: Vec<u8>) -> SecureChannel<std::io::Cursor<Vec<u8>>> {
+        endpoint(std::io::Cursor::new(frames), 1, 2, session)
+    }
+
+    /// Complaint of participant 2 against 1, signed for DKG session `"dkg"`.
+    fn complaint() -> Message {
+        let two = Participant::from_seed(2, &[12; 32]).unwrap();
+        let complaint = Complaint {
+            accused: 1,
+            complainant: 2,
+        };
+        Message::DkgComplaint(two.sign("dkg", complaint).unwrap())
+    }
+
+    #[test]
+    fn test_frame_tampering_and_replay_rejected() {
+        let (mut sender, session) = session_pair();
+        sender.send(&complaint()).unwrap();
+        let frame = sender.into_inner();
+
+        assert!(matches!(
+            receiver(&session, frame.clone()).recv().unwrap(),
+            Message::DkgComplaint(signed) if signed.payload.accused == 1
+        ));
+
+        let mut tampered = frame.clone();
+        tampered[6] ^= 1;
+        assert!(matches!(
+            receiver(&session, tampered).recv(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let replayed = [frame.clone(), frame].concat();
+        let mut channel = receiver(&session, replayed);
+        channel.recv().unwrap();
+        assert!(matches!(
+            channel.recv(),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_identities_required() {
+        let one = Participant::from_seed(1, &[11; 32]).unwrap();
+        let two = Participant::from_seed(2, &[12; 32]).unwrap();
+        let rogue = Participant::from_seed(2, &[13; 32]).unwrap();
+        let roster = Roster::new(&[one.identity(), two.identity()]).unwrap();
+        let complaint = complaint();
+        let signed_by = |identity: &Participant| {
+            let (sender, session) = session_pair();
+            let mut sender = sender
+                .with_identities(identity.clone(), roster.clone())
+                .unwrap();
+            sender.send(&complaint).unwrap();
+            (sender.into_inner(), session)
+        };
+        let receive = |session: &[u8; 32], frame: Vec<u8>| {
+            receiver(session, frame)
+                .with_identities(one.clone(), roster.clone())
+                .unwrap()
+                .recv()
+        };
+
+        let (frame, session) = signed_by(&two);
+        assert!(matches!(
+            receive(&session, frame),
+            Ok(Message::DkgComplaint(signed)) if signed.payload.complainant == 2
+        ));
+        let (frame, session) = signed_by(&rogue);
+        assert!(matches!(
+            receive(&session, frame),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let (mut unsigned, session) = session_pair();
+        unsigned.send(&complaint).unwrap();
+        assert!(matches!(
+            receive(&session, unsigned.into_inner()),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        // Signed for another connection, then re-encrypted with a stolen channel key.
+        let (sender, session) = session_pair();
+        let mut sender = sender.with_identities(two.clone(), roster.clone()).unwrap();
+        sender.session_id = "another connection".to_string();
+        sender.send(&complaint).unwrap();
+        assert!(matches!(
+            receive(&session, sender.into_inner()),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let (channel, _) = session_pair();
+        assert!(channel
+            .with_identities(one.clone(), roster.clone())
+            .is_err());
+    }
+
+    #[test]
+    fn test_dkg_over_channels() {
+        use crate::dkg::DkgParticipant;
+
+        const DKG: &str = "dkg-over-transport";
+        let identities: Vec<Participant> = (1..=3)
+            .map(|i| Participant::from_seed(i, &[20 + i as u8; 32]).unwrap())
+            .collect();
+        let roster =
+            Roster::new(&identities.iter().map(|p| p.identity()).collect::<Vec<_>>()).unwrap();
+        // One message from `from` to `to` over a channel signed with their identities.
+        let deliver = |from: usize, to: usize, message: &Message| {
+            let session = [(16 * from + to) as u8; 32];
+            let mut sender = endpoint(Vec::new(), from, to, &session)
+                .with_identities(identities[from - 1].clone(), roster.clone())
+                .unwrap();
+            sender.send(message).unwrap();
+            endpoint(
+                std::io::Cursor::new(sender.into_inner()),
+                to,
+                from,
+                &session,
+            )
+            .with_identities(identities[to - 1].clone(), roster.clone())
+            .unwrap()
+            .recv()
+            .unwrap()
+        };
+        let mut parties: Vec<DkgParticipant> = (1..=3)
+            .map(|i| DkgParticipant::new(i, 2, 3, DKG, roster.clone()).unwrap())
+            .collect();
+
+        let broadcasts: Vec<Signed<Round1Broadcast>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .map(|(p, id)| id.sign(DKG, p.round1().unwrap()).unwrap())
+            .collect();
+        // Participant 2 cannot pass off its own signature on dealer 1's broadcast.
+        let forged = Message::DkgRound1(
+            identities[1]
+                .sign(DKG, broadcasts[0].payload.clone())
+                .unwrap(),
+        );
+        let Message::DkgRound1(forged) = deliver(2, 3, &forged) else {
+            panic!("expected a round-one broadcast");
+        };
+        assert!(matches!(
+            parties[2].receive_round1(forged),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        for broadcast in &broadcasts {
+            let from = broadcast.payload.sender;
+            let message = Message::DkgRound1(broadcast.clone());
+            for to in (1..=3).filter(|&to| to != from) {
+                // Dealer 1's broadcast reaches 3 relayed by 2, still signed by 1.
+                let received = if (from, to) == (1, 3) {
+                    deliver(2, 3, &deliver(1, 2, &message))
+                } else {
+                    deliver(from, to, &message)
+                };
+                let Message::DkgRound1(signed) = received else {
+                    panic!("expected a round-one broadcast");
+                };
+                parties[to - 1].receive_round1(signed).unwrap();
+            }
+        }
+
+        let shares: Vec<Signed<Round2Share>> = parties
+            .iter_mut()
+            .zip(&identities)
+            .flat_map(|(p, id)| {
+                p.round2()
+                    .unwrap()
+                    .into_iter()
+                    .map(|share| id.sign(DKG, share).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        for share in shares {
+            let (from, to) = (share.payload.sender, share.payload.recipient);
+            let Message::DkgRound2(signed) = deliver(from, to, &Message::DkgRound2(share)) else {
+                panic!("expected a round-two slice");
+            };
+            assert!(parties[to - 1].receive_round2(signed).unwrap().is_none());
+        }
+        let outputs: Vec<_> = parties.iter_mut().map(|p| p.finalize().unwrap()).collect();
+        for output in &outputs {
+            assert_eq!(output.qualified, [1, 2, 3]);
+            assert_eq!(output.group_public_key, outputs[0].group_public_key);
+        }
+    }
+}
@@ -3,23 +3,101 @@
 //! Encapsulates general-purpose helper functions and global constants, including random number generation, fast power calculations, and the definition of global generators.
 
 use std::sync::LazyLock;
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE};
+use curve25519_dalek::ristretto::RistrettoBasepointTable;
 use curve25519_dalek::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
+#[cfg(feature = "dealer")]
 use rand::rngs::OsRng;
-use rand::RngCore;
+#[cfg(feature = "dealer")]
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "dealer")]
+use zeroize::Zeroize;
+#[cfg(feature = "dealer")]
+use crate::error::{CryptoError, CryptoResult};
 use crate::hash_adapter::Blake3Adapter;
 
 /// Returns a new instance of the OS random number generator.
+#[cfg(feature = "dealer")]
 pub fn new_rng() -> OsRng {
     OsRng
 }
 
+/// Fill `buf` from the OS generator, reporting failure as `CryptoError::InsufficientEntropy`.
+#[cfg(feature = "dealer")]
+pub(crate) fn fill_random(buf: &mut [u8]) -> CryptoResult<()> {
+    new_rng()
+        .try_fill_bytes(buf)
+        .map_err(|_| CryptoError::InsufficientEntropy {
+            required: (buf.len() * 8) as u32,
+            actual: 0,
+        })
+}
+
+/// Monotonic timer for performance metrics, also taking the operation counts at its start.
+///
+/// `wasm32-unknown-unknown` has no clock in `std` (`Instant::now` panics there), so on that target
+/// the stopwatch always reads zero.
+#[cfg(feature = "dealer")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    started: std::time::Instant,
+    counts: Option<crate::op_counts::OperationCounts>,
+}
+
+#[cfg(feature = "dealer")]
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Stopwatch {
+            #[cfg(not(target_arch = "wasm32"))]
+            started: std::time::Instant::now(),
+            counts: crate::op_counts::OperationCounts::current(),
+        }
+    }
+
+    /// Nanoseconds since [`Stopwatch::start`].
+    pub(crate) fn elapsed_ns(&self) -> u64 {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.started.elapsed().as_nanos() as u64;
+        #[cfg(target_arch = "wasm32")]
+        return 0;
+    }
+
+    /// Operation counts since [`Stopwatch::start`], with the `op-counters` feature.
+    pub(crate) fn counts(&self) -> Option<crate::op_counts::OperationCounts> {
+        let start = self.counts?;
+        Some(crate::op_counts::OperationCounts::current()?.since(&start))
+    }
+}
+
+/// Bits of entropy drawn for every random scalar.
+#[cfg(feature = "dealer")]
+const SCALAR_ENTROPY_BITS: u32 = 512;
+
 /// Generate a random scalar, using 64-byte entropy and Blake3 hashing to ensure that the scalar is uniformly distributed.
-pub fn random_scalar(rng: &mut OsRng) -> Scalar {
+///
+/// This is the single entry point for randomness in the crate. A failing generator is reported as
+/// `CryptoError::InsufficientEntropy` instead of panicking.
+#[cfg(feature = "dealer")]
+pub fn random_scalar<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> CryptoResult<Scalar> {
     let mut buf = [0u8; 64];
-    rng.fill_bytes(&mut buf);
-    Scalar::hash_from_bytes::<crate::hash_adapter::Blake3Adapter>(&buf)
+    if let Err(source) = rng.try_fill_bytes(&mut buf) {
+        let error = CryptoError::InsufficientEntropy {
+            required: SCALAR_ENTROPY_BITS,
+            actual: 0,
+        };
+        crate::events::emit(
+            log::Level::Error,
+            error.code(),
+            &[("source", source.to_string())],
+            "random generator failure",
+        );
+        return Err(error);
+    }
+    let scalar = Scalar::hash_from_bytes::<crate::hash_adapter::Blake3Adapter>(&buf);
+    buf.zeroize();
+    Ok(scalar)
 }
 
 /// Distributed random number generation: simulate multiple participants to generate random numbers, and finally accumulate them
@@ -48,3 +126,18 @@ pub static ANOTHER_POINT: LazyLock<RistrettoPoint> = LazyLock::new(|| {
         RISTRETTO_BASEPOINT_POINT.compress().as_bytes(),
     )
 });
+
+/// Precomputed multiples of [`ANOTHER_POINT`] for fixed-base multiplication.
+pub static ANOTHER_POINT_TABLE: LazyLock<RistrettoBasepointTable> =
+    LazyLock::new(|| RistrettoBasepointTable::create(&ANOTHER_POINT));
+
+/// Pedersen commitment `value·G + blinding·H`, using the precomputed tables of both generators.
+///
+/// Both scalars are secret wherever slices are committed (dealing, refresh, threshold changes),
+/// so this stays constant-time at every [`SecurityLevel`](crate::SecurityLevel); two fixed-base
+/// table lookups cost about half of two variable-base multiplications and less than a two-term
+/// multiscalar multiplication.
+pub fn pedersen_commit(value: &Scalar, blinding: &Scalar) -> RistrettoPoint {
+    crate::op_counts::count(crate::op_counts::Op::PointMul, 2);
+    RISTRETTO_BASEPOINT_TABLE * value + &*ANOTHER_POINT_TABLE * blinding
+}
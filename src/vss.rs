@@ -1,23 +1,1031 @@
 //! **vss module**
 //!
 //! Implements Verifiable Secret Sharing (VSS) for slice validity verification.
+//!
+//! A dealing made with [`crate::sharing::generate_key_shares_with_commitments`] publishes
+//! Pedersen commitments to every coefficient of the sharing and blinding polynomials
+//! ([`VssCommitments`]). Each receiver can then check that its slice lies on the committed
+//! polynomial, which detects a dealer handing out slices of different polynomials.
+use crate::error::{CryptoError, CryptoResult, VerifyError};
+use crate::group::{encoding, CurveGroup, Ristretto255};
+use crate::hash_adapter::Blake3Adapter;
+use crate::identity::{Roster, Signed, SignedPayload};
+use crate::op_counts::{count, Op};
 use crate::proof;
-use crate::sharing::ShareData;
+use crate::pvss::{DleqProof, DleqStatement};
+use crate::sharing::{MultiShareData, ShareData};
+use crate::utils::ANOTHER_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul};
+
+/// Version byte of the canonical binary encoding of [`VssCommitments`].
+pub const COMMITMENTS_ENCODING_VERSION: u8 = 1;
+
+/// Domain separation context for [`VssCommitments::root`].
+const COMMITMENT_ROOT_CONTEXT: &str = "zk-thresh-pro 2024 vss commitment root v1";
+
+const REFRESH_UPDATE_CONTEXT: &str = "zk-thresh-pro 2024 refresh update v1";
+const SHARE_LINK_CONTEXT: &str = "zk-thresh-pro 2024 refresh share link v1";
+
+/// Pedersen-VSS commitments `C_j = a_j·G + b_j·H` to the coefficients of the sharing polynomial
+/// `f(x) = Σ a_j x^j` (with `a_0` the secret) and the blinding polynomial `g(x) = Σ b_j x^j`.
+///
+/// A slice `(i, f(i), g(i))` belongs to the dealing iff `f(i)·G + g(i)·H = Σ_j i^j · C_j`.
+/// The commitments are in ristretto255 unless another [`CurveGroup`] is named.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct VssCommitments<G: CurveGroup = Ristretto255> {
+    /// `C_0, ..., C_{t-1}`, lowest degree first.
+    #[serde(
+        serialize_with = "encoding::serialize_elements::<G, _>",
+        deserialize_with = "encoding::deserialize_elements::<G, _>"
+    )]
+    pub coefficients: Vec<G::Element>,
+}
+
+impl VssCommitments {
+    /// Canonical binary encoding: the version byte, the coefficient count as a little-endian
+    /// `u32`, then each compressed point (32 bytes), lowest degree first.
+    ///
+    /// Two equal commitment vectors always encode to the same bytes, so the encoding can be
+    /// hashed, signed or compared byte-wise.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5 + 32 * self.coefficients.len());
+        bytes.push(COMMITMENTS_ENCODING_VERSION);
+        bytes.extend_from_slice(&(self.coefficients.len() as u32).to_le_bytes());
+        for point in &self.coefficients {
+            bytes.extend_from_slice(point.compress().as_bytes());
+        }
+        bytes
+    }
+
+    /// Decode [`Self::to_canonical_bytes`], rejecting unknown versions, length mismatches,
+    /// non-canonical point encodings and the identity point.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        let invalid = |details: &str| CryptoError::Serialization {
+            details: details.to_string(),
+        };
+        let (&version, rest) = bytes.split_first().ok_or_else(|| invalid("empty input"))?;
+        if version != COMMITMENTS_ENCODING_VERSION {
+            return Err(invalid("unsupported commitment encoding version"));
+        }
+        if rest.len() < 4 {
+            return Err(invalid("truncated coefficient count"));
+        }
+        let (count, points) = rest.split_at(4);
+        let count = u32::from_le_bytes(count.try_into().expect("4-byte count")) as usize;
+        if points.len() != count.saturating_mul(32) {
+            return Err(invalid("coefficient count does not match length"));
+        }
+        let coefficients = points
+            .chunks_exact(32)
+            .map(|chunk| {
+                let chunk: &[u8; 32] = chunk
+                    .try_into()
+                    .map_err(|_| invalid("invalid point length"))?;
+                match crate::serialization::canonical_point(chunk) {
+                    Some(point) if point != RistrettoPoint::identity() => Ok(point),
+                    Some(_) => Err(invalid("identity point in commitments")),
+                    None => Err(invalid("non-canonical compressed point")),
+                }
+            })
+            .collect::<CryptoResult<_>>()?;
+        Ok(Self { coefficients })
+    }
+
+    /// Commitment root: domain-separated BLAKE3 digest of [`Self::to_canonical_bytes`].
+    ///
+    /// The dealer publishes the root (or its hex form, the commitment fingerprint) out of band,
+    /// so custodians can check that the commitment vector they were given is the published one.
+    pub fn root(&self) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(COMMITMENT_ROOT_CONTEXT);
+        digest::Update::update(&mut hasher, &self.to_canonical_bytes());
+        let mut root = [0u8; 32];
+        hasher.finalize_extended(&mut root);
+        root
+    }
+}
+
+impl<G: CurveGroup> VssCommitments<G> {
+    /// Threshold of the dealing (number of committed coefficients).
+    pub fn threshold(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// The commitment `Σ_j index^j · C_j` that the slice at `index` must carry.
+    pub fn expected_commitment(&self, index: usize) -> G::Element {
+        let x = G::scalar_from_u64(index as u64);
+        let mut power = G::scalar_from_u64(1);
+        let powers: Vec<G::Scalar> = (0..self.coefficients.len())
+            .map(|_| {
+                let current = power;
+                power = power * x;
+                current
+            })
+            .collect();
+        count(Op::ScalarMul, powers.len());
+        count(Op::PointMul, powers.len());
+        G::vartime_multiscalar_mul(&powers, &self.coefficients)
+    }
+
+    /// Verify one slice against the committed polynomials.
+    ///
+    /// Checks, in order, that the slice opens its commitment (`VerifyError::OpeningMismatch`),
+    /// that the commitment lies on the committed polynomial (`VerifyError::CommitmentMismatch`),
+    /// and that its proof is valid. An empty commitment vector or index 0 is
+    /// `VerifyError::WrongContext`.
+    pub fn verify_share(&self, share: &ShareData<G>) -> Result<(), VerifyError> {
+        let index = share.index;
+        if self.coefficients.is_empty() {
+            return Err(VerifyError::WrongContext {
+                index,
+                details: "commitment vector is empty".to_string(),
+            });
+        }
+        if index == 0 {
+            return Err(VerifyError::WrongContext {
+                index,
+                details: "index 0 is reserved for the secret".to_string(),
+            });
+        }
+        count(Op::PointMul, 2);
+        let opening = G::generator() * share.share + G::blinding_generator() * share.random;
+        if opening != share.commitment {
+            return Err(VerifyError::OpeningMismatch { index });
+        }
+        if share.commitment != self.expected_commitment(index) {
+            return Err(VerifyError::CommitmentMismatch { index });
+        }
+        proof::verify_group_proof(&share.proof, share.commitment, index)
+    }
+}
+
+/// Verify one slice against the commitments of every epoch of a key, oldest first.
+///
+/// The last entry of `history` is the current epoch. A slice that fails against it but matches an
+/// earlier epoch is reported as `VerifyError::StaleEpoch` (a holder that missed a refresh);
+/// otherwise the failure against the current commitments is returned.
+pub fn verify_share_epochs(
+    share: &ShareData,
+    history: &[(u64, &VssCommitments)],
+) -> Result<(), VerifyError> {
+    let Some(((current_epoch, current), earlier)) = history.split_last() else {
+        return Err(VerifyError::WrongContext {
+            index: share.index,
+            details: "no commitments to verify against".to_string(),
+        });
+    };
+    let error = match current.verify_share(share) {
+        Ok(()) => return Ok(()),
+        Err(e) => e,
+    };
+    if matches!(error, VerifyError::CommitmentMismatch { .. }) {
+        if let Some((epoch, _)) = earlier
+            .iter()
+            .rev()
+            .find(|(_, commitments)| commitments.verify_share(share).is_ok())
+        {
+            return Err(VerifyError::StaleEpoch {
+                index: share.index,
+                epoch: *epoch,
+                current_epoch: *current_epoch,
+            });
+        }
+    }
+    Err(error)
+}
+
+/// Parse a slice from its JSON form, naming the first field that is not canonically encoded.
+///
+/// Scalars must be 32-byte canonical encodings and points valid, non-identity compressed
+/// Ristretto points, as in [`crate::format`]. Returns `VerifyError::NonCanonicalEncoding`
+/// otherwise, with the field path (e.g. `proof.challenge`).
+pub fn parse_share(json: &[u8]) -> Result<ShareData, VerifyError> {
+    let invalid = |field: &str, reason: &str| VerifyError::NonCanonicalEncoding {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    };
+    let value: serde_json::Value =
+        serde_json::from_slice(json).map_err(|e| invalid("share", &e.to_string()))?;
+
+    let scalars = [
+        "share",
+        "random",
+        "proof.response_share",
+        "proof.response_random",
+        "proof.challenge",
+    ];
+    let points = ["commitment", "proof.proof_ristretto_point"];
+    for path in scalars.iter().chain(&points) {
+        let encoded = path
+            .split('.')
+            .try_fold(&value, |v, key| v.get(key))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| invalid(path, "missing or not a string"))?;
+        if !crate::serialization::is_lower_hex(encoded) {
+            return Err(invalid(path, "expected lowercase hex"));
+        }
+        let mut bytes = zeroize::Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(encoded, bytes.as_mut())
+            .map_err(|_| invalid(path, "expected 32 hex-encoded bytes"))?;
+        if scalars.contains(path) {
+            if Option::<Scalar>::from(Scalar::from_canonical_bytes(*bytes)).is_none() {
+                return Err(invalid(path, "scalar is not reduced"));
+            }
+        } else {
+            match crate::serialization::canonical_point(&bytes) {
+                Some(point) if point != RistrettoPoint::identity() => {}
+                Some(_) => return Err(invalid(path, "identity point")),
+                None => return Err(invalid(path, "not a canonical compressed point")),
+            }
+        }
+    }
+    serde_json::from_value(value).map_err(|e| invalid("share", &e.to_string()))
+}
 
 /// Verify the validity of all splits (including promises and proofs).
 ///
+/// Each slice is checked in isolation; this does not show that the slices come from the same
+/// polynomial. Use [`verify_share_validity_with_commitments`] when the dealer published
+/// [`VssCommitments`].
+///
+/// # Parameters
+///
+/// - `shares`: collection of slices.
+///
+/// # Return value
+///
+/// Returns `Ok(())` if all slices are valid, otherwise the failure of the first invalid slice.
+pub fn verify_share_validity(shares: &[ShareData]) -> Result<(), VerifyError> {
+    for share in shares {
+        proof::verify_proof(&share.proof, share.commitment, share.index)?;
+    }
+    Ok(())
+}
+
+/// Slices per multiscalar multiplication in [`verify_share_validity_batch`].
+#[cfg(feature = "dealer")]
+const BATCH_CHUNK: usize = 64;
+
+/// Batch form of [`verify_share_validity`] for checking hundreds of slices.
+///
+/// The slices are split into chunks of 64, each verified with [`proof::verify_proofs_batch`]
+/// (one multiscalar multiplication per chunk) on the rayon pool.
+///
+/// # Return value
+///
+/// Same as [`verify_share_validity`]: `Ok(())` if every proof is valid, otherwise the error of
+/// the first invalid slice.
+#[cfg(feature = "dealer")]
+pub fn verify_share_validity_batch(shares: &[ShareData]) -> Result<(), VerifyError> {
+    use rayon::prelude::*;
+    let results: Vec<_> = shares.par_chunks(BATCH_CHUNK).map(verify_chunk).collect();
+    results.into_iter().collect()
+}
+
+/// [`verify_share_validity_batch`] stopping with `CryptoError::Timeout` once `deadline` passes.
+///
+/// The deadline is checked before each chunk of 64 slices; an invalid slice is reported as by
+/// `From<VerifyError> for CryptoError`.
+#[cfg(feature = "dealer")]
+pub fn verify_share_validity_batch_until(
+    shares: &[ShareData],
+    deadline: &crate::deadline::Deadline,
+) -> crate::error::CryptoResult<()> {
+    use rayon::prelude::*;
+    let results: Vec<_> = shares
+        .par_chunks(BATCH_CHUNK)
+        .map(|chunk| {
+            deadline.check("batch_verification")?;
+            Ok(verify_chunk(chunk)?)
+        })
+        .collect();
+    results.into_iter().collect()
+}
+
+/// One multiscalar multiplication over the proofs of `chunk`.
+#[cfg(feature = "dealer")]
+fn verify_chunk(chunk: &[ShareData]) -> Result<(), VerifyError> {
+    let statements: Vec<_> = chunk
+        .iter()
+        .map(|share| (&share.proof, share.commitment, share.index))
+        .collect();
+    proof::verify_proofs_batch(&statements)
+}
+
+/// Differences between two commitment vectors of the same secret, e.g. before and after a
+/// refresh or reshare.
+///
+/// A refresh is permitted to change every coefficient except `C_0` (which commits to the secret)
+/// and must keep the threshold; a reshare may also change the threshold.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommitmentDiff {
+    pub old_threshold: usize,
+    pub new_threshold: usize,
+    /// Positions `j` whose `C_j` differs, including positions present in only one vector.
+    pub changed_coefficients: Vec<usize>,
+    /// Coordinate-wise `new - old`; the identity where a coefficient is unchanged.
+    #[serde(skip)]
+    delta: Vec<RistrettoPoint>,
+}
+
+impl CommitmentDiff {
+    /// Whether `C_0`, the commitment to the secret, changed.
+    pub fn constant_term_changed(&self) -> bool {
+        self.changed_coefficients.first() == Some(&0)
+    }
+
+    /// Whether the change is a valid proactive refresh: same threshold and unchanged `C_0`.
+    pub fn is_permitted_refresh(&self) -> bool {
+        self.old_threshold == self.new_threshold && !self.constant_term_changed()
+    }
+
+    /// Whether the expected commitment of the slice at `index` differs between the two vectors.
+    pub fn share_changed(&self, index: usize) -> bool {
+        let delta: VssCommitments = VssCommitments {
+            coefficients: self.delta.clone(),
+        };
+        delta.expected_commitment(index) != RistrettoPoint::identity()
+    }
+
+    /// The slice indices among `indices` whose expected commitment changed.
+    pub fn changed_shares(&self, indices: &[usize]) -> Vec<usize> {
+        indices
+            .iter()
+            .copied()
+            .filter(|&index| self.share_changed(index))
+            .collect()
+    }
+}
+
+/// Compare two commitment vectors coefficient by coefficient.
+pub fn diff(old: &VssCommitments, new: &VssCommitments) -> CommitmentDiff {
+    let len = old.coefficients.len().max(new.coefficients.len());
+    let identity = RistrettoPoint::identity();
+    let delta: Vec<RistrettoPoint> = (0..len)
+        .map(|j| {
+            let before = old.coefficients.get(j).copied().unwrap_or(identity);
+            let after = new.coefficients.get(j).copied().unwrap_or(identity);
+            after - before
+        })
+        .collect();
+    let changed_coefficients = delta
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| **d != identity)
+        .map(|(j, _)| j)
+        .collect();
+
+    CommitmentDiff {
+        old_threshold: old.threshold(),
+        new_threshold: new.threshold(),
+        changed_coefficients,
+        delta,
+    }
+}
+
+/// Published record of a proactive refresh ([`crate::sharing::refresh_shares`]).
+///
+/// A refresh adds update polynomials `δ(x)` to the slices and `ε(x)` to the blinding randoms,
+/// both with a zero constant term. The dealer publishes their coefficient commitments
+/// `D_j = δ_j·G + ε_j·H` next to the new commitments `C'_j = C_j + D_j`. [`RefreshRecord::verify`]
+/// checks that relation against the old dealing; with `D_0` the identity, `C'_0 = C_0` shows
+/// `δ(0)·G + ε(0)·H = 0`, so (by the binding property) the refresh kept the committed secret.
+/// Holders then check their new slices against `commitments` as usual.
+///
+/// Published through parties that could alter it, the record travels [`Signed`] by the
+/// participant that ran the refresh; holders check it with [`RefreshRecord::verify_signed`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefreshRecord {
+    /// [`VssCommitments::root`] of the dealing before the refresh.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub old_root: Vec<u8>,
+    /// `D_1, ..., D_{t-1}`; `D_0` is the identity and not stored.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_vec_helpers")]
+    pub update: Vec<RistrettoPoint>,
+    /// Commitments after the refresh.
+    pub commitments: VssCommitments,
+}
+
+impl SignedPayload for RefreshRecord {
+    const DOMAIN: &'static str = "vss refresh record";
+}
+
+impl RefreshRecord {
+    /// Check that the refresh continues `old`: the record names `old` by its root, keeps the
+    /// threshold, and every refreshed commitment is the old one plus the update.
+    ///
+    /// Returns `VerifyError::WrongContext` if the record belongs to another dealing and
+    /// `VerifyError::RefreshMismatch` for the first coefficient that breaks continuity.
+    pub fn verify(&self, old: &VssCommitments) -> Result<(), VerifyError> {
+        if self.old_root.as_slice() != old.root() {
+            return Err(VerifyError::WrongContext {
+                index: 0,
+                details: "refresh record is for another dealing".to_string(),
+            });
+        }
+        let len = old
+            .threshold()
+            .max(self.commitments.threshold())
+            .max(self.update.len() + 1);
+        for j in 0..len {
+            let update = match j {
+                0 => Some(RistrettoPoint::identity()),
+                _ => self.update.get(j - 1).copied(),
+            };
+            let continued = match (
+                old.coefficients.get(j),
+                update,
+                self.commitments.coefficients.get(j),
+            ) {
+                (Some(before), Some(update), Some(after)) => before + update == *after,
+                _ => false,
+            };
+            if !continued {
+                return Err(VerifyError::RefreshMismatch { coefficient: j });
+            }
+        }
+        Ok(())
+    }
+
+    /// [`Self::verify`] of a record signed for `session` by `refresher`, the participant that ran
+    /// the refresh.
+    ///
+    /// Returns `CryptoError::SecurityViolation` for another session or signer, or if the refresh
+    /// does not continue `old`.
+    pub fn verify_signed(
+        record: &Signed<Self>,
+        roster: &Roster,
+        session: &str,
+        refresher: usize,
+        old: &VssCommitments,
+    ) -> CryptoResult<()> {
+        record
+            .verify_from(roster, session, refresher)?
+            .verify(old)?;
+        Ok(())
+    }
+}
+
+/// Link of one refreshed slice commitment to its previous one, in a [`RefreshLinks`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ShareLink {
+    pub index: usize,
+    /// Commitment `C_i` of the slice before the refresh.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub old_commitment: RistrettoPoint,
+    /// Commitment `C'_i` of the slice after the refresh.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub new_commitment: RistrettoPoint,
+    /// Proof of knowledge of `w` with `C'_i - C_i - D(i) = w·H`.
+    pub proof: DleqProof,
+}
+
+/// Published continuity proofs of a refresh of slices without coefficient commitments
+/// ([`crate::sharing::update_shares_linked`]).
+///
+/// Such a refresh adds `δ(i)` (with `δ(0) = 0`) to every slice and draws a fresh blinding random
+/// `r'_i`, so `C'_i - C_i` alone says nothing about the slice values. The refresher publishes
+/// `D_j = δ_j·G + ε_j·H` for a random `ε(x)` and, per slice, a proof that
+/// `L_i = C'_i - C_i - Σ_j i^j·D_j` is a multiple of `H` alone (witness `r'_i - r_i - ε(i)`).
+/// With `L_i` free of `G`, the new slice value is `s_i + δ(i)`, so any `threshold` refreshed
+/// slices still interpolate the old secret. Holders check their own old and new slices with
+/// [`RefreshLinks::verify_share`] instead of trusting the refresher.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefreshLinks {
+    /// `D_1, ..., D_{t-1}`; `D_0` is the identity and not stored.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_vec_helpers")]
+    pub update: Vec<RistrettoPoint>,
+    /// One link per refreshed slice.
+    pub links: Vec<ShareLink>,
+}
+
+impl RefreshLinks {
+    /// Digest of the update commitments every link proof is bound to.
+    fn update_digest(update: &[RistrettoPoint]) -> [u8; 32] {
+        let mut hasher = Blake3Adapter::new_derive_key(REFRESH_UPDATE_CONTEXT);
+        digest::Update::update(&mut hasher, &(update.len() as u64).to_le_bytes());
+        for point in update {
+            digest::Update::update(&mut hasher, point.compress().as_bytes());
+        }
+        let mut out = [0u8; 32];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+
+    /// `C'_i - C_i - Σ_j i^j·D_j`.
+    fn link_point(
+        update: &[RistrettoPoint],
+        index: usize,
+        old_commitment: &RistrettoPoint,
+        new_commitment: &RistrettoPoint,
+    ) -> RistrettoPoint {
+        let x = Scalar::from(index as u64);
+        let mut power = Scalar::ONE;
+        let powers: Vec<Scalar> = update
+            .iter()
+            .map(|_| {
+                power *= x;
+                power
+            })
+            .collect();
+        count(Op::PointMul, update.len());
+        new_commitment - old_commitment - RistrettoPoint::vartime_multiscalar_mul(&powers, update)
+    }
+
+    /// The Schnorr statement of a link: a DLEQ over the pair `(H, L_i)` twice.
+    pub(crate) fn statement<'a>(
+        digest: &'a [u8; 32],
+        update: &[RistrettoPoint],
+        index: usize,
+        old_commitment: &RistrettoPoint,
+        new_commitment: &RistrettoPoint,
+    ) -> DleqStatement<'a> {
+        let link = Self::link_point(update, index, old_commitment, new_commitment);
+        DleqStatement {
+            context: SHARE_LINK_CONTEXT,
+            binding: digest,
+            index,
+            g1: *ANOTHER_POINT,
+            h1: link,
+            g2: *ANOTHER_POINT,
+            h2: link,
+        }
+    }
+
+    /// Link of the slice `old`, refreshed into `new`, under the update commitments `update`.
+    ///
+    /// `witness` is `r'_i - r_i - ε(i)`. Returns `CryptoError::InsufficientEntropy` if the proof
+    /// nonce cannot be drawn.
+    #[cfg(feature = "dealer")]
+    pub(crate) fn link(
+        update: &[RistrettoPoint],
+        old: &ShareData,
+        new: &ShareData,
+        witness: &Scalar,
+    ) -> CryptoResult<ShareLink> {
+        let digest = Self::update_digest(update);
+        let proof = Self::statement(&digest, update, old.index, &old.commitment, &new.commitment)
+            .prove(witness)?;
+        Ok(ShareLink {
+            index: old.index,
+            old_commitment: old.commitment,
+            new_commitment: new.commitment,
+            proof,
+        })
+    }
+
+    fn verify_link(&self, digest: &[u8; 32], link: &ShareLink) -> Result<(), VerifyError> {
+        let statement = Self::statement(
+            digest,
+            &self.update,
+            link.index,
+            &link.old_commitment,
+            &link.new_commitment,
+        );
+        count(Op::PointMul, 4);
+        if statement.verify(&link.proof) {
+            Ok(())
+        } else {
+            Err(VerifyError::ChallengeMismatch { index: link.index })
+        }
+    }
+
+    /// Check every link, e.g. by an auditor holding only the published record.
+    ///
+    /// Returns `VerifyError::WrongContext` for index 0 or a repeated index and
+    /// `VerifyError::ChallengeMismatch` for the first link whose proof does not verify.
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let digest = Self::update_digest(&self.update);
+        let mut seen = std::collections::HashSet::new();
+        for link in &self.links {
+            if link.index == 0 || !seen.insert(link.index) {
+                return Err(VerifyError::WrongContext {
+                    index: link.index,
+                    details: "link index is zero or repeated".to_string(),
+                });
+            }
+            self.verify_link(&digest, link)?;
+        }
+        Ok(())
+    }
+
+    /// Holder-side check that `new` continues `old`: the record links exactly these two
+    /// commitments and the link verifies.
+    ///
+    /// Returns `VerifyError::WrongContext` if the record has no link from `old` to `new` and
+    /// `VerifyError::ChallengeMismatch` if the link does not verify. The new slice itself is
+    /// checked as usual, e.g. with [`verify_share_validity`].
+    pub fn verify_share(&self, old: &ShareData, new: &ShareData) -> Result<(), VerifyError> {
+        let link = self
+            .links
+            .iter()
+            .find(|l| l.index == old.index)
+            .filter(|l| {
+                new.index == old.index
+                    && l.old_commitment == old.commitment
+                    && l.new_commitment == new.commitment
+            })
+            .ok_or_else(|| VerifyError::WrongContext {
+                index: old.index,
+                details: "refresh record does not link these slices".to_string(),
+            })?;
+        self.verify_link(&Self::update_digest(&self.update), link)
+    }
+}
+
+/// Verify all slices against the dealer's published coefficient commitments.
+///
 /// # Parameters
 ///
 /// - `shares`: collection of slices.
+/// - `commitments`: the dealing's [`VssCommitments`].
+///
+/// # Return value
+///
+/// Returns `Ok(())` if every slice opens its commitment, lies on the committed polynomial and
+/// carries a valid proof, otherwise the failure of the first invalid slice
+/// ([`VssCommitments::verify_share`]).
+pub fn verify_share_validity_with_commitments(
+    shares: &[ShareData],
+    commitments: &VssCommitments,
+) -> Result<(), VerifyError> {
+    shares
+        .iter()
+        .try_for_each(|share| commitments.verify_share(share))
+}
+
+/// Verify the validity of all multi-secret slices (one combined proof per slice).
+///
+/// # Parameters
+///
+/// - `shares`: collection of multi-secret slices.
 ///
 /// # Return value
 ///
-/// Returns `true` if all slices are valid; otherwise returns `false`.
-pub fn verify_share_validity(shares: &[ShareData]) -> bool {
+/// Returns `true` if every slice carries matching per-secret vectors and a valid combined proof;
+/// otherwise returns `false`.
+pub fn verify_multi_share_validity(shares: &[MultiShareData]) -> bool {
     for share in shares {
-        if !proof::verify_proof(&share.proof, share.commitment, share.index) {
+        let count = share.commitments.len();
+        if count == 0 || share.shares.len() != count || share.randoms.len() != count {
+            return false;
+        }
+        if proof::verify_proof(&share.proof, share.combined_commitment(), share.index).is_err() {
             return false;
         }
     }
     true
 }
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares_with_commitments;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    #[test]
+    fn test_vss_accepts_honest_dealing() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+
+        assert_eq!(commitments.threshold(), 3);
+        assert!(verify_share_validity_with_commitments(&shares, &commitments).is_ok());
+    }
+
+    #[test]
+    fn test_vss_detects_inconsistent_dealer() {
+        let (mut shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+
+        // A slice of a different polynomial, correctly committed and proven on its own.
+        let forged = &mut shares[2];
+        forged.share += Scalar::ONE;
+        forged.commitment =
+            RISTRETTO_BASEPOINT_POINT * forged.share + (*ANOTHER_POINT) * forged.random;
+        forged.proof =
+            proof::generate_proof(forged.share, forged.random, forged.index, forged.commitment)
+                .unwrap();
+
+        assert!(verify_share_validity(&shares).is_ok());
+        assert_eq!(
+            verify_share_validity_with_commitments(&shares, &commitments),
+            Err(VerifyError::CommitmentMismatch { index: 3 })
+        );
+    }
+
+    #[test]
+    fn test_verify_error_diagnostics() {
+        use crate::error::FailureClass;
+
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 2, 3).unwrap();
+        let (refreshed, new_commitments) =
+            crate::sharing::update_shares_with_commitments(&shares, &commitments).unwrap();
+
+        let mut corrupted = refreshed[0].clone();
+        corrupted.random += Scalar::ONE;
+        let error = new_commitments.verify_share(&corrupted).unwrap_err();
+        assert_eq!(error, VerifyError::OpeningMismatch { index: 1 });
+        assert_eq!(error.class(), FailureClass::Malice);
+
+        let mut wrong_index = refreshed[0].clone();
+        wrong_index.index = 2;
+        wrong_index.commitment = refreshed[1].commitment;
+        wrong_index.share = refreshed[1].share;
+        wrong_index.random = refreshed[1].random;
+        assert_eq!(
+            proof::verify_proof(&wrong_index.proof, wrong_index.commitment, 2),
+            Err(VerifyError::ChallengeMismatch { index: 2 })
+        );
+
+        let history = [(0, &commitments), (1, &new_commitments)];
+        assert!(verify_share_epochs(&refreshed[2], &history).is_ok());
+        let stale = verify_share_epochs(&shares[2], &history).unwrap_err();
+        assert_eq!(
+            stale,
+            VerifyError::StaleEpoch {
+                index: 3,
+                epoch: 0,
+                current_epoch: 1
+            }
+        );
+        assert_eq!(stale.class(), FailureClass::VersionSkew);
+        assert_eq!(stale.code(), "ZKT-V007");
+
+        let json = serde_json::to_value(&refreshed[0]).unwrap();
+        let parsed = parse_share(json.to_string().as_bytes()).unwrap();
+        assert!(new_commitments.verify_share(&parsed).is_ok());
+        let mut unreduced = json.clone();
+        unreduced["proof"]["challenge"] = serde_json::Value::String("ff".repeat(32));
+        let error = parse_share(unreduced.to_string().as_bytes()).unwrap_err();
+        assert!(matches!(
+            &error,
+            VerifyError::NonCanonicalEncoding { field, .. } if field == "proof.challenge"
+        ));
+        assert_eq!(error.class(), FailureClass::Corruption);
+        assert!(matches!(
+            CryptoError::from(error),
+            CryptoError::Serialization { .. }
+        ));
+    }
+
+    #[test]
+    fn test_canonical_bytes_round_trip() {
+        let (_, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+        let bytes = commitments.to_canonical_bytes();
+
+        assert_eq!(bytes.len(), 5 + 3 * 32);
+        assert_eq!(
+            VssCommitments::from_canonical_bytes(&bytes).unwrap(),
+            commitments
+        );
+        assert!(VssCommitments::from_canonical_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut identity = bytes.clone();
+        identity[5..37].fill(0);
+        assert!(VssCommitments::from_canonical_bytes(&identity).is_err());
+    }
+
+    #[test]
+    fn test_diff_after_refresh() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+        let (refreshed, new_commitments) =
+            crate::sharing::update_shares_with_commitments(&shares, &commitments).unwrap();
+        assert!(verify_share_validity_with_commitments(&refreshed, &new_commitments).is_ok());
+
+        let report = diff(&commitments, &new_commitments);
+        assert!(report.is_permitted_refresh());
+        assert_eq!(report.changed_coefficients, vec![1, 2]);
+        assert_eq!(report.changed_shares(&[1, 2, 3]), vec![1, 2, 3]);
+
+        let (_, redealt) = generate_key_shares_with_commitments(Scalar::from(5u64), 4, 5).unwrap();
+        let report = diff(&commitments, &redealt);
+        assert!(report.constant_term_changed());
+        assert!(!report.is_permitted_refresh());
+        assert!(diff(&commitments, &commitments)
+            .changed_coefficients
+            .is_empty());
+    }
+
+    #[test]
+    fn test_refresh_record_proves_continuity() {
+        let (shares, commitments) =
+            generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+        let (refreshed, record) = crate::sharing::refresh_shares(&shares, &commitments).unwrap();
+        assert!(record.verify(&commitments).is_ok());
+        assert!(verify_share_validity_with_commitments(&refreshed, &record.commitments).is_ok());
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RefreshRecord>(&json).unwrap(),
+            record
+        );
+
+        let mut tampered = record.clone();
+        tampered.update[1] += RISTRETTO_BASEPOINT_POINT;
+        assert_eq!(
+            tampered.verify(&commitments),
+            Err(VerifyError::RefreshMismatch { coefficient: 2 })
+        );
+        // Moving the secret's commitment breaks continuity at C_0 even with a matching root.
+        let mut moved = record.clone();
+        moved.commitments.coefficients[0] += RISTRETTO_BASEPOINT_POINT;
+        assert_eq!(
+            moved.verify(&commitments),
+            Err(VerifyError::RefreshMismatch { coefficient: 0 })
+        );
+        let mut truncated = record.clone();
+        truncated.update.pop();
+        truncated.commitments.coefficients.pop();
+        assert_eq!(
+            truncated.verify(&commitments),
+            Err(VerifyError::RefreshMismatch { coefficient: 2 })
+        );
+
+        let refresher = crate::identity::Participant::from_seed(1, &[1; 32]).unwrap();
+        let roster = Roster::new(&[refresher.identity()]).unwrap();
+        let signed = refresher.sign("refresh-1", record.clone()).unwrap();
+        assert!(
+            RefreshRecord::verify_signed(&signed, &roster, "refresh-1", 1, &commitments).is_ok()
+        );
+        assert!(matches!(
+            RefreshRecord::verify_signed(&signed, &roster, "refresh-2", 1, &commitments),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let signed_tampered = refresher.sign("refresh-1", tampered).unwrap();
+        assert!(matches!(
+            RefreshRecord::verify_signed(&signed_tampered, &roster, "refresh-1", 1, &commitments),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+
+        let (_, other) = generate_key_shares_with_commitments(Scalar::from(5u64), 3, 5).unwrap();
+        assert!(matches!(
+            record.verify(&other),
+            Err(VerifyError::WrongContext { .. })
+        ));
+    }
+
+    #[test]
+    fn test_refresh_links_bind_new_slices_to_old() {
+        let secret = Scalar::from(5u64);
+        let shares = crate::sharing::generate_key_shares(secret, 3, 5).unwrap();
+        let (refreshed, links) = crate::sharing::update_shares_linked(&shares, 3).unwrap();
+        links.verify().unwrap();
+        for (old, new) in shares.iter().zip(&refreshed) {
+            links.verify_share(old, new).unwrap();
+        }
+        verify_share_validity(&refreshed).unwrap();
+        assert_eq!(
+            crate::lagrange_fft::recover_secret_fft(&refreshed[2..]).unwrap(),
+            secret
+        );
+        let json = serde_json::to_string(&links).unwrap();
+        assert_eq!(serde_json::from_str::<RefreshLinks>(&json).unwrap(), links);
+
+        // A refresher moving a slice value cannot link it, whatever witness it proves with.
+        let value = refreshed[0].share + Scalar::ONE;
+        let random = refreshed[0].random;
+        let commitment = crate::utils::pedersen_commit(&value, &random);
+        let forged = ShareData {
+            share: value,
+            commitment,
+            proof: proof::generate_proof(value, random, 1, commitment).unwrap(),
+            ..refreshed[0].clone()
+        };
+        assert!(matches!(
+            links.verify_share(&shares[0], &forged),
+            Err(VerifyError::WrongContext { index: 1, .. })
+        ));
+        let witness = random - shares[0].random;
+        let mut tampered = links.clone();
+        tampered.links[0] =
+            RefreshLinks::link(&links.update, &shares[0], &forged, &witness).unwrap();
+        assert_eq!(
+            tampered.verify_share(&shares[0], &forged),
+            Err(VerifyError::ChallengeMismatch { index: 1 })
+        );
+        assert!(tampered.verify().is_err());
+
+        // Links are bound to the published update commitments.
+        let mut moved = links.clone();
+        moved.update[0] += RISTRETTO_BASEPOINT_POINT;
+        assert!(moved.verify_share(&shares[1], &refreshed[1]).is_err());
+        assert!(links.verify_share(&shares[1], &refreshed[2]).is_err());
+    }
+
+    #[test]
+    fn test_batch_verification_matches_single() {
+        let (shares, _) =
+            generate_key_shares_with_commitments(Scalar::from(12u64), 4, 150).unwrap();
+        assert!(verify_share_validity_batch(&shares).is_ok());
+        assert!(verify_share_validity_batch(&[]).is_ok());
+
+        let mut swapped = shares.clone();
+        swapped[97].proof = shares[96].proof.clone();
+        assert_eq!(
+            verify_share_validity_batch(&swapped),
+            Err(VerifyError::ChallengeMismatch { index: 98 })
+        );
+        assert_eq!(
+            verify_share_validity_batch(&swapped),
+            verify_share_validity(&swapped)
+        );
+
+        // Altered responses keep the challenge valid but fail the combined equation.
+        let mut altered = shares.clone();
+        let mut bytes = shares[120].proof.to_bytes();
+        bytes[32..64].copy_from_slice(Scalar::ONE.as_bytes());
+        altered[120].proof = proof::Proof::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            verify_share_validity_batch(&altered),
+            Err(VerifyError::InvalidResponse { index: 121 })
+        );
+    }
+
+    fn deals_in<G: CurveGroup + PartialEq + std::fmt::Debug>() {
+        use crate::sharing::{generate_group_shares, recover_group_secret};
+        let secret = G::scalar_from_u64(42);
+        let (shares, commitments) = generate_group_shares::<G>(secret, 3, 5).unwrap();
+        for share in &shares {
+            assert_eq!(commitments.verify_share(share), Ok(()));
+        }
+        assert_eq!(recover_group_secret(&shares[1..4]).unwrap(), secret);
+
+        let json = serde_json::to_string(&shares[2]).unwrap();
+        let parsed: ShareData<G> = serde_json::from_str(&json).unwrap();
+        assert_eq!(commitments.verify_share(&parsed), Ok(()));
+        let json = serde_json::to_string(&commitments).unwrap();
+        assert_eq!(
+            serde_json::from_str::<VssCommitments<G>>(&json).unwrap(),
+            commitments
+        );
+
+        let mut moved = shares[0].clone();
+        moved.index = 2;
+        assert_eq!(
+            commitments.verify_share(&moved),
+            Err(VerifyError::CommitmentMismatch { index: 2 })
+        );
+        let mut forged = shares[0].clone();
+        forged.share = forged.share + G::scalar_from_u64(1);
+        assert_eq!(
+            commitments.verify_share(&forged),
+            Err(VerifyError::OpeningMismatch { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_group_dealing() {
+        use crate::group::{Edwards25519, Ristretto255};
+        use crate::serialization::SerRistrettoPoint;
+        deals_in::<Ristretto255>();
+        deals_in::<Edwards25519>();
+        #[cfg(feature = "secp256k1")]
+        deals_in::<crate::group::secp256k1::Secp256k1>();
+
+        // Over ristretto255 the generic and ristretto255-only paths accept each other's slices,
+        // and serialize them identically.
+        let (shares, commitments) =
+            crate::sharing::generate_group_shares::<Ristretto255>(Scalar::from(7u64), 2, 3)
+                .unwrap();
+        assert!(verify_share_validity_with_commitments(&shares, &commitments).is_ok());
+        assert!(verify_share_validity_batch(&shares).is_ok());
+        assert_eq!(
+            crate::sharing::recover_secret(&shares).unwrap(),
+            Scalar::from(7u64)
+        );
+        let json = serde_json::to_string(&shares[0]).unwrap();
+        assert_eq!(
+            parse_share(json.as_bytes()).unwrap().to_bytes(),
+            shares[0].to_bytes()
+        );
+        assert_eq!(
+            VssCommitments::from_canonical_bytes(&commitments.to_canonical_bytes()).unwrap(),
+            commitments
+        );
+
+        let (shares, _) = generate_key_shares_with_commitments(Scalar::from(8u64), 2, 3).unwrap();
+        for share in &shares {
+            assert_eq!(
+                proof::verify_group_proof(&share.proof, share.commitment, share.index),
+                Ok(())
+            );
+        }
+        let value = serde_json::to_value(&shares[0]).unwrap();
+        assert_eq!(
+            value["commitment"],
+            serde_json::to_value(SerRistrettoPoint(shares[0].commitment)).unwrap()
+        );
+        assert_eq!(
+            value["proof"]["challenge"],
+            hex::encode(&shares[0].proof.to_bytes()[96..])
+        );
+        assert_eq!(
+            crate::sharing::recover_group_secret(&shares).unwrap(),
+            Scalar::from(8u64)
+        );
+    }
+}
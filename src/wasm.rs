@@ -0,0 +1,87 @@
+//! **wasm module**
+//!
+//! `wasm-bindgen` bindings for browser wallets that hold and verify slices client-side (feature
+//! `wasm`, target `wasm32-unknown-unknown`):
+//!
+//! ```text
+//! wasm-pack build --target web -- --features wasm
+//! ```
+//!
+//! Values cross the boundary as strings in the [`crate::format`] encodings: scalars as lowercase
+//! hex of their canonical bytes, slices as `ShareData` JSON. Errors are thrown as `Error` objects
+//! whose message starts with the stable code (`ZKT-E…` or `ZKT-V…`).
+//!
+//! Randomness comes from `crypto.getRandomValues` through `getrandom`'s `js` backend. Strings
+//! handed to JavaScript are outside Rust's control and are not zeroized; keep secrets in JS memory
+//! no longer than needed.
+
+use crate::error::{CryptoError, CryptoResult};
+use crate::recovered::{RecoveredSecret, SecretEncoding};
+use crate::serialization::scalar_from_slice;
+use crate::sharing::{self, ShareData};
+use crate::{lagrange_fft, proof, vss};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+fn js_error(code: &str, error: impl std::fmt::Display) -> JsError {
+    JsError::new(&format!("{} {}", code, error))
+}
+
+fn crypto_error(error: CryptoError) -> JsError {
+    js_error(error.code(), error)
+}
+
+fn json_error(what: &str, error: serde_json::Error) -> CryptoError {
+    CryptoError::Serialization {
+        details: format!("{}: {}", what, error),
+    }
+}
+
+fn deal(secret_hex: &str, threshold: usize, num_shares: usize) -> CryptoResult<String> {
+    let bytes = hex::decode(secret_hex).map_err(|e| CryptoError::Serialization {
+        details: format!("secret is not hex: {}", e),
+    })?;
+    let bytes = Zeroizing::new(bytes);
+    let secret = RecoveredSecret::new(scalar_from_slice(&bytes, "secret")?);
+    let shares = sharing::generate_key_shares(*secret.as_scalar(), threshold, num_shares)?;
+    serde_json::to_string(&shares).map_err(|e| json_error("shares", e))
+}
+
+fn recover(shares_json: &str) -> CryptoResult<String> {
+    let shares: Vec<ShareData> =
+        serde_json::from_str(shares_json).map_err(|e| json_error("shares", e))?;
+    let secret = lagrange_fft::recover_secret_fft(&shares).map_err(|e| {
+        CryptoError::CryptographicOperation {
+            operation: format!("secret_recovery: {}", e),
+        }
+    })?;
+    let encoded = RecoveredSecret::new(secret).encode(SecretEncoding::Hex);
+    Ok(String::from_utf8(encoded.to_vec()).expect("hex is ASCII"))
+}
+
+/// Deal `secret_hex` into `num_shares` slices, any `threshold` of which recover it.
+///
+/// Returns the slices as a JSON array of `ShareData`.
+#[wasm_bindgen(js_name = generateKeyShares)]
+pub fn generate_key_shares(
+    secret_hex: &str,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<String, JsError> {
+    deal(secret_hex, threshold, num_shares).map_err(crypto_error)
+}
+
+/// Check the encoding and the proof of a `ShareData` JSON slice against its own commitment and
+/// index; throws the `VerifyError` otherwise.
+#[wasm_bindgen(js_name = verifyProof)]
+pub fn verify_proof(share_json: &str) -> Result<(), JsError> {
+    let share = vss::parse_share(share_json.as_bytes()).map_err(|e| js_error(e.code(), &e))?;
+    proof::verify_proof(&share.proof, share.commitment, share.index)
+        .map_err(|e| js_error(e.code(), &e))
+}
+
+/// Recover the secret from a JSON array of `ShareData` slices, as lowercase hex.
+#[wasm_bindgen(js_name = recoverSecretFft)]
+pub fn recover_secret_fft(shares_json: &str) -> Result<String, JsError> {
+    recover(shares_json).map_err(crypto_error)
+}
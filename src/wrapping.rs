@@ -0,0 +1,338 @@
+//! **wrapping module**
+//!
+//! Encryption of slices to a participant's X25519 key, so a dealer can publish every wrapped slice
+//! on an untrusted channel.
+//!
+//! [`ShareData::seal`] is ECIES over X25519 with a BLAKE3 KDF:
+//!
+//! - the dealer draws an ephemeral X25519 key and computes the shared secret with the recipient's
+//!   [`WrappingPublicKey`]; an all-zero shared secret (low-order recipient key) is rejected;
+//! - an encryption key and a MAC key are derived with BLAKE3 `derive_key` over the shared secret,
+//!   both public keys and the slice index;
+//! - the slice value and blinding randomness are XORed with the keyed BLAKE3 XOF under the
+//!   encryption key, and a keyed BLAKE3 tag under the MAC key covers the version, index,
+//!   commitment and ciphertext (the public keys are bound through the KDF).
+//!
+//! The slice commitment and its proof are public anyway and travel in the clear, so anyone can
+//! check which slice an envelope carries. [`ShareData::unseal`] re-checks both after decryption.
+
+use crate::algorithms::{AlgorithmId, AlgorithmSuite};
+use crate::error::{CryptoError, CryptoResult};
+use crate::hash_adapter::Blake3Adapter;
+use crate::proof::{self, Proof};
+use crate::sharing::ShareData;
+use crate::utils::ANOTHER_POINT;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// Version of the [`WrappedShare`] envelope.
+pub const WRAPPED_SHARE_VERSION: u8 = 1;
+
+/// Algorithms of a version 1 [`WrappedShare`].
+pub const WRAPPED_SHARE_ALGORITHMS: AlgorithmSuite = AlgorithmSuite {
+    curve: AlgorithmId::Ristretto255,
+    hash: AlgorithmId::Blake3,
+    kem: Some(AlgorithmId::X25519),
+    aead: Some(AlgorithmId::Blake3XofEtm),
+    kdf: Some(AlgorithmId::Blake3DeriveKey),
+    proof: Some(AlgorithmId::SchnorrPedersen),
+};
+
+const KDF_CONTEXT: &str = "zk-thresh-pro 2024 share wrapping kdf v1";
+const PLAINTEXT_LENGTH: usize = 64;
+const TAG_LENGTH: usize = 32;
+
+/// A participant's X25519 public key.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrappingPublicKey(#[serde(with = "hex_array")] [u8; 32]);
+
+impl WrappingPublicKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// A participant's X25519 private key.
+///
+/// `Debug` output is redacted; the key is zeroized on drop.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct WrappingSecretKey([u8; 32]);
+
+impl WrappingSecretKey {
+    /// Wrap 32 bytes as an X25519 private key; clamping happens on use.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Draw a fresh key from the OS generator.
+    #[cfg(feature = "dealer")]
+    pub fn generate() -> CryptoResult<Self> {
+        let mut bytes = [0u8; 32];
+        crate::utils::fill_random(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    pub fn public_key(&self) -> WrappingPublicKey {
+        WrappingPublicKey(MontgomeryPoint::mul_base_clamped(self.0).to_bytes())
+    }
+}
+
+impl fmt::Debug for WrappingSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WrappingSecretKey(<redacted>)")
+    }
+}
+
+fn default_algorithms() -> AlgorithmSuite {
+    WRAPPED_SHARE_ALGORITHMS
+}
+
+/// A slice encrypted to one participant's [`WrappingPublicKey`].
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct WrappedShare {
+    /// Envelope version, [`WRAPPED_SHARE_VERSION`].
+    pub version: u8,
+    /// Algorithms the envelope was produced with; [`WRAPPED_SHARE_ALGORITHMS`] when absent.
+    #[serde(default = "default_algorithms")]
+    pub algorithms: AlgorithmSuite,
+    pub index: usize,
+    /// Recipient the slice is wrapped to.
+    pub recipient: WrappingPublicKey,
+    /// Public commitment of the slice.
+    #[serde(with = "crate::serialization::serialize_ristretto_point_helpers")]
+    pub commitment: RistrettoPoint,
+    /// Public proof of knowledge of the commitment opening.
+    pub proof: Proof,
+    /// Dealer's ephemeral X25519 public key.
+    pub ephemeral: WrappingPublicKey,
+    /// Encrypted `share || random`.
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub ciphertext: Vec<u8>,
+    #[serde(with = "crate::serialization::serialize_hex_bytes_helpers")]
+    pub tag: Vec<u8>,
+}
+
+mod hex_array {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if !crate::serialization::is_lower_hex(&s) {
+            return Err(serde::de::Error::custom("expected lowercase hex"));
+        }
+        let mut out = [0u8; 32];
+        hex::decode_to_slice(&s, &mut out).map_err(serde::de::Error::custom)?;
+        Ok(out)
+    }
+}
+
+/// Encryption and MAC keys of one envelope.
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct EnvelopeKeys {
+    encryption: [u8; 32],
+    mac: [u8; 32],
+}
+
+impl EnvelopeKeys {
+    fn derive(
+        shared: &MontgomeryPoint,
+        ephemeral: &WrappingPublicKey,
+        recipient: &WrappingPublicKey,
+        index: usize,
+    ) -> CryptoResult<Self> {
+        if shared.to_bytes() == [0u8; 32] {
+            return Err(CryptoError::SecurityViolation {
+                details: "X25519 shared secret is zero (low-order public key)".to_string(),
+            });
+        }
+        let mut hasher = Blake3Adapter::new_derive_key(KDF_CONTEXT);
+        digest::Update::update(&mut hasher, shared.as_bytes());
+        digest::Update::update(&mut hasher, &ephemeral.0);
+        digest::Update::update(&mut hasher, &recipient.0);
+        digest::Update::update(&mut hasher, &(index as u64).to_le_bytes());
+        let mut okm = Zeroizing::new([0u8; 64]);
+        hasher.finalize_extended(okm.as_mut());
+        let mut keys = Self {
+            encryption: [0u8; 32],
+            mac: [0u8; 32],
+        };
+        keys.encryption.copy_from_slice(&okm[..32]);
+        keys.mac.copy_from_slice(&okm[32..]);
+        Ok(keys)
+    }
+
+    fn apply_keystream(&self, data: &mut [u8]) {
+        let mut keystream = Zeroizing::new(vec![0u8; data.len()]);
+        Blake3Adapter::new_keyed(&self.encryption).finalize_extended(&mut keystream);
+        for (byte, k) in data.iter_mut().zip(keystream.iter()) {
+            *byte ^= k;
+        }
+    }
+
+    fn tag(&self, envelope: &WrappedShare) -> [u8; TAG_LENGTH] {
+        let mut hasher = Blake3Adapter::new_keyed(&self.mac);
+        digest::Update::update(&mut hasher, &[envelope.version]);
+        digest::Update::update(&mut hasher, &(envelope.index as u64).to_le_bytes());
+        digest::Update::update(&mut hasher, envelope.commitment.compress().as_bytes());
+        digest::Update::update(&mut hasher, &envelope.ciphertext);
+        let mut out = [0u8; TAG_LENGTH];
+        hasher.finalize_extended(&mut out);
+        out
+    }
+}
+
+impl ShareData {
+    /// Encrypt the slice value and blinding randomness to `recipient`.
+    #[cfg(feature = "dealer")]
+    pub fn seal(&self, recipient: &WrappingPublicKey) -> CryptoResult<WrappedShare> {
+        let ephemeral_secret = WrappingSecretKey::generate()?;
+        let ephemeral = ephemeral_secret.public_key();
+        let shared = MontgomeryPoint(recipient.0).mul_clamped(ephemeral_secret.0);
+        let keys = EnvelopeKeys::derive(&shared, &ephemeral, recipient, self.index)?;
+
+        let mut ciphertext = Zeroizing::new(Vec::with_capacity(PLAINTEXT_LENGTH));
+        ciphertext.extend_from_slice(self.share.as_bytes());
+        ciphertext.extend_from_slice(self.random.as_bytes());
+        keys.apply_keystream(&mut ciphertext);
+        let mut envelope = WrappedShare {
+            version: WRAPPED_SHARE_VERSION,
+            algorithms: WRAPPED_SHARE_ALGORITHMS,
+            index: self.index,
+            recipient: *recipient,
+            commitment: self.commitment,
+            proof: self.proof.clone(),
+            ephemeral,
+            ciphertext: ciphertext.to_vec(),
+            tag: Vec::new(),
+        };
+        envelope.tag = keys.tag(&envelope).to_vec();
+        Ok(envelope)
+    }
+
+    /// Decrypt a wrapped slice with the recipient's private key.
+    ///
+    /// Returns `CryptoError::Serialization` for a malformed envelope and
+    /// `CryptoError::SecurityViolation` if it does not authenticate under `key` or the decrypted
+    /// slice does not open the envelope's commitment.
+    pub fn unseal(wrapped: &WrappedShare, key: &WrappingSecretKey) -> CryptoResult<ShareData> {
+        if wrapped.version != WRAPPED_SHARE_VERSION {
+            return Err(CryptoError::Serialization {
+                details: format!("unsupported wrapped share version {}", wrapped.version),
+            });
+        }
+        wrapped
+            .algorithms
+            .require(&WRAPPED_SHARE_ALGORITHMS, "wrapped share")?;
+        if wrapped.ciphertext.len() != PLAINTEXT_LENGTH || wrapped.tag.len() != TAG_LENGTH {
+            return Err(CryptoError::Serialization {
+                details: "wrapped share ciphertext or tag has the wrong length".to_string(),
+            });
+        }
+        let recipient = key.public_key();
+        let shared = MontgomeryPoint(wrapped.ephemeral.0).mul_clamped(key.0);
+        let keys = EnvelopeKeys::derive(&shared, &wrapped.ephemeral, &recipient, wrapped.index)?;
+        let mut expected = [0u8; TAG_LENGTH];
+        expected.copy_from_slice(&wrapped.tag);
+        // blake3::Hash compares in constant time.
+        if blake3::Hash::from(keys.tag(wrapped)) != blake3::Hash::from(expected) {
+            return Err(CryptoError::SecurityViolation {
+                details: format!("wrapped share {} failed authentication", wrapped.index),
+            });
+        }
+
+        let mut plaintext = Zeroizing::new(wrapped.ciphertext.clone());
+        keys.apply_keystream(&mut plaintext);
+        let decode = |bytes: &[u8]| {
+            let mut buf = Zeroizing::new([0u8; 32]);
+            buf.copy_from_slice(bytes);
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(*buf)).ok_or_else(|| {
+                CryptoError::Serialization {
+                    details: "wrapped share holds a non-canonical scalar".to_string(),
+                }
+            })
+        };
+        let share = ShareData {
+            index: wrapped.index,
+            share: decode(&plaintext[..32])?,
+            commitment: wrapped.commitment,
+            random: decode(&plaintext[32..])?,
+            proof: wrapped.proof.clone(),
+        };
+        if RISTRETTO_BASEPOINT_POINT * share.share + (*ANOTHER_POINT) * share.random
+            != share.commitment
+            || proof::verify_proof(&share.proof, share.commitment, share.index).is_err()
+        {
+            return Err(CryptoError::SecurityViolation {
+                details: format!(
+                    "wrapped share {} does not open its commitment",
+                    wrapped.index
+                ),
+            });
+        }
+        Ok(share)
+    }
+}
+
+#[cfg(all(test, feature = "dealer"))]
+mod tests {
+    use super::*;
+    use crate::sharing::generate_key_shares;
+
+    #[test]
+    fn test_wrap_roundtrip_and_rejections() {
+        let shares = generate_key_shares(Scalar::from(21u64), 2, 3).unwrap();
+        let key = WrappingSecretKey::generate().unwrap();
+        let wrapped = shares[0].seal(&key.public_key()).unwrap();
+
+        let json = serde_json::to_string(&wrapped).unwrap();
+        let parsed: WrappedShare = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        let unsealed = ShareData::unseal(&parsed, &key).unwrap();
+        assert_eq!(unsealed.share, shares[0].share);
+        assert_eq!(unsealed.random, shares[0].random);
+
+        // Envelopes written before the algorithm suite was recorded parse as version 1 ones.
+        let mut legacy = serde_json::to_value(&wrapped).unwrap();
+        legacy.as_object_mut().unwrap().remove("algorithms");
+        let legacy: WrappedShare = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.algorithms, WRAPPED_SHARE_ALGORITHMS);
+        ShareData::unseal(&legacy, &key).unwrap();
+        let mut migrated = wrapped.clone();
+        migrated.algorithms.kem = None;
+        assert!(matches!(
+            ShareData::unseal(&migrated, &key),
+            Err(CryptoError::Serialization { .. })
+        ));
+
+        let other = WrappingSecretKey::generate().unwrap();
+        assert!(matches!(
+            ShareData::unseal(&wrapped, &other),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+        let mut tampered = wrapped.clone();
+        tampered.ciphertext[3] ^= 1;
+        assert!(ShareData::unseal(&tampered, &key).is_err());
+        let mut swapped = wrapped.clone();
+        swapped.commitment = shares[1].commitment;
+        assert!(ShareData::unseal(&swapped, &key).is_err());
+
+        // The identity is a low-order point.
+        let low_order = WrappingPublicKey::from_bytes([0u8; 32]);
+        assert!(matches!(
+            shares[0].seal(&low_order),
+            Err(CryptoError::SecurityViolation { .. })
+        ));
+    }
+}